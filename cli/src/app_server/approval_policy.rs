@@ -0,0 +1,1230 @@
+//! CodexPotter approval policy.
+//!
+//! Upstream `codex app-server` asks the client before running commands or writing files. Instead
+//! of approving everything, the bridge decides each request with allow/deny rules from
+//! `[approvals]` in `~/.codexpotter/config.toml` and `<workdir>/.codexpotter/config.toml` (see
+//! [`crate::config`]):
+//!
+//! - `command_prefix`: matches commands whose argv starts with the given words, e.g. `git push`.
+//!   Leading `NAME=value` assignments and `env` / `sudo` / `command` / `exec` / `nohup` wrappers
+//!   are ignored, and the program is compared by its basename (`/usr/bin/git` is `git`).
+//! - `command_regex`: matches the command line of a command (after the same normalization).
+//! - `path_glob`: matches changed files, relative to the thread working directory.
+//!
+//! For each command the first matching rule wins (repo rules before user rules); otherwise
+//! `default` applies. A script (`bash -lc`) is split into its `&&` / `||` / `;` / `|` / `&` /
+//! newline separated commands, which are decided one by one: any denied command declines the
+//! script, and it is allowed only when every command is. Commands running code the rules cannot
+//! see (nested shells, subshells, command substitutions) ask the user, and so do requests that do
+//! not say what they would run or change.
+//! Declined requests are reported back to the model with their reasons in a follow-up turn, and
+//! every decision is surfaced as `EventMsg::PotterApprovalDecision`.
+//!
+//! The `ask` action (or `--ask-approval`, which makes `ask` the default) hands the decision to the
+//! user: the question travels through an [`ApprovalAsker`] to the Potter app-server, which sends
+//! an `approval/request` to its client (the TUI shows a selection view). "Approve for session"
+//! answers are remembered for the rest of the round.
+//!
+//! Without rules the policy approves everything and the thread keeps `approvalPolicy: "never"`.
+//! With rules the thread requests `approvalPolicy: "untrusted"` so upstream actually asks.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterApprovalChoice;
+use codex_protocol::protocol::PotterApprovalKind;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use regex_automata::meta::Regex;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+
+use crate::app_server::potter::ApprovalRequestResponse;
+use crate::app_server::upstream_protocol::AskForApproval;
+use crate::app_server::upstream_protocol::ServerRequest;
+
+/// Maximum number of follow-up turns per round that report declined requests to the model.
+const MAX_DECLINE_FOLLOW_UPS: u32 = 3;
+
+/// Programs that run their arguments as another command; skipped when matching rules.
+const COMMAND_WRAPPERS: [&str; 5] = ["env", "sudo", "command", "exec", "nohup"];
+
+/// Wrapper options taking a value (`sudo -u root`, `env -u NAME`).
+const WRAPPER_OPTIONS_WITH_VALUE: [&str; 8] = ["-u", "-g", "-h", "-p", "-C", "-D", "-U", "-r"];
+
+/// Programs that run a script of their own, which approval rules cannot see into.
+const NESTED_SHELLS: [&str; 9] = [
+    "bash", "sh", "zsh", "dash", "ksh", "fish", "eval", "xargs", "su",
+];
+
+/// Decision of a rule or of the policy default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalAction {
+    Allow,
+    Deny,
+    /// Let the user decide (see [`ApprovalAsker`]).
+    Ask,
+}
+
+impl ApprovalAction {
+    /// Parse `allow` / `deny` / `ask`.
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            "ask" => Ok(Self::Ask),
+            other => anyhow::bail!(
+                "unknown approval action `{other}` (expected `allow`, `deny` or `ask`)"
+            ),
+        }
+    }
+}
+
+/// One `[[approvals.rules]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalRule {
+    action: ApprovalAction,
+    matcher: ApprovalMatcher,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum ApprovalMatcher {
+    CommandPrefix { prefix: String, words: Vec<String> },
+    CommandRegex { pattern: String, regex: Regex },
+    PathGlob { pattern: String, glob: GlobMatcher },
+}
+
+impl PartialEq for ApprovalMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.describe() == other.describe()
+    }
+}
+
+impl Eq for ApprovalMatcher {}
+
+impl ApprovalMatcher {
+    fn describe(&self) -> String {
+        match self {
+            Self::CommandPrefix { prefix, .. } => format!("command_prefix = {prefix:?}"),
+            Self::CommandRegex { pattern, .. } => format!("command_regex = {pattern:?}"),
+            Self::PathGlob { pattern, .. } => format!("path_glob = {pattern:?}"),
+        }
+    }
+}
+
+impl ApprovalRule {
+    /// Rule matching commands that start with the words of `prefix`.
+    pub fn command_prefix(
+        action: ApprovalAction,
+        prefix: &str,
+        reason: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut words = split_command_line(prefix);
+        let Some(program) = words.first_mut() else {
+            anyhow::bail!("`command_prefix` must not be empty");
+        };
+        *program = program_name(program);
+        Ok(Self {
+            action,
+            matcher: ApprovalMatcher::CommandPrefix {
+                prefix: prefix.to_string(),
+                words,
+            },
+            reason,
+        })
+    }
+
+    /// Rule matching command lines against a regular expression.
+    pub fn command_regex(
+        action: ApprovalAction,
+        pattern: &str,
+        reason: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let regex =
+            Regex::new(pattern).with_context(|| format!("invalid `command_regex` {pattern:?}"))?;
+        Ok(Self {
+            action,
+            matcher: ApprovalMatcher::CommandRegex {
+                pattern: pattern.to_string(),
+                regex,
+            },
+            reason,
+        })
+    }
+
+    /// Rule matching changed files against a glob (`*` stays within a path component).
+    pub fn path_glob(
+        action: ApprovalAction,
+        pattern: &str,
+        reason: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("invalid `path_glob` {pattern:?}"))?
+            .compile_matcher();
+        Ok(Self {
+            action,
+            matcher: ApprovalMatcher::PathGlob {
+                pattern: pattern.to_string(),
+                glob,
+            },
+            reason,
+        })
+    }
+
+    fn matches_command(&self, words: &[String], command_line: &str) -> bool {
+        match &self.matcher {
+            ApprovalMatcher::CommandPrefix { words: prefix, .. } => words.starts_with(prefix),
+            ApprovalMatcher::CommandRegex { regex, .. } => regex.is_match(command_line),
+            ApprovalMatcher::PathGlob { .. } => false,
+        }
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        match &self.matcher {
+            ApprovalMatcher::PathGlob { glob, .. } => glob.is_match(path),
+            ApprovalMatcher::CommandPrefix { .. } | ApprovalMatcher::CommandRegex { .. } => false,
+        }
+    }
+
+    fn verdict(&self) -> ApprovalVerdict {
+        ApprovalVerdict {
+            action: self.action,
+            reason: self
+                .reason
+                .clone()
+                .unwrap_or_else(|| format!("matched rule `{}`", self.matcher.describe())),
+        }
+    }
+}
+
+/// `[approvals]` settings from a single config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalPolicySettings {
+    /// Decision when no rule matches.
+    pub default: Option<ApprovalAction>,
+    /// Decline file changes outside the thread working directory.
+    pub deny_edits_outside_workdir: Option<bool>,
+    pub rules: Vec<ApprovalRule>,
+}
+
+/// Effective approval policy of a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalPolicy {
+    default: ApprovalAction,
+    deny_edits_outside_workdir: bool,
+    rules: Vec<ApprovalRule>,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            default: ApprovalAction::Allow,
+            deny_edits_outside_workdir: false,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of [`ApprovalPolicy::decide`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalVerdict {
+    pub action: ApprovalAction,
+    /// Rule reason, or a description of what decided.
+    pub reason: String,
+}
+
+impl ApprovalVerdict {
+    pub fn approved(&self) -> bool {
+        self.action == ApprovalAction::Allow
+    }
+}
+
+/// What the agent asked to do, normalized from the different upstream request shapes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalRequest {
+    Command { argv: Vec<String> },
+    FileChange { paths: Vec<PathBuf> },
+}
+
+impl ApprovalRequest {
+    pub fn kind(&self) -> PotterApprovalKind {
+        match self {
+            Self::Command { .. } => PotterApprovalKind::Command,
+            Self::FileChange { .. } => PotterApprovalKind::FileChange,
+        }
+    }
+
+    /// Command line or comma-separated paths, for display.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Command { argv } => command_script(argv),
+            Self::FileChange { paths } => paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    /// Whether the request does not say what it would run or change (unparsable parameters, or a
+    /// v2 file change whose announcing event was not seen).
+    fn is_unknown(&self) -> bool {
+        match self {
+            Self::Command { argv } => argv.iter().all(|word| word.trim().is_empty()),
+            Self::FileChange { paths } => paths.is_empty(),
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    /// Combine per-repo and user settings: repo rules are checked first and repo scalars win.
+    pub fn layered(repo: ApprovalPolicySettings, user: ApprovalPolicySettings) -> Self {
+        let defaults = Self::default();
+        Self {
+            default: repo.default.or(user.default).unwrap_or(defaults.default),
+            deny_edits_outside_workdir: repo
+                .deny_edits_outside_workdir
+                .or(user.deny_edits_outside_workdir)
+                .unwrap_or(defaults.deny_edits_outside_workdir),
+            rules: repo.rules.into_iter().chain(user.rules).collect(),
+        }
+    }
+
+    /// Layer `<workdir>/.codexpotter/config.toml` over the user settings.
+    pub fn for_workdir(user: ApprovalPolicySettings, workdir: &Path) -> anyhow::Result<Self> {
+        let repo_config = workdir.join(".codexpotter").join("config.toml");
+        let repo = crate::config::ConfigStore::new(repo_config.clone())
+            .approval_policy_settings()
+            .with_context(|| format!("read [approvals] from {}", repo_config.display()))?;
+        Ok(Self::layered(repo, user))
+    }
+
+    /// Whether every request is approved regardless of its content.
+    pub fn approves_everything(&self) -> bool {
+        self.default == ApprovalAction::Allow
+            && !self.deny_edits_outside_workdir
+            && self.rules.is_empty()
+    }
+
+    /// `approvalPolicy` requested for the upstream thread.
+    pub fn upstream_approval_policy(&self) -> AskForApproval {
+        if self.approves_everything() {
+            AskForApproval::Never
+        } else {
+            AskForApproval::UnlessTrusted
+        }
+    }
+
+    /// Decide `request` for a thread running in `workdir`.
+    pub fn decide(&self, request: &ApprovalRequest, workdir: &Path) -> ApprovalVerdict {
+        // Rules cannot match what they cannot see, so unknown requests never fall through to the
+        // default.
+        if request.is_unknown() && !self.approves_everything() {
+            let what = match request {
+                ApprovalRequest::Command { .. } => "which command it runs",
+                ApprovalRequest::FileChange { .. } => "which files it changes",
+            };
+            return ApprovalVerdict {
+                action: ApprovalAction::Ask,
+                reason: format!("the request does not say {what}"),
+            };
+        }
+        match request {
+            ApprovalRequest::Command { argv } => {
+                let script = command_script(argv);
+                self.combine(
+                    command_segments(&script)
+                        .iter()
+                        .map(|segment| self.decide_segment(segment)),
+                )
+            }
+            ApprovalRequest::FileChange { paths } => {
+                self.combine(paths.iter().map(|path| self.decide_path(path, workdir)))
+            }
+        }
+    }
+
+    /// Combine the verdicts of the parts of a request (script commands, changed files): any
+    /// denied part declines the request; otherwise any part needing an answer asks.
+    fn combine(&self, verdicts: impl Iterator<Item = ApprovalVerdict>) -> ApprovalVerdict {
+        let mut decided: Option<ApprovalVerdict> = None;
+        for verdict in verdicts {
+            match verdict.action {
+                ApprovalAction::Deny => return verdict,
+                ApprovalAction::Ask
+                    if decided
+                        .as_ref()
+                        .is_none_or(|decided| decided.action == ApprovalAction::Allow) =>
+                {
+                    decided = Some(verdict);
+                }
+                ApprovalAction::Allow | ApprovalAction::Ask => {
+                    decided.get_or_insert(verdict);
+                }
+            }
+        }
+        decided.unwrap_or_else(|| self.default_verdict())
+    }
+
+    fn decide_segment(&self, segment: &CommandSegment) -> ApprovalVerdict {
+        match segment {
+            CommandSegment::Simple(words) => {
+                let command_line = shlex::try_join(words.iter().map(String::as_str))
+                    .unwrap_or_else(|_| words.join(" "));
+                self.rules
+                    .iter()
+                    .find(|rule| rule.matches_command(words, &command_line))
+                    .map_or_else(|| self.default_verdict(), ApprovalRule::verdict)
+            }
+            // Only a deny can be decided without seeing the nested code.
+            CommandSegment::Opaque(text) => self
+                .rules
+                .iter()
+                .find(|rule| {
+                    rule.action == ApprovalAction::Deny
+                        && matches!(&rule.matcher, ApprovalMatcher::CommandRegex { .. })
+                        && rule.matches_command(&[], text)
+                })
+                .map_or_else(
+                    || ApprovalVerdict {
+                        action: ApprovalAction::Ask,
+                        reason: format!("`{text}` runs a nested shell or command substitution"),
+                    },
+                    ApprovalRule::verdict,
+                ),
+        }
+    }
+
+    fn decide_path(&self, path: &Path, workdir: &Path) -> ApprovalVerdict {
+        let absolute = normalize_path(&workdir.join(path));
+        let relative = absolute.strip_prefix(normalize_path(workdir)).ok();
+        if relative.is_none() && self.deny_edits_outside_workdir {
+            return ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: format!("{} is outside the working directory", absolute.display()),
+            };
+        }
+
+        let candidate = relative.unwrap_or(&absolute);
+        self.rules
+            .iter()
+            .find(|rule| rule.matches_path(candidate))
+            .map_or_else(|| self.default_verdict(), ApprovalRule::verdict)
+    }
+
+    fn default_verdict(&self) -> ApprovalVerdict {
+        let reason = match self.default {
+            ApprovalAction::Allow => "no rule matched",
+            ApprovalAction::Deny => "not allowed by any approval rule",
+            ApprovalAction::Ask => "not covered by any approval rule",
+        };
+        ApprovalVerdict {
+            action: self.default,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Make `ask` the decision when no rule matches (`--ask-approval`).
+    pub fn ask_by_default(mut self) -> Self {
+        self.default = ApprovalAction::Ask;
+        self
+    }
+}
+
+/// Question sent to the user when the policy resolves to [`ApprovalAction::Ask`].
+#[derive(Debug)]
+pub struct ApprovalQuestion {
+    pub kind: PotterApprovalKind,
+    pub summary: String,
+    pub reason: String,
+    pub reply_tx: oneshot::Sender<ApprovalRequestResponse>,
+}
+
+/// Routes `ask` decisions of a project's rounds to the Potter app-server, which forwards them to
+/// its client as `approval/request`.
+#[derive(Debug, Clone)]
+pub struct ApprovalAsker {
+    tx: UnboundedSender<ApprovalQuestion>,
+}
+
+impl ApprovalAsker {
+    pub fn new(tx: UnboundedSender<ApprovalQuestion>) -> Self {
+        Self { tx }
+    }
+
+    /// Ask the user about `request`. Returns `None` when nobody answered (the client went away).
+    pub async fn ask(
+        &self,
+        request: &ApprovalRequest,
+        reason: String,
+    ) -> Option<ApprovalRequestResponse> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(ApprovalQuestion {
+                kind: request.kind(),
+                summary: request.summary(),
+                reason,
+                reply_tx,
+            })
+            .ok()?;
+        reply_rx.await.ok()
+    }
+}
+
+/// Per-round approval state of the backend bridge.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalSession {
+    policy: ApprovalPolicy,
+    workdir: PathBuf,
+    /// Requests announced by `codex/event/*` approval events, keyed by call id. v2 file change
+    /// requests only carry the item id, so the paths come from here.
+    announced: HashMap<String, ApprovalRequest>,
+    /// `summary: reason` lines of requests declined since the last follow-up turn.
+    declined: Vec<String>,
+    follow_ups: u32,
+    /// Asks the user about `ask` verdicts; without it they are declined.
+    asker: Option<ApprovalAsker>,
+    /// Summaries the user approved for the rest of the round.
+    approved_for_session: HashSet<String>,
+}
+
+impl ApprovalSession {
+    pub fn new(policy: ApprovalPolicy, workdir: PathBuf, asker: Option<ApprovalAsker>) -> Self {
+        Self {
+            policy,
+            workdir,
+            asker,
+            ..Default::default()
+        }
+    }
+
+    /// Remember the request announced by an upstream approval event.
+    pub fn observe_event(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::ExecApprovalRequest(ev) => {
+                self.announced.insert(
+                    ev.effective_approval_id(),
+                    ApprovalRequest::Command {
+                        argv: ev.command.clone(),
+                    },
+                );
+            }
+            EventMsg::ApplyPatchApprovalRequest(ev) => {
+                let mut paths = ev.changes.keys().cloned().collect::<Vec<_>>();
+                paths.sort();
+                self.announced
+                    .insert(ev.call_id.clone(), ApprovalRequest::FileChange { paths });
+            }
+            _ => {}
+        }
+    }
+
+    /// Normalize an upstream approval request, falling back to the announcing event.
+    pub fn request_for(&mut self, request: &ServerRequest) -> ApprovalRequest {
+        let (params, id_key) = match request {
+            ServerRequest::CommandExecution { params, .. }
+            | ServerRequest::FileChange { params, .. } => (params, "itemId"),
+            ServerRequest::ApplyPatch { params, .. }
+            | ServerRequest::ExecCommand { params, .. } => (params, "callId"),
+        };
+        let params = params.as_ref();
+        let announced = params
+            .and_then(|params| params.get(id_key))
+            .and_then(serde_json::Value::as_str)
+            .and_then(|id| self.announced.remove(id));
+
+        let parsed = params.and_then(|params| match request {
+            ServerRequest::CommandExecution { .. } => params
+                .get("command")
+                .and_then(serde_json::Value::as_str)
+                .map(|command| ApprovalRequest::Command {
+                    argv: split_command_line(command),
+                }),
+            ServerRequest::ExecCommand { .. } => params
+                .get("command")
+                .and_then(|command| serde_json::from_value::<Vec<String>>(command.clone()).ok())
+                .map(|argv| ApprovalRequest::Command { argv }),
+            ServerRequest::ApplyPatch { .. } => params
+                .get("fileChanges")
+                .and_then(serde_json::Value::as_object)
+                .map(|changes| ApprovalRequest::FileChange {
+                    paths: changes.keys().map(PathBuf::from).collect(),
+                }),
+            ServerRequest::FileChange { .. } => None,
+        });
+
+        parsed.or(announced).unwrap_or_else(|| match request {
+            ServerRequest::CommandExecution { .. } | ServerRequest::ExecCommand { .. } => {
+                ApprovalRequest::Command { argv: Vec::new() }
+            }
+            ServerRequest::FileChange { .. } | ServerRequest::ApplyPatch { .. } => {
+                ApprovalRequest::FileChange { paths: Vec::new() }
+            }
+        })
+    }
+
+    /// Decide `request`, remembering declined requests for [`Self::take_follow_up_prompt`].
+    ///
+    /// Returns an [`ApprovalAction::Ask`] verdict only when an asker is available; answer it with
+    /// [`Self::record_answer`].
+    pub fn decide(&mut self, request: &ApprovalRequest) -> ApprovalVerdict {
+        let mut verdict = self.policy.decide(request, &self.workdir);
+        if verdict.action == ApprovalAction::Ask {
+            if self.approved_for_session.contains(&request.summary()) {
+                verdict = ApprovalVerdict {
+                    action: ApprovalAction::Allow,
+                    reason: "approved for this session by the user".to_string(),
+                };
+            } else if self.asker.is_none() {
+                verdict = ApprovalVerdict {
+                    action: ApprovalAction::Deny,
+                    reason: format!("{}; no one is available to approve it", verdict.reason),
+                };
+            }
+        }
+        if verdict.action == ApprovalAction::Deny {
+            self.record_declined(request, &verdict.reason);
+        }
+        verdict
+    }
+
+    /// Asker for `ask` verdicts returned by [`Self::decide`].
+    pub fn asker(&self) -> Option<ApprovalAsker> {
+        self.asker.clone()
+    }
+
+    /// Turn the user's answer to an `ask` verdict into the final verdict. `None` means the
+    /// question went unanswered.
+    pub fn record_answer(
+        &mut self,
+        request: &ApprovalRequest,
+        answer: Option<&ApprovalRequestResponse>,
+    ) -> ApprovalVerdict {
+        let Some(answer) = answer else {
+            let reason = "no answer from the user".to_string();
+            self.record_declined(request, &reason);
+            return ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason,
+            };
+        };
+
+        match answer.decision {
+            PotterApprovalChoice::Approve => ApprovalVerdict {
+                action: ApprovalAction::Allow,
+                reason: "approved by the user".to_string(),
+            },
+            PotterApprovalChoice::ApproveForSession => {
+                // An unknown request has no summary to remember it by.
+                if !request.is_unknown() {
+                    self.approved_for_session.insert(request.summary());
+                }
+                ApprovalVerdict {
+                    action: ApprovalAction::Allow,
+                    reason: "approved for this session by the user".to_string(),
+                }
+            }
+            PotterApprovalChoice::Deny => {
+                let reason = match answer
+                    .feedback
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|feedback| !feedback.is_empty())
+                {
+                    Some(feedback) => format!("declined by the user: {feedback}"),
+                    None => "declined by the user".to_string(),
+                };
+                self.record_declined(request, &reason);
+                ApprovalVerdict {
+                    action: ApprovalAction::Deny,
+                    reason,
+                }
+            }
+        }
+    }
+
+    fn record_declined(&mut self, request: &ApprovalRequest, reason: &str) {
+        self.declined
+            .push(format!("`{}`: {}", request.summary(), reason));
+    }
+
+    /// Whether a follow-up turn should report declined requests before the round completes.
+    pub fn has_follow_up(&self) -> bool {
+        !self.declined.is_empty() && self.follow_ups < MAX_DECLINE_FOLLOW_UPS
+    }
+
+    /// Prompt of the follow-up turn telling the model what was declined and why.
+    pub fn take_follow_up_prompt(&mut self) -> Option<String> {
+        if !self.has_follow_up() {
+            return None;
+        }
+        self.follow_ups += 1;
+        let declined = std::mem::take(&mut self.declined);
+        let mut prompt = String::from("The CodexPotter approval policy declined these requests:\n");
+        for line in declined {
+            prompt.push_str(&format!("- {line}\n"));
+        }
+        prompt.push_str(
+            "\nDo not retry them. Find another way, or leave them to the user and note them in \
+             the progress file, then continue.",
+        );
+        Some(prompt)
+    }
+
+    /// Drop pending follow-ups, e.g. when the turn was interrupted.
+    pub fn clear_declined(&mut self) {
+        self.declined.clear();
+    }
+}
+
+/// Command line of `argv`, unwrapping `bash -lc <script>` style invocations to the script.
+fn command_script(argv: &[String]) -> String {
+    if let [shell, flag, script] = argv {
+        let shell_name = Path::new(shell)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if matches!(shell_name, "bash" | "sh" | "zsh") && matches!(flag.as_str(), "-lc" | "-c") {
+            return script.clone();
+        }
+    }
+    shlex::try_join(argv.iter().map(String::as_str)).unwrap_or_else(|_| argv.join(" "))
+}
+
+/// One command of a script, as approval rules see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CommandSegment {
+    /// Argv without leading assignments and wrappers; the program is a basename.
+    Simple(Vec<String>),
+    /// Runs code the rules cannot see (nested shell, subshell, command substitution); holds the
+    /// command text.
+    Opaque(String),
+}
+
+/// Split `script` into its `&&` / `||` / `;` / `|` / `&` / newline separated commands.
+fn command_segments(script: &str) -> Vec<CommandSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut opaque = false;
+    let mut finish = |current: &mut String, opaque: &mut bool| {
+        let text = current.trim().to_string();
+        if !text.is_empty() {
+            segments.push(if *opaque {
+                CommandSegment::Opaque(text)
+            } else {
+                normalize_segment(&text)
+            });
+        }
+        current.clear();
+        *opaque = false;
+    };
+
+    let (mut in_single, mut in_double, mut escaped) = (false, false, false);
+    let mut chars = script.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if escaped {
+            escaped = false;
+            current.push(ch);
+            continue;
+        }
+        match ch {
+            '\\' if !in_single => escaped = true,
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '`' if !in_single => opaque = true,
+            '$' if !in_single && chars.peek() == Some(&'(') => opaque = true,
+            '(' | ')' if !in_single && !in_double => opaque = true,
+            // `2>&1`, `&>` and `>|` are redirections, not separators.
+            '&' | '|' if current.ends_with(['>', '<']) || chars.peek() == Some(&'>') => {}
+            ';' | '&' | '|' | '\n' if !in_single && !in_double => {
+                finish(&mut current, &mut opaque);
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    finish(&mut current, &mut opaque);
+    segments
+}
+
+/// Strip leading `NAME=value` assignments and command wrappers from a command and reduce its
+/// program to a basename.
+fn normalize_segment(text: &str) -> CommandSegment {
+    let words = split_command_line(text);
+    let mut rest = words.as_slice();
+    loop {
+        let Some((word, tail)) = rest.split_first() else {
+            break;
+        };
+        if is_env_assignment(word) {
+            rest = tail;
+        } else if COMMAND_WRAPPERS.contains(&program_name(word).as_str()) {
+            // `sudo -s` / `sudo -i` / `env -S` run a shell or split a script of their own.
+            let Some(tail) = skip_wrapper_options(tail) else {
+                return CommandSegment::Opaque(text.to_string());
+            };
+            rest = tail;
+        } else {
+            break;
+        }
+    }
+
+    let Some((program, args)) = rest.split_first() else {
+        return CommandSegment::Simple(Vec::new());
+    };
+    let program = program_name(program);
+    if program.starts_with('{') || NESTED_SHELLS.contains(&program.as_str()) {
+        return CommandSegment::Opaque(text.to_string());
+    }
+    CommandSegment::Simple(
+        std::iter::once(program)
+            .chain(args.iter().cloned())
+            .collect(),
+    )
+}
+
+/// Skip the options of a wrapper; `None` when an option makes it run a shell or split a script.
+fn skip_wrapper_options(mut words: &[String]) -> Option<&[String]> {
+    while let Some((word, tail)) = words.split_first() {
+        if word == "--" {
+            return Some(tail);
+        }
+        if !word.starts_with('-') {
+            break;
+        }
+        if matches!(
+            word.as_str(),
+            "-s" | "-i" | "--shell" | "--login" | "--split-string"
+        ) || word.starts_with("-S")
+            || word.starts_with("--split-string=")
+        {
+            return None;
+        }
+        words = if WRAPPER_OPTIONS_WITH_VALUE.contains(&word.as_str()) {
+            tail.get(1..).unwrap_or_default()
+        } else {
+            tail
+        };
+    }
+    Some(words)
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|ch: char| ch.is_ascii_digit())
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    })
+}
+
+/// Basename of a program path (`/usr/bin/git` -> `git`).
+fn program_name(program: &str) -> String {
+    Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program)
+        .to_string()
+}
+
+fn split_command_line(command: &str) -> Vec<String> {
+    shlex::split(command)
+        .unwrap_or_else(|| command.split_whitespace().map(str::to_string).collect())
+}
+
+/// Resolve `.` and `..` lexically (the path may not exist yet).
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn command(script: &str) -> ApprovalRequest {
+        ApprovalRequest::Command {
+            argv: vec![
+                "/bin/bash".to_string(),
+                "-lc".to_string(),
+                script.to_string(),
+            ],
+        }
+    }
+
+    fn policy(rules: Vec<ApprovalRule>) -> ApprovalPolicy {
+        ApprovalPolicy::layered(
+            ApprovalPolicySettings {
+                rules,
+                ..Default::default()
+            },
+            ApprovalPolicySettings::default(),
+        )
+    }
+
+    #[test]
+    fn command_prefix_matches_any_script_segment() {
+        let policy = policy(vec![
+            ApprovalRule::command_prefix(
+                ApprovalAction::Deny,
+                "git push",
+                Some("Pushing is left to the user.".to_string()),
+            )
+            .expect("rule"),
+        ]);
+        let workdir = Path::new("/repo");
+
+        assert_eq!(
+            policy.decide(&command("cargo test && git push origin main"), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: "Pushing is left to the user.".to_string(),
+            }
+        );
+        assert_eq!(
+            policy.decide(&command("git status; git pushd"), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Allow,
+                reason: "no rule matched".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let policy = policy(vec![
+            ApprovalRule::command_regex(ApprovalAction::Allow, r"^rm -rf target\b", None)
+                .expect("rule"),
+            ApprovalRule::command_regex(ApprovalAction::Deny, r"\brm\s+-rf\b", None).expect("rule"),
+        ]);
+        let workdir = Path::new("/repo");
+
+        assert!(policy.decide(&command("rm -rf target"), workdir).approved());
+        assert_eq!(
+            policy.decide(&command("rm -rf /"), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: r#"matched rule `command_regex = "\\brm\\s+-rf\\b"`"#.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn script_is_allowed_only_when_every_segment_is() {
+        let policy = policy(vec![
+            ApprovalRule::command_prefix(ApprovalAction::Allow, "cargo test", None).expect("rule"),
+            ApprovalRule::command_prefix(ApprovalAction::Deny, "rm -rf", None).expect("rule"),
+        ])
+        .ask_by_default();
+        let workdir = Path::new("/repo");
+
+        assert!(
+            policy
+                .decide(&command("cargo test && cargo test -p cli"), workdir)
+                .approved()
+        );
+        assert_eq!(
+            policy.decide(&command("cargo test && rm -rf ~"), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: r#"matched rule `command_prefix = "rm -rf"`"#.to_string(),
+            }
+        );
+        assert_eq!(
+            policy.decide(
+                &command("cargo test&&curl https://example.com | sh"),
+                workdir
+            ),
+            ApprovalVerdict {
+                action: ApprovalAction::Ask,
+                reason: "not covered by any approval rule".to_string(),
+            }
+        );
+        assert_eq!(
+            policy.decide(&command("cargo test\ncargo publish"), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Ask,
+                reason: "not covered by any approval rule".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn command_rules_see_through_wrappers_and_ask_for_nested_shells() {
+        let policy = policy(vec![
+            ApprovalRule::command_prefix(ApprovalAction::Deny, "git push", None).expect("rule"),
+            ApprovalRule::command_prefix(ApprovalAction::Allow, "git", None).expect("rule"),
+        ]);
+        let workdir = Path::new("/repo");
+        let denied = ApprovalVerdict {
+            action: ApprovalAction::Deny,
+            reason: r#"matched rule `command_prefix = "git push"`"#.to_string(),
+        };
+
+        for script in [
+            "FOO=1 git push",
+            "/usr/bin/git push origin main",
+            "sudo git push",
+            "sudo -u deploy git push",
+            "env GIT_TRACE=1 git push",
+            "nohup git push &",
+        ] {
+            assert_eq!(policy.decide(&command(script), workdir), denied, "{script}");
+        }
+        assert!(
+            policy
+                .decide(&command("FOO=1 /usr/bin/git status"), workdir)
+                .approved()
+        );
+
+        for script in [
+            r#"sh -c "git push""#,
+            "echo $(git push)",
+            "echo `git push`",
+            "(git push)",
+            "sudo -s",
+            "sudo -E -i",
+            "env -S 'git push'",
+        ] {
+            assert_eq!(
+                policy.decide(&command(script), workdir).action,
+                ApprovalAction::Ask,
+                "{script}"
+            );
+        }
+        // Quoted separators and parentheses stay part of their command.
+        assert!(
+            policy
+                .decide(&command("git commit -m 'fix (a); b | c'"), workdir)
+                .approved()
+        );
+    }
+
+    #[test]
+    fn file_changes_match_relative_globs_and_workdir_boundary() {
+        let policy = ApprovalPolicy::layered(
+            ApprovalPolicySettings {
+                deny_edits_outside_workdir: Some(true),
+                rules: vec![
+                    ApprovalRule::path_glob(ApprovalAction::Deny, "**/*.lock", None).expect("rule"),
+                ],
+                ..Default::default()
+            },
+            ApprovalPolicySettings {
+                default: Some(ApprovalAction::Deny),
+                ..Default::default()
+            },
+        );
+        let workdir = Path::new("/repo");
+        let change = |paths: &[&str]| ApprovalRequest::FileChange {
+            paths: paths.iter().map(PathBuf::from).collect(),
+        };
+
+        assert!(
+            !policy
+                .decide(&change(&["/repo/cli/Cargo.lock"]), workdir)
+                .approved()
+        );
+        assert_eq!(
+            policy.decide(
+                &change(&["/repo/../etc/hosts", "/repo/src/main.rs"]),
+                workdir
+            ),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: "/etc/hosts is outside the working directory".to_string(),
+            }
+        );
+        assert_eq!(
+            policy.decide(&change(&["src/main.rs"]), workdir),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: "not allowed by any approval rule".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn session_reports_declined_requests_in_follow_up_prompt() {
+        let mut session = ApprovalSession::new(
+            policy(vec![
+                ApprovalRule::command_prefix(ApprovalAction::Deny, "git push", None).expect("rule"),
+            ]),
+            PathBuf::from("/repo"),
+            None,
+        );
+
+        let request = session.request_for(&ServerRequest::CommandExecution {
+            request_id: crate::app_server::upstream_protocol::RequestId::Integer(1),
+            params: Some(serde_json::json!({
+                "itemId": "call_1",
+                "command": "git push origin main",
+            })),
+        });
+        assert!(!session.decide(&request).approved());
+        assert!(session.has_follow_up());
+
+        assert_eq!(
+            session.take_follow_up_prompt().as_deref(),
+            Some(
+                "The CodexPotter approval policy declined these requests:\n\
+                 - `git push origin main`: matched rule `command_prefix = \"git push\"`\n\
+                 \nDo not retry them. Find another way, or leave them to the user and note them \
+                 in the progress file, then continue."
+            )
+        );
+        assert!(!session.has_follow_up());
+    }
+
+    #[test]
+    fn session_uses_announced_paths_for_v2_file_change_requests() {
+        let mut session = ApprovalSession::default();
+        session.observe_event(&EventMsg::ApplyPatchApprovalRequest(
+            codex_protocol::approvals::ApplyPatchApprovalRequestEvent {
+                call_id: "call_2".to_string(),
+                turn_id: "turn_1".to_string(),
+                changes: HashMap::from([(
+                    PathBuf::from("/repo/src/lib.rs"),
+                    codex_protocol::protocol::FileChange::Delete {
+                        content: String::new(),
+                    },
+                )]),
+                reason: None,
+                grant_root: None,
+            },
+        ));
+
+        let request = session.request_for(&ServerRequest::FileChange {
+            request_id: crate::app_server::upstream_protocol::RequestId::Integer(2),
+            params: Some(serde_json::json!({ "itemId": "call_2" })),
+        });
+        assert_eq!(
+            request,
+            ApprovalRequest::FileChange {
+                paths: vec![PathBuf::from("/repo/src/lib.rs")],
+            }
+        );
+    }
+
+    #[test]
+    fn unannounced_file_changes_are_not_allowed_by_default() {
+        let deny_secrets = policy(vec![
+            ApprovalRule::path_glob(ApprovalAction::Deny, "secrets/**", None).expect("rule"),
+        ]);
+        let unannounced = ServerRequest::FileChange {
+            request_id: crate::app_server::upstream_protocol::RequestId::Integer(3),
+            params: Some(serde_json::json!({ "itemId": "call_3" })),
+        };
+
+        let mut unattended =
+            ApprovalSession::new(deny_secrets.clone(), PathBuf::from("/repo"), None);
+        let request = unattended.request_for(&unannounced);
+        assert_eq!(request, ApprovalRequest::FileChange { paths: Vec::new() });
+        assert_eq!(
+            unattended.decide(&request),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: "the request does not say which files it changes; no one is available \
+                         to approve it"
+                    .to_string(),
+            }
+        );
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut attended = ApprovalSession::new(
+            deny_secrets,
+            PathBuf::from("/repo"),
+            Some(ApprovalAsker::new(tx)),
+        );
+        let request = attended.request_for(&unannounced);
+        assert_eq!(attended.decide(&request).action, ApprovalAction::Ask);
+        let answer = ApprovalRequestResponse {
+            decision: PotterApprovalChoice::ApproveForSession,
+            feedback: None,
+        };
+        assert!(attended.record_answer(&request, Some(&answer)).approved());
+        assert_eq!(
+            attended.decide(&request).action,
+            ApprovalAction::Ask,
+            "unknown requests are never approved for the session"
+        );
+
+        let mut everything = ApprovalSession::default();
+        let request = everything.request_for(&unannounced);
+        assert!(everything.decide(&request).approved());
+    }
+
+    #[test]
+    fn ask_verdicts_go_to_the_user_and_remember_session_approvals() {
+        let ask_policy = ApprovalPolicy::default().ask_by_default();
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut session = ApprovalSession::new(
+            ask_policy.clone(),
+            PathBuf::from("/repo"),
+            Some(ApprovalAsker::new(tx)),
+        );
+        let request = command("cargo publish");
+
+        assert_eq!(
+            session.decide(&request),
+            ApprovalVerdict {
+                action: ApprovalAction::Ask,
+                reason: "not covered by any approval rule".to_string(),
+            }
+        );
+        let answer = ApprovalRequestResponse {
+            decision: PotterApprovalChoice::ApproveForSession,
+            feedback: None,
+        };
+        assert!(session.record_answer(&request, Some(&answer)).approved());
+        assert_eq!(
+            session.decide(&request),
+            ApprovalVerdict {
+                action: ApprovalAction::Allow,
+                reason: "approved for this session by the user".to_string(),
+            }
+        );
+
+        let denied = command("git push");
+        let answer = ApprovalRequestResponse {
+            decision: PotterApprovalChoice::Deny,
+            feedback: Some(" Push after review. ".to_string()),
+        };
+        assert_eq!(
+            session.record_answer(&denied, Some(&answer)).reason,
+            "declined by the user: Push after review."
+        );
+        assert!(session.has_follow_up());
+
+        let mut unattended = ApprovalSession::new(ask_policy, PathBuf::from("/repo"), None);
+        assert_eq!(
+            unattended.decide(&request),
+            ApprovalVerdict {
+                action: ApprovalAction::Deny,
+                reason: "not covered by any approval rule; no one is available to approve it"
+                    .to_string(),
+            }
+        );
+    }
+}
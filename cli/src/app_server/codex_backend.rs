@@ -2,26 +2,48 @@
 //!
 //! This module is the execution plane for CodexPotter rounds:
 //!
-//! - Spawns an external `codex app-server` process (one process per round).
+//! - Spawns an external `codex app-server` process: one process per round, or one process reused
+//!   across rounds through an [`AppServerPool`].
 //! - Drives the JSON-RPC request/response lifecycle (`thread/*`, `turn/start`, etc.).
 //! - Translates upstream notifications into `codex_protocol::protocol::EventMsg`.
 //! - Implements CodexPotter-specific stream recovery by injecting `PotterStreamRecovery*` markers
 //!   and retrying with follow-up `Continue` turns when retryable transient errors occur.
+//! - Answers upstream approval requests with the project's
+//!   [`ApprovalPolicy`](crate::app_server::approval_policy::ApprovalPolicy) (asking the user via
+//!   [`ApprovalAsker`] for `ask` verdicts), and reports declined requests to the model in a
+//!   follow-up turn.
+//! - Routes upstream questions for the user (`request_user_input`, MCP elicitations) through a
+//!   [`UserInputAsker`], declining them when there is nobody to ask.
+//! - Tees the app-server's stderr to the round's log file and attaches its last lines to the
+//!   error that ends a round (`EventMsg::PotterAppServerStderr`, `PotterRoundOutcome::Fatal`).
+//! - Checks the app-server's version from the `initialize` response and reports upstream events the
+//!   protocol mirror cannot decode (see [`upstream_version`]).
 //!
 //! The backend emits a well-formed round boundary by synthesizing `EventMsg::PotterRoundFinished`,
 //! and applies additional event filtering depending on [`AppServerEventMode`].
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
-
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::app_server::approval_policy::ApprovalAction;
+use crate::app_server::approval_policy::ApprovalAsker;
+use crate::app_server::approval_policy::ApprovalPolicy;
+use crate::app_server::approval_policy::ApprovalRequest;
+use crate::app_server::approval_policy::ApprovalSession;
+use crate::app_server::approval_policy::ApprovalVerdict;
+use crate::app_server::potter::ApprovalRequestResponse;
 use crate::app_server::stream_recovery::ContinueRetryDecision;
 use crate::app_server::stream_recovery::ContinueRetryPlan;
 use crate::app_server::stream_recovery::PotterStreamRecovery;
+use crate::app_server::stream_recovery::StreamRecoveryPolicy;
 use crate::app_server::upstream_protocol::ApplyPatchApprovalResponse;
 use crate::app_server::upstream_protocol::ClientInfo;
 use crate::app_server::upstream_protocol::ClientNotification;
@@ -32,6 +54,7 @@ use crate::app_server::upstream_protocol::ExecCommandApprovalResponse;
 use crate::app_server::upstream_protocol::FileChangeApprovalDecision;
 use crate::app_server::upstream_protocol::FileChangeRequestApprovalResponse;
 use crate::app_server::upstream_protocol::InitializeParams;
+use crate::app_server::upstream_protocol::InitializeResponse;
 use crate::app_server::upstream_protocol::JSONRPCError;
 use crate::app_server::upstream_protocol::JSONRPCErrorError;
 use crate::app_server::upstream_protocol::JSONRPCMessage;
@@ -47,6 +70,9 @@ use crate::app_server::upstream_protocol::ThreadStartResponse;
 use crate::app_server::upstream_protocol::TurnStartParams;
 use crate::app_server::upstream_protocol::TurnStartResponse;
 use crate::app_server::upstream_protocol::UserInput as ApiUserInput;
+use crate::app_server::upstream_version;
+use crate::app_server::user_input::UserInputAsker;
+use crate::app_server::user_input::UserInputRequest;
 use anyhow::Context;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -54,10 +80,14 @@ use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterApprovalChoice;
 use codex_protocol::protocol::PotterRoundOutcome;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SessionConfiguredEvent;
+use codex_protocol::protocol::WarningEvent;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
 use codex_protocol::user_input::UserInput as CodexUserInput;
+use serde::Deserialize as _;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
@@ -75,9 +105,38 @@ use tokio::sync::mpsc::unbounded_channel;
 /// (e.g. interrupting a turn that already completed).
 const JSONRPC_INVALID_REQUEST_ERROR_CODE: i64 = -32600;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 enum RecoveryAction {
-    RetryContinue { attempt: u32 },
+    RetryContinue {
+        attempt: u32,
+    },
+    /// Tell the model which approval requests were declined (see [`ApprovalSession`]).
+    ReportDeclinedApprovals,
+    /// The user answered an approval request that the policy resolved to `ask`.
+    ApprovalAnswered {
+        pending: Box<PendingApproval>,
+        answer: Option<ApprovalRequestResponse>,
+    },
+    /// The user answered (or declined) a user input request.
+    UserInputAnswered {
+        pending: Box<PendingUserInput>,
+        answers: Option<HashMap<String, RequestUserInputAnswer>>,
+    },
+}
+
+/// Upstream approval request waiting for the user's answer.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingApproval {
+    request_id: RequestId,
+    server_request: ServerRequest,
+    approval_request: ApprovalRequest,
+}
+
+/// Upstream user input request waiting for the user's answer.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingUserInput {
+    request_id: RequestId,
+    request: UserInputRequest,
 }
 
 struct StreamRecoveryContext {
@@ -89,6 +148,18 @@ struct StreamRecoveryContext {
     has_finished_round: bool,
     last_turn_start_was_recovery_continue: bool,
     event_mode: AppServerEventMode,
+    approvals: ApprovalSession,
+    /// Asks the user to answer user input requests; without it they are declined.
+    user_input_asker: Option<UserInputAsker>,
+    /// Recent stderr of the app-server, attached to the error that ends the round.
+    stderr_tail: StderrTail,
+    /// Round log the app-server's stderr is tee'd to.
+    stderr_log: Option<PathBuf>,
+    /// Upstream events of this round the protocol mirror could not decode, by event type.
+    unsupported_events: BTreeMap<String, usize>,
+    /// Thread this round runs on; `codex/event/*` notifications for other threads are dropped
+    /// (a reused app-server may still flush events of the previous round's thread).
+    thread_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -131,16 +202,31 @@ pub enum AppServerEventMode {
     ExecJson,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct AppServerBackendConfig {
     pub codex_bin: String,
     pub developer_instructions: Option<String>,
     pub launch: AppServerLaunchConfig,
     pub upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+    /// Reasoning effort override for the thread (applied via `thread/*` config).
+    pub reasoning_effort: Option<ReasoningEffort>,
     pub codex_home: Option<PathBuf>,
     pub thread_cwd: Option<PathBuf>,
     pub resume_thread_id: Option<ThreadId>,
     pub event_mode: AppServerEventMode,
+    /// Decides upstream approval requests.
+    pub approval_policy: ApprovalPolicy,
+    /// Asks the user about `ask` verdicts; without it they are declined.
+    pub approval_asker: Option<ApprovalAsker>,
+    /// Asks the user to answer user input requests; without it they are declined.
+    pub user_input_asker: Option<UserInputAsker>,
+    /// How retries after recoverable turn errors are scheduled.
+    pub stream_recovery: StreamRecoveryPolicy,
+    /// Keeps the app-server process alive for the next round; `None` spawns one process per
+    /// round.
+    pub process_pool: Option<AppServerPool>,
+    /// File the app-server's stderr is tee'd to while this round runs.
+    pub stderr_log: Option<PathBuf>,
 }
 
 pub async fn run_app_server_backend(
@@ -180,62 +266,61 @@ async fn run_app_server_backend_inner(
         developer_instructions,
         launch,
         upstream_cli_args,
+        reasoning_effort,
         codex_home,
         thread_cwd,
         resume_thread_id,
         event_mode,
+        approval_policy,
+        approval_asker,
+        user_input_asker,
+        stream_recovery,
+        process_pool,
+        stderr_log,
     } = config;
-    let (mut child, stdin, stdout, stderr) = spawn_app_server(
-        &codex_bin,
-        launch,
-        &upstream_cli_args,
-        codex_home.as_deref(),
-    )
-    .await?;
-    let stderr_capture = Arc::new(Mutex::new(Vec::<u8>::new()));
-    let stderr_truncated = Arc::new(AtomicBool::new(false));
-    let stderr_task = {
-        let stderr_capture = stderr_capture.clone();
-        let stderr_truncated = stderr_truncated.clone();
-        tokio::spawn(async move {
-            const LIMIT_BYTES: usize = 32 * 1024;
-            let mut stderr = stderr;
-            let mut buf = [0u8; 4096];
-
-            loop {
-                let n = stderr.read(&mut buf).await?;
-                if n == 0 {
-                    break;
-                }
-
-                let mut capture = match stderr_capture.lock() {
-                    Ok(guard) => guard,
-                    Err(err) => err.into_inner(),
-                };
-                let remaining = LIMIT_BYTES.saturating_sub(capture.len());
-                if remaining == 0 {
-                    stderr_truncated.store(true, Ordering::Relaxed);
-                    continue;
-                }
-
-                let take = remaining.min(n);
-                capture.extend_from_slice(&buf[..take]);
-                if take < n {
-                    stderr_truncated.store(true, Ordering::Relaxed);
-                }
-            }
-
-            Ok::<(), std::io::Error>(())
-        })
+    let upstream_approval_policy = approval_policy.upstream_approval_policy();
+    let approvals_workdir = match &thread_cwd {
+        Some(cwd) => cwd.clone(),
+        None => std::env::current_dir().context("resolve current directory")?,
+    };
+    let key = AppServerProcessKey::new(codex_bin, launch, &upstream_cli_args, codex_home);
+    let process = match process_pool.as_ref().and_then(|pool| pool.take(&key)) {
+        Some(process) => process,
+        None => spawn_app_server(key).await?,
+    };
+    let AppServerProcess {
+        key,
+        mut child,
+        stdin,
+        mut lines,
+        mut stderr,
+        mut next_id,
+        spawned_at,
+        mut startup,
+    } = process;
+    // Errors of this round should only carry this round's stderr.
+    let stderr_log = match stderr.start_round(stderr_log.as_deref()) {
+        Ok(()) => stderr_log,
+        Err(err) => {
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("app-server stderr will not be logged: {err:#}"),
+                }),
+            });
+            None
+        }
     };
 
     let mut stdin = Some(stdin);
-    let mut lines = BufReader::new(stdout).lines();
-    let mut next_id: i64 = 1;
     let mut shutdown_requested = false;
+    let mut keep_process = false;
+    // Output schema of the round's prompt, reapplied to the turns codex-potter starts on its own
+    // (recovery `Continue`, declined approval reports) so the final message stays structured.
+    let mut output_schema = None;
     let (recovery_action_tx, mut recovery_action_rx) = unbounded_channel::<RecoveryAction>();
     let mut recovery = StreamRecoveryContext {
-        stream_recovery: PotterStreamRecovery::new(),
+        stream_recovery: PotterStreamRecovery::with_policy(stream_recovery),
         recovery_action_tx,
         pending_continue_retry: None,
         active_turn_id: None,
@@ -243,19 +328,32 @@ async fn run_app_server_backend_inner(
         has_finished_round: false,
         last_turn_start_was_recovery_continue: false,
         event_mode,
+        approvals: ApprovalSession::new(approval_policy, approvals_workdir, approval_asker),
+        user_input_asker,
+        stderr_tail: stderr.tail.clone(),
+        stderr_log: stderr_log.clone(),
+        unsupported_events: BTreeMap::new(),
+        thread_id: None,
     };
 
     let result = async {
-        initialize_app_server(
-            stdin
-                .as_mut()
-                .context("codex app-server stdin unavailable")?,
-            &mut lines,
-            &mut next_id,
-            &mut recovery,
-            event_tx,
-        )
-        .await?;
+        let startup_saved = match startup {
+            Some(startup) => Some(startup),
+            None => {
+                initialize_app_server(
+                    stdin
+                        .as_mut()
+                        .context("codex app-server stdin unavailable")?,
+                    &mut lines,
+                    &mut next_id,
+                    &mut recovery,
+                    event_tx,
+                )
+                .await?;
+                startup = Some(spawned_at.elapsed());
+                None
+            }
+        };
 
         let thread_start_or_resume = match resume_thread_id {
             Some(thread_id) => ThreadStartOrResume::Resume(
@@ -268,8 +366,10 @@ async fn run_app_server_backend_inner(
                     ThreadResumeSettings {
                         thread_id,
                         model: upstream_cli_args.model.clone(),
+                        reasoning_effort,
                         developer_instructions,
                         sandbox_mode: launch.thread_sandbox,
+                        approval_policy: upstream_approval_policy,
                         cwd: thread_cwd,
                     },
                     &mut recovery,
@@ -286,8 +386,10 @@ async fn run_app_server_backend_inner(
                     &mut next_id,
                     ThreadStartSettings {
                         model: upstream_cli_args.model.clone(),
+                        reasoning_effort,
                         developer_instructions,
                         sandbox_mode: launch.thread_sandbox,
+                        approval_policy: upstream_approval_policy,
                         cwd: thread_cwd,
                     },
                     &mut recovery,
@@ -298,27 +400,45 @@ async fn run_app_server_backend_inner(
         };
 
         let thread_id = thread_start_or_resume.thread_id().to_string();
+        recovery.thread_id = Some(thread_id.clone());
 
         let session_configured = synthesize_session_configured(&thread_start_or_resume)?;
         let _ = event_tx.send(Event {
             id: "".to_string(),
             msg: EventMsg::SessionConfigured(session_configured),
         });
+        if let Some(startup_saved) = startup_saved {
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg: EventMsg::PotterAppServerReused { startup_saved },
+            });
+        }
 
         loop {
             tokio::select! {
                 maybe_op = op_rx.recv(), if !shutdown_requested => {
                     let Some(op) = maybe_op else {
                         shutdown_requested = true;
+                        // An idle app-server can serve the next round; one with a turn in flight
+                        // is shut down as before.
+                        if process_pool.is_some() && recovery.active_turn_id.is_none() {
+                            keep_process = true;
+                            break;
+                        }
                         stdin.take();
                         continue;
                     };
-                    if matches!(op, Op::UserInput { .. }) {
+                    if let Op::UserInput {
+                        final_output_json_schema,
+                        ..
+                    } = &op
+                    {
+                        output_schema = final_output_json_schema.clone();
                         let was_in_retry_streak = recovery.stream_recovery.is_in_retry_streak();
                         recovery.has_sent_turn_start = true;
                         recovery.last_turn_start_was_recovery_continue = false;
                         recovery.pending_continue_retry = None;
-                        recovery.stream_recovery = PotterStreamRecovery::new();
+                        recovery.stream_recovery.reset();
                         if was_in_retry_streak {
                             let _ = event_tx.send(Event {
                                 id: "".to_string(),
@@ -342,11 +462,9 @@ async fn run_app_server_backend_inner(
                         continue;
                     };
 
-                    if !recovery.stream_recovery.is_in_retry_streak() {
-                        continue;
-                    }
-
                     match action {
+                        RecoveryAction::RetryContinue { .. }
+                            if !recovery.stream_recovery.is_in_retry_streak() => {}
                         RecoveryAction::RetryContinue { attempt } => {
                             recovery.has_sent_turn_start = true;
                             if attempt >= 2 && recovery.last_turn_start_was_recovery_continue {
@@ -374,7 +492,7 @@ async fn run_app_server_backend_inner(
                                         text: String::from("Continue"),
                                         text_elements: Vec::new(),
                                     }],
-                                    final_output_json_schema: None,
+                                    final_output_json_schema: output_schema.clone(),
                                 },
                                 stdin.as_mut().context("codex app-server stdin unavailable")?,
                                 &mut lines,
@@ -384,6 +502,66 @@ async fn run_app_server_backend_inner(
                             )
                             .await?;
                         }
+                        RecoveryAction::ReportDeclinedApprovals => {
+                            let Some(text) = recovery.approvals.take_follow_up_prompt() else {
+                                continue;
+                            };
+                            recovery.has_sent_turn_start = true;
+                            recovery.last_turn_start_was_recovery_continue = false;
+                            handle_op(
+                                &thread_id,
+                                Op::UserInput {
+                                    items: vec![CodexUserInput::Text {
+                                        text,
+                                        text_elements: Vec::new(),
+                                    }],
+                                    final_output_json_schema: output_schema.clone(),
+                                },
+                                stdin.as_mut().context("codex app-server stdin unavailable")?,
+                                &mut lines,
+                                &mut next_id,
+                                &mut recovery,
+                                event_tx,
+                            )
+                            .await?;
+                        }
+                        RecoveryAction::ApprovalAnswered { pending, answer } => {
+                            let PendingApproval {
+                                request_id,
+                                server_request,
+                                approval_request,
+                            } = *pending;
+                            let verdict = recovery
+                                .approvals
+                                .record_answer(&approval_request, answer.as_ref());
+                            let choice = match answer.map(|answer| answer.decision) {
+                                Some(PotterApprovalChoice::ApproveForSession) => {
+                                    PotterApprovalChoice::ApproveForSession
+                                }
+                                _ if verdict.approved() => PotterApprovalChoice::Approve,
+                                _ => PotterApprovalChoice::Deny,
+                            };
+                            answer_approval_request(
+                                stdin.as_mut().context("codex app-server stdin unavailable")?,
+                                request_id,
+                                &server_request,
+                                &approval_request,
+                                verdict,
+                                choice,
+                                event_tx,
+                            )
+                            .await?;
+                        }
+                        RecoveryAction::UserInputAnswered { pending, answers } => {
+                            answer_user_input_request(
+                                stdin.as_mut().context("codex app-server stdin unavailable")?,
+                                pending.request_id,
+                                &pending.request,
+                                answers.as_ref(),
+                                event_tx,
+                            )
+                            .await?;
+                        }
                     }
                 }
                 maybe_line = lines.next_line() => {
@@ -403,45 +581,47 @@ async fn run_app_server_backend_inner(
             }
         }
 
-        let _ = child.wait().await;
+        if !keep_process {
+            let _ = child.wait().await;
+        }
         Ok::<(), anyhow::Error>(())
     }
     .await;
+    // Events that arrived after the round finished (or a round that ended with an error).
+    report_unsupported_events(&mut recovery, "", event_tx);
 
     if result.is_err() {
         // Do not await the drain task on failure: the child might keep running and we'd hang while
         // waiting for stderr to close. We already captured enough to provide context.
-        stderr_task.abort();
-    } else {
-        let _ = stderr_task.await;
-    }
-
-    result.map_err(|err| {
-        let stderr = {
-            let capture = match stderr_capture.lock() {
-                Ok(guard) => guard,
-                Err(err) => err.into_inner(),
-            };
-            String::from_utf8_lossy(&capture).to_string()
-        };
-
-        let stderr = stderr.trim_end_matches(['\n', '\r']).to_string();
-        if stderr.is_empty() {
-            return err;
-        }
-
-        let mut message = String::new();
-        message.push_str(&err.to_string());
-        message.push_str("\n\n");
-        message.push_str("app-server stderr:");
-        message.push('\n');
-        message.push_str(&stderr);
-        if stderr_truncated.load(Ordering::Relaxed) {
-            message.push('\n');
-            message.push_str("[stderr truncated]");
+        stderr.task.abort();
+        let tail = stderr.tail.lines();
+        if !tail.is_empty() || stderr_log.is_some() {
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg: EventMsg::PotterAppServerStderr {
+                    tail,
+                    log_path: stderr_log,
+                },
+            });
         }
-        anyhow::Error::msg(message)
-    })?;
+    } else if !keep_process {
+        let _ = (&mut stderr.task).await;
+    }
+    result?;
+
+    if keep_process && let (Some(pool), Some(stdin)) = (process_pool, stdin) {
+        stderr.finish_round();
+        pool.put(AppServerProcess {
+            key,
+            child,
+            stdin,
+            lines,
+            stderr,
+            next_id,
+            spawned_at,
+            startup,
+        });
+    }
 
     // If the backend finishes while the UI still expects it to be alive, ensure the UI can exit.
     if !shutdown_requested {
@@ -459,38 +639,255 @@ async fn run_app_server_backend_inner(
     Ok(())
 }
 
-async fn spawn_app_server(
-    codex_bin: &str,
-    launch: AppServerLaunchConfig,
-    upstream_cli_args: &crate::app_server::UpstreamCodexCliArgs,
-    codex_home: Option<&Path>,
-) -> anyhow::Result<(Child, ChildStdin, ChildStdout, ChildStderr)> {
-    let mut cmd = Command::new(codex_bin);
-    cmd.kill_on_drop(true);
+/// Identifies app-server processes that are interchangeable: same binary, arguments and
+/// `CODEX_HOME`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AppServerProcessKey {
+    codex_bin: String,
+    args: Vec<String>,
+    codex_home: Option<PathBuf>,
+}
 
-    if let Some(codex_home) = codex_home {
-        cmd.env("CODEX_HOME", codex_home);
+impl AppServerProcessKey {
+    fn new(
+        codex_bin: String,
+        launch: AppServerLaunchConfig,
+        upstream_cli_args: &crate::app_server::UpstreamCodexCliArgs,
+        codex_home: Option<PathBuf>,
+    ) -> Self {
+        let mut args = upstream_cli_args.to_upstream_codex_args();
+        if launch.bypass_approvals_and_sandbox {
+            args.push("--dangerously-bypass-approvals-and-sandbox".to_string());
+        }
+        if let Some(mode) = launch.spawn_sandbox {
+            args.push("--sandbox".to_string());
+            args.push(super::sandbox_mode_cli_arg(mode).to_string());
+        }
+        args.push("app-server".to_string());
+
+        Self {
+            codex_bin,
+            args,
+            codex_home,
+        }
+    }
+}
+
+/// A running upstream `codex app-server` process.
+struct AppServerProcess {
+    key: AppServerProcessKey,
+    child: Child,
+    stdin: ChildStdin,
+    lines: tokio::io::Lines<BufReader<ChildStdout>>,
+    stderr: StderrCapture,
+    next_id: i64,
+    spawned_at: Instant,
+    /// Time spent spawning and initializing the process; `None` until `initialize` succeeded.
+    startup: Option<Duration>,
+}
+
+/// Idle upstream `codex app-server` processes kept alive between rounds (`--reuse-app-server`).
+///
+/// A round takes a matching process (spawning one when there is none), starts a new thread on it,
+/// and puts it back when the round ends without a turn in flight. Processes that exited in the
+/// meantime are dropped, so the next round transparently respawns.
+#[derive(Clone, Default)]
+pub struct AppServerPool {
+    idle: Arc<Mutex<Vec<AppServerProcess>>>,
+}
+
+impl std::fmt::Debug for AppServerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let idle = match self.idle.lock() {
+            Ok(guard) => guard.len(),
+            Err(err) => err.into_inner().len(),
+        };
+        f.debug_struct("AppServerPool")
+            .field("idle", &idle)
+            .finish()
+    }
+}
+
+impl AppServerPool {
+    fn take(&self, key: &AppServerProcessKey) -> Option<AppServerProcess> {
+        let mut idle = match self.idle.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        };
+        idle.retain_mut(|process| matches!(process.child.try_wait(), Ok(None)));
+        let index = idle.iter().position(|process| &process.key == key)?;
+        Some(idle.swap_remove(index))
+    }
+
+    fn put(&self, process: AppServerProcess) {
+        let mut idle = match self.idle.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        };
+        idle.push(process);
+    }
+}
+
+/// Number of stderr lines attached to the error that ends a round.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Drains an app-server's stderr, keeping its most recent output and teeing it to the current
+/// round's log file.
+struct StderrCapture {
+    tail: StderrTail,
+    log_file: Arc<Mutex<Option<std::fs::File>>>,
+    task: tokio::task::JoinHandle<std::io::Result<()>>,
+}
+
+impl StderrCapture {
+    fn spawn(stderr: ChildStderr) -> Self {
+        let tail = StderrTail::default();
+        let log_file = Arc::new(Mutex::new(None::<std::fs::File>));
+        let task = {
+            let tail = tail.clone();
+            let log_file = log_file.clone();
+            tokio::spawn(async move {
+                let mut stderr = stderr;
+                let mut buf = [0u8; 4096];
+
+                loop {
+                    let n = stderr.read(&mut buf).await?;
+                    if n == 0 {
+                        break;
+                    }
+
+                    let mut log_file = match log_file.lock() {
+                        Ok(guard) => guard,
+                        Err(err) => err.into_inner(),
+                    };
+                    // Stop logging on the first write error instead of failing on every chunk.
+                    if let Some(file) = log_file.as_mut()
+                        && file.write_all(&buf[..n]).is_err()
+                    {
+                        *log_file = None;
+                    }
+                    drop(log_file);
+
+                    tail.push(&buf[..n]);
+                }
+
+                Ok::<(), std::io::Error>(())
+            })
+        };
+
+        Self {
+            tail,
+            log_file,
+            task,
+        }
     }
 
-    for arg in upstream_cli_args.to_upstream_codex_args() {
-        cmd.arg(arg);
+    /// Forget the output of previous rounds and tee new output to `log_path`.
+    fn start_round(&self, log_path: Option<&Path>) -> anyhow::Result<()> {
+        self.tail.clear();
+        let file = match log_path {
+            Some(log_path) => {
+                if let Some(parent) = log_path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("create {}", parent.display()))?;
+                }
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(log_path)
+                    .with_context(|| format!("open {}", log_path.display()))?;
+                Some(file)
+            }
+            None => None,
+        };
+        *self.log_file_guard() = file;
+        Ok(())
     }
 
-    if launch.bypass_approvals_and_sandbox {
-        cmd.arg("--dangerously-bypass-approvals-and-sandbox");
+    /// Stop teeing output to the round's log file (the process goes back to the pool).
+    fn finish_round(&self) {
+        *self.log_file_guard() = None;
     }
-    if let Some(mode) = launch.spawn_sandbox {
-        cmd.arg("--sandbox");
-        cmd.arg(super::sandbox_mode_cli_arg(mode));
+
+    fn log_file_guard(&self) -> std::sync::MutexGuard<'_, Option<std::fs::File>> {
+        match self.log_file.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        }
+    }
+}
+
+/// Most recent stderr output of an app-server (bounded), shared with the event handler.
+#[derive(Clone, Default)]
+struct StderrTail {
+    buffer: Arc<Mutex<StderrTailBuffer>>,
+}
+
+#[derive(Default)]
+struct StderrTailBuffer {
+    bytes: Vec<u8>,
+    /// Whether older output was dropped, which leaves the first line incomplete.
+    trimmed: bool,
+}
+
+impl StderrTail {
+    const LIMIT_BYTES: usize = 8 * 1024;
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StderrTailBuffer> {
+        match self.buffer.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        }
+    }
+
+    fn push(&self, chunk: &[u8]) {
+        let mut buffer = self.lock();
+        buffer.bytes.extend_from_slice(chunk);
+        if buffer.bytes.len() > Self::LIMIT_BYTES {
+            let excess = buffer.bytes.len() - Self::LIMIT_BYTES;
+            buffer.bytes.drain(..excess);
+            buffer.trimmed = true;
+        }
+    }
+
+    fn clear(&self) {
+        let mut buffer = self.lock();
+        buffer.bytes.clear();
+        buffer.trimmed = false;
+    }
+
+    /// The last [`STDERR_TAIL_LINES`] non-blank lines.
+    fn lines(&self) -> Vec<String> {
+        let buffer = self.lock();
+        let text = String::from_utf8_lossy(&buffer.bytes);
+        let lines = text
+            .lines()
+            .skip(usize::from(buffer.trimmed))
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>();
+        lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+}
+
+async fn spawn_app_server(key: AppServerProcessKey) -> anyhow::Result<AppServerProcess> {
+    let spawned_at = Instant::now();
+    let mut cmd = Command::new(&key.codex_bin);
+    cmd.kill_on_drop(true);
+
+    if let Some(codex_home) = &key.codex_home {
+        cmd.env("CODEX_HOME", codex_home);
     }
 
     let mut child = cmd
-        .arg("app-server")
+        .args(&key.args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .with_context(|| format!("failed to start `{codex_bin}` app-server"))?;
+        .with_context(|| format!("failed to start `{}` app-server", key.codex_bin))?;
 
     let stdin = child
         .stdin
@@ -504,7 +901,16 @@ async fn spawn_app_server(
         .stderr
         .take()
         .context("codex app-server stderr unavailable")?;
-    Ok((child, stdin, stdout, stderr))
+    Ok(AppServerProcess {
+        key,
+        child,
+        stdin,
+        lines: BufReader::new(stdout).lines(),
+        stderr: StderrCapture::spawn(stderr),
+        next_id: 1,
+        spawned_at,
+        startup: None,
+    })
 }
 
 async fn initialize_app_server(
@@ -526,7 +932,19 @@ async fn initialize_app_server(
         },
     };
     send_message(stdin, &request).await?;
-    let _response = read_until_response(stdin, lines, request_id, recovery, event_tx).await?;
+    let response = read_until_response(stdin, lines, request_id, recovery, event_tx).await?;
+    let response: InitializeResponse =
+        serde_json::from_value(response.result).context("decode initialize response")?;
+    if let Some(message) = response
+        .user_agent
+        .as_deref()
+        .and_then(upstream_version::app_server_version_warning_once)
+    {
+        let _ = event_tx.send(Event {
+            id: "".to_string(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        });
+    }
 
     send_message(stdin, &ClientNotification::Initialized).await?;
     Ok(())
@@ -534,16 +952,20 @@ async fn initialize_app_server(
 
 struct ThreadStartSettings {
     model: Option<String>,
+    reasoning_effort: Option<ReasoningEffort>,
     developer_instructions: Option<String>,
     sandbox_mode: Option<crate::app_server::upstream_protocol::SandboxMode>,
+    approval_policy: crate::app_server::upstream_protocol::AskForApproval,
     cwd: Option<PathBuf>,
 }
 
 struct ThreadResumeSettings {
     thread_id: ThreadId,
     model: Option<String>,
+    reasoning_effort: Option<ReasoningEffort>,
     developer_instructions: Option<String>,
     sandbox_mode: Option<crate::app_server::upstream_protocol::SandboxMode>,
+    approval_policy: crate::app_server::upstream_protocol::AskForApproval,
     cwd: Option<PathBuf>,
 }
 
@@ -553,9 +975,9 @@ impl ThreadStartSettings {
             model: self.model,
             model_provider: None,
             cwd: self.cwd.map(|cwd| cwd.to_string_lossy().to_string()),
-            approval_policy: Some(crate::app_server::upstream_protocol::AskForApproval::Never),
+            approval_policy: Some(self.approval_policy),
             sandbox: self.sandbox_mode,
-            config: None,
+            config: reasoning_effort_config(self.reasoning_effort),
             base_instructions: None,
             developer_instructions: self.developer_instructions,
             experimental_raw_events: false,
@@ -570,15 +992,27 @@ impl ThreadResumeSettings {
             model: self.model,
             model_provider: None,
             cwd: self.cwd.map(|cwd| cwd.to_string_lossy().to_string()),
-            approval_policy: Some(crate::app_server::upstream_protocol::AskForApproval::Never),
+            approval_policy: Some(self.approval_policy),
             sandbox: self.sandbox_mode,
-            config: None,
+            config: reasoning_effort_config(self.reasoning_effort),
             base_instructions: None,
             developer_instructions: self.developer_instructions,
         }
     }
 }
 
+/// `thread/*` config overrides selecting `effort` (`model_reasoning_effort`).
+fn reasoning_effort_config(
+    effort: Option<ReasoningEffort>,
+) -> Option<HashMap<String, serde_json::Value>> {
+    effort.map(|effort| {
+        HashMap::from([(
+            "model_reasoning_effort".to_string(),
+            serde_json::Value::String(effort.to_string()),
+        )])
+    })
+}
+
 async fn thread_start(
     stdin: &mut ChildStdin,
     lines: &mut tokio::io::Lines<BufReader<ChildStdout>>,
@@ -714,6 +1148,11 @@ async fn handle_op(
             // backend. Ignore the request so the UI can stay simple.
             Ok(())
         }
+        Op::PotterApprovalResponse { .. } | Op::PotterUserInputResponse { .. } => {
+            // Approval and user input answers reach the backend through `ApprovalAsker` /
+            // `UserInputAsker`, not the op channel.
+            Ok(())
+        }
     }
 }
 
@@ -734,7 +1173,7 @@ async fn handle_app_server_message(
         }
         JSONRPCMessage::Request(request) => {
             if let Some(stdin) = stdin.as_mut() {
-                handle_server_request(stdin, request).await?;
+                handle_server_request(stdin, request, recovery, event_tx).await?;
             }
         }
         JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => {}
@@ -755,12 +1194,61 @@ fn handle_codex_event_notification(
     let Some(params) = params else {
         return Ok(());
     };
+    if let (Some(thread_id), Some(conversation_id)) = (
+        recovery.thread_id.as_deref(),
+        params
+            .get("conversationId")
+            .and_then(serde_json::Value::as_str),
+    ) && conversation_id != thread_id
+    {
+        return Ok(());
+    }
 
-    let event: Event = serde_json::from_value(params)?;
+    let event = Event::deserialize(&params)?;
+    if matches!(event.msg, EventMsg::Unknown) {
+        let event_type = params
+            .get("msg")
+            .and_then(|msg| msg.get("type"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("<missing type>");
+        *recovery
+            .unsupported_events
+            .entry(event_type.to_string())
+            .or_default() += 1;
+        return Ok(());
+    }
     handle_codex_event(event, recovery, event_tx);
     Ok(())
 }
 
+/// Warn about the upstream events of this round the protocol mirror could not decode.
+fn report_unsupported_events(
+    recovery: &mut StreamRecoveryContext,
+    event_id: &str,
+    event_tx: &UnboundedSender<Event>,
+) {
+    let unsupported_events = std::mem::take(&mut recovery.unsupported_events);
+    if unsupported_events.is_empty() {
+        return;
+    }
+    let total = unsupported_events.values().sum::<usize>();
+    let summary = unsupported_events
+        .iter()
+        .map(|(event_type, count)| format!("{event_type} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = event_tx.send(Event {
+        id: event_id.to_string(),
+        msg: EventMsg::Warning(WarningEvent {
+            message: format!(
+                "ignored {total} upstream event(s) this codex-potter does not support: {summary}; \
+                 it supports codex {}",
+                upstream_version::supported_codex_versions()
+            ),
+        }),
+    });
+}
+
 fn handle_codex_event(
     event: Event,
     recovery: &mut StreamRecoveryContext,
@@ -776,6 +1264,17 @@ fn handle_codex_event(
         return;
     }
 
+    // User input requests are answered through the matching server request (see
+    // `handle_server_request`); the raw events would only duplicate them.
+    if matches!(
+        &event.msg,
+        EventMsg::RequestUserInput(_) | EventMsg::ElicitationRequest(_)
+    ) {
+        return;
+    }
+
+    recovery.approvals.observe_event(&event.msg);
+
     match &event.msg {
         EventMsg::TurnStarted(ev) if !ev.turn_id.is_empty() => {
             recovery.active_turn_id = Some(ev.turn_id.clone());
@@ -831,7 +1330,7 @@ fn handle_codex_event(
         && recovery.has_sent_turn_start
     {
         if recovery.pending_continue_retry.is_some()
-            && codex_protocol::potter_stream_recovery::is_retryable_stream_error(err)
+            && recovery.stream_recovery.is_recoverable_error(err)
         {
             // A retryable error was already observed for the current turn. Wait for TurnComplete
             // and then issue the planned automatic `Continue`.
@@ -849,6 +1348,8 @@ fn handle_codex_event(
                         attempt: plan.attempt,
                         max_attempts: plan.max_attempts,
                         error_message: err.message.clone(),
+                        backoff: plan.backoff,
+                        strategy: plan.strategy,
                     };
                     match recovery.event_mode {
                         AppServerEventMode::Interactive => pre_forward_events.push(msg),
@@ -886,6 +1387,7 @@ fn handle_codex_event(
 
     if matches!(&event.msg, EventMsg::TurnAborted(_)) {
         recovery.pending_continue_retry = None;
+        recovery.approvals.clear_declined();
     }
 
     if matches!(&event.msg, EventMsg::TurnComplete(_))
@@ -909,9 +1411,21 @@ fn handle_codex_event(
         should_forward = false;
     }
 
+    // Report declined approval requests in a follow-up turn before the round can complete.
+    let reports_declined_approvals = turn_complete_counts_for_round_completion
+        && recovery.pending_continue_retry.is_none()
+        && recovery.approvals.has_follow_up();
+    if reports_declined_approvals {
+        let _ = recovery
+            .recovery_action_tx
+            .send(RecoveryAction::ReportDeclinedApprovals);
+    }
+
     if round_outcome.is_none() {
         round_outcome = match &event.msg {
-            EventMsg::TurnComplete(_) if turn_complete_counts_for_round_completion => {
+            EventMsg::TurnComplete(_)
+                if turn_complete_counts_for_round_completion && !reports_declined_approvals =>
+            {
                 Some(PotterRoundOutcome::Completed)
             }
             EventMsg::TurnAborted(ev) => match ev.reason {
@@ -924,8 +1438,17 @@ fn handle_codex_event(
                 codex_protocol::protocol::TurnAbortReason::Replaced => None,
             },
             EventMsg::Error(err) if should_forward && !error_is_recoverable => {
+                let stderr_tail = recovery.stderr_tail.lines();
+                if !stderr_tail.is_empty() || recovery.stderr_log.is_some() {
+                    pre_forward_events.push(EventMsg::PotterAppServerStderr {
+                        tail: stderr_tail.clone(),
+                        log_path: recovery.stderr_log.clone(),
+                    });
+                }
                 Some(PotterRoundOutcome::Fatal {
                     message: err.message.clone(),
+                    stderr_tail,
+                    stderr_log: recovery.stderr_log.clone(),
                 })
             }
             _ => None,
@@ -952,6 +1475,7 @@ fn handle_codex_event(
         && let Some(outcome) = round_outcome
     {
         recovery.has_finished_round = true;
+        report_unsupported_events(recovery, &event_id, event_tx);
         let _ = event_tx.send(Event {
             id: event_id,
             msg: EventMsg::PotterRoundFinished { outcome },
@@ -962,9 +1486,57 @@ fn handle_codex_event(
 async fn handle_server_request(
     stdin: &mut ChildStdin,
     request: crate::app_server::upstream_protocol::JSONRPCRequest,
+    recovery: &mut StreamRecoveryContext,
+    event_tx: &UnboundedSender<Event>,
 ) -> anyhow::Result<()> {
     let request_id = request.id.clone();
     let method = request.method.clone();
+    if let Some(user_input_request) =
+        UserInputRequest::from_server_request(&method, request.params.as_ref())
+    {
+        let user_input_request = match user_input_request {
+            Ok(user_input_request) => user_input_request,
+            Err(err) => {
+                send_message(
+                    stdin,
+                    &JSONRPCMessage::Error(JSONRPCError {
+                        error: JSONRPCErrorError {
+                            code: -32602,
+                            message: format!("invalid {method:?} params: {err:#}"),
+                            data: None,
+                        },
+                        id: request_id,
+                    }),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+        let Some(asker) = recovery.user_input_asker.clone() else {
+            return answer_user_input_request(
+                stdin,
+                request_id,
+                &user_input_request,
+                None,
+                event_tx,
+            )
+            .await;
+        };
+        // Keep streaming events while the user answers; the answer comes back as an action.
+        let action_tx = recovery.recovery_action_tx.clone();
+        tokio::spawn(async move {
+            let answers = asker.ask(&user_input_request).await;
+            let _ = action_tx.send(RecoveryAction::UserInputAnswered {
+                pending: Box::new(PendingUserInput {
+                    request_id,
+                    request: user_input_request,
+                }),
+                answers,
+            });
+        });
+        return Ok(());
+    }
+
     let server_request = match ServerRequest::try_from(request) {
         Ok(request) => request,
         Err(err) => {
@@ -985,28 +1557,98 @@ async fn handle_server_request(
         }
     };
 
+    let approval_request = recovery.approvals.request_for(&server_request);
+    let verdict = recovery.approvals.decide(&approval_request);
+    if verdict.action == ApprovalAction::Ask
+        && let Some(asker) = recovery.approvals.asker()
+    {
+        // Keep streaming events while the user decides; the answer comes back as an action.
+        let action_tx = recovery.recovery_action_tx.clone();
+        tokio::spawn(async move {
+            let answer = asker.ask(&approval_request, verdict.reason).await;
+            let _ = action_tx.send(RecoveryAction::ApprovalAnswered {
+                pending: Box::new(PendingApproval {
+                    request_id,
+                    server_request,
+                    approval_request,
+                }),
+                answer,
+            });
+        });
+        return Ok(());
+    }
+
+    let choice = if verdict.approved() {
+        PotterApprovalChoice::Approve
+    } else {
+        PotterApprovalChoice::Deny
+    };
+    answer_approval_request(
+        stdin,
+        request_id,
+        &server_request,
+        &approval_request,
+        verdict,
+        choice,
+        event_tx,
+    )
+    .await
+}
+
+/// Report the decision for an upstream approval request and send the matching response.
+async fn answer_approval_request(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    server_request: &ServerRequest,
+    approval_request: &ApprovalRequest,
+    verdict: ApprovalVerdict,
+    choice: PotterApprovalChoice,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterApprovalDecision {
+            kind: approval_request.kind(),
+            summary: approval_request.summary(),
+            approved: verdict.approved(),
+            reason: verdict.reason,
+        },
+    });
+
     match server_request {
         ServerRequest::CommandExecution { .. } => {
             let response = CommandExecutionRequestApprovalResponse {
-                decision: CommandExecutionApprovalDecision::Accept,
+                decision: match choice {
+                    PotterApprovalChoice::Approve => CommandExecutionApprovalDecision::Accept,
+                    PotterApprovalChoice::ApproveForSession => {
+                        CommandExecutionApprovalDecision::AcceptForSession
+                    }
+                    PotterApprovalChoice::Deny => CommandExecutionApprovalDecision::Decline,
+                },
             };
             send_response(stdin, request_id, response).await?;
         }
         ServerRequest::FileChange { .. } => {
             let response = FileChangeRequestApprovalResponse {
-                decision: FileChangeApprovalDecision::Accept,
+                decision: match choice {
+                    PotterApprovalChoice::Approve => FileChangeApprovalDecision::Accept,
+                    PotterApprovalChoice::ApproveForSession => {
+                        FileChangeApprovalDecision::AcceptForSession
+                    }
+                    PotterApprovalChoice::Deny => FileChangeApprovalDecision::Decline,
+                },
             };
             send_response(stdin, request_id, response).await?;
         }
         ServerRequest::ApplyPatch { .. } => {
             let response = ApplyPatchApprovalResponse {
-                decision: ReviewDecision::Approved,
+                decision: review_decision(choice),
             };
             send_response(stdin, request_id, response).await?;
         }
         ServerRequest::ExecCommand { .. } => {
             let response = ExecCommandApprovalResponse {
-                decision: ReviewDecision::Approved,
+                decision: review_decision(choice),
             };
             send_response(stdin, request_id, response).await?;
         }
@@ -1015,6 +1657,29 @@ async fn handle_server_request(
     Ok(())
 }
 
+/// Report how a user input request was answered and send the upstream response; `None` declines.
+async fn answer_user_input_request(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    request: &UserInputRequest,
+    answers: Option<&HashMap<String, RequestUserInputAnswer>>,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: request.answered_event(answers),
+    });
+    send_response(stdin, request_id, request.response(answers)).await
+}
+
+fn review_decision(choice: PotterApprovalChoice) -> ReviewDecision {
+    match choice {
+        PotterApprovalChoice::Approve => ReviewDecision::Approved,
+        PotterApprovalChoice::ApproveForSession => ReviewDecision::ApprovedForSession,
+        PotterApprovalChoice::Deny => ReviewDecision::Denied,
+    }
+}
+
 async fn send_message<T>(stdin: &mut ChildStdin, message: &T) -> anyhow::Result<()>
 where
     T: serde::Serialize,
@@ -1087,7 +1752,7 @@ async fn read_until_response_or_error(
                 )?;
             }
             JSONRPCMessage::Request(request) => {
-                handle_server_request(stdin, request).await?;
+                handle_server_request(stdin, request, recovery, event_tx).await?;
             }
             _ => {}
         }
@@ -1201,6 +1866,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1217,12 +1888,15 @@ mod stream_recovery_tests {
             attempt,
             max_attempts,
             error_message,
+            backoff,
+            strategy: _,
         } = event.msg
         else {
             panic!("expected PotterStreamRecoveryUpdate, got: {:?}", event.msg);
         };
         assert_eq!(attempt, 1);
         assert_eq!(max_attempts, 10);
+        assert_eq!(backoff, Duration::ZERO);
         assert_eq!(
             error_message,
             "stream disconnected before completion: error sending request for url (...)"
@@ -1295,6 +1969,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1332,6 +2012,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         let err = retryable_error_event();
@@ -1394,6 +2080,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1425,6 +2117,81 @@ mod stream_recovery_tests {
         assert!(recovery.has_finished_round, "round should end as fatal");
     }
 
+    #[test]
+    fn unsupported_upstream_events_are_reported_when_the_round_finishes() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, _action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            active_turn_id: None,
+            has_sent_turn_start: true,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
+        };
+
+        for event_type in ["future_event", "other_future_event", "future_event"] {
+            handle_codex_event_notification(
+                "codex/event/test",
+                Some(serde_json::json!({
+                    "id": "1",
+                    "msg": { "type": event_type, "payload": 1 },
+                })),
+                &mut recovery,
+                &event_tx,
+            )
+            .expect("handle unsupported event");
+        }
+        assert!(
+            event_rx.try_recv().is_err(),
+            "unsupported events should not be forwarded"
+        );
+
+        handle_codex_event_notification(
+            "codex/event/test",
+            Some(serde_json::json!({
+                "id": "2",
+                "msg": { "type": "turn_complete", "last_agent_message": null },
+            })),
+            &mut recovery,
+            &event_tx,
+        )
+        .expect("handle turn complete");
+
+        let mut events = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            events.push(event.msg);
+        }
+        assert_eq!(events.len(), 3, "unexpected events: {events:?}");
+        assert!(matches!(events[0], EventMsg::TurnComplete(_)));
+        let EventMsg::Warning(warning) = &events[1] else {
+            panic!("expected unsupported events warning, got {:?}", events[1]);
+        };
+        assert_eq!(
+            warning.message,
+            format!(
+                "ignored 3 upstream event(s) this codex-potter does not support: \
+                 future_event (2), other_future_event (1); it supports codex {}",
+                upstream_version::supported_codex_versions()
+            )
+        );
+        assert!(matches!(
+            events[2],
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::Completed
+            }
+        ));
+        assert!(recovery.unsupported_events.is_empty());
+    }
+
     #[test]
     fn thread_rolled_back_event_is_suppressed() {
         let (event_tx, mut event_rx) = unbounded_channel::<Event>();
@@ -1438,6 +2205,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1473,6 +2246,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::ExecJson,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1512,6 +2291,12 @@ mod stream_recovery_tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::ExecJson,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1603,17 +2388,64 @@ mod tests {
     use tokio::time::Duration;
     use tokio::time::timeout;
 
+    #[test]
+    fn stderr_tail_keeps_the_last_complete_lines() {
+        let tail = StderrTail::default();
+        tail.push(b"first\n\n");
+        tail.push(b"second\nthi");
+        tail.push(b"rd\n");
+        assert_eq!(tail.lines(), vec!["first", "second", "third"]);
+
+        for i in 0..25 {
+            tail.push(format!("line {i}\n").as_bytes());
+        }
+        let lines = tail.lines();
+        assert_eq!(lines.len(), STDERR_TAIL_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("line 5"));
+
+        tail.push(&vec![b'x'; StderrTail::LIMIT_BYTES]);
+        tail.push(b"\nlast\n");
+        assert_eq!(tail.lines(), vec!["last"]);
+
+        tail.clear();
+        assert_eq!(tail.lines(), Vec::<String>::new());
+    }
+
     #[test]
     fn thread_start_settings_into_params_preserves_model_override() {
         let params = ThreadStartSettings {
             model: Some("o3".to_string()),
+            reasoning_effort: None,
             developer_instructions: None,
             sandbox_mode: None,
+            approval_policy: crate::app_server::upstream_protocol::AskForApproval::Never,
             cwd: None,
         }
         .into_params();
 
         assert_eq!(params.model.as_deref(), Some("o3"));
+        assert_eq!(params.config, None);
+    }
+
+    #[test]
+    fn thread_start_settings_into_params_sets_reasoning_effort_config() {
+        let params = ThreadStartSettings {
+            model: None,
+            reasoning_effort: Some(ReasoningEffort::High),
+            developer_instructions: None,
+            sandbox_mode: None,
+            approval_policy: crate::app_server::upstream_protocol::AskForApproval::Never,
+            cwd: None,
+        }
+        .into_params();
+
+        assert_eq!(
+            params.config,
+            Some(HashMap::from([(
+                "model_reasoning_effort".to_string(),
+                serde_json::Value::String("high".to_string()),
+            )]))
+        );
     }
 
     #[test]
@@ -1623,8 +2455,10 @@ mod tests {
         let params = ThreadResumeSettings {
             thread_id,
             model: Some("o3".to_string()),
+            reasoning_effort: None,
             developer_instructions: None,
             sandbox_mode: None,
+            approval_policy: crate::app_server::upstream_protocol::AskForApproval::Never,
             cwd: None,
         }
         .into_params();
@@ -1647,6 +2481,12 @@ mod tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1678,6 +2518,12 @@ mod tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1709,6 +2555,12 @@ mod tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1740,6 +2592,12 @@ mod tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1771,6 +2629,12 @@ mod tests {
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
             event_mode: AppServerEventMode::Interactive,
+            approvals: ApprovalSession::default(),
+            user_input_asker: None,
+            stderr_tail: StderrTail::default(),
+            stderr_log: None,
+            unsupported_events: BTreeMap::new(),
+            thread_id: None,
         };
 
         handle_codex_event(
@@ -1861,10 +2725,17 @@ done
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2013,10 +2884,17 @@ done
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2191,10 +3069,17 @@ done
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2344,10 +3229,17 @@ done
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2494,10 +3386,17 @@ touch "$MARKER"
                         bypass_approvals_and_sandbox: true,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2511,6 +3410,112 @@ touch "$MARKER"
         assert!(marker.exists(), "dummy server did not observe stdin EOF");
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_reuses_pooled_app_server_for_next_round() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let spawns = temp.path().join("spawns");
+
+        let thread_start_response = |id: u32| {
+            format!(
+                r#"{{"id":{id},"result":{{"thread":{{"id":"00000000-0000-0000-0000-00000000000{id}","preview":"","modelProvider":"test-provider","createdAt":0,"updatedAt":0,"path":"rollout.jsonl","cwd":"project","cliVersion":"0.0.0","source":"appServer","gitInfo":null,"turns":[]}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}}}"#
+            )
+        };
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+echo spawned >> "{spawns}"
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+# thread/start request of the first round
+IFS= read -r _line
+echo '{first}'
+
+# thread/start request of the second round, on the same process
+IFS= read -r _line
+echo '{second}'
+
+while IFS= read -r _line; do
+  :
+done
+"#,
+            spawns = spawns.display(),
+            first = thread_start_response(2),
+            second = thread_start_response(3),
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let pool = AppServerPool::default();
+        let mut reused = Vec::new();
+        for _ in 0..2 {
+            let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+            let (fatal_exit_tx, _fatal_exit_rx) = unbounded_channel::<String>();
+            let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+            drop(op_tx);
+
+            timeout(
+                Duration::from_secs(5),
+                run_app_server_backend_inner(
+                    AppServerBackendConfig {
+                        codex_bin: codex_bin.display().to_string(),
+                        developer_instructions: None,
+                        launch: AppServerLaunchConfig {
+                            spawn_sandbox: None,
+                            thread_sandbox: None,
+                            bypass_approvals_and_sandbox: false,
+                        },
+                        upstream_cli_args: Default::default(),
+                        reasoning_effort: None,
+                        codex_home: None,
+                        thread_cwd: None,
+                        resume_thread_id: None,
+                        event_mode: AppServerEventMode::Interactive,
+                        approval_policy: ApprovalPolicy::default(),
+                        approval_asker: None,
+                        user_input_asker: None,
+                        stream_recovery: StreamRecoveryPolicy::default(),
+                        process_pool: Some(pool.clone()),
+                        stderr_log: None,
+                    },
+                    &mut op_rx,
+                    &event_tx,
+                    &fatal_exit_tx,
+                ),
+            )
+            .await
+            .expect("backend timed out")
+            .expect("backend failed");
+
+            let mut round_reused = false;
+            while let Ok(event) = event_rx.try_recv() {
+                round_reused |= matches!(event.msg, EventMsg::PotterAppServerReused { .. });
+            }
+            reused.push(round_reused);
+        }
+
+        assert_eq!(reused, vec![false, true]);
+        assert_eq!(
+            std::fs::read_to_string(&spawns).expect("read spawns"),
+            "spawned\n"
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn backend_exits_when_op_channel_is_closed_workspace_write() {
@@ -2596,10 +3601,17 @@ touch "$MARKER"
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2683,10 +3695,17 @@ touch "$MARKER"
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2779,10 +3798,17 @@ touch "$MARKER"
                         bypass_approvals_and_sandbox: false,
                     },
                     upstream_cli_args: Default::default(),
+                    reasoning_effort: None,
                     codex_home: Some(codex_home),
                     thread_cwd: None,
                     resume_thread_id: None,
                     event_mode: AppServerEventMode::Interactive,
+                    approval_policy: ApprovalPolicy::default(),
+                    approval_asker: None,
+                    user_input_asker: None,
+                    stream_recovery: StreamRecoveryPolicy::default(),
+                    process_pool: None,
+                    stderr_log: None,
                 },
                 &mut op_rx,
                 &event_tx,
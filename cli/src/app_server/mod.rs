@@ -1,18 +1,23 @@
 //! App-server related modules.
 //!
 //! This contains both:
-//! - the upstream `codex app-server` backend driver (one process per round)
+//! - the upstream `codex app-server` backend driver (one process per round, or one process
+//!   reused across rounds)
 //! - the long-lived `codex-potter app-server` implementation (project control plane)
 
+pub mod approval_policy;
 pub mod codex_backend;
 pub mod potter;
 pub mod stream_recovery;
 pub mod upstream_cli_args;
 pub mod upstream_protocol;
+pub mod upstream_version;
+pub mod user_input;
 
 pub use codex_backend::AppServerBackendConfig;
 pub use codex_backend::AppServerEventMode;
 pub use codex_backend::AppServerLaunchConfig;
+pub use codex_backend::AppServerPool;
 pub use codex_backend::run_app_server_backend;
 pub use upstream_cli_args::UpstreamCodexCliArgs;
 
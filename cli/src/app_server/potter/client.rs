@@ -1,14 +1,22 @@
 //! Client for `codex-potter app-server`.
 //!
 //! This client spawns the current `codex-potter` executable in `app-server` mode and speaks a
-//! small JSON-RPC protocol over stdin/stdout. It is used by:
+//! small JSON-RPC protocol over stdin/stdout, or connects to a server started with
+//! `app-server --listen` ([`PotterAppServerClient::connect`]). It is used by:
 //!
 //! - interactive CLI sessions (`codex-potter` default + `resume`)
 //! - non-interactive automation (`codex-potter exec --json`)
+//! - `codex-potter attach` (socket connection)
 //!
 //! The server is responsible for project-level orchestration; this client is intentionally thin
 //! and does not interpret `EventMsg` semantics beyond buffering and forwarding.
+//!
+//! Server → client `approval/request` calls are surfaced as `EventMsg::PotterApprovalRequest`
+//! events in the same stream and answered with [`PotterAppServerClient::answer_approval`];
+//! `userInput/request` calls likewise become `EventMsg::PotterUserInputRequest` events answered
+//! with [`PotterAppServerClient::answer_user_input`].
 
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -16,22 +24,34 @@ use std::process::Stdio;
 use anyhow::Context;
 use codex_protocol::protocol::Event;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
-use tokio::process::ChildStdin;
-use tokio::process::ChildStdout;
 use tokio::process::Command;
 
 use crate::app_server::upstream_protocol::ClientInfo;
-use crate::app_server::upstream_protocol::InitializeParams;
 use crate::app_server::upstream_protocol::JSONRPCMessage;
+use crate::app_server::upstream_protocol::JSONRPCRequest;
+use crate::app_server::upstream_protocol::JSONRPCResponse;
 use crate::app_server::upstream_protocol::RequestId;
 use crate::app_server::upstream_protocol::Result as JsonRpcResult;
 
+use super::protocol::ApprovalRequestParams;
+use super::protocol::ApprovalRequestResponse;
+use super::protocol::POTTER_APPROVAL_REQUEST_METHOD;
 use super::protocol::POTTER_EVENT_NOTIFICATION_METHOD;
+use super::protocol::POTTER_USER_INPUT_REQUEST_METHOD;
 use super::protocol::PotterAppServerClientNotification;
 use super::protocol::PotterAppServerClientRequest;
+use super::protocol::PotterInitializeParams;
+use super::protocol::PotterProjectEvent;
+use super::protocol::ProjectAttachParams;
+use super::protocol::ProjectAttachResponse;
+use super::protocol::ProjectDetachParams;
+use super::protocol::ProjectGetParams;
+use super::protocol::ProjectGetResponse;
 use super::protocol::ProjectInterruptParams;
 use super::protocol::ProjectListParams;
 use super::protocol::ProjectListResponse;
@@ -43,12 +63,56 @@ use super::protocol::ProjectStartParams;
 use super::protocol::ProjectStartResponse;
 use super::protocol::ProjectStartRoundsParams;
 use super::protocol::ProjectStartRoundsResponse;
+use super::protocol::ProjectStatusParams;
+use super::protocol::ProjectStatusResponse;
+use super::protocol::QueueAddParams;
+use super::protocol::QueueAddResponse;
+use super::protocol::QueueListParams;
+use super::protocol::QueueListResponse;
+use super::protocol::QueueRemoveParams;
+use super::protocol::QueueRemoveResponse;
+use super::protocol::QueueReorderParams;
+use super::protocol::QueueTakeParams;
+use super::protocol::QueueTakeResponse;
+use super::protocol::UserInputRequestParams;
+use super::protocol::UserInputRequestResponse;
+use super::transport::PotterAppServerListenAddress;
+
+/// Collects events streamed while a request is awaiting its JSON-RPC response.
+///
+/// `Vec<Event>` drops the project tag (single-project callers); `Vec<PotterProjectEvent>` keeps it
+/// so `--parallel` callers can demultiplex the buffered events.
+pub trait PotterEventBuffer {
+    fn push_event(&mut self, event: PotterProjectEvent);
+}
+
+impl PotterEventBuffer for Vec<Event> {
+    fn push_event(&mut self, event: PotterProjectEvent) {
+        self.push(event.event);
+    }
+}
+
+impl PotterEventBuffer for Vec<PotterProjectEvent> {
+    fn push_event(&mut self, event: PotterProjectEvent) {
+        self.push(event);
+    }
+}
+
+type ServerReader = Box<dyn AsyncRead + Unpin + Send>;
+type ServerWriter = Box<dyn AsyncWrite + Unpin + Send>;
 
 pub struct PotterAppServerClient {
-    child: Child,
-    stdin: Option<ChildStdin>,
-    stdout_lines: tokio::io::Lines<BufReader<ChildStdout>>,
+    /// The spawned server; `None` when connected to a listening one.
+    child: Option<Child>,
+    writer: Option<ServerWriter>,
+    lines: tokio::io::Lines<BufReader<ServerReader>>,
+    /// `--listen-token` sent with `initialize`.
+    token: Option<String>,
     next_id: i64,
+    /// Unanswered `approval/request` calls: approval id → JSON-RPC request id.
+    pending_approvals: HashMap<String, RequestId>,
+    /// Unanswered `userInput/request` calls: request id → JSON-RPC request id.
+    pending_user_inputs: HashMap<String, RequestId>,
 }
 
 impl PotterAppServerClient {
@@ -58,6 +122,7 @@ impl PotterAppServerClient {
         rounds: NonZeroUsize,
         launch: crate::app_server::AppServerLaunchConfig,
         upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+        project_cli_args: super::PotterProjectCliArgs,
     ) -> anyhow::Result<Self> {
         let exe = std::env::current_exe().context("resolve codex-potter executable path")?;
 
@@ -75,6 +140,10 @@ impl PotterAppServerClient {
             cmd.arg(arg);
         }
 
+        for arg in project_cli_args.to_potter_app_server_args() {
+            cmd.arg(arg);
+        }
+
         if launch.bypass_approvals_and_sandbox {
             cmd.arg("--dangerously-bypass-approvals-and-sandbox");
         }
@@ -101,28 +170,75 @@ impl PotterAppServerClient {
             .take()
             .context("potter app-server stdout unavailable")?;
 
-        Ok(Self {
+        Ok(Self::new(
+            Some(child),
+            Box::new(stdout),
+            Box::new(stdin),
+            None,
+        ))
+    }
+
+    /// Connect to a server started with `app-server --listen`; `token` is its `--listen-token`.
+    pub async fn connect(
+        address: &PotterAppServerListenAddress,
+        token: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let (reader, writer): (ServerReader, ServerWriter) = match address {
+            #[cfg(unix)]
+            PotterAppServerListenAddress::Unix(path) => {
+                let stream = tokio::net::UnixStream::connect(path)
+                    .await
+                    .with_context(|| format!("connect to {address}"))?;
+                let (reader, writer) = stream.into_split();
+                (Box::new(reader), Box::new(writer))
+            }
+            #[cfg(not(unix))]
+            PotterAppServerListenAddress::Unix(_) => {
+                anyhow::bail!("unix:// addresses are not supported on this platform")
+            }
+            PotterAppServerListenAddress::Tcp(addr) => {
+                let stream = tokio::net::TcpStream::connect(addr)
+                    .await
+                    .with_context(|| format!("connect to {address}"))?;
+                let (reader, writer) = stream.into_split();
+                (Box::new(reader), Box::new(writer))
+            }
+        };
+        Ok(Self::new(None, reader, writer, token))
+    }
+
+    fn new(
+        child: Option<Child>,
+        reader: ServerReader,
+        writer: ServerWriter,
+        token: Option<String>,
+    ) -> Self {
+        Self {
             child,
-            stdin: Some(stdin),
-            stdout_lines: BufReader::new(stdout).lines(),
+            writer: Some(writer),
+            lines: BufReader::new(reader).lines(),
+            token,
             next_id: 1,
-        })
+            pending_approvals: HashMap::new(),
+            pending_user_inputs: HashMap::new(),
+        }
     }
 
     pub async fn initialize(&mut self) -> anyhow::Result<()> {
         let request_id = self.next_request_id();
         let request = PotterAppServerClientRequest::Initialize {
             request_id: request_id.clone(),
-            params: InitializeParams {
+            params: PotterInitializeParams {
                 client_info: ClientInfo {
                     name: "codex-potter".to_string(),
                     title: Some("codex-potter".to_string()),
                     version: codex_tui::CODEX_POTTER_VERSION.to_string(),
                 },
+                token: self.token.clone(),
             },
         };
 
-        let mut buffered_events = Vec::new();
+        let mut buffered_events: Vec<Event> = Vec::new();
         let _: serde_json::Value = self
             .send_request(request_id, request, &mut buffered_events)
             .await?;
@@ -136,10 +252,10 @@ impl PotterAppServerClient {
         Ok(())
     }
 
-    pub async fn project_list(
+    pub async fn project_list<B: PotterEventBuffer>(
         &mut self,
         params: ProjectListParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<ProjectListResponse> {
         let request_id = self.next_request_id();
         self.send_request(
@@ -150,10 +266,10 @@ impl PotterAppServerClient {
         .await
     }
 
-    pub async fn project_start(
+    pub async fn project_start<B: PotterEventBuffer>(
         &mut self,
         params: ProjectStartParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<ProjectStartResponse> {
         let request_id = self.next_request_id();
         self.send_request(
@@ -164,10 +280,10 @@ impl PotterAppServerClient {
         .await
     }
 
-    pub async fn project_resume(
+    pub async fn project_resume<B: PotterEventBuffer>(
         &mut self,
         params: ProjectResumeParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<ProjectResumeResponse> {
         let request_id = self.next_request_id();
         self.send_request(
@@ -178,10 +294,10 @@ impl PotterAppServerClient {
         .await
     }
 
-    pub async fn project_start_rounds(
+    pub async fn project_start_rounds<B: PotterEventBuffer>(
         &mut self,
         params: ProjectStartRoundsParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<ProjectStartRoundsResponse> {
         let request_id = self.next_request_id();
         self.send_request(
@@ -192,10 +308,10 @@ impl PotterAppServerClient {
         .await
     }
 
-    pub async fn project_interrupt(
+    pub async fn project_interrupt<B: PotterEventBuffer>(
         &mut self,
         params: ProjectInterruptParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<()> {
         let request_id = self.next_request_id();
         let _: serde_json::Value = self
@@ -208,10 +324,10 @@ impl PotterAppServerClient {
         Ok(())
     }
 
-    pub async fn project_resolve_interrupt(
+    pub async fn project_resolve_interrupt<B: PotterEventBuffer>(
         &mut self,
         params: ProjectResolveInterruptParams,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<ProjectResolveInterruptResponse> {
         let request_id = self.next_request_id();
         self.send_request(
@@ -222,13 +338,175 @@ impl PotterAppServerClient {
         .await
     }
 
+    /// Attach to an active project (see [`ProjectAttachResponse`]).
+    ///
+    /// The project's pending server requests are registered and pushed to `buffered_events` like
+    /// live ones, so the returned response has no `pending_requests`.
+    pub async fn project_attach<B: PotterEventBuffer>(
+        &mut self,
+        params: ProjectAttachParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<ProjectAttachResponse> {
+        let request_id = self.next_request_id();
+        let mut response: ProjectAttachResponse = self
+            .send_request(
+                request_id.clone(),
+                PotterAppServerClientRequest::ProjectAttach { request_id, params },
+                buffered_events,
+            )
+            .await?;
+        for request in std::mem::take(&mut response.pending_requests) {
+            if let Some(event) = self.server_request_event(request)? {
+                buffered_events.push_event(event);
+            }
+        }
+        Ok(response)
+    }
+
+    pub async fn project_detach<B: PotterEventBuffer>(
+        &mut self,
+        params: ProjectDetachParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<()> {
+        let request_id = self.next_request_id();
+        let _: serde_json::Value = self
+            .send_request(
+                request_id.clone(),
+                PotterAppServerClientRequest::ProjectDetach { request_id, params },
+                buffered_events,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn project_get<B: PotterEventBuffer>(
+        &mut self,
+        params: ProjectGetParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<ProjectGetResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::ProjectGet { request_id, params },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn project_status<B: PotterEventBuffer>(
+        &mut self,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<ProjectStatusResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::ProjectStatus {
+                request_id,
+                params: ProjectStatusParams::default(),
+            },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn queue_add<B: PotterEventBuffer>(
+        &mut self,
+        params: QueueAddParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<QueueAddResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::QueueAdd { request_id, params },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn queue_list<B: PotterEventBuffer>(
+        &mut self,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<QueueListResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::QueueList {
+                request_id,
+                params: QueueListParams::default(),
+            },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn queue_remove<B: PotterEventBuffer>(
+        &mut self,
+        params: QueueRemoveParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<QueueRemoveResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::QueueRemove { request_id, params },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn queue_reorder<B: PotterEventBuffer>(
+        &mut self,
+        params: QueueReorderParams,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<QueueListResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::QueueReorder { request_id, params },
+            buffered_events,
+        )
+        .await
+    }
+
+    pub async fn queue_take<B: PotterEventBuffer>(
+        &mut self,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<QueueTakeResponse> {
+        let request_id = self.next_request_id();
+        self.send_request(
+            request_id.clone(),
+            PotterAppServerClientRequest::QueueTake {
+                request_id,
+                params: QueueTakeParams::default(),
+            },
+            buffered_events,
+        )
+        .await
+    }
+
+    /// Take the next prompt from the server's persistent queue, if any.
+    pub async fn take_queued_prompt<B: PotterEventBuffer>(
+        &mut self,
+        buffered_events: &mut B,
+    ) -> anyhow::Result<Option<String>> {
+        let response = self.queue_take(buffered_events).await?;
+        Ok(response.entry.map(|entry| entry.prompt))
+    }
+
     pub async fn read_next_event(&mut self) -> anyhow::Result<Option<Event>> {
+        Ok(self
+            .read_next_project_event()
+            .await?
+            .map(|event| event.event))
+    }
+
+    /// Read the next event together with the project that emitted it.
+    pub async fn read_next_project_event(&mut self) -> anyhow::Result<Option<PotterProjectEvent>> {
         loop {
             let Some(line) = self
-                .stdout_lines
+                .lines
                 .next_line()
                 .await
-                .context("read potter app-server stdout line")?
+                .context("read potter app-server line")?
             else {
                 return Ok(None);
             };
@@ -246,31 +524,92 @@ impl PotterAppServerClient {
                         let params = notification
                             .params
                             .context("potter app-server event notification missing params")?;
-                        let event: Event = serde_json::from_value(params)
+                        let event = PotterProjectEvent::from_notification_params(params)
                             .context("deserialize potter app-server event payload")?;
                         return Ok(Some(event));
                     }
                 }
-                JSONRPCMessage::Request(_)
-                | JSONRPCMessage::Response(_)
-                | JSONRPCMessage::Error(_) => {}
+                JSONRPCMessage::Request(request) => {
+                    if let Some(event) = self.server_request_event(request)? {
+                        return Ok(Some(event));
+                    }
+                }
+                JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => {}
             }
         }
     }
 
+    /// Answer an `approval/request` surfaced as `EventMsg::PotterApprovalRequest`.
+    pub async fn answer_approval(
+        &mut self,
+        approval_id: &str,
+        response: ApprovalRequestResponse,
+    ) -> anyhow::Result<()> {
+        let request_id = self
+            .pending_approvals
+            .remove(approval_id)
+            .with_context(|| format!("unknown approval request `{approval_id}`"))?;
+        let result = serde_json::to_value(response).context("serialize approval response")?;
+        let writer = self
+            .writer
+            .as_mut()
+            .context("potter app-server connection closed")?;
+        send_message(
+            writer,
+            &JSONRPCMessage::Response(JSONRPCResponse {
+                id: request_id,
+                result,
+            }),
+        )
+        .await
+        .context("send potter app-server approval response")
+    }
+
+    /// Answer a `userInput/request` surfaced as `EventMsg::PotterUserInputRequest`.
+    pub async fn answer_user_input(
+        &mut self,
+        request_id: &str,
+        response: UserInputRequestResponse,
+    ) -> anyhow::Result<()> {
+        let jsonrpc_id = self
+            .pending_user_inputs
+            .remove(request_id)
+            .with_context(|| format!("unknown user input request `{request_id}`"))?;
+        let result = serde_json::to_value(response).context("serialize user input response")?;
+        let writer = self
+            .writer
+            .as_mut()
+            .context("potter app-server connection closed")?;
+        send_message(
+            writer,
+            &JSONRPCMessage::Response(JSONRPCResponse {
+                id: jsonrpc_id,
+                result,
+            }),
+        )
+        .await
+        .context("send potter app-server user input response")
+    }
+
+    /// Close the connection; a spawned server is waited for (and killed if it does not exit).
     pub async fn shutdown(&mut self) -> anyhow::Result<()> {
-        drop(self.stdin.take());
-        let wait = self.child.wait();
+        if let Some(mut writer) = self.writer.take() {
+            let _ = writer.shutdown().await;
+        }
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+        let wait = child.wait();
         match tokio::time::timeout(std::time::Duration::from_secs(2), wait).await {
             Ok(status) => {
                 status.context("wait for potter app-server process")?;
             }
             Err(_) => {
-                self.child
+                child
                     .kill()
                     .await
                     .context("kill potter app-server process")?;
-                self.child
+                child
                     .wait()
                     .await
                     .context("wait for killed potter app-server process")?;
@@ -285,20 +624,21 @@ impl PotterAppServerClient {
         RequestId::Integer(id)
     }
 
-    async fn send_request<T>(
+    async fn send_request<T, B>(
         &mut self,
         request_id: RequestId,
         request: PotterAppServerClientRequest,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<T>
     where
         T: serde::de::DeserializeOwned,
+        B: PotterEventBuffer,
     {
-        let stdin = self
-            .stdin
+        let writer = self
+            .writer
             .as_mut()
-            .context("potter app-server stdin unavailable")?;
-        send_message(stdin, &request)
+            .context("potter app-server connection closed")?;
+        send_message(writer, &request)
             .await
             .context("send potter app-server request")?;
 
@@ -314,29 +654,29 @@ impl PotterAppServerClient {
         &mut self,
         notification: PotterAppServerClientNotification,
     ) -> anyhow::Result<()> {
-        let stdin = self
-            .stdin
+        let writer = self
+            .writer
             .as_mut()
-            .context("potter app-server stdin unavailable")?;
-        send_message(stdin, &notification)
+            .context("potter app-server connection closed")?;
+        send_message(writer, &notification)
             .await
             .context("send potter app-server notification")?;
         Ok(())
     }
 
-    async fn read_until_response(
+    async fn read_until_response<B: PotterEventBuffer>(
         &mut self,
         request_id: RequestId,
-        buffered_events: &mut Vec<Event>,
+        buffered_events: &mut B,
     ) -> anyhow::Result<JsonRpcResult> {
         loop {
             let Some(line) = self
-                .stdout_lines
+                .lines
                 .next_line()
                 .await
-                .context("read potter app-server stdout line")?
+                .context("read potter app-server line")?
             else {
-                anyhow::bail!("potter app-server closed stdout unexpectedly");
+                anyhow::bail!("potter app-server closed the connection unexpectedly");
             };
             if line.trim().is_empty() {
                 continue;
@@ -353,9 +693,9 @@ impl PotterAppServerClient {
                     let params = notification
                         .params
                         .context("potter app-server event notification missing params")?;
-                    let event: Event = serde_json::from_value(params)
+                    let event = PotterProjectEvent::from_notification_params(params)
                         .context("deserialize potter event payload")?;
-                    buffered_events.push(event);
+                    buffered_events.push_event(event);
                 }
                 JSONRPCMessage::Response(response) => {
                     if response.id == request_id {
@@ -371,10 +711,62 @@ impl PotterAppServerClient {
                         );
                     }
                 }
-                JSONRPCMessage::Request(_) => {}
+                JSONRPCMessage::Request(request) => {
+                    if let Some(event) = self.server_request_event(request)? {
+                        buffered_events.push_event(event);
+                    }
+                }
             }
         }
     }
+
+    /// Turn an `approval/request` or `userInput/request` call into an event; other server
+    /// requests are ignored.
+    fn server_request_event(
+        &mut self,
+        request: JSONRPCRequest,
+    ) -> anyhow::Result<Option<PotterProjectEvent>> {
+        if request.method == POTTER_USER_INPUT_REQUEST_METHOD {
+            let params = request
+                .params
+                .context("potter app-server user input request missing params")?;
+            let params: UserInputRequestParams =
+                serde_json::from_value(params).context("deserialize user input request params")?;
+            let request_id = match &request.id {
+                RequestId::String(id) => id.clone(),
+                RequestId::Integer(id) => id.to_string(),
+            };
+            // Already surfaced: live and replayed by `project/attach`.
+            if self
+                .pending_user_inputs
+                .insert(request_id.clone(), request.id)
+                .is_some()
+            {
+                return Ok(None);
+            }
+            return Ok(Some(params.into_project_event(request_id)));
+        }
+        if request.method != POTTER_APPROVAL_REQUEST_METHOD {
+            return Ok(None);
+        }
+        let params = request
+            .params
+            .context("potter app-server approval request missing params")?;
+        let params: ApprovalRequestParams =
+            serde_json::from_value(params).context("deserialize approval request params")?;
+        let approval_id = match &request.id {
+            RequestId::String(id) => id.clone(),
+            RequestId::Integer(id) => id.to_string(),
+        };
+        if self
+            .pending_approvals
+            .insert(approval_id.clone(), request.id)
+            .is_some()
+        {
+            return Ok(None);
+        }
+        Ok(Some(params.into_project_event(approval_id)))
+    }
 }
 
 impl crate::workflow::project_render_loop::PotterEventSource for PotterAppServerClient {
@@ -391,7 +783,7 @@ impl crate::workflow::project_render_loop::PotterProjectController for PotterApp
         project_id: String,
     ) -> crate::workflow::round_runner::UiFuture<'a, Vec<Event>> {
         Box::pin(async move {
-            let mut buffered_events = Vec::new();
+            let mut buffered_events: Vec<Event> = Vec::new();
             PotterAppServerClient::project_interrupt(
                 self,
                 ProjectInterruptParams { project_id },
@@ -401,21 +793,45 @@ impl crate::workflow::project_render_loop::PotterProjectController for PotterApp
             Ok(buffered_events)
         })
     }
+
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        response: ApprovalRequestResponse,
+    ) -> crate::workflow::round_runner::UiFuture<'a, ()> {
+        Box::pin(async move {
+            PotterAppServerClient::answer_approval(self, &approval_id, response).await
+        })
+    }
+
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        response: UserInputRequestResponse,
+    ) -> crate::workflow::round_runner::UiFuture<'a, ()> {
+        Box::pin(async move {
+            PotterAppServerClient::answer_user_input(self, &request_id, response).await
+        })
+    }
 }
 
-async fn send_message<T: serde::Serialize>(stdin: &mut ChildStdin, msg: &T) -> anyhow::Result<()> {
+async fn send_message<W, T>(writer: &mut W, msg: &T) -> anyhow::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: serde::Serialize,
+{
     let json = serde_json::to_vec(&msg).context("serialize potter app-server JSON-RPC message")?;
-    stdin
+    writer
         .write_all(&json)
         .await
-        .context("write potter app-server stdin")?;
-    stdin
+        .context("write potter app-server message")?;
+    writer
         .write_all(b"\n")
         .await
-        .context("write potter app-server stdin newline")?;
-    stdin
+        .context("write potter app-server newline")?;
+    writer
         .flush()
         .await
-        .context("flush potter app-server stdin")?;
+        .context("flush potter app-server input")?;
     Ok(())
 }
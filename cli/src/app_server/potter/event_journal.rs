@@ -0,0 +1,423 @@
+//! Per-project event journal backing `project/attach`.
+//!
+//! Every message the server sends passes through [`EventJournal::record`] on its way to the
+//! transport. Event notifications (`codex/event/potter`) are stamped with a server-wide `seq`, and
+//! the journal keeps what a client attaching later needs to render the project:
+//!
+//! - its `PotterProjectStarted` event,
+//! - every event since its latest `PotterRoundStarted` (round boundaries, interrupt and plan review
+//!   pauses included),
+//! - its server → client requests until they are answered.
+//!
+//! It also follows each project's progress (latest round, token usage, start time) for
+//! `project/get` and `project/status`. A project is forgotten once it emits
+//! `PotterProjectCompleted`.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde_json::Value;
+
+use crate::app_server::upstream_protocol::JSONRPCMessage;
+use crate::app_server::upstream_protocol::JSONRPCRequest;
+use crate::app_server::upstream_protocol::RequestId;
+
+use super::protocol::POTTER_EVENT_NOTIFICATION_METHOD;
+use super::protocol::POTTER_EVENT_PROJECT_ID_KEY;
+use super::protocol::POTTER_EVENT_SEQ_KEY;
+use super::protocol::PotterProjectEvent;
+use super::protocol::ProjectAttachEvent;
+use super::protocol::ProjectLiveRound;
+use super::protocol::ProjectLiveState;
+use super::protocol::ProjectLiveStatus;
+
+#[derive(Debug, Default)]
+pub(super) struct EventJournal {
+    last_seq: u64,
+    projects: HashMap<String, ProjectJournal>,
+}
+
+#[derive(Debug)]
+struct ProjectJournal {
+    /// Notification params of `PotterProjectStarted`.
+    project_started: Option<Value>,
+    /// Notification params of the events since the latest `PotterRoundStarted`.
+    round: Vec<Value>,
+    pending_requests: Vec<JSONRPCRequest>,
+    /// When the server sent the project's first message.
+    first_seen: Instant,
+    /// `current` / `total` of the latest `PotterRoundStarted`.
+    round_progress: Option<ProjectLiveRound>,
+    /// Tokens used by the rounds before the current one.
+    finished_rounds_tokens: u64,
+    /// Cumulative token usage of the current round (from `TokenCount`).
+    round_tokens: u64,
+}
+
+impl Default for ProjectJournal {
+    fn default() -> Self {
+        Self {
+            project_started: None,
+            round: Vec::new(),
+            pending_requests: Vec::new(),
+            first_seen: Instant::now(),
+            round_progress: None,
+            finished_rounds_tokens: 0,
+            round_tokens: 0,
+        }
+    }
+}
+
+/// What `project/attach` replays for a project.
+#[derive(Debug, Default)]
+pub(super) struct ProjectReplay {
+    pub events: Vec<ProjectAttachEvent>,
+    pub pending_requests: Vec<JSONRPCRequest>,
+}
+
+impl EventJournal {
+    /// Stamp an outgoing message with its sequence number (event notifications only) and record
+    /// it for its project.
+    pub(super) fn record(&mut self, msg: &mut JSONRPCMessage) {
+        match msg {
+            JSONRPCMessage::Notification(notification)
+                if notification.method == POTTER_EVENT_NOTIFICATION_METHOD =>
+            {
+                let Some(Value::Object(params)) = notification.params.as_mut() else {
+                    return;
+                };
+                self.last_seq = self.last_seq.saturating_add(1);
+                params.insert(POTTER_EVENT_SEQ_KEY.to_string(), self.last_seq.into());
+
+                let Some(project_id) = params
+                    .get(POTTER_EVENT_PROJECT_ID_KEY)
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                else {
+                    return;
+                };
+                let event_type = params
+                    .get("msg")
+                    .and_then(|msg| msg.get("type"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                self.record_event(project_id, &event_type, Value::Object(params.clone()));
+            }
+            JSONRPCMessage::Request(request) => {
+                let Some(project_id) = request
+                    .params
+                    .as_ref()
+                    .and_then(|params| params.get(POTTER_EVENT_PROJECT_ID_KEY))
+                    .and_then(Value::as_str)
+                else {
+                    return;
+                };
+                self.projects
+                    .entry(project_id.to_string())
+                    .or_default()
+                    .pending_requests
+                    .push(request.clone());
+            }
+            JSONRPCMessage::Notification(_)
+            | JSONRPCMessage::Response(_)
+            | JSONRPCMessage::Error(_) => {}
+        }
+    }
+
+    fn record_event(&mut self, project_id: String, event_type: &str, params: Value) {
+        // `EventMsg` is tagged with its snake_case variant name.
+        match event_type {
+            "potter_project_completed" => {
+                self.projects.remove(&project_id);
+            }
+            "potter_project_started" => {
+                let project = self.projects.entry(project_id).or_default();
+                project.project_started = Some(params);
+                project.round.clear();
+            }
+            "potter_round_started" => {
+                let project = self.projects.entry(project_id).or_default();
+                project.round_progress = round_progress(&params);
+                project.finished_rounds_tokens = project
+                    .finished_rounds_tokens
+                    .saturating_add(project.round_tokens);
+                project.round_tokens = 0;
+                project.round.clear();
+                // Requests of the previous round are moot: their round no longer waits for them.
+                project.pending_requests.clear();
+                project.round.push(params);
+            }
+            "token_count" => {
+                let project = self.projects.entry(project_id).or_default();
+                if let Some(total_tokens) = params
+                    .pointer("/msg/info/total_token_usage/total_tokens")
+                    .and_then(Value::as_u64)
+                {
+                    project.round_tokens = total_tokens;
+                }
+                project.round.push(params);
+            }
+            _ => self
+                .projects
+                .entry(project_id)
+                .or_default()
+                .round
+                .push(params),
+        }
+    }
+
+    /// Forget a server request once the client answered it.
+    pub(super) fn resolve_request(&mut self, id: &RequestId) {
+        for project in self.projects.values_mut() {
+            project.pending_requests.retain(|request| &request.id != id);
+        }
+    }
+
+    /// Forget a project that stopped without completing (force-aborted).
+    pub(super) fn forget(&mut self, project_id: &str) {
+        self.projects.remove(project_id);
+    }
+
+    /// Replay for a client attaching to `project_id`.
+    pub(super) fn replay(&self, project_id: &str) -> anyhow::Result<ProjectReplay> {
+        let Some(project) = self.projects.get(project_id) else {
+            return Ok(ProjectReplay::default());
+        };
+        let mut events = Vec::new();
+        for params in project.project_started.iter().chain(&project.round) {
+            let event = PotterProjectEvent::from_notification_params(params.clone())?;
+            let Some(seq) = event.seq else {
+                continue;
+            };
+            events.push(ProjectAttachEvent {
+                seq,
+                event: event.event,
+            });
+        }
+        Ok(ProjectReplay {
+            events,
+            pending_requests: project.pending_requests.clone(),
+        })
+    }
+
+    /// Progress of an active project, as far as its events tell.
+    pub(super) fn live_state(
+        &self,
+        project_id: &str,
+        status: ProjectLiveStatus,
+    ) -> ProjectLiveState {
+        let project = self.projects.get(project_id);
+        ProjectLiveState {
+            project_id: project_id.to_string(),
+            status,
+            round: project.and_then(|project| project.round_progress),
+            elapsed_secs: project
+                .map(|project| project.first_seen.elapsed().as_secs())
+                .unwrap_or_default(),
+            tokens_used: project
+                .map(|project| {
+                    project
+                        .finished_rounds_tokens
+                        .saturating_add(project.round_tokens)
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn round_progress(params: &Value) -> Option<ProjectLiveRound> {
+    let msg = params.get("msg")?;
+    let number = |key: &str| {
+        msg.get(key)
+            .and_then(Value::as_u64)
+            .and_then(|value| u32::try_from(value).ok())
+    };
+    Some(ProjectLiveRound {
+        current: number("current")?,
+        total: number("total")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::app_server::upstream_protocol::JSONRPCNotification;
+
+    fn event(project_id: &str, msg: Value) -> JSONRPCMessage {
+        JSONRPCMessage::Notification(JSONRPCNotification {
+            method: POTTER_EVENT_NOTIFICATION_METHOD.to_string(),
+            params: Some(json!({ "id": "", "msg": msg, "projectId": project_id })),
+        })
+    }
+
+    fn record(journal: &mut EventJournal, mut msg: JSONRPCMessage) -> JSONRPCMessage {
+        journal.record(&mut msg);
+        msg
+    }
+
+    fn replayed(journal: &EventJournal, project_id: &str) -> Vec<(u64, String)> {
+        journal
+            .replay(project_id)
+            .expect("replay")
+            .events
+            .into_iter()
+            .map(|event| {
+                let msg = serde_json::to_value(&event.event.msg).expect("encode msg");
+                (
+                    event.seq,
+                    msg["type"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn round_started(current: u32) -> Value {
+        json!({ "type": "potter_round_started", "current": current, "total": 3 })
+    }
+
+    fn agent_message(message: &str) -> Value {
+        json!({ "type": "agent_message", "message": message })
+    }
+
+    #[test]
+    fn notifications_are_numbered_in_send_order() {
+        let mut journal = EventJournal::default();
+        let first = record(&mut journal, event("/a/MAIN.md", round_started(1)));
+        let second = record(&mut journal, event("/b/MAIN.md", round_started(1)));
+
+        let JSONRPCMessage::Notification(first) = first else {
+            panic!("notifications stay notifications");
+        };
+        let JSONRPCMessage::Notification(second) = second else {
+            panic!("notifications stay notifications");
+        };
+        assert_eq!(first.params.expect("params")["seq"], json!(1));
+        assert_eq!(second.params.expect("params")["seq"], json!(2));
+    }
+
+    #[test]
+    fn replay_keeps_the_project_start_and_the_latest_round() {
+        let mut journal = EventJournal::default();
+        let project = "/repo/.codexpotter/projects/2026/10/17/1/MAIN.md";
+        record(
+            &mut journal,
+            event(
+                project,
+                json!({
+                    "type": "potter_project_started",
+                    "user_message": "Fix it",
+                    "working_dir": "/repo",
+                    "project_dir": "/repo/.codexpotter/projects/2026/10/17/1",
+                    "user_prompt_file": project
+                }),
+            ),
+        );
+        record(&mut journal, event(project, round_started(1)));
+        record(&mut journal, event(project, agent_message("first round")));
+        record(
+            &mut journal,
+            event("/other/MAIN.md", agent_message("other")),
+        );
+        record(&mut journal, event(project, round_started(2)));
+        record(&mut journal, event(project, agent_message("second round")));
+
+        assert_eq!(
+            replayed(&journal, project),
+            vec![
+                (1, "potter_project_started".to_string()),
+                (5, "potter_round_started".to_string()),
+                (6, "agent_message".to_string()),
+            ]
+        );
+
+        record(
+            &mut journal,
+            event(
+                project,
+                json!({ "type": "potter_project_completed", "outcome": { "type": "succeeded" } }),
+            ),
+        );
+        assert_eq!(replayed(&journal, project), Vec::new());
+    }
+
+    #[test]
+    fn pending_requests_are_replayed_until_answered() {
+        let mut journal = EventJournal::default();
+        let project = "/repo/MAIN.md";
+        let request = |id: &str| JSONRPCRequest {
+            id: RequestId::String(id.to_string()),
+            method: "approval/request".to_string(),
+            params: Some(json!({ "projectId": project, "kind": "exec" })),
+        };
+        record(&mut journal, event(project, round_started(1)));
+        record(&mut journal, JSONRPCMessage::Request(request("approval-1")));
+        record(&mut journal, JSONRPCMessage::Request(request("approval-2")));
+        journal.resolve_request(&RequestId::String("approval-1".to_string()));
+
+        assert_eq!(
+            journal.replay(project).expect("replay").pending_requests,
+            vec![request("approval-2")]
+        );
+
+        record(&mut journal, event(project, round_started(2)));
+        assert_eq!(
+            journal.replay(project).expect("replay").pending_requests,
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn live_state_follows_rounds_and_token_usage() {
+        let mut journal = EventJournal::default();
+        let project = "/repo/MAIN.md";
+        let token_count = |total_tokens: u64| {
+            json!({
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": {
+                        "input_tokens": 0,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 0,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": total_tokens
+                    },
+                    "last_token_usage": {
+                        "input_tokens": 0,
+                        "cached_input_tokens": 0,
+                        "output_tokens": 0,
+                        "reasoning_output_tokens": 0,
+                        "total_tokens": 0
+                    },
+                    "model_context_window": null
+                }
+            })
+        };
+        record(&mut journal, event(project, round_started(1)));
+        record(&mut journal, event(project, token_count(100)));
+        record(&mut journal, event(project, token_count(250)));
+        record(&mut journal, event(project, round_started(2)));
+        record(&mut journal, event(project, token_count(40)));
+
+        let state = journal.live_state(project, ProjectLiveStatus::Running);
+        assert_eq!(
+            (state.round, state.tokens_used),
+            (
+                Some(ProjectLiveRound {
+                    current: 2,
+                    total: 3
+                }),
+                290
+            )
+        );
+        assert_eq!(
+            journal
+                .live_state("/other/MAIN.md", ProjectLiveStatus::Paused)
+                .round,
+            None
+        );
+    }
+}
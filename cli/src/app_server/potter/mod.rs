@@ -5,10 +5,16 @@
 //!
 //! - **Server** (`server`): a JSON-RPC server that exposes project-level methods like
 //!   `project/start`, `project/resume`, `project/start_rounds`, and `project/list`.
-//! - **Client** (`client`): a small helper for spawning the server (as a subprocess) and
-//!   consuming the event stream.
+//! - **Client** (`client`): a small helper for spawning the server (as a subprocess), or
+//!   connecting to a listening one, and consuming the event stream.
+//! - **Event journal** (`event_journal`): numbers outgoing events, keeps what `project/attach`
+//!   replays and follows each project's round and token usage for `project/status`.
 //! - **Protocol** (`protocol`): request/response and event wire types. The message envelope mirrors
 //!   upstream Codex app-server JSON-RPC to keep tooling consistent.
+//! - **Project CLI args** (`project_cli_args`): project-level flags (e.g. `--verify-command`) that
+//!   the client re-passes to the spawned server.
+//! - **Transport** (`transport`): stdin/stdout for the spawning client, or a Unix/TCP socket
+//!   (`app-server --listen`) shared by any number of clients.
 //!
 //! Each project round is still executed by the upstream `codex app-server` backend driver
 //! (see `crate::app_server::codex_backend`); the Potter app-server is responsible for the
@@ -16,10 +22,16 @@
 //! layer.
 
 pub mod client;
+mod event_journal;
+pub mod project_cli_args;
 pub mod protocol;
 pub mod server;
+pub mod transport;
 
 pub use client::PotterAppServerClient;
+pub use project_cli_args::PotterProjectCliArgs;
 pub use protocol::*;
 pub use server::PotterAppServerConfig;
 pub use server::run_potter_app_server;
+pub use transport::PotterAppServerListenAddress;
+pub use transport::PotterAppServerTransport;
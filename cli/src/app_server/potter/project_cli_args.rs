@@ -0,0 +1,232 @@
+//! Project-level CLI flags handled by the CodexPotter control plane.
+//!
+//! Unlike [`crate::app_server::UpstreamCodexCliArgs`], these flags are never forwarded to the
+//! upstream `codex` backend. They are parsed by the `codex-potter` front-end, re-passed to the
+//! spawned `codex-potter app-server`, and consumed there when running project rounds.
+
+use std::num::NonZeroU64;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use clap::Args;
+
+/// Flags that configure how CodexPotter runs a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct PotterProjectCliArgs {
+    /// Shell command to run in the project working directory after each completed round
+    /// (e.g. `cargo nextest run`).
+    ///
+    /// A project only succeeds when this command exits successfully. On failure the command
+    /// output is reported in the progress file for the next round.
+    #[arg(long = "verify-command", value_name = "CMD", global = true)]
+    pub verify_command: Option<String>,
+
+    /// Kill `--verify-command` and fail the gate when it runs longer than DURATION (e.g. `10m`;
+    /// default: 30m).
+    #[arg(
+        long = "verify-timeout",
+        value_name = "DURATION",
+        value_parser = crate::workflow::budget::parse_duration,
+        global = true
+    )]
+    pub verify_timeout: Option<Duration>,
+
+    /// Run each new project in a dedicated git worktree and branch
+    /// (`codexpotter/YYYY-MM-DD/N`) instead of the current working directory.
+    #[arg(long = "worktree", default_value_t = false, global = true)]
+    pub worktree: bool,
+
+    /// Run up to N queued projects concurrently (must be >= 1).
+    ///
+    /// With N > 1 every project runs in its own git worktree (implies `--worktree`), so concurrent
+    /// projects never edit the same checkout.
+    #[arg(long = "parallel", value_name = "N", global = true)]
+    pub parallel: Option<NonZeroUsize>,
+
+    /// End a project as stalled after N consecutive rounds that leave the git tree unchanged.
+    ///
+    /// Edits to `.codexpotter/` (including the progress file) do not count as changes. Disabled by
+    /// default and outside git repositories.
+    #[arg(long = "stall-rounds", value_name = "N", global = true)]
+    pub stall_rounds: Option<NonZeroUsize>,
+
+    /// Stop a project once its rounds have used N tokens in total.
+    ///
+    /// The active round is interrupted when the budget runs out.
+    #[arg(long = "max-tokens", value_name = "N", global = true)]
+    pub max_tokens: Option<NonZeroU64>,
+
+    /// Stop a project once it has been running for DURATION (e.g. `90m`, `2h`, `1h30m`).
+    ///
+    /// The active round is interrupted when the budget runs out.
+    #[arg(
+        long = "max-duration",
+        value_name = "DURATION",
+        value_parser = crate::workflow::budget::parse_duration,
+        global = true
+    )]
+    pub max_duration: Option<Duration>,
+
+    /// Before starting a round, wait for upstream rate-limit windows used at PERCENT or more to
+    /// reset (default: 95).
+    ///
+    /// Usage comes from the rate limits reported by the previous round.
+    #[arg(
+        long = "rate-limit-threshold",
+        value_name = "PERCENT",
+        value_parser = crate::workflow::rate_limit::parse_threshold,
+        global = true
+    )]
+    pub rate_limit_threshold: Option<u8>,
+
+    /// Pause after the planning round (when the progress file moves from `status: initial` to
+    /// `status: open`) so the Todo list can be approved, edited, or aborted before any
+    /// implementation round starts.
+    #[arg(long = "review-plan", default_value_t = false, global = true)]
+    pub review_plan: bool,
+
+    /// Ask for approval (in the TUI) for every upstream approval request that no
+    /// `[approvals]` rule decides, instead of approving it.
+    ///
+    /// `codex-potter exec` cannot ask and denies such requests.
+    #[arg(long = "ask-approval", default_value_t = false, global = true)]
+    pub ask_approval: bool,
+
+    /// Keep one upstream `codex app-server` process alive across rounds (starting a new thread per
+    /// round) instead of spawning a fresh process for every round. A process that died is
+    /// respawned automatically.
+    #[arg(long = "reuse-app-server", default_value_t = false, global = true)]
+    pub reuse_app_server: bool,
+}
+
+impl PotterProjectCliArgs {
+    /// Maximum number of projects that may be active at the same time.
+    pub fn parallel_projects(&self) -> NonZeroUsize {
+        self.parallel.unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Whether new projects run in a dedicated git worktree.
+    pub fn worktree_enabled(&self) -> bool {
+        self.worktree || self.parallel_projects().get() > 1
+    }
+
+    /// Render CLI args for launching `codex-potter app-server` as a subprocess.
+    pub fn to_potter_app_server_args(&self) -> Vec<String> {
+        let mut out = Vec::new();
+
+        if let Some(command) = &self.verify_command {
+            out.push("--verify-command".to_string());
+            out.push(command.clone());
+        }
+        if let Some(timeout) = self.verify_timeout {
+            out.push("--verify-timeout".to_string());
+            out.push(crate::workflow::budget::format_duration(timeout));
+        }
+        if self.worktree {
+            out.push("--worktree".to_string());
+        }
+        if let Some(parallel) = self.parallel {
+            out.push("--parallel".to_string());
+            out.push(parallel.to_string());
+        }
+        if let Some(stall_rounds) = self.stall_rounds {
+            out.push("--stall-rounds".to_string());
+            out.push(stall_rounds.to_string());
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            out.push("--max-tokens".to_string());
+            out.push(max_tokens.to_string());
+        }
+        if let Some(max_duration) = self.max_duration {
+            out.push("--max-duration".to_string());
+            out.push(crate::workflow::budget::format_duration(max_duration));
+        }
+        if let Some(threshold) = self.rate_limit_threshold {
+            out.push("--rate-limit-threshold".to_string());
+            out.push(threshold.to_string());
+        }
+        if self.review_plan {
+            out.push("--review-plan".to_string());
+        }
+        if self.ask_approval {
+            out.push("--ask-approval".to_string());
+        }
+        if self.reuse_app_server {
+            out.push("--reuse-app-server".to_string());
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn to_potter_app_server_args_round_trips_project_flags() {
+        assert_eq!(
+            PotterProjectCliArgs::default().to_potter_app_server_args(),
+            Vec::<String>::new()
+        );
+
+        let args = PotterProjectCliArgs {
+            verify_command: Some("cargo test --workspace".to_string()),
+            verify_timeout: Some(Duration::from_secs(600)),
+            worktree: true,
+            parallel: NonZeroUsize::new(3),
+            stall_rounds: NonZeroUsize::new(2),
+            max_tokens: NonZeroU64::new(2_000_000),
+            max_duration: Some(Duration::from_secs(5400)),
+            rate_limit_threshold: Some(90),
+            review_plan: true,
+            ask_approval: true,
+            reuse_app_server: true,
+        };
+        assert_eq!(
+            args.to_potter_app_server_args(),
+            vec![
+                "--verify-command".to_string(),
+                "cargo test --workspace".to_string(),
+                "--verify-timeout".to_string(),
+                "10m".to_string(),
+                "--worktree".to_string(),
+                "--parallel".to_string(),
+                "3".to_string(),
+                "--stall-rounds".to_string(),
+                "2".to_string(),
+                "--max-tokens".to_string(),
+                "2000000".to_string(),
+                "--max-duration".to_string(),
+                "1h30m".to_string(),
+                "--rate-limit-threshold".to_string(),
+                "90".to_string(),
+                "--review-plan".to_string(),
+                "--ask-approval".to_string(),
+                "--reuse-app-server".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn parallel_projects_implies_worktree() {
+        let args = PotterProjectCliArgs::default();
+        assert_eq!(args.parallel_projects().get(), 1);
+        assert!(!args.worktree_enabled());
+
+        let args = PotterProjectCliArgs {
+            parallel: NonZeroUsize::new(1),
+            ..Default::default()
+        };
+        assert!(!args.worktree_enabled());
+
+        let args = PotterProjectCliArgs {
+            parallel: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        assert_eq!(args.parallel_projects().get(), 2);
+        assert!(args.worktree_enabled());
+    }
+}
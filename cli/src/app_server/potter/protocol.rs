@@ -9,21 +9,183 @@
 //! Keeping the shapes close to upstream reduces mental overhead and makes it easier to share
 //! tooling across `codex` and `codex-potter`.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterApprovalChoice;
+use codex_protocol::protocol::PotterApprovalKind;
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::app_server::upstream_protocol::InitializeParams;
+use crate::app_server::upstream_protocol::ClientInfo;
 use crate::app_server::upstream_protocol::JSONRPCNotification;
 use crate::app_server::upstream_protocol::JSONRPCRequest;
 use crate::app_server::upstream_protocol::RequestId;
 
 pub const POTTER_EVENT_NOTIFICATION_METHOD: &str = "codex/event/potter";
 
+/// Server → client request asking the user to decide an upstream approval request.
+///
+/// Params are [`ApprovalRequestParams`]; the client answers with an [`ApprovalRequestResponse`].
+/// Sent when the project's approval policy resolves to `ask`. The round waits for the answer, so
+/// clients that cannot ask a user should answer `deny` right away.
+pub const POTTER_APPROVAL_REQUEST_METHOD: &str = "approval/request";
+
+/// Server → client request asking the user to answer questions of the agent
+/// (`request_user_input`) or of an MCP server (elicitation).
+///
+/// Params are [`UserInputRequestParams`]; the client answers with a [`UserInputRequestResponse`].
+/// The round waits for the answer, so clients that cannot ask a user should decline right away.
+pub const POTTER_USER_INPUT_REQUEST_METHOD: &str = "userInput/request";
+
+/// Keys added next to the [`Event`] fields in event notification params.
+pub(super) const POTTER_EVENT_PROJECT_ID_KEY: &str = "projectId";
+pub(super) const POTTER_EVENT_SEQ_KEY: &str = "seq";
+
+/// An event streamed by the server, tagged with the project that emitted it.
+///
+/// On the wire this is the `params` of a [`POTTER_EVENT_NOTIFICATION_METHOD`] notification: the
+/// [`Event`] fields plus an optional `projectId` and `seq`. Several projects can be active at once
+/// (`--parallel`), so clients use `projectId` to demultiplex the shared event stream; clients
+/// driving a single project can ignore it.
+#[derive(Debug, Clone)]
+pub struct PotterProjectEvent {
+    pub project_id: Option<String>,
+    /// Server-wide sequence number, increasing with every event notification. Clients use it to
+    /// drop live events already included in a `project/attach` replay.
+    pub seq: Option<u64>,
+    pub event: Event,
+}
+
+impl PotterProjectEvent {
+    pub fn to_notification_params(&self) -> serde_json::Result<serde_json::Value> {
+        let mut params = serde_json::to_value(&self.event)?;
+        if let serde_json::Value::Object(map) = &mut params {
+            if let Some(project_id) = &self.project_id {
+                map.insert(
+                    POTTER_EVENT_PROJECT_ID_KEY.to_string(),
+                    serde_json::Value::String(project_id.clone()),
+                );
+            }
+            if let Some(seq) = self.seq {
+                map.insert(POTTER_EVENT_SEQ_KEY.to_string(), seq.into());
+            }
+        }
+        Ok(params)
+    }
+
+    pub fn from_notification_params(mut params: serde_json::Value) -> serde_json::Result<Self> {
+        let (project_id, seq) = match params.as_object_mut() {
+            Some(map) => (
+                map.remove(POTTER_EVENT_PROJECT_ID_KEY),
+                map.remove(POTTER_EVENT_SEQ_KEY),
+            ),
+            None => (None, None),
+        };
+        let project_id = match project_id {
+            Some(serde_json::Value::String(project_id)) => Some(project_id),
+            _ => None,
+        };
+        let seq = seq.as_ref().and_then(serde_json::Value::as_u64);
+        let event = serde_json::from_value(params)?;
+        Ok(Self {
+            project_id,
+            seq,
+            event,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequestParams {
+    /// Project whose round is waiting for the answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub kind: PotterApprovalKind,
+    /// Command line or affected paths, for display.
+    pub summary: String,
+    /// Why the policy asks (matched rule, or the policy default).
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequestResponse {
+    pub decision: PotterApprovalChoice,
+    /// Optional note for the model when the request is denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<String>,
+}
+
+impl ApprovalRequestParams {
+    /// Client-side event for this request; `approval_id` is the JSON-RPC request id.
+    pub fn into_project_event(self, approval_id: String) -> PotterProjectEvent {
+        PotterProjectEvent {
+            project_id: self.project_id,
+            seq: None,
+            event: Event {
+                id: String::new(),
+                msg: EventMsg::PotterApprovalRequest {
+                    approval_id,
+                    kind: self.kind,
+                    summary: self.summary,
+                    reason: self.reason,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserInputRequestParams {
+    /// Project whose round is waiting for the answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// MCP server asking, for elicitations; absent when the agent asks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    /// Elicitation message shown above the questions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub questions: Vec<RequestUserInputQuestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserInputRequestResponse {
+    /// Answers keyed by question id; absent when the user declined the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answers: Option<HashMap<String, RequestUserInputAnswer>>,
+}
+
+impl UserInputRequestParams {
+    /// Client-side event for this request; `request_id` is the JSON-RPC request id.
+    pub fn into_project_event(self, request_id: String) -> PotterProjectEvent {
+        PotterProjectEvent {
+            project_id: self.project_id,
+            seq: None,
+            event: Event {
+                id: String::new(),
+                msg: EventMsg::PotterUserInputRequest {
+                    request_id,
+                    server_name: self.server_name,
+                    message: self.message,
+                    questions: self.questions,
+                },
+            },
+        }
+    }
+}
+
 /// Requests from a Potter app-server client.
 ///
 /// The wire format intentionally mirrors upstream Codex app-server JSON-RPC requests:
@@ -36,7 +198,7 @@ pub enum PotterAppServerClientRequest {
     Initialize {
         #[serde(rename = "id")]
         request_id: RequestId,
-        params: InitializeParams,
+        params: PotterInitializeParams,
     },
 
     #[serde(rename = "project/list")]
@@ -90,6 +252,98 @@ pub enum PotterAppServerClientRequest {
         request_id: RequestId,
         params: ProjectResolveInterruptParams,
     },
+
+    /// Follow an active project: returns a replay of its current round, after which its live
+    /// events are sent to this connection again (see [`ProjectAttachResponse`]).
+    #[serde(rename = "project/attach")]
+    ProjectAttach {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        #[serde(default)]
+        params: ProjectAttachParams,
+    },
+
+    /// Stop sending a project's events and server requests to this connection. The project keeps
+    /// running.
+    #[serde(rename = "project/detach")]
+    ProjectDetach {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: ProjectDetachParams,
+    },
+
+    /// Describe a project: progress file front matter and task lists, completed round outcomes,
+    /// and live state while it runs on this server.
+    #[serde(rename = "project/get")]
+    ProjectGet {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: ProjectGetParams,
+    },
+
+    /// Live state of every project running (or paused) on this server.
+    #[serde(rename = "project/status")]
+    ProjectStatus {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        #[serde(default)]
+        params: ProjectStatusParams,
+    },
+
+    /// Append a prompt to the persistent queue (`.codexpotter/queue.jsonl` of the server
+    /// workdir).
+    ///
+    /// Servers started with `--listen` start queued prompts as projects whenever a project slot is
+    /// free; other clients take prompts from the queue themselves.
+    #[serde(rename = "queue/add")]
+    QueueAdd {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: QueueAddParams,
+    },
+
+    #[serde(rename = "queue/list")]
+    QueueList {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        #[serde(default)]
+        params: QueueListParams,
+    },
+
+    #[serde(rename = "queue/remove")]
+    QueueRemove {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: QueueRemoveParams,
+    },
+
+    /// Move the listed entries to the front of the queue, in the given order.
+    #[serde(rename = "queue/reorder")]
+    QueueReorder {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: QueueReorderParams,
+    },
+
+    /// Remove and return the next entry of the queue; no two callers get the same entry.
+    #[serde(rename = "queue/take")]
+    QueueTake {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        #[serde(default)]
+        params: QueueTakeParams,
+    },
+}
+
+/// `initialize` params: upstream's client info, plus the `--listen-token` of a listening server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PotterInitializeParams {
+    pub client_info: ClientInfo,
+    /// Checked by the transport before the request reaches the server (see
+    /// [`super::transport::POTTER_LISTEN_TOKEN_PARAM`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
 }
 
 /// Notifications from a Potter app-server client.
@@ -155,6 +409,9 @@ pub struct ProjectListEntry {
     pub updated_at_unix_secs: u64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -167,6 +424,12 @@ pub struct ProjectStartParams {
     pub rounds: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub event_mode: Option<PotterEventMode>,
+    /// JSON Schema of the project's final report.
+    ///
+    /// When set, a successful project runs one more read-only turn whose final message must match
+    /// the schema; it is emitted as `PotterProjectReport` before `PotterProjectCompleted`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -181,6 +444,9 @@ pub struct ProjectStartResponse {
     pub git_commit_start: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<PathBuf>,
     pub rounds_total: u32,
 }
 
@@ -205,6 +471,9 @@ pub struct ProjectResumeResponse {
     pub progress_file: PathBuf,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<PathBuf>,
     pub replay: ProjectResumeReplay,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unfinished_round: Option<ProjectResumeUnfinishedRound>,
@@ -231,6 +500,9 @@ pub struct ProjectResumeReplayRound {
 pub struct ProjectResumeUnfinishedRound {
     pub round_current: u32,
     pub round_total: u32,
+    /// Schedule role recorded when the unfinished round started.
+    #[serde(default)]
+    pub round_kind: PotterRoundKind,
     /// Minimal boundary events that should be rendered before prompting for a follow-up action.
     pub pre_action_events: Vec<EventMsg>,
     /// Number of rounds remaining if the user chooses "Continue & iterate".
@@ -255,6 +527,9 @@ pub struct ProjectStartRoundsParams {
     pub resume_policy: Option<ResumePolicy>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub event_mode: Option<PotterEventMode>,
+    /// Same as [`ProjectStartParams::output_schema`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -285,6 +560,9 @@ pub struct ProjectResolveInterruptParams {
     ///
     /// Required when `action` is [`ResolveInterruptAction::Continue`]. The server uses this as the
     /// first turn prompt when retrying the interrupted round.
+    ///
+    /// Optional when resolving a `PotterProjectAwaitingPlanApproval` pause, where `Continue`
+    /// approves the plan and `Stop` aborts the project.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub turn_prompt_override: Option<String>,
 }
@@ -305,3 +583,204 @@ pub struct ProjectResolveInterruptResponse {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub summary: Option<InterruptedProjectSummary>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAttachParams {
+    /// Project to attach to; may be omitted while exactly one project is running or interrupted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAttachResponse {
+    pub project_id: String,
+    pub working_dir: PathBuf,
+    pub project_dir: PathBuf,
+    pub progress_file: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<PathBuf>,
+    /// Events already sent for the project, oldest first: `PotterProjectStarted` (when known) and
+    /// everything since the latest `PotterRoundStarted`.
+    ///
+    /// Live events follow the response; events that were in flight while the request was handled
+    /// may arrive twice, so clients drop live events whose `seq` is not above the last replayed
+    /// one.
+    pub events: Vec<ProjectAttachEvent>,
+    /// `approval/request` and `userInput/request` calls of the project that are still waiting for
+    /// an answer. Answer them like live requests.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_requests: Vec<JSONRPCRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAttachEvent {
+    pub seq: u64,
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetachParams {
+    pub project_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGetParams {
+    /// Same semantics as [`ProjectResumeParams::project_path`].
+    pub project_path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGetResponse {
+    pub project_id: String,
+    pub project_dir: PathBuf,
+    pub progress_file: PathBuf,
+    pub front_matter: ProjectFrontMatter,
+    pub tasks: ProjectTasks,
+    /// Outcomes of the rounds recorded in `potter-rollout.jsonl`, oldest first.
+    pub rounds: Vec<ProjectRoundSummary>,
+    /// Present while the project runs (or is paused) on this server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live: Option<ProjectLiveState>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFrontMatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_title: Option<String>,
+    pub finite_incantatem: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+}
+
+/// Entries of the progress file's `In Progress`, `Todo` and `Done` sections.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTasks {
+    pub in_progress: Vec<String>,
+    pub todo: Vec<String>,
+    pub done: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRoundSummary {
+    pub current: u32,
+    pub total: u32,
+    pub kind: PotterRoundKind,
+    pub outcome: PotterRoundOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatusParams {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatusResponse {
+    pub projects: Vec<ProjectLiveState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectLiveStatus {
+    /// A round is running (or about to start).
+    Running,
+    /// Waiting for `project/resolve_interrupt` after an interrupt or a plan review pause.
+    Paused,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLiveState {
+    pub project_id: String,
+    pub status: ProjectLiveStatus,
+    /// Latest round started, as `PotterRoundStarted` reported it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round: Option<ProjectLiveRound>,
+    /// Seconds since the project started (or since this server picked it up).
+    pub elapsed_secs: u64,
+    /// Tokens used by the project's rounds on this server.
+    pub tokens_used: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLiveRound {
+    pub current: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedPromptEntry {
+    pub id: String,
+    pub prompt: String,
+    pub queued_at_unix_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueAddParams {
+    pub prompt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueAddResponse {
+    pub entry: QueuedPromptEntry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueListParams {}
+
+/// The queue, next prompt first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueListResponse {
+    pub entries: Vec<QueuedPromptEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemoveParams {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRemoveResponse {
+    pub entry: QueuedPromptEntry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueReorderParams {
+    /// Entries to move to the front; unlisted entries keep their order after them.
+    pub ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTakeParams {}
+
+/// The entry taken off the queue; `None` when the queue was empty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTakeResponse {
+    pub entry: Option<QueuedPromptEntry>,
+}
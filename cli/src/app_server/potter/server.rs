@@ -6,10 +6,22 @@
 //! - Spawns per-round upstream `codex app-server` backends via `crate::app_server::codex_backend`.
 //! - Forwards all `EventMsg` notifications to clients via `codex/event/potter`.
 //! - Persists project boundaries to `potter-rollout.jsonl` and supports replay via `project/resume`.
+//! - Numbers every event notification and keeps the active round of each project, so clients can
+//!   `project/attach` to a running project (see `super::event_journal`).
+//! - Reports project progress (`project/get`) and the live state of active projects
+//!   (`project/status`).
+//! - Owns the persistent prompt queue (`queue/*`, `.codexpotter/queue.jsonl`); listening servers
+//!   start queued prompts as projects whenever a project slot is free.
 //!
 //! The server is long-lived and can serve multiple sequential project runs. Each round backend is
 //! short-lived and isolated by spawning a new upstream process.
+//!
+//! With `--parallel N`, up to N fresh projects can be active (running or interrupted) at the same
+//! time. Every event notification carries the `projectId` of the project that emitted it so
+//! clients can demultiplex the shared stream.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::io::BufRead as _;
 use std::num::NonZeroUsize;
 use std::path::Path;
@@ -25,25 +37,40 @@ use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::PotterProjectOutcome;
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
 use codex_protocol::protocol::SessionConfiguredEvent;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::user_input::UserInput;
-use tokio::io::AsyncBufReadExt;
-use tokio::io::AsyncWriteExt;
-use tokio::io::BufReader;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::oneshot;
 use tokio::sync::watch;
 
+use crate::app_server::approval_policy::ApprovalAsker;
+use crate::app_server::approval_policy::ApprovalPolicy;
+use crate::app_server::approval_policy::ApprovalPolicySettings;
+use crate::app_server::approval_policy::ApprovalQuestion;
+use crate::app_server::potter::ApprovalRequestParams;
+use crate::app_server::potter::ApprovalRequestResponse;
+use crate::app_server::potter::POTTER_APPROVAL_REQUEST_METHOD;
 use crate::app_server::potter::POTTER_EVENT_NOTIFICATION_METHOD;
+use crate::app_server::potter::POTTER_USER_INPUT_REQUEST_METHOD;
 use crate::app_server::potter::PotterAppServerClientNotification;
 use crate::app_server::potter::PotterAppServerClientRequest;
 use crate::app_server::potter::PotterEventMode;
+use crate::app_server::potter::PotterProjectEvent;
+use crate::app_server::potter::ProjectAttachParams;
+use crate::app_server::potter::ProjectAttachResponse;
+use crate::app_server::potter::ProjectFrontMatter;
+use crate::app_server::potter::ProjectGetParams;
+use crate::app_server::potter::ProjectGetResponse;
 use crate::app_server::potter::ProjectInterruptParams;
 use crate::app_server::potter::ProjectListEntry;
 use crate::app_server::potter::ProjectListParams;
 use crate::app_server::potter::ProjectListResponse;
+use crate::app_server::potter::ProjectLiveState;
+use crate::app_server::potter::ProjectLiveStatus;
 use crate::app_server::potter::ProjectResolveInterruptParams;
 use crate::app_server::potter::ProjectResolveInterruptResponse;
 use crate::app_server::potter::ProjectResumeParams;
@@ -51,12 +78,28 @@ use crate::app_server::potter::ProjectResumeReplay;
 use crate::app_server::potter::ProjectResumeReplayRound;
 use crate::app_server::potter::ProjectResumeResponse;
 use crate::app_server::potter::ProjectResumeUnfinishedRound;
+use crate::app_server::potter::ProjectRoundSummary;
 use crate::app_server::potter::ProjectStartParams;
 use crate::app_server::potter::ProjectStartResponse;
 use crate::app_server::potter::ProjectStartRoundsParams;
 use crate::app_server::potter::ProjectStartRoundsResponse;
+use crate::app_server::potter::ProjectStatusResponse;
+use crate::app_server::potter::ProjectTasks;
+use crate::app_server::potter::QueueAddParams;
+use crate::app_server::potter::QueueAddResponse;
+use crate::app_server::potter::QueueListResponse;
+use crate::app_server::potter::QueueRemoveParams;
+use crate::app_server::potter::QueueRemoveResponse;
+use crate::app_server::potter::QueueReorderParams;
+use crate::app_server::potter::QueueTakeResponse;
+use crate::app_server::potter::QueuedPromptEntry;
 use crate::app_server::potter::ResolveInterruptAction;
 use crate::app_server::potter::ResumePolicy;
+use crate::app_server::potter::UserInputRequestParams;
+use crate::app_server::potter::UserInputRequestResponse;
+use crate::app_server::potter::event_journal::EventJournal;
+use crate::app_server::potter::transport::PotterAppServerListener;
+use crate::app_server::potter::transport::PotterAppServerTransport;
 use crate::app_server::upstream_protocol::JSONRPCError;
 use crate::app_server::upstream_protocol::JSONRPCErrorError;
 use crate::app_server::upstream_protocol::JSONRPCMessage;
@@ -64,6 +107,9 @@ use crate::app_server::upstream_protocol::JSONRPCNotification;
 use crate::app_server::upstream_protocol::JSONRPCRequest;
 use crate::app_server::upstream_protocol::JSONRPCResponse;
 use crate::app_server::upstream_protocol::RequestId;
+use crate::app_server::user_input::UserInputAsker;
+use crate::app_server::user_input::UserInputQuestion;
+use crate::file_lock::FileLock;
 
 #[derive(Debug, Clone)]
 pub struct PotterAppServerConfig {
@@ -73,11 +119,20 @@ pub struct PotterAppServerConfig {
     pub codex_compat_home: Option<PathBuf>,
     pub rounds: NonZeroUsize,
     pub upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+    pub project_cli_args: super::PotterProjectCliArgs,
+    /// Implement/review round schedule from `config.toml`.
+    pub round_schedule: crate::workflow::round_schedule::RoundSchedule,
+    /// `[approvals]` from the user `config.toml`; layered with the project's repo config.
+    pub approval_settings: crate::app_server::approval_policy::ApprovalPolicySettings,
+    /// `[stream_recovery]` from the user `config.toml`.
+    pub stream_recovery: crate::app_server::stream_recovery::StreamRecoveryPolicy,
+    /// Start prompts from the persistent queue as projects whenever a project slot is free
+    /// (`app-server --listen`). Otherwise clients take queued prompts themselves.
+    pub drain_queue: bool,
 }
 
 #[derive(Debug)]
 struct RunningProject {
-    project_id: String,
     handle: tokio::task::JoinHandle<()>,
     interrupt_tx: watch::Sender<bool>,
 }
@@ -87,6 +142,7 @@ struct ResumedProject {
     project_id: String,
     resolved: crate::workflow::resume::ResolvedProjectPaths,
     progress_file_rel: PathBuf,
+    worktree_dir: Option<PathBuf>,
     potter_rollout_lines: Vec<crate::workflow::rollout::PotterRolloutLine>,
     index: crate::workflow::rollout_resume_index::PotterRolloutResumeIndex,
 }
@@ -97,18 +153,67 @@ struct InterruptedProject {
     user_prompt_file: PathBuf,
     rounds_run: u32,
     plan: FreshProjectPlan,
+    pause: ProjectPause,
+}
+
+/// Why a fresh project is paused waiting for `project/resolve_interrupt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ProjectPause {
+    /// The user interrupted a round (Esc).
+    Interrupted,
+    /// The planning round finished and `--review-plan` is waiting for the plan to be approved.
+    PlanApproval { todo: Vec<String> },
 }
 
 struct ServerState {
     config: PotterAppServerConfig,
-    running: Option<RunningProject>,
+    /// Running projects keyed by project id.
+    running: BTreeMap<String, RunningProject>,
     resumed: Option<ResumedProject>,
-    interrupted: Option<InterruptedProject>,
+    /// Interrupted projects (waiting for `project/resolve_interrupt`) keyed by project id.
+    interrupted: BTreeMap<String, InterruptedProject>,
+    /// Ownership locks of the running and interrupted projects, keyed by project id (see
+    /// [`crate::workflow::project_owner`]).
+    owners: BTreeMap<String, FileLock>,
+    /// `approval/request` calls sent to the client and waiting for its answer.
+    pending_approvals: PendingApprovals,
+    /// `userInput/request` calls sent to the client and waiting for its answer.
+    pending_user_inputs: PendingUserInputs,
+    /// What was sent for each active project, replayed by `project/attach`.
+    journal: EventJournal,
+}
+
+/// Server-to-client `approval/request` calls keyed by JSON-RPC request id.
+#[derive(Debug, Default)]
+struct PendingApprovals {
+    next_id: u64,
+    replies: HashMap<String, oneshot::Sender<ApprovalRequestResponse>>,
+}
+
+/// Server-to-client `userInput/request` calls keyed by JSON-RPC request id.
+#[derive(Debug, Default)]
+struct PendingUserInputs {
+    next_id: u64,
+    replies: HashMap<String, oneshot::Sender<UserInputRequestResponse>>,
 }
 
 enum InternalEvent {
-    ProjectFinished { project_id: String },
-    ProjectInterrupted { project: Box<InterruptedProject> },
+    ProjectFinished {
+        project_id: String,
+    },
+    ProjectInterrupted {
+        project: Box<InterruptedProject>,
+    },
+    /// A round of `project_id` asks the user to decide an approval request.
+    ApprovalRequested {
+        project_id: String,
+        question: ApprovalQuestion,
+    },
+    /// A round of `project_id` asks the user to answer questions.
+    UserInputRequested {
+        project_id: String,
+        question: UserInputQuestion,
+    },
 }
 
 enum ProjectRunExit {
@@ -116,119 +221,137 @@ enum ProjectRunExit {
     Interrupted(Box<InterruptedProject>),
 }
 
-fn decode_jsonrpc_message_line(line: &str) -> anyhow::Result<Option<JSONRPCMessage>> {
-    let trimmed = line.trim();
-    if trimmed.is_empty() {
-        return Ok(None);
-    }
-
-    let msg: JSONRPCMessage = serde_json::from_str(trimmed)
-        .with_context(|| format!("decode potter app-server JSON-RPC: {trimmed:?}"))?;
-    Ok(Some(msg))
-}
-
-pub async fn run_potter_app_server(config: PotterAppServerConfig) -> anyhow::Result<()> {
+/// Run the server until stdin reaches EOF ([`PotterAppServerTransport::Stdio`]) or, when
+/// listening, until the process is stopped.
+pub async fn run_potter_app_server(
+    config: PotterAppServerConfig,
+    transport: PotterAppServerTransport,
+) -> anyhow::Result<()> {
     tokio::task::LocalSet::new()
-        .run_until(run_potter_app_server_inner(config))
+        .run_until(run_potter_app_server_inner(config, transport))
         .await
 }
 
-async fn run_potter_app_server_inner(config: PotterAppServerConfig) -> anyhow::Result<()> {
-    let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
-    let writer = tokio::spawn(async move {
-        let mut stdout = tokio::io::stdout();
-        while let Some(msg) = writer_rx.recv().await {
-            let json = serde_json::to_vec(&msg).context("serialize potter app-server jsonrpc")?;
-            stdout
-                .write_all(&json)
-                .await
-                .context("write potter app-server stdout")?;
-            stdout
-                .write_all(b"\n")
-                .await
-                .context("write potter app-server newline")?;
-            stdout
-                .flush()
-                .await
-                .context("flush potter app-server stdout")?;
+async fn run_potter_app_server_inner(
+    config: PotterAppServerConfig,
+    transport: PotterAppServerTransport,
+) -> anyhow::Result<()> {
+    let (transport_tx, writer_rx) = unbounded_channel::<JSONRPCMessage>();
+    let (incoming_tx, mut incoming_rx) = unbounded_channel::<JSONRPCMessage>();
+    let transport = match transport {
+        PotterAppServerTransport::Stdio => {
+            tokio::spawn(super::transport::serve_stdio(incoming_tx, writer_rx))
         }
-        Ok::<(), anyhow::Error>(())
-    });
+        PotterAppServerTransport::Listen { address, token } => {
+            let listener = PotterAppServerListener::bind(&address, token).await?;
+            eprintln!(
+                "codex-potter app-server listening on {}",
+                listener.address()
+            );
+            tokio::spawn(listener.serve(incoming_tx, writer_rx))
+        }
+    };
 
+    // Outgoing messages pass through the journal on their way to the transport.
+    let (writer_tx, mut outgoing_rx) = unbounded_channel::<JSONRPCMessage>();
     let (internal_tx, mut internal_rx) = unbounded_channel::<InternalEvent>();
     let mut state = ServerState {
         config,
-        running: None,
+        running: BTreeMap::new(),
         resumed: None,
-        interrupted: None,
+        interrupted: BTreeMap::new(),
+        owners: BTreeMap::new(),
+        pending_approvals: PendingApprovals::default(),
+        pending_user_inputs: PendingUserInputs::default(),
+        journal: EventJournal::default(),
     };
 
-    let stdin = tokio::io::stdin();
-    let mut lines = BufReader::new(stdin).lines();
-
     loop {
         tokio::select! {
-            maybe_line = lines.next_line() => {
-                let Some(line) = maybe_line.context("read potter app-server stdin line")? else {
+            msg = incoming_rx.recv() => {
+                let Some(msg) = msg else {
                     break;
                 };
-
-                let msg = match decode_jsonrpc_message_line(&line) {
-                    Ok(Some(msg)) => msg,
-                    Ok(None) => continue,
-                    Err(err) => {
-                        eprintln!("warning: {err:#}");
-                        continue;
-                    }
-                };
                 handle_jsonrpc_message(msg, &mut state, &writer_tx, &internal_tx).await;
             }
+            Some(msg) = outgoing_rx.recv() => {
+                forward_outgoing(&mut state.journal, &transport_tx, msg);
+            }
             Some(event) = internal_rx.recv() => match event {
                 InternalEvent::ProjectFinished { project_id } => {
-                    if state
-                        .running
-                        .as_ref()
-                        .is_some_and(|running| running.project_id == project_id)
-                    {
-                        state.running = None;
-                    }
+                    state.running.remove(&project_id);
+                    state.owners.remove(&project_id);
+                    drain_prompt_queue(&mut state, &writer_tx, &internal_tx).await;
                 }
                 InternalEvent::ProjectInterrupted { project } => {
                     let project = *project;
-                    if state
-                        .running
-                        .as_ref()
-                        .is_some_and(|running| running.project_id == project.project_id)
-                    {
-                        state.running = None;
-                    }
+                    state.running.remove(&project.project_id);
                     state.resumed = None;
-                    state.interrupted = Some(project);
 
-                    let project = state
-                        .interrupted
-                        .as_ref()
-                        .expect("interrupted project just set");
+                    let msg = match &project.pause {
+                        ProjectPause::Interrupted => EventMsg::PotterProjectInterrupted {
+                            project_id: project.project_id.clone(),
+                            user_prompt_file: project.user_prompt_file.clone(),
+                        },
+                        ProjectPause::PlanApproval { todo } => {
+                            EventMsg::PotterProjectAwaitingPlanApproval {
+                                project_id: project.project_id.clone(),
+                                user_prompt_file: project.user_prompt_file.clone(),
+                                todo: todo.clone(),
+                            }
+                        }
+                    };
                     emit_potter_event(
                         writer_tx.clone(),
+                        &project.project_id,
                         Event {
                             id: "".to_string(),
-                            msg: EventMsg::PotterProjectInterrupted {
-                                project_id: project.project_id.clone(),
-                                user_prompt_file: project.user_prompt_file.clone(),
-                            },
+                            msg,
                         },
                     );
+                    state
+                        .interrupted
+                        .insert(project.project_id.clone(), project);
+                }
+                InternalEvent::ApprovalRequested { project_id, question } => {
+                    send_approval_request(
+                        &mut state.pending_approvals,
+                        &writer_tx,
+                        &project_id,
+                        question,
+                    );
+                }
+                InternalEvent::UserInputRequested { project_id, question } => {
+                    send_user_input_request(
+                        &mut state.pending_user_inputs,
+                        &writer_tx,
+                        &project_id,
+                        question,
+                    );
                 }
             }
         }
     }
 
     drop(writer_tx);
-    let _ = writer.await;
+    while let Some(msg) = outgoing_rx.recv().await {
+        forward_outgoing(&mut state.journal, &transport_tx, msg);
+    }
+    drop(transport_tx);
+    let _ = transport.await;
     Ok(())
 }
 
+/// Record an outgoing message for `project/attach` and hand it to the transport.
+fn forward_outgoing(
+    journal: &mut EventJournal,
+    transport_tx: &UnboundedSender<JSONRPCMessage>,
+    mut msg: JSONRPCMessage,
+) {
+    journal.record(&mut msg);
+    let _ = transport_tx.send(msg);
+}
+
 async fn handle_jsonrpc_message(
     msg: JSONRPCMessage,
     state: &mut ServerState,
@@ -246,10 +369,200 @@ async fn handle_jsonrpc_message(
                 eprintln!("potter app-server notification failed: {err:#}");
             }
         }
-        JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => {}
+        JSONRPCMessage::Response(response) => {
+            state.journal.resolve_request(&response.id);
+            if let Some(reply_tx) = state.pending_user_inputs.take(&response.id) {
+                match serde_json::from_value::<UserInputRequestResponse>(response.result) {
+                    Ok(answer) => {
+                        let _ = reply_tx.send(answer);
+                    }
+                    Err(err) => {
+                        eprintln!(
+                            "warning: invalid {POTTER_USER_INPUT_REQUEST_METHOD} response: {err}"
+                        );
+                    }
+                }
+                return;
+            }
+            let Some(reply_tx) = state.pending_approvals.take(&response.id) else {
+                return;
+            };
+            match serde_json::from_value::<ApprovalRequestResponse>(response.result) {
+                Ok(answer) => {
+                    let _ = reply_tx.send(answer);
+                }
+                Err(err) => {
+                    eprintln!("warning: invalid {POTTER_APPROVAL_REQUEST_METHOD} response: {err}");
+                }
+            }
+        }
+        // Dropping the reply channel counts as "no answer", which denies (or declines) the
+        // request.
+        JSONRPCMessage::Error(error) => {
+            state.journal.resolve_request(&error.id);
+            state.pending_approvals.take(&error.id);
+            state.pending_user_inputs.take(&error.id);
+        }
+    }
+}
+
+impl PendingApprovals {
+    fn take(&mut self, id: &RequestId) -> Option<oneshot::Sender<ApprovalRequestResponse>> {
+        let RequestId::String(id) = id else {
+            return None;
+        };
+        self.replies.remove(id)
+    }
+}
+
+impl PendingUserInputs {
+    fn take(&mut self, id: &RequestId) -> Option<oneshot::Sender<UserInputRequestResponse>> {
+        let RequestId::String(id) = id else {
+            return None;
+        };
+        self.replies.remove(id)
+    }
+}
+
+/// Forward an `ask` approval question to the client as an `approval/request` call.
+fn send_approval_request(
+    pending: &mut PendingApprovals,
+    writer_tx: &UnboundedSender<JSONRPCMessage>,
+    project_id: &str,
+    question: ApprovalQuestion,
+) {
+    pending.next_id = pending.next_id.saturating_add(1);
+    let id = format!("approval-{}", pending.next_id);
+    let ApprovalQuestion {
+        kind,
+        summary,
+        reason,
+        reply_tx,
+    } = question;
+    let params = ApprovalRequestParams {
+        project_id: Some(project_id.to_string()),
+        kind,
+        summary,
+        reason,
+    };
+    let params = match serde_json::to_value(params) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("warning: encode {POTTER_APPROVAL_REQUEST_METHOD} params: {err}");
+            return;
+        }
+    };
+
+    if writer_tx
+        .send(JSONRPCMessage::Request(JSONRPCRequest {
+            id: RequestId::String(id.clone()),
+            method: POTTER_APPROVAL_REQUEST_METHOD.to_string(),
+            params: Some(params),
+        }))
+        .is_ok()
+    {
+        pending.replies.insert(id, reply_tx);
+    }
+}
+
+/// Create the asker for the rounds of `project_id`: questions are forwarded to the main loop,
+/// which sends them to the client.
+fn spawn_approval_question_forwarder(
+    project_id: String,
+    internal_tx: UnboundedSender<InternalEvent>,
+) -> ApprovalAsker {
+    let (question_tx, mut question_rx) = unbounded_channel::<ApprovalQuestion>();
+    tokio::task::spawn_local(async move {
+        while let Some(question) = question_rx.recv().await {
+            let event = InternalEvent::ApprovalRequested {
+                project_id: project_id.clone(),
+                question,
+            };
+            if internal_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    ApprovalAsker::new(question_tx)
+}
+
+/// Forward user input questions to the client as a `userInput/request` call.
+fn send_user_input_request(
+    pending: &mut PendingUserInputs,
+    writer_tx: &UnboundedSender<JSONRPCMessage>,
+    project_id: &str,
+    question: UserInputQuestion,
+) {
+    pending.next_id = pending.next_id.saturating_add(1);
+    let id = format!("user-input-{}", pending.next_id);
+    let UserInputQuestion {
+        server_name,
+        message,
+        questions,
+        reply_tx,
+    } = question;
+    let params = UserInputRequestParams {
+        project_id: Some(project_id.to_string()),
+        server_name,
+        message,
+        questions,
+    };
+    let params = match serde_json::to_value(params) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("warning: encode {POTTER_USER_INPUT_REQUEST_METHOD} params: {err}");
+            return;
+        }
+    };
+
+    if writer_tx
+        .send(JSONRPCMessage::Request(JSONRPCRequest {
+            id: RequestId::String(id.clone()),
+            method: POTTER_USER_INPUT_REQUEST_METHOD.to_string(),
+            params: Some(params),
+        }))
+        .is_ok()
+    {
+        pending.replies.insert(id, reply_tx);
     }
 }
 
+/// Create the user input asker for the rounds of `project_id`, like
+/// [`spawn_approval_question_forwarder`].
+fn spawn_user_input_question_forwarder(
+    project_id: String,
+    internal_tx: UnboundedSender<InternalEvent>,
+) -> UserInputAsker {
+    let (question_tx, mut question_rx) = unbounded_channel::<UserInputQuestion>();
+    tokio::task::spawn_local(async move {
+        while let Some(question) = question_rx.recv().await {
+            let event = InternalEvent::UserInputRequested {
+                project_id: project_id.clone(),
+                question,
+            };
+            if internal_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    UserInputAsker::new(question_tx)
+}
+
+/// Approval policy of a project in `workdir`; `--ask-approval` asks instead of approving
+/// requests no rule decides.
+fn project_approval_policy(
+    settings: ApprovalPolicySettings,
+    ask_approval: bool,
+    workdir: &Path,
+) -> anyhow::Result<ApprovalPolicy> {
+    let policy = ApprovalPolicy::for_workdir(settings, workdir)?;
+    Ok(if ask_approval {
+        policy.ask_by_default()
+    } else {
+        policy
+    })
+}
+
 async fn handle_notification(notification: JSONRPCNotification) -> anyhow::Result<()> {
     let _notification = PotterAppServerClientNotification::try_from(notification)?;
     Ok(())
@@ -290,22 +603,8 @@ async fn handle_request(
             Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
         },
         PotterAppServerClientRequest::ProjectStart { request_id, params } => {
-            if state.running.is_some() {
-                send_error(
-                    writer_tx,
-                    request_id,
-                    -32000,
-                    "a project is already running".to_string(),
-                );
-                return Ok(());
-            }
-            if state.interrupted.is_some() {
-                send_error(
-                    writer_tx,
-                    request_id,
-                    -32000,
-                    "a project is interrupted; resolve it first".to_string(),
-                );
+            if let Some(message) = project_start_unavailable_reason(state) {
+                send_error(writer_tx, request_id, -32000, message);
                 return Ok(());
             }
 
@@ -315,7 +614,7 @@ async fn handle_request(
             }
         }
         PotterAppServerClientRequest::ProjectResume { request_id, params } => {
-            if state.running.is_some() {
+            if !state.running.is_empty() {
                 send_error(
                     writer_tx,
                     request_id,
@@ -324,7 +623,7 @@ async fn handle_request(
                 );
                 return Ok(());
             }
-            if state.interrupted.is_some() {
+            if !state.interrupted.is_empty() {
                 send_error(
                     writer_tx,
                     request_id,
@@ -340,7 +639,7 @@ async fn handle_request(
             }
         }
         PotterAppServerClientRequest::ProjectStartRounds { request_id, params } => {
-            if state.running.is_some() {
+            if !state.running.is_empty() {
                 send_error(
                     writer_tx,
                     request_id,
@@ -349,7 +648,7 @@ async fn handle_request(
                 );
                 return Ok(());
             }
-            if state.interrupted.is_some() {
+            if !state.interrupted.is_empty() {
                 send_error(
                     writer_tx,
                     request_id,
@@ -375,6 +674,102 @@ async fn handle_request(
                 Ok(response) => send_response(writer_tx, request_id, response),
                 Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
             }
+            // Stopping an interrupted project frees its slot.
+            drain_prompt_queue(state, writer_tx, internal_tx).await;
+        }
+        PotterAppServerClientRequest::ProjectAttach { request_id, params } => {
+            match attach_project(state, params) {
+                Ok(response) => send_response(writer_tx, request_id, response),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+        }
+        // Routing is per connection, so the transport handles detaching; the server only acks.
+        PotterAppServerClientRequest::ProjectDetach { request_id, .. } => {
+            send_response(writer_tx, request_id, serde_json::json!({}));
+        }
+        PotterAppServerClientRequest::ProjectGet { request_id, params } => {
+            match get_project(state, params) {
+                Ok(response) => send_response(writer_tx, request_id, response),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+        }
+        PotterAppServerClientRequest::ProjectStatus { request_id, .. } => {
+            send_response(
+                writer_tx,
+                request_id,
+                ProjectStatusResponse {
+                    projects: live_projects(state),
+                },
+            );
+        }
+        PotterAppServerClientRequest::QueueAdd { request_id, params } => {
+            let QueueAddParams { prompt } = params;
+            match run_prompt_queue_op(&state.config.default_workdir, move |workdir| {
+                crate::workflow::prompt_queue::push_queued_prompt(workdir, prompt)
+            })
+            .await
+            {
+                Ok(entry) => send_response(
+                    writer_tx,
+                    request_id,
+                    QueueAddResponse {
+                        entry: queued_prompt_entry(entry),
+                    },
+                ),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+            drain_prompt_queue(state, writer_tx, internal_tx).await;
+        }
+        PotterAppServerClientRequest::QueueList { request_id, .. } => {
+            match crate::workflow::prompt_queue::read_prompt_queue(&state.config.default_workdir) {
+                Ok(queue) => send_response(writer_tx, request_id, queue_list_response(queue)),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+        }
+        PotterAppServerClientRequest::QueueRemove { request_id, params } => {
+            let QueueRemoveParams { id } = params;
+            match run_prompt_queue_op(&state.config.default_workdir, move |workdir| {
+                crate::workflow::prompt_queue::remove_queued_prompt(workdir, &id)
+            })
+            .await
+            {
+                Ok(entry) => send_response(
+                    writer_tx,
+                    request_id,
+                    QueueRemoveResponse {
+                        entry: queued_prompt_entry(entry),
+                    },
+                ),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+        }
+        PotterAppServerClientRequest::QueueReorder { request_id, params } => {
+            let QueueReorderParams { ids } = params;
+            match run_prompt_queue_op(&state.config.default_workdir, move |workdir| {
+                crate::workflow::prompt_queue::reorder_prompt_queue(workdir, &ids)
+            })
+            .await
+            {
+                Ok(queue) => send_response(writer_tx, request_id, queue_list_response(queue)),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
+        }
+        PotterAppServerClientRequest::QueueTake { request_id, .. } => {
+            match run_prompt_queue_op(
+                &state.config.default_workdir,
+                crate::workflow::prompt_queue::take_queued_prompt,
+            )
+            .await
+            {
+                Ok(entry) => send_response(
+                    writer_tx,
+                    request_id,
+                    QueueTakeResponse {
+                        entry: entry.map(queued_prompt_entry),
+                    },
+                ),
+                Err(err) => send_error(writer_tx, request_id, -32000, format!("{err:#}")),
+            }
         }
     }
 
@@ -382,12 +777,108 @@ async fn handle_request(
 }
 
 fn clear_finished_running_project(state: &mut ServerState) {
-    if state
+    state
         .running
-        .as_ref()
-        .is_some_and(|running| running.handle.is_finished())
-    {
-        state.running = None;
+        .retain(|_, running| !running.handle.is_finished());
+}
+
+/// Returns why `project/start` cannot start another project right now, if it cannot.
+///
+/// Interrupted projects still hold a slot: they continue once resolved.
+fn project_start_unavailable_reason(state: &ServerState) -> Option<String> {
+    let limit = state.config.project_cli_args.parallel_projects().get();
+    if state.running.len() + state.interrupted.len() < limit {
+        return None;
+    }
+    if limit > 1 {
+        return Some(format!(
+            "{limit} projects are already active (--parallel {limit})"
+        ));
+    }
+    if state.running.is_empty() {
+        Some("a project is interrupted; resolve it first".to_string())
+    } else {
+        Some("a project is already running".to_string())
+    }
+}
+
+/// Start prompts from the persistent queue while project slots are free (only with
+/// [`PotterAppServerConfig::drain_queue`]).
+///
+/// A prompt leaves the queue once its project started; if starting fails it stays queued and is
+/// retried after the next project.
+async fn drain_prompt_queue(
+    state: &mut ServerState,
+    writer_tx: &UnboundedSender<JSONRPCMessage>,
+    internal_tx: &UnboundedSender<InternalEvent>,
+) {
+    if !state.config.drain_queue {
+        return;
+    }
+    clear_finished_running_project(state);
+    while project_start_unavailable_reason(state).is_none() {
+        let workdir = state.config.default_workdir.clone();
+        // Take the entry before starting it, so another server draining the same queue cannot
+        // start it too.
+        let next =
+            match run_prompt_queue_op(&workdir, crate::workflow::prompt_queue::take_queued_prompt)
+                .await
+            {
+                Ok(Some(next)) => next,
+                Ok(None) => return,
+                Err(err) => {
+                    eprintln!("warning: take from prompt queue: {err:#}");
+                    return;
+                }
+            };
+
+        let params = ProjectStartParams {
+            user_message: next.prompt.clone(),
+            cwd: None,
+            rounds: None,
+            event_mode: None,
+            output_schema: None,
+        };
+        if let Err(err) = start_project(state, params, writer_tx, internal_tx).await {
+            eprintln!("warning: start queued prompt {}: {err:#}", next.id);
+            if let Err(err) = run_prompt_queue_op(&workdir, move |workdir| {
+                crate::workflow::prompt_queue::restore_queued_prompt(workdir, next)
+            })
+            .await
+            {
+                eprintln!("warning: put queued prompt back: {err:#}");
+            }
+            return;
+        }
+    }
+}
+
+/// Run a change to the persistent prompt queue of `workdir` on the blocking pool: it waits for the
+/// queue lock, which another app-server or client may be holding, and must not stall the server
+/// loop meanwhile.
+async fn run_prompt_queue_op<T: Send + 'static>(
+    workdir: &Path,
+    op: impl FnOnce(&Path) -> anyhow::Result<T> + Send + 'static,
+) -> anyhow::Result<T> {
+    let workdir = workdir.to_path_buf();
+    tokio::task::spawn_blocking(move || op(&workdir))
+        .await
+        .context("prompt queue task panicked")?
+}
+
+fn queued_prompt_entry(entry: crate::workflow::prompt_queue::QueuedPrompt) -> QueuedPromptEntry {
+    QueuedPromptEntry {
+        id: entry.id,
+        prompt: entry.prompt,
+        queued_at_unix_secs: entry.queued_at_unix_secs,
+    }
+}
+
+fn queue_list_response(
+    queue: Vec<crate::workflow::prompt_queue::QueuedPrompt>,
+) -> QueueListResponse {
+    QueueListResponse {
+        entries: queue.into_iter().map(queued_prompt_entry).collect(),
     }
 }
 
@@ -415,6 +906,7 @@ fn project_list(
             created_at_unix_secs: created_at,
             updated_at_unix_secs: updated_at,
             git_branch: row.git_branch,
+            worktree: row.worktree,
         });
     }
 
@@ -432,15 +924,26 @@ async fn start_project(
         cwd,
         rounds,
         event_mode,
+        output_schema,
     } = params;
+    check_output_schema(output_schema.as_ref())?;
 
     let workdir = cwd.unwrap_or_else(|| state.config.default_workdir.clone());
     let workdir = workdir
         .canonicalize()
         .with_context(|| format!("canonicalize {}", workdir.display()))?;
 
-    let init = crate::workflow::project::init_project(&workdir, &user_message, Local::now())
-        .context("initialize .codexpotter project")?;
+    let init = crate::workflow::project::init_project(
+        &workdir,
+        &user_message,
+        Local::now(),
+        state.config.project_cli_args.worktree_enabled(),
+    )
+    .context("initialize .codexpotter project")?;
+    let worktree_dir = init
+        .worktree
+        .as_ref()
+        .map(|worktree| workdir.join(&worktree.dir_rel));
     let progress_file_abs = workdir.join(&init.progress_file_rel);
     let project_dir_rel = init
         .progress_file_rel
@@ -464,12 +967,14 @@ async fn start_project(
     spawn_fresh_project(
         &mut state.running,
         &mut state.resumed,
+        &mut state.owners,
         state.config.clone(),
         writer_tx.clone(),
         internal_tx.clone(),
         project_id.clone(),
         FreshProjectPlan {
             workdir: workdir.clone(),
+            worktree_dir: worktree_dir.clone(),
             user_message: user_message.clone(),
             project_dir_rel: project_dir_rel.clone(),
             progress_file_rel: init.progress_file_rel.clone(),
@@ -477,6 +982,7 @@ async fn start_project(
             potter_rollout_path,
             rounds_total: rounds_total_u32,
             event_mode: mode,
+            output_schema,
             project_started_at: Instant::now(),
             round_start_index: 0,
             emit_project_started_event: true,
@@ -492,6 +998,7 @@ async fn start_project(
         progress_file: progress_file_abs,
         git_commit_start: init.git_commit_start,
         git_branch,
+        worktree: worktree_dir,
         rounds_total: rounds_total_u32,
     })
 }
@@ -517,6 +1024,10 @@ fn resume_project(
 
     let git_branch = crate::workflow::project::progress_file_git_branch(&resolved.progress_file)
         .context("read git_branch from progress file")?;
+    let worktree_dir = crate::workflow::worktree::resolve_project_worktree_dir(
+        &resolved.workdir,
+        &resolved.progress_file,
+    )?;
 
     let potter_rollout_path = crate::workflow::rollout::potter_rollout_path(&resolved.project_dir);
     let potter_rollout_lines = load_potter_rollout_lines(&potter_rollout_path)?;
@@ -531,6 +1042,7 @@ fn resume_project(
         project_id: project_id.clone(),
         resolved: resolved.clone(),
         progress_file_rel: progress_file_rel.clone(),
+        worktree_dir: worktree_dir.clone(),
         potter_rollout_lines,
         index,
     });
@@ -542,11 +1054,21 @@ fn resume_project(
         progress_file_rel,
         progress_file: resolved.progress_file,
         git_branch,
+        worktree: worktree_dir,
         replay,
         unfinished_round,
     })
 }
 
+/// Reject `outputSchema`s the report check cannot enforce.
+fn check_output_schema(output_schema: Option<&serde_json::Value>) -> anyhow::Result<()> {
+    let Some(output_schema) = output_schema else {
+        return Ok(());
+    };
+    crate::workflow::report::check_supported_schema(output_schema)
+        .map_err(|err| anyhow::anyhow!("invalid outputSchema: {err}"))
+}
+
 async fn start_rounds(
     state: &mut ServerState,
     params: ProjectStartRoundsParams,
@@ -558,7 +1080,9 @@ async fn start_rounds(
         rounds,
         resume_policy,
         event_mode,
+        output_schema,
     } = params;
+    check_output_schema(output_schema.as_ref())?;
 
     let Some(resumed) = state.resumed.clone() else {
         anyhow::bail!("no resumed project is active");
@@ -597,6 +1121,7 @@ async fn start_rounds(
     spawn_resumed_project(
         &mut state.running,
         &mut state.resumed,
+        &mut state.owners,
         state.config.clone(),
         writer_tx.clone(),
         internal_tx.clone(),
@@ -609,6 +1134,7 @@ async fn start_rounds(
             rounds_total: rounds_total_u32,
             resume_policy,
             event_mode: mode,
+            output_schema,
             project_started_at: Instant::now(),
         },
     )?;
@@ -618,33 +1144,187 @@ async fn start_rounds(
     })
 }
 
+fn attach_project(
+    state: &ServerState,
+    params: ProjectAttachParams,
+) -> anyhow::Result<ProjectAttachResponse> {
+    let ProjectAttachParams { project_id } = params;
+
+    let active_project_ids = state
+        .running
+        .keys()
+        .chain(state.interrupted.keys())
+        .cloned()
+        .collect::<Vec<_>>();
+    let project_id = match project_id {
+        Some(project_id) => {
+            anyhow::ensure!(
+                active_project_ids.contains(&project_id),
+                "project is not running: {project_id}"
+            );
+            project_id
+        }
+        None => match active_project_ids.as_slice() {
+            [project_id] => project_id.clone(),
+            [] => anyhow::bail!("no project is running"),
+            _ => anyhow::bail!(
+                "several projects are running ({}); pass projectId",
+                active_project_ids.join(",")
+            ),
+        },
+    };
+
+    // Project ids are absolute progress file paths.
+    let resolved = crate::workflow::resume::resolve_project_paths(
+        &state.config.default_workdir,
+        Path::new(&project_id),
+    )?;
+    let git_branch = crate::workflow::project::progress_file_git_branch(&resolved.progress_file)
+        .context("read git_branch from progress file")?;
+    let worktree_dir = crate::workflow::worktree::resolve_project_worktree_dir(
+        &resolved.workdir,
+        &resolved.progress_file,
+    )?;
+    let replay = state
+        .journal
+        .replay(&project_id)
+        .context("replay project events")?;
+
+    Ok(ProjectAttachResponse {
+        project_id,
+        working_dir: resolved.workdir,
+        project_dir: resolved.project_dir,
+        progress_file: resolved.progress_file,
+        git_branch,
+        worktree: worktree_dir,
+        events: replay.events,
+        pending_requests: replay.pending_requests,
+    })
+}
+
+fn get_project(
+    state: &ServerState,
+    params: ProjectGetParams,
+) -> anyhow::Result<ProjectGetResponse> {
+    let ProjectGetParams { project_path, cwd } = params;
+
+    let cwd = cwd.unwrap_or_else(|| state.config.default_workdir.clone());
+    let resolved = crate::workflow::resume::resolve_project_paths(&cwd, &project_path)?;
+    let overview = crate::workflow::project::read_progress_file_overview(&resolved.progress_file)?;
+    let rounds = completed_round_summaries(&resolved.project_dir)?;
+
+    let project_id = resolved.progress_file.to_string_lossy().to_string();
+    let live = live_projects(state)
+        .into_iter()
+        .find(|live| live.project_id == project_id);
+
+    Ok(ProjectGetResponse {
+        project_id,
+        project_dir: resolved.project_dir,
+        progress_file: resolved.progress_file,
+        front_matter: ProjectFrontMatter {
+            status: overview.status,
+            short_title: overview.short_title,
+            finite_incantatem: overview.finite_incantatem,
+            git_commit: overview.git_commit,
+        },
+        tasks: ProjectTasks {
+            in_progress: overview.in_progress,
+            todo: overview.todo,
+            done: overview.done,
+        },
+        rounds,
+        live,
+    })
+}
+
+/// Outcomes of the rounds `potter-rollout.jsonl` records as finished.
+///
+/// The rollout of a running project ends mid-round, so only the lines up to the last finished
+/// round are indexed. Projects that have not written a rollout yet have no rounds.
+fn completed_round_summaries(project_dir: &Path) -> anyhow::Result<Vec<ProjectRoundSummary>> {
+    let potter_rollout_path = crate::workflow::rollout::potter_rollout_path(project_dir);
+    if !potter_rollout_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let mut lines = crate::workflow::rollout::read_lines(&potter_rollout_path)
+        .with_context(|| format!("read {}", potter_rollout_path.display()))?;
+    let finished_lines = lines
+        .iter()
+        .rposition(|line| {
+            matches!(
+                line,
+                crate::workflow::rollout::PotterRolloutLine::RoundFinished { .. }
+                    | crate::workflow::rollout::PotterRolloutLine::ProjectStalled { .. }
+            )
+        })
+        .map_or(0, |index| index + 1);
+    lines.truncate(finished_lines);
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let index = crate::workflow::rollout_resume_index::build_resume_index(&lines)?;
+    Ok(index
+        .completed_rounds
+        .into_iter()
+        .map(|round| ProjectRoundSummary {
+            current: round.round_current,
+            total: round.round_total,
+            kind: round.kind,
+            outcome: round.outcome,
+        })
+        .collect())
+}
+
+/// Running and paused projects, in project id order.
+fn live_projects(state: &ServerState) -> Vec<ProjectLiveState> {
+    let mut projects = state
+        .running
+        .keys()
+        .map(|project_id| {
+            state
+                .journal
+                .live_state(project_id, ProjectLiveStatus::Running)
+        })
+        .chain(state.interrupted.keys().map(|project_id| {
+            state
+                .journal
+                .live_state(project_id, ProjectLiveStatus::Paused)
+        }))
+        .collect::<Vec<_>>();
+    projects.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+    projects
+}
+
 fn interrupt_project(
     state: &mut ServerState,
     params: ProjectInterruptParams,
 ) -> anyhow::Result<()> {
     let ProjectInterruptParams { project_id } = params;
 
-    if let Some(running) = state.running.as_ref() {
-        let running_project_id = running.project_id.clone();
-        let already_requested = *running.interrupt_tx.borrow();
-        let interrupt_tx = running.interrupt_tx.clone();
-
-        anyhow::ensure!(
-            running_project_id == project_id,
-            "active running project mismatch: running={running_project_id} requested={project_id}",
-        );
+    if !state.running.is_empty() {
+        let Some(running) = state.running.get(&project_id) else {
+            let running_project_ids = state.running.keys().cloned().collect::<Vec<_>>();
+            anyhow::bail!(
+                "active running project mismatch: running={} requested={project_id}",
+                running_project_ids.join(",")
+            );
+        };
 
-        if already_requested {
+        if *running.interrupt_tx.borrow() {
             let running = state
                 .running
-                .take()
+                .remove(&project_id)
                 .context("take running project after id match")?;
             running.handle.abort();
+            state.journal.forget(&project_id);
+            state.owners.remove(&project_id);
             state.resumed = None;
             return Ok(());
         }
 
-        let _ = interrupt_tx.send(true);
+        let _ = running.interrupt_tx.send(true);
         return Ok(());
     }
 
@@ -673,23 +1353,25 @@ fn resolve_interrupt_project(
         turn_prompt_override,
     } = params;
 
-    let interrupted = state
-        .interrupted
-        .as_ref()
-        .context("no interrupted project to resolve")?;
-
     anyhow::ensure!(
-        interrupted.project_id == project_id,
-        "active interrupted project mismatch: interrupted={} requested={project_id}",
-        interrupted.project_id
+        !state.interrupted.is_empty(),
+        "no interrupted project to resolve"
     );
+    if !state.interrupted.contains_key(&project_id) {
+        let interrupted_project_ids = state.interrupted.keys().cloned().collect::<Vec<_>>();
+        anyhow::bail!(
+            "active interrupted project mismatch: interrupted={} requested={project_id}",
+            interrupted_project_ids.join(",")
+        );
+    }
 
     match action {
         ResolveInterruptAction::Stop => {
             let interrupted = state
                 .interrupted
-                .take()
+                .remove(&project_id)
                 .context("take interrupted project after id match")?;
+            state.owners.remove(&project_id);
 
             let InterruptedProject {
                 rounds_run,
@@ -700,14 +1382,18 @@ fn resolve_interrupt_project(
 
             let FreshProjectPlan {
                 workdir,
+                worktree_dir,
                 git_commit_start,
                 project_started_at,
                 ..
             } = plan;
 
-            let git_commit_end = crate::workflow::project::resolve_git_commit(&workdir);
+            let git_commit_end = crate::workflow::project::resolve_git_commit(
+                worktree_dir.as_deref().unwrap_or(&workdir),
+            );
             emit_potter_event(
                 writer_tx.clone(),
+                &project_id,
                 Event {
                     id: "".to_string(),
                     msg: EventMsg::PotterProjectCompleted {
@@ -727,29 +1413,39 @@ fn resolve_interrupt_project(
             })
         }
         ResolveInterruptAction::Continue => {
+            let interrupted = state
+                .interrupted
+                .get(&project_id)
+                .context("take interrupted project after id match")?;
             let turn_prompt_override = turn_prompt_override
                 .as_ref()
                 .map(|prompt| prompt.trim())
                 .filter(|prompt| !prompt.is_empty())
-                .context("turn_prompt_override is required for continue")?
-                .to_string();
-
+                .map(str::to_string);
+            // Continuing a plan review approves the plan; the next round runs with the regular
+            // prompt unless the caller provides an override.
+            anyhow::ensure!(
+                turn_prompt_override.is_some()
+                    || matches!(interrupted.pause, ProjectPause::PlanApproval { .. }),
+                "turn_prompt_override is required for continue"
+            );
+            anyhow::ensure!(
+                interrupted.plan.round_start_index < interrupted.plan.rounds_total,
+                "no rounds remaining to continue (round_start_index={} rounds_total={})",
+                interrupted.plan.round_start_index,
+                interrupted.plan.rounds_total
+            );
             let interrupted = state
                 .interrupted
-                .take()
+                .remove(&project_id)
                 .context("take interrupted project after id match")?;
 
             let mut plan = interrupted.plan;
-            anyhow::ensure!(
-                plan.round_start_index < plan.rounds_total,
-                "no rounds remaining to continue (round_start_index={} rounds_total={})",
-                plan.round_start_index,
-                plan.rounds_total
-            );
-            plan.initial_turn_prompt_override = Some(turn_prompt_override);
+            plan.initial_turn_prompt_override = turn_prompt_override;
             spawn_fresh_project(
                 &mut state.running,
                 &mut state.resumed,
+                &mut state.owners,
                 state.config.clone(),
                 writer_tx.clone(),
                 internal_tx.clone(),
@@ -800,8 +1496,13 @@ fn send_error(
     }));
 }
 
-fn emit_potter_event(writer_tx: UnboundedSender<JSONRPCMessage>, event: Event) {
-    let Ok(params) = serde_json::to_value(event) else {
+fn emit_potter_event(writer_tx: UnboundedSender<JSONRPCMessage>, project_id: &str, event: Event) {
+    let event = PotterProjectEvent {
+        project_id: Some(project_id.to_string()),
+        seq: None,
+        event,
+    };
+    let Ok(params) = event.to_notification_params() else {
         return;
     };
     let _ = writer_tx.send(JSONRPCMessage::Notification(JSONRPCNotification {
@@ -843,7 +1544,7 @@ fn count_completed_rounds(lines: &[crate::workflow::rollout::PotterRolloutLine])
     lines
         .iter()
         .filter(|line| match line {
-            crate::workflow::rollout::PotterRolloutLine::RoundFinished { outcome } => {
+            crate::workflow::rollout::PotterRolloutLine::RoundFinished { outcome, .. } => {
                 !matches!(outcome, PotterRoundOutcome::Interrupted)
             }
             _ => false,
@@ -873,6 +1574,7 @@ fn build_resume_replay(
         events.push(EventMsg::PotterRoundStarted {
             current: round.round_current,
             total: round.round_total,
+            kind: round.kind,
         });
 
         let rollout_path = resolve_rollout_path_for_replay(resolved, &round.rollout_path);
@@ -885,6 +1587,23 @@ fn build_resume_replay(
             .with_context(|| format!("replay rollout {}", rollout_path.display()))?;
         events.append(&mut rollout_events);
 
+        events.extend(round.user_inputs.iter().map(|user_input| {
+            EventMsg::PotterUserInputAnswered {
+                server_name: user_input.server_name.clone(),
+                answers: user_input.answers.clone(),
+                declined: user_input.declined,
+            }
+        }));
+
+        if let Some(verification) = &round.verification {
+            events.push(EventMsg::PotterRoundVerification {
+                command: verification.command.clone(),
+                passed: verification.passed,
+                exit_code: verification.exit_code,
+                output: verification.output.clone(),
+            });
+        }
+
         if let Some(project_succeeded) = &round.project_succeeded {
             events.push(EventMsg::PotterProjectSucceeded {
                 rounds: project_succeeded.rounds,
@@ -930,6 +1649,7 @@ fn build_unfinished_round_pre_action(
     pre_action_events.push(EventMsg::PotterRoundStarted {
         current: unfinished.round_current,
         total: unfinished.round_total,
+        kind: unfinished.kind,
     });
     pre_action_events.push(EventMsg::PotterRoundFinished {
         outcome: PotterRoundOutcome::Completed,
@@ -941,6 +1661,7 @@ fn build_unfinished_round_pre_action(
     Ok(Some(ProjectResumeUnfinishedRound {
         round_current: unfinished.round_current,
         round_total: unfinished.round_total,
+        round_kind: unfinished.kind,
         pre_action_events,
         remaining_rounds_including_current,
     }))
@@ -1109,6 +1830,8 @@ fn read_upstream_rollout_event_msgs(rollout_path: &Path) -> anyhow::Result<Vec<E
 #[derive(Debug, Clone)]
 struct FreshProjectPlan {
     workdir: PathBuf,
+    /// Dedicated git worktree the agent runs in (`--worktree` mode).
+    worktree_dir: Option<PathBuf>,
     user_message: String,
     project_dir_rel: PathBuf,
     progress_file_rel: PathBuf,
@@ -1116,6 +1839,8 @@ struct FreshProjectPlan {
     potter_rollout_path: PathBuf,
     rounds_total: u32,
     event_mode: PotterEventMode,
+    /// JSON Schema of the final report turn run after the project succeeds.
+    output_schema: Option<serde_json::Value>,
     project_started_at: Instant,
     round_start_index: u32,
     emit_project_started_event: bool,
@@ -1136,6 +1861,19 @@ impl FreshProjectPlan {
             ..self.clone()
         }
     }
+
+    /// Build a continuation plan that starts after the completed planning round
+    /// (`--review-plan`).
+    ///
+    /// `planning_round_index` is zero-based (same scale as `round_start_index`).
+    fn continuation_after_plan_review(&self, planning_round_index: u32) -> FreshProjectPlan {
+        FreshProjectPlan {
+            round_start_index: planning_round_index.saturating_add(1),
+            emit_project_started_event: false,
+            initial_turn_prompt_override: None,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1147,25 +1885,68 @@ struct ResumedProjectPlan {
     rounds_total: u32,
     resume_policy: ResumePolicy,
     event_mode: PotterEventMode,
+    /// JSON Schema of the final report turn run after the project succeeds.
+    output_schema: Option<serde_json::Value>,
     project_started_at: Instant,
 }
 
+/// Own the project in `project_dir` until its entry in `owners` is removed, which happens once
+/// the project completes or is stopped. Continuing an interrupted project keeps its ownership.
+fn own_project(
+    owners: &mut BTreeMap<String, FileLock>,
+    project_id: &str,
+    project_dir: &Path,
+) -> anyhow::Result<()> {
+    if owners.contains_key(project_id) {
+        return Ok(());
+    }
+    let Some(owner) = crate::workflow::project_owner::claim_project(project_dir)? else {
+        anyhow::bail!(
+            "project {project_id} is owned by another codex-potter process; stop or detach it first"
+        );
+    };
+    owners.insert(project_id.to_string(), owner);
+    Ok(())
+}
+
 fn spawn_fresh_project(
-    running: &mut Option<RunningProject>,
+    running: &mut BTreeMap<String, RunningProject>,
     resumed: &mut Option<ResumedProject>,
+    owners: &mut BTreeMap<String, FileLock>,
     config: PotterAppServerConfig,
     writer_tx: UnboundedSender<JSONRPCMessage>,
     internal_tx: UnboundedSender<InternalEvent>,
     project_id: String,
     plan: FreshProjectPlan,
 ) -> anyhow::Result<()> {
-    anyhow::ensure!(running.is_none(), "internal error: project already running");
+    anyhow::ensure!(
+        !running.contains_key(&project_id),
+        "internal error: project already running"
+    );
+    own_project(
+        owners,
+        &project_id,
+        &plan.workdir.join(&plan.project_dir_rel),
+    )?;
     *resumed = None;
 
     let (interrupt_tx, interrupt_rx) = watch::channel(false);
     let project_id_for_event = project_id.clone();
+    let approval_asker = spawn_approval_question_forwarder(project_id.clone(), internal_tx.clone());
+    let user_input_asker =
+        spawn_user_input_question_forwarder(project_id.clone(), internal_tx.clone());
     let handle = tokio::task::spawn_local(async move {
-        match run_fresh_project(config, writer_tx.clone(), plan, interrupt_rx).await {
+        match run_fresh_project(
+            config,
+            writer_tx.clone(),
+            project_id_for_event.clone(),
+            plan,
+            interrupt_rx,
+            approval_asker,
+            user_input_asker,
+        )
+        .await
+        {
             Ok(ProjectRunExit::Completed) => {
                 let _ = internal_tx.send(InternalEvent::ProjectFinished {
                     project_id: project_id_for_event,
@@ -1183,31 +1964,51 @@ fn spawn_fresh_project(
         }
     });
 
-    *running = Some(RunningProject {
+    running.insert(
         project_id,
-        handle,
-        interrupt_tx,
-    });
+        RunningProject {
+            handle,
+            interrupt_tx,
+        },
+    );
 
     Ok(())
 }
 
 fn spawn_resumed_project(
-    running: &mut Option<RunningProject>,
+    running: &mut BTreeMap<String, RunningProject>,
     resumed: &mut Option<ResumedProject>,
+    owners: &mut BTreeMap<String, FileLock>,
     config: PotterAppServerConfig,
     writer_tx: UnboundedSender<JSONRPCMessage>,
     internal_tx: UnboundedSender<InternalEvent>,
     project_id: String,
     plan: ResumedProjectPlan,
 ) -> anyhow::Result<()> {
-    anyhow::ensure!(running.is_none(), "internal error: project already running");
+    anyhow::ensure!(
+        !running.contains_key(&project_id),
+        "internal error: project already running"
+    );
+    own_project(owners, &project_id, &plan.resumed.resolved.project_dir)?;
     *resumed = None;
 
     let (interrupt_tx, interrupt_rx) = watch::channel(false);
     let project_id_for_event = project_id.clone();
+    let approval_asker = spawn_approval_question_forwarder(project_id.clone(), internal_tx.clone());
+    let user_input_asker =
+        spawn_user_input_question_forwarder(project_id.clone(), internal_tx.clone());
     let handle = tokio::task::spawn_local(async move {
-        match run_resumed_project(config, writer_tx.clone(), plan, interrupt_rx).await {
+        match run_resumed_project(
+            config,
+            writer_tx.clone(),
+            project_id_for_event.clone(),
+            plan,
+            interrupt_rx,
+            approval_asker,
+            user_input_asker,
+        )
+        .await
+        {
             Ok(ProjectRunExit::Completed) => {
                 let _ = internal_tx.send(InternalEvent::ProjectFinished {
                     project_id: project_id_for_event,
@@ -1223,46 +2024,215 @@ fn spawn_resumed_project(
                 });
             }
         }
-    });
+    });
+
+    running.insert(
+        project_id,
+        RunningProject {
+            handle,
+            interrupt_tx,
+        },
+    );
+
+    Ok(())
+}
+
+/// Render the developer prompt of `kind` rounds for a project.
+fn render_project_developer_prompt(
+    kind: PotterRoundKind,
+    workdir: &Path,
+    progress_file_rel: &Path,
+    worktree_dir: Option<&Path>,
+) -> String {
+    let render = match kind {
+        PotterRoundKind::Implement => crate::workflow::project::render_developer_prompt,
+        PotterRoundKind::Review => crate::workflow::project::render_review_developer_prompt,
+    };
+    render(&progress_file_for_prompt(
+        workdir,
+        progress_file_rel,
+        worktree_dir,
+    ))
+}
+
+/// Progress file path as prompts reference it.
+///
+/// In worktree mode the agent runs in the worktree while the progress file stays in the launch
+/// workdir, so prompts must reference it by absolute path.
+fn progress_file_for_prompt(
+    workdir: &Path,
+    progress_file_rel: &Path,
+    worktree_dir: Option<&Path>,
+) -> PathBuf {
+    match worktree_dir {
+        Some(_) => workdir.join(progress_file_rel),
+        None => progress_file_rel.to_path_buf(),
+    }
+}
+
+/// Final report turn requested with `outputSchema`.
+#[derive(Debug)]
+struct ProjectReport {
+    output_schema: serde_json::Value,
+    developer_prompt: String,
+}
+
+impl ProjectReport {
+    fn new(
+        output_schema: serde_json::Value,
+        workdir: &Path,
+        progress_file_rel: &Path,
+        worktree_dir: Option<&Path>,
+    ) -> Self {
+        Self {
+            output_schema,
+            developer_prompt: crate::workflow::project::render_report_developer_prompt(
+                &progress_file_for_prompt(workdir, progress_file_rel, worktree_dir),
+            ),
+        }
+    }
+}
 
-    *running = Some(RunningProject {
-        project_id,
-        handle,
-        interrupt_tx,
-    });
+/// Emit `PotterProjectCompleted` for `outcome`.
+///
+/// When the project succeeded and the client asked for a report, the report turn runs first and
+/// its output is emitted as `PotterProjectReport`; a failed report turns the outcome fatal.
+async fn complete_project(
+    ui: &mut EventForwardingRoundUi,
+    round_context: &crate::workflow::round_runner::PotterRoundContext,
+    report: Option<&ProjectReport>,
+    outcome: PotterProjectOutcome,
+) {
+    let outcome = match (outcome, report) {
+        (PotterProjectOutcome::Succeeded, Some(report)) => {
+            match crate::workflow::report::run_report_turn(
+                round_context.report_backend_config(report.developer_prompt.clone()),
+                crate::workflow::project::report_prompt()
+                    .trim_end()
+                    .to_string(),
+                &report.output_schema,
+            )
+            .await
+            {
+                Ok(output) => {
+                    ui.emit_marker(EventMsg::PotterProjectReport { output });
+                    PotterProjectOutcome::Succeeded
+                }
+                Err(err) => PotterProjectOutcome::Fatal {
+                    message: format!("final report failed: {err:#}"),
+                },
+            }
+        }
+        (outcome, _) => outcome,
+    };
+    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
+}
 
-    Ok(())
+/// Extend the upstream CLI args for a project.
+///
+/// In worktree mode `.codexpotter/` of the launch workdir is outside the thread cwd; add it as a
+/// writable root so the agent can still update the progress file under `workspace-write`.
+fn upstream_cli_args_for_project(
+    mut upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+    workdir: &Path,
+    worktree_dir: Option<&Path>,
+) -> anyhow::Result<crate::app_server::UpstreamCodexCliArgs> {
+    if worktree_dir.is_some() {
+        let root = workdir.join(".codexpotter");
+        let root = serde_json::to_string(&root.to_string_lossy())
+            .context("encode worktree writable root")?;
+        upstream_cli_args
+            .config_overrides
+            .push(format!("sandbox_workspace_write.writable_roots=[{root}]"));
+    }
+    Ok(upstream_cli_args)
 }
 
 async fn run_fresh_project(
     config: PotterAppServerConfig,
     writer_tx: UnboundedSender<JSONRPCMessage>,
+    project_id: String,
     plan: FreshProjectPlan,
     interrupt_rx: watch::Receiver<bool>,
+    approval_asker: ApprovalAsker,
+    user_input_asker: UserInputAsker,
 ) -> anyhow::Result<ProjectRunExit> {
-    let developer_prompt =
-        crate::workflow::project::render_developer_prompt(&plan.progress_file_rel);
+    let developer_prompt = render_project_developer_prompt(
+        PotterRoundKind::Implement,
+        &plan.workdir,
+        &plan.progress_file_rel,
+        plan.worktree_dir.as_deref(),
+    );
+    let review_developer_prompt = render_project_developer_prompt(
+        PotterRoundKind::Review,
+        &plan.workdir,
+        &plan.progress_file_rel,
+        plan.worktree_dir.as_deref(),
+    );
     let turn_prompt = crate::workflow::project::fixed_prompt()
         .trim_end()
         .to_string();
+    let report = plan.output_schema.clone().map(|output_schema| {
+        ProjectReport::new(
+            output_schema,
+            &plan.workdir,
+            &plan.progress_file_rel,
+            plan.worktree_dir.as_deref(),
+        )
+    });
 
     let backend_event_mode = backend_event_mode_for_potter(plan.event_mode);
+    let stall_rounds = config.project_cli_args.stall_rounds;
+    let max_tokens = config.project_cli_args.max_tokens;
+    let max_duration = config.project_cli_args.max_duration;
+    let rate_limit_threshold = config.project_cli_args.rate_limit_threshold;
+    let progress_file = plan.workdir.join(&plan.progress_file_rel);
+    let awaiting_plan_review =
+        config.project_cli_args.review_plan && progress_file_status_is(&progress_file, "initial");
+
+    let approval_policy = project_approval_policy(
+        config.approval_settings,
+        config.project_cli_args.ask_approval,
+        &plan.workdir,
+    )?;
 
     let round_context = crate::workflow::round_runner::PotterRoundContext {
         codex_bin: config.codex_bin,
         developer_prompt,
+        review_developer_prompt,
+        round_schedule: config.round_schedule,
         backend_launch: config.backend_launch,
         backend_event_mode,
-        upstream_cli_args: config.upstream_cli_args,
+        approval_policy,
+        approval_asker: Some(approval_asker),
+        user_input_asker: Some(user_input_asker),
+        stream_recovery: config.stream_recovery,
+        app_server_pool: config
+            .project_cli_args
+            .reuse_app_server
+            .then(crate::app_server::AppServerPool::default),
+        upstream_cli_args: upstream_cli_args_for_project(
+            config.upstream_cli_args,
+            &plan.workdir,
+            plan.worktree_dir.as_deref(),
+        )?,
         codex_compat_home: config.codex_compat_home,
-        thread_cwd: Some(plan.workdir.clone()),
+        thread_cwd: Some(plan.worktree_dir.clone().unwrap_or(plan.workdir.clone())),
         turn_prompt,
+        review_turn_prompt: crate::workflow::project::review_prompt()
+            .trim_end()
+            .to_string(),
         workdir: plan.workdir.clone(),
         progress_file_rel: plan.progress_file_rel.clone(),
         user_prompt_file: plan.progress_file_rel.clone(),
         git_commit_start: plan.git_commit_start.clone(),
         potter_rollout_path: plan.potter_rollout_path.clone(),
         project_started_at: plan.project_started_at,
+        verification_command: config.project_cli_args.verify_command,
+        verification_timeout: config
+            .project_cli_args
+            .verify_timeout
+            .unwrap_or(crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT),
     };
 
     let round_context_with_override =
@@ -1270,13 +2240,30 @@ async fn run_fresh_project(
             .clone()
             .map(
                 |turn_prompt| crate::workflow::round_runner::PotterRoundContext {
+                    review_turn_prompt: turn_prompt.clone(),
                     turn_prompt,
                     ..round_context.clone()
                 },
             );
 
-    let mut ui = EventForwardingRoundUi::new(writer_tx, interrupt_rx);
+    let budget = crate::workflow::budget::ProjectBudget::new(
+        max_tokens,
+        max_duration,
+        plan.project_started_at,
+    );
+    let rate_limits = crate::workflow::rate_limit::RateLimitGate::new(rate_limit_threshold);
+    let mut ui = EventForwardingRoundUi::new(
+        writer_tx,
+        project_id.clone(),
+        interrupt_rx,
+        budget,
+        rate_limits,
+    );
 
+    let mut stall = crate::workflow::stall::StallDetector::start(
+        plan.worktree_dir.as_deref().unwrap_or(&plan.workdir),
+        stall_rounds,
+    );
     let mut outcome = PotterProjectOutcome::BudgetExhausted;
 
     for round_index in plan.round_start_index..plan.rounds_total {
@@ -1329,21 +2316,49 @@ async fn run_fresh_project(
                     outcome = PotterProjectOutcome::Succeeded;
                     break;
                 }
+                if let Some(stalled) = stalled_project_outcome(
+                    &mut stall,
+                    round_result.round_kind,
+                    &plan.potter_rollout_path,
+                ) {
+                    outcome = stalled;
+                    break;
+                }
+                if let Some(exhausted) = ui.exhausted_budget() {
+                    outcome = exhausted;
+                    break;
+                }
                 if round_index.saturating_add(1) >= plan.rounds_total {
                     outcome = PotterProjectOutcome::BudgetExhausted;
+                    break;
+                }
+                if awaiting_plan_review && progress_file_status_is(&progress_file, "open") {
+                    let todo = crate::workflow::project::progress_file_todo_items(&progress_file)
+                        .unwrap_or_else(|err| {
+                            eprintln!("warning: failed to read plan for review: {err:#}");
+                            Vec::new()
+                        });
+                    return Ok(ProjectRunExit::Interrupted(Box::new(InterruptedProject {
+                        project_id,
+                        user_prompt_file: plan.progress_file_rel.clone(),
+                        rounds_run: current_round,
+                        plan: plan.continuation_after_plan_review(round_index),
+                        pause: ProjectPause::PlanApproval { todo },
+                    })));
                 }
             }
             codex_tui::ExitReason::Interrupted => {
+                if let Some(exhausted) = ui.exhausted_budget() {
+                    outcome = exhausted;
+                    break;
+                }
                 let continuation_plan = plan.continuation_after_interrupt(round_index);
                 return Ok(ProjectRunExit::Interrupted(Box::new(InterruptedProject {
-                    project_id: plan
-                        .workdir
-                        .join(&plan.progress_file_rel)
-                        .to_string_lossy()
-                        .to_string(),
+                    project_id,
                     user_prompt_file: plan.progress_file_rel.clone(),
                     rounds_run: current_round,
                     plan: continuation_plan,
+                    pause: ProjectPause::Interrupted,
                 })));
             }
             codex_tui::ExitReason::TaskFailed(message) => {
@@ -1363,15 +2378,18 @@ async fn run_fresh_project(
         }
     }
 
-    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
+    complete_project(&mut ui, &round_context, report.as_ref(), outcome).await;
     Ok(ProjectRunExit::Completed)
 }
 
 async fn run_resumed_project(
     config: PotterAppServerConfig,
     writer_tx: UnboundedSender<JSONRPCMessage>,
+    project_id: String,
     plan: ResumedProjectPlan,
     interrupt_rx: watch::Receiver<bool>,
+    approval_asker: ApprovalAsker,
+    user_input_asker: UserInputAsker,
 ) -> anyhow::Result<ProjectRunExit> {
     let ResumedProjectPlan {
         resumed,
@@ -1381,36 +2399,101 @@ async fn run_resumed_project(
         rounds_total,
         resume_policy,
         event_mode,
+        output_schema,
         project_started_at,
         ..
     } = plan;
 
-    let developer_prompt =
-        crate::workflow::project::render_developer_prompt(&resumed.progress_file_rel);
+    let developer_prompt = render_project_developer_prompt(
+        PotterRoundKind::Implement,
+        &resumed.resolved.workdir,
+        &resumed.progress_file_rel,
+        resumed.worktree_dir.as_deref(),
+    );
+    let review_developer_prompt = render_project_developer_prompt(
+        PotterRoundKind::Review,
+        &resumed.resolved.workdir,
+        &resumed.progress_file_rel,
+        resumed.worktree_dir.as_deref(),
+    );
     let turn_prompt = crate::workflow::project::fixed_prompt()
         .trim_end()
         .to_string();
+    let report = output_schema.map(|output_schema| {
+        ProjectReport::new(
+            output_schema,
+            &resumed.resolved.workdir,
+            &resumed.progress_file_rel,
+            resumed.worktree_dir.as_deref(),
+        )
+    });
 
     let backend_event_mode = backend_event_mode_for_potter(event_mode);
+    let stall_rounds = config.project_cli_args.stall_rounds;
+    let max_tokens = config.project_cli_args.max_tokens;
+    let max_duration = config.project_cli_args.max_duration;
+    let rate_limit_threshold = config.project_cli_args.rate_limit_threshold;
+    let thread_workdir = resumed
+        .worktree_dir
+        .clone()
+        .unwrap_or(resumed.resolved.workdir.clone());
+
+    let approval_policy = project_approval_policy(
+        config.approval_settings,
+        config.project_cli_args.ask_approval,
+        &resumed.resolved.workdir,
+    )?;
 
     let round_context = crate::workflow::round_runner::PotterRoundContext {
         codex_bin: config.codex_bin,
         developer_prompt,
+        review_developer_prompt,
+        round_schedule: config.round_schedule,
         backend_launch: config.backend_launch,
         backend_event_mode,
-        upstream_cli_args: config.upstream_cli_args,
+        approval_policy,
+        approval_asker: Some(approval_asker),
+        user_input_asker: Some(user_input_asker),
+        stream_recovery: config.stream_recovery,
+        app_server_pool: config
+            .project_cli_args
+            .reuse_app_server
+            .then(crate::app_server::AppServerPool::default),
+        upstream_cli_args: upstream_cli_args_for_project(
+            config.upstream_cli_args,
+            &resumed.resolved.workdir,
+            resumed.worktree_dir.as_deref(),
+        )?,
         codex_compat_home: config.codex_compat_home,
-        thread_cwd: Some(resumed.resolved.workdir.clone()),
+        thread_cwd: Some(thread_workdir.clone()),
         turn_prompt,
+        review_turn_prompt: crate::workflow::project::review_prompt()
+            .trim_end()
+            .to_string(),
         workdir: resumed.resolved.workdir.clone(),
         progress_file_rel: resumed.progress_file_rel.clone(),
         user_prompt_file: resumed.progress_file_rel.clone(),
         git_commit_start,
         potter_rollout_path,
         project_started_at,
+        verification_command: config.project_cli_args.verify_command,
+        verification_timeout: config
+            .project_cli_args
+            .verify_timeout
+            .unwrap_or(crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT),
     };
 
-    let mut ui = EventForwardingRoundUi::new(writer_tx, interrupt_rx);
+    let budget =
+        crate::workflow::budget::ProjectBudget::new(max_tokens, max_duration, project_started_at);
+    let rate_limits = crate::workflow::rate_limit::RateLimitGate::new(rate_limit_threshold);
+    let mut ui = EventForwardingRoundUi::new(
+        writer_tx,
+        project_id.clone(),
+        interrupt_rx,
+        budget,
+        rate_limits,
+    );
+    let mut stall = crate::workflow::stall::StallDetector::start(&thread_workdir, stall_rounds);
 
     if let Some(unfinished) = resumed.index.unfinished_round.clone()
         && matches!(resume_policy, ResumePolicy::ContinueUnfinishedRound)
@@ -1461,6 +2544,7 @@ async fn run_resumed_project(
                 pad_before_first_cell: true,
                 round_current: unfinished.round_current,
                 round_total: total_rounds,
+                round_kind: unfinished.kind,
                 project_succeeded_rounds: baseline_rounds.saturating_add(1),
                 resume_thread_id: unfinished.thread_id,
                 replay_event_msgs,
@@ -1485,14 +2569,28 @@ async fn run_resumed_project(
             codex_tui::ExitReason::Completed => {
                 if round_result.stop_due_to_finite_incantatem {
                     outcome = PotterProjectOutcome::Succeeded;
-                    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
+                    complete_project(&mut ui, &round_context, report.as_ref(), outcome).await;
+                    return Ok(ProjectRunExit::Completed);
+                }
+                if let Some(stalled) = stalled_project_outcome(
+                    &mut stall,
+                    round_result.round_kind,
+                    &round_context.potter_rollout_path,
+                ) {
+                    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome: stalled });
+                    return Ok(ProjectRunExit::Completed);
+                }
+                if let Some(exhausted) = ui.exhausted_budget() {
+                    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome: exhausted });
                     return Ok(ProjectRunExit::Completed);
                 }
             }
             codex_tui::ExitReason::Interrupted => {
-                outcome = PotterProjectOutcome::Fatal {
-                    message: String::from("interrupted"),
-                };
+                outcome = ui
+                    .exhausted_budget()
+                    .unwrap_or_else(|| PotterProjectOutcome::Fatal {
+                        message: String::from("interrupted"),
+                    });
                 ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
                 return Ok(ProjectRunExit::Completed);
             }
@@ -1554,11 +2652,25 @@ async fn run_resumed_project(
                         outcome = PotterProjectOutcome::Succeeded;
                         break;
                     }
+                    if let Some(stalled) = stalled_project_outcome(
+                        &mut stall,
+                        round_result.round_kind,
+                        &round_context.potter_rollout_path,
+                    ) {
+                        outcome = stalled;
+                        break;
+                    }
+                    if let Some(exhausted) = ui.exhausted_budget() {
+                        outcome = exhausted;
+                        break;
+                    }
                 }
                 codex_tui::ExitReason::Interrupted => {
-                    outcome = PotterProjectOutcome::Fatal {
-                        message: String::from("interrupted"),
-                    };
+                    outcome =
+                        ui.exhausted_budget()
+                            .unwrap_or_else(|| PotterProjectOutcome::Fatal {
+                                message: String::from("interrupted"),
+                            });
                     break;
                 }
                 codex_tui::ExitReason::TaskFailed(message) => {
@@ -1578,7 +2690,7 @@ async fn run_resumed_project(
             }
         }
 
-        ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
+        complete_project(&mut ui, &round_context, report.as_ref(), outcome).await;
         return Ok(ProjectRunExit::Completed);
     }
 
@@ -1618,14 +2730,28 @@ async fn run_resumed_project(
                     outcome = PotterProjectOutcome::Succeeded;
                     break;
                 }
+                if let Some(stalled) = stalled_project_outcome(
+                    &mut stall,
+                    round_result.round_kind,
+                    &round_context.potter_rollout_path,
+                ) {
+                    outcome = stalled;
+                    break;
+                }
+                if let Some(exhausted) = ui.exhausted_budget() {
+                    outcome = exhausted;
+                    break;
+                }
                 if rounds_run >= rounds_total {
                     outcome = PotterProjectOutcome::BudgetExhausted;
                 }
             }
             codex_tui::ExitReason::Interrupted => {
-                outcome = PotterProjectOutcome::Fatal {
-                    message: String::from("interrupted"),
-                };
+                outcome = ui
+                    .exhausted_budget()
+                    .unwrap_or_else(|| PotterProjectOutcome::Fatal {
+                        message: String::from("interrupted"),
+                    });
                 break;
             }
             codex_tui::ExitReason::TaskFailed(message) => {
@@ -1645,10 +2771,43 @@ async fn run_resumed_project(
         }
     }
 
-    ui.emit_marker(EventMsg::PotterProjectCompleted { outcome });
+    complete_project(&mut ui, &round_context, report.as_ref(), outcome).await;
     Ok(ProjectRunExit::Completed)
 }
 
+/// Whether the progress file front matter currently records `status: <expected>`.
+///
+/// Read errors are treated as a mismatch so a broken progress file never pauses the project.
+fn progress_file_status_is(progress_file: &Path, expected: &str) -> bool {
+    matches!(
+        crate::workflow::project::progress_file_status(progress_file),
+        Ok(Some(status)) if status == expected
+    )
+}
+
+/// Returns the `Stalled` outcome (recorded in `potter-rollout.jsonl`) once `stall` has seen
+/// enough consecutive no-op rounds.
+///
+/// Review rounds run read-only and never change git state, so they are not counted.
+fn stalled_project_outcome(
+    stall: &mut Option<crate::workflow::stall::StallDetector>,
+    round_kind: PotterRoundKind,
+    potter_rollout_path: &Path,
+) -> Option<PotterProjectOutcome> {
+    if !round_kind.is_implement() {
+        return None;
+    }
+    let rounds = stall.as_mut()?.observe_round()?;
+    Some(
+        match crate::workflow::stall::record_project_stalled(potter_rollout_path, rounds) {
+            Ok(()) => PotterProjectOutcome::Stalled { rounds },
+            Err(err) => PotterProjectOutcome::Fatal {
+                message: format!("{err:#}"),
+            },
+        },
+    )
+}
+
 fn backend_event_mode_for_potter(mode: PotterEventMode) -> crate::app_server::AppServerEventMode {
     match mode {
         PotterEventMode::Interactive => crate::app_server::AppServerEventMode::Interactive,
@@ -1658,23 +2817,41 @@ fn backend_event_mode_for_potter(mode: PotterEventMode) -> crate::app_server::Ap
 
 struct EventForwardingRoundUi {
     writer_tx: UnboundedSender<JSONRPCMessage>,
+    project_id: String,
     interrupt_rx: watch::Receiver<bool>,
+    budget: Option<crate::workflow::budget::ProjectBudget>,
+    /// Set when the active round was interrupted because a budget ran out.
+    budget_outcome: Option<PotterProjectOutcome>,
+    rate_limits: crate::workflow::rate_limit::RateLimitGate,
+    /// Round prompt held back while the round waits for a rate-limit window to reset.
+    pending_prompt: Option<String>,
     token_usage: TokenUsage,
     thread_id: Option<ThreadId>,
     saw_round_finished: bool,
+    /// Last `PotterAppServerStderr` of the round, attached to a synthesized fatal outcome.
+    app_server_stderr: Option<(Vec<String>, Option<PathBuf>)>,
 }
 
 impl EventForwardingRoundUi {
     fn new(
         writer_tx: UnboundedSender<JSONRPCMessage>,
+        project_id: String,
         interrupt_rx: watch::Receiver<bool>,
+        budget: Option<crate::workflow::budget::ProjectBudget>,
+        rate_limits: crate::workflow::rate_limit::RateLimitGate,
     ) -> Self {
         Self {
             writer_tx,
+            project_id,
             interrupt_rx,
+            budget,
+            budget_outcome: None,
+            rate_limits,
+            pending_prompt: None,
             token_usage: TokenUsage::default(),
             thread_id: None,
             saw_round_finished: false,
+            app_server_stderr: None,
         }
     }
 
@@ -1683,6 +2860,14 @@ impl EventForwardingRoundUi {
             && let Some(info) = &ev.info
         {
             self.token_usage = info.total_token_usage.clone();
+            if let Some(budget) = self.budget.as_mut() {
+                budget.observe_round_tokens(self.token_usage.total_tokens);
+            }
+        }
+        if let EventMsg::TokenCount(ev) = &event.msg
+            && let Some(rate_limits) = &ev.rate_limits
+        {
+            self.rate_limits.observe(rate_limits);
         }
         if let EventMsg::SessionConfigured(cfg) = &event.msg {
             self.thread_id = Some(cfg.session_id);
@@ -1691,16 +2876,80 @@ impl EventForwardingRoundUi {
         if matches!(&event.msg, EventMsg::PotterRoundFinished { .. }) {
             self.saw_round_finished = true;
         }
+        if let EventMsg::PotterAppServerStderr { tail, log_path } = &event.msg {
+            self.app_server_stderr = Some((tail.clone(), log_path.clone()));
+        }
+
+        emit_potter_event(self.writer_tx.clone(), &self.project_id, event.clone());
+
+        if matches!(
+            &event.msg,
+            EventMsg::PotterRoundStarted { .. } | EventMsg::TokenCount(_)
+        ) && let Some(budget) = &self.budget
+        {
+            let status = Event {
+                id: "".to_string(),
+                msg: budget.status_event_at(Instant::now()),
+            };
+            emit_potter_event(self.writer_tx.clone(), &self.project_id, status);
+        }
+    }
 
-        let Ok(params) = serde_json::to_value(event) else {
+    /// Interrupt the active round once a budget has run out.
+    fn interrupt_if_budget_exhausted(
+        &mut self,
+        codex_op_tx: &UnboundedSender<codex_protocol::protocol::Op>,
+        interrupt_sent: &mut bool,
+    ) {
+        if *interrupt_sent {
+            return;
+        }
+        let Some(outcome) = self
+            .budget
+            .as_ref()
+            .and_then(|budget| budget.exhausted_at(Instant::now()))
+        else {
             return;
         };
-        let _ = self
-            .writer_tx
-            .send(JSONRPCMessage::Notification(JSONRPCNotification {
-                method: POTTER_EVENT_NOTIFICATION_METHOD.to_string(),
-                params: Some(params),
-            }));
+        self.budget_outcome = Some(outcome);
+        self.send_interrupt(codex_op_tx);
+        *interrupt_sent = true;
+    }
+
+    /// Send the round prompt if a rate-limit pause still holds it back.
+    fn send_pending_prompt(
+        &mut self,
+        codex_op_tx: &UnboundedSender<codex_protocol::protocol::Op>,
+    ) -> anyhow::Result<()> {
+        let Some(prompt) = self.pending_prompt.take() else {
+            return Ok(());
+        };
+        codex_op_tx
+            .send(codex_protocol::protocol::Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: prompt,
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .map_err(|_| anyhow::anyhow!("codex op channel closed"))
+    }
+
+    /// Interrupt the round. A prompt still held back by a rate-limit pause is dropped instead of
+    /// sent: the round then finishes as interrupted without starting its app-server.
+    fn send_interrupt(&mut self, codex_op_tx: &UnboundedSender<codex_protocol::protocol::Op>) {
+        self.pending_prompt = None;
+        let _ = codex_op_tx.send(codex_protocol::protocol::Op::Interrupt);
+    }
+
+    /// Returns the budget outcome when the last round was stopped by a budget, or when a budget
+    /// ran out by the time the round finished.
+    fn exhausted_budget(&mut self) -> Option<PotterProjectOutcome> {
+        self.budget_outcome.take().or_else(|| {
+            self.budget
+                .as_ref()
+                .and_then(|budget| budget.exhausted_at(Instant::now()))
+        })
     }
 
     fn synthesize_round_fatal_closure(&mut self, message: &str) {
@@ -1714,11 +2963,14 @@ impl EventForwardingRoundUi {
         self.forward_event(&error);
 
         if !self.saw_round_finished {
+            let (stderr_tail, stderr_log) = self.app_server_stderr.take().unwrap_or_default();
             let finished = Event {
                 id: "".to_string(),
                 msg: EventMsg::PotterRoundFinished {
                     outcome: PotterRoundOutcome::Fatal {
                         message: message.to_string(),
+                        stderr_tail,
+                        stderr_log,
                     },
                 },
             };
@@ -1754,26 +3006,44 @@ impl crate::workflow::round_runner::PotterRoundUi for EventForwardingRoundUi {
             self.token_usage = TokenUsage::default();
             self.thread_id = None;
             self.saw_round_finished = false;
-
-            codex_op_tx
-                .send(codex_protocol::protocol::Op::UserInput {
-                    items: vec![UserInput::Text {
-                        text: prompt,
-                        text_elements: Vec::new(),
-                    }],
-                    final_output_json_schema: None,
-                })
-                .map_err(|_| anyhow::anyhow!("codex op channel closed"))?;
-
+            self.app_server_stderr = None;
+            self.budget_outcome = None;
+            if let Some(budget) = self.budget.as_mut() {
+                budget.start_round();
+            }
+            let budget_deadline = self
+                .budget
+                .as_ref()
+                .and_then(crate::workflow::budget::ProjectBudget::deadline);
+
+            // The round's app-server only starts with the prompt, so hold the prompt back while a
+            // rate-limit window is nearly used up; the pause is announced once the queued
+            // `PotterRoundStarted` has been forwarded.
+            self.pending_prompt = Some(prompt);
+            let mut rate_limit_pause = None;
+            let mut rate_limit_resume_at = None;
             let mut interrupt_sent = false;
-            if *self.interrupt_rx.borrow() {
-                let _ = codex_op_tx.send(codex_protocol::protocol::Op::Interrupt);
+            let interrupted = *self.interrupt_rx.borrow();
+            if interrupted {
+                self.send_interrupt(&codex_op_tx);
                 interrupt_sent = true;
             }
+            self.interrupt_if_budget_exhausted(&codex_op_tx, &mut interrupt_sent);
+            if !interrupt_sent {
+                if let Some(pause) = self.rate_limits.pause_at(SystemTime::now()) {
+                    rate_limit_resume_at = Some(Instant::now() + pause.resets_in);
+                    rate_limit_pause = Some(pause);
+                } else {
+                    self.send_pending_prompt(&codex_op_tx)?;
+                }
+            }
 
             loop {
                 while let Ok(event) = codex_event_rx.try_recv() {
                     self.forward_event(&event);
+                    if matches!(&event.msg, EventMsg::TokenCount(_)) {
+                        self.interrupt_if_budget_exhausted(&codex_op_tx, &mut interrupt_sent);
+                    }
                     if let EventMsg::PotterRoundFinished { outcome } = &event.msg {
                         return Ok(codex_tui::AppExitInfo {
                             token_usage: self.token_usage.clone(),
@@ -1782,6 +3052,9 @@ impl crate::workflow::round_runner::PotterRoundUi for EventForwardingRoundUi {
                         });
                     }
                 }
+                if let Some(pause) = rate_limit_pause.take() {
+                    self.emit_marker(pause.event());
+                }
 
                 if let Ok(message) = fatal_exit_rx.try_recv() {
                     self.synthesize_round_fatal_closure(&message);
@@ -1795,10 +3068,17 @@ impl crate::workflow::round_runner::PotterRoundUi for EventForwardingRoundUi {
                 tokio::select! {
                     interrupt_changed = self.interrupt_rx.changed(), if !interrupt_sent => {
                         if interrupt_changed.is_ok() && *self.interrupt_rx.borrow() {
-                            let _ = codex_op_tx.send(codex_protocol::protocol::Op::Interrupt);
+                            self.send_interrupt(&codex_op_tx);
                             interrupt_sent = true;
                         }
                     }
+                    _ = sleep_until_deadline(budget_deadline), if !interrupt_sent => {
+                        self.interrupt_if_budget_exhausted(&codex_op_tx, &mut interrupt_sent);
+                    }
+                    _ = sleep_until_deadline(rate_limit_resume_at), if self.pending_prompt.is_some() => {
+                        rate_limit_resume_at = None;
+                        self.send_pending_prompt(&codex_op_tx)?;
+                    }
                     Some(message) = fatal_exit_rx.recv() => {
                         while let Ok(event) = codex_event_rx.try_recv() {
                             self.forward_event(&event);
@@ -1821,6 +3101,9 @@ impl crate::workflow::round_runner::PotterRoundUi for EventForwardingRoundUi {
                             });
                         };
                         self.forward_event(&event);
+                        if matches!(&event.msg, EventMsg::TokenCount(_)) {
+                            self.interrupt_if_budget_exhausted(&codex_op_tx, &mut interrupt_sent);
+                        }
                         if let EventMsg::PotterRoundFinished { outcome } = &event.msg {
                             return Ok(codex_tui::AppExitInfo {
                                 token_usage: self.token_usage.clone(),
@@ -1835,6 +3118,14 @@ impl crate::workflow::round_runner::PotterRoundUi for EventForwardingRoundUi {
     }
 }
 
+/// Resolves at `deadline`, or never when there is no time budget.
+async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)).await,
+        None => std::future::pending().await,
+    }
+}
+
 fn exit_reason_from_outcome(outcome: &PotterRoundOutcome) -> codex_tui::ExitReason {
     match outcome {
         PotterRoundOutcome::Completed => codex_tui::ExitReason::Completed,
@@ -1843,61 +3134,301 @@ fn exit_reason_from_outcome(outcome: &PotterRoundOutcome) -> codex_tui::ExitReas
         PotterRoundOutcome::TaskFailed { message } => {
             codex_tui::ExitReason::TaskFailed(message.clone())
         }
-        PotterRoundOutcome::Fatal { message } => codex_tui::ExitReason::Fatal(message.clone()),
+        PotterRoundOutcome::Fatal { message, .. } => codex_tui::ExitReason::Fatal(message.clone()),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    #[test]
+    fn count_completed_rounds_ignores_interrupted_outcome() {
+        let lines = vec![
+            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::Interrupted,
+                checkpoint: None,
+            },
+            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
+            },
+            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::TaskFailed {
+                    message: String::from("nope"),
+                },
+                checkpoint: None,
+            },
+            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::UserRequested,
+                checkpoint: None,
+            },
+        ];
+
+        assert_eq!(count_completed_rounds(&lines), 3);
+    }
+
+    #[tokio::test]
+    async fn event_forwarding_round_ui_sends_interrupt_and_waits_for_round_finished() {
+        let (writer_tx, _writer_rx) = unbounded_channel::<JSONRPCMessage>();
+        let (interrupt_tx, interrupt_rx) = watch::channel(false);
+
+        let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<codex_protocol::protocol::Op>();
+        let (codex_event_tx, codex_event_rx) = unbounded_channel::<Event>();
+        let (_fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
+
+        let params = codex_tui::RenderRoundParams {
+            prompt: "Hello".to_string(),
+            pad_before_first_cell: false,
+            status_header_prefix: None,
+            prompt_footer: codex_tui::PromptFooterContext::new(PathBuf::from("/tmp"), None),
+            codex_op_tx,
+            codex_event_rx,
+            fatal_exit_rx,
+        };
+
+        let render = async move {
+            let mut ui = EventForwardingRoundUi::new(
+                writer_tx,
+                project_id,
+                interrupt_rx,
+                None,
+                crate::workflow::rate_limit::RateLimitGate::new(None),
+            );
+            crate::workflow::round_runner::PotterRoundUi::render_round(&mut ui, params).await
+        };
+
+        let driver = async move {
+            let first_op = codex_op_rx.recv().await.expect("op");
+            assert_eq!(
+                first_op,
+                codex_protocol::protocol::Op::UserInput {
+                    items: vec![UserInput::Text {
+                        text: "Hello".to_string(),
+                        text_elements: Vec::new(),
+                    }],
+                    final_output_json_schema: None,
+                }
+            );
+
+            interrupt_tx.send(true).expect("interrupt");
+
+            let second_op = codex_op_rx.recv().await.expect("op");
+            assert_eq!(second_op, codex_protocol::protocol::Op::Interrupt);
+
+            codex_event_tx
+                .send(Event {
+                    id: String::new(),
+                    msg: EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::UserRequested,
+                    },
+                })
+                .expect("round finished");
+        };
+
+        let (exit_info, ()) = tokio::join!(render, driver);
+        let exit_info = exit_info.expect("render");
+        assert!(matches!(
+            exit_info.exit_reason,
+            codex_tui::ExitReason::UserRequested
+        ));
+    }
+
+    #[tokio::test]
+    async fn event_forwarding_round_ui_interrupts_when_token_budget_runs_out() {
+        let (writer_tx, _writer_rx) = unbounded_channel::<JSONRPCMessage>();
+        let (_interrupt_tx, interrupt_rx) = watch::channel(false);
+
+        let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<codex_protocol::protocol::Op>();
+        let (codex_event_tx, codex_event_rx) = unbounded_channel::<Event>();
+        let (_fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
+
+        let params = codex_tui::RenderRoundParams {
+            prompt: "Hello".to_string(),
+            pad_before_first_cell: false,
+            status_header_prefix: None,
+            prompt_footer: codex_tui::PromptFooterContext::new(PathBuf::from("/tmp"), None),
+            codex_op_tx,
+            codex_event_rx,
+            fatal_exit_rx,
+        };
+        let budget = crate::workflow::budget::ProjectBudget::new(
+            std::num::NonZeroU64::new(100),
+            None,
+            Instant::now(),
+        );
+        let mut ui = EventForwardingRoundUi::new(
+            writer_tx,
+            "project_1".to_string(),
+            interrupt_rx,
+            budget,
+            crate::workflow::rate_limit::RateLimitGate::new(None),
+        );
+
+        let render = crate::workflow::round_runner::PotterRoundUi::render_round(&mut ui, params);
+        let driver = async move {
+            let first_op = codex_op_rx.recv().await.expect("op");
+            assert!(matches!(
+                first_op,
+                codex_protocol::protocol::Op::UserInput { .. }
+            ));
+
+            let usage = TokenUsage {
+                total_tokens: 150,
+                ..TokenUsage::default()
+            };
+            codex_event_tx
+                .send(Event {
+                    id: String::new(),
+                    msg: EventMsg::TokenCount(codex_protocol::protocol::TokenCountEvent {
+                        info: Some(codex_protocol::protocol::TokenUsageInfo {
+                            total_token_usage: usage.clone(),
+                            last_token_usage: usage,
+                            model_context_window: None,
+                        }),
+                        rate_limits: None,
+                    }),
+                })
+                .expect("token count");
+
+            let second_op = codex_op_rx.recv().await.expect("op");
+            assert_eq!(second_op, codex_protocol::protocol::Op::Interrupt);
 
-    use pretty_assertions::assert_eq;
-    use tokio::sync::mpsc::UnboundedReceiver;
+            codex_event_tx
+                .send(Event {
+                    id: String::new(),
+                    msg: EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::Interrupted,
+                    },
+                })
+                .expect("round finished");
+        };
 
-    #[test]
-    fn decode_jsonrpc_message_line_errors_on_invalid_json() {
-        let err = decode_jsonrpc_message_line("{not json").expect_err("should fail");
-        assert!(
-            err.to_string()
-                .contains("decode potter app-server JSON-RPC")
+        let (exit_info, ()) = tokio::join!(render, driver);
+        let exit_info = exit_info.expect("render");
+        assert!(matches!(
+            exit_info.exit_reason,
+            codex_tui::ExitReason::Interrupted
+        ));
+        assert_eq!(
+            ui.exhausted_budget(),
+            Some(PotterProjectOutcome::TokenBudgetExhausted {
+                tokens_used: 150,
+                max_tokens: 100,
+            })
         );
     }
 
-    #[test]
-    fn decode_jsonrpc_message_line_ignores_empty_lines() {
-        assert!(
-            decode_jsonrpc_message_line(" \t ")
-                .expect("decode")
-                .is_none()
-        );
+    fn rate_limits_resetting_in_an_hour() -> crate::workflow::rate_limit::RateLimitGate {
+        let resets_at = system_time_to_unix_secs(SystemTime::now()).expect("unix time") + 3600;
+        let mut rate_limits = crate::workflow::rate_limit::RateLimitGate::new(None);
+        rate_limits.observe(&codex_protocol::protocol::RateLimitSnapshot {
+            primary: Some(codex_protocol::protocol::RateLimitWindow {
+                used_percent: 98.0,
+                window_minutes: Some(300),
+                resets_at: i64::try_from(resets_at).ok(),
+            }),
+            secondary: None,
+            credits: None,
+            plan_type: None,
+        });
+        rate_limits
     }
 
-    #[test]
-    fn count_completed_rounds_ignores_interrupted_outcome() {
-        let lines = vec![
-            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::Interrupted,
-            },
-            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::Completed,
-            },
-            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::TaskFailed {
-                    message: String::from("nope"),
+    #[tokio::test]
+    async fn event_forwarding_round_ui_holds_prompt_while_rate_limited() {
+        let (writer_tx, writer_rx) = unbounded_channel::<JSONRPCMessage>();
+        let (interrupt_tx, interrupt_rx) = watch::channel(false);
+
+        let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<codex_protocol::protocol::Op>();
+        let (codex_event_tx, codex_event_rx) = unbounded_channel::<Event>();
+        let (_fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
+
+        codex_event_tx
+            .send(Event {
+                id: String::new(),
+                msg: EventMsg::PotterRoundStarted {
+                    current: 2,
+                    total: 10,
+                    kind: PotterRoundKind::Implement,
                 },
-            },
-            crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::UserRequested,
-            },
-        ];
+            })
+            .expect("round started");
 
-        assert_eq!(count_completed_rounds(&lines), 3);
+        let params = codex_tui::RenderRoundParams {
+            prompt: "Hello".to_string(),
+            pad_before_first_cell: false,
+            status_header_prefix: None,
+            prompt_footer: codex_tui::PromptFooterContext::new(PathBuf::from("/tmp"), None),
+            codex_op_tx,
+            codex_event_rx,
+            fatal_exit_rx,
+        };
+        let mut ui = EventForwardingRoundUi::new(
+            writer_tx,
+            "project_1".to_string(),
+            interrupt_rx,
+            None,
+            rate_limits_resetting_in_an_hour(),
+        );
+
+        let render = crate::workflow::round_runner::PotterRoundUi::render_round(&mut ui, params);
+        let driver = async move {
+            tokio::task::yield_now().await;
+            assert!(
+                codex_op_rx.try_recv().is_err(),
+                "prompt must wait for the rate-limit window to reset"
+            );
+
+            // The held prompt is dropped, so the round never starts its app-server.
+            interrupt_tx.send(true).expect("interrupt");
+            assert_eq!(
+                codex_op_rx.recv().await.expect("op"),
+                codex_protocol::protocol::Op::Interrupt
+            );
+            assert!(codex_op_rx.try_recv().is_err());
+
+            codex_event_tx
+                .send(Event {
+                    id: String::new(),
+                    msg: EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::Interrupted,
+                    },
+                })
+                .expect("round finished");
+        };
+
+        let (exit_info, ()) = tokio::join!(render, driver);
+        let exit_info = exit_info.expect("render");
+        assert!(matches!(
+            exit_info.exit_reason,
+            codex_tui::ExitReason::Interrupted
+        ));
+
+        let events = drain_potter_events(writer_rx);
+        assert!(matches!(
+            events[0].msg,
+            EventMsg::PotterRoundStarted { current: 2, .. }
+        ));
+        let EventMsg::PotterRateLimitWait {
+            used_percent,
+            window_minutes,
+            resets_in,
+        } = &events[1].msg
+        else {
+            panic!("expected PotterRateLimitWait, got {:?}", events[1].msg);
+        };
+        assert_eq!((*used_percent, *window_minutes), (98.0, Some(300)));
+        assert!(resets_in.as_secs() > 3000, "resets_in={resets_in:?}");
     }
 
     #[tokio::test]
-    async fn event_forwarding_round_ui_sends_interrupt_and_waits_for_round_finished() {
+    async fn event_forwarding_round_ui_drops_held_prompt_when_time_budget_runs_out() {
         let (writer_tx, _writer_rx) = unbounded_channel::<JSONRPCMessage>();
-        let (interrupt_tx, interrupt_rx) = watch::channel(false);
+        let (_interrupt_tx, interrupt_rx) = watch::channel(false);
 
         let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<codex_protocol::protocol::Op>();
         let (codex_event_tx, codex_event_rx) = unbounded_channel::<Event>();
@@ -1912,45 +3443,48 @@ mod tests {
             codex_event_rx,
             fatal_exit_rx,
         };
+        let budget = crate::workflow::budget::ProjectBudget::new(
+            None,
+            Some(std::time::Duration::from_millis(50)),
+            Instant::now(),
+        );
+        let mut ui = EventForwardingRoundUi::new(
+            writer_tx,
+            "project_1".to_string(),
+            interrupt_rx,
+            budget,
+            rate_limits_resetting_in_an_hour(),
+        );
 
-        let render = async move {
-            let mut ui = EventForwardingRoundUi::new(writer_tx, interrupt_rx);
-            crate::workflow::round_runner::PotterRoundUi::render_round(&mut ui, params).await
-        };
-
+        let render = crate::workflow::round_runner::PotterRoundUi::render_round(&mut ui, params);
         let driver = async move {
-            let first_op = codex_op_rx.recv().await.expect("op");
             assert_eq!(
-                first_op,
-                codex_protocol::protocol::Op::UserInput {
-                    items: vec![UserInput::Text {
-                        text: "Hello".to_string(),
-                        text_elements: Vec::new(),
-                    }],
-                    final_output_json_schema: None,
-                }
+                codex_op_rx.recv().await.expect("op"),
+                codex_protocol::protocol::Op::Interrupt
+            );
+            assert!(
+                codex_op_rx.try_recv().is_err(),
+                "the held prompt must not be sent once the budget ran out"
             );
-
-            interrupt_tx.send(true).expect("interrupt");
-
-            let second_op = codex_op_rx.recv().await.expect("op");
-            assert_eq!(second_op, codex_protocol::protocol::Op::Interrupt);
 
             codex_event_tx
                 .send(Event {
                     id: String::new(),
                     msg: EventMsg::PotterRoundFinished {
-                        outcome: PotterRoundOutcome::UserRequested,
+                        outcome: PotterRoundOutcome::Interrupted,
                     },
                 })
                 .expect("round finished");
         };
 
         let (exit_info, ()) = tokio::join!(render, driver);
-        let exit_info = exit_info.expect("render");
         assert!(matches!(
-            exit_info.exit_reason,
-            codex_tui::ExitReason::UserRequested
+            exit_info.expect("render").exit_reason,
+            codex_tui::ExitReason::Interrupted
+        ));
+        assert!(matches!(
+            ui.exhausted_budget(),
+            Some(PotterProjectOutcome::TimeBudgetExhausted { .. })
         ));
     }
 
@@ -1968,12 +3502,21 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
         let mut state = ServerState {
             config,
-            running: None,
+            running: BTreeMap::new(),
             resumed: None,
-            interrupted: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2008,6 +3551,207 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn approval_request_is_answered_by_client_response() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let config = PotterAppServerConfig {
+            default_workdir: temp.path().to_path_buf(),
+            codex_bin: "codex".to_string(),
+            backend_launch: crate::app_server::AppServerLaunchConfig {
+                spawn_sandbox: None,
+                thread_sandbox: None,
+                bypass_approvals_and_sandbox: false,
+            },
+            codex_compat_home: None,
+            rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
+            upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
+        };
+        let mut state = ServerState {
+            config,
+            running: BTreeMap::new(),
+            resumed: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
+        };
+
+        let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
+        let (internal_tx, _internal_rx) = unbounded_channel::<InternalEvent>();
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        send_approval_request(
+            &mut state.pending_approvals,
+            &writer_tx,
+            "project_1",
+            ApprovalQuestion {
+                kind: codex_protocol::protocol::PotterApprovalKind::Command,
+                summary: "git push".to_string(),
+                reason: "not covered by any approval rule".to_string(),
+                reply_tx,
+            },
+        );
+
+        let msg = writer_rx.recv().await.expect("approval request");
+        let JSONRPCMessage::Request(request) = msg else {
+            panic!("expected JSONRPC request, got {msg:?}");
+        };
+        assert_eq!(request.method, POTTER_APPROVAL_REQUEST_METHOD);
+        assert_eq!(
+            request.params,
+            Some(serde_json::json!({
+                "projectId": "project_1",
+                "kind": "command",
+                "summary": "git push",
+                "reason": "not covered by any approval rule",
+            }))
+        );
+
+        // Responses to unknown ids are ignored.
+        handle_jsonrpc_message(
+            JSONRPCMessage::Response(JSONRPCResponse {
+                id: RequestId::String("approval-99".to_string()),
+                result: serde_json::json!({ "decision": "approve" }),
+            }),
+            &mut state,
+            &writer_tx,
+            &internal_tx,
+        )
+        .await;
+        handle_jsonrpc_message(
+            JSONRPCMessage::Response(JSONRPCResponse {
+                id: request.id,
+                result: serde_json::json!({
+                    "decision": "deny",
+                    "feedback": "push after review",
+                }),
+            }),
+            &mut state,
+            &writer_tx,
+            &internal_tx,
+        )
+        .await;
+
+        assert_eq!(
+            reply_rx.await.expect("approval answer"),
+            ApprovalRequestResponse {
+                decision: codex_protocol::protocol::PotterApprovalChoice::Deny,
+                feedback: Some("push after review".to_string()),
+            }
+        );
+        assert!(state.pending_approvals.replies.is_empty());
+    }
+
+    #[tokio::test]
+    async fn user_input_request_is_answered_by_client_response() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut state = ServerState {
+            config: PotterAppServerConfig {
+                default_workdir: temp.path().to_path_buf(),
+                codex_bin: "codex".to_string(),
+                backend_launch: crate::app_server::AppServerLaunchConfig {
+                    spawn_sandbox: None,
+                    thread_sandbox: None,
+                    bypass_approvals_and_sandbox: false,
+                },
+                codex_compat_home: None,
+                rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
+                upstream_cli_args: Default::default(),
+                project_cli_args: Default::default(),
+                round_schedule: Default::default(),
+                approval_settings: Default::default(),
+                stream_recovery: Default::default(),
+                drain_queue: false,
+            },
+            running: BTreeMap::new(),
+            resumed: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
+        };
+
+        let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
+        let (internal_tx, _internal_rx) = unbounded_channel::<InternalEvent>();
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        send_user_input_request(
+            &mut state.pending_user_inputs,
+            &writer_tx,
+            "project_1",
+            UserInputQuestion {
+                server_name: Some("deploy".to_string()),
+                message: Some("Confirm the deployment".to_string()),
+                questions: vec![
+                    codex_protocol::request_user_input::RequestUserInputQuestion {
+                        id: "environment".to_string(),
+                        header: "Environment".to_string(),
+                        question: "Where should it go?".to_string(),
+                        is_other: true,
+                        is_secret: false,
+                        options: None,
+                    },
+                ],
+                reply_tx,
+            },
+        );
+
+        let msg = writer_rx.recv().await.expect("user input request");
+        let JSONRPCMessage::Request(request) = msg else {
+            panic!("expected JSONRPC request, got {msg:?}");
+        };
+        assert_eq!(request.method, POTTER_USER_INPUT_REQUEST_METHOD);
+        assert_eq!(request.id, RequestId::String("user-input-1".to_string()));
+        assert_eq!(
+            request.params,
+            Some(serde_json::json!({
+                "projectId": "project_1",
+                "serverName": "deploy",
+                "message": "Confirm the deployment",
+                "questions": [{
+                    "id": "environment",
+                    "header": "Environment",
+                    "question": "Where should it go?",
+                    "isOther": true,
+                    "isSecret": false,
+                }],
+            }))
+        );
+
+        handle_jsonrpc_message(
+            JSONRPCMessage::Response(JSONRPCResponse {
+                id: request.id,
+                result: serde_json::json!({
+                    "answers": { "environment": { "answers": ["staging"] } },
+                }),
+            }),
+            &mut state,
+            &writer_tx,
+            &internal_tx,
+        )
+        .await;
+
+        assert_eq!(
+            reply_rx.await.expect("user input answer"),
+            UserInputRequestResponse {
+                answers: Some(HashMap::from([(
+                    "environment".to_string(),
+                    codex_protocol::request_user_input::RequestUserInputAnswer {
+                        answers: vec!["staging".to_string()],
+                    },
+                )])),
+            }
+        );
+        assert!(state.pending_user_inputs.replies.is_empty());
+    }
+
     #[tokio::test]
     async fn resumed_project_missing_rollout_emits_project_completed_marker() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -2023,6 +3767,11 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let workdir = temp.path().to_path_buf();
@@ -2047,6 +3796,7 @@ mod tests {
                 crate::workflow::rollout_resume_index::UnfinishedRoundIndex {
                     round_current: 1,
                     round_total: 1,
+                    kind: PotterRoundKind::Implement,
                     thread_id: ThreadId::default(),
                     rollout_path: PathBuf::from("missing-rollout.jsonl"),
                 },
@@ -2058,6 +3808,7 @@ mod tests {
                 project_id: project_id.clone(),
                 resolved,
                 progress_file_rel: progress_file_rel.clone(),
+                worktree_dir: None,
                 potter_rollout_lines: Vec::new(),
                 index,
             },
@@ -2067,15 +3818,24 @@ mod tests {
             rounds_total: 1,
             resume_policy: ResumePolicy::ContinueUnfinishedRound,
             event_mode: PotterEventMode::Interactive,
+            output_schema: None,
             project_started_at: Instant::now(),
         };
 
         let (writer_tx, writer_rx) = unbounded_channel::<JSONRPCMessage>();
         let (_interrupt_tx, interrupt_rx) = watch::channel(false);
 
-        run_resumed_project(config, writer_tx, plan, interrupt_rx)
-            .await
-            .expect("run resumed project");
+        run_resumed_project(
+            config,
+            writer_tx,
+            project_id,
+            plan,
+            interrupt_rx,
+            ApprovalAsker::new(unbounded_channel().0),
+            UserInputAsker::new(unbounded_channel().0),
+        )
+        .await
+        .expect("run resumed project");
 
         let events = drain_potter_events(writer_rx);
         assert!(
@@ -2113,6 +3873,11 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let handle = tokio::spawn(async {
@@ -2122,13 +3887,19 @@ mod tests {
 
         let mut state = ServerState {
             config,
-            running: Some(RunningProject {
-                project_id: "project_1".to_string(),
-                handle,
-                interrupt_tx,
-            }),
+            running: BTreeMap::from([(
+                "project_1".to_string(),
+                RunningProject {
+                    handle,
+                    interrupt_tx,
+                },
+            )]),
             resumed: None,
-            interrupted: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2157,7 +3928,7 @@ mod tests {
         assert_eq!(response.result, serde_json::json!({}));
 
         assert!(
-            state.running.is_some(),
+            state.running.contains_key("project_1"),
             "expected running project to remain active; got state.running={:?}",
             state.running
         );
@@ -2166,7 +3937,7 @@ mod tests {
             "expected interrupt flag to be set on first request"
         );
 
-        let running = state.running.take().expect("running project");
+        let running = state.running.remove("project_1").expect("running project");
         running.handle.abort();
         let _ = running.handle.await;
     }
@@ -2186,6 +3957,11 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         struct DropNotify(Option<tokio::sync::oneshot::Sender<()>>);
@@ -2210,13 +3986,19 @@ mod tests {
 
         let mut state = ServerState {
             config,
-            running: Some(RunningProject {
-                project_id: "project_1".to_string(),
-                handle,
-                interrupt_tx,
-            }),
+            running: BTreeMap::from([(
+                "project_1".to_string(),
+                RunningProject {
+                    handle,
+                    interrupt_tx,
+                },
+            )]),
             resumed: None,
-            interrupted: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2247,7 +4029,7 @@ mod tests {
         }
 
         assert!(
-            state.running.is_none(),
+            state.running.is_empty(),
             "expected running project to be force-aborted on second interrupt; got state.running={:?}",
             state.running
         );
@@ -2274,6 +4056,11 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let handle = tokio::spawn(async {
@@ -2283,13 +4070,19 @@ mod tests {
 
         let mut state = ServerState {
             config,
-            running: Some(RunningProject {
-                project_id: "project_1".to_string(),
-                handle,
-                interrupt_tx,
-            }),
+            running: BTreeMap::from([(
+                "project_1".to_string(),
+                RunningProject {
+                    handle,
+                    interrupt_tx,
+                },
+            )]),
             resumed: None,
-            interrupted: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2323,15 +4116,12 @@ mod tests {
         );
 
         assert!(
-            state
-                .running
-                .as_ref()
-                .is_some_and(|running| running.project_id == "project_1"),
+            state.running.contains_key("project_1"),
             "expected running project to be preserved; got state.running={:?}",
             state.running
         );
 
-        let running = state.running.take().expect("running project");
+        let running = state.running.remove("project_1").expect("running project");
         running.handle.abort();
         let _ = running.handle.await;
     }
@@ -2351,6 +4141,11 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let handle = tokio::spawn(async {});
@@ -2358,13 +4153,19 @@ mod tests {
 
         let mut state = ServerState {
             config,
-            running: Some(RunningProject {
-                project_id: "project_1".to_string(),
-                handle,
-                interrupt_tx,
-            }),
+            running: BTreeMap::from([(
+                "project_1".to_string(),
+                RunningProject {
+                    handle,
+                    interrupt_tx,
+                },
+            )]),
             resumed: None,
-            interrupted: None,
+            interrupted: BTreeMap::new(),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         tokio::task::yield_now().await;
@@ -2372,7 +4173,7 @@ mod tests {
         clear_finished_running_project(&mut state);
 
         assert!(
-            state.running.is_none(),
+            state.running.is_empty(),
             "expected running state to be cleared for finished tasks; got {:?}",
             state.running
         );
@@ -2394,10 +4195,16 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let plan = FreshProjectPlan {
             workdir: workdir.clone(),
+            worktree_dir: None,
             user_message: "hello".to_string(),
             project_dir_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1"),
             progress_file_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md"),
@@ -2405,6 +4212,7 @@ mod tests {
             potter_rollout_path: workdir.join("potter-rollout.jsonl"),
             rounds_total: 1,
             event_mode: PotterEventMode::Interactive,
+            output_schema: None,
             project_started_at: Instant::now(),
             round_start_index: 0,
             emit_project_started_event: true,
@@ -2416,13 +4224,18 @@ mod tests {
             user_prompt_file: plan.progress_file_rel.clone(),
             rounds_run: 1,
             plan,
+            pause: ProjectPause::Interrupted,
         };
 
         let mut state = ServerState {
             config,
-            running: None,
+            running: BTreeMap::new(),
             resumed: None,
-            interrupted: Some(interrupted_project),
+            interrupted: BTreeMap::from([("project_1".to_string(), interrupted_project)]),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, _writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2445,7 +4258,7 @@ mod tests {
             "unexpected error: {err:#}"
         );
         assert!(
-            state.interrupted.is_some(),
+            state.interrupted.contains_key("project_1"),
             "expected interrupted state to remain on validation failure"
         );
     }
@@ -2457,6 +4270,7 @@ mod tests {
 
         let plan = FreshProjectPlan {
             workdir: workdir.clone(),
+            worktree_dir: None,
             user_message: "hello".to_string(),
             project_dir_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1"),
             progress_file_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md"),
@@ -2464,6 +4278,7 @@ mod tests {
             potter_rollout_path: workdir.join("potter-rollout.jsonl"),
             rounds_total: 3,
             event_mode: PotterEventMode::Interactive,
+            output_schema: None,
             project_started_at: Instant::now(),
             round_start_index: 0,
             emit_project_started_event: true,
@@ -2486,6 +4301,7 @@ mod tests {
 
         let plan = FreshProjectPlan {
             workdir: workdir.clone(),
+            worktree_dir: None,
             user_message: "hello".to_string(),
             project_dir_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1"),
             progress_file_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md"),
@@ -2493,6 +4309,7 @@ mod tests {
             potter_rollout_path: workdir.join("potter-rollout.jsonl"),
             rounds_total: 1,
             event_mode: PotterEventMode::Interactive,
+            output_schema: None,
             project_started_at: Instant::now(),
             round_start_index: 0,
             emit_project_started_event: true,
@@ -2507,6 +4324,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fresh_project_plan_continuation_after_plan_review_starts_next_round() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path().to_path_buf();
+
+        let plan = FreshProjectPlan {
+            workdir: workdir.clone(),
+            worktree_dir: None,
+            user_message: "hello".to_string(),
+            project_dir_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1"),
+            progress_file_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md"),
+            git_commit_start: String::from("start"),
+            potter_rollout_path: workdir.join("potter-rollout.jsonl"),
+            rounds_total: 3,
+            event_mode: PotterEventMode::Interactive,
+            output_schema: None,
+            project_started_at: Instant::now(),
+            round_start_index: 0,
+            emit_project_started_event: true,
+            initial_turn_prompt_override: Some(String::from("override")),
+        };
+
+        let continuation = plan.continuation_after_plan_review(0);
+        assert_eq!(continuation.round_start_index, 1);
+        assert!(!continuation.emit_project_started_event);
+        assert!(continuation.initial_turn_prompt_override.is_none());
+        assert_eq!(continuation.rounds_total, 3);
+    }
+
     #[test]
     fn resolve_interrupt_stop_returns_summary_and_emits_completed_marker() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -2525,12 +4371,18 @@ mod tests {
             codex_compat_home: None,
             rounds: NonZeroUsize::new(1).expect("nonzero rounds"),
             upstream_cli_args: Default::default(),
+            project_cli_args: Default::default(),
+            round_schedule: Default::default(),
+            approval_settings: Default::default(),
+            stream_recovery: Default::default(),
+            drain_queue: false,
         };
 
         let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md");
 
         let plan = FreshProjectPlan {
             workdir: workdir.clone(),
+            worktree_dir: None,
             user_message: "hello".to_string(),
             project_dir_rel: PathBuf::from(".codexpotter/projects/2026/03/06/1"),
             progress_file_rel: progress_file_rel.clone(),
@@ -2538,6 +4390,7 @@ mod tests {
             potter_rollout_path: workdir.join("potter-rollout.jsonl"),
             rounds_total: 3,
             event_mode: PotterEventMode::Interactive,
+            output_schema: None,
             project_started_at: Instant::now(),
             round_start_index: 1,
             emit_project_started_event: false,
@@ -2549,13 +4402,18 @@ mod tests {
             user_prompt_file: progress_file_rel.clone(),
             rounds_run: 2,
             plan,
+            pause: ProjectPause::Interrupted,
         };
 
         let mut state = ServerState {
             config,
-            running: None,
+            running: BTreeMap::new(),
             resumed: None,
-            interrupted: Some(interrupted_project),
+            interrupted: BTreeMap::from([("project_1".to_string(), interrupted_project)]),
+            owners: BTreeMap::new(),
+            pending_approvals: PendingApprovals::default(),
+            pending_user_inputs: PendingUserInputs::default(),
+            journal: EventJournal::default(),
         };
 
         let (writer_tx, writer_rx) = unbounded_channel::<JSONRPCMessage>();
@@ -2574,7 +4432,7 @@ mod tests {
         .expect("resolve_interrupt stop");
 
         assert!(
-            state.interrupted.is_none(),
+            state.interrupted.is_empty(),
             "expected interrupted state cleared"
         );
 
@@ -2607,10 +4465,10 @@ mod tests {
             let Some(params) = notification.params else {
                 continue;
             };
-            let Ok(event) = serde_json::from_value::<Event>(params) else {
+            let Ok(event) = PotterProjectEvent::from_notification_params(params) else {
                 continue;
             };
-            events.push(event);
+            events.push(event.event);
         }
         events
     }
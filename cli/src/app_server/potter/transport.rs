@@ -0,0 +1,1144 @@
+//! Transports for `codex-potter app-server`.
+//!
+//! By default the server speaks newline-delimited JSON-RPC over its own stdin/stdout, so only the
+//! process that spawned it (see [`super::PotterAppServerClient`]) can talk to it. With
+//! `--listen unix://PATH` or `--listen tcp://IP:PORT` it accepts any number of client connections
+//! instead, using the same line format on each:
+//!
+//! - Every connection gets the responses to its own requests. Request ids are replaced by
+//!   server-unique ids on the way in and restored on the way out, so clients may reuse ids.
+//! - Event notifications (`codex/event/potter`) are sent to every connection.
+//! - Server → client requests of a project (`approval/request`, `userInput/request`) go to the
+//!   project's owner: the connection that last started, resumed or attached to it. When the owner
+//!   closes or detaches from the project, its unanswered requests (and later ones) go to every
+//!   other connection instead, and the first answer wins. Clients that only watch (dashboards)
+//!   should ignore server requests rather than answer them.
+//! - After `project/detach`, a connection no longer gets the events and server requests of that
+//!   project; `project/attach` (without `projectId`: for every project) subscribes it again.
+//! - With `--listen-token`, the first message of a connection must be an `initialize` request
+//!   carrying the token as `params.token`; otherwise the connection is refused. TCP listeners
+//!   always require a token, compared in constant time. Unix sockets are created in a private
+//!   directory and restricted to the current user before they are moved into place, so no other
+//!   user can ever connect.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::app_server::upstream_protocol::JSONRPCError;
+use crate::app_server::upstream_protocol::JSONRPCErrorError;
+use crate::app_server::upstream_protocol::JSONRPCMessage;
+use crate::app_server::upstream_protocol::JSONRPCNotification;
+use crate::app_server::upstream_protocol::JSONRPCRequest;
+use crate::app_server::upstream_protocol::JSONRPCResponse;
+use crate::app_server::upstream_protocol::RequestId;
+
+/// `initialize` param carrying the `--listen-token` of a listening server.
+pub const POTTER_LISTEN_TOKEN_PARAM: &str = "token";
+
+/// How the server talks to its clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PotterAppServerTransport {
+    /// A single client on stdin/stdout (the spawning process).
+    Stdio,
+    /// Any number of clients connecting to `address`.
+    Listen {
+        address: PotterAppServerListenAddress,
+        /// Required from every connection when set (always set for TCP).
+        token: Option<String>,
+    },
+}
+
+/// Address given to `--listen`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PotterAppServerListenAddress {
+    /// `unix://PATH`
+    Unix(PathBuf),
+    /// `tcp://IP:PORT`
+    Tcp(SocketAddr),
+}
+
+impl fmt::Display for PotterAppServerListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+        }
+    }
+}
+
+/// Parse a `--listen` value (`unix://PATH` or `tcp://IP:PORT`).
+pub fn parse_listen_address(value: &str) -> Result<PotterAppServerListenAddress, String> {
+    if let Some(path) = value.strip_prefix("unix://") {
+        if path.is_empty() {
+            return Err(format!("missing socket path in `{value}`"));
+        }
+        return Ok(PotterAppServerListenAddress::Unix(PathBuf::from(path)));
+    }
+    if let Some(addr) = value.strip_prefix("tcp://") {
+        return addr
+            .parse()
+            .map(PotterAppServerListenAddress::Tcp)
+            .map_err(|_| format!("invalid address `{value}` (expected tcp://IP:PORT)"));
+    }
+    Err(format!(
+        "unsupported listen address `{value}` (expected unix://PATH or tcp://IP:PORT)"
+    ))
+}
+
+pub(super) fn decode_jsonrpc_message_line(line: &str) -> anyhow::Result<Option<JSONRPCMessage>> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let msg: JSONRPCMessage = serde_json::from_str(trimmed)
+        .with_context(|| format!("decode potter app-server JSON-RPC: {trimmed:?}"))?;
+    Ok(Some(msg))
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    msg: &JSONRPCMessage,
+) -> anyhow::Result<()> {
+    let json = serde_json::to_vec(msg).context("serialize potter app-server jsonrpc")?;
+    writer
+        .write_all(&json)
+        .await
+        .context("write potter app-server message")?;
+    writer
+        .write_all(b"\n")
+        .await
+        .context("write potter app-server newline")?;
+    writer
+        .flush()
+        .await
+        .context("flush potter app-server output")?;
+    Ok(())
+}
+
+/// Serve the single stdin/stdout client until `writer_rx` closes.
+///
+/// `incoming_tx` is dropped when stdin reaches EOF.
+pub(super) async fn serve_stdio(
+    incoming_tx: UnboundedSender<JSONRPCMessage>,
+    mut writer_rx: UnboundedReceiver<JSONRPCMessage>,
+) -> anyhow::Result<()> {
+    let reader = tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        loop {
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    eprintln!("warning: read potter app-server stdin line: {err}");
+                    break;
+                }
+            };
+            match decode_jsonrpc_message_line(&line) {
+                Ok(Some(msg)) => {
+                    if incoming_tx.send(msg).is_err() {
+                        break;
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => eprintln!("warning: {err:#}"),
+            }
+        }
+    });
+
+    let mut stdout = tokio::io::stdout();
+    while let Some(msg) = writer_rx.recv().await {
+        write_message(&mut stdout, &msg)
+            .await
+            .context("write potter app-server stdout")?;
+    }
+    reader.abort();
+    Ok(())
+}
+
+type ConnectionReader = Box<dyn AsyncRead + Unpin + Send>;
+type ConnectionWriter = Box<dyn AsyncWrite + Unpin + Send>;
+
+enum Listener {
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+    Tcp(tokio::net::TcpListener),
+}
+
+/// A bound `--listen` socket.
+pub(super) struct PotterAppServerListener {
+    listener: Listener,
+    /// Bound address; for `tcp://IP:0` this carries the port picked by the OS.
+    address: PotterAppServerListenAddress,
+    token: Option<String>,
+    /// Socket file created for a `unix://` address, removed again on drop.
+    #[cfg(unix)]
+    socket_file: Option<SocketFile>,
+}
+
+impl PotterAppServerListener {
+    pub(super) async fn bind(
+        address: &PotterAppServerListenAddress,
+        token: Option<String>,
+    ) -> anyhow::Result<Self> {
+        match address {
+            PotterAppServerListenAddress::Unix(path) => bind_unix(address, path, token),
+            PotterAppServerListenAddress::Tcp(addr) => {
+                anyhow::ensure!(
+                    token.is_some(),
+                    "listening on {address} requires --listen-token"
+                );
+                let listener = tokio::net::TcpListener::bind(addr)
+                    .await
+                    .with_context(|| format!("listen on {address}"))?;
+                let addr = listener
+                    .local_addr()
+                    .with_context(|| format!("resolve address of {address}"))?;
+                Ok(Self {
+                    listener: Listener::Tcp(listener),
+                    address: PotterAppServerListenAddress::Tcp(addr),
+                    token,
+                    #[cfg(unix)]
+                    socket_file: None,
+                })
+            }
+        }
+    }
+
+    pub(super) fn address(&self) -> &PotterAppServerListenAddress {
+        &self.address
+    }
+
+    async fn accept(&self) -> std::io::Result<(ConnectionReader, ConnectionWriter)> {
+        match &self.listener {
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept().await?;
+                let (reader, writer) = stream.into_split();
+                Ok((Box::new(reader), Box::new(writer)))
+            }
+            Listener::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                let (reader, writer) = stream.into_split();
+                Ok((Box::new(reader), Box::new(writer)))
+            }
+        }
+    }
+
+    /// Accept clients and route messages between them and the server until `writer_rx` or
+    /// `incoming_tx` closes.
+    pub(super) async fn serve(
+        self,
+        incoming_tx: UnboundedSender<JSONRPCMessage>,
+        mut writer_rx: UnboundedReceiver<JSONRPCMessage>,
+    ) -> anyhow::Result<()> {
+        let (events_tx, mut events_rx) = unbounded_channel::<ConnectionEvent>();
+        let mut router = ConnectionRouter::default();
+        let mut next_connection: u64 = 0;
+
+        loop {
+            tokio::select! {
+                accepted = self.accept() => match accepted {
+                    Ok((reader, writer)) => {
+                        next_connection = next_connection.saturating_add(1);
+                        spawn_connection(
+                            next_connection,
+                            reader,
+                            writer,
+                            self.token.clone(),
+                            events_tx.clone(),
+                        );
+                    }
+                    Err(err) => {
+                        eprintln!("warning: accept potter app-server connection: {err}");
+                    }
+                },
+                Some(event) = events_rx.recv() => match event {
+                    ConnectionEvent::Opened { connection, writer_tx } => {
+                        router.open(connection, writer_tx);
+                    }
+                    ConnectionEvent::Message { connection, msg } => {
+                        if incoming_tx.send(router.incoming(connection, msg)).is_err() {
+                            break;
+                        }
+                    }
+                    ConnectionEvent::Closed { connection } => router.close(connection),
+                },
+                msg = writer_rx.recv() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    router.outgoing(msg);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+impl Drop for PotterAppServerListener {
+    fn drop(&mut self) {
+        if let Some(socket_file) = &self.socket_file
+            && socket_file.is_in_place()
+        {
+            let _ = std::fs::remove_file(&socket_file.path);
+        }
+    }
+}
+
+/// The socket file a `unix://` listener created, identified by device and inode so a file that
+/// replaced it is never removed.
+#[cfg(unix)]
+struct SocketFile {
+    path: std::path::PathBuf,
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+impl SocketFile {
+    fn is_in_place(&self) -> bool {
+        use std::os::unix::fs::FileTypeExt as _;
+        use std::os::unix::fs::MetadataExt as _;
+
+        std::fs::symlink_metadata(&self.path).is_ok_and(|metadata| {
+            metadata.file_type().is_socket()
+                && metadata.dev() == self.dev
+                && metadata.ino() == self.ino
+        })
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix(
+    address: &PotterAppServerListenAddress,
+    path: &std::path::Path,
+    token: Option<String>,
+) -> anyhow::Result<PotterAppServerListener> {
+    use std::os::unix::fs::FileTypeExt as _;
+    use std::os::unix::fs::MetadataExt as _;
+    use std::os::unix::fs::PermissionsExt as _;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) => {
+            anyhow::ensure!(
+                metadata.file_type().is_socket(),
+                "{} exists and is not a socket; refusing to replace it",
+                path.display()
+            );
+            // A socket nobody accepts on is left over from a server that did not exit cleanly.
+            if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                anyhow::bail!(
+                    "another app-server is already listening on unix://{}",
+                    path.display()
+                );
+            }
+            std::fs::remove_file(path)
+                .with_context(|| format!("remove stale socket {}", path.display()))?;
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => {
+            return Err(err).with_context(|| format!("inspect {}", path.display()));
+        }
+    }
+
+    // Bind in a fresh 0700 directory next to `path`, restrict the socket, then move it into place:
+    // binding at `path` directly would leave it connectable by anyone until the chmod.
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    let private_dir = tempfile::Builder::new()
+        .prefix(".potter-listen-")
+        .permissions(std::fs::Permissions::from_mode(0o700))
+        .tempdir_in(parent)
+        .with_context(|| format!("create a private directory in {}", parent.display()))?;
+    let private_path = private_dir.path().join("socket");
+    let listener = tokio::net::UnixListener::bind(&private_path)
+        .with_context(|| format!("listen on unix://{}", path.display()))?;
+    std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("restrict permissions of {}", private_path.display()))?;
+    let metadata = std::fs::symlink_metadata(&private_path)
+        .with_context(|| format!("inspect {}", private_path.display()))?;
+    std::fs::rename(&private_path, path)
+        .with_context(|| format!("move socket to {}", path.display()))?;
+    Ok(PotterAppServerListener {
+        listener: Listener::Unix(listener),
+        address: address.clone(),
+        token,
+        socket_file: Some(SocketFile {
+            path: path.to_path_buf(),
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+        }),
+    })
+}
+
+#[cfg(not(unix))]
+fn bind_unix(
+    _address: &PotterAppServerListenAddress,
+    _path: &std::path::Path,
+    _token: Option<String>,
+) -> anyhow::Result<PotterAppServerListener> {
+    anyhow::bail!("unix:// listen addresses are not supported on this platform")
+}
+
+enum ConnectionEvent {
+    /// The connection is authenticated and receives responses and broadcasts from now on.
+    Opened {
+        connection: u64,
+        writer_tx: UnboundedSender<JSONRPCMessage>,
+    },
+    Message {
+        connection: u64,
+        msg: JSONRPCMessage,
+    },
+    Closed {
+        connection: u64,
+    },
+}
+
+/// Run one client connection: a writer task draining its queue, and a reader task that checks the
+/// token and forwards messages to the router.
+fn spawn_connection(
+    connection: u64,
+    reader: ConnectionReader,
+    mut writer: ConnectionWriter,
+    token: Option<String>,
+    events_tx: UnboundedSender<ConnectionEvent>,
+) {
+    let (writer_tx, mut writer_rx) = unbounded_channel::<JSONRPCMessage>();
+    tokio::spawn(async move {
+        while let Some(msg) = writer_rx.recv().await {
+            if write_message(&mut writer, &msg).await.is_err() {
+                break;
+            }
+        }
+        let _ = writer.shutdown().await;
+    });
+
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        let mut opened = false;
+        while let Ok(Some(line)) = lines.next_line().await {
+            let msg = match decode_jsonrpc_message_line(&line) {
+                Ok(Some(msg)) => msg,
+                Ok(None) => continue,
+                Err(err) => {
+                    eprintln!("warning: {err:#}");
+                    continue;
+                }
+            };
+            if !opened {
+                if let Some(token) = &token
+                    && let Err(error) = check_token(&msg, token)
+                {
+                    let _ = writer_tx.send(JSONRPCMessage::Error(error));
+                    break;
+                }
+                opened = true;
+                let opened_event = ConnectionEvent::Opened {
+                    connection,
+                    writer_tx: writer_tx.clone(),
+                };
+                if events_tx.send(opened_event).is_err() {
+                    break;
+                }
+            }
+            if events_tx
+                .send(ConnectionEvent::Message { connection, msg })
+                .is_err()
+            {
+                break;
+            }
+        }
+        let _ = events_tx.send(ConnectionEvent::Closed { connection });
+    });
+}
+
+/// Check that the first message of a connection is an `initialize` request carrying `token`.
+fn check_token(msg: &JSONRPCMessage, token: &str) -> Result<(), JSONRPCError> {
+    let JSONRPCMessage::Request(JSONRPCRequest { id, method, params }) = msg else {
+        return Err(unauthorized(RequestId::Integer(0)));
+    };
+    let presented = params
+        .as_ref()
+        .and_then(|params| params.get(POTTER_LISTEN_TOKEN_PARAM))
+        .and_then(serde_json::Value::as_str);
+    if method == "initialize"
+        && presented
+            .is_some_and(|presented| constant_time_eq(presented.as_bytes(), token.as_bytes()))
+    {
+        Ok(())
+    } else {
+        Err(unauthorized(id.clone()))
+    }
+}
+
+/// Compare without exiting at the first differing byte, so response timing does not reveal how
+/// much of a guessed token is right. Only the length can leak.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |diff, (a, b)| diff | std::hint::black_box(a ^ b));
+    diff == 0
+}
+
+fn unauthorized(id: RequestId) -> JSONRPCError {
+    JSONRPCError {
+        id,
+        error: JSONRPCErrorError {
+            code: -32001,
+            data: None,
+            message: "unauthorized: the first request must be `initialize` with a valid token"
+                .to_string(),
+        },
+    }
+}
+
+/// Requests whose response makes the requesting connection the owner of `result.projectId`.
+const OWNING_METHODS: [&str; 3] = ["project/start", "project/resume", "project/attach"];
+
+/// A client request awaiting its response.
+#[derive(Debug)]
+struct PendingClientRequest {
+    connection: u64,
+    /// The id the client used.
+    client_id: RequestId,
+    method: String,
+}
+
+/// A server request of a project awaiting an answer.
+#[derive(Debug)]
+struct PendingServerRequest {
+    project_id: String,
+    msg: JSONRPCMessage,
+    /// Connections it was sent to.
+    sent_to: BTreeSet<u64>,
+}
+
+/// Routes server output to client connections.
+#[derive(Debug, Default)]
+struct ConnectionRouter {
+    connections: BTreeMap<u64, UnboundedSender<JSONRPCMessage>>,
+    next_request_id: i64,
+    /// Server-side request id → the client request it stands for.
+    requests: HashMap<RequestId, PendingClientRequest>,
+    /// Connection and project id pairs left with `project/detach`.
+    detached: BTreeSet<(u64, String)>,
+    /// Project id → connection its server requests go to.
+    owners: HashMap<String, u64>,
+    /// Unanswered server requests of projects, by request id.
+    server_requests: HashMap<RequestId, PendingServerRequest>,
+}
+
+impl ConnectionRouter {
+    fn open(&mut self, connection: u64, writer_tx: UnboundedSender<JSONRPCMessage>) {
+        self.connections.insert(connection, writer_tx);
+    }
+
+    fn close(&mut self, connection: u64) {
+        self.connections.remove(&connection);
+        self.requests
+            .retain(|_, request| request.connection != connection);
+        self.detached.retain(|(owner, _)| *owner != connection);
+        let orphaned = self
+            .owners
+            .iter()
+            .filter(|(_, owner)| **owner == connection)
+            .map(|(project_id, _)| project_id.clone())
+            .collect::<Vec<_>>();
+        for project_id in orphaned {
+            self.disown(&project_id);
+        }
+    }
+
+    /// Drop the owner of `project_id` and hand its unanswered requests to every other connection.
+    fn disown(&mut self, project_id: &str) {
+        self.owners.remove(project_id);
+        let pending = self
+            .server_requests
+            .keys()
+            .filter(|id| self.server_requests[*id].project_id == project_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        for id in pending {
+            self.send_server_request(&id);
+        }
+    }
+
+    /// Prepare a client message for the server: requests get a server-unique id.
+    fn incoming(&mut self, connection: u64, msg: JSONRPCMessage) -> JSONRPCMessage {
+        let mut request = match msg {
+            JSONRPCMessage::Request(request) => request,
+            // Answers to server requests.
+            JSONRPCMessage::Response(JSONRPCResponse { ref id, .. })
+            | JSONRPCMessage::Error(JSONRPCError { ref id, .. }) => {
+                self.server_requests.remove(id);
+                return msg;
+            }
+            JSONRPCMessage::Notification(_) => return msg,
+        };
+        let project_id = project_id(request.params.as_ref()).map(str::to_string);
+        match (request.method.as_str(), project_id) {
+            ("project/detach", Some(project_id)) => {
+                let owned = self.owners.get(&project_id) == Some(&connection);
+                self.detached.insert((connection, project_id.clone()));
+                if owned {
+                    self.disown(&project_id);
+                }
+            }
+            ("project/attach", Some(project_id)) => {
+                self.detached.remove(&(connection, project_id));
+            }
+            ("project/attach", None) => {
+                self.detached.retain(|(owner, _)| *owner != connection);
+            }
+            _ => {}
+        }
+        self.next_request_id = self.next_request_id.saturating_add(1);
+        let id = RequestId::Integer(self.next_request_id);
+        let client_id = std::mem::replace(&mut request.id, id.clone());
+        self.requests.insert(
+            id,
+            PendingClientRequest {
+                connection,
+                client_id,
+                method: request.method.clone(),
+            },
+        );
+        JSONRPCMessage::Request(request)
+    }
+
+    /// Send a server message to the connection it answers, the owner of its project, or to every
+    /// connection.
+    fn outgoing(&mut self, msg: JSONRPCMessage) {
+        match msg {
+            JSONRPCMessage::Response(mut response) => {
+                if let Some(request) = self.requests.remove(&response.id) {
+                    if OWNING_METHODS.contains(&request.method.as_str())
+                        && let Some(project_id) = project_id(Some(&response.result))
+                    {
+                        self.owners
+                            .insert(project_id.to_string(), request.connection);
+                    }
+                    response.id = request.client_id;
+                    self.send(request.connection, JSONRPCMessage::Response(response));
+                }
+            }
+            JSONRPCMessage::Error(mut error) => {
+                if let Some(request) = self.requests.remove(&error.id) {
+                    error.id = request.client_id;
+                    self.send(request.connection, JSONRPCMessage::Error(error));
+                }
+            }
+            JSONRPCMessage::Request(JSONRPCRequest {
+                ref id, ref params, ..
+            }) if project_id(params.as_ref()).is_some() => {
+                let id = id.clone();
+                let project_id = project_id(params.as_ref()).unwrap_or_default().to_string();
+                self.server_requests.insert(
+                    id.clone(),
+                    PendingServerRequest {
+                        project_id,
+                        msg,
+                        sent_to: BTreeSet::new(),
+                    },
+                );
+                self.send_server_request(&id);
+            }
+            JSONRPCMessage::Notification(JSONRPCNotification { ref params, .. })
+            | JSONRPCMessage::Request(JSONRPCRequest { ref params, .. }) => {
+                let project_id = project_id(params.as_ref());
+                for connection in self.connections.keys() {
+                    if !project_id
+                        .is_some_and(|project_id| self.is_detached(*connection, project_id))
+                    {
+                        self.send(*connection, msg.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Send an unanswered server request to its project's owner, or without a reachable owner to
+    /// every connection following the project that has not got it yet.
+    fn send_server_request(&mut self, id: &RequestId) {
+        let Some(request) = self.server_requests.get(id) else {
+            return;
+        };
+        let owner = self
+            .owners
+            .get(&request.project_id)
+            .copied()
+            .filter(|owner| {
+                self.connections.contains_key(owner)
+                    && !self.is_detached(*owner, &request.project_id)
+            });
+        let targets = match owner {
+            Some(owner) => vec![owner],
+            None => self
+                .connections
+                .keys()
+                .copied()
+                .filter(|connection| !self.is_detached(*connection, &request.project_id))
+                .collect(),
+        };
+        let targets = targets
+            .into_iter()
+            .filter(|connection| !request.sent_to.contains(connection))
+            .collect::<Vec<_>>();
+        for connection in &targets {
+            self.send(*connection, request.msg.clone());
+        }
+        if let Some(request) = self.server_requests.get_mut(id) {
+            request.sent_to.extend(targets);
+        }
+    }
+
+    fn is_detached(&self, connection: u64, project_id: &str) -> bool {
+        self.detached
+            .contains(&(connection, project_id.to_string()))
+    }
+
+    fn send(&self, connection: u64, msg: JSONRPCMessage) {
+        if let Some(writer_tx) = self.connections.get(&connection) {
+            let _ = writer_tx.send(msg);
+        }
+    }
+}
+
+/// `params.projectId` of a request, event notification or server request.
+fn project_id(params: Option<&serde_json::Value>) -> Option<&str> {
+    params?.get("projectId")?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn decode_jsonrpc_message_line_errors_on_invalid_json() {
+        let err = decode_jsonrpc_message_line("{not json").expect_err("should fail");
+        assert!(
+            err.to_string()
+                .contains("decode potter app-server JSON-RPC")
+        );
+    }
+
+    #[test]
+    fn decode_jsonrpc_message_line_ignores_empty_lines() {
+        assert!(
+            decode_jsonrpc_message_line(" \t ")
+                .expect("decode")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_listen_address_accepts_unix_and_tcp() {
+        assert_eq!(
+            parse_listen_address("unix:///tmp/potter.sock"),
+            Ok(PotterAppServerListenAddress::Unix(PathBuf::from(
+                "/tmp/potter.sock"
+            )))
+        );
+        assert_eq!(
+            parse_listen_address("tcp://127.0.0.1:4500"),
+            Ok(PotterAppServerListenAddress::Tcp(
+                "127.0.0.1:4500".parse().expect("socket addr")
+            ))
+        );
+        assert_eq!(
+            parse_listen_address("tcp://localhost:4500"),
+            Err("invalid address `tcp://localhost:4500` (expected tcp://IP:PORT)".to_string())
+        );
+        assert_eq!(
+            parse_listen_address("unix://"),
+            Err("missing socket path in `unix://`".to_string())
+        );
+        assert_eq!(
+            parse_listen_address("/tmp/potter.sock"),
+            Err(
+                "unsupported listen address `/tmp/potter.sock` (expected unix://PATH or \
+                 tcp://IP:PORT)"
+                    .to_string()
+            )
+        );
+    }
+
+    fn request(id: i64, method: &str) -> JSONRPCMessage {
+        JSONRPCMessage::Request(JSONRPCRequest {
+            id: RequestId::Integer(id),
+            method: method.to_string(),
+            params: None,
+        })
+    }
+
+    fn response(id: RequestId, result: serde_json::Value) -> JSONRPCMessage {
+        JSONRPCMessage::Response(JSONRPCResponse { id, result })
+    }
+
+    fn drain(rx: &mut UnboundedReceiver<JSONRPCMessage>) -> Vec<JSONRPCMessage> {
+        let mut messages = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            messages.push(msg);
+        }
+        messages
+    }
+
+    #[test]
+    fn router_answers_each_connection_with_its_own_request_ids() {
+        let mut router = ConnectionRouter::default();
+        let (first_tx, mut first_rx) = unbounded_channel();
+        let (second_tx, mut second_rx) = unbounded_channel();
+        router.open(1, first_tx);
+        router.open(2, second_tx);
+
+        // Both clients use id 1; the server sees distinct ids.
+        let first = router.incoming(1, request(1, "project/list"));
+        let second = router.incoming(2, request(1, "project/list"));
+        let (JSONRPCMessage::Request(first), JSONRPCMessage::Request(second)) = (first, second)
+        else {
+            panic!("requests stay requests");
+        };
+        assert_ne!(first.id, second.id);
+
+        router.outgoing(response(second.id, json!("second")));
+        router.outgoing(response(first.id.clone(), json!("first")));
+        // Answered ids are forgotten.
+        router.outgoing(response(first.id, json!("again")));
+
+        assert_eq!(
+            drain(&mut first_rx),
+            vec![response(RequestId::Integer(1), json!("first"))]
+        );
+        assert_eq!(
+            drain(&mut second_rx),
+            vec![response(RequestId::Integer(1), json!("second"))]
+        );
+    }
+
+    #[test]
+    fn router_broadcasts_notifications_and_server_requests() {
+        let mut router = ConnectionRouter::default();
+        let (first_tx, mut first_rx) = unbounded_channel();
+        let (second_tx, mut second_rx) = unbounded_channel();
+        router.open(1, first_tx);
+        router.open(2, second_tx);
+        let pending = router.incoming(2, request(7, "project/interrupt"));
+        router.close(2);
+
+        let notification = JSONRPCMessage::Notification(JSONRPCNotification {
+            method: "codex/event/potter".to_string(),
+            params: Some(json!({ "id": "", "msg": { "type": "potter_project_budget" } })),
+        });
+        let approval = JSONRPCMessage::Request(JSONRPCRequest {
+            id: RequestId::String("approval-1".to_string()),
+            method: "approval/request".to_string(),
+            params: None,
+        });
+        router.outgoing(notification.clone());
+        router.outgoing(approval.clone());
+        let JSONRPCMessage::Request(pending) = pending else {
+            panic!("requests stay requests");
+        };
+        router.outgoing(response(pending.id, json!({})));
+
+        assert_eq!(drain(&mut first_rx), vec![notification, approval]);
+        assert_eq!(drain(&mut second_rx), Vec::new());
+        // Answers to server requests pass through unchanged.
+        let answer = response(RequestId::String("approval-1".to_string()), json!({}));
+        assert_eq!(router.incoming(1, answer.clone()), answer);
+    }
+
+    #[test]
+    fn router_skips_projects_a_connection_detached_from() {
+        let mut router = ConnectionRouter::default();
+        let (first_tx, mut first_rx) = unbounded_channel();
+        let (second_tx, mut second_rx) = unbounded_channel();
+        router.open(1, first_tx);
+        router.open(2, second_tx);
+        let project_request = |id: i64, method: &str, project_id: Option<&str>| {
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::Integer(id),
+                method: method.to_string(),
+                params: Some(match project_id {
+                    Some(project_id) => json!({ "projectId": project_id }),
+                    None => json!({}),
+                }),
+            })
+        };
+        let event = |project_id: &str| {
+            JSONRPCMessage::Notification(JSONRPCNotification {
+                method: "codex/event/potter".to_string(),
+                params: Some(json!({ "id": "", "msg": {}, "projectId": project_id })),
+            })
+        };
+
+        router.incoming(1, project_request(1, "project/detach", Some("a")));
+        router.incoming(1, project_request(2, "project/detach", Some("b")));
+        router.outgoing(event("a"));
+        router.outgoing(event("b"));
+        router.outgoing(event("c"));
+        assert_eq!(drain(&mut first_rx), vec![event("c")]);
+        assert_eq!(
+            drain(&mut second_rx),
+            vec![event("a"), event("b"), event("c")]
+        );
+
+        router.incoming(1, project_request(3, "project/attach", Some("a")));
+        router.outgoing(event("a"));
+        router.outgoing(event("b"));
+        assert_eq!(drain(&mut first_rx), vec![event("a")]);
+
+        router.incoming(1, project_request(4, "project/attach", None));
+        router.outgoing(event("b"));
+        assert_eq!(drain(&mut first_rx), vec![event("b")]);
+    }
+
+    #[test]
+    fn router_sends_server_requests_to_the_project_owner() {
+        let mut router = ConnectionRouter::default();
+        let mut receivers = Vec::new();
+        for connection in 1..=3 {
+            let (tx, rx) = unbounded_channel();
+            router.open(connection, tx);
+            receivers.push(rx);
+        }
+        fn own(
+            router: &mut ConnectionRouter,
+            receivers: &mut [UnboundedReceiver<JSONRPCMessage>],
+            connection: u64,
+            method: &str,
+        ) {
+            let JSONRPCMessage::Request(request) = router.incoming(
+                connection,
+                JSONRPCMessage::Request(JSONRPCRequest {
+                    id: RequestId::Integer(1),
+                    method: method.to_string(),
+                    params: Some(json!({ "projectId": "p" })),
+                }),
+            ) else {
+                panic!("requests stay requests");
+            };
+            router.outgoing(response(request.id, json!({ "projectId": "p" })));
+            for rx in receivers {
+                drain(rx);
+            }
+        }
+        let approval = |id: &str| {
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::String(id.to_string()),
+                method: "approval/request".to_string(),
+                params: Some(json!({ "projectId": "p" })),
+            })
+        };
+
+        own(&mut router, &mut receivers, 1, "project/start");
+        router.outgoing(approval("a1"));
+        assert_eq!(drain(&mut receivers[0]), vec![approval("a1")]);
+        assert_eq!(drain(&mut receivers[1]), Vec::new());
+        router.incoming(1, response(RequestId::String("a1".to_string()), json!({})));
+
+        // Attaching takes over the project's requests.
+        own(&mut router, &mut receivers, 2, "project/attach");
+        router.outgoing(approval("a2"));
+        assert_eq!(drain(&mut receivers[0]), Vec::new());
+        assert_eq!(drain(&mut receivers[1]), vec![approval("a2")]);
+
+        // Once the owner detaches, unanswered requests go to everyone still following.
+        router.incoming(
+            2,
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::Integer(2),
+                method: "project/detach".to_string(),
+                params: Some(json!({ "projectId": "p" })),
+            }),
+        );
+        assert_eq!(drain(&mut receivers[0]), vec![approval("a2")]);
+        assert_eq!(drain(&mut receivers[1]), Vec::new());
+        assert_eq!(drain(&mut receivers[2]), vec![approval("a2")]);
+
+        // Without an owner, new requests are broadcast too.
+        router.outgoing(approval("a3"));
+        assert_eq!(drain(&mut receivers[0]), vec![approval("a3")]);
+        assert_eq!(drain(&mut receivers[2]), vec![approval("a3")]);
+
+        // Closing the owner's connection hands its requests over as well.
+        own(&mut router, &mut receivers, 3, "project/resume");
+        router.outgoing(approval("a4"));
+        assert_eq!(drain(&mut receivers[0]), Vec::new());
+        router.close(3);
+        assert_eq!(drain(&mut receivers[0]), vec![approval("a4")]);
+    }
+
+    #[tokio::test]
+    async fn tcp_connections_must_present_the_token() {
+        let listener = PotterAppServerListener::bind(
+            &PotterAppServerListenAddress::Tcp("127.0.0.1:0".parse().expect("socket addr")),
+            Some("secret".to_string()),
+        )
+        .await
+        .expect("bind");
+        let PotterAppServerListenAddress::Tcp(addr) = *listener.address() else {
+            panic!("tcp listener");
+        };
+        let (incoming_tx, mut incoming_rx) = unbounded_channel();
+        let (writer_tx, writer_rx) = unbounded_channel();
+        tokio::spawn(listener.serve(incoming_tx, writer_rx));
+        // Echo the method of every request back as its result.
+        tokio::spawn(async move {
+            while let Some(msg) = incoming_rx.recv().await {
+                if let JSONRPCMessage::Request(request) = msg {
+                    let _ = writer_tx.send(response(request.id, json!(request.method)));
+                }
+            }
+        });
+
+        async fn exchange(addr: SocketAddr, line: serde_json::Value) -> JSONRPCMessage {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+            let (reader, mut writer) = stream.split();
+            writer
+                .write_all(format!("{line}\n").as_bytes())
+                .await
+                .expect("write");
+            let mut lines = BufReader::new(reader).lines();
+            let reply = lines.next_line().await.expect("read").expect("reply");
+            serde_json::from_str(&reply).expect("decode reply")
+        }
+
+        let refused = exchange(
+            addr,
+            json!({ "id": 1, "method": "initialize", "params": { "token": "guess" } }),
+        )
+        .await;
+        let JSONRPCMessage::Error(error) = &refused else {
+            panic!("expected an error, got {refused:?}");
+        };
+        assert_eq!(error.id, RequestId::Integer(1));
+        assert_eq!(error.error.code, -32001);
+
+        let accepted = exchange(
+            addr,
+            json!({ "id": 1, "method": "initialize", "params": { "token": "secret" } }),
+        )
+        .await;
+        assert_eq!(
+            accepted,
+            response(RequestId::Integer(1), json!("initialize"))
+        );
+    }
+
+    #[test]
+    fn tokens_are_compared_in_full() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_sockets_are_private_from_the_start() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("potter.sock");
+        let listener =
+            PotterAppServerListener::bind(&PotterAppServerListenAddress::Unix(path.clone()), None)
+                .await
+                .expect("bind");
+
+        let mode = std::fs::metadata(&path)
+            .expect("socket metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // The private directory the socket was bound in is gone.
+        let entries = std::fs::read_dir(dir.path())
+            .expect("read dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![std::ffi::OsString::from("potter.sock")]);
+
+        drop(listener);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_listeners_only_ever_remove_their_own_socket() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "keep me\n").expect("write file");
+
+        let err =
+            PotterAppServerListener::bind(&PotterAppServerListenAddress::Unix(path.clone()), None)
+                .await
+                .err()
+                .expect("bind over a regular file");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{} exists and is not a socket; refusing to replace it",
+                path.display()
+            )
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read file"),
+            "keep me\n"
+        );
+
+        // A file that replaced the socket after binding survives the listener.
+        let path = dir.path().join("potter.sock");
+        let listener =
+            PotterAppServerListener::bind(&PotterAppServerListenAddress::Unix(path.clone()), None)
+                .await
+                .expect("bind");
+        std::fs::remove_file(&path).expect("remove socket");
+        std::fs::write(&path, "replacement\n").expect("write replacement");
+        drop(listener);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read replacement"),
+            "replacement\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn tcp_listeners_require_a_token() {
+        let err = PotterAppServerListener::bind(
+            &PotterAppServerListenAddress::Tcp("127.0.0.1:0".parse().expect("socket addr")),
+            None,
+        )
+        .await
+        .err()
+        .expect("bind without a token");
+        assert_eq!(
+            err.to_string(),
+            "listening on tcp://127.0.0.1:0 requires --listen-token"
+        );
+    }
+}
@@ -3,15 +3,80 @@
 //! CodexPotter runs multi-round workflows. When Codex emits certain transient network/streaming
 //! errors mid-turn (e.g. response stream disconnected), we want to keep the current round alive
 //! and let the agent recover by issuing a follow-up `continue` prompt.
+//!
+//! How retries are scheduled is a [`StreamRecoveryPolicy`] (`[stream_recovery]` in
+//! `~/.codexpotter/config.toml`, see [`crate::config`]):
+//! - transient errors retry immediately once, then back off exponentially from `base_backoff` up
+//!   to `max_backoff`, randomized by `jitter`;
+//! - usage-limit errors wait until the limit resets (taken from the last `TokenCount` rate-limit
+//!   snapshot) when that is within `max_usage_limit_wait`; otherwise they end the round as before.
 
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use codex_protocol::potter_stream_recovery as protocol_recovery;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterStreamRecoveryStrategy;
+use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::TurnCompleteEvent;
 
-const MAX_CONTINUE_RETRIES: u32 = 10;
+/// Extra wait after a usage-limit reset, so the retry does not race the reset.
+const USAGE_LIMIT_RESET_GRACE: Duration = Duration::from_secs(5);
+
+/// How CodexPotter retries after recoverable turn errors (`[stream_recovery]`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamRecoveryPolicy {
+    /// Retries allowed within a continuous-error streak before giving up.
+    pub max_retries: u32,
+    /// Backoff before the second retry (the first one is immediate); doubles with every retry.
+    pub base_backoff: Duration,
+    /// Upper bound of the backoff.
+    pub max_backoff: Duration,
+    /// Fraction (`0.0..=1.0`) of the backoff that is randomly added or removed.
+    pub jitter: f64,
+    /// Wait for usage limits to reset instead of failing the round.
+    pub wait_for_usage_limit_reset: bool,
+    /// Longest wait for a usage-limit reset; a later reset fails the round.
+    pub max_usage_limit_wait: Duration,
+}
+
+impl Default for StreamRecoveryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 10,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5 * 60),
+            jitter: 0.0,
+            wait_for_usage_limit_reset: true,
+            max_usage_limit_wait: Duration::from_secs(6 * 60 * 60),
+        }
+    }
+}
+
+impl StreamRecoveryPolicy {
+    /// Backoff before the 1-based retry `attempt`, randomized by `jitter_sample` (`-1.0..=1.0`).
+    fn backoff_for_attempt(&self, attempt: u32, jitter_sample: f64) -> Duration {
+        if attempt <= 1 {
+            return Duration::ZERO;
+        }
+        let factor = 1u32.checked_shl(attempt - 2).unwrap_or(u32::MAX);
+        let backoff = self.base_backoff.saturating_mul(factor);
+        let scale = 1.0 + self.jitter.clamp(0.0, 1.0) * jitter_sample.clamp(-1.0, 1.0);
+        Duration::try_from_secs_f64(backoff.as_secs_f64() * scale)
+            .unwrap_or(backoff)
+            .min(self.max_backoff)
+    }
+
+    fn backoff_strategy(&self) -> PotterStreamRecoveryStrategy {
+        PotterStreamRecoveryStrategy::Backoff {
+            base: self.base_backoff,
+            max: self.max_backoff,
+            jitter_percent: (self.jitter.clamp(0.0, 1.0) * 100.0).round() as u32,
+        }
+    }
+}
 
 /// A plan to retry a failed turn by sending a follow-up `continue` prompt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +87,8 @@ pub struct ContinueRetryPlan {
     pub max_attempts: u32,
     /// Backoff duration to wait before sending `continue`.
     pub backoff: Duration,
+    /// Why the backoff is as long as it is.
+    pub strategy: PotterStreamRecoveryStrategy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,21 +100,42 @@ pub enum ContinueRetryDecision {
 /// Tracks retry/backoff state for "continue after stream disconnect" behavior.
 #[derive(Debug, Default)]
 pub struct PotterStreamRecovery {
+    policy: StreamRecoveryPolicy,
     continue_sends_since_activity: u32,
+    /// Latest rate-limit snapshot; tells when a usage limit resets.
+    rate_limits: Option<RateLimitSnapshot>,
 }
 
 impl PotterStreamRecovery {
     pub fn new() -> Self {
+        Self::with_policy(StreamRecoveryPolicy::default())
+    }
+
+    pub fn with_policy(policy: StreamRecoveryPolicy) -> Self {
         Self {
+            policy,
             continue_sends_since_activity: 0,
+            rate_limits: None,
         }
     }
 
+    /// Start over with a fresh retry budget (for example after new user input).
+    pub fn reset(&mut self) {
+        self.continue_sends_since_activity = 0;
+    }
+
     /// Returns `true` when CodexPotter is still in a continuous-error retry streak.
     pub fn is_in_retry_streak(&self) -> bool {
         self.continue_sends_since_activity > 0
     }
 
+    /// Returns `true` when `error` is one this policy recovers from.
+    pub fn is_recoverable_error(&self, error: &ErrorEvent) -> bool {
+        protocol_recovery::is_retryable_stream_error(error)
+            || (self.policy.wait_for_usage_limit_reset
+                && protocol_recovery::is_usage_limit_error(error))
+    }
+
     /// Returns `true` when `turn_complete` should be suppressed from the UI.
     ///
     /// While in a retry streak, Codex can emit an empty `TurnComplete` that corresponds to a
@@ -64,39 +152,88 @@ impl PotterStreamRecovery {
 
     /// Observe a non-error event and reset backoff state when we see activity.
     pub fn observe_event(&mut self, msg: &EventMsg) {
+        if let EventMsg::TokenCount(ev) = msg
+            && let Some(rate_limits) = &ev.rate_limits
+        {
+            self.rate_limits = Some(rate_limits.clone());
+        }
         if protocol_recovery::is_activity_event(msg) {
             self.continue_sends_since_activity = 0;
         }
     }
 
-    /// If `error` is retryable, returns a decision describing whether to retry (and how) or give
-    /// up due to exceeding the retry cap.
+    /// If `error` is recoverable, returns a decision describing whether to retry (and how) or
+    /// give up due to exceeding the retry cap.
     pub fn plan_retry(&mut self, error: &ErrorEvent) -> Option<ContinueRetryDecision> {
-        if !protocol_recovery::is_retryable_stream_error(error) {
+        let jitter_sample = rand::random_range(-1.0..=1.0);
+        self.plan_retry_at(error, SystemTime::now(), jitter_sample)
+    }
+
+    fn plan_retry_at(
+        &mut self,
+        error: &ErrorEvent,
+        now: SystemTime,
+        jitter_sample: f64,
+    ) -> Option<ContinueRetryDecision> {
+        let usage_limit_wait = if protocol_recovery::is_retryable_stream_error(error) {
+            None
+        } else if self.policy.wait_for_usage_limit_reset
+            && protocol_recovery::is_usage_limit_error(error)
+        {
+            Some(self.usage_limit_reset_wait(now)?)
+        } else {
             return None;
-        }
+        };
 
-        if self.continue_sends_since_activity >= MAX_CONTINUE_RETRIES {
+        let max_attempts = self.policy.max_retries;
+        if self.continue_sends_since_activity >= max_attempts {
             return Some(ContinueRetryDecision::GiveUp {
                 attempts: self.continue_sends_since_activity,
-                max_attempts: MAX_CONTINUE_RETRIES,
+                max_attempts,
             });
         }
 
         let attempt = self.continue_sends_since_activity + 1;
-        let backoff = Duration::from_secs(if self.continue_sends_since_activity == 0 {
-            0
-        } else {
-            1u64 << (self.continue_sends_since_activity - 1)
-        });
+        let (backoff, strategy) = match usage_limit_wait {
+            Some(wait) => (wait, PotterStreamRecoveryStrategy::UsageLimitReset),
+            None => (
+                self.policy.backoff_for_attempt(attempt, jitter_sample),
+                self.policy.backoff_strategy(),
+            ),
+        };
         self.continue_sends_since_activity += 1;
 
         Some(ContinueRetryDecision::Retry(ContinueRetryPlan {
             attempt,
-            max_attempts: MAX_CONTINUE_RETRIES,
+            max_attempts,
             backoff,
+            strategy,
         }))
     }
+
+    /// Time until the exhausted rate-limit window resets, if known and not too far away.
+    fn usage_limit_reset_wait(&self, now: SystemTime) -> Option<Duration> {
+        let rate_limits = self.rate_limits.as_ref()?;
+        let windows = [rate_limits.primary.as_ref(), rate_limits.secondary.as_ref()];
+        let exhausted_reset = windows
+            .iter()
+            .flatten()
+            .filter(|window| window.used_percent >= 100.0)
+            .filter_map(|window| window.resets_at)
+            .max();
+        let resets_at = exhausted_reset.or_else(|| {
+            windows
+                .iter()
+                .flatten()
+                .filter_map(|window| window.resets_at)
+                .max()
+        })?;
+
+        let now = i64::try_from(now.duration_since(UNIX_EPOCH).ok()?.as_secs()).ok()?;
+        let wait = Duration::from_secs(u64::try_from(resets_at.saturating_sub(now)).unwrap_or(0))
+            + USAGE_LIMIT_RESET_GRACE;
+        (wait <= self.policy.max_usage_limit_wait).then_some(wait)
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +241,8 @@ mod tests {
     use super::*;
     use codex_protocol::protocol::AgentMessageDeltaEvent;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::RateLimitWindow;
+    use codex_protocol::protocol::TokenCountEvent;
     use pretty_assertions::assert_eq;
 
     fn retryable_error_event() -> ErrorEvent {
@@ -116,6 +255,21 @@ mod tests {
         }
     }
 
+    fn default_backoff_strategy() -> PotterStreamRecoveryStrategy {
+        PotterStreamRecoveryStrategy::Backoff {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(300),
+            jitter_percent: 0,
+        }
+    }
+
+    fn usage_limit_error_event() -> ErrorEvent {
+        ErrorEvent {
+            message: "You've hit your usage limit.".to_string(),
+            codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+        }
+    }
+
     #[test]
     fn plan_retry_sends_immediately_then_backs_off_exponentially() {
         let mut state = PotterStreamRecovery::new();
@@ -136,16 +290,19 @@ mod tests {
                     attempt: 1,
                     max_attempts: 10,
                     backoff: Duration::from_secs(0),
+                    strategy: default_backoff_strategy(),
                 },
                 ContinueRetryPlan {
                     attempt: 2,
                     max_attempts: 10,
                     backoff: Duration::from_secs(1),
+                    strategy: default_backoff_strategy(),
                 },
                 ContinueRetryPlan {
                     attempt: 3,
                     max_attempts: 10,
                     backoff: Duration::from_secs(2),
+                    strategy: default_backoff_strategy(),
                 },
             ]
         );
@@ -197,6 +354,97 @@ mod tests {
         assert_eq!((attempts, max_attempts), (10, 10));
     }
 
+    #[test]
+    fn plan_retry_caps_backoff_and_applies_jitter() {
+        let mut state = PotterStreamRecovery::with_policy(StreamRecoveryPolicy {
+            max_retries: 3,
+            base_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(15),
+            jitter: 0.5,
+            ..Default::default()
+        });
+        let err = retryable_error_event();
+        let now = SystemTime::now();
+
+        let backoffs = [0.0, 1.0, -1.0]
+            .into_iter()
+            .map(
+                |jitter_sample| match state.plan_retry_at(&err, now, jitter_sample) {
+                    Some(ContinueRetryDecision::Retry(plan)) => plan.backoff,
+                    other => panic!("expected retry plan, got: {other:?}"),
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(
+            backoffs,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(15),
+                Duration::from_secs(10),
+            ]
+        );
+        assert_eq!(
+            state.plan_retry_at(&err, now, 0.0),
+            Some(ContinueRetryDecision::GiveUp {
+                attempts: 3,
+                max_attempts: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn plan_retry_waits_for_usage_limit_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut state = PotterStreamRecovery::new();
+        let err = usage_limit_error_event();
+
+        // Without a rate-limit snapshot the reset time is unknown: the error ends the round.
+        assert_eq!(state.plan_retry_at(&err, now, 0.0), None);
+
+        state.observe_event(&EventMsg::TokenCount(TokenCountEvent {
+            info: None,
+            rate_limits: Some(RateLimitSnapshot {
+                primary: Some(RateLimitWindow {
+                    used_percent: 40.0,
+                    window_minutes: Some(300),
+                    resets_at: Some(5_000),
+                }),
+                secondary: Some(RateLimitWindow {
+                    used_percent: 100.0,
+                    window_minutes: Some(10_080),
+                    resets_at: Some(4_600),
+                }),
+                credits: None,
+                plan_type: None,
+            }),
+        }));
+        assert_eq!(
+            state.plan_retry_at(&err, now, 0.0),
+            Some(ContinueRetryDecision::Retry(ContinueRetryPlan {
+                attempt: 1,
+                max_attempts: 10,
+                backoff: Duration::from_secs(3_605),
+                strategy: PotterStreamRecoveryStrategy::UsageLimitReset,
+            }))
+        );
+
+        let mut state = PotterStreamRecovery::with_policy(StreamRecoveryPolicy {
+            max_usage_limit_wait: Duration::from_secs(60),
+            ..Default::default()
+        });
+        state.rate_limits = Some(RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: 100.0,
+                window_minutes: None,
+                resets_at: Some(4_600),
+            }),
+            secondary: None,
+            credits: None,
+            plan_type: None,
+        });
+        assert_eq!(state.plan_retry_at(&err, now, 0.0), None);
+    }
+
     #[test]
     fn should_suppress_turn_complete_during_retry_streak() {
         let mut state = PotterStreamRecovery::new();
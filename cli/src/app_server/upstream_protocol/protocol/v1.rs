@@ -23,6 +23,15 @@ pub struct ClientInfo {
     pub version: String,
 }
 
+/// Response payload for the `initialize` request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResponse {
+    /// Upstream user agent, e.g. `codex_cli_rs/0.98.0 (Linux 6.1.0; x86_64) xterm-256color`.
+    #[serde(default)]
+    pub user_agent: Option<String>,
+}
+
 /// Response payload for an `applyPatch` approval request.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -49,12 +49,16 @@ pub enum SandboxMode {
 #[serde(rename_all = "camelCase")]
 pub enum CommandExecutionApprovalDecision {
     Accept,
+    AcceptForSession,
+    Decline,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum FileChangeApprovalDecision {
     Accept,
+    AcceptForSession,
+    Decline,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
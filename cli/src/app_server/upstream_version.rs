@@ -0,0 +1,204 @@
+//! Upstream `codex` versions the protocol mirror in [`super::upstream_protocol`] supports.
+//!
+//! The mirror is synced from a specific upstream release, so newer (or older) `codex` binaries may
+//! speak a slightly different protocol. This module checks the installed version in two places:
+//!
+//! - at startup, by running `codex --version` ([`query_codex_version`]); versions older than
+//!   [`MIN_SUPPORTED_CODEX_VERSION`] are refused (see `startup::check_codex_bin_version`)
+//! - for every spawned app-server, by parsing the `userAgent` of the `initialize` response
+//!   ([`version_from_user_agent`]); versions outside the supported range are reported as a warning
+//!
+//! Events the mirror cannot decode (`EventMsg::Unknown`) are counted per round by the backend and
+//! reported with [`supported_codex_versions`] as a hint.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::process::Command;
+use std::sync::Mutex;
+
+use anyhow::Context;
+
+/// Oldest `codex` release whose app-server protocol the mirror supports.
+pub const MIN_SUPPORTED_CODEX_VERSION: CodexVersion = CodexVersion::new(0, 98, 0);
+
+/// First `codex` release the mirror has not been verified against.
+pub const FIRST_UNTESTED_CODEX_VERSION: CodexVersion = CodexVersion::new(0, 105, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CodexVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl CodexVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse the first `MAJOR.MINOR.PATCH` token of `text` (e.g. `codex-cli 0.98.0` or
+    /// `codex_cli_rs/0.98.0 (Linux 6.1; x86_64)`). Pre-release suffixes are ignored.
+    pub fn parse(text: &str) -> Option<Self> {
+        text.split(|ch: char| ch.is_whitespace() || ch == '/')
+            .find_map(parse_version_token)
+    }
+
+    pub fn support(self) -> CodexVersionSupport {
+        if self < MIN_SUPPORTED_CODEX_VERSION {
+            CodexVersionSupport::TooOld
+        } else if self >= FIRST_UNTESTED_CODEX_VERSION {
+            CodexVersionSupport::Untested
+        } else {
+            CodexVersionSupport::Supported
+        }
+    }
+}
+
+impl fmt::Display for CodexVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+fn parse_version_token(token: &str) -> Option<CodexVersion> {
+    let token = token.strip_prefix('v').unwrap_or(token);
+    let core = token.split(['-', '+']).next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse::<u64>().ok()?;
+    let minor = parts.next()?.parse::<u64>().ok()?;
+    let patch = parts.next()?.parse::<u64>().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(CodexVersion::new(major, minor, patch))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodexVersionSupport {
+    Supported,
+    /// Older than [`MIN_SUPPORTED_CODEX_VERSION`]: requests the mirror relies on may be missing.
+    TooOld,
+    /// At or after [`FIRST_UNTESTED_CODEX_VERSION`]: the protocol may have drifted.
+    Untested,
+}
+
+/// Human-readable supported range, used in warnings and errors.
+pub fn supported_codex_versions() -> String {
+    format!(">= {MIN_SUPPORTED_CODEX_VERSION}, < {FIRST_UNTESTED_CODEX_VERSION}")
+}
+
+/// Run `<codex_bin> --version` and parse the reported version.
+pub fn query_codex_version(codex_bin: &str) -> anyhow::Result<CodexVersion> {
+    let output = Command::new(codex_bin)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("run `{codex_bin} --version`"))?;
+    if !output.status.success() {
+        anyhow::bail!("`{codex_bin} --version` exited with {}", output.status);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    CodexVersion::parse(&stdout).with_context(|| {
+        format!(
+            "parse version from `{codex_bin} --version` output: {:?}",
+            stdout.trim()
+        )
+    })
+}
+
+/// Version of the app-server that sent `user_agent` in its `initialize` response.
+pub fn version_from_user_agent(user_agent: &str) -> Option<CodexVersion> {
+    CodexVersion::parse(user_agent)
+}
+
+/// Warning for an app-server whose version is outside the supported range; `None` when it is
+/// supported.
+pub fn app_server_version_warning(user_agent: &str) -> Option<String> {
+    let supported = supported_codex_versions();
+    let Some(version) = version_from_user_agent(user_agent) else {
+        return Some(format!(
+            "could not determine the codex app-server version from {user_agent:?}; codex-potter \
+             supports codex {supported}"
+        ));
+    };
+    match version.support() {
+        CodexVersionSupport::Supported => None,
+        CodexVersionSupport::TooOld => Some(format!(
+            "codex app-server {version} is older than codex-potter supports \
+             ({supported}); upgrade codex if rounds fail"
+        )),
+        CodexVersionSupport::Untested => Some(format!(
+            "codex app-server {version} is newer than codex-potter was tested with \
+             ({supported}); unsupported events will be reported"
+        )),
+    }
+}
+
+/// [`app_server_version_warning`], reported once per user agent per process: without
+/// `--reuse-app-server` every round spawns (and initializes) a new app-server.
+pub fn app_server_version_warning_once(user_agent: &str) -> Option<String> {
+    static REPORTED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+    let warning = app_server_version_warning(user_agent)?;
+    let mut reported = match REPORTED.lock() {
+        Ok(guard) => guard,
+        Err(err) => err.into_inner(),
+    };
+    reported.insert(user_agent.to_string()).then_some(warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_versions_from_cli_output_and_user_agents() {
+        assert_eq!(
+            CodexVersion::parse("codex-cli 0.98.0\n"),
+            Some(CodexVersion::new(0, 98, 0))
+        );
+        assert_eq!(
+            CodexVersion::parse("codex_cli_rs/0.104.1-alpha.2 (Linux 6.1.0; x86_64) xterm"),
+            Some(CodexVersion::new(0, 104, 1))
+        );
+        assert_eq!(
+            CodexVersion::parse("codex v1.2.3"),
+            Some(CodexVersion::new(1, 2, 3))
+        );
+        assert_eq!(CodexVersion::parse("codex-cli dev"), None);
+        assert_eq!(CodexVersion::parse("Linux 6.1.0.1"), None);
+    }
+
+    #[test]
+    fn classifies_versions_against_the_supported_range() {
+        assert_eq!(
+            CodexVersion::new(0, 97, 9).support(),
+            CodexVersionSupport::TooOld
+        );
+        assert_eq!(
+            MIN_SUPPORTED_CODEX_VERSION.support(),
+            CodexVersionSupport::Supported
+        );
+        assert_eq!(
+            FIRST_UNTESTED_CODEX_VERSION.support(),
+            CodexVersionSupport::Untested
+        );
+
+        assert_eq!(
+            app_server_version_warning(&format!("codex_cli_rs/{MIN_SUPPORTED_CODEX_VERSION}")),
+            None
+        );
+        assert_eq!(
+            app_server_version_warning("codex_cli_rs/1.0.0 (Linux; x86_64)"),
+            Some(format!(
+                "codex app-server 1.0.0 is newer than codex-potter was tested with \
+                 ({}); unsupported events will be reported",
+                supported_codex_versions()
+            ))
+        );
+    }
+}
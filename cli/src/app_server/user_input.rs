@@ -0,0 +1,454 @@
+//! Upstream requests for user input.
+//!
+//! Besides approvals, upstream `codex app-server` can block a turn on questions for the user:
+//!
+//! - `item/tool/requestUserInput`: the agent calls its `request_user_input` tool with a list of
+//!   questions (see [`RequestUserInputQuestion`]).
+//! - `mcpServer/elicitation/request`: an MCP server asks for a form described by a JSON schema.
+//!   Each schema property becomes a question; `enum` and `boolean` properties become options.
+//!
+//! The backend bridge parses both into a [`UserInputRequest`] and hands it to a
+//! [`UserInputAsker`], which routes it to the Potter app-server (and from there to the client as
+//! `userInput/request`). Without an asker, or when nobody answers, the request is declined. Every
+//! outcome is surfaced as `EventMsg::PotterUserInputAnswered` so the transcript (and resume) shows
+//! what was answered.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+
+use crate::app_server::potter::UserInputRequestResponse;
+
+/// Server request sent for the agent's `request_user_input` tool.
+pub const TOOL_REQUEST_USER_INPUT_METHOD: &str = "item/tool/requestUserInput";
+
+/// Server request sent for MCP elicitations.
+pub const MCP_ELICITATION_REQUEST_METHOD: &str = "mcpServer/elicitation/request";
+
+/// Shown instead of the answers of secret questions.
+const SECRET_ANSWER_MASK: &str = "********";
+
+/// Option labels offered for `boolean` elicitation properties.
+const BOOLEAN_YES: &str = "Yes";
+const BOOLEAN_NO: &str = "No";
+
+/// Where a [`UserInputRequest`] came from, which decides the shape of the upstream response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserInputSource {
+    /// The agent's `request_user_input` tool.
+    Tool,
+    /// An MCP server elicitation; `properties` is the `requestedSchema` property map.
+    Elicitation {
+        server_name: String,
+        properties: serde_json::Map<String, Value>,
+    },
+}
+
+/// Questions for the user, parsed from an upstream server request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserInputRequest {
+    pub source: UserInputSource,
+    /// Elicitation message shown above the questions.
+    pub message: Option<String>,
+    pub questions: Vec<RequestUserInputQuestion>,
+}
+
+impl UserInputRequest {
+    /// Parse an upstream server request. Returns `None` when `method` is not a user input request.
+    pub fn from_server_request(
+        method: &str,
+        params: Option<&Value>,
+    ) -> Option<anyhow::Result<Self>> {
+        match method {
+            TOOL_REQUEST_USER_INPUT_METHOD => Some(Self::from_tool_params(params)),
+            MCP_ELICITATION_REQUEST_METHOD => Some(Self::from_elicitation_params(params)),
+            _ => None,
+        }
+    }
+
+    fn from_tool_params(params: Option<&Value>) -> anyhow::Result<Self> {
+        let questions = params
+            .and_then(|params| params.get("questions"))
+            .context("requestUserInput params missing `questions`")?;
+        let questions = serde_json::from_value(questions.clone())
+            .context("decode requestUserInput questions")?;
+        Ok(Self {
+            source: UserInputSource::Tool,
+            message: None,
+            questions,
+        })
+    }
+
+    fn from_elicitation_params(params: Option<&Value>) -> anyhow::Result<Self> {
+        let params = params.context("elicitation request missing params")?;
+        let server_name = params
+            .get("serverName")
+            .and_then(Value::as_str)
+            .context("elicitation request missing `serverName`")?
+            .to_string();
+        let message = params
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let properties = params
+            .get("requestedSchema")
+            .and_then(|schema| schema.get("properties"))
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let questions = properties
+            .iter()
+            .map(|(name, property)| elicitation_question(name, property))
+            .collect();
+        Ok(Self {
+            source: UserInputSource::Elicitation {
+                server_name,
+                properties,
+            },
+            message,
+            questions,
+        })
+    }
+
+    pub fn server_name(&self) -> Option<&str> {
+        match &self.source {
+            UserInputSource::Tool => None,
+            UserInputSource::Elicitation { server_name, .. } => Some(server_name),
+        }
+    }
+
+    /// Upstream JSON-RPC result for `answers`; `None` declines the request.
+    pub fn response(&self, answers: Option<&HashMap<String, RequestUserInputAnswer>>) -> Value {
+        match (&self.source, answers) {
+            (UserInputSource::Tool, answers) => json!({
+                "answers": answers.cloned().unwrap_or_default(),
+            }),
+            (UserInputSource::Elicitation { .. }, None) => json!({ "action": "decline" }),
+            (UserInputSource::Elicitation { properties, .. }, Some(answers)) => {
+                let content = properties
+                    .iter()
+                    .filter_map(|(name, property)| {
+                        let answer = answers.get(name)?.answers.first()?;
+                        Some((name.clone(), elicitation_value(property, answer)))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                json!({ "action": "accept", "content": content })
+            }
+        }
+    }
+
+    /// Event recording how the request was answered, with secret answers masked.
+    pub fn answered_event(
+        &self,
+        answers: Option<&HashMap<String, RequestUserInputAnswer>>,
+    ) -> EventMsg {
+        let recorded = answers
+            .map(|answers| {
+                self.questions
+                    .iter()
+                    .filter_map(|question| {
+                        let answer = answers.get(&question.id)?;
+                        Some(PotterUserInputAnswer {
+                            header: question.header.clone(),
+                            question: question.question.clone(),
+                            answers: if question.is_secret {
+                                vec![SECRET_ANSWER_MASK.to_string()]
+                            } else {
+                                answer.answers.clone()
+                            },
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        EventMsg::PotterUserInputAnswered {
+            server_name: self.server_name().map(str::to_string),
+            answers: recorded,
+            declined: answers.is_none(),
+        }
+    }
+}
+
+fn elicitation_question(name: &str, property: &Value) -> RequestUserInputQuestion {
+    let title = property.get("title").and_then(Value::as_str);
+    let description = property.get("description").and_then(Value::as_str);
+    let options = match (
+        property.get("type").and_then(Value::as_str),
+        property.get("enum").and_then(Value::as_array),
+    ) {
+        (_, Some(values)) => {
+            let names = property.get("enumNames").and_then(Value::as_array);
+            Some(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| RequestUserInputQuestionOption {
+                        label: match value {
+                            Value::String(value) => value.clone(),
+                            value => value.to_string(),
+                        },
+                        description: names
+                            .and_then(|names| names.get(idx))
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                    .collect(),
+            )
+        }
+        (Some("boolean"), None) => Some(
+            [BOOLEAN_YES, BOOLEAN_NO]
+                .into_iter()
+                .map(|label| RequestUserInputQuestionOption {
+                    label: label.to_string(),
+                    description: String::new(),
+                })
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    RequestUserInputQuestion {
+        id: name.to_string(),
+        header: title.unwrap_or(name).to_string(),
+        question: description.or(title).unwrap_or(name).to_string(),
+        is_other: false,
+        is_secret: false,
+        options,
+    }
+}
+
+/// Convert a text answer to the JSON type the elicitation schema asks for.
+///
+/// Answers that do not parse are sent as strings; the MCP server reports the mismatch.
+fn elicitation_value(property: &Value, answer: &str) -> Value {
+    let answer = answer.trim();
+    let parsed = match property.get("type").and_then(Value::as_str) {
+        Some("boolean") => match answer.to_ascii_lowercase().as_str() {
+            "yes" | "true" => Some(Value::Bool(true)),
+            "no" | "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Some("integer") => answer.parse::<i64>().ok().map(Value::from),
+        Some("number") => answer.parse::<f64>().ok().map(Value::from),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Value::String(answer.to_string()))
+}
+
+/// Question sent to the user for a [`UserInputRequest`].
+#[derive(Debug)]
+pub struct UserInputQuestion {
+    pub server_name: Option<String>,
+    pub message: Option<String>,
+    pub questions: Vec<RequestUserInputQuestion>,
+    pub reply_tx: oneshot::Sender<UserInputRequestResponse>,
+}
+
+/// Routes user input requests of a project's rounds to the Potter app-server, which forwards them
+/// to its client as `userInput/request`.
+#[derive(Debug, Clone)]
+pub struct UserInputAsker {
+    tx: UnboundedSender<UserInputQuestion>,
+}
+
+impl UserInputAsker {
+    pub fn new(tx: UnboundedSender<UserInputQuestion>) -> Self {
+        Self { tx }
+    }
+
+    /// Ask the user to answer `request`. Returns `None` when the user declined or nobody answered
+    /// (the client went away).
+    pub async fn ask(
+        &self,
+        request: &UserInputRequest,
+    ) -> Option<HashMap<String, RequestUserInputAnswer>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(UserInputQuestion {
+                server_name: request.server_name().map(str::to_string),
+                message: request.message.clone(),
+                questions: request.questions.clone(),
+                reply_tx,
+            })
+            .ok()?;
+        reply_rx.await.ok()?.answers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn answers(entries: &[(&str, &str)]) -> HashMap<String, RequestUserInputAnswer> {
+        entries
+            .iter()
+            .map(|(id, answer)| {
+                (
+                    id.to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec![answer.to_string()],
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tool_request_answers_by_question_id_and_masks_secrets() {
+        let params = json!({
+            "threadId": "thread-1",
+            "turnId": "turn-1",
+            "itemId": "call-1",
+            "questions": [
+                {
+                    "id": "db",
+                    "header": "Database",
+                    "question": "Which database should the service use?",
+                    "options": [
+                        { "label": "Postgres", "description": "Managed instance" },
+                        { "label": "SQLite", "description": "Local file" }
+                    ]
+                },
+                {
+                    "id": "token",
+                    "header": "Token",
+                    "question": "API token for staging?",
+                    "isSecret": true
+                }
+            ]
+        });
+        let request =
+            UserInputRequest::from_server_request(TOOL_REQUEST_USER_INPUT_METHOD, Some(&params))
+                .expect("user input method")
+                .expect("parse request");
+        assert_eq!(request.source, UserInputSource::Tool);
+        assert_eq!(request.questions.len(), 2);
+        assert!(request.questions[1].is_secret);
+
+        let answers = answers(&[("db", "SQLite"), ("token", "s3cr3t")]);
+        assert_eq!(
+            request.response(Some(&answers)),
+            json!({
+                "answers": {
+                    "db": { "answers": ["SQLite"] },
+                    "token": { "answers": ["s3cr3t"] },
+                }
+            })
+        );
+        assert_eq!(request.response(None), json!({ "answers": {} }));
+
+        let EventMsg::PotterUserInputAnswered {
+            server_name,
+            answers,
+            declined,
+        } = request.answered_event(Some(&answers))
+        else {
+            panic!("expected PotterUserInputAnswered");
+        };
+        assert_eq!(server_name, None);
+        assert!(!declined);
+        assert_eq!(
+            answers,
+            vec![
+                PotterUserInputAnswer {
+                    header: "Database".to_string(),
+                    question: "Which database should the service use?".to_string(),
+                    answers: vec!["SQLite".to_string()],
+                },
+                PotterUserInputAnswer {
+                    header: "Token".to_string(),
+                    question: "API token for staging?".to_string(),
+                    answers: vec![SECRET_ANSWER_MASK.to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn elicitation_schema_properties_become_typed_questions() {
+        let params = json!({
+            "threadId": "thread-1",
+            "serverName": "deploy",
+            "message": "Confirm the deployment",
+            "requestedSchema": {
+                "type": "object",
+                "properties": {
+                    "environment": {
+                        "type": "string",
+                        "title": "Environment",
+                        "enum": ["staging", "production"],
+                        "enumNames": ["Staging", "Production"]
+                    },
+                    "notify": { "type": "boolean", "description": "Notify the team?" },
+                    "replicas": { "type": "integer" }
+                },
+                "required": ["environment"]
+            }
+        });
+        let request =
+            UserInputRequest::from_server_request(MCP_ELICITATION_REQUEST_METHOD, Some(&params))
+                .expect("user input method")
+                .expect("parse request");
+        assert_eq!(request.server_name(), Some("deploy"));
+        assert_eq!(request.message, Some("Confirm the deployment".to_string()));
+        assert_eq!(
+            request
+                .questions
+                .iter()
+                .map(|question| {
+                    let options = question
+                        .options
+                        .iter()
+                        .flatten()
+                        .map(|option| option.label.as_str())
+                        .collect::<Vec<_>>()
+                        .join("|");
+                    format!("{}: {} [{options}]", question.header, question.question)
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                "Environment: Environment [staging|production]".to_string(),
+                "notify: Notify the team? [Yes|No]".to_string(),
+                "replicas: replicas []".to_string(),
+            ]
+        );
+
+        let answers = answers(&[
+            ("environment", "staging"),
+            ("notify", "No"),
+            ("replicas", "3"),
+        ]);
+        assert_eq!(
+            request.response(Some(&answers)),
+            json!({
+                "action": "accept",
+                "content": { "environment": "staging", "notify": false, "replicas": 3 },
+            })
+        );
+        assert_eq!(request.response(None), json!({ "action": "decline" }));
+        assert!(matches!(
+            request.answered_event(None),
+            EventMsg::PotterUserInputAnswered { declined: true, .. }
+        ));
+    }
+
+    #[test]
+    fn other_server_requests_are_not_user_input() {
+        assert!(
+            UserInputRequest::from_server_request("item/commandExecution/requestApproval", None)
+                .is_none()
+        );
+    }
+}
@@ -12,6 +12,15 @@
 //! Current keys:
 //! - `[notice] hide_gitignore_prompt` (bool): hides the gitignore startup prompt.
 //! - `check_for_update_on_startup` (bool): enables update checks on startup (default: `true`).
+//! - `[rounds] schedule` (array of `"implement"` / `"review"`): repeating round schedule (see
+//!   [`crate::workflow::round_schedule`]); defaults to implement rounds only.
+//! - `[rounds.review] model` / `reasoning_effort` (strings): backend overrides for review rounds.
+//! - `[approvals]` (`default`, `deny_edits_outside_workdir`, `[[approvals.rules]]`): approval
+//!   policy for upstream requests (see [`crate::app_server::approval_policy`]). Also read from
+//!   `<workdir>/.codexpotter/config.toml`.
+//! - `[stream_recovery]` (`max_retries`, `base_backoff`, `max_backoff`, `jitter`,
+//!   `wait_for_usage_limit_reset`, `max_usage_limit_wait`): retry schedule for recoverable turn
+//!   errors (see [`crate::app_server::stream_recovery`]).
 
 use std::io::ErrorKind;
 use std::path::Path;
@@ -22,7 +31,13 @@ use toml_edit::Item as TomlItem;
 use toml_edit::Table as TomlTable;
 use toml_edit::value;
 
+use crate::app_server::approval_policy::ApprovalAction;
+use crate::app_server::approval_policy::ApprovalPolicySettings;
+use crate::app_server::approval_policy::ApprovalRule;
+use crate::app_server::stream_recovery::StreamRecoveryPolicy;
 use crate::atomic_write::write_atomic_text;
+use crate::workflow::round_schedule::ReviewRoundSettings;
+use crate::workflow::round_schedule::RoundSchedule;
 
 /// Persistent user configuration backed by a TOML file on disk.
 #[derive(Debug, Clone)]
@@ -75,6 +90,185 @@ impl ConfigStore {
         Ok(read_check_for_update_on_startup(&doc).unwrap_or(true))
     }
 
+    /// Round schedule from `[rounds]`.
+    ///
+    /// Unlike the boolean keys there is no fallback parser: when the TOML is invalid the default
+    /// schedule (implement rounds only) is used. Invalid values are reported as errors so a typo
+    /// does not silently disable review rounds.
+    pub fn round_schedule(&self) -> anyhow::Result<RoundSchedule> {
+        let Some(content) = read_document_string(&self.path)? else {
+            return Ok(RoundSchedule::default());
+        };
+        let Ok(doc) = content.parse::<DocumentMut>() else {
+            return Ok(RoundSchedule::default());
+        };
+        let Some(rounds) = doc.get("rounds").and_then(TomlItem::as_table) else {
+            return Ok(RoundSchedule::default());
+        };
+
+        let kinds = match rounds.get("schedule") {
+            None => Vec::new(),
+            Some(item) => {
+                let Some(array) = item.as_array() else {
+                    anyhow::bail!("`rounds.schedule` must be an array of strings");
+                };
+                array
+                    .iter()
+                    .map(|entry| {
+                        let Some(entry) = entry.as_str() else {
+                            anyhow::bail!("`rounds.schedule` must be an array of strings");
+                        };
+                        crate::workflow::round_schedule::parse_round_kind(entry)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+            }
+        };
+
+        let review_table = rounds.get("review").and_then(TomlItem::as_table);
+        let review_string = |key: &str| -> anyhow::Result<Option<String>> {
+            let Some(item) = review_table.and_then(|review| review.get(key)) else {
+                return Ok(None);
+            };
+            let Some(value) = item.as_str() else {
+                anyhow::bail!("`rounds.review.{key}` must be a string");
+            };
+            Ok(Some(value.to_string()))
+        };
+        let review = ReviewRoundSettings {
+            model: review_string("model")?,
+            reasoning_effort: review_string("reasoning_effort")?
+                .map(|effort| crate::workflow::round_schedule::parse_reasoning_effort(&effort))
+                .transpose()?,
+        };
+
+        RoundSchedule::new(kinds, review)
+    }
+
+    /// Approval policy settings from `[approvals]`.
+    ///
+    /// Deny rules must not silently disappear, so invalid TOML is an error when the file mentions
+    /// `approvals`; otherwise it falls back to the defaults like the other keys.
+    pub fn approval_policy_settings(&self) -> anyhow::Result<ApprovalPolicySettings> {
+        let Some(content) = read_document_string(&self.path)? else {
+            return Ok(ApprovalPolicySettings::default());
+        };
+        let doc = match content.parse::<DocumentMut>() {
+            Ok(doc) => doc,
+            Err(err) if content.contains("approvals") => {
+                return Err(anyhow::Error::new(err).context("parse config.toml"));
+            }
+            Err(_) => return Ok(ApprovalPolicySettings::default()),
+        };
+        let Some(approvals) = doc.get("approvals").and_then(TomlItem::as_table) else {
+            return Ok(ApprovalPolicySettings::default());
+        };
+
+        let default = match approvals.get("default") {
+            None => None,
+            Some(item) => {
+                let Some(value) = item.as_str() else {
+                    anyhow::bail!("`approvals.default` must be a string");
+                };
+                Some(ApprovalAction::parse(value)?)
+            }
+        };
+        let deny_edits_outside_workdir = match approvals.get("deny_edits_outside_workdir") {
+            None => None,
+            Some(item) => {
+                let Some(value) = item.as_bool() else {
+                    anyhow::bail!("`approvals.deny_edits_outside_workdir` must be a boolean");
+                };
+                Some(value)
+            }
+        };
+        let rules = match approvals.get("rules") {
+            None => Vec::new(),
+            Some(item) => {
+                let Some(rules) = item.as_array_of_tables() else {
+                    anyhow::bail!("`approvals.rules` must be an array of tables");
+                };
+                rules
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, rule)| {
+                        read_approval_rule(rule)
+                            .with_context(|| format!("invalid approvals.rules[{idx}]"))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+            }
+        };
+
+        Ok(ApprovalPolicySettings {
+            default,
+            deny_edits_outside_workdir,
+            rules,
+        })
+    }
+
+    /// Stream recovery policy from `[stream_recovery]`; missing keys keep their defaults.
+    ///
+    /// Like [`Self::round_schedule`], invalid TOML falls back to the defaults while invalid values
+    /// are reported as errors.
+    pub fn stream_recovery_policy(&self) -> anyhow::Result<StreamRecoveryPolicy> {
+        let mut policy = StreamRecoveryPolicy::default();
+        let Some(content) = read_document_string(&self.path)? else {
+            return Ok(policy);
+        };
+        let Ok(doc) = content.parse::<DocumentMut>() else {
+            return Ok(policy);
+        };
+        let Some(table) = doc.get("stream_recovery").and_then(TomlItem::as_table) else {
+            return Ok(policy);
+        };
+
+        let duration = |key: &str| -> anyhow::Result<Option<std::time::Duration>> {
+            let Some(item) = table.get(key) else {
+                return Ok(None);
+            };
+            let Some(value) = item.as_str() else {
+                anyhow::bail!("`stream_recovery.{key}` must be a duration string like \"30s\"");
+            };
+            crate::workflow::budget::parse_duration(value)
+                .map(Some)
+                .map_err(|err| anyhow::anyhow!("`stream_recovery.{key}`: {err}"))
+        };
+
+        if let Some(item) = table.get("max_retries") {
+            policy.max_retries = item
+                .as_integer()
+                .and_then(|value| u32::try_from(value).ok())
+                .filter(|value| *value > 0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("`stream_recovery.max_retries` must be a positive integer")
+                })?;
+        }
+        if let Some(base_backoff) = duration("base_backoff")? {
+            policy.base_backoff = base_backoff;
+        }
+        if let Some(max_backoff) = duration("max_backoff")? {
+            policy.max_backoff = max_backoff;
+        }
+        if let Some(item) = table.get("jitter") {
+            policy.jitter = item
+                .as_float()
+                .or_else(|| item.as_integer().map(|value| value as f64))
+                .filter(|value| (0.0..=1.0).contains(value))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("`stream_recovery.jitter` must be a number between 0 and 1")
+                })?;
+        }
+        if let Some(item) = table.get("wait_for_usage_limit_reset") {
+            policy.wait_for_usage_limit_reset = item.as_bool().ok_or_else(|| {
+                anyhow::anyhow!("`stream_recovery.wait_for_usage_limit_reset` must be a boolean")
+            })?;
+        }
+        if let Some(max_usage_limit_wait) = duration("max_usage_limit_wait")? {
+            policy.max_usage_limit_wait = max_usage_limit_wait;
+        }
+
+        Ok(policy)
+    }
+
     pub fn set_notice_hide_gitignore_prompt(&self, hide: bool) -> anyhow::Result<()> {
         let content = match read_document_string(&self.path) {
             Ok(Some(existing)) => existing,
@@ -116,6 +310,37 @@ fn read_check_for_update_on_startup(doc: &DocumentMut) -> Option<bool> {
         .and_then(|v| v.as_bool())
 }
 
+fn read_approval_rule(rule: &TomlTable) -> anyhow::Result<ApprovalRule> {
+    let string = |key: &str| -> anyhow::Result<Option<&str>> {
+        match rule.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_str() {
+                Some(value) => Ok(Some(value)),
+                None => anyhow::bail!("`{key}` must be a string"),
+            },
+        }
+    };
+
+    let Some(action) = string("action")? else {
+        anyhow::bail!("missing `action` (`allow` or `deny`)");
+    };
+    let action = ApprovalAction::parse(action)?;
+    let reason = string("reason")?.map(str::to_string);
+
+    match (
+        string("command_prefix")?,
+        string("command_regex")?,
+        string("path_glob")?,
+    ) {
+        (Some(prefix), None, None) => ApprovalRule::command_prefix(action, prefix, reason),
+        (None, Some(pattern), None) => ApprovalRule::command_regex(action, pattern, reason),
+        (None, None, Some(pattern)) => ApprovalRule::path_glob(action, pattern, reason),
+        _ => anyhow::bail!(
+            "expected exactly one of `command_prefix`, `command_regex` or `path_glob`"
+        ),
+    }
+}
+
 fn set_notice_hide_gitignore_prompt(doc: &mut DocumentMut, hide: bool) {
     let notice = ensure_table_for_write(doc, "notice");
     notice["hide_gitignore_prompt"] = value(hide);
@@ -329,4 +554,165 @@ check_for_update_on_startup = false # keep me
             home.join(".codexpotter").join("config.toml")
         );
     }
+
+    #[test]
+    fn reads_round_schedule_and_review_overrides() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"[rounds]
+schedule = ["implement", "implement", "review"]
+
+[rounds.review]
+model = "o3"
+reasoning_effort = "high"
+"#,
+        )
+        .expect("write config");
+
+        let schedule = ConfigStore::new(path)
+            .round_schedule()
+            .expect("read schedule");
+        assert_eq!(
+            schedule.kind_for_round(3),
+            codex_protocol::protocol::PotterRoundKind::Review
+        );
+        assert_eq!(
+            schedule.review,
+            ReviewRoundSettings {
+                model: Some("o3".to_string()),
+                reasoning_effort: Some(codex_protocol::openai_models::ReasoningEffort::High),
+            }
+        );
+    }
+
+    #[test]
+    fn round_schedule_rejects_unknown_round_kind() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[rounds]\nschedule = [\"implement\", \"audit\"]\n")
+            .expect("write config");
+
+        let err = ConfigStore::new(path).round_schedule().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown round kind `audit` (expected `implement` or `review`)"
+        );
+    }
+
+    #[test]
+    fn reads_stream_recovery_policy() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"[stream_recovery]
+max_retries = 5
+base_backoff = "2s"
+max_backoff = "1m"
+jitter = 0.2
+wait_for_usage_limit_reset = false
+"#,
+        )
+        .expect("write config");
+
+        assert_eq!(
+            ConfigStore::new(path.clone())
+                .stream_recovery_policy()
+                .expect("read stream recovery"),
+            StreamRecoveryPolicy {
+                max_retries: 5,
+                base_backoff: std::time::Duration::from_secs(2),
+                max_backoff: std::time::Duration::from_secs(60),
+                jitter: 0.2,
+                wait_for_usage_limit_reset: false,
+                ..Default::default()
+            }
+        );
+
+        std::fs::write(&path, "[stream_recovery]\njitter = 3\n").expect("write config");
+        let err = ConfigStore::new(path).stream_recovery_policy().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`stream_recovery.jitter` must be a number between 0 and 1"
+        );
+    }
+
+    #[test]
+    fn round_schedule_defaults_when_missing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = ConfigStore::new(dir.path().join("config.toml"));
+        assert_eq!(
+            store.round_schedule().expect("read schedule"),
+            RoundSchedule::default()
+        );
+    }
+
+    #[test]
+    fn reads_approval_rules() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"[approvals]
+default = "deny"
+deny_edits_outside_workdir = true
+
+[[approvals.rules]]
+action = "deny"
+command_prefix = "git push"
+reason = "Pushing is left to the user."
+
+[[approvals.rules]]
+action = "allow"
+path_glob = "src/**"
+"#,
+        )
+        .expect("write config");
+
+        let settings = ConfigStore::new(path)
+            .approval_policy_settings()
+            .expect("read approvals");
+        assert_eq!(
+            settings,
+            ApprovalPolicySettings {
+                default: Some(ApprovalAction::Deny),
+                deny_edits_outside_workdir: Some(true),
+                rules: vec![
+                    ApprovalRule::command_prefix(
+                        ApprovalAction::Deny,
+                        "git push",
+                        Some("Pushing is left to the user.".to_string()),
+                    )
+                    .expect("rule"),
+                    ApprovalRule::path_glob(ApprovalAction::Allow, "src/**", None).expect("rule"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn approval_rules_require_a_single_matcher() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"[[approvals.rules]]
+action = "deny"
+command_prefix = "git push"
+path_glob = "**"
+"#,
+        )
+        .expect("write config");
+
+        let err = ConfigStore::new(path)
+            .approval_policy_settings()
+            .unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid approvals.rules[0]: expected exactly one of `command_prefix`, \
+             `command_regex` or `path_glob`"
+        );
+    }
 }
@@ -0,0 +1,638 @@
+//! Background projects (`codex-potter start --detach`, `status`, `stop`).
+//!
+//! A background project is owned by a daemon: a `codex-potter start` process that runs the potter
+//! app-server on a Unix socket (see [`crate::app_server::potter::PotterAppServerTransport`]),
+//! starts the project over its own connection and exits once the project completes. Anyone can
+//! follow the project with `codex-potter attach`.
+//!
+//! Each daemon keeps a record under `.codexpotter/daemons/`:
+//!
+//! - `<pid>.json`: a [`DaemonRecord`] with the socket, the project and its latest round and state,
+//!   rewritten as the project progresses;
+//! - `<pid>.sock`: the app-server socket, removed when the daemon exits;
+//! - `<pid>.log`: the daemon's stdout and stderr.
+//!
+//! A daemon whose socket no longer accepts connections has exited; records are kept so `status`
+//! can show how finished projects ended.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterProjectOutcome;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::app_server::potter::PotterAppServerClient;
+use crate::app_server::potter::PotterAppServerListenAddress;
+use crate::app_server::potter::PotterProjectEvent;
+
+/// Set in the environment of the process `start --detach` spawns, which then runs the daemon
+/// instead of spawning another one.
+const DAEMON_CHILD_ENV: &str = "CODEX_POTTER_DAEMON_CHILD";
+
+/// How long `start --detach` waits for the daemon to start the project.
+const DAEMON_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the daemon waits for its app-server socket to accept connections.
+const SOCKET_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// State of a background project, as last written by its daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonState {
+    /// The daemon is starting its app-server and the project.
+    Starting,
+    /// A round is running.
+    Running,
+    /// The project was interrupted or waits for its plan to be approved; `attach` to answer, or
+    /// `stop` it.
+    Paused,
+    Completed {
+        outcome: PotterProjectOutcome,
+    },
+    /// The daemon failed before the project completed.
+    Failed {
+        message: String,
+    },
+}
+
+impl DaemonState {
+    fn is_final(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::Failed { .. })
+    }
+}
+
+/// `.codexpotter/daemons/<pid>.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonRecord {
+    pub pid: u32,
+    pub socket: PathBuf,
+    pub log_file: PathBuf,
+    pub prompt: String,
+    pub started_at_unix_secs: u64,
+    /// The project id (absolute `MAIN.md` path), once the project started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Progress file relative to the working directory, once the project started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_file: Option<PathBuf>,
+    /// Latest round started: `(current, total)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round: Option<(u32, u32)>,
+    pub state: DaemonState,
+}
+
+impl DaemonRecord {
+    fn address(&self) -> PotterAppServerListenAddress {
+        PotterAppServerListenAddress::Unix(self.socket.clone())
+    }
+
+    fn write(&self, workdir: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self).context("encode daemon record")?;
+        crate::atomic_write::write_atomic_text(&record_path(workdir, self.pid), &contents)
+    }
+
+    /// Whether the daemon still accepts connections.
+    pub fn is_alive(&self) -> bool {
+        #[cfg(unix)]
+        {
+            std::os::unix::net::UnixStream::connect(&self.socket).is_ok()
+        }
+        #[cfg(not(unix))]
+        {
+            false
+        }
+    }
+}
+
+fn daemons_dir(workdir: &Path) -> PathBuf {
+    workdir.join(".codexpotter").join("daemons")
+}
+
+fn record_path(workdir: &Path, pid: u32) -> PathBuf {
+    daemons_dir(workdir).join(format!("{pid}.json"))
+}
+
+/// Whether this process was spawned by `start --detach` to run the daemon.
+pub fn is_daemon_child() -> bool {
+    std::env::var_os(DAEMON_CHILD_ENV).is_some()
+}
+
+/// Fail on platforms without Unix sockets, which daemons listen on and are probed through.
+pub fn ensure_supported() -> anyhow::Result<()> {
+    anyhow::ensure!(
+        cfg!(unix),
+        "background projects need Unix sockets, which this platform does not support"
+    );
+    Ok(())
+}
+
+/// Re-run the current command line as a daemon in the background and wait until it started the
+/// project.
+pub async fn spawn_detached(workdir: &Path) -> anyhow::Result<DaemonRecord> {
+    ensure_supported()?;
+    let dir = daemons_dir(workdir);
+    std::fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    let log = tempfile::Builder::new()
+        .prefix("starting-")
+        .suffix(".log")
+        .tempfile_in(&dir)
+        .context("create daemon log")?;
+    let stdout = log.reopen().context("open daemon log")?;
+    let stderr = log.reopen().context("open daemon log")?;
+
+    let exe = std::env::current_exe().context("resolve codex-potter executable")?;
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(std::env::args_os().skip(1))
+        .env(DAEMON_CHILD_ENV, "1")
+        .current_dir(workdir)
+        .stdin(std::process::Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+    // Run the daemon in its own session: ctrl+c in the terminal does not reach it, and it has no
+    // controlling terminal, so closing the terminal (SIGHUP) does not stop it either.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: `setsid` is async-signal-safe and the closure touches no other process state.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    let mut child = cmd.spawn().context("spawn background codex-potter")?;
+
+    let log_file = dir.join(format!("{}.log", child.id()));
+    log.persist(&log_file)
+        .map_err(|err| anyhow::Error::new(err.error))
+        .with_context(|| format!("persist daemon log {}", log_file.display()))?;
+
+    let record_path = record_path(workdir, child.id());
+    let deadline = Instant::now() + DAEMON_STARTUP_TIMEOUT;
+    loop {
+        if let Some(record) = read_record(&record_path)? {
+            match &record.state {
+                DaemonState::Starting => {}
+                DaemonState::Failed { message } => {
+                    anyhow::bail!("background project failed to start: {message}");
+                }
+                _ => return Ok(record),
+            }
+        }
+        if let Some(status) = child.try_wait().context("check background codex-potter")? {
+            anyhow::bail!(
+                "background codex-potter exited during startup ({status}); see {}",
+                log_file.display()
+            );
+        }
+        anyhow::ensure!(
+            Instant::now() < deadline,
+            "background project did not start within {}s; see {}",
+            DAEMON_STARTUP_TIMEOUT.as_secs(),
+            log_file.display()
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+fn read_record(path: &Path) -> anyhow::Result<Option<DaemonRecord>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+    };
+    serde_json::from_str(&contents)
+        .map(Some)
+        .with_context(|| format!("parse {}", path.display()))
+}
+
+/// Records of every daemon started in `workdir`, oldest first.
+pub fn list_daemons(workdir: &Path) -> anyhow::Result<Vec<DaemonRecord>> {
+    let dir = daemons_dir(workdir);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("read {}", dir.display())),
+    };
+    let mut records = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("read {}", dir.display()))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        if let Some(record) = read_record(&path)? {
+            records.push(record);
+        }
+    }
+    records.sort_by_key(|record| (record.started_at_unix_secs, record.pid));
+    Ok(records)
+}
+
+/// Run the daemon: serve the app-server on this process's socket, start the project and keep the
+/// record up to date until the project completes.
+pub async fn run_daemon(
+    workdir: &Path,
+    prompt: String,
+    rounds: u32,
+    config: crate::app_server::potter::PotterAppServerConfig,
+) -> anyhow::Result<PotterProjectOutcome> {
+    let pid = std::process::id();
+    let dir = daemons_dir(workdir);
+    let mut record = DaemonRecord {
+        pid,
+        socket: dir.join(format!("{pid}.sock")),
+        log_file: dir.join(format!("{pid}.log")),
+        prompt,
+        started_at_unix_secs: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+        project_id: None,
+        progress_file: None,
+        round: None,
+        state: DaemonState::Starting,
+    };
+    record.write(workdir)?;
+
+    let server = crate::app_server::potter::run_potter_app_server(
+        config,
+        crate::app_server::potter::PotterAppServerTransport::Listen {
+            address: record.address(),
+            token: None,
+        },
+    );
+    let result = tokio::select! {
+        result = server => match result {
+            Ok(()) => Err(anyhow::anyhow!("the app-server stopped before the project completed")),
+            Err(err) => Err(err),
+        },
+        result = supervise_project(workdir, &mut record, rounds) => result,
+    };
+    let _ = std::fs::remove_file(&record.socket);
+
+    if let Err(err) = &result {
+        record.state = DaemonState::Failed {
+            message: format!("{err:#}"),
+        };
+        record.write(workdir)?;
+    }
+    result
+}
+
+async fn supervise_project(
+    workdir: &Path,
+    record: &mut DaemonRecord,
+    rounds: u32,
+) -> anyhow::Result<PotterProjectOutcome> {
+    let mut client = connect_when_listening(&record.address()).await?;
+    client
+        .initialize()
+        .await
+        .context("initialize potter app-server")?;
+
+    let mut buffered_events: Vec<PotterProjectEvent> = Vec::new();
+    let response = client
+        .project_start(
+            crate::app_server::potter::ProjectStartParams {
+                user_message: record.prompt.clone(),
+                cwd: Some(workdir.to_path_buf()),
+                rounds: Some(rounds),
+                event_mode: Some(crate::app_server::potter::PotterEventMode::Interactive),
+                output_schema: None,
+            },
+            &mut buffered_events,
+        )
+        .await
+        .context("project/start via potter app-server")?;
+    record.project_id = Some(response.project_id.clone());
+    record.progress_file = Some(response.progress_file_rel);
+    record.state = DaemonState::Running;
+    record.write(workdir)?;
+
+    let mut buffered_events = buffered_events.into_iter();
+    loop {
+        let event = match buffered_events.next() {
+            Some(event) => event,
+            None => client
+                .read_next_project_event()
+                .await?
+                .context("potter app-server closed the event stream")?,
+        };
+        if event.project_id.as_deref() != Some(response.project_id.as_str()) {
+            continue;
+        }
+        match event.event.msg {
+            EventMsg::PotterRoundStarted { current, total, .. } => {
+                record.round = Some((current, total));
+                record.state = DaemonState::Running;
+            }
+            EventMsg::PotterProjectInterrupted { .. }
+            | EventMsg::PotterProjectAwaitingPlanApproval { .. } => {
+                record.state = DaemonState::Paused;
+            }
+            EventMsg::PotterProjectCompleted { outcome } => {
+                record.state = DaemonState::Completed {
+                    outcome: outcome.clone(),
+                };
+                record.write(workdir)?;
+                let _ = client.shutdown().await;
+                return Ok(outcome);
+            }
+            _ => continue,
+        }
+        record.write(workdir)?;
+    }
+}
+
+async fn connect_when_listening(
+    address: &PotterAppServerListenAddress,
+) -> anyhow::Result<PotterAppServerClient> {
+    let deadline = Instant::now() + SOCKET_CONNECT_TIMEOUT;
+    loop {
+        match PotterAppServerClient::connect(address, None).await {
+            Ok(client) => return Ok(client),
+            Err(err) if Instant::now() >= deadline => return Err(err),
+            Err(_) => tokio::time::sleep(Duration::from_millis(50)).await,
+        }
+    }
+}
+
+/// The live daemon in `workdir` running the project of `progress_file` (absolute), if any.
+pub fn live_daemon_for_project(
+    workdir: &Path,
+    progress_file: &Path,
+) -> anyhow::Result<Option<DaemonRecord>> {
+    let project_id = progress_file.to_string_lossy();
+    Ok(list_daemons(workdir)?.into_iter().find(|record| {
+        record.project_id.as_deref() == Some(project_id.as_ref())
+            && !record.state.is_final()
+            && record.is_alive()
+    }))
+}
+
+/// Find the daemon `stop` should act on: `target` is a pid or a project path (same forms as
+/// `resume`); without it, the only running daemon.
+pub fn select_running_daemon(
+    workdir: &Path,
+    records: Vec<DaemonRecord>,
+    target: Option<&str>,
+) -> anyhow::Result<DaemonRecord> {
+    let mut running = records
+        .into_iter()
+        .filter(|record| !record.state.is_final() && record.is_alive())
+        .collect::<Vec<_>>();
+    let Some(target) = target else {
+        return match running.len() {
+            0 => anyhow::bail!("no background project is running"),
+            1 => Ok(running.remove(0)),
+            _ => anyhow::bail!(
+                "several background projects are running (pids {}); pass a pid or project path",
+                running
+                    .iter()
+                    .map(|record| record.pid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+    };
+    if let Ok(pid) = target.parse::<u32>()
+        && let Some(index) = running.iter().position(|record| record.pid == pid)
+    {
+        return Ok(running.remove(index));
+    }
+    let progress_file =
+        crate::workflow::resume::resolve_project_paths(workdir, Path::new(target))?.progress_file;
+    let project_id = progress_file.to_string_lossy();
+    running
+        .into_iter()
+        .find(|record| record.project_id.as_deref() == Some(project_id.as_ref()))
+        .with_context(|| format!("no running background project for {target}"))
+}
+
+/// Interrupt the daemon's project and stop it at the end of the interrupted round.
+///
+/// A project paused by an earlier interrupt or a plan review is stopped right away. Returns how
+/// the project ended (it may complete on its own before the interrupt lands).
+pub async fn stop_daemon(record: &DaemonRecord) -> anyhow::Result<PotterProjectOutcome> {
+    let project_id = record
+        .project_id
+        .clone()
+        .context("the background project has not started yet")?;
+    let mut client = PotterAppServerClient::connect(&record.address(), None)
+        .await
+        .with_context(|| format!("connect to background project (pid {})", record.pid))?;
+    client
+        .initialize()
+        .await
+        .context("initialize potter app-server")?;
+
+    let mut events: Vec<PotterProjectEvent> = Vec::new();
+    let attached = client
+        .project_attach(
+            crate::app_server::potter::ProjectAttachParams {
+                project_id: Some(project_id.clone()),
+            },
+            &mut events,
+        )
+        .await
+        .context("project/attach via potter app-server")?;
+    let paused = attached.events.last().is_some_and(|event| {
+        matches!(
+            event.event.msg,
+            EventMsg::PotterProjectInterrupted { .. }
+                | EventMsg::PotterProjectAwaitingPlanApproval { .. }
+        )
+    });
+
+    if !paused {
+        client
+            .project_interrupt(
+                crate::app_server::potter::ProjectInterruptParams {
+                    project_id: project_id.clone(),
+                },
+                &mut events,
+            )
+            .await
+            .context("project/interrupt via potter app-server")?;
+        let mut events = std::mem::take(&mut events).into_iter();
+        loop {
+            let event = match events.next() {
+                Some(event) => event,
+                None => client
+                    .read_next_project_event()
+                    .await?
+                    .context("potter app-server closed the event stream")?,
+            };
+            if event.project_id.as_deref() != Some(project_id.as_str()) {
+                continue;
+            }
+            match event.event.msg {
+                EventMsg::PotterProjectInterrupted { .. }
+                | EventMsg::PotterProjectAwaitingPlanApproval { .. } => break,
+                EventMsg::PotterProjectCompleted { outcome } => {
+                    let _ = client.shutdown().await;
+                    return Ok(outcome);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    client
+        .project_resolve_interrupt(
+            crate::app_server::potter::ProjectResolveInterruptParams {
+                project_id,
+                action: crate::app_server::potter::ResolveInterruptAction::Stop,
+                turn_prompt_override: None,
+            },
+            &mut Vec::<PotterProjectEvent>::new(),
+        )
+        .await
+        .context("project/resolve_interrupt(stop) via potter app-server")?;
+    let _ = client.shutdown().await;
+    Ok(PotterProjectOutcome::Interrupted)
+}
+
+/// One `status` line: pid, state, round, project and prompt.
+pub fn describe_daemon(record: &DaemonRecord, alive: bool) -> String {
+    let state = match &record.state {
+        DaemonState::Completed { outcome } => describe_outcome(outcome),
+        DaemonState::Failed { message } => format!("failed: {message}"),
+        _ if !alive => String::from("exited unexpectedly"),
+        DaemonState::Starting => String::from("starting"),
+        DaemonState::Running => String::from("running"),
+        DaemonState::Paused => String::from("paused (attach to continue)"),
+    };
+    let round = match record.round {
+        Some((current, total)) => format!("round {current}/{total}"),
+        None => String::from("-"),
+    };
+    let project = match &record.progress_file {
+        Some(progress_file) => progress_file
+            .parent()
+            .unwrap_or(progress_file.as_path())
+            .strip_prefix(Path::new(".codexpotter").join("projects"))
+            .unwrap_or(progress_file.as_path())
+            .display()
+            .to_string(),
+        None => String::from("-"),
+    };
+    let prompt = record.prompt.lines().next().unwrap_or_default();
+    format!(
+        "{:<8} {:<14} {:<12} {:<14} {prompt}",
+        record.pid, state, round, project
+    )
+}
+
+pub fn describe_outcome(outcome: &PotterProjectOutcome) -> String {
+    match outcome {
+        PotterProjectOutcome::Succeeded => String::from("succeeded"),
+        PotterProjectOutcome::Interrupted => String::from("stopped"),
+        PotterProjectOutcome::BudgetExhausted => String::from("round budget exhausted"),
+        PotterProjectOutcome::TaskFailed { message } => format!("task failed: {message}"),
+        PotterProjectOutcome::Fatal { message } => format!("failed: {message}"),
+        PotterProjectOutcome::Stalled { .. }
+        | PotterProjectOutcome::TokenBudgetExhausted { .. }
+        | PotterProjectOutcome::TimeBudgetExhausted { .. } => {
+            crate::workflow::project_runner::early_stop_notice(outcome).unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(pid: u32, state: DaemonState) -> DaemonRecord {
+        DaemonRecord {
+            pid,
+            socket: PathBuf::from(format!("/repo/.codexpotter/daemons/{pid}.sock")),
+            log_file: PathBuf::from(format!("/repo/.codexpotter/daemons/{pid}.log")),
+            prompt: String::from("Fix the flaky test\nand more"),
+            started_at_unix_secs: u64::from(pid),
+            project_id: Some(String::from(
+                "/repo/.codexpotter/projects/2026/10/17/1/MAIN.md",
+            )),
+            progress_file: Some(PathBuf::from(".codexpotter/projects/2026/10/17/1/MAIN.md")),
+            round: Some((2, 10)),
+            state,
+        }
+    }
+
+    #[test]
+    fn records_round_trip_through_the_daemons_dir() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let completed = record(
+            20,
+            DaemonState::Completed {
+                outcome: PotterProjectOutcome::Succeeded,
+            },
+        );
+        let running = record(10, DaemonState::Running);
+        completed.write(temp.path()).expect("write record");
+        running.write(temp.path()).expect("write record");
+        std::fs::write(daemons_dir(temp.path()).join("10.log"), "log").expect("write log");
+
+        assert_eq!(
+            list_daemons(temp.path()).expect("list daemons"),
+            vec![running, completed]
+        );
+        assert_eq!(
+            list_daemons(&temp.path().join("elsewhere")).expect("list daemons"),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn status_lines_show_round_and_outcome() {
+        assert_eq!(
+            describe_daemon(&record(4242, DaemonState::Running), true),
+            "4242     running        round 2/10   2026/10/17/1   Fix the flaky test"
+        );
+        assert_eq!(
+            describe_daemon(&record(4242, DaemonState::Running), false),
+            "4242     exited unexpectedly round 2/10   2026/10/17/1   Fix the flaky test"
+        );
+        assert_eq!(
+            describe_daemon(
+                &record(
+                    4242,
+                    DaemonState::Completed {
+                        outcome: PotterProjectOutcome::Interrupted
+                    }
+                ),
+                false
+            ),
+            "4242     stopped        round 2/10   2026/10/17/1   Fix the flaky test"
+        );
+    }
+
+    #[test]
+    fn stop_needs_a_running_daemon() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        // No socket accepts connections, so neither daemon is running.
+        let records = vec![
+            record(1, DaemonState::Running),
+            record(
+                2,
+                DaemonState::Completed {
+                    outcome: PotterProjectOutcome::Succeeded,
+                },
+            ),
+        ];
+        let err = select_running_daemon(temp.path(), records, None).expect_err("nothing running");
+        assert_eq!(err.to_string(), "no background project is running");
+    }
+}
@@ -0,0 +1,135 @@
+//! Canned answers for user input requests (`exec --answers-file`).
+//!
+//! `exec` cannot ask anyone, so questions from the agent's `request_user_input` tool and MCP
+//! elicitations are answered from a JSON object keyed by question id (for elicitations: the schema
+//! property name). Values are a string or a list of strings:
+//!
+//! ```json
+//! { "database": "SQLite", "targets": ["linux", "macos"] }
+//! ```
+//!
+//! Questions without an entry are left unanswered; a request none of whose questions has an entry
+//! is declined.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum CannedAnswer {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Answers loaded from `--answers-file`; empty without the flag (every request is declined).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecAnswers {
+    by_question_id: HashMap<String, Vec<String>>,
+}
+
+impl ExecAnswers {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("read answers file {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("parse answers file {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<Self> {
+        let answers: HashMap<String, CannedAnswer> = serde_json::from_str(contents)
+            .context("expected a JSON object mapping question ids to a string or string list")?;
+        Ok(Self {
+            by_question_id: answers
+                .into_iter()
+                .map(|(id, answer)| {
+                    let answers = match answer {
+                        CannedAnswer::One(answer) => vec![answer],
+                        CannedAnswer::Many(answers) => answers,
+                    };
+                    (id, answers)
+                })
+                .collect(),
+        })
+    }
+
+    /// Answers for `questions`, or `None` (decline) when none of them has a canned answer.
+    pub fn answers_for(
+        &self,
+        questions: &[RequestUserInputQuestion],
+    ) -> Option<HashMap<String, RequestUserInputAnswer>> {
+        let answers = questions
+            .iter()
+            .filter_map(|question| {
+                let answers = self.by_question_id.get(&question.id)?;
+                Some((
+                    question.id.clone(),
+                    RequestUserInputAnswer {
+                        answers: answers.clone(),
+                    },
+                ))
+            })
+            .collect::<HashMap<_, _>>();
+        (!answers.is_empty()).then_some(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn question(id: &str) -> RequestUserInputQuestion {
+        RequestUserInputQuestion {
+            id: id.to_string(),
+            header: id.to_string(),
+            question: format!("{id}?"),
+            is_other: false,
+            is_secret: false,
+            options: None,
+        }
+    }
+
+    #[test]
+    fn answers_cover_known_questions_and_decline_unknown_requests() {
+        let answers =
+            ExecAnswers::parse(r#"{ "database": "SQLite", "targets": ["linux", "macos"] }"#)
+                .expect("parse answers");
+
+        assert_eq!(
+            answers.answers_for(&[question("database"), question("region")]),
+            Some(HashMap::from([(
+                "database".to_string(),
+                RequestUserInputAnswer {
+                    answers: vec!["SQLite".to_string()],
+                },
+            )]))
+        );
+        assert_eq!(
+            answers
+                .answers_for(&[question("targets")])
+                .and_then(|answers| answers.get("targets").cloned()),
+            Some(RequestUserInputAnswer {
+                answers: vec!["linux".to_string(), "macos".to_string()],
+            })
+        );
+        assert_eq!(answers.answers_for(&[question("region")]), None);
+        assert_eq!(
+            ExecAnswers::default().answers_for(&[question("database")]),
+            None
+        );
+    }
+
+    #[test]
+    fn answers_file_must_map_ids_to_strings() {
+        let err = ExecAnswers::parse(r#"{ "replicas": 3 }"#).expect_err("numbers are rejected");
+        assert!(
+            format!("{err:#}").contains("JSON object mapping question ids"),
+            "unexpected error: {err:#}"
+        );
+    }
+}
@@ -104,6 +104,8 @@ impl<W: Write> ExecJsonRoundUi<W> {
         self.write_jsonl_event(&crate::exec::ExecJsonlEvent::Error(
             crate::exec::ThreadErrorEvent {
                 message: message.clone(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
             },
         ))?;
         self.synthesize_round_fatal_closure(&message)?;
@@ -156,6 +158,8 @@ impl<W: Write> ExecJsonRoundUi<W> {
             let event = crate::exec::ExecJsonlEvent::TurnFailed(crate::exec::TurnFailedEvent {
                 error: crate::exec::ThreadErrorEvent {
                     message: message.to_string(),
+                    stderr_tail: Vec::new(),
+                    stderr_log: None,
                 },
             });
             self.observe_json_turn_state(&event);
@@ -266,7 +270,7 @@ fn exit_reason_from_outcome(outcome: &PotterRoundOutcome) -> ExitReason {
         PotterRoundOutcome::Interrupted => ExitReason::Interrupted,
         PotterRoundOutcome::UserRequested => ExitReason::UserRequested,
         PotterRoundOutcome::TaskFailed { message } => ExitReason::TaskFailed(message.clone()),
-        PotterRoundOutcome::Fatal { message } => ExitReason::Fatal(message.clone()),
+        PotterRoundOutcome::Fatal { message, .. } => ExitReason::Fatal(message.clone()),
     }
 }
 
@@ -276,6 +280,7 @@ mod tests {
     use crate::workflow::round_runner::PotterRoundUi;
     use codex_protocol::approvals::ElicitationRequestEvent;
     use codex_protocol::mcp::RequestId;
+    use codex_protocol::protocol::PotterRoundKind;
     use codex_protocol::protocol::TurnStartedEvent;
     use pretty_assertions::assert_eq;
     use tokio::sync::mpsc::unbounded_channel;
@@ -292,6 +297,7 @@ mod tests {
                 msg: EventMsg::PotterRoundStarted {
                     current: 1,
                     total: 3,
+                    kind: PotterRoundKind::Implement,
                 },
             })
             .expect("send PotterRoundStarted");
@@ -356,16 +362,21 @@ mod tests {
                 crate::exec::ExecJsonlEvent::PotterRoundStarted(
                     crate::exec::PotterRoundStartedEvent {
                         current: 1,
-                        total: 3
+                        total: 3,
+                        kind: PotterRoundKind::Implement,
                     }
                 ),
                 crate::exec::ExecJsonlEvent::TurnStarted(crate::exec::TurnStartedEvent {}),
                 crate::exec::ExecJsonlEvent::Error(crate::exec::ThreadErrorEvent {
                     message: expected_error_message.clone(),
+                    stderr_tail: Vec::new(),
+                    stderr_log: None,
                 }),
                 crate::exec::ExecJsonlEvent::TurnFailed(crate::exec::TurnFailedEvent {
                     error: crate::exec::ThreadErrorEvent {
                         message: expected_error_message.clone(),
+                        stderr_tail: Vec::new(),
+                        stderr_log: None,
                     },
                 }),
                 crate::exec::ExecJsonlEvent::PotterRoundCompleted(
@@ -390,6 +401,7 @@ mod tests {
                 msg: EventMsg::PotterRoundStarted {
                     current: 1,
                     total: 3,
+                    kind: PotterRoundKind::Implement,
                 },
             })
             .expect("send PotterRoundStarted");
@@ -444,13 +456,16 @@ mod tests {
                 crate::exec::ExecJsonlEvent::PotterRoundStarted(
                     crate::exec::PotterRoundStartedEvent {
                         current: 1,
-                        total: 3
+                        total: 3,
+                        kind: PotterRoundKind::Implement,
                     }
                 ),
                 crate::exec::ExecJsonlEvent::TurnStarted(crate::exec::TurnStartedEvent {}),
                 crate::exec::ExecJsonlEvent::TurnFailed(crate::exec::TurnFailedEvent {
                     error: crate::exec::ThreadErrorEvent {
                         message: "fatal exit".to_string(),
+                        stderr_tail: Vec::new(),
+                        stderr_log: None,
                     },
                 }),
                 crate::exec::ExecJsonlEvent::PotterRoundCompleted(
@@ -13,6 +13,9 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterApprovalKind;
+use codex_protocol::protocol::PotterRoundKind;
+use codex_protocol::protocol::PotterUserInputAnswer;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -41,10 +44,24 @@ pub enum ExecJsonlEvent {
 
     #[serde(rename = "potter.project.started")]
     PotterProjectStarted(PotterProjectStartedEvent),
+    #[serde(rename = "potter.project.resumed")]
+    PotterProjectResumed(PotterProjectResumedEvent),
+    #[serde(rename = "potter.replay.started")]
+    PotterReplayStarted(PotterReplayStartedEvent),
+    #[serde(rename = "potter.replay.completed")]
+    PotterReplayCompleted(PotterReplayCompletedEvent),
     #[serde(rename = "potter.round.started")]
     PotterRoundStarted(PotterRoundStartedEvent),
+    #[serde(rename = "potter.round.verification")]
+    PotterRoundVerification(PotterRoundVerificationEvent),
+    #[serde(rename = "potter.approval.decision")]
+    PotterApprovalDecision(PotterApprovalDecisionEvent),
+    #[serde(rename = "potter.user_input.answered")]
+    PotterUserInputAnswered(PotterUserInputAnsweredEvent),
     #[serde(rename = "potter.round.completed")]
     PotterRoundCompleted(PotterRoundCompletedEvent),
+    #[serde(rename = "potter.project.awaiting_plan_approval")]
+    PotterProjectAwaitingPlanApproval(PotterProjectAwaitingPlanApprovalEvent),
     #[serde(rename = "potter.project.succeeded")]
     PotterProjectSucceeded(PotterProjectSucceededEvent),
     #[serde(rename = "potter.project.completed")]
@@ -55,6 +72,8 @@ pub enum ExecJsonlEvent {
     PotterStreamRecoveryRecovered(PotterStreamRecoveryRecoveredEvent),
     #[serde(rename = "potter.stream_recovery.gave_up")]
     PotterStreamRecoveryGaveUp(PotterStreamRecoveryGaveUpEvent),
+    #[serde(rename = "potter.rate_limit.wait")]
+    PotterRateLimitWait(PotterRateLimitWaitEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -100,6 +119,12 @@ pub struct ItemCompletedEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ThreadErrorEvent {
     pub message: String,
+    /// Last lines of the upstream app-server's stderr, for the error that ends a round.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stderr_tail: Vec<String>,
+    /// Round log with the complete upstream app-server stderr.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr_log: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -276,12 +301,64 @@ pub struct PotterProjectStartedEvent {
     pub user_message: String,
     pub git_commit_start: String,
     pub git_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterProjectResumedEvent {
+    pub working_dir: String,
+    pub project_dir: String,
+    pub progress_file: String,
+    pub git_commit_start: String,
+    pub git_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
 }
 
+/// Start of the replayed history emitted by `exec resume`.
+///
+/// Every event until the matching `potter.replay.completed` describes previously recorded rounds,
+/// not live activity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterReplayStartedEvent {
+    pub rounds: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PotterReplayCompletedEvent {}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PotterRoundStartedEvent {
     pub current: u32,
     pub total: u32,
+    #[serde(default, skip_serializing_if = "PotterRoundKind::is_implement")]
+    pub kind: PotterRoundKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterRoundVerificationEvent {
+    pub command: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterApprovalDecisionEvent {
+    pub kind: PotterApprovalKind,
+    pub summary: String,
+    pub approved: bool,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterUserInputAnsweredEvent {
+    /// MCP server that asked, for elicitations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    pub answers: Vec<PotterUserInputAnswer>,
+    pub declined: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -299,6 +376,13 @@ pub struct PotterRoundCompletedEvent {
     pub message: Option<String>,
 }
 
+/// The planning round finished under `--review-plan`; `todo` lists the planned items.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterProjectAwaitingPlanApprovalEvent {
+    pub progress_file: String,
+    pub todo: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PotterProjectSucceededEvent {
     pub rounds: u32,
@@ -313,6 +397,14 @@ pub struct PotterProjectSucceededEvent {
 pub enum PotterProjectCompletedOutcome {
     Succeeded,
     BudgetExhausted,
+    /// Stopped after consecutive rounds without git changes (`--stall-rounds`).
+    Stalled,
+    /// Stopped after reaching `--max-tokens`.
+    TokenBudgetExhausted,
+    /// Stopped after reaching `--max-duration`.
+    TimeBudgetExhausted,
+    /// Stopped after the planning round so the plan can be reviewed (`--review-plan`).
+    AwaitingPlanApproval,
     TaskFailed,
     Fatal,
 }
@@ -329,6 +421,9 @@ pub struct PotterProjectCompletedEvent {
     pub git_commit_start: String,
     pub git_commit_end: String,
     pub git_branch: Option<String>,
+    /// Final report of a successful project, matching `--output-schema`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -336,6 +431,10 @@ pub struct PotterStreamRecoveryUpdateEvent {
     pub attempt: u32,
     pub max_attempts: u32,
     pub error_message: String,
+    /// Wait before the retry, in milliseconds.
+    pub backoff_ms: u64,
+    /// `true` when the retry waits for a usage limit to reset instead of backing off.
+    pub waits_for_usage_limit_reset: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -348,6 +447,15 @@ pub struct PotterStreamRecoveryGaveUpEvent {
     pub max_attempts: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PotterRateLimitWaitEvent {
+    pub used_percent: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_minutes: Option<i64>,
+    /// Wait before the round starts, in seconds.
+    pub resets_in_secs: u64,
+}
+
 #[derive(Debug, Clone)]
 struct RunningCommand {
     command: String,
@@ -393,6 +501,8 @@ pub struct ExecJsonlEventProcessor {
     running_todo_list: Option<RunningTodoList>,
     last_total_token_usage: Option<codex_protocol::protocol::TokenUsage>,
     last_critical_error: Option<ThreadErrorEvent>,
+    /// `PotterAppServerStderr` waiting to be attached to the error that follows it.
+    pending_app_server_stderr: Option<(Vec<String>, Option<PathBuf>)>,
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
 }
 
@@ -463,8 +573,12 @@ impl ExecJsonlEventProcessor {
                 },
             })],
             EventMsg::Error(ev) => {
+                let (stderr_tail, stderr_log) =
+                    self.pending_app_server_stderr.take().unwrap_or_default();
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
+                    stderr_tail,
+                    stderr_log,
                 };
                 self.last_critical_error = Some(error.clone());
                 vec![ExecJsonlEvent::Error(error)]
@@ -476,7 +590,11 @@ impl ExecJsonlEventProcessor {
                     }
                     _ => ev.message.clone(),
                 };
-                vec![ExecJsonlEvent::Error(ThreadErrorEvent { message })]
+                vec![ExecJsonlEvent::Error(ThreadErrorEvent {
+                    message,
+                    stderr_tail: Vec::new(),
+                    stderr_log: None,
+                })]
             }
             EventMsg::PlanUpdate(args) => self.handle_plan_update(args),
             EventMsg::WebSearchEnd(ev) => vec![ExecJsonlEvent::ItemCompleted(ItemCompletedEvent {
@@ -496,19 +614,71 @@ impl ExecJsonlEventProcessor {
             EventMsg::CollabWaitingEnd(ev) => self.handle_collab_wait_end(ev),
             EventMsg::CollabCloseBegin(ev) => self.handle_collab_close_begin(ev),
             EventMsg::CollabCloseEnd(ev) => self.handle_collab_close_end(ev),
-            EventMsg::PotterRoundStarted { current, total } => {
+            EventMsg::PotterRoundStarted {
+                current,
+                total,
+                kind,
+            } => {
                 vec![ExecJsonlEvent::PotterRoundStarted(
                     PotterRoundStartedEvent {
                         current: *current,
                         total: *total,
+                        kind: *kind,
                     },
                 )]
             }
+            EventMsg::PotterRoundVerification {
+                command,
+                passed,
+                exit_code,
+                output,
+            } => vec![ExecJsonlEvent::PotterRoundVerification(
+                PotterRoundVerificationEvent {
+                    command: command.clone(),
+                    passed: *passed,
+                    exit_code: *exit_code,
+                    output: output.clone(),
+                },
+            )],
+            EventMsg::PotterApprovalDecision {
+                kind,
+                summary,
+                approved,
+                reason,
+            } => vec![ExecJsonlEvent::PotterApprovalDecision(
+                PotterApprovalDecisionEvent {
+                    kind: *kind,
+                    summary: summary.clone(),
+                    approved: *approved,
+                    reason: reason.clone(),
+                },
+            )],
+            EventMsg::PotterUserInputAnswered {
+                server_name,
+                answers,
+                declined,
+            } => vec![ExecJsonlEvent::PotterUserInputAnswered(
+                PotterUserInputAnsweredEvent {
+                    server_name: server_name.clone(),
+                    answers: answers.clone(),
+                    declined: *declined,
+                },
+            )],
             EventMsg::PotterRoundFinished { outcome } => {
                 vec![ExecJsonlEvent::PotterRoundCompleted(
                     potter_round_completed_from_outcome(outcome),
                 )]
             }
+            EventMsg::PotterProjectAwaitingPlanApproval {
+                user_prompt_file,
+                todo,
+                ..
+            } => vec![ExecJsonlEvent::PotterProjectAwaitingPlanApproval(
+                PotterProjectAwaitingPlanApprovalEvent {
+                    progress_file: self.resolve_path(user_prompt_file),
+                    todo: todo.clone(),
+                },
+            )],
             EventMsg::PotterProjectSucceeded {
                 rounds,
                 duration,
@@ -528,11 +698,18 @@ impl ExecJsonlEventProcessor {
                 attempt,
                 max_attempts,
                 error_message,
+                backoff,
+                strategy,
             } => vec![ExecJsonlEvent::PotterStreamRecoveryUpdate(
                 PotterStreamRecoveryUpdateEvent {
                     attempt: *attempt,
                     max_attempts: *max_attempts,
                     error_message: error_message.clone(),
+                    backoff_ms: u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX),
+                    waits_for_usage_limit_reset: matches!(
+                        strategy,
+                        codex_protocol::protocol::PotterStreamRecoveryStrategy::UsageLimitReset
+                    ),
                 },
             )],
             EventMsg::PotterStreamRecoveryRecovered => {
@@ -551,6 +728,21 @@ impl ExecJsonlEventProcessor {
                     max_attempts: *max_attempts,
                 },
             )],
+            EventMsg::PotterRateLimitWait {
+                used_percent,
+                window_minutes,
+                resets_in,
+            } => vec![ExecJsonlEvent::PotterRateLimitWait(
+                PotterRateLimitWaitEvent {
+                    used_percent: *used_percent,
+                    window_minutes: *window_minutes,
+                    resets_in_secs: resets_in.as_secs(),
+                },
+            )],
+            EventMsg::PotterAppServerStderr { tail, log_path } => {
+                self.pending_app_server_stderr = Some((tail.clone(), log_path.clone()));
+                Vec::new()
+            }
             _ => Vec::new(),
         }
     }
@@ -564,6 +756,7 @@ impl ExecJsonlEventProcessor {
         self.running_todo_list = None;
         self.last_total_token_usage = None;
         self.last_critical_error = None;
+        self.pending_app_server_stderr = None;
         self.running_collab_tool_calls.clear();
     }
 
@@ -670,6 +863,8 @@ impl ExecJsonlEventProcessor {
             .take()
             .unwrap_or_else(|| ThreadErrorEvent {
                 message: format!("turn aborted: {:?}", &ev.reason),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
             });
 
         out.push(ExecJsonlEvent::TurnFailed(TurnFailedEvent { error }));
@@ -1129,7 +1324,7 @@ fn potter_round_completed_from_outcome(
                 message: Some(message.clone()),
             }
         }
-        codex_protocol::protocol::PotterRoundOutcome::Fatal { message } => {
+        codex_protocol::protocol::PotterRoundOutcome::Fatal { message, .. } => {
             PotterRoundCompletedEvent {
                 outcome: PotterRoundCompletedOutcome::Fatal,
                 message: Some(message.clone()),
@@ -1222,7 +1417,9 @@ mod tests {
                 codex_error_info: None,
             })),
             vec![ExecJsonlEvent::Error(ThreadErrorEvent {
-                message: "boom".to_string()
+                message: "boom".to_string(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
             })]
         );
 
@@ -1235,11 +1432,52 @@ mod tests {
             vec![ExecJsonlEvent::TurnFailed(TurnFailedEvent {
                 error: ThreadErrorEvent {
                     message: "boom".to_string(),
+                    stderr_tail: Vec::new(),
+                    stderr_log: None,
                 }
             })]
         );
     }
 
+    #[test]
+    fn app_server_stderr_is_attached_to_the_following_error() {
+        let mut ep = ExecJsonlEventProcessor::default();
+
+        assert_eq!(
+            ep.collect_event(&EventMsg::PotterAppServerStderr {
+                tail: vec!["thread 'main' panicked".to_string()],
+                log_path: Some(PathBuf::from("rounds/2/app-server.stderr.log")),
+            }),
+            Vec::new()
+        );
+        let error = ep.collect_event(&EventMsg::Error(ErrorEvent {
+            message: "boom".to_string(),
+            codex_error_info: None,
+        }));
+        assert_eq!(
+            serde_json::to_value(&error[0]).expect("serialize"),
+            serde_json::json!({
+                "type": "error",
+                "message": "boom",
+                "stderr_tail": ["thread 'main' panicked"],
+                "stderr_log": "rounds/2/app-server.stderr.log",
+            })
+        );
+
+        // Later errors do not repeat the tail.
+        assert_eq!(
+            ep.collect_event(&EventMsg::Error(ErrorEvent {
+                message: "again".to_string(),
+                codex_error_info: None,
+            })),
+            vec![ExecJsonlEvent::Error(ThreadErrorEvent {
+                message: "again".to_string(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
+            })]
+        );
+    }
+
     #[test]
     fn agent_message_and_reasoning_emit_completed_items() {
         let mut ep = ExecJsonlEventProcessor::default();
@@ -1460,11 +1698,13 @@ mod tests {
             ep.collect_event(&EventMsg::PotterRoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
             }),
             vec![ExecJsonlEvent::PotterRoundStarted(
                 PotterRoundStartedEvent {
                     current: 1,
                     total: 10,
+                    kind: PotterRoundKind::Implement,
                 }
             )]
         );
@@ -1482,6 +1722,62 @@ mod tests {
                 }
             )]
         );
+
+        let verification = ep.collect_event(&EventMsg::PotterRoundVerification {
+            command: "cargo test".to_string(),
+            passed: false,
+            exit_code: Some(101),
+            output: "FAILED".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_value(&verification[0]).expect("serialize"),
+            serde_json::json!({
+                "type": "potter.round.verification",
+                "command": "cargo test",
+                "passed": false,
+                "exit_code": 101,
+                "output": "FAILED",
+            })
+        );
+
+        let decision = ep.collect_event(&EventMsg::PotterApprovalDecision {
+            kind: PotterApprovalKind::Command,
+            summary: "git push".to_string(),
+            approved: false,
+            reason: "Pushing is left to the user.".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_value(&decision[0]).expect("serialize"),
+            serde_json::json!({
+                "type": "potter.approval.decision",
+                "kind": "command",
+                "summary": "git push",
+                "approved": false,
+                "reason": "Pushing is left to the user.",
+            })
+        );
+
+        let answered = ep.collect_event(&EventMsg::PotterUserInputAnswered {
+            server_name: None,
+            answers: vec![PotterUserInputAnswer {
+                header: "Database".to_string(),
+                question: "Which database?".to_string(),
+                answers: vec!["SQLite".to_string()],
+            }],
+            declined: false,
+        });
+        assert_eq!(
+            serde_json::to_value(&answered[0]).expect("serialize"),
+            serde_json::json!({
+                "type": "potter.user_input.answered",
+                "answers": [{
+                    "header": "Database",
+                    "question": "Which database?",
+                    "answers": ["SQLite"],
+                }],
+                "declined": false,
+            })
+        );
     }
 
     #[test]
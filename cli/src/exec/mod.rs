@@ -1,21 +1,42 @@
-//! Non-interactive `exec --json` runner.
+//! Non-interactive `exec` runner.
 //!
-//! `codex-potter exec --json` is intended for automation and tooling integration. It runs a
-//! CodexPotter project in headless mode and emits newline-delimited JSON events to stdout.
+//! `codex-potter exec` is intended for automation and tooling integration. It runs a CodexPotter
+//! project in headless mode and writes events to stdout, either as newline-delimited JSON
+//! (`--json`) or as human-readable text (the default).
 //!
 //! Design notes:
-//! - Output is a strict superset of upstream `codex exec --json` events (see [`ExecJsonlEvent`]).
-//! - Interactive requests (e.g. `RequestUserInput`) are treated as fatal because `exec` is
-//!   non-interactive.
-//! - Preflight failures should still produce a single JSONL `error` event so downstream consumers
-//!   can handle failures uniformly.
+//! - JSON output is a strict superset of upstream `codex exec --json` events (see
+//!   [`ExecJsonlEvent`]).
+//! - Text output renders the very same [`ExecJsonlEvent`] values (see [`ExecTextRenderer`]), so
+//!   both modes observe an identical item lifecycle.
+//! - Questions from the agent (`request_user_input`) and MCP elicitations are answered from
+//!   `--answers-file` (see [`ExecAnswers`]); requests without canned answers are declined. Raw
+//!   interactive request events that reach `exec` anyway are treated as fatal.
+//! - Preflight failures should still produce a single JSONL `error` event (in `--json` mode) so
+//!   downstream consumers can handle failures uniformly.
+//! - `exec resume` continues an existing project; recorded history is emitted as a distinct
+//!   `potter.replay.*` section so consumers can tell it apart from live events.
+//! - With `--review-plan`, nobody can approve the plan interactively: the run stops after the
+//!   planning round with outcome `awaiting_plan_approval`, and `exec resume` implements the
+//!   (possibly edited) plan.
+//! - Approval requests the policy leaves to the user (`ask`) are denied, with feedback telling
+//!   the model that nobody can approve them.
+//! - With `--output-schema`, a successful project ends with a structured report (see
+//!   [`ExecOutputSchema`]).
 
+mod answers;
 mod jsonl;
+mod output_schema;
+mod text;
 
 #[cfg(test)]
 mod json_round_ui;
 
+pub use answers::ExecAnswers;
 pub use jsonl::*;
+pub use output_schema::ExecOutputSchema;
+pub use text::ExecTextRenderer;
+pub use text::stdout_supports_color;
 
 use std::io::Read as _;
 use std::io::Write;
@@ -26,58 +47,87 @@ use std::time::Instant;
 
 use anyhow::Context;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterApprovalChoice;
 use codex_protocol::protocol::PotterProjectOutcome;
 
-pub async fn run_exec_json(
+/// Output format for `codex-potter exec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecOutputFormat {
+    /// Strict JSONL event stream (`--json`).
+    Json,
+    /// Line-oriented human-readable text.
+    Text { color: bool },
+}
+
+/// Settings used to spawn the `codex-potter app-server` control plane for `exec`.
+#[derive(Debug, Clone)]
+pub struct ExecAppServerOptions {
+    pub rounds: NonZeroUsize,
+    pub codex_bin: String,
+    pub backend_launch: crate::app_server::AppServerLaunchConfig,
+    pub upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+    pub project_cli_args: crate::app_server::potter::PotterProjectCliArgs,
+}
+
+impl ExecAppServerOptions {
+    async fn spawn_client(
+        self,
+        workdir: &Path,
+    ) -> anyhow::Result<crate::app_server::potter::PotterAppServerClient> {
+        crate::app_server::potter::PotterAppServerClient::spawn(
+            workdir.to_path_buf(),
+            self.codex_bin,
+            self.rounds,
+            self.backend_launch,
+            self.upstream_cli_args,
+            self.project_cli_args,
+        )
+        .await
+    }
+}
+
+pub async fn run_exec(
     workdir: &Path,
+    output_format: ExecOutputFormat,
     prompt: Option<String>,
-    rounds: NonZeroUsize,
-    codex_bin: String,
-    backend_launch: crate::app_server::AppServerLaunchConfig,
-    upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
+    app_server: ExecAppServerOptions,
+    answers: ExecAnswers,
+    output_schema: Option<ExecOutputSchema>,
 ) -> i32 {
     let prompt = match prompt {
         Some(prompt) => prompt,
         None => match read_prompt_from_stdin() {
             Ok(prompt) => prompt,
             Err(err) => {
-                let _ = write_exec_json_preflight_error(&format!("{err:#}"));
+                let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
                 return 1;
             }
         },
     };
 
     if prompt.trim().is_empty() {
-        let _ = write_exec_json_preflight_error("prompt is empty");
+        let _ = write_exec_preflight_error(output_format, "prompt is empty");
         return 1;
     }
 
-    let rounds_total_u32 = match crate::rounds::round_budget_to_u32(rounds) {
+    let rounds_total_u32 = match crate::rounds::round_budget_to_u32(app_server.rounds) {
         Ok(rounds_total_u32) => rounds_total_u32,
         Err(err) => {
-            let _ = write_exec_json_preflight_error(&err.to_string());
+            let _ = write_exec_preflight_error(output_format, &err.to_string());
             return 1;
         }
     };
 
-    let mut client = match crate::app_server::potter::PotterAppServerClient::spawn(
-        workdir.to_path_buf(),
-        codex_bin,
-        rounds,
-        backend_launch,
-        upstream_cli_args,
-    )
-    .await
-    {
+    let mut client = match app_server.spawn_client(workdir).await {
         Ok(client) => client,
         Err(err) => {
-            let _ = write_exec_json_preflight_error(&format!("{err:#}"));
+            let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
             return 1;
         }
     };
 
     if let Err(err) = client.initialize().await {
-        let _ = write_exec_json_preflight_error(&format!("{err:#}"));
+        let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
         return 1;
     }
 
@@ -89,6 +139,7 @@ pub async fn run_exec_json(
                 cwd: Some(workdir.to_path_buf()),
                 rounds: Some(rounds_total_u32),
                 event_mode: Some(crate::app_server::potter::PotterEventMode::ExecJson),
+                output_schema: output_schema.as_ref().map(|output| output.schema.clone()),
             },
             &mut buffered_events,
         )
@@ -96,17 +147,21 @@ pub async fn run_exec_json(
     {
         Ok(response) => response,
         Err(err) => {
-            let _ = write_exec_json_preflight_error(&format!("{err:#}"));
+            let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
             let _ = client.shutdown().await;
             return 1;
         }
     };
 
     let stdout = std::io::stdout();
-    let mut emitter = ExecJsonlEmitter::new(stdout.lock(), start_response.working_dir.clone());
+    let mut emitter = ExecEmitter::new(
+        stdout.lock(),
+        start_response.working_dir.clone(),
+        output_format,
+    );
 
     if emitter
-        .write_jsonl_event(&crate::exec::ExecJsonlEvent::PotterProjectStarted(
+        .write_event(&crate::exec::ExecJsonlEvent::PotterProjectStarted(
             crate::exec::PotterProjectStartedEvent {
                 working_dir: start_response.working_dir.to_string_lossy().to_string(),
                 project_dir: start_response.project_dir.to_string_lossy().to_string(),
@@ -114,6 +169,10 @@ pub async fn run_exec_json(
                 user_message: prompt.clone(),
                 git_commit_start: start_response.git_commit_start.clone(),
                 git_branch: start_response.git_branch.clone(),
+                worktree: start_response
+                    .worktree
+                    .as_ref()
+                    .map(|worktree| worktree.to_string_lossy().to_string()),
             },
         ))
         .is_err()
@@ -122,10 +181,252 @@ pub async fn run_exec_json(
         return 1;
     }
 
+    let summary = ExecProjectSummary {
+        project_id: start_response.project_id,
+        working_dir: start_response.working_dir,
+        progress_file: start_response.progress_file,
+        git_commit_start: start_response.git_commit_start,
+        git_branch: start_response.git_branch,
+        worktree: start_response.worktree,
+        rounds_total: start_response.rounds_total,
+    };
+    stream_project_until_completed(
+        &mut client,
+        &mut emitter,
+        &summary,
+        &answers,
+        output_schema.as_ref(),
+        buffered_events,
+    )
+    .await
+}
+
+/// Options for `codex-potter exec resume`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecResumeOptions {
+    /// Project path to resolve to a unique `MAIN.md` (same forms as `codex-potter resume`).
+    pub project_path: PathBuf,
+    /// How to handle an unfinished last round.
+    pub resume_policy: crate::app_server::potter::ResumePolicy,
+    /// Whether to emit previously completed rounds as a `potter.replay.*` section.
+    pub replay: bool,
+}
+
+/// Resume an existing project and continue iterating, emitting the exec event stream.
+///
+/// The stream starts with `potter.project.resumed`, optionally followed by a
+/// `potter.replay.started` ... `potter.replay.completed` section containing the recorded history,
+/// then continues with the same live events as a fresh `exec --json` run.
+pub async fn run_exec_resume(
+    workdir: &Path,
+    output_format: ExecOutputFormat,
+    options: ExecResumeOptions,
+    app_server: ExecAppServerOptions,
+    answers: ExecAnswers,
+    output_schema: Option<ExecOutputSchema>,
+) -> i32 {
+    let ExecResumeOptions {
+        project_path,
+        resume_policy,
+        replay,
+    } = options;
+
+    let rounds_u32 = match crate::rounds::round_budget_to_u32(app_server.rounds) {
+        Ok(rounds_u32) => rounds_u32,
+        Err(err) => {
+            let _ = write_exec_preflight_error(output_format, &err.to_string());
+            return 1;
+        }
+    };
+
+    let mut client = match app_server.spawn_client(workdir).await {
+        Ok(client) => client,
+        Err(err) => {
+            let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
+            return 1;
+        }
+    };
+
+    if let Err(err) = client.initialize().await {
+        let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
+        return 1;
+    }
+
+    let resume = match client
+        .project_resume(
+            crate::app_server::potter::ProjectResumeParams {
+                project_path,
+                cwd: Some(workdir.to_path_buf()),
+                event_mode: Some(crate::app_server::potter::PotterEventMode::ExecJson),
+            },
+            &mut Vec::new(),
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
+            let _ = client.shutdown().await;
+            return 1;
+        }
+    };
+
+    let git_commit_start = match crate::workflow::project::progress_file_git_commit_start(
+        &resume.working_dir,
+        &resume.progress_file_rel,
+    ) {
+        Ok(git_commit_start) => git_commit_start,
+        Err(err) => {
+            let _ = write_exec_preflight_error(output_format, &format!("{err:#}"));
+            let _ = client.shutdown().await;
+            return 1;
+        }
+    };
+
+    let stdout = std::io::stdout();
+    let mut emitter = ExecEmitter::new(stdout.lock(), resume.working_dir.clone(), output_format);
+
+    if emitter
+        .write_event(&crate::exec::ExecJsonlEvent::PotterProjectResumed(
+            crate::exec::PotterProjectResumedEvent {
+                working_dir: resume.working_dir.to_string_lossy().to_string(),
+                project_dir: resume.project_dir.to_string_lossy().to_string(),
+                progress_file: resume.progress_file.to_string_lossy().to_string(),
+                git_commit_start: git_commit_start.clone(),
+                git_branch: resume.git_branch.clone(),
+                worktree: resume
+                    .worktree
+                    .as_ref()
+                    .map(|worktree| worktree.to_string_lossy().to_string()),
+            },
+        ))
+        .is_err()
+    {
+        let _ = client.shutdown().await;
+        return 1;
+    }
+
+    if replay && emitter.write_replay_section(&resume.replay).is_err() {
+        let _ = client.shutdown().await;
+        return 1;
+    }
+
+    // Mirror the interactive resume flow: an unfinished round is continued with its remaining
+    // budget, otherwise a new iteration window of `--rounds` starts.
+    let continued_round = resume
+        .unfinished_round
+        .as_ref()
+        .filter(|_| {
+            matches!(
+                resume_policy,
+                crate::app_server::potter::ResumePolicy::ContinueUnfinishedRound
+            )
+        })
+        .map(|unfinished| {
+            (
+                unfinished.round_current,
+                unfinished.round_total,
+                unfinished.round_kind,
+            )
+        });
+    let rounds = match resume.unfinished_round.as_ref() {
+        Some(unfinished) if continued_round.is_some() => {
+            unfinished.remaining_rounds_including_current
+        }
+        _ => rounds_u32,
+    };
+
+    let mut buffered_events = Vec::new();
+    let start_rounds_response = match client
+        .project_start_rounds(
+            crate::app_server::potter::ProjectStartRoundsParams {
+                project_id: resume.project_id.clone(),
+                rounds: Some(rounds),
+                resume_policy: Some(resume_policy),
+                event_mode: Some(crate::app_server::potter::PotterEventMode::ExecJson),
+                output_schema: output_schema.as_ref().map(|output| output.schema.clone()),
+            },
+            &mut buffered_events,
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            let _ = emitter.fail_fast_with_error(format!("{err:#}"));
+            let _ = client.shutdown().await;
+            return 1;
+        }
+    };
+
+    // A continued round does not emit a new `PotterRoundStarted` marker; synthesize one so the
+    // round lifecycle in the JSONL stream stays well-formed.
+    if let Some((current, total, kind)) = continued_round
+        && emitter
+            .process_event_msg(&EventMsg::PotterRoundStarted {
+                current,
+                total,
+                kind,
+            })
+            .is_err()
+    {
+        let _ = client.shutdown().await;
+        return 1;
+    }
+
+    let summary = ExecProjectSummary {
+        project_id: resume.project_id,
+        working_dir: resume.working_dir,
+        progress_file: resume.progress_file,
+        git_commit_start,
+        git_branch: resume.git_branch,
+        worktree: resume.worktree,
+        rounds_total: start_rounds_response.rounds_total,
+    };
+    stream_project_until_completed(
+        &mut client,
+        &mut emitter,
+        &summary,
+        &answers,
+        output_schema.as_ref(),
+        buffered_events,
+    )
+    .await
+}
+
+/// Project metadata needed to emit the final `potter.project.completed` event.
+struct ExecProjectSummary {
+    project_id: String,
+    working_dir: PathBuf,
+    progress_file: PathBuf,
+    git_commit_start: String,
+    git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    worktree: Option<PathBuf>,
+    rounds_total: u32,
+}
+
+/// Feedback sent with approval requests `exec` denies because it cannot ask anyone.
+const EXEC_APPROVAL_FEEDBACK: &str = "codex-potter exec runs unattended and cannot ask for approval; find another way or leave \
+     this step to the user";
+
+/// Forward live project events as JSONL until the project completes, then emit
+/// `potter.project.completed` (with the report requested by `--output-schema`) and shut down the
+/// app-server.
+///
+/// Returns the process exit code (`0` only when the project succeeded).
+async fn stream_project_until_completed<W: Write>(
+    client: &mut crate::app_server::potter::PotterAppServerClient,
+    emitter: &mut ExecEmitter<W>,
+    summary: &ExecProjectSummary,
+    answers: &ExecAnswers,
+    output_schema: Option<&ExecOutputSchema>,
+    buffered_events: Vec<codex_protocol::protocol::Event>,
+) -> i32 {
     let project_started_at = Instant::now();
 
     let mut final_outcome: Option<PotterProjectOutcome> = None;
     let mut should_interrupt_project = false;
+    let mut awaiting_plan_approval = false;
 
     let mut buffered_iter = buffered_events.into_iter();
     while final_outcome.is_none() {
@@ -158,12 +459,82 @@ pub async fn run_exec_json(
             break;
         };
 
+        if let EventMsg::PotterApprovalRequest { approval_id, .. } = &event.msg
+            && let Err(err) = client
+                .answer_approval(
+                    approval_id,
+                    crate::app_server::potter::ApprovalRequestResponse {
+                        decision: PotterApprovalChoice::Deny,
+                        feedback: Some(EXEC_APPROVAL_FEEDBACK.to_string()),
+                    },
+                )
+                .await
+        {
+            let message = format!("{err:#}");
+            should_interrupt_project = true;
+            if emitter.fail_fast_with_error(message.clone()).is_err() {
+                let _ = client.shutdown().await;
+                return 1;
+            }
+            final_outcome = Some(PotterProjectOutcome::Fatal { message });
+            break;
+        }
+
+        if let EventMsg::PotterUserInputRequest {
+            request_id,
+            questions,
+            ..
+        } = &event.msg
+            && let Err(err) = client
+                .answer_user_input(
+                    request_id,
+                    crate::app_server::potter::UserInputRequestResponse {
+                        answers: answers.answers_for(questions),
+                    },
+                )
+                .await
+        {
+            let message = format!("{err:#}");
+            should_interrupt_project = true;
+            if emitter.fail_fast_with_error(message.clone()).is_err() {
+                let _ = client.shutdown().await;
+                return 1;
+            }
+            final_outcome = Some(PotterProjectOutcome::Fatal { message });
+            break;
+        }
+
         match emitter.process_event_msg(&event.msg) {
             Ok(ExecEventProgress::Continue) => {}
             Ok(ExecEventProgress::ProjectCompleted { outcome }) => {
                 final_outcome = Some(outcome);
                 break;
             }
+            Ok(ExecEventProgress::AwaitingPlanApproval) => {
+                // Stop the paused project; the plan stays in the progress file for review.
+                if let Err(err) = client
+                    .project_resolve_interrupt(
+                        crate::app_server::potter::ProjectResolveInterruptParams {
+                            project_id: summary.project_id.clone(),
+                            action: crate::app_server::potter::ResolveInterruptAction::Stop,
+                            turn_prompt_override: None,
+                        },
+                        &mut Vec::new(),
+                    )
+                    .await
+                {
+                    let message = format!("{err:#}");
+                    if emitter.fail_fast_with_error(message.clone()).is_err() {
+                        let _ = client.shutdown().await;
+                        return 1;
+                    }
+                    final_outcome = Some(PotterProjectOutcome::Fatal { message });
+                    break;
+                }
+                awaiting_plan_approval = true;
+                final_outcome = Some(PotterProjectOutcome::Interrupted);
+                break;
+            }
             Ok(ExecEventProgress::FailFast { message }) => {
                 should_interrupt_project = true;
                 if emitter.fail_fast_with_error(message.clone()).is_err() {
@@ -192,7 +563,7 @@ pub async fn run_exec_json(
         let _ = client
             .project_interrupt(
                 crate::app_server::potter::ProjectInterruptParams {
-                    project_id: start_response.project_id.clone(),
+                    project_id: summary.project_id.clone(),
                 },
                 &mut Vec::new(),
             )
@@ -202,28 +573,56 @@ pub async fn run_exec_json(
     let final_outcome = final_outcome.unwrap_or_else(|| PotterProjectOutcome::Fatal {
         message: "missing PotterProjectCompleted marker".to_string(),
     });
-    let (final_outcome_json, final_message) = exec_project_outcome(&final_outcome);
+    let (final_outcome_json, final_message) = if awaiting_plan_approval {
+        (
+            crate::exec::PotterProjectCompletedOutcome::AwaitingPlanApproval,
+            Some(String::from(
+                "review or edit the Todo list, then run `codex-potter exec resume` to implement it",
+            )),
+        )
+    } else {
+        exec_project_outcome(&final_outcome)
+    };
 
-    let git_commit_end = crate::workflow::project::resolve_git_commit(&start_response.working_dir);
+    let git_commit_end = crate::workflow::project::resolve_git_commit(
+        summary.worktree.as_deref().unwrap_or(&summary.working_dir),
+    );
+    let output = emitter.take_report();
     let project_completed = crate::exec::ExecJsonlEvent::PotterProjectCompleted(
         crate::exec::PotterProjectCompletedEvent {
             outcome: final_outcome_json.clone(),
             message: final_message.clone(),
             rounds_run,
-            rounds_total: start_response.rounds_total,
+            rounds_total: summary.rounds_total,
             duration_secs: project_started_at.elapsed().as_secs(),
-            progress_file: start_response.progress_file.to_string_lossy().to_string(),
-            git_commit_start: start_response.git_commit_start.clone(),
+            progress_file: summary.progress_file.to_string_lossy().to_string(),
+            git_commit_start: summary.git_commit_start.clone(),
             git_commit_end,
-            git_branch: start_response.git_branch.clone(),
+            git_branch: summary.git_branch.clone(),
+            output: output.clone(),
         },
     );
 
-    if emitter.write_jsonl_event(&project_completed).is_err() {
+    if emitter.write_event(&project_completed).is_err() {
         let _ = client.shutdown().await;
         return 1;
     }
 
+    if let (Some(output_schema), Some(output)) = (output_schema, &output) {
+        let written = match output_schema.write_last_message(output) {
+            Ok(written) => written,
+            Err(err) => {
+                eprintln!("error: {err:#}");
+                let _ = client.shutdown().await;
+                return 1;
+            }
+        };
+        if !written && emitter.write_report(output).is_err() {
+            let _ = client.shutdown().await;
+            return 1;
+        }
+    }
+
     let exit_code = if matches!(
         final_outcome_json,
         crate::exec::PotterProjectCompletedOutcome::Succeeded
@@ -251,10 +650,40 @@ pub fn write_exec_json_preflight_error(message: &str) -> anyhow::Result<()> {
         &mut out,
         &crate::exec::ExecJsonlEvent::Error(crate::exec::ThreadErrorEvent {
             message: message.to_string(),
+            stderr_tail: Vec::new(),
+            stderr_log: None,
         }),
     )
 }
 
+/// Report an `exec` preflight failure in the requested output format.
+///
+/// JSON mode emits a single `error` event to stdout; text mode prints to stderr.
+fn write_exec_preflight_error(
+    output_format: ExecOutputFormat,
+    message: &str,
+) -> anyhow::Result<()> {
+    match output_format {
+        ExecOutputFormat::Json => write_exec_json_preflight_error(message),
+        ExecOutputFormat::Text { .. } => {
+            eprintln!("error: {message}");
+            Ok(())
+        }
+    }
+}
+
+fn write_text_lines<W: Write>(out: &mut W, lines: &[String]) -> anyhow::Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    for line in lines {
+        out.write_all(line.as_bytes())?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
 fn write_jsonl_event<W: Write>(
     out: &mut W,
     event: &crate::exec::ExecJsonlEvent,
@@ -268,26 +697,48 @@ fn write_jsonl_event<W: Write>(
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ExecEventProgress {
     Continue,
-    ProjectCompleted { outcome: PotterProjectOutcome },
-    FailFast { message: String },
+    ProjectCompleted {
+        outcome: PotterProjectOutcome,
+    },
+    /// The planning round finished and the project is paused for review (`--review-plan`).
+    AwaitingPlanApproval,
+    FailFast {
+        message: String,
+    },
+}
+
+enum ExecEmitterFormat {
+    Json,
+    Text(ExecTextRenderer),
 }
 
-struct ExecJsonlEmitter<W: Write> {
+struct ExecEmitter<W: Write> {
     output: W,
+    format: ExecEmitterFormat,
     processor: crate::exec::ExecJsonlEventProcessor,
     json_turn_open: bool,
     round_in_progress: bool,
     rounds_run: u32,
+    /// Output of `PotterProjectReport`, reported with `potter.project.completed`.
+    report: Option<serde_json::Value>,
 }
 
-impl<W: Write> ExecJsonlEmitter<W> {
-    fn new(output: W, workdir: PathBuf) -> Self {
+impl<W: Write> ExecEmitter<W> {
+    fn new(output: W, workdir: PathBuf, output_format: ExecOutputFormat) -> Self {
+        let format = match output_format {
+            ExecOutputFormat::Json => ExecEmitterFormat::Json,
+            ExecOutputFormat::Text { color } => {
+                ExecEmitterFormat::Text(ExecTextRenderer::new(color))
+            }
+        };
         Self {
             output,
+            format,
             processor: crate::exec::ExecJsonlEventProcessor::with_workdir(workdir),
             json_turn_open: false,
             round_in_progress: false,
             rounds_run: 0,
+            report: None,
         }
     }
 
@@ -295,8 +746,30 @@ impl<W: Write> ExecJsonlEmitter<W> {
         self.rounds_run
     }
 
-    fn write_jsonl_event(&mut self, event: &crate::exec::ExecJsonlEvent) -> anyhow::Result<()> {
-        write_jsonl_event(&mut self.output, event).context("write exec jsonl event")?;
+    fn take_report(&mut self) -> Option<serde_json::Value> {
+        self.report.take()
+    }
+
+    /// Print the report after the text output; JSON mode already carries it in
+    /// `potter.project.completed`.
+    fn write_report(&mut self, output: &serde_json::Value) -> anyhow::Result<()> {
+        if matches!(self.format, ExecEmitterFormat::Json) {
+            return Ok(());
+        }
+        let report = serde_json::to_string_pretty(output).context("encode final output")?;
+        write_text_lines(&mut self.output, &[report]).context("write exec text output")
+    }
+
+    fn write_event(&mut self, event: &crate::exec::ExecJsonlEvent) -> anyhow::Result<()> {
+        match &self.format {
+            ExecEmitterFormat::Json => {
+                write_jsonl_event(&mut self.output, event).context("write exec jsonl event")?;
+            }
+            ExecEmitterFormat::Text(renderer) => {
+                write_text_lines(&mut self.output, &renderer.render_event(event))
+                    .context("write exec text output")?;
+            }
+        }
         self.observe_json_turn_state(event);
         Ok(())
     }
@@ -316,6 +789,37 @@ impl<W: Write> ExecJsonlEmitter<W> {
         self.round_in_progress = true;
     }
 
+    /// Emit previously completed rounds as a `potter.replay.*` section.
+    ///
+    /// Replayed rounds are history only: they are not counted in `rounds_run` and never fail fast.
+    fn write_replay_section(
+        &mut self,
+        replay: &crate::app_server::potter::ProjectResumeReplay,
+    ) -> anyhow::Result<()> {
+        let rounds = crate::rounds::usize_to_u32(replay.completed_rounds.len(), "replayed rounds")?;
+        self.write_event(&crate::exec::ExecJsonlEvent::PotterReplayStarted(
+            crate::exec::PotterReplayStartedEvent { rounds },
+        ))?;
+
+        for round in &replay.completed_rounds {
+            for msg in &round.events {
+                if matches!(msg, EventMsg::PotterRoundStarted { .. }) {
+                    self.processor.reset_round_state();
+                    self.json_turn_open = false;
+                }
+                for mapped in self.processor.collect_event(msg) {
+                    self.write_event(&mapped)?;
+                }
+            }
+        }
+
+        self.processor.reset_round_state();
+        self.json_turn_open = false;
+        self.write_event(&crate::exec::ExecJsonlEvent::PotterReplayCompleted(
+            crate::exec::PotterReplayCompletedEvent {},
+        ))
+    }
+
     fn process_event_msg(&mut self, msg: &EventMsg) -> anyhow::Result<ExecEventProgress> {
         match msg {
             EventMsg::RequestUserInput(ev) => {
@@ -337,6 +841,10 @@ impl<W: Write> ExecJsonlEmitter<W> {
                     outcome: outcome.clone(),
                 });
             }
+            EventMsg::PotterProjectReport { output } => {
+                self.report = Some(output.clone());
+                return Ok(ExecEventProgress::Continue);
+            }
             _ => {}
         }
 
@@ -345,7 +853,7 @@ impl<W: Write> ExecJsonlEmitter<W> {
         }
 
         for mapped in self.processor.collect_event(msg) {
-            self.write_jsonl_event(&mapped)?;
+            self.write_event(&mapped)?;
         }
 
         if matches!(msg, EventMsg::PotterRoundFinished { .. }) {
@@ -353,13 +861,19 @@ impl<W: Write> ExecJsonlEmitter<W> {
             self.rounds_run = self.rounds_run.saturating_add(1);
         }
 
+        if matches!(msg, EventMsg::PotterProjectAwaitingPlanApproval { .. }) {
+            return Ok(ExecEventProgress::AwaitingPlanApproval);
+        }
+
         Ok(ExecEventProgress::Continue)
     }
 
     fn fail_fast_with_error(&mut self, message: String) -> anyhow::Result<()> {
-        self.write_jsonl_event(&crate::exec::ExecJsonlEvent::Error(
+        self.write_event(&crate::exec::ExecJsonlEvent::Error(
             crate::exec::ThreadErrorEvent {
                 message: message.clone(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
             },
         ))?;
         self.synthesize_round_fatal_closure(&message)?;
@@ -368,17 +882,19 @@ impl<W: Write> ExecJsonlEmitter<W> {
 
     fn synthesize_round_fatal_closure(&mut self, message: &str) -> anyhow::Result<()> {
         if self.json_turn_open {
-            self.write_jsonl_event(&crate::exec::ExecJsonlEvent::TurnFailed(
+            self.write_event(&crate::exec::ExecJsonlEvent::TurnFailed(
                 crate::exec::TurnFailedEvent {
                     error: crate::exec::ThreadErrorEvent {
                         message: message.to_string(),
+                        stderr_tail: Vec::new(),
+                        stderr_log: None,
                     },
                 },
             ))?;
         }
 
         if self.round_in_progress {
-            self.write_jsonl_event(&crate::exec::ExecJsonlEvent::PotterRoundCompleted(
+            self.write_event(&crate::exec::ExecJsonlEvent::PotterRoundCompleted(
                 crate::exec::PotterRoundCompletedEvent {
                     outcome: crate::exec::PotterRoundCompletedOutcome::Fatal,
                     message: Some(message.to_string()),
@@ -407,6 +923,18 @@ fn exec_project_outcome(
             crate::exec::PotterProjectCompletedOutcome::BudgetExhausted,
             None,
         ),
+        PotterProjectOutcome::Stalled { rounds } => (
+            crate::exec::PotterProjectCompletedOutcome::Stalled,
+            Some(crate::workflow::stall::describe_stall(*rounds)),
+        ),
+        PotterProjectOutcome::TokenBudgetExhausted { .. } => (
+            crate::exec::PotterProjectCompletedOutcome::TokenBudgetExhausted,
+            crate::workflow::budget::describe_budget_exhaustion(outcome),
+        ),
+        PotterProjectOutcome::TimeBudgetExhausted { .. } => (
+            crate::exec::PotterProjectCompletedOutcome::TimeBudgetExhausted,
+            crate::workflow::budget::describe_budget_exhaustion(outcome),
+        ),
         PotterProjectOutcome::TaskFailed { message } => (
             crate::exec::PotterProjectCompletedOutcome::TaskFailed,
             Some(message.clone()),
@@ -417,3 +945,165 @@ fn exec_project_outcome(
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::PotterRoundKind;
+    use codex_protocol::protocol::PotterRoundOutcome;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+
+    fn parse_jsonl(output: &[u8]) -> Vec<crate::exec::ExecJsonlEvent> {
+        String::from_utf8_lossy(output)
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("parse jsonl event"))
+            .collect()
+    }
+
+    #[test]
+    fn exec_project_outcome_reports_stalled_with_reason() {
+        assert_eq!(
+            exec_project_outcome(&PotterProjectOutcome::Stalled { rounds: 3 }),
+            (
+                crate::exec::PotterProjectCompletedOutcome::Stalled,
+                Some("no git changes in the last 3 rounds".to_string()),
+            )
+        );
+        assert_eq!(
+            serde_json::to_value(crate::exec::PotterProjectCompletedOutcome::Stalled)
+                .expect("serialize outcome"),
+            serde_json::json!("stalled")
+        );
+    }
+
+    #[test]
+    fn exec_project_outcome_reports_budget_exhaustion() {
+        assert_eq!(
+            exec_project_outcome(&PotterProjectOutcome::TokenBudgetExhausted {
+                tokens_used: 1_050_000,
+                max_tokens: 1_000_000,
+            }),
+            (
+                crate::exec::PotterProjectCompletedOutcome::TokenBudgetExhausted,
+                Some("used 1050000 tokens (--max-tokens 1000000)".to_string()),
+            )
+        );
+        assert_eq!(
+            exec_project_outcome(&PotterProjectOutcome::TimeBudgetExhausted {
+                elapsed_secs: 5410,
+                max_duration_secs: 5400,
+            }),
+            (
+                crate::exec::PotterProjectCompletedOutcome::TimeBudgetExhausted,
+                Some("ran for 1h30m10s (--max-duration 1h30m)".to_string()),
+            )
+        );
+        assert_eq!(
+            serde_json::to_value(crate::exec::PotterProjectCompletedOutcome::TimeBudgetExhausted)
+                .expect("serialize outcome"),
+            serde_json::json!("time_budget_exhausted")
+        );
+    }
+
+    #[test]
+    fn awaiting_plan_approval_marker_is_emitted_and_pauses_the_stream() {
+        let mut emitter = ExecEmitter::new(
+            Vec::new(),
+            PathBuf::from("/tmp/project"),
+            ExecOutputFormat::Json,
+        );
+
+        let progress = emitter
+            .process_event_msg(&EventMsg::PotterProjectAwaitingPlanApproval {
+                project_id: "project_1".to_string(),
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/03/06/1/MAIN.md"),
+                todo: vec!["Add flag".to_string(), "Write docs".to_string()],
+            })
+            .expect("process event");
+
+        assert_eq!(progress, ExecEventProgress::AwaitingPlanApproval);
+        assert_eq!(
+            parse_jsonl(&emitter.output),
+            vec![ExecJsonlEvent::PotterProjectAwaitingPlanApproval(
+                PotterProjectAwaitingPlanApprovalEvent {
+                    progress_file: "/tmp/project/.codexpotter/projects/2026/03/06/1/MAIN.md"
+                        .to_string(),
+                    todo: vec!["Add flag".to_string(), "Write docs".to_string()],
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn replay_section_wraps_recorded_rounds_without_counting_them() {
+        let replay = crate::app_server::potter::ProjectResumeReplay {
+            completed_rounds: vec![crate::app_server::potter::ProjectResumeReplayRound {
+                outcome: PotterRoundOutcome::Completed,
+                events: vec![
+                    EventMsg::PotterRoundStarted {
+                        current: 1,
+                        total: 3,
+                        kind: PotterRoundKind::Implement,
+                    },
+                    EventMsg::TurnStarted(TurnStartedEvent {
+                        turn_id: "turn-1".to_string(),
+                        model_context_window: None,
+                    }),
+                    EventMsg::AgentMessage(AgentMessageEvent {
+                        message: "done".to_string(),
+                        phase: None,
+                    }),
+                    EventMsg::TurnComplete(TurnCompleteEvent {
+                        turn_id: "turn-1".to_string(),
+                        last_agent_message: None,
+                    }),
+                    EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::Completed,
+                    },
+                ],
+            }],
+        };
+
+        let mut emitter = ExecEmitter::new(
+            Vec::new(),
+            PathBuf::from("/tmp/project"),
+            ExecOutputFormat::Json,
+        );
+        emitter
+            .write_replay_section(&replay)
+            .expect("write replay section");
+        assert_eq!(emitter.rounds_run(), 0);
+
+        assert_eq!(
+            parse_jsonl(&emitter.output),
+            vec![
+                ExecJsonlEvent::PotterReplayStarted(PotterReplayStartedEvent { rounds: 1 }),
+                ExecJsonlEvent::PotterRoundStarted(PotterRoundStartedEvent {
+                    current: 1,
+                    total: 3,
+                    kind: PotterRoundKind::Implement,
+                }),
+                ExecJsonlEvent::TurnStarted(crate::exec::TurnStartedEvent {}),
+                ExecJsonlEvent::ItemCompleted(ItemCompletedEvent {
+                    item: ThreadItem {
+                        id: "item_0".to_string(),
+                        details: ThreadItemDetails::AgentMessage(AgentMessageItem {
+                            text: "done".to_string(),
+                        }),
+                    },
+                }),
+                ExecJsonlEvent::TurnCompleted(TurnCompletedEvent {
+                    usage: Usage::default(),
+                }),
+                ExecJsonlEvent::PotterRoundCompleted(PotterRoundCompletedEvent {
+                    outcome: PotterRoundCompletedOutcome::Completed,
+                    message: None,
+                }),
+                ExecJsonlEvent::PotterReplayCompleted(PotterReplayCompletedEvent {}),
+            ]
+        );
+    }
+}
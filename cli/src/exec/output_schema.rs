@@ -0,0 +1,113 @@
+//! Structured final output (`exec --output-schema`).
+//!
+//! The schema is sent with `project/start` (or `project/start_rounds`); once the project succeeds
+//! the app-server runs a read-only report turn constrained to it (see
+//! [`crate::workflow::report`]). The validated report is included as `output` in
+//! `potter.project.completed` and written to `--output-last-message`, or printed after the text
+//! output when no file is given.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde_json::Value;
+
+/// JSON Schema loaded from `--output-schema`, plus where to write the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecOutputSchema {
+    pub schema: Value,
+    /// `--output-last-message`: file the report is written to.
+    pub last_message_file: Option<PathBuf>,
+}
+
+impl ExecOutputSchema {
+    pub fn load(path: &Path, last_message_file: Option<PathBuf>) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("read output schema {}", path.display()))?;
+        let schema = Self::parse(&contents)
+            .with_context(|| format!("parse output schema {}", path.display()))?;
+        Ok(Self {
+            schema,
+            last_message_file,
+        })
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<Value> {
+        let schema: Value = serde_json::from_str(contents).context("expected a JSON Schema")?;
+        anyhow::ensure!(schema.is_object(), "expected a JSON Schema object");
+        crate::workflow::report::check_supported_schema(&schema).map_err(anyhow::Error::msg)?;
+        Ok(schema)
+    }
+
+    /// Write `output` to `--output-last-message`. Returns `false` when no file was given.
+    pub fn write_last_message(&self, output: &Value) -> anyhow::Result<bool> {
+        let Some(path) = &self.last_message_file else {
+            return Ok(false);
+        };
+        let mut contents = serde_json::to_string_pretty(output).context("encode final output")?;
+        contents.push('\n');
+        std::fs::write(path, contents)
+            .with_context(|| format!("write final output {}", path.display()))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn schema_must_be_a_json_object() {
+        assert_eq!(
+            ExecOutputSchema::parse(r#"{ "type": "object" }"#).expect("parse schema"),
+            json!({ "type": "object" })
+        );
+
+        let err = ExecOutputSchema::parse("[]").expect_err("array schema");
+        assert_eq!(format!("{err:#}"), "expected a JSON Schema object");
+        let err = ExecOutputSchema::parse("type: object").expect_err("yaml schema");
+        assert!(
+            format!("{err:#}").starts_with("expected a JSON Schema: "),
+            "unexpected error: {err:#}"
+        );
+    }
+
+    #[test]
+    fn schema_with_unsupported_keyword_is_rejected() {
+        let err = ExecOutputSchema::parse(r#"{ "type": "string", "pattern": "^v[0-9]+$" }"#)
+            .expect_err("unsupported keyword");
+        assert!(
+            format!("{err:#}").starts_with("at /: unsupported keyword `pattern`"),
+            "unexpected error: {err:#}"
+        );
+    }
+
+    #[test]
+    fn report_is_written_to_the_last_message_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("report.json");
+        let output = json!({ "summary": "Added the flag" });
+
+        let without_file = ExecOutputSchema {
+            schema: json!({}),
+            last_message_file: None,
+        };
+        assert!(
+            !without_file
+                .write_last_message(&output)
+                .expect("no file to write")
+        );
+
+        let with_file = ExecOutputSchema {
+            schema: json!({}),
+            last_message_file: Some(path.clone()),
+        };
+        assert!(with_file.write_last_message(&output).expect("write file"));
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read report"),
+            "{\n  \"summary\": \"Added the flag\"\n}\n"
+        );
+    }
+}
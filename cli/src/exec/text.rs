@@ -0,0 +1,554 @@
+//! Human-readable `exec` output.
+//!
+//! [`ExecTextRenderer`] turns [`ExecJsonlEvent`] values into line-oriented plain text suitable for
+//! CI logs and terminal multiplexers. It consumes the exact same events as `exec --json`, so both
+//! output modes share the [`crate::exec::ExecJsonlEventProcessor`] item lifecycle.
+//!
+//! Rendering rules:
+//! - only completed / terminal states are printed for items with a lifecycle, except command
+//!   executions (the command line is printed when it starts) and TODO lists (printed on every
+//!   update)
+//! - reasoning items are skipped, matching the interactive TUI
+//! - colour is plain ANSI SGR and can be disabled entirely
+
+use std::io::IsTerminal as _;
+use std::time::Duration;
+
+use codex_protocol::protocol::PotterApprovalKind;
+
+use crate::exec::CollabTool;
+use crate::exec::CollabToolCallStatus;
+use crate::exec::CommandExecutionStatus;
+use crate::exec::ExecJsonlEvent;
+use crate::exec::PatchApplyStatus;
+use crate::exec::PatchChangeKind;
+use crate::exec::PotterProjectCompletedOutcome;
+use crate::exec::PotterRoundCompletedOutcome;
+use crate::exec::ThreadItem;
+use crate::exec::ThreadItemDetails;
+use crate::exec::TodoItem;
+
+/// Maximum number of trailing command output lines to print per command.
+const COMMAND_OUTPUT_MAX_LINES: usize = 20;
+
+const SGR_BOLD: &str = "1";
+const SGR_DIM: &str = "2";
+const SGR_RED: &str = "31";
+const SGR_GREEN: &str = "32";
+const SGR_YELLOW: &str = "33";
+const SGR_CYAN: &str = "36";
+
+/// Whether text output on stdout should be coloured.
+///
+/// Colour is enabled only when stdout is a terminal and `NO_COLOR` is unset or empty
+/// (<https://no-color.org>).
+pub fn stdout_supports_color() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && std::io::stdout().is_terminal()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecTextRenderer {
+    color: bool,
+}
+
+impl ExecTextRenderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Render a single event into zero or more output lines (without trailing newlines).
+    pub fn render_event(&self, event: &ExecJsonlEvent) -> Vec<String> {
+        match event {
+            ExecJsonlEvent::ThreadStarted(_)
+            | ExecJsonlEvent::TurnStarted(_)
+            | ExecJsonlEvent::ItemUpdated(_) => Vec::new(),
+            ExecJsonlEvent::TurnCompleted(ev) => vec![self.paint(
+                SGR_DIM,
+                &format!(
+                    "tokens: {} input ({} cached), {} output",
+                    ev.usage.input_tokens, ev.usage.cached_input_tokens, ev.usage.output_tokens
+                ),
+            )],
+            ExecJsonlEvent::TurnFailed(ev) => {
+                vec![self.paint(SGR_RED, &format!("turn failed: {}", ev.error.message))]
+            }
+            ExecJsonlEvent::ItemStarted(ev) => self.render_item_started(&ev.item),
+            ExecJsonlEvent::ItemCompleted(ev) => self.render_item_completed(&ev.item),
+            ExecJsonlEvent::Error(ev) => {
+                let mut lines = vec![self.paint(SGR_RED, &format!("error: {}", ev.message))];
+                if !ev.stderr_tail.is_empty() {
+                    lines.push(self.paint(SGR_DIM, "  app-server stderr:"));
+                    lines.extend(
+                        ev.stderr_tail
+                            .iter()
+                            .map(|line| self.paint(SGR_DIM, &format!("    {line}"))),
+                    );
+                }
+                if let Some(stderr_log) = &ev.stderr_log {
+                    lines.push(self.paint(
+                        SGR_DIM,
+                        &format!("  full stderr log: {}", stderr_log.display()),
+                    ));
+                }
+                lines
+            }
+            ExecJsonlEvent::PotterProjectStarted(ev) => {
+                let mut lines = vec![
+                    self.paint(SGR_BOLD, "Project started"),
+                    format!("  progress file: {}", ev.progress_file),
+                    format!("  working dir:   {}", ev.working_dir),
+                ];
+                if let Some(branch) = &ev.git_branch {
+                    lines.push(format!("  git branch:    {branch}"));
+                }
+                if let Some(worktree) = &ev.worktree {
+                    lines.push(format!("  worktree:      {worktree}"));
+                }
+                lines
+            }
+            ExecJsonlEvent::PotterProjectResumed(ev) => {
+                let mut lines = vec![
+                    self.paint(SGR_BOLD, "Project resumed"),
+                    format!("  progress file: {}", ev.progress_file),
+                    format!("  working dir:   {}", ev.working_dir),
+                ];
+                if let Some(branch) = &ev.git_branch {
+                    lines.push(format!("  git branch:    {branch}"));
+                }
+                if let Some(worktree) = &ev.worktree {
+                    lines.push(format!("  worktree:      {worktree}"));
+                }
+                lines
+            }
+            ExecJsonlEvent::PotterReplayStarted(ev) => {
+                let rounds_label = if ev.rounds == 1 { "round" } else { "rounds" };
+                vec![self.paint(
+                    SGR_DIM,
+                    &format!("── Replay of {} recorded {rounds_label} ──", ev.rounds),
+                )]
+            }
+            ExecJsonlEvent::PotterReplayCompleted(_) => {
+                vec![self.paint(SGR_DIM, "── End of replay ──")]
+            }
+            ExecJsonlEvent::PotterRoundStarted(ev) => vec![
+                String::new(),
+                self.paint(
+                    SGR_BOLD,
+                    &format!("━━ {} ━━", ev.kind.round_prefix(ev.current, ev.total)),
+                ),
+            ],
+            ExecJsonlEvent::PotterRoundVerification(ev) => {
+                if ev.passed {
+                    return vec![
+                        self.paint(SGR_GREEN, &format!("Verification passed: {}", ev.command)),
+                    ];
+                }
+                let exit = match ev.exit_code {
+                    Some(code) => format!("exit {code}"),
+                    None => "no exit code".to_string(),
+                };
+                let mut lines = vec![self.paint(
+                    SGR_RED,
+                    &format!("Verification failed ({exit}): {}", ev.command),
+                )];
+                lines.extend(
+                    tail_lines(&ev.output, COMMAND_OUTPUT_MAX_LINES)
+                        .into_iter()
+                        .map(|line| self.paint(SGR_DIM, &format!("  {line}"))),
+                );
+                lines
+            }
+            ExecJsonlEvent::PotterApprovalDecision(ev) => {
+                let subject = match ev.kind {
+                    PotterApprovalKind::Command => "command",
+                    PotterApprovalKind::FileChange => "file change",
+                };
+                if ev.approved {
+                    vec![self.paint(
+                        SGR_DIM,
+                        &format!("Approved {subject}: {} ({})", ev.summary, ev.reason),
+                    )]
+                } else {
+                    vec![self.paint(
+                        SGR_RED,
+                        &format!("Declined {subject}: {} ({})", ev.summary, ev.reason),
+                    )]
+                }
+            }
+            ExecJsonlEvent::PotterUserInputAnswered(ev) => {
+                if ev.declined {
+                    let from = ev
+                        .server_name
+                        .as_ref()
+                        .map(|server_name| format!(" from {server_name}"))
+                        .unwrap_or_default();
+                    vec![self.paint(SGR_YELLOW, &format!("Declined user input request{from}"))]
+                } else {
+                    ev.answers
+                        .iter()
+                        .map(|answer| {
+                            self.paint(
+                                SGR_DIM,
+                                &format!(
+                                    "Answered {}: {}",
+                                    answer.header,
+                                    answer.answers.join(", ")
+                                ),
+                            )
+                        })
+                        .collect()
+                }
+            }
+            ExecJsonlEvent::PotterRoundCompleted(ev) => {
+                let (code, label) = match ev.outcome {
+                    PotterRoundCompletedOutcome::Completed => (SGR_DIM, "Round completed"),
+                    PotterRoundCompletedOutcome::TaskFailed => (SGR_RED, "Round task failed"),
+                    PotterRoundCompletedOutcome::Fatal => (SGR_RED, "Round failed"),
+                };
+                let text = match &ev.message {
+                    Some(message) => format!("{label}: {message}"),
+                    None => label.to_string(),
+                };
+                vec![self.paint(code, &text)]
+            }
+            ExecJsonlEvent::PotterProjectAwaitingPlanApproval(ev) => {
+                let mut lines = vec![
+                    String::new(),
+                    self.paint(
+                        SGR_BOLD,
+                        &format!("Plan ready for review: {}", ev.progress_file),
+                    ),
+                ];
+                if ev.todo.is_empty() {
+                    lines.push(self.paint(SGR_DIM, "  (no Todo items)"));
+                }
+                lines.extend(
+                    ev.todo
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, item)| format!("  {}. {item}", idx + 1)),
+                );
+                lines
+            }
+            ExecJsonlEvent::PotterProjectSucceeded(ev) => vec![self.paint(
+                SGR_GREEN,
+                &format!(
+                    "Project succeeded after {} rounds ({}s)",
+                    ev.rounds, ev.duration_secs
+                ),
+            )],
+            ExecJsonlEvent::PotterProjectCompleted(ev) => {
+                let (code, label) = match ev.outcome {
+                    PotterProjectCompletedOutcome::Succeeded => (SGR_GREEN, "succeeded"),
+                    PotterProjectCompletedOutcome::BudgetExhausted => {
+                        (SGR_YELLOW, "budget exhausted")
+                    }
+                    PotterProjectCompletedOutcome::Stalled => (SGR_YELLOW, "stalled"),
+                    PotterProjectCompletedOutcome::TokenBudgetExhausted => {
+                        (SGR_YELLOW, "token budget exhausted")
+                    }
+                    PotterProjectCompletedOutcome::TimeBudgetExhausted => {
+                        (SGR_YELLOW, "time budget exhausted")
+                    }
+                    PotterProjectCompletedOutcome::AwaitingPlanApproval => {
+                        (SGR_YELLOW, "awaiting plan approval")
+                    }
+                    PotterProjectCompletedOutcome::TaskFailed => (SGR_RED, "task failed"),
+                    PotterProjectCompletedOutcome::Fatal => (SGR_RED, "failed"),
+                };
+                let headline = match &ev.message {
+                    Some(message) => format!("Project {label}: {message}"),
+                    None => format!("Project {label}"),
+                };
+                vec![
+                    String::new(),
+                    self.paint(code, &headline),
+                    format!(
+                        "  rounds:        {}/{} in {}s",
+                        ev.rounds_run, ev.rounds_total, ev.duration_secs
+                    ),
+                    format!("  progress file: {}", ev.progress_file),
+                    format!(
+                        "  git:           {} -> {}",
+                        short_commit(&ev.git_commit_start),
+                        short_commit(&ev.git_commit_end)
+                    ),
+                ]
+            }
+            ExecJsonlEvent::PotterStreamRecoveryUpdate(ev) => {
+                let backoff =
+                    crate::workflow::budget::format_duration(Duration::from_millis(ev.backoff_ms));
+                let message = if ev.waits_for_usage_limit_reset {
+                    format!(
+                        "usage limit reached, retrying ({}/{}) after the reset in {backoff}: {}",
+                        ev.attempt, ev.max_attempts, ev.error_message
+                    )
+                } else {
+                    format!(
+                        "stream error, retrying ({}/{}) in {backoff}: {}",
+                        ev.attempt, ev.max_attempts, ev.error_message
+                    )
+                };
+                vec![self.paint(SGR_YELLOW, &message)]
+            }
+            ExecJsonlEvent::PotterStreamRecoveryRecovered(_) => {
+                vec![self.paint(SGR_DIM, "stream recovered")]
+            }
+            ExecJsonlEvent::PotterStreamRecoveryGaveUp(ev) => vec![self.paint(
+                SGR_RED,
+                &format!(
+                    "stream recovery gave up after {}/{} attempts: {}",
+                    ev.attempts, ev.max_attempts, ev.error_message
+                ),
+            )],
+            ExecJsonlEvent::PotterRateLimitWait(ev) => {
+                let window = ev
+                    .window_minutes
+                    .and_then(|minutes| u64::try_from(minutes).ok())
+                    .map(|minutes| {
+                        format!(
+                            " ({} window)",
+                            crate::workflow::budget::format_duration(Duration::from_secs(
+                                minutes * 60
+                            ))
+                        )
+                    })
+                    .unwrap_or_default();
+                vec![self.paint(
+                    SGR_YELLOW,
+                    &format!(
+                        "rate limit {:.0}% used{window}, waiting {} for the reset",
+                        ev.used_percent,
+                        crate::workflow::budget::format_duration(Duration::from_secs(
+                            ev.resets_in_secs
+                        ))
+                    ),
+                )]
+            }
+        }
+    }
+
+    fn render_item_started(&self, item: &ThreadItem) -> Vec<String> {
+        match &item.details {
+            ThreadItemDetails::CommandExecution(command) => {
+                vec![self.paint(SGR_CYAN, &format!("$ {}", command.command))]
+            }
+            ThreadItemDetails::TodoList(todo) => self.render_todo_list(&todo.items),
+            _ => Vec::new(),
+        }
+    }
+
+    fn render_item_completed(&self, item: &ThreadItem) -> Vec<String> {
+        match &item.details {
+            ThreadItemDetails::AgentMessage(message) => {
+                let mut lines = vec![String::new(), self.paint(SGR_BOLD, "codex")];
+                lines.extend(message.text.lines().map(str::to_string));
+                lines.push(String::new());
+                lines
+            }
+            ThreadItemDetails::Reasoning(_) => Vec::new(),
+            ThreadItemDetails::CommandExecution(command) => {
+                let mut lines = tail_lines(&command.aggregated_output, COMMAND_OUTPUT_MAX_LINES)
+                    .into_iter()
+                    .map(|line| self.paint(SGR_DIM, &format!("  {line}")))
+                    .collect::<Vec<_>>();
+                let status = match (&command.status, command.exit_code) {
+                    (CommandExecutionStatus::Completed, _) => self.paint(SGR_GREEN, "  ✓ exited 0"),
+                    (CommandExecutionStatus::Failed, Some(code)) => {
+                        self.paint(SGR_RED, &format!("  ✗ exited {code}"))
+                    }
+                    (CommandExecutionStatus::Failed, None) => self.paint(SGR_RED, "  ✗ failed"),
+                    (CommandExecutionStatus::Declined, _) => self.paint(SGR_YELLOW, "  declined"),
+                    (CommandExecutionStatus::InProgress, _) => {
+                        self.paint(SGR_DIM, "  (still running)")
+                    }
+                };
+                lines.push(status);
+                lines
+            }
+            ThreadItemDetails::FileChange(change) => {
+                let headline = match change.status {
+                    PatchApplyStatus::Completed => self.paint(SGR_BOLD, "patch applied"),
+                    PatchApplyStatus::Failed => self.paint(SGR_RED, "patch failed"),
+                    PatchApplyStatus::InProgress => self.paint(SGR_DIM, "patch in progress"),
+                };
+                let mut lines = vec![headline];
+                lines.extend(change.changes.iter().map(|change| {
+                    let marker = match change.kind {
+                        PatchChangeKind::Add => "A",
+                        PatchChangeKind::Delete => "D",
+                        PatchChangeKind::Update => "M",
+                    };
+                    format!("  {marker} {}", change.path)
+                }));
+                lines
+            }
+            ThreadItemDetails::CollabToolCall(call) => {
+                let tool = match call.tool {
+                    CollabTool::SpawnAgent => "spawn agent",
+                    CollabTool::SendInput => "send input",
+                    CollabTool::Wait => "wait",
+                    CollabTool::CloseAgent => "close agent",
+                };
+                let line = match call.status {
+                    CollabToolCallStatus::Failed => {
+                        self.paint(SGR_RED, &format!("collab {tool} failed"))
+                    }
+                    CollabToolCallStatus::Completed | CollabToolCallStatus::InProgress => {
+                        self.paint(SGR_DIM, &format!("collab {tool}"))
+                    }
+                };
+                vec![line]
+            }
+            ThreadItemDetails::WebSearch(search) => {
+                vec![self.paint(SGR_DIM, &format!("web search: {}", search.query))]
+            }
+            ThreadItemDetails::TodoList(_) => Vec::new(),
+            ThreadItemDetails::Error(error) => {
+                vec![self.paint(SGR_YELLOW, &format!("warning: {}", error.message))]
+            }
+        }
+    }
+
+    fn render_todo_list(&self, items: &[TodoItem]) -> Vec<String> {
+        let mut lines = vec![self.paint(SGR_BOLD, "plan")];
+        lines.extend(items.iter().map(|item| {
+            if item.completed {
+                self.paint(SGR_DIM, &format!("  [x] {}", item.text))
+            } else {
+                format!("  [ ] {}", item.text)
+            }
+        }));
+        lines
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\u{1b}[{code}m{text}\u{1b}[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn tail_lines(text: &str, max_lines: usize) -> Vec<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.len() <= max_lines {
+        return lines.into_iter().map(str::to_string).collect();
+    }
+
+    let omitted = lines.len() - max_lines;
+    let mut out = vec![format!("… +{omitted} lines")];
+    out.extend(lines[omitted..].iter().map(|line| (*line).to_string()));
+    out
+}
+
+fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::CommandExecutionItem;
+    use crate::exec::FileChangeItem;
+    use crate::exec::FileUpdateChange;
+    use crate::exec::ItemCompletedEvent;
+    use crate::exec::PotterRoundStartedEvent;
+    use codex_protocol::protocol::PotterRoundKind;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_started_renders_plain_header_without_color() {
+        let renderer = ExecTextRenderer::new(false);
+        assert_eq!(
+            renderer.render_event(&ExecJsonlEvent::PotterRoundStarted(
+                PotterRoundStartedEvent {
+                    current: 2,
+                    total: 10,
+                    kind: PotterRoundKind::Implement,
+                }
+            )),
+            vec![String::new(), "━━ Round 2/10 ━━".to_string()]
+        );
+        assert_eq!(
+            renderer.render_event(&ExecJsonlEvent::PotterRoundStarted(
+                PotterRoundStartedEvent {
+                    current: 3,
+                    total: 10,
+                    kind: PotterRoundKind::Review,
+                }
+            )),
+            vec![String::new(), "━━ Round 3/10 (review) ━━".to_string()]
+        );
+    }
+
+    #[test]
+    fn color_wraps_lines_in_sgr_sequences() {
+        let renderer = ExecTextRenderer::new(true);
+        assert_eq!(
+            renderer.render_event(&ExecJsonlEvent::Error(crate::exec::ThreadErrorEvent {
+                message: "boom".to_string(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
+            })),
+            vec!["\u{1b}[31merror: boom\u{1b}[0m".to_string()]
+        );
+    }
+
+    #[test]
+    fn failed_command_prints_tail_of_output_and_exit_code() {
+        let renderer = ExecTextRenderer::new(false);
+        let output = (1..=22)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = renderer.render_event(&ExecJsonlEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                    command: "cargo test".to_string(),
+                    aggregated_output: output,
+                    exit_code: Some(101),
+                    status: CommandExecutionStatus::Failed,
+                }),
+            },
+        }));
+
+        assert_eq!(lines.len(), 22);
+        assert_eq!(lines[0], "  … +2 lines");
+        assert_eq!(lines[1], "  line 3");
+        assert_eq!(lines[21], "  ✗ exited 101");
+    }
+
+    #[test]
+    fn file_change_lists_paths_with_change_markers() {
+        let renderer = ExecTextRenderer::new(false);
+        assert_eq!(
+            renderer.render_event(&ExecJsonlEvent::ItemCompleted(ItemCompletedEvent {
+                item: ThreadItem {
+                    id: "item_1".to_string(),
+                    details: ThreadItemDetails::FileChange(FileChangeItem {
+                        changes: vec![
+                            FileUpdateChange {
+                                path: "src/lib.rs".to_string(),
+                                kind: PatchChangeKind::Update,
+                            },
+                            FileUpdateChange {
+                                path: "src/new.rs".to_string(),
+                                kind: PatchChangeKind::Add,
+                            },
+                        ],
+                        status: PatchApplyStatus::Completed,
+                    }),
+                },
+            })),
+            vec![
+                "patch applied".to_string(),
+                "  M src/lib.rs".to_string(),
+                "  A src/new.rs".to_string(),
+            ]
+        );
+    }
+}
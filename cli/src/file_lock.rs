@@ -0,0 +1,68 @@
+//! Advisory file locks shared between CodexPotter processes.
+//!
+//! Locks are taken on a dedicated lock file rather than on the data they protect, because data
+//! files are replaced by [`crate::atomic_write::write_atomic_text`]. The operating system releases
+//! a lock when its [`FileLock`] is dropped or the holding process exits, so a crash never leaves a
+//! stale lock behind.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
+use std::path::Path;
+
+use anyhow::Context;
+
+/// An exclusive lock on a lock file, released on drop.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+fn open_lock_file(path: &Path) -> anyhow::Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("open {}", path.display()))
+}
+
+/// Wait until the exclusive lock on `path` is acquired, creating the lock file when missing.
+pub fn lock_exclusive(path: &Path) -> anyhow::Result<FileLock> {
+    let file = open_lock_file(path)?;
+    file.lock()
+        .with_context(|| format!("lock {}", path.display()))?;
+    Ok(FileLock { _file: file })
+}
+
+/// Acquire the exclusive lock on `path` without waiting; `None` when someone else holds it.
+pub fn try_lock_exclusive(path: &Path) -> anyhow::Result<Option<FileLock>> {
+    let file = open_lock_file(path)?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(FileLock { _file: file })),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(err)) => {
+            Err(anyhow::Error::new(err).context(format!("lock {}", path.display())))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("data.lock");
+
+        let held = lock_exclusive(&path).expect("lock");
+        assert!(try_lock_exclusive(&path).expect("try lock").is_none());
+
+        drop(held);
+        assert!(try_lock_exclusive(&path).expect("try lock").is_some());
+    }
+}
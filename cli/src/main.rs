@@ -6,8 +6,8 @@
 //!   provides the long-lived `codex-potter app-server` implementation (project control plane).
 //! - `workflow`: Orchestrates CodexPotter projects/rounds, persists `potter-rollout.jsonl`, and
 //!   supports `resume` by replaying recorded events.
-//! - `exec`: Runs CodexPotter non-interactively and emits a machine-readable JSONL stream
-//!   (`codex-potter exec --json`).
+//! - `exec`: Runs CodexPotter non-interactively and prints either human-readable text or a
+//!   machine-readable JSONL stream (`codex-potter exec --json`).
 //!
 //! Interactive mode (default) uses the `codex-tui` crate for rendering; the TUI is kept as a pure
 //! renderer that is driven by the `EventMsg` stream from the app-server.
@@ -16,7 +16,9 @@ mod app_server;
 mod atomic_write;
 mod codex_compat;
 mod config;
+mod daemon;
 mod exec;
+mod file_lock;
 mod global_gitignore;
 mod path_utils;
 mod rounds;
@@ -94,9 +96,16 @@ struct Cli {
     )]
     dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Start even when the `codex` binary is older than the versions CodexPotter supports.
+    #[arg(long, env = "CODEX_POTTER_ALLOW_UNSUPPORTED_CODEX", global = true)]
+    allow_unsupported_codex: bool,
+
     #[clap(flatten)]
     upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
 
+    #[clap(flatten)]
+    project_cli_args: crate::app_server::potter::PotterProjectCliArgs,
+
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -108,30 +117,201 @@ enum CliCommand {
         /// Project path to resolve to a unique `MAIN.md`. If omitted, open a picker UI.
         project_path: Option<PathBuf>,
     },
-    /// Run CodexPotter non-interactively and emit a machine-readable JSONL event stream.
+    /// Restore the workspace to a git checkpoint recorded at a round boundary.
+    Rollback {
+        /// Project path to resolve to a unique `MAIN.md` (same forms as `resume`).
+        project_path: PathBuf,
+        /// Round whose checkpoint should be restored, counted across all sessions of the project
+        /// (the `round-<N>` of its checkpoint refs).
+        #[arg(long, value_name = "N")]
+        to_round: u32,
+        /// Restore the state at the end (default) or at the start of the round.
+        #[arg(long, value_enum, default_value_t = CliRollbackPoint::End)]
+        at: CliRollbackPoint,
+    },
+    /// Run CodexPotter non-interactively and print progress as text (or JSONL with `--json`).
     Exec {
-        /// Prompt to run. If omitted, read from stdin.
+        #[command(subcommand)]
+        command: Option<ExecCommand>,
+        /// Prompt to run. If omitted, read from stdin. A prompt that is exactly `resume` must
+        /// follow `--`.
         prompt: Option<String>,
-        /// Emit a strict JSONL event stream to stdout.
-        #[arg(long)]
+        /// Emit a strict JSONL event stream to stdout instead of human-readable text.
+        #[arg(long, global = true)]
         json: bool,
+        /// JSON object of canned answers (question id → string or list of strings) for questions
+        /// from the agent and MCP elicitations. Requests without a matching answer are declined.
+        #[arg(long, global = true, value_name = "PATH")]
+        answers_file: Option<PathBuf>,
+        /// JSON Schema file for a final report: once the project succeeds, a read-only turn
+        /// answers with JSON matching it, included as `output` in `potter.project.completed`.
+        #[arg(long, global = true, value_name = "FILE")]
+        output_schema: Option<PathBuf>,
+        /// Write the final report to this file instead of printing it after the text output.
+        #[arg(long, global = true, value_name = "FILE", requires = "output_schema")]
+        output_last_message: Option<PathBuf>,
     },
     /// Run a long-lived JSON-RPC app-server that encapsulates CodexPotter project logic.
     ///
     /// This is primarily intended for internal use.
-    AppServer,
+    AppServer {
+        /// Accept client connections on `unix://PATH` or `tcp://IP:PORT` instead of serving the
+        /// parent process over stdin/stdout.
+        #[arg(
+            long,
+            value_name = "URL",
+            value_parser = crate::app_server::potter::transport::parse_listen_address
+        )]
+        listen: Option<crate::app_server::potter::PotterAppServerListenAddress>,
+        /// Token clients must pass as `token` in their `initialize` params (required for TCP).
+        #[arg(
+            long,
+            env = "CODEX_POTTER_LISTEN_TOKEN",
+            value_name = "TOKEN",
+            requires = "listen",
+            hide_env_values = true
+        )]
+        listen_token: Option<String>,
+    },
+    /// Render a project running on a listening app-server (`app-server --listen`). Leaving the UI
+    /// detaches; the project keeps running.
+    Attach {
+        /// Address of the app-server: `unix://PATH` or `tcp://IP:PORT`.
+        #[arg(
+            long,
+            value_name = "URL",
+            value_parser = crate::app_server::potter::transport::parse_listen_address
+        )]
+        connect: crate::app_server::potter::PotterAppServerListenAddress,
+        /// The app-server's `--listen-token`.
+        #[arg(
+            long,
+            env = "CODEX_POTTER_LISTEN_TOKEN",
+            value_name = "TOKEN",
+            hide_env_values = true
+        )]
+        token: Option<String>,
+        /// Project path to resolve to a unique `MAIN.md` (same forms as `resume`). May be omitted
+        /// when a single project is running.
+        project_path: Option<PathBuf>,
+    },
+    /// Start a project owned by an app-server daemon (`codex-potter app-server` on a Unix socket
+    /// under `.codexpotter/daemons/`) that exits once the project completes.
+    Start {
+        /// Return once the project started; the daemon keeps running in the background.
+        #[arg(long)]
+        detach: bool,
+        /// Prompt for the project.
+        prompt: String,
+    },
+    /// List the background projects started in this directory, with their round and outcome.
+    Status,
+    /// Stop a background project: interrupt its current round, then stop the project.
+    Stop {
+        /// Pid of the daemon or project path (same forms as `resume`). May be omitted when a
+        /// single background project is running.
+        target: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ExecCommand {
+    /// Resume an existing CodexPotter project and continue iterating non-interactively.
+    Resume {
+        /// Project path to resolve to a unique `MAIN.md`.
+        project_path: PathBuf,
+        /// How to handle an unfinished last round (EOF without `RoundFinished`).
+        #[arg(long, value_enum, default_value_t = CliResumePolicy::ContinueUnfinishedRound)]
+        resume_policy: CliResumePolicy,
+        /// Do not emit the `potter.replay.*` section for previously completed rounds.
+        #[arg(long)]
+        no_replay: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+enum CliResumePolicy {
+    ContinueUnfinishedRound,
+    StartNewRound,
+}
+
+impl CliResumePolicy {
+    fn as_protocol(self) -> crate::app_server::potter::ResumePolicy {
+        match self {
+            CliResumePolicy::ContinueUnfinishedRound => {
+                crate::app_server::potter::ResumePolicy::ContinueUnfinishedRound
+            }
+            CliResumePolicy::StartNewRound => {
+                crate::app_server::potter::ResumePolicy::StartNewRound
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+enum CliRollbackPoint {
+    Start,
+    End,
+}
+
+impl CliRollbackPoint {
+    fn as_workflow(self) -> crate::workflow::checkpoint::RollbackPoint {
+        match self {
+            CliRollbackPoint::Start => crate::workflow::checkpoint::RollbackPoint::RoundStart,
+            CliRollbackPoint::End => crate::workflow::checkpoint::RollbackPoint::RoundEnd,
+        }
+    }
 }
 
 fn parse_cli() -> Cli {
     let matches = Cli::command()
         .version(codex_tui::CODEX_POTTER_VERSION)
         .get_matches();
-    Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit())
+    Cli::from_arg_matches(&matches)
+        .and_then(check_exec_args)
+        .unwrap_or_else(|err| err.exit())
+}
+
+/// `exec` runs either a prompt or its `resume` subcommand. An argument that is exactly `resume`
+/// always selects the subcommand (pass such a prompt after `--`), and clap would also accept a
+/// prompt followed by the subcommand, silently dropping the prompt; reject that.
+fn check_exec_args(cli: Cli) -> Result<Cli, clap::Error> {
+    if let Some(CliCommand::Exec {
+        command: Some(_),
+        prompt: Some(prompt),
+        ..
+    }) = &cli.command
+    {
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::ArgumentConflict,
+            format!(
+                "`exec` got both the prompt `{prompt}` and the `resume` subcommand; pass a prompt \
+                 that is literally `resume` after `--` (`exec -- resume`)"
+            ),
+        ));
+    }
+    Ok(cli)
 }
 
-fn resolve_codex_bin_or_exit(codex_bin: &str) -> String {
-    match startup::resolve_codex_bin(codex_bin) {
-        Ok(resolved) => resolved.command_for_spawn,
+/// Resolve `codex_bin` and, when `check_version` is set, refuse unsupported `codex` releases.
+fn resolve_checked_codex_bin(
+    codex_bin: &str,
+    check_version: bool,
+) -> Result<String, startup::CodexBinError> {
+    let resolved = startup::resolve_codex_bin(codex_bin)?;
+    if check_version
+        && let Some(warning) = startup::check_codex_bin_version(&resolved.command_for_spawn)?
+    {
+        eprintln!("warning: {warning}");
+    }
+    Ok(resolved.command_for_spawn)
+}
+
+fn resolve_codex_bin_or_exit(codex_bin: &str, check_version: bool) -> String {
+    match resolve_checked_codex_bin(codex_bin, check_version) {
+        Ok(command_for_spawn) => command_for_spawn,
         Err(err) => {
             eprint!("{}", err.render_ansi());
             std::process::exit(1);
@@ -139,24 +319,74 @@ fn resolve_codex_bin_or_exit(codex_bin: &str) -> String {
     }
 }
 
-fn resolve_workdir_or_exec_json_exit() -> PathBuf {
+fn resolve_workdir_or_exec_exit(output_format: crate::exec::ExecOutputFormat) -> PathBuf {
     match std::env::current_dir() {
         Ok(workdir) => workdir,
         Err(err) => {
             let message = format!("resolve current directory: {err}");
             eprintln!("error: {message}");
-            let _ = crate::exec::write_exec_json_preflight_error(&message);
+            if output_format == crate::exec::ExecOutputFormat::Json {
+                let _ = crate::exec::write_exec_json_preflight_error(&message);
+            }
             std::process::exit(1);
         }
     }
 }
 
-fn resolve_codex_bin_or_exec_json_exit(codex_bin: &str) -> String {
-    match startup::resolve_codex_bin(codex_bin) {
-        Ok(resolved) => resolved.command_for_spawn,
+fn load_exec_answers_or_exit(
+    answers_file: Option<&Path>,
+    output_format: crate::exec::ExecOutputFormat,
+) -> crate::exec::ExecAnswers {
+    let Some(answers_file) = answers_file else {
+        return crate::exec::ExecAnswers::default();
+    };
+    match crate::exec::ExecAnswers::load(answers_file) {
+        Ok(answers) => answers,
+        Err(err) => {
+            let message = format!("{err:#}");
+            eprintln!("error: {message}");
+            if output_format == crate::exec::ExecOutputFormat::Json {
+                let _ = crate::exec::write_exec_json_preflight_error(&message);
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn load_exec_output_schema_or_exit(
+    output_schema: Option<&Path>,
+    output_last_message: Option<&Path>,
+    output_format: crate::exec::ExecOutputFormat,
+) -> Option<crate::exec::ExecOutputSchema> {
+    let output_schema = output_schema?;
+    match crate::exec::ExecOutputSchema::load(
+        output_schema,
+        output_last_message.map(Path::to_path_buf),
+    ) {
+        Ok(output_schema) => Some(output_schema),
+        Err(err) => {
+            let message = format!("{err:#}");
+            eprintln!("error: {message}");
+            if output_format == crate::exec::ExecOutputFormat::Json {
+                let _ = crate::exec::write_exec_json_preflight_error(&message);
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn resolve_codex_bin_or_exec_exit(
+    codex_bin: &str,
+    check_version: bool,
+    output_format: crate::exec::ExecOutputFormat,
+) -> String {
+    match resolve_checked_codex_bin(codex_bin, check_version) {
+        Ok(command_for_spawn) => command_for_spawn,
         Err(err) => {
             eprint!("{}", err.render_ansi());
-            let _ = crate::exec::write_exec_json_preflight_error(&err.to_string());
+            if output_format == crate::exec::ExecOutputFormat::Json {
+                let _ = crate::exec::write_exec_json_preflight_error(&err.to_string());
+            }
             std::process::exit(1);
         }
     }
@@ -171,54 +401,239 @@ async fn main() -> anyhow::Result<()> {
     );
     let upstream_cli_args = cli.upstream_cli_args.clone();
 
-    if let Some(CliCommand::Exec { prompt, json }) = cli.command.as_ref() {
-        if !json {
-            eprintln!("error: currently only --json output is supported for exec");
-            std::process::exit(1);
-        }
+    if let Some(CliCommand::Rollback {
+        project_path,
+        to_round,
+        at,
+    }) = cli.command.as_ref()
+    {
+        let workdir = std::env::current_dir().context("resolve current directory")?;
+        let outcome = crate::workflow::checkpoint::run_rollback(
+            &workdir,
+            project_path,
+            *to_round,
+            at.as_workflow(),
+        )?;
+        println!(
+            "Rolled back to round {to_round} ({}): HEAD {}",
+            match at {
+                CliRollbackPoint::Start => "start",
+                CliRollbackPoint::End => "end",
+            },
+            outcome.checkpoint.head
+        );
+        println!("Previous state saved as {}", outcome.backup_ref);
+        return Ok(());
+    }
 
-        let workdir = resolve_workdir_or_exec_json_exit();
-        maybe_apply_default_global_gitignore(&workdir);
-        let codex_bin = resolve_codex_bin_or_exec_json_exit(&cli.codex_bin);
+    if let Some(CliCommand::Status) = cli.command.as_ref() {
+        let workdir = std::env::current_dir().context("resolve current directory")?;
+        let records = crate::daemon::list_daemons(&workdir)?;
+        if records.is_empty() {
+            println!("No background projects.");
+        }
+        for record in records {
+            let alive = record.is_alive();
+            println!("{}", crate::daemon::describe_daemon(&record, alive));
+        }
+        return Ok(());
+    }
 
-        let exit_code = crate::exec::run_exec_json(
+    if let Some(CliCommand::Stop { target }) = cli.command.as_ref() {
+        let workdir = std::env::current_dir().context("resolve current directory")?;
+        let record = crate::daemon::select_running_daemon(
             &workdir,
-            prompt.clone(),
-            cli.rounds,
+            crate::daemon::list_daemons(&workdir)?,
+            target.as_deref(),
+        )?;
+        let outcome = crate::daemon::stop_daemon(&record).await?;
+        println!(
+            "Background project {} (pid {}): {}",
+            record
+                .progress_file
+                .as_deref()
+                .map(derive_resume_project_path_for_note)
+                .unwrap_or_default(),
+            record.pid,
+            crate::daemon::describe_outcome(&outcome)
+        );
+        return Ok(());
+    }
+
+    if let Some(CliCommand::Exec {
+        command,
+        prompt,
+        json,
+        answers_file,
+        output_schema,
+        output_last_message,
+    }) = cli.command.as_ref()
+    {
+        let output_format = if *json {
+            crate::exec::ExecOutputFormat::Json
+        } else {
+            crate::exec::ExecOutputFormat::Text {
+                color: crate::exec::stdout_supports_color(),
+            }
+        };
+
+        let workdir = resolve_workdir_or_exec_exit(output_format);
+        maybe_apply_default_global_gitignore(&workdir);
+        let codex_bin = resolve_codex_bin_or_exec_exit(
+            &cli.codex_bin,
+            !cli.allow_unsupported_codex,
+            output_format,
+        );
+        let answers = load_exec_answers_or_exit(answers_file.as_deref(), output_format);
+        let output_schema = load_exec_output_schema_or_exit(
+            output_schema.as_deref(),
+            output_last_message.as_deref(),
+            output_format,
+        );
+        let app_server = crate::exec::ExecAppServerOptions {
+            rounds: cli.rounds,
             codex_bin,
             backend_launch,
             upstream_cli_args,
-        )
-        .await;
+            project_cli_args: cli.project_cli_args.clone(),
+        };
+
+        let exit_code = match command {
+            Some(ExecCommand::Resume {
+                project_path,
+                resume_policy,
+                no_replay,
+            }) => {
+                crate::exec::run_exec_resume(
+                    &workdir,
+                    output_format,
+                    crate::exec::ExecResumeOptions {
+                        project_path: project_path.clone(),
+                        resume_policy: resume_policy.as_protocol(),
+                        replay: !no_replay,
+                    },
+                    app_server,
+                    answers,
+                    output_schema,
+                )
+                .await
+            }
+            None => {
+                crate::exec::run_exec(
+                    &workdir,
+                    output_format,
+                    prompt.clone(),
+                    app_server,
+                    answers,
+                    output_schema,
+                )
+                .await
+            }
+        };
         std::process::exit(exit_code);
     }
 
+    if let Some(CliCommand::Attach {
+        connect,
+        token,
+        project_path,
+    }) = cli.command.as_ref()
+    {
+        let workdir = std::env::current_dir().context("resolve current directory")?;
+        let project_id = match project_path {
+            Some(project_path) => Some(
+                crate::workflow::resume::resolve_project_paths(&workdir, project_path)?
+                    .progress_file
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            None => None,
+        };
+        let mut client =
+            crate::app_server::potter::PotterAppServerClient::connect(connect, token.clone())
+                .await
+                .with_context(|| format!("connect to potter app-server at {connect}"))?;
+        client
+            .initialize()
+            .await
+            .context("initialize potter app-server")?;
+
+        let turn_prompt = crate::workflow::project::fixed_prompt()
+            .trim_end()
+            .to_string();
+        let mut ui = codex_tui::CodexPotterTui::new()?;
+        let attach_exit =
+            crate::workflow::attach::run_attach(&mut ui, &mut client, project_id, turn_prompt)
+                .await;
+        let attach_exit = match attach_exit {
+            Ok(exit) => exit,
+            Err(err) => {
+                drop(ui);
+                return Err(err);
+            }
+        };
+        let _ = client.shutdown().await;
+        drop(ui);
+        match attach_exit {
+            crate::workflow::attach::AttachExit::Finished => {}
+            crate::workflow::attach::AttachExit::Detached { project_id } => {
+                print_reattach_note(connect, &project_id);
+            }
+            crate::workflow::attach::AttachExit::FatalExitRequested => std::process::exit(1),
+        }
+        return Ok(());
+    }
+
     crate::rounds::round_budget_to_u32(cli.rounds)?;
 
     let workdir = std::env::current_dir().context("resolve current directory")?;
-    let codex_bin = resolve_codex_bin_or_exit(&cli.codex_bin);
+    // A stdio app-server is spawned by a client that already checked the version; its
+    // `initialize` responses are still checked per round.
+    let codex_bin = resolve_codex_bin_or_exit(
+        &cli.codex_bin,
+        !cli.allow_unsupported_codex
+            && !matches!(
+                cli.command,
+                Some(CliCommand::AppServer { listen: None, .. })
+            ),
+    );
 
-    if matches!(cli.command, Some(CliCommand::AppServer)) {
-        maybe_apply_default_global_gitignore(&workdir);
-        let codex_compat_home = match crate::codex_compat::ensure_default_codex_compat_home() {
-            Ok(home) => home,
-            Err(err) => {
-                eprintln!("warning: failed to configure codex-compat home: {err}");
-                None
-            }
+    if let Some(CliCommand::AppServer {
+        listen,
+        listen_token,
+    }) = &cli.command
+    {
+        let transport = match listen {
+            Some(address) => crate::app_server::potter::PotterAppServerTransport::Listen {
+                address: address.clone(),
+                token: listen_token.clone(),
+            },
+            None => crate::app_server::potter::PotterAppServerTransport::Stdio,
         };
+        maybe_apply_default_global_gitignore(&workdir);
+        let mut config = potter_app_server_config(&cli, workdir, codex_bin, backend_launch)?;
+        // The spawning client of a stdio server picks queued prompts itself.
+        config.drain_queue = listen.is_some();
+        crate::app_server::potter::run_potter_app_server(config, transport).await?;
+        return Ok(());
+    }
 
-        crate::app_server::potter::run_potter_app_server(
-            crate::app_server::potter::PotterAppServerConfig {
-                default_workdir: workdir,
-                codex_bin,
-                backend_launch,
-                codex_compat_home,
-                rounds: cli.rounds,
-                upstream_cli_args,
-            },
-        )
-        .await?;
+    if let Some(CliCommand::Start { detach, prompt }) = &cli.command {
+        crate::daemon::ensure_supported()?;
+        if *detach && !crate::daemon::is_daemon_child() {
+            let record = crate::daemon::spawn_detached(&workdir).await?;
+            print_background_project_note(&record);
+            return Ok(());
+        }
+
+        maybe_apply_default_global_gitignore(&workdir);
+        let rounds = crate::rounds::round_budget_to_u32(cli.rounds)?;
+        let config = potter_app_server_config(&cli, workdir.clone(), codex_bin, backend_launch)?;
+        let outcome = crate::daemon::run_daemon(&workdir, prompt.clone(), rounds, config).await?;
+        println!(
+            "Background project finished: {}",
+            crate::daemon::describe_outcome(&outcome)
+        );
         return Ok(());
     }
 
@@ -286,6 +701,7 @@ async fn main() -> anyhow::Result<()> {
         cli.rounds,
         backend_launch,
         cli.upstream_cli_args.clone(),
+        cli.project_cli_args.clone(),
     )
     .await
     .context("spawn potter app-server")?;
@@ -328,6 +744,7 @@ async fn main() -> anyhow::Result<()> {
                                 created_at,
                                 updated_at,
                                 git_branch: project.git_branch,
+                                worktree: project.worktree,
                             })
                         })
                         .collect::<Vec<_>>()
@@ -358,8 +775,11 @@ async fn main() -> anyhow::Result<()> {
                         .into_iter()
                         .collect::<Vec<_>>();
                     let resume_note_path = derive_resume_project_path_for_note(&project_path);
+                    let (saved_prompts, queued_prompts) =
+                        save_queued_prompts(&mut potter_app_server, queued_prompts).await;
                     let _ = potter_app_server.shutdown().await;
                     drop(ui);
+                    print_saved_prompts_note(saved_prompts);
                     print_queued_prompts_note(&queued_prompts);
                     print_resume_note(&resume_note_path);
                     return Ok(());
@@ -383,6 +803,7 @@ async fn main() -> anyhow::Result<()> {
         crate::workflow::project_runner::ProjectQueueOptions {
             rounds: cli.rounds,
             turn_prompt: turn_prompt.clone(),
+            parallel: cli.project_cli_args.parallel_projects(),
         },
     )
     .await?;
@@ -408,9 +829,12 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    let (saved_prompts, queued_prompts_on_exit) =
+        save_queued_prompts(&mut potter_app_server, queued_prompts_on_exit).await;
     let _ = potter_app_server.shutdown().await;
 
     drop(ui);
+    print_saved_prompts_note(saved_prompts);
     print_queued_prompts_note(&queued_prompts_on_exit);
     if let Some(project_path) = resume_note_project_path {
         print_resume_note(&project_path);
@@ -419,6 +843,51 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Server configuration shared by `app-server` and background projects (`start`).
+fn potter_app_server_config(
+    cli: &Cli,
+    workdir: PathBuf,
+    codex_bin: String,
+    backend_launch: crate::app_server::AppServerLaunchConfig,
+) -> anyhow::Result<crate::app_server::potter::PotterAppServerConfig> {
+    let codex_compat_home = match crate::codex_compat::ensure_default_codex_compat_home() {
+        Ok(home) => home,
+        Err(err) => {
+            eprintln!("warning: failed to configure codex-compat home: {err}");
+            None
+        }
+    };
+    let (round_schedule, approval_settings, stream_recovery) =
+        match crate::config::ConfigStore::new_default() {
+            Ok(store) => (
+                store
+                    .round_schedule()
+                    .context("read [rounds] from ~/.codexpotter/config.toml")?,
+                store
+                    .approval_policy_settings()
+                    .context("read [approvals] from ~/.codexpotter/config.toml")?,
+                store
+                    .stream_recovery_policy()
+                    .context("read [stream_recovery] from ~/.codexpotter/config.toml")?,
+            ),
+            Err(_) => Default::default(),
+        };
+
+    Ok(crate::app_server::potter::PotterAppServerConfig {
+        default_workdir: workdir,
+        codex_bin,
+        backend_launch,
+        codex_compat_home,
+        rounds: cli.rounds,
+        upstream_cli_args: cli.upstream_cli_args.clone(),
+        project_cli_args: cli.project_cli_args.clone(),
+        round_schedule,
+        approval_settings,
+        stream_recovery,
+        drain_queue: false,
+    })
+}
+
 fn run_update_action(action: codex_tui::UpdateAction) -> anyhow::Result<()> {
     println!();
     let cmd_str = action.command_str();
@@ -502,6 +971,92 @@ fn print_resume_note(project_path: &str) {
     println!("  {}", ansi_cyan(&command));
 }
 
+fn print_reattach_note(
+    address: &crate::app_server::potter::PotterAppServerListenAddress,
+    project_id: &str,
+) {
+    let project_path = derive_resume_project_path_for_note(Path::new(project_id));
+    let command = format!("codex-potter attach --connect {address} {project_path}");
+    println!(
+        "{} The project keeps running. To attach again, run:",
+        ansi_bold("Note:")
+    );
+    println!("  {}", ansi_cyan(&command));
+}
+
+fn print_background_project_note(record: &crate::daemon::DaemonRecord) {
+    let project_path = record
+        .progress_file
+        .as_deref()
+        .map(derive_resume_project_path_for_note)
+        .unwrap_or_default();
+    println!(
+        "{} Started {project_path} in the background (pid {}, log {}).",
+        ansi_bold("Note:"),
+        record.pid,
+        crate::path_utils::display_with_tilde(&record.log_file)
+    );
+    println!(
+        "  {}",
+        ansi_cyan(&format!(
+            "codex-potter attach --connect unix://{} {project_path}",
+            record.socket.display()
+        ))
+    );
+    println!("  {}", ansi_cyan("codex-potter status"));
+    println!(
+        "  {}",
+        ansi_cyan(&format!("codex-potter stop {}", record.pid))
+    );
+}
+
+/// Move prompts still queued in the UI to the server's persistent queue, so the next session
+/// picks them up.
+///
+/// Each prompt is appended with its own `queue/add` (a locked append on the server), so entries
+/// other clients queued in the meantime are kept.
+///
+/// Returns how many were saved and the prompts that could not be saved.
+async fn save_queued_prompts(
+    potter_app_server: &mut crate::app_server::potter::PotterAppServerClient,
+    queued_prompts: Vec<String>,
+) -> (usize, Vec<String>) {
+    let mut saved = 0;
+    let mut unsaved = Vec::new();
+    for prompt in queued_prompts {
+        let mut ignored_events: Vec<codex_protocol::protocol::Event> = Vec::new();
+        let added = potter_app_server
+            .queue_add(
+                crate::app_server::potter::QueueAddParams {
+                    prompt: prompt.clone(),
+                },
+                &mut ignored_events,
+            )
+            .await;
+        match added {
+            Ok(_) => saved += 1,
+            Err(_) => unsaved.push(prompt),
+        }
+    }
+    (saved, unsaved)
+}
+
+fn print_saved_prompts_note(saved_prompts: usize) {
+    if saved_prompts == 0 {
+        return;
+    }
+    let prompt_label = if saved_prompts == 1 {
+        "prompt was"
+    } else {
+        "prompts were"
+    };
+    println!();
+    println!(
+        "{} {saved_prompts} queued {prompt_label} saved to .codexpotter/queue.jsonl and will run the next time codex-potter starts here.",
+        ansi_bold("Note:")
+    );
+}
+
 fn print_queued_prompts_note(queued_prompts: &[String]) {
     let Some(note) = render_queued_prompts_note(queued_prompts) else {
         return;
@@ -697,6 +1252,8 @@ mod tests {
             "unified_exec",
             "--disable",
             "web_search_request",
+            "--verify-command",
+            "cargo test",
         ])
         .expect("parse args");
 
@@ -719,6 +1276,10 @@ mod tests {
             cli.upstream_cli_args.disable_features,
             vec!["web_search_request".to_string()]
         );
+        assert_eq!(
+            cli.project_cli_args.verify_command.as_deref(),
+            Some("cargo test")
+        );
 
         let Some(CliCommand::Resume { project_path }) = cli.command else {
             panic!("expected resume command, got: {:?}", cli.command);
@@ -752,18 +1313,283 @@ mod tests {
         let cli =
             Cli::try_parse_from(["codex-potter", "exec", "hello", "--json"]).expect("parse args");
 
-        let Some(CliCommand::Exec { prompt, json }) = cli.command else {
+        let Some(CliCommand::Exec {
+            command,
+            prompt,
+            json,
+            answers_file,
+            output_schema,
+            output_last_message,
+        }) = cli.command
+        else {
             panic!("expected exec command, got: {:?}", cli.command);
         };
+        assert!(command.is_none());
         assert_eq!(prompt, Some("hello".to_string()));
         assert!(json);
+        assert_eq!(answers_file, None);
+        assert_eq!(output_schema, None);
+        assert_eq!(output_last_message, None);
+    }
+
+    #[test]
+    fn exec_prompt_named_resume_needs_double_dash() {
+        let cli = Cli::try_parse_from(["codex-potter", "exec", "--", "resume"])
+            .and_then(check_exec_args)
+            .expect("parse args");
+        let Some(CliCommand::Exec {
+            command, prompt, ..
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        assert!(command.is_none());
+        assert_eq!(prompt, Some("resume".to_string()));
+
+        let cli = Cli::try_parse_from(["codex-potter", "exec", "resume the failing build"])
+            .and_then(check_exec_args)
+            .expect("parse args");
+        let Some(CliCommand::Exec {
+            command, prompt, ..
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        assert!(command.is_none());
+        assert_eq!(prompt, Some("resume the failing build".to_string()));
+
+        assert!(
+            Cli::try_parse_from(["codex-potter", "exec", "resume"])
+                .and_then(check_exec_args)
+                .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["codex-potter", "exec", "hello", "resume", "2026/02/01/1"])
+                .and_then(check_exec_args)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn exec_output_last_message_requires_output_schema() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "exec",
+            "hello",
+            "--output-schema",
+            "schema.json",
+            "--output-last-message",
+            "report.json",
+        ])
+        .expect("parse args");
+        let Some(CliCommand::Exec {
+            output_schema,
+            output_last_message,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        assert_eq!(output_schema, Some(PathBuf::from("schema.json")));
+        assert_eq!(output_last_message, Some(PathBuf::from("report.json")));
+
+        let err = Cli::try_parse_from([
+            "codex-potter",
+            "exec",
+            "hello",
+            "--output-last-message",
+            "report.json",
+        ])
+        .expect_err("missing --output-schema");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn exec_resume_subcommand_parses_project_path_and_flags() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "exec",
+            "resume",
+            "2026/02/01/1",
+            "--json",
+            "--resume-policy",
+            "start-new-round",
+            "--no-replay",
+            "--answers-file",
+            "answers.json",
+        ])
+        .expect("parse args");
+
+        let Some(CliCommand::Exec {
+            command,
+            prompt,
+            json,
+            answers_file,
+            output_schema,
+            output_last_message,
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        let Some(ExecCommand::Resume {
+            project_path,
+            resume_policy,
+            no_replay,
+        }) = command
+        else {
+            panic!("expected exec resume command");
+        };
+        assert_eq!(prompt, None);
+        assert!(json);
+        assert_eq!(answers_file, Some(PathBuf::from("answers.json")));
+        assert_eq!(output_schema, None);
+        assert_eq!(output_last_message, None);
+        assert_eq!(project_path, PathBuf::from("2026/02/01/1"));
+        assert_eq!(resume_policy, CliResumePolicy::StartNewRound);
+        assert!(no_replay);
+    }
+
+    #[test]
+    fn exec_resume_defaults_to_continue_unfinished_round_with_replay() {
+        let cli = Cli::try_parse_from(["codex-potter", "exec", "--json", "resume", "2026/02/01/1"])
+            .expect("parse args");
+
+        let Some(CliCommand::Exec {
+            command:
+                Some(ExecCommand::Resume {
+                    resume_policy,
+                    no_replay,
+                    ..
+                }),
+            json,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected exec resume command, got: {:?}", cli.command);
+        };
+        assert!(json);
+        assert_eq!(resume_policy, CliResumePolicy::ContinueUnfinishedRound);
+        assert!(!no_replay);
+    }
+
+    #[test]
+    fn rollback_subcommand_parses_round_and_defaults_to_round_end() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "rollback",
+            "2026/02/01/1",
+            "--to-round",
+            "3",
+        ])
+        .expect("parse args");
+        let Some(CliCommand::Rollback {
+            project_path,
+            to_round,
+            at,
+        }) = cli.command
+        else {
+            panic!("expected rollback command, got: {:?}", cli.command);
+        };
+        assert_eq!(project_path, PathBuf::from("2026/02/01/1"));
+        assert_eq!(to_round, 3);
+        assert_eq!(at, CliRollbackPoint::End);
+
+        assert!(Cli::try_parse_from(["codex-potter", "rollback", "2026/02/01/1"]).is_err());
     }
 
     #[test]
     fn app_server_subcommand_parses() {
         let cli = Cli::try_parse_from(["codex-potter", "app-server"]).expect("parse args");
 
-        assert!(matches!(cli.command, Some(CliCommand::AppServer)));
+        assert!(matches!(
+            cli.command,
+            Some(CliCommand::AppServer {
+                listen: None,
+                listen_token: None
+            })
+        ));
+    }
+
+    #[test]
+    fn app_server_listen_parses_socket_urls() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "app-server",
+            "--listen",
+            "unix:///tmp/potter.sock",
+        ])
+        .expect("parse args");
+        let Some(CliCommand::AppServer { listen, .. }) = cli.command else {
+            panic!("expected app-server subcommand");
+        };
+        assert_eq!(
+            listen,
+            Some(
+                crate::app_server::potter::PotterAppServerListenAddress::Unix(PathBuf::from(
+                    "/tmp/potter.sock"
+                ))
+            )
+        );
+
+        assert!(
+            Cli::try_parse_from(["codex-potter", "app-server", "--listen", "/tmp/potter.sock"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn attach_requires_a_server_address() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "attach",
+            "--connect",
+            "tcp://127.0.0.1:4500",
+            "2026/10/17/1",
+        ])
+        .expect("parse args");
+        let Some(CliCommand::Attach {
+            connect,
+            project_path,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected attach subcommand");
+        };
+        assert_eq!(
+            connect,
+            crate::app_server::potter::PotterAppServerListenAddress::Tcp(
+                "127.0.0.1:4500".parse().expect("socket address")
+            )
+        );
+        assert_eq!(project_path, Some(PathBuf::from("2026/10/17/1")));
+
+        assert!(Cli::try_parse_from(["codex-potter", "attach", "2026/10/17/1"]).is_err());
+    }
+
+    #[test]
+    fn background_project_commands_parse() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "--rounds",
+            "3",
+            "start",
+            "--detach",
+            "Fix the flaky test",
+        ])
+        .expect("parse args");
+        let Some(CliCommand::Start { detach, prompt }) = cli.command else {
+            panic!("expected start subcommand");
+        };
+        assert!(detach);
+        assert_eq!(prompt, "Fix the flaky test");
+
+        let cli = Cli::try_parse_from(["codex-potter", "stop", "4242"]).expect("parse args");
+        let Some(CliCommand::Stop { target }) = cli.command else {
+            panic!("expected stop subcommand");
+        };
+        assert_eq!(target.as_deref(), Some("4242"));
+
+        assert!(Cli::try_parse_from(["codex-potter", "start", "--detach"]).is_err());
     }
 
     #[test]
@@ -6,6 +6,10 @@
 //! - When the user passes `--codex-bin` that looks like a path, validate it is an executable file.
 //! - Otherwise, resolve it via `$PATH` (e.g. `codex`).
 //!
+//! The resolved binary is then checked against the upstream versions the protocol mirror supports
+//! (see `app_server::upstream_version`); releases that are too old are refused unless the user
+//! passes `--allow-unsupported-codex`.
+//!
 //! It also provides user-facing error messages in both plain text (`Display`) and ANSI-rendered
 //! form (used by the CLI/TUI). Snapshot tests cover the most important error formatting.
 
@@ -13,12 +17,15 @@ use std::fmt;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::app_server::upstream_version;
+use crate::app_server::upstream_version::CodexVersionSupport;
 use crate::path_utils;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CodexBinError {
     NotFoundInPath { command: String },
     InvalidPath { path: PathBuf, reason: String },
+    UnsupportedVersion { version: String, supported: String },
 }
 
 impl fmt::Display for CodexBinError {
@@ -33,6 +40,10 @@ impl fmt::Display for CodexBinError {
                 "Failed to find codex binary specified by `--codex-bin`: {} ({reason}).",
                 path.display()
             ),
+            CodexBinError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "codex {version} is older than codex-potter supports ({supported}). Upgrade codex, or pass `--allow-unsupported-codex` to start anyway."
+            ),
         }
     }
 }
@@ -56,6 +67,10 @@ impl CodexBinError {
                 "Failed to find codex binary specified by `--codex-bin`: {} ({reason}).\n",
                 path.display()
             )),
+            CodexBinError::UnsupportedVersion { version, supported } => ansi_red(format!(
+                "codex {version} is older than codex-potter supports ({supported}).\n\
+                 Upgrade codex, or pass `--allow-unsupported-codex` to start anyway.\n"
+            )),
         }
     }
 }
@@ -83,6 +98,31 @@ pub fn resolve_codex_bin(codex_bin: &str) -> Result<ResolvedCodexBin, CodexBinEr
     })
 }
 
+/// Refuse `codex` releases older than the protocol mirror supports.
+///
+/// A version that cannot be determined is not refused here; instead a warning naming the binary
+/// and the reason is returned for the caller to print. The app-server's `initialize` response is
+/// checked again once a round starts.
+pub fn check_codex_bin_version(command_for_spawn: &str) -> Result<Option<String>, CodexBinError> {
+    let version = match upstream_version::query_codex_version(command_for_spawn) {
+        Ok(version) => version,
+        Err(err) => {
+            return Ok(Some(format!(
+                "could not determine the version of `{command_for_spawn}`: {err:#}; starting \
+                 anyway (codex-potter supports codex {})",
+                upstream_version::supported_codex_versions()
+            )));
+        }
+    };
+    match version.support() {
+        CodexVersionSupport::TooOld => Err(CodexBinError::UnsupportedVersion {
+            version: version.to_string(),
+            supported: upstream_version::supported_codex_versions(),
+        }),
+        CodexVersionSupport::Supported | CodexVersionSupport::Untested => Ok(None),
+    }
+}
+
 fn validate_executable_path(path: &Path) -> Result<(), CodexBinError> {
     let meta = std::fs::metadata(path).map_err(|err| CodexBinError::InvalidPath {
         path: path.to_path_buf(),
@@ -241,4 +281,76 @@ mod tests {
             }
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_codex_bin_version_refuses_old_releases() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let fake_codex = |name: &str, version: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(
+                &path,
+                format!("#!/usr/bin/env bash\necho \"codex-cli {version}\"\n"),
+            )
+            .expect("write fake codex");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+                .expect("chmod fake codex");
+            path.display().to_string()
+        };
+
+        assert_eq!(
+            check_codex_bin_version(&fake_codex("old-codex", "0.1.0")),
+            Err(CodexBinError::UnsupportedVersion {
+                version: "0.1.0".to_string(),
+                supported: upstream_version::supported_codex_versions(),
+            })
+        );
+        let supported = upstream_version::MIN_SUPPORTED_CODEX_VERSION.to_string();
+        assert_eq!(
+            check_codex_bin_version(&fake_codex("codex", &supported)),
+            Ok(None)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_codex_bin_version_warns_when_the_version_is_unknown() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let fake_codex = |name: &str, script: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("#!/usr/bin/env bash\n{script}\n"))
+                .expect("write fake codex");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+                .expect("chmod fake codex");
+            path.display().to_string()
+        };
+
+        let dev_codex = fake_codex("dev-codex", "echo \"codex-cli dev\"");
+        let warning = check_codex_bin_version(&dev_codex)
+            .expect("unknown versions are not refused")
+            .expect("warning");
+        assert!(
+            warning.starts_with(&format!(
+                "could not determine the version of `{dev_codex}`: parse version from"
+            )),
+            "{warning}"
+        );
+        assert!(warning.contains("\"codex-cli dev\""), "{warning}");
+
+        let broken_codex = fake_codex("broken-codex", "exit 3");
+        let warning = check_codex_bin_version(&broken_codex)
+            .expect("unknown versions are not refused")
+            .expect("warning");
+        assert!(
+            warning.starts_with(&format!(
+                "could not determine the version of `{broken_codex}`: `{broken_codex} --version` \
+                 exited with"
+            )),
+            "{warning}"
+        );
+    }
 }
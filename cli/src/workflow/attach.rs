@@ -0,0 +1,271 @@
+//! `codex-potter attach`: render a project running on a listening `codex-potter app-server`.
+//!
+//! The client sends `project/attach`, renders the replayed events (the project start and the
+//! current round) and then follows the live stream through the regular project renderer, so
+//! interrupts, plan reviews and approvals work as in the terminal that started the project.
+//!
+//! Leaving the UI sends `project/detach`; the project keeps running on the server.
+
+use std::time::Instant;
+
+use anyhow::Context;
+use codex_protocol::protocol::Event;
+
+use crate::app_server::potter::PotterAppServerClient;
+use crate::app_server::potter::PotterProjectEvent;
+use crate::workflow::project_runner::DrivenProject;
+use crate::workflow::project_runner::DrivenProjectExit;
+use crate::workflow::round_runner::UiFuture;
+
+/// How [`run_attach`] returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachExit {
+    /// The project completed (or was stopped) while attached.
+    Finished,
+    /// The user left the UI; the project keeps running on the server.
+    Detached { project_id: String },
+    /// The UI requested a fatal exit; the client detached from the project.
+    FatalExitRequested,
+}
+
+/// Attach to `project_id` (the only running project when `None`) and render it until it completes
+/// or the user leaves.
+pub async fn run_attach(
+    ui: &mut codex_tui::CodexPotterTui,
+    client: &mut PotterAppServerClient,
+    project_id: Option<String>,
+    turn_prompt: String,
+) -> anyhow::Result<AttachExit> {
+    let mut live_events: Vec<PotterProjectEvent> = Vec::new();
+    let response = client
+        .project_attach(
+            crate::app_server::potter::ProjectAttachParams { project_id },
+            &mut live_events,
+        )
+        .await
+        .context("project/attach via potter app-server")?;
+
+    let replayed_seq = response
+        .events
+        .last()
+        .map(|event| event.seq)
+        .unwrap_or_default();
+    let mut attached = AttachedProject {
+        client,
+        filter: LiveEventFilter {
+            project_id: response.project_id.clone(),
+            replayed_seq,
+        },
+    };
+    let buffered_events = response
+        .events
+        .into_iter()
+        .map(|event| event.event)
+        .chain(attached.filter.keep_all(live_events))
+        .collect();
+
+    // The replay starts at the current round, so the project clock starts when attaching.
+    ui.set_project_started_at(Instant::now());
+    let prompt_footer = codex_tui::PromptFooterContext::new(
+        response
+            .worktree
+            .clone()
+            .unwrap_or_else(|| response.working_dir.clone()),
+        response.git_branch.clone(),
+    );
+    let project = DrivenProject {
+        project_id: response.project_id,
+        workdir: response.working_dir,
+        worktree: response.worktree,
+        git_branch: response.git_branch,
+        turn_prompt,
+        prompt_footer,
+    };
+
+    let exit = crate::workflow::project_runner::drive_project(
+        ui,
+        &mut attached,
+        &project,
+        buffered_events,
+    )
+    .await?;
+    match exit {
+        DrivenProjectExit::Finished => Ok(AttachExit::Finished),
+        DrivenProjectExit::UserRequested => {
+            detach(attached.client, project.project_id.clone()).await?;
+            Ok(AttachExit::Detached {
+                project_id: project.project_id,
+            })
+        }
+        DrivenProjectExit::FatalExitRequested => {
+            // Best-effort: the connection is closed right after.
+            let _ = detach(attached.client, project.project_id).await;
+            Ok(AttachExit::FatalExitRequested)
+        }
+    }
+}
+
+async fn detach(client: &mut PotterAppServerClient, project_id: String) -> anyhow::Result<()> {
+    let mut ignored_events: Vec<PotterProjectEvent> = Vec::new();
+    client
+        .project_detach(
+            crate::app_server::potter::ProjectDetachParams { project_id },
+            &mut ignored_events,
+        )
+        .await
+        .context("project/detach via potter app-server")
+}
+
+/// The attached project's view of the connection.
+struct AttachedProject<'c> {
+    client: &'c mut PotterAppServerClient,
+    filter: LiveEventFilter,
+}
+
+/// Drops events of other projects, and live events already included in the replay.
+#[derive(Debug)]
+struct LiveEventFilter {
+    project_id: String,
+    /// `seq` of the last replayed event.
+    replayed_seq: u64,
+}
+
+impl LiveEventFilter {
+    fn keep(&self, event: &PotterProjectEvent) -> bool {
+        event.project_id.as_deref() == Some(self.project_id.as_str())
+            && event.seq.is_none_or(|seq| seq > self.replayed_seq)
+    }
+
+    fn keep_all(&self, events: Vec<PotterProjectEvent>) -> Vec<Event> {
+        events
+            .into_iter()
+            .filter(|event| self.keep(event))
+            .map(|event| event.event)
+            .collect()
+    }
+}
+
+impl crate::workflow::project_render_loop::PotterEventSource for AttachedProject<'_> {
+    fn read_next_event<'a>(&'a mut self) -> UiFuture<'a, Option<Event>> {
+        Box::pin(async move {
+            loop {
+                let Some(event) = self.client.read_next_project_event().await? else {
+                    return Ok(None);
+                };
+                if self.filter.keep(&event) {
+                    return Ok(Some(event.event));
+                }
+            }
+        })
+    }
+}
+
+impl crate::workflow::project_render_loop::PotterProjectController for AttachedProject<'_> {
+    fn interrupt_project<'a>(&'a mut self, project_id: String) -> UiFuture<'a, Vec<Event>> {
+        Box::pin(async move {
+            let mut buffered_events: Vec<PotterProjectEvent> = Vec::new();
+            self.client
+                .project_interrupt(
+                    crate::app_server::potter::ProjectInterruptParams { project_id },
+                    &mut buffered_events,
+                )
+                .await?;
+            Ok(self.filter.keep_all(buffered_events))
+        })
+    }
+
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        response: crate::app_server::potter::ApprovalRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move { self.client.answer_approval(&approval_id, response).await })
+    }
+
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        response: crate::app_server::potter::UserInputRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move { self.client.answer_user_input(&request_id, response).await })
+    }
+}
+
+impl crate::workflow::project_runner::ProjectAppServer for AttachedProject<'_> {
+    fn project_start<'a>(
+        &'a mut self,
+        _params: crate::app_server::potter::ProjectStartParams,
+    ) -> UiFuture<'a, (crate::app_server::potter::ProjectStartResponse, Vec<Event>)> {
+        Box::pin(
+            async move { anyhow::bail!("internal error: attached clients do not start projects") },
+        )
+    }
+
+    fn project_interrupt<'a>(&'a mut self, project_id: String) -> UiFuture<'a, ()> {
+        Box::pin(async move {
+            let mut ignored_events: Vec<PotterProjectEvent> = Vec::new();
+            self.client
+                .project_interrupt(
+                    crate::app_server::potter::ProjectInterruptParams { project_id },
+                    &mut ignored_events,
+                )
+                .await?;
+            Ok(())
+        })
+    }
+
+    // Attached clients render one project and never pick the next prompt.
+    fn take_queued_prompt<'a>(&'a mut self) -> UiFuture<'a, Option<String>> {
+        Box::pin(async move { Ok(None) })
+    }
+
+    fn project_resolve_interrupt<'a>(
+        &'a mut self,
+        params: crate::app_server::potter::ProjectResolveInterruptParams,
+    ) -> UiFuture<
+        'a,
+        (
+            crate::app_server::potter::ProjectResolveInterruptResponse,
+            Vec<Event>,
+        ),
+    > {
+        Box::pin(async move {
+            let mut buffered_events: Vec<PotterProjectEvent> = Vec::new();
+            let response = self
+                .client
+                .project_resolve_interrupt(params, &mut buffered_events)
+                .await?;
+            Ok((response, self.filter.keep_all(buffered_events)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::EventMsg;
+
+    fn event(project_id: &str, seq: Option<u64>) -> PotterProjectEvent {
+        PotterProjectEvent {
+            project_id: Some(project_id.to_string()),
+            seq,
+            event: Event {
+                id: String::new(),
+                msg: EventMsg::ShutdownComplete,
+            },
+        }
+    }
+
+    #[test]
+    fn live_events_already_replayed_or_of_other_projects_are_dropped() {
+        let filter = LiveEventFilter {
+            project_id: "/repo/MAIN.md".to_string(),
+            replayed_seq: 7,
+        };
+        assert!(!filter.keep(&event("/repo/MAIN.md", Some(7))));
+        assert!(filter.keep(&event("/repo/MAIN.md", Some(8))));
+        // Server requests carry no `seq`.
+        assert!(filter.keep(&event("/repo/MAIN.md", None)));
+        assert!(!filter.keep(&event("/other/MAIN.md", Some(9))));
+    }
+}
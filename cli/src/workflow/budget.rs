@@ -0,0 +1,291 @@
+//! Token and wall-clock project budgets (`--max-tokens`, `--max-duration`).
+//!
+//! The control plane sums the `total_token_usage` reported by each round's `TokenCount` events
+//! (every round runs in a fresh thread, so the per-round totals add up) and measures wall time
+//! from `project_started_at`. When a budget runs out mid-round the round is interrupted the same
+//! way as an Esc press, but instead of waiting for the user the project ends with
+//! `PotterProjectOutcome::TokenBudgetExhausted` / `TimeBudgetExhausted`.
+//!
+//! Budgets apply to a single run: resuming a project starts fresh counters.
+
+use std::num::NonZeroU64;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterProjectOutcome;
+
+/// Tracks token and wall-clock usage against the configured limits for one project run.
+#[derive(Debug, Clone)]
+pub struct ProjectBudget {
+    max_tokens: Option<NonZeroU64>,
+    max_duration: Option<Duration>,
+    project_started_at: Instant,
+    /// Tokens used by rounds that already finished.
+    finished_rounds_tokens: u64,
+    /// Tokens used so far by the current round.
+    round_tokens: u64,
+}
+
+impl ProjectBudget {
+    /// Returns `None` when neither budget is configured.
+    pub fn new(
+        max_tokens: Option<NonZeroU64>,
+        max_duration: Option<Duration>,
+        project_started_at: Instant,
+    ) -> Option<Self> {
+        if max_tokens.is_none() && max_duration.is_none() {
+            return None;
+        }
+        Some(Self {
+            max_tokens,
+            max_duration,
+            project_started_at,
+            finished_rounds_tokens: 0,
+            round_tokens: 0,
+        })
+    }
+
+    /// Fold the previous round's usage into the project total before a new round starts.
+    pub fn start_round(&mut self) {
+        self.finished_rounds_tokens = self.tokens_used();
+        self.round_tokens = 0;
+    }
+
+    /// Record the current round's cumulative token usage (from `TokenCount`).
+    pub fn observe_round_tokens(&mut self, total_tokens: i64) {
+        self.round_tokens = u64::try_from(total_tokens).unwrap_or(0);
+    }
+
+    pub fn tokens_used(&self) -> u64 {
+        self.finished_rounds_tokens
+            .saturating_add(self.round_tokens)
+    }
+
+    /// Instant at which `--max-duration` runs out.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.max_duration
+            .and_then(|max_duration| self.project_started_at.checked_add(max_duration))
+    }
+
+    /// Returns the outcome to finish the project with once a budget has run out.
+    ///
+    /// The token budget wins when both are exhausted at the same time.
+    pub fn exhausted_at(&self, now: Instant) -> Option<PotterProjectOutcome> {
+        let tokens_used = self.tokens_used();
+        if let Some(max_tokens) = self.max_tokens
+            && tokens_used >= max_tokens.get()
+        {
+            return Some(PotterProjectOutcome::TokenBudgetExhausted {
+                tokens_used,
+                max_tokens: max_tokens.get(),
+            });
+        }
+
+        let elapsed = now.saturating_duration_since(self.project_started_at);
+        if let Some(max_duration) = self.max_duration
+            && elapsed >= max_duration
+        {
+            return Some(PotterProjectOutcome::TimeBudgetExhausted {
+                elapsed_secs: elapsed.as_secs(),
+                max_duration_secs: max_duration.as_secs(),
+            });
+        }
+
+        None
+    }
+
+    /// Remaining budget marker for the UI status header.
+    pub fn status_event_at(&self, now: Instant) -> EventMsg {
+        EventMsg::PotterProjectBudget {
+            tokens_remaining: self
+                .max_tokens
+                .map(|max_tokens| max_tokens.get().saturating_sub(self.tokens_used())),
+            time_remaining: self
+                .deadline()
+                .map(|deadline| deadline.saturating_duration_since(now)),
+        }
+    }
+}
+
+/// Parse a `--max-duration` value such as `90m`, `2h`, `1h30m`, or `45s`.
+///
+/// A bare number is interpreted as seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("duration must not be empty".to_string());
+    }
+    if let Ok(secs) = value.parse::<u64>() {
+        return non_zero_duration(Duration::from_secs(secs));
+    }
+
+    let mut total_secs: u64 = 0;
+    let mut digits = String::new();
+    for ch in value.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit_secs = match ch {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("invalid duration `{value}`: unknown unit `{ch}`")),
+        };
+        let amount = digits
+            .parse::<u64>()
+            .map_err(|_| format!("invalid duration `{value}`: expected a number before `{ch}`"))?;
+        digits.clear();
+        total_secs = amount
+            .checked_mul(unit_secs)
+            .and_then(|secs| total_secs.checked_add(secs))
+            .ok_or_else(|| format!("invalid duration `{value}`: too large"))?;
+    }
+    if !digits.is_empty() {
+        return Err(format!(
+            "invalid duration `{value}`: missing unit after `{digits}` (use h, m, or s)"
+        ));
+    }
+
+    non_zero_duration(Duration::from_secs(total_secs))
+}
+
+fn non_zero_duration(duration: Duration) -> Result<Duration, String> {
+    if duration.is_zero() {
+        return Err("duration must be greater than zero".to_string());
+    }
+    Ok(duration)
+}
+
+/// Format a duration in the syntax accepted by [`parse_duration`] (e.g. `1h30m`).
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    let mut out = String::new();
+    if hours > 0 {
+        out.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{minutes}m"));
+    }
+    if seconds > 0 || out.is_empty() {
+        out.push_str(&format!("{seconds}s"));
+    }
+    out
+}
+
+/// Human-readable reason for a `TokenBudgetExhausted` / `TimeBudgetExhausted` outcome.
+pub fn describe_budget_exhaustion(outcome: &PotterProjectOutcome) -> Option<String> {
+    match outcome {
+        PotterProjectOutcome::TokenBudgetExhausted {
+            tokens_used,
+            max_tokens,
+        } => Some(format!(
+            "used {tokens_used} tokens (--max-tokens {max_tokens})"
+        )),
+        PotterProjectOutcome::TimeBudgetExhausted {
+            elapsed_secs,
+            max_duration_secs,
+        } => Some(format!(
+            "ran for {} (--max-duration {})",
+            format_duration(Duration::from_secs(*elapsed_secs)),
+            format_duration(Duration::from_secs(*max_duration_secs)),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_duration_accepts_units_and_bare_seconds() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("90m"), Ok(Duration::from_secs(90 * 60)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(2 * 3600)));
+        assert_eq!(
+            parse_duration("1h30m15s"),
+            Ok(Duration::from_secs(3600 + 30 * 60 + 15))
+        );
+
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn format_duration_round_trips() {
+        for secs in [1, 59, 60, 3600, 5400, 3661] {
+            let duration = Duration::from_secs(secs);
+            assert_eq!(parse_duration(&format_duration(duration)), Ok(duration));
+        }
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+    }
+
+    #[test]
+    fn token_budget_sums_rounds() {
+        let started_at = Instant::now();
+        let mut budget =
+            ProjectBudget::new(NonZeroU64::new(1000), None, started_at).expect("budget");
+
+        budget.start_round();
+        budget.observe_round_tokens(400);
+        budget.observe_round_tokens(600);
+        assert_eq!(budget.exhausted_at(started_at), None);
+
+        budget.start_round();
+        budget.observe_round_tokens(300);
+        assert_eq!(budget.tokens_used(), 900);
+        assert!(matches!(
+            budget.status_event_at(started_at),
+            EventMsg::PotterProjectBudget {
+                tokens_remaining: Some(100),
+                time_remaining: None,
+            }
+        ));
+
+        budget.observe_round_tokens(1200);
+        assert_eq!(
+            budget.exhausted_at(started_at),
+            Some(PotterProjectOutcome::TokenBudgetExhausted {
+                tokens_used: 1800,
+                max_tokens: 1000,
+            })
+        );
+    }
+
+    #[test]
+    fn time_budget_runs_out_at_deadline() {
+        let started_at = Instant::now();
+        let budget =
+            ProjectBudget::new(None, Some(Duration::from_secs(60)), started_at).expect("budget");
+
+        assert_eq!(
+            budget.deadline(),
+            Some(started_at + Duration::from_secs(60))
+        );
+        assert_eq!(
+            budget.exhausted_at(started_at + Duration::from_secs(59)),
+            None
+        );
+        assert_eq!(
+            budget.exhausted_at(started_at + Duration::from_secs(61)),
+            Some(PotterProjectOutcome::TimeBudgetExhausted {
+                elapsed_secs: 61,
+                max_duration_secs: 60,
+            })
+        );
+    }
+
+    #[test]
+    fn disabled_without_limits() {
+        assert!(ProjectBudget::new(None, None, Instant::now()).is_none());
+    }
+}
@@ -0,0 +1,799 @@
+//! Per-round git checkpoints and rollback.
+//!
+//! At every round boundary the control plane records the current `HEAD` plus a snapshot commit of
+//! the working tree (tracked changes and untracked, non-ignored files) under
+//! `refs/codexpotter/<project>/round-<n>/{started,finished}`, where `<n>` counts the rounds started
+//! in the project across all sessions (resumed sessions restart their displayed round numbers at
+//! 1). The snapshot is built with a temporary index file, so the user's index, working tree and
+//! stash are never touched.
+//!
+//! `codex-potter rollback` restores one of these checkpoints: it resets `HEAD` to the recorded
+//! commit and then re-applies the snapshot as uncommitted changes. `.codexpotter/` is always
+//! excluded so project progress files and logs survive a rollback.
+//!
+//! Checkpointing is best-effort: outside a git repository (or when git fails) no checkpoint is
+//! recorded and the round proceeds normally.
+
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::workflow::git::CODEXPOTTER_DIR_EXCLUDE;
+use crate::workflow::git::git_path;
+use crate::workflow::git::git_stdout;
+use crate::workflow::git::git_stdout_with_env;
+use crate::workflow::rollout::PotterRolloutLine;
+
+const CHECKPOINT_REF_PREFIX: &str = "refs/codexpotter";
+const CHECKPOINT_AUTHOR_NAME: &str = "codex-potter";
+const CHECKPOINT_AUTHOR_EMAIL: &str = "codex-potter@localhost";
+
+/// Git state captured at a round boundary (persisted in `potter-rollout.jsonl`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PotterGitCheckpoint {
+    /// `HEAD` commit at the time of the checkpoint.
+    pub head: String,
+    /// Commit whose tree is the full working tree state (parent: `head`).
+    pub snapshot: String,
+    /// Ref that keeps `snapshot` reachable.
+    pub snapshot_ref: String,
+}
+
+/// Which boundary of a round a checkpoint was taken at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointPhase {
+    Started,
+    Finished,
+}
+
+impl CheckpointPhase {
+    fn as_ref_component(self) -> &'static str {
+        match self {
+            CheckpointPhase::Started => "started",
+            CheckpointPhase::Finished => "finished",
+        }
+    }
+}
+
+/// Build the checkpoint ref name for a round boundary of the project owning `progress_file_rel`.
+///
+/// `project_round` is the project-global round index (see [`project_round_index`]). The project
+/// component is the progress file directory relative to `.codexpotter/projects` (e.g.
+/// `2026/03/04/1`), so refs read `refs/codexpotter/2026/03/04/1/round-3/started`.
+pub fn checkpoint_ref_name(
+    progress_file_rel: &Path,
+    project_round: u32,
+    phase: CheckpointPhase,
+) -> String {
+    format!(
+        "{}/round-{project_round}/{}",
+        project_ref_prefix(progress_file_rel),
+        phase.as_ref_component()
+    )
+}
+
+/// Number of rounds started in the project so far, across all sessions.
+///
+/// Counts the `round_started` lines of `potter-rollout.jsonl`; a missing or unreadable file counts
+/// as no rounds. The round being started next has index `project_round_index(..) + 1`.
+pub fn project_round_index(potter_rollout_path: &Path) -> u32 {
+    crate::workflow::rollout::read_lines(potter_rollout_path)
+        .map(|lines| {
+            let started = lines
+                .iter()
+                .filter(|line| matches!(line, PotterRolloutLine::RoundStarted { .. }))
+                .count();
+            u32::try_from(started).unwrap_or(u32::MAX)
+        })
+        .unwrap_or(0)
+}
+
+fn project_ref_prefix(progress_file_rel: &Path) -> String {
+    let project_dir = progress_file_rel.parent().unwrap_or(progress_file_rel);
+    let project_dir = project_dir
+        .strip_prefix(Path::new(".codexpotter").join("projects"))
+        .unwrap_or(project_dir);
+    let project_id = project_dir
+        .components()
+        .map(|component| sanitize_ref_component(&component.as_os_str().to_string_lossy()))
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{CHECKPOINT_REF_PREFIX}/{project_id}")
+}
+
+fn sanitize_ref_component(component: &str) -> String {
+    let sanitized = component
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_') {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    sanitized.trim_matches('-').to_string()
+}
+
+/// Record a checkpoint for the current state of `workdir`.
+///
+/// Returns `None` when `workdir` is not a git repository with a resolvable `HEAD`, or when any
+/// git step fails.
+pub fn record_checkpoint(workdir: &Path, snapshot_ref: &str) -> Option<PotterGitCheckpoint> {
+    let head = git_stdout(workdir, &["rev-parse", "--verify", "-q", "HEAD"]).ok()?;
+    let snapshot = create_snapshot_commit(workdir, &head, snapshot_ref).ok()?;
+    git_stdout(workdir, &["update-ref", snapshot_ref, &snapshot]).ok()?;
+    Some(PotterGitCheckpoint {
+        head,
+        snapshot,
+        snapshot_ref: snapshot_ref.to_string(),
+    })
+}
+
+/// Fingerprint of the committed and uncommitted state of `workdir`, excluding `.codexpotter/`.
+///
+/// Two fingerprints are equal when neither `HEAD` nor the working tree changed in between. Returns
+/// `None` outside git repositories or when git fails. Nothing is written to the object database
+/// beyond the tree objects `git write-tree` creates.
+pub fn working_tree_fingerprint(workdir: &Path) -> Option<String> {
+    let head = git_stdout(workdir, &["rev-parse", "--verify", "-q", "HEAD"]).ok()?;
+    let tree = write_snapshot_tree(workdir).ok()?;
+    Some(format!("{head}:{tree}"))
+}
+
+fn create_snapshot_commit(
+    workdir: &Path,
+    head: &str,
+    snapshot_ref: &str,
+) -> anyhow::Result<String> {
+    let tree = write_snapshot_tree(workdir)?;
+    let message = format!("codex-potter checkpoint {snapshot_ref}");
+    git_stdout_with_env(
+        workdir,
+        &["commit-tree", &tree, "-p", head, "-m", &message],
+        &[
+            ("GIT_AUTHOR_NAME", CHECKPOINT_AUTHOR_NAME),
+            ("GIT_AUTHOR_EMAIL", CHECKPOINT_AUTHOR_EMAIL),
+            ("GIT_COMMITTER_NAME", CHECKPOINT_AUTHOR_NAME),
+            ("GIT_COMMITTER_EMAIL", CHECKPOINT_AUTHOR_EMAIL),
+        ],
+    )
+}
+
+/// Write a tree object for the working tree (minus `.codexpotter/`) using a temporary index.
+fn write_snapshot_tree(workdir: &Path) -> anyhow::Result<String> {
+    let index_path = git_path(workdir, "index")?;
+    let temp_index = git_path(workdir, "codexpotter-checkpoint.index")?;
+
+    // Seed from the real index so unchanged files keep their cached stat info.
+    if index_path.is_file() {
+        std::fs::copy(&index_path, &temp_index)
+            .with_context(|| format!("copy {}", index_path.display()))?;
+    }
+
+    let temp_index_str = temp_index.to_string_lossy().to_string();
+    let env = [("GIT_INDEX_FILE", temp_index_str.as_str())];
+    let result = git_stdout_with_env(
+        workdir,
+        &["add", "-A", "--", ":/", CODEXPOTTER_DIR_EXCLUDE],
+        &env,
+    )
+    .and_then(|_| git_stdout_with_env(workdir, &["write-tree"], &env));
+
+    let _ = std::fs::remove_file(&temp_index);
+    result
+}
+
+/// Which checkpoint of a round to restore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollbackPoint {
+    /// State right before the round started.
+    RoundStart,
+    /// State right after the round finished.
+    RoundEnd,
+}
+
+/// Result of a successful rollback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackOutcome {
+    pub checkpoint: PotterGitCheckpoint,
+    /// Ref holding a snapshot of the state that was discarded by the rollback.
+    pub backup_ref: String,
+}
+
+/// Find the recorded checkpoint for `round` in a project's rollout log.
+///
+/// `round` is the project-global round index used in the checkpoint ref names (see
+/// [`project_round_index`]): the `round`-th round started in the project, counting every session.
+/// Resumed sessions display their rounds from 1 again, so that number is not used here.
+pub fn find_round_checkpoint(
+    lines: &[PotterRolloutLine],
+    round: u32,
+    point: RollbackPoint,
+) -> Option<PotterGitCheckpoint> {
+    let mut started = 0u32;
+    let mut found = None;
+    for line in lines {
+        match line {
+            PotterRolloutLine::RoundStarted { checkpoint, .. } => {
+                started = started.saturating_add(1);
+                if started == round && point == RollbackPoint::RoundStart {
+                    found = checkpoint.clone();
+                }
+            }
+            PotterRolloutLine::RoundFinished { checkpoint, .. } => {
+                if started == round && point == RollbackPoint::RoundEnd {
+                    found = checkpoint.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Resolve `project_path`, find the checkpoint for `round` and restore it.
+///
+/// This backs `codex-potter rollback`. Projects owned by a live daemon or app-server are refused.
+/// A note is appended to the progress file so later rounds know that recorded work may be gone.
+pub fn run_rollback(
+    cwd: &Path,
+    project_path: &Path,
+    round: u32,
+    point: RollbackPoint,
+) -> anyhow::Result<RollbackOutcome> {
+    let resolved = crate::workflow::resume::resolve_project_paths(cwd, project_path)?;
+    if let Some(daemon) =
+        crate::daemon::live_daemon_for_project(&resolved.workdir, &resolved.progress_file)?
+    {
+        anyhow::bail!(
+            "the project is owned by background project {pid}; stop it first (`codex-potter stop \
+             {pid}`)",
+            pid = daemon.pid
+        );
+    }
+    // Own the project for the whole rollback, so no potter can start rounds in the meantime.
+    let Some(_owner) = crate::workflow::project_owner::claim_project(&resolved.project_dir)? else {
+        anyhow::bail!("the project is running; stop it before rolling back");
+    };
+    let potter_rollout_path = crate::workflow::rollout::potter_rollout_path(&resolved.project_dir);
+    let lines = crate::workflow::rollout::read_lines(&potter_rollout_path)
+        .with_context(|| format!("read {}", potter_rollout_path.display()))?;
+
+    let Some(checkpoint) = find_round_checkpoint(&lines, round, point) else {
+        anyhow::bail!(
+            "no git checkpoint recorded for round {round} in {} (rounds are counted across all \
+             sessions of the project, like the round-<n> checkpoint refs)",
+            potter_rollout_path.display()
+        );
+    };
+
+    let progress_file_rel = resolved
+        .progress_file
+        .strip_prefix(&resolved.workdir)
+        .unwrap_or(&resolved.progress_file)
+        .to_path_buf();
+    // Checkpoints of `--worktree` projects are recorded in (and restored to) the worktree.
+    let repo_dir = crate::workflow::worktree::resolve_project_worktree_dir(
+        &resolved.workdir,
+        &resolved.progress_file,
+    )?
+    .unwrap_or(resolved.workdir.clone());
+    let outcome = rollback_to_checkpoint(&repo_dir, &progress_file_rel, &checkpoint)?;
+    append_rollback_note(
+        &resolved.workdir,
+        &progress_file_rel,
+        round,
+        point,
+        &checkpoint,
+    )
+    .context("append rollback note to progress file")?;
+    Ok(outcome)
+}
+
+/// Restore `workdir` to `checkpoint`.
+///
+/// The current state is snapshotted under a new `<project>/pre-rollback-<n>` ref first (and the
+/// rollback fails without touching the workspace when that is not possible), then
+/// `HEAD` is reset to the checkpoint commit, untracked files are removed, and the checkpoint's uncommitted changes
+/// are re-applied to the working tree (unstaged). `.codexpotter/` is left untouched.
+pub fn rollback_to_checkpoint(
+    workdir: &Path,
+    progress_file_rel: &Path,
+    checkpoint: &PotterGitCheckpoint,
+) -> anyhow::Result<RollbackOutcome> {
+    git_stdout(
+        workdir,
+        &[
+            "cat-file",
+            "-e",
+            &format!("{}^{{commit}}", checkpoint.snapshot),
+        ],
+    )
+    .with_context(|| format!("checkpoint snapshot {} is missing", checkpoint.snapshot))?;
+
+    // Nothing is discarded unless the current state is safe under the backup ref.
+    let backup_ref = next_backup_ref_name(workdir, progress_file_rel)?;
+    let Some(backup) = record_checkpoint(workdir, &backup_ref) else {
+        anyhow::bail!(
+            "could not save the current state under {backup_ref}; the workspace was left as-is"
+        );
+    };
+    let backup_ref = backup.snapshot_ref;
+
+    git_stdout(workdir, &["reset", "-q", "--hard", &checkpoint.head])
+        .context("reset to checkpoint HEAD")?;
+    git_stdout(
+        workdir,
+        &[
+            "clean",
+            "-q",
+            "-f",
+            "-d",
+            "--",
+            ":/",
+            CODEXPOTTER_DIR_EXCLUDE,
+        ],
+    )
+    .context("remove untracked files")?;
+    git_stdout(
+        workdir,
+        &["read-tree", "-u", "--reset", &checkpoint.snapshot],
+    )
+    .context("apply checkpoint snapshot")?;
+    git_stdout(workdir, &["reset", "-q"]).context("unstage checkpoint snapshot")?;
+
+    Ok(RollbackOutcome {
+        checkpoint: checkpoint.clone(),
+        backup_ref,
+    })
+}
+
+/// Name of the ref for the next pre-rollback backup: `<project>/pre-rollback-<n>`, numbered after
+/// the existing backups so earlier ones are kept.
+fn next_backup_ref_name(workdir: &Path, progress_file_rel: &Path) -> anyhow::Result<String> {
+    let prefix = format!("{}/pre-rollback-", project_ref_prefix(progress_file_rel));
+    let refs = git_stdout(
+        workdir,
+        &[
+            "for-each-ref",
+            "--format=%(refname)",
+            &project_ref_prefix(progress_file_rel),
+        ],
+    )
+    .context("list pre-rollback refs")?;
+    let last = refs
+        .lines()
+        .filter_map(|name| name.strip_prefix(&prefix)?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    Ok(format!("{prefix}{}", last.saturating_add(1)))
+}
+
+/// Append a note about a rollback to the progress file, so the next round knows that work
+/// recorded after the checkpoint no longer exists in the workspace.
+pub fn append_rollback_note(
+    workdir: &Path,
+    progress_file_rel: &Path,
+    round: u32,
+    point: RollbackPoint,
+    checkpoint: &PotterGitCheckpoint,
+) -> anyhow::Result<()> {
+    let progress_file = workdir.join(progress_file_rel);
+    let mut contents = std::fs::read_to_string(&progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+
+    let when = match point {
+        RollbackPoint::RoundStart => "the start",
+        RollbackPoint::RoundEnd => "the end",
+    };
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&format!(
+        "\n## Rollback\n\n\
+         The workspace was rolled back to {when} of round {round} (HEAD `{}`). Code changes made \
+         after that point were discarded; re-check `Done` and `In Progress` against the actual \
+         code before continuing.\n",
+        checkpoint.head
+    ));
+
+    std::fs::write(&progress_file, contents)
+        .with_context(|| format!("write {}", progress_file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codex_protocol::protocol::PotterRoundKind;
+    use pretty_assertions::assert_eq;
+
+    fn git(workdir: &Path, args: &[&str]) -> String {
+        git_stdout(workdir, args).unwrap_or_else(|err| panic!("{err:#}"))
+    }
+
+    fn init_repo(workdir: &Path) {
+        git(workdir, &["init", "-q"]);
+        git(workdir, &["config", "user.name", "test"]);
+        git(workdir, &["config", "user.email", "test@example.com"]);
+        std::fs::write(workdir.join("a.txt"), "one\n").expect("write a.txt");
+        git(workdir, &["add", "."]);
+        git(workdir, &["commit", "-q", "-m", "init"]);
+    }
+
+    #[test]
+    fn checkpoint_ref_name_uses_project_path_under_projects_dir() {
+        assert_eq!(
+            checkpoint_ref_name(
+                Path::new(".codexpotter/projects/2026/03/04/1/MAIN.md"),
+                3,
+                CheckpointPhase::Started
+            ),
+            "refs/codexpotter/2026/03/04/1/round-3/started"
+        );
+        assert_eq!(
+            checkpoint_ref_name(Path::new("odd dir/MAIN.md"), 1, CheckpointPhase::Finished),
+            "refs/codexpotter/odd-dir/round-1/finished"
+        );
+    }
+
+    #[test]
+    fn record_checkpoint_returns_none_outside_git_repo() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            record_checkpoint(dir.path(), "refs/codexpotter/x/round-1/started"),
+            None
+        );
+    }
+
+    #[test]
+    fn rollback_restores_committed_and_uncommitted_state() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        init_repo(workdir);
+        let progress_file_rel = Path::new(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        std::fs::create_dir_all(workdir.join(".codexpotter/projects/2026/03/04/1")).expect("mkdir");
+        std::fs::write(workdir.join(progress_file_rel), "# Overall Goal\n").expect("write");
+
+        // Checkpoint with an uncommitted modification and an untracked file.
+        std::fs::write(workdir.join("a.txt"), "two\n").expect("modify a.txt");
+        std::fs::write(workdir.join("new.txt"), "new\n").expect("write new.txt");
+        let checkpoint = record_checkpoint(
+            workdir,
+            &checkpoint_ref_name(progress_file_rel, 1, CheckpointPhase::Finished),
+        )
+        .expect("checkpoint");
+        assert_eq!(checkpoint.head, git(workdir, &["rev-parse", "HEAD"]));
+        assert_eq!(
+            git(workdir, &["status", "--porcelain", "--", "a.txt"]),
+            "M a.txt",
+            "recording a checkpoint must not touch the index"
+        );
+
+        // A later round commits more work and leaves junk behind.
+        git(workdir, &["add", "a.txt", "new.txt"]);
+        git(workdir, &["commit", "-q", "-m", "round 2"]);
+        std::fs::write(workdir.join("a.txt"), "three\n").expect("modify a.txt");
+        std::fs::write(workdir.join("junk.txt"), "junk\n").expect("write junk.txt");
+
+        let outcome =
+            rollback_to_checkpoint(workdir, progress_file_rel, &checkpoint).expect("rollback");
+        assert_eq!(
+            outcome.backup_ref,
+            "refs/codexpotter/2026/03/04/1/pre-rollback-1"
+        );
+
+        assert_eq!(git(workdir, &["rev-parse", "HEAD"]), checkpoint.head);
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("a.txt")).expect("read a.txt"),
+            "two\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("new.txt")).expect("read new.txt"),
+            "new\n"
+        );
+        assert!(!workdir.join("junk.txt").exists());
+        assert!(workdir.join(progress_file_rel).exists());
+
+        // Rolling back again keeps the first backup.
+        std::fs::write(workdir.join("a.txt"), "four\n").expect("modify a.txt");
+        let again =
+            rollback_to_checkpoint(workdir, progress_file_rel, &checkpoint).expect("rollback");
+        assert_eq!(
+            again.backup_ref,
+            "refs/codexpotter/2026/03/04/1/pre-rollback-2"
+        );
+        let backup_1 = git(
+            workdir,
+            &[
+                "rev-parse",
+                "refs/codexpotter/2026/03/04/1/pre-rollback-1:a.txt",
+            ],
+        );
+        assert_eq!(git(workdir, &["cat-file", "-p", &backup_1]), "three");
+    }
+
+    #[test]
+    fn rollback_leaves_workspace_alone_when_backup_fails() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        init_repo(workdir);
+        let progress_file_rel = Path::new(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        let checkpoint = record_checkpoint(
+            workdir,
+            &checkpoint_ref_name(progress_file_rel, 1, CheckpointPhase::Finished),
+        )
+        .expect("checkpoint");
+
+        // An unborn branch has no HEAD, so the current state cannot be backed up.
+        git(workdir, &["checkout", "-q", "--orphan", "unborn"]);
+        std::fs::write(workdir.join("a.txt"), "uncommitted\n").expect("modify a.txt");
+        std::fs::write(workdir.join("new.txt"), "new\n").expect("write new.txt");
+
+        let err = rollback_to_checkpoint(workdir, progress_file_rel, &checkpoint)
+            .expect_err("backup fails");
+        assert_eq!(
+            err.to_string(),
+            "could not save the current state under \
+             refs/codexpotter/2026/03/04/1/pre-rollback-1; the workspace was left as-is"
+        );
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("a.txt")).expect("read a.txt"),
+            "uncommitted\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("new.txt")).expect("read new.txt"),
+            "new\n"
+        );
+        assert_eq!(git(workdir, &["symbolic-ref", "HEAD"]), "refs/heads/unborn");
+    }
+
+    #[test]
+    fn run_rollback_refuses_projects_with_a_live_owner() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        init_repo(workdir);
+        let project_dir = workdir.join(".codexpotter/projects/2026/03/04/1");
+        std::fs::create_dir_all(&project_dir).expect("mkdir");
+        std::fs::write(project_dir.join("MAIN.md"), "# Overall Goal\n").expect("write");
+
+        let _owner = crate::workflow::project_owner::claim_project(&project_dir)
+            .expect("claim")
+            .expect("unowned project");
+        let err = run_rollback(
+            workdir,
+            Path::new("2026/03/04/1"),
+            1,
+            RollbackPoint::RoundEnd,
+        )
+        .expect_err("owned project");
+        assert_eq!(
+            err.to_string(),
+            "the project is running; stop it before rolling back"
+        );
+    }
+
+    #[test]
+    fn working_tree_fingerprint_ignores_codexpotter_dir() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        init_repo(workdir);
+
+        let initial = working_tree_fingerprint(workdir).expect("fingerprint");
+        std::fs::create_dir_all(workdir.join(".codexpotter")).expect("create .codexpotter");
+        std::fs::write(workdir.join(".codexpotter/MAIN.md"), "progress\n").expect("write progress");
+        assert_eq!(working_tree_fingerprint(workdir), Some(initial.clone()));
+
+        std::fs::write(workdir.join("a.txt"), "two\n").expect("write a.txt");
+        let edited = working_tree_fingerprint(workdir).expect("fingerprint");
+        assert_ne!(edited, initial);
+
+        git(workdir, &["commit", "-q", "-am", "edit"]);
+        assert_ne!(working_tree_fingerprint(workdir), Some(edited));
+    }
+
+    #[test]
+    fn find_round_checkpoint_picks_boundary_of_requested_round() {
+        let checkpoint = |name: &str| PotterGitCheckpoint {
+            head: format!("{name}-head"),
+            snapshot: format!("{name}-snapshot"),
+            snapshot_ref: format!("refs/codexpotter/p/{name}"),
+        };
+        let lines = vec![
+            PotterRolloutLine::RoundStarted {
+                current: 1,
+                total: 2,
+                kind: PotterRoundKind::Implement,
+                checkpoint: Some(checkpoint("r1s")),
+            },
+            PotterRolloutLine::RoundFinished {
+                outcome: codex_protocol::protocol::PotterRoundOutcome::Completed,
+                checkpoint: Some(checkpoint("r1f")),
+            },
+            PotterRolloutLine::RoundStarted {
+                current: 2,
+                total: 2,
+                kind: PotterRoundKind::Implement,
+                checkpoint: Some(checkpoint("r2s")),
+            },
+            PotterRolloutLine::RoundFinished {
+                outcome: codex_protocol::protocol::PotterRoundOutcome::Completed,
+                checkpoint: None,
+            },
+        ];
+
+        assert_eq!(
+            find_round_checkpoint(&lines, 1, RollbackPoint::RoundEnd),
+            Some(checkpoint("r1f"))
+        );
+        assert_eq!(
+            find_round_checkpoint(&lines, 2, RollbackPoint::RoundStart),
+            Some(checkpoint("r2s"))
+        );
+        assert_eq!(
+            find_round_checkpoint(&lines, 2, RollbackPoint::RoundEnd),
+            None
+        );
+        assert_eq!(
+            find_round_checkpoint(&lines, 3, RollbackPoint::RoundStart),
+            None
+        );
+    }
+
+    #[test]
+    fn find_round_checkpoint_counts_rounds_across_resumed_sessions() {
+        let checkpoint = |name: &str| PotterGitCheckpoint {
+            head: format!("{name}-head"),
+            snapshot: format!("{name}-snapshot"),
+            snapshot_ref: format!("refs/codexpotter/p/{name}/finished"),
+        };
+        let round = |current: u32, name: &str| {
+            [
+                PotterRolloutLine::RoundStarted {
+                    current,
+                    total: 2,
+                    kind: PotterRoundKind::Implement,
+                    checkpoint: None,
+                },
+                PotterRolloutLine::RoundFinished {
+                    outcome: codex_protocol::protocol::PotterRoundOutcome::Completed,
+                    checkpoint: Some(checkpoint(name)),
+                },
+            ]
+        };
+        // A second session (after `resume`) displays its rounds from 1 again.
+        let lines = [
+            round(1, "round-1"),
+            round(2, "round-2"),
+            round(1, "round-3"),
+        ]
+        .concat();
+
+        assert_eq!(
+            find_round_checkpoint(&lines, 1, RollbackPoint::RoundEnd),
+            Some(checkpoint("round-1"))
+        );
+        assert_eq!(
+            find_round_checkpoint(&lines, 3, RollbackPoint::RoundEnd),
+            Some(checkpoint("round-3"))
+        );
+        assert_eq!(
+            find_round_checkpoint(&lines, 4, RollbackPoint::RoundEnd),
+            None
+        );
+    }
+
+    #[test]
+    fn run_rollback_restores_rounds_of_resumed_sessions() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        init_repo(workdir);
+        let progress_file_rel = Path::new(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        let project_dir = workdir.join(".codexpotter/projects/2026/03/04/1");
+        std::fs::create_dir_all(&project_dir).expect("mkdir");
+        std::fs::write(workdir.join(progress_file_rel), "# Overall Goal\n").expect("write");
+        let rollout_path = crate::workflow::rollout::potter_rollout_path(&project_dir);
+
+        // Two sessions of one round each: both display "round 1".
+        for (contents, session_round) in [("first\n", 1), ("second\n", 1)] {
+            let project_round = project_round_index(&rollout_path) + 1;
+            crate::workflow::rollout::append_line(
+                &rollout_path,
+                &PotterRolloutLine::RoundStarted {
+                    current: session_round,
+                    total: 1,
+                    kind: PotterRoundKind::Implement,
+                    checkpoint: None,
+                },
+            )
+            .expect("append round_started");
+            std::fs::write(workdir.join("a.txt"), contents).expect("write a.txt");
+            let checkpoint = record_checkpoint(
+                workdir,
+                &checkpoint_ref_name(progress_file_rel, project_round, CheckpointPhase::Finished),
+            );
+            crate::workflow::rollout::append_line(
+                &rollout_path,
+                &PotterRolloutLine::RoundFinished {
+                    outcome: codex_protocol::protocol::PotterRoundOutcome::Completed,
+                    checkpoint,
+                },
+            )
+            .expect("append round_finished");
+        }
+        std::fs::write(workdir.join("a.txt"), "later\n").expect("write a.txt");
+
+        let outcome = run_rollback(
+            workdir,
+            Path::new("2026/03/04/1"),
+            1,
+            RollbackPoint::RoundEnd,
+        )
+        .expect("roll back to the first session");
+        assert_eq!(
+            outcome.checkpoint.snapshot_ref,
+            "refs/codexpotter/2026/03/04/1/round-1/finished"
+        );
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("a.txt")).expect("read a.txt"),
+            "first\n"
+        );
+
+        run_rollback(
+            workdir,
+            Path::new("2026/03/04/1"),
+            2,
+            RollbackPoint::RoundEnd,
+        )
+        .expect("roll back to the resumed session");
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("a.txt")).expect("read a.txt"),
+            "second\n"
+        );
+    }
+
+    #[test]
+    fn project_round_index_counts_rounds_across_sessions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("potter-rollout.jsonl");
+        assert_eq!(project_round_index(&path), 0);
+
+        for current in [1, 2, 1] {
+            crate::workflow::rollout::append_line(
+                &path,
+                &PotterRolloutLine::RoundStarted {
+                    current,
+                    total: 2,
+                    kind: PotterRoundKind::Implement,
+                    checkpoint: None,
+                },
+            )
+            .expect("append");
+        }
+        assert_eq!(project_round_index(&path), 3);
+    }
+}
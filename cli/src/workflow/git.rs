@@ -0,0 +1,58 @@
+//! Small helpers for running `git` in a project workdir.
+//!
+//! Shared by the workflow modules that talk to git directly (project metadata, checkpoints and
+//! worktrees) so they agree on error reporting and on which paths belong to CodexPotter.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+
+/// Pathspec excluding `.codexpotter/`, so git operations never touch progress files or logs.
+pub const CODEXPOTTER_DIR_EXCLUDE: &str = ":(exclude).codexpotter";
+
+/// Run `git -C <workdir> <args>` and return its trimmed stdout; fails when git exits non-zero.
+pub fn git_stdout(workdir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    git_stdout_with_env(workdir, args, &[])
+}
+
+/// Like [`git_stdout`], with extra environment variables for the git process.
+pub fn git_stdout_with_env(
+    workdir: &Path,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workdir)
+        .args(args)
+        .envs(envs.iter().copied())
+        .output()
+        .with_context(|| format!("run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Like [`git_stdout`], but `None` when git fails (or is missing) or prints nothing.
+pub fn git_stdout_nonempty(workdir: &Path, args: &[&str]) -> Option<String> {
+    git_stdout(workdir, args)
+        .ok()
+        .filter(|stdout| !stdout.is_empty())
+}
+
+/// Resolve `git rev-parse --git-path <name>` to a path usable from the current process.
+pub fn git_path(workdir: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(git_stdout(workdir, &["rev-parse", "--git-path", name])?);
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        workdir.join(path)
+    })
+}
@@ -8,6 +8,7 @@
 //!   renderer, and persist `potter-rollout.jsonl` for replay.
 //! - **Resume**: read the persisted rollout/progress file, reconstruct the latest known state, and
 //!   replay events into a UI (optionally continuing unfinished work).
+//! - **Attach**: render a project running on a listening app-server (`codex-potter attach`).
 //!
 //! Key artifacts:
 //! - Progress file (`MAIN.md`) with YAML front matter (e.g. `status`, `finite_incantatem`).
@@ -19,13 +20,25 @@
 //! - Backend interactions are handled by `crate::app_server`; workflow consumes the resulting
 //!   `EventMsg` stream and persists/replays it.
 
+pub mod attach;
+pub mod budget;
+pub mod checkpoint;
+pub mod git;
+pub mod parallel_project_runner;
 pub mod project;
+pub mod project_owner;
 pub mod project_render_loop;
 pub mod project_runner;
 pub mod prompt_queue;
+pub mod rate_limit;
+pub mod report;
 pub mod resume;
 pub mod resume_picker_index;
 pub mod rollout;
 pub mod rollout_resume_index;
 mod round_event_bridge;
 pub mod round_runner;
+pub mod round_schedule;
+pub mod stall;
+pub mod verification;
+pub mod worktree;
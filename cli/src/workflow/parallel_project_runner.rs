@@ -0,0 +1,939 @@
+//! Concurrent project queue runner (`--parallel N`).
+//!
+//! Up to N projects run at once on the potter app-server, each in its own git worktree. All of
+//! them share one event stream; [`ParallelEventRouter`] demultiplexes it by `projectId` into
+//! per-project queues so that only the *focused* project is rendered into the transcript while
+//! the others keep buffering.
+//!
+//! Focus moves:
+//! - to the next project at a round boundary when the user presses `Ctrl+O` (see
+//!   [`codex_tui::ProjectStrip`]), and
+//! - to the next project once the focused one completes; its buffered rounds are then replayed.
+//!
+//! Free slots are filled from queued prompts (the UI's, then the server's persistent queue)
+//! whenever control returns to the runner; the composer prompt is only shown when no project is
+//! running.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::Context;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterProjectOutcome;
+use codex_tui::InterruptedProjectAction;
+use codex_tui::ProjectStrip;
+use codex_tui::ProjectStripEntry;
+use codex_tui::ProjectStripState;
+
+use crate::app_server::potter::PotterProjectEvent;
+use crate::workflow::project_render_loop::PotterProjectRenderExit;
+use crate::workflow::project_runner::PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE;
+use crate::workflow::project_runner::ProjectClock;
+use crate::workflow::project_runner::ProjectQueueExit;
+use crate::workflow::project_runner::ProjectQueueOptions;
+use crate::workflow::project_runner::ProjectRunnerUi;
+use crate::workflow::round_runner::UiFuture;
+
+/// App-server operations needed to drive several projects over one event stream.
+///
+/// Unlike the single-project runner, buffered events keep their `projectId` so they can be routed.
+pub(crate) trait ParallelProjectAppServer {
+    fn project_start<'a>(
+        &'a mut self,
+        params: crate::app_server::potter::ProjectStartParams,
+    ) -> UiFuture<
+        'a,
+        (
+            crate::app_server::potter::ProjectStartResponse,
+            Vec<PotterProjectEvent>,
+        ),
+    >;
+
+    fn project_interrupt<'a>(
+        &'a mut self,
+        project_id: String,
+    ) -> UiFuture<'a, Vec<PotterProjectEvent>>;
+
+    fn project_resolve_interrupt<'a>(
+        &'a mut self,
+        params: crate::app_server::potter::ProjectResolveInterruptParams,
+    ) -> UiFuture<
+        'a,
+        (
+            crate::app_server::potter::ProjectResolveInterruptResponse,
+            Vec<PotterProjectEvent>,
+        ),
+    >;
+
+    /// Take the next prompt from the server's persistent queue (atomically, via `queue/take`).
+    fn take_queued_prompt<'a>(
+        &'a mut self,
+    ) -> UiFuture<'a, (Option<String>, Vec<PotterProjectEvent>)>;
+
+    fn read_next_project_event<'a>(&'a mut self) -> UiFuture<'a, Option<PotterProjectEvent>>;
+
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        response: crate::app_server::potter::ApprovalRequestResponse,
+    ) -> UiFuture<'a, ()>;
+
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        response: crate::app_server::potter::UserInputRequestResponse,
+    ) -> UiFuture<'a, ()>;
+}
+
+impl ParallelProjectAppServer for crate::app_server::potter::PotterAppServerClient {
+    fn project_start<'a>(
+        &'a mut self,
+        params: crate::app_server::potter::ProjectStartParams,
+    ) -> UiFuture<
+        'a,
+        (
+            crate::app_server::potter::ProjectStartResponse,
+            Vec<PotterProjectEvent>,
+        ),
+    > {
+        Box::pin(async move {
+            let mut buffered_events = Vec::new();
+            let response = self.project_start(params, &mut buffered_events).await?;
+            Ok((response, buffered_events))
+        })
+    }
+
+    fn project_interrupt<'a>(
+        &'a mut self,
+        project_id: String,
+    ) -> UiFuture<'a, Vec<PotterProjectEvent>> {
+        Box::pin(async move {
+            let mut buffered_events = Vec::new();
+            self.project_interrupt(
+                crate::app_server::potter::ProjectInterruptParams { project_id },
+                &mut buffered_events,
+            )
+            .await?;
+            Ok(buffered_events)
+        })
+    }
+
+    fn project_resolve_interrupt<'a>(
+        &'a mut self,
+        params: crate::app_server::potter::ProjectResolveInterruptParams,
+    ) -> UiFuture<
+        'a,
+        (
+            crate::app_server::potter::ProjectResolveInterruptResponse,
+            Vec<PotterProjectEvent>,
+        ),
+    > {
+        Box::pin(async move {
+            let mut buffered_events = Vec::new();
+            let response = self
+                .project_resolve_interrupt(params, &mut buffered_events)
+                .await?;
+            Ok((response, buffered_events))
+        })
+    }
+
+    fn take_queued_prompt<'a>(
+        &'a mut self,
+    ) -> UiFuture<'a, (Option<String>, Vec<PotterProjectEvent>)> {
+        Box::pin(async move {
+            let mut buffered_events = Vec::new();
+            let prompt = self.take_queued_prompt(&mut buffered_events).await?;
+            Ok((prompt, buffered_events))
+        })
+    }
+
+    fn read_next_project_event<'a>(&'a mut self) -> UiFuture<'a, Option<PotterProjectEvent>> {
+        Box::pin(crate::app_server::potter::PotterAppServerClient::read_next_project_event(self))
+    }
+
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        response: crate::app_server::potter::ApprovalRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move {
+            crate::app_server::potter::PotterAppServerClient::answer_approval(
+                self,
+                &approval_id,
+                response,
+            )
+            .await
+        })
+    }
+
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        response: crate::app_server::potter::UserInputRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move {
+            crate::app_server::potter::PotterAppServerClient::answer_user_input(
+                self,
+                &request_id,
+                response,
+            )
+            .await
+        })
+    }
+}
+
+#[derive(Debug)]
+struct RoutedProject {
+    project_id: String,
+    label: String,
+    state: ProjectStripState,
+    pending_events: VecDeque<Event>,
+}
+
+/// Demultiplexes the shared server event stream into per-project queues and mirrors each
+/// project's lifecycle into the [`ProjectStrip`].
+#[derive(Debug, Default)]
+struct ParallelEventRouter {
+    /// Active projects in start order; focus rotates through this list.
+    projects: Vec<RoutedProject>,
+    focused: Option<String>,
+    strip: ProjectStrip,
+}
+
+impl ParallelEventRouter {
+    fn strip(&self) -> ProjectStrip {
+        self.strip.clone()
+    }
+
+    fn active_count(&self) -> usize {
+        self.projects.len()
+    }
+
+    /// Register a started project, then route the events buffered while starting it.
+    fn add_project(
+        &mut self,
+        project_id: String,
+        label: String,
+        buffered: Vec<PotterProjectEvent>,
+    ) {
+        self.projects.push(RoutedProject {
+            project_id,
+            label,
+            state: ProjectStripState::Starting,
+            pending_events: VecDeque::new(),
+        });
+        self.route_all(buffered);
+    }
+
+    /// Drop a project whose completion has been rendered; focus moves to the project after it.
+    fn remove_project(&mut self, project_id: &str) {
+        let Some(idx) = self.index_of(project_id) else {
+            return;
+        };
+        self.projects.remove(idx);
+        if self.focused.as_deref() == Some(project_id) {
+            self.focused = if self.projects.is_empty() {
+                None
+            } else {
+                Some(self.projects[idx % self.projects.len()].project_id.clone())
+            };
+        }
+        self.publish();
+    }
+
+    /// Focus the first project when nothing is focused yet.
+    fn ensure_focus(&mut self) -> Option<String> {
+        if self.focused.is_none() {
+            self.focused = self
+                .projects
+                .first()
+                .map(|project| project.project_id.clone());
+            self.publish();
+        }
+        self.focused.clone()
+    }
+
+    /// Move focus to the next project in start order (wrapping).
+    fn focus_next(&mut self) -> Option<String> {
+        let next_idx = match self.focused.as_deref().and_then(|id| self.index_of(id)) {
+            Some(idx) => idx + 1,
+            None => 0,
+        };
+        self.focused = if self.projects.is_empty() {
+            None
+        } else {
+            Some(
+                self.projects[next_idx % self.projects.len()]
+                    .project_id
+                    .clone(),
+            )
+        };
+        self.publish();
+        self.focused.clone()
+    }
+
+    fn has_other_project(&self, project_id: &str) -> bool {
+        self.projects
+            .iter()
+            .any(|project| project.project_id != project_id)
+    }
+
+    fn project_ids(&self) -> Vec<String> {
+        self.projects
+            .iter()
+            .map(|project| project.project_id.clone())
+            .collect()
+    }
+
+    fn route_all(&mut self, events: Vec<PotterProjectEvent>) {
+        for event in events {
+            self.route(event);
+        }
+    }
+
+    /// Queue `event` for its project. Untagged events belong to the focused project; events for
+    /// projects that are no longer active are dropped.
+    fn route(&mut self, event: PotterProjectEvent) {
+        let PotterProjectEvent {
+            project_id, event, ..
+        } = event;
+        let Some(project_id) = project_id.or_else(|| self.focused.clone()) else {
+            return;
+        };
+        let Some(idx) = self.index_of(&project_id) else {
+            return;
+        };
+
+        let project = &mut self.projects[idx];
+        let state = match &event.msg {
+            EventMsg::PotterRoundStarted { current, total, .. } => Some(ProjectStripState::Round {
+                current: *current,
+                total: *total,
+            }),
+            EventMsg::PotterProjectInterrupted { .. } => Some(ProjectStripState::Interrupted),
+            EventMsg::PotterProjectAwaitingPlanApproval { .. } => {
+                Some(ProjectStripState::AwaitingPlanApproval)
+            }
+            EventMsg::PotterProjectCompleted { outcome } => {
+                Some(ProjectStripState::Completed(outcome.clone()))
+            }
+            _ => None,
+        };
+        project.pending_events.push_back(event);
+        if let Some(state) = state {
+            project.state = state;
+            self.publish();
+        }
+    }
+
+    fn pop_event(&mut self, project_id: &str) -> Option<Event> {
+        let idx = self.index_of(project_id)?;
+        self.projects[idx].pending_events.pop_front()
+    }
+
+    fn index_of(&self, project_id: &str) -> Option<usize> {
+        self.projects
+            .iter()
+            .position(|project| project.project_id == project_id)
+    }
+
+    fn publish(&self) {
+        let focused = self.focused.as_deref();
+        self.strip.set_entries(
+            self.projects
+                .iter()
+                .map(|project| ProjectStripEntry {
+                    label: project.label.clone(),
+                    state: project.state.clone(),
+                    focused: focused == Some(project.project_id.as_str()),
+                })
+                .collect(),
+        );
+    }
+}
+
+/// Event source for the focused project: serves its queued events first and routes everything
+/// else read from the server to the other projects' queues.
+struct FocusedProjectEventSource<'s, S> {
+    app_server: &'s mut S,
+    router: &'s mut ParallelEventRouter,
+    project_id: String,
+}
+
+impl<S> crate::workflow::project_render_loop::PotterEventSource for FocusedProjectEventSource<'_, S>
+where
+    S: ParallelProjectAppServer,
+{
+    fn read_next_event<'a>(&'a mut self) -> UiFuture<'a, Option<Event>> {
+        Box::pin(async move {
+            loop {
+                if let Some(event) = self.router.pop_event(&self.project_id) {
+                    return Ok(Some(event));
+                }
+                let Some(event) = self.app_server.read_next_project_event().await? else {
+                    return Ok(None);
+                };
+                self.router.route(event);
+            }
+        })
+    }
+
+    fn take_detach_request(&mut self) -> bool {
+        self.router.strip.take_focus_next_request()
+            && self.router.has_other_project(&self.project_id)
+    }
+}
+
+impl<S> crate::workflow::project_render_loop::PotterProjectController
+    for FocusedProjectEventSource<'_, S>
+where
+    S: ParallelProjectAppServer,
+{
+    fn interrupt_project<'a>(&'a mut self, project_id: String) -> UiFuture<'a, Vec<Event>> {
+        Box::pin(async move {
+            let buffered_events = self.app_server.project_interrupt(project_id).await?;
+            // Events for the focused project are served from its queue, in order.
+            self.router.route_all(buffered_events);
+            Ok(Vec::new())
+        })
+    }
+
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        response: crate::app_server::potter::ApprovalRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        self.app_server.answer_approval(approval_id, response)
+    }
+
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        response: crate::app_server::potter::UserInputRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        self.app_server.answer_user_input(request_id, response)
+    }
+}
+
+/// Per-project data the runner needs once a project has been started.
+struct ActiveProject {
+    started_at: Instant,
+    start_response: crate::app_server::potter::ProjectStartResponse,
+    prompt_footer: codex_tui::PromptFooterContext,
+    project_dir: PathBuf,
+    label: String,
+}
+
+/// Run the project queue with up to `options.parallel` projects in flight.
+pub(crate) async fn run_parallel_project_queue<U, S, C>(
+    ui: &mut U,
+    app_server: &mut S,
+    workdir: PathBuf,
+    options: ProjectQueueOptions,
+    clock: &C,
+) -> anyhow::Result<ProjectQueueExit>
+where
+    U: ProjectRunnerUi,
+    S: ParallelProjectAppServer,
+    C: ProjectClock,
+{
+    let mut router = ParallelEventRouter::default();
+    ui.set_project_strip(Some(router.strip()));
+    let exit =
+        run_parallel_project_queue_inner(ui, app_server, &mut router, workdir, options, clock)
+            .await;
+    ui.set_project_strip(None);
+    exit
+}
+
+async fn run_parallel_project_queue_inner<U, S, C>(
+    ui: &mut U,
+    app_server: &mut S,
+    router: &mut ParallelEventRouter,
+    workdir: PathBuf,
+    options: ProjectQueueOptions,
+    clock: &C,
+) -> anyhow::Result<ProjectQueueExit>
+where
+    U: ProjectRunnerUi,
+    S: ParallelProjectAppServer,
+    C: ProjectClock,
+{
+    let rounds_total_u32 = crate::rounds::round_budget_to_u32(options.rounds)?;
+    let mut active: std::collections::BTreeMap<String, ActiveProject> =
+        std::collections::BTreeMap::new();
+    let mut rendered_project_id: Option<String> = None;
+
+    loop {
+        // Fill free slots from queued prompts; only prompt the user when nothing is running.
+        while router.active_count() < options.parallel.get() {
+            let queued_prompt = match ui.pop_queued_user_prompt() {
+                Some(prompt) => Some(prompt),
+                None => match app_server.take_queued_prompt().await {
+                    Ok((prompt, buffered_events)) => {
+                        router.route_all(buffered_events);
+                        prompt
+                    }
+                    Err(err) => {
+                        ui.insert_notice(format!("Skipped the prompt queue: {err:#}"));
+                        None
+                    }
+                },
+            };
+            let user_prompt = match queued_prompt {
+                Some(prompt) => prompt,
+                None if router.active_count() == 0 => {
+                    let prompt_footer = codex_tui::PromptFooterContext::new(
+                        workdir.clone(),
+                        crate::workflow::project::resolve_git_branch(&workdir),
+                    );
+                    let Some(prompt) = ui.prompt_user(prompt_footer).await? else {
+                        return Ok(ProjectQueueExit::Completed);
+                    };
+                    // Clear prompt UI remnants before doing any work / streaming output.
+                    ui.clear()?;
+                    prompt
+                }
+                None => break,
+            };
+
+            let started_at = clock.now_instant();
+            let (start_response, buffered_events) = app_server
+                .project_start(crate::app_server::potter::ProjectStartParams {
+                    user_message: user_prompt,
+                    cwd: Some(workdir.clone()),
+                    rounds: Some(rounds_total_u32),
+                    event_mode: Some(crate::app_server::potter::PotterEventMode::Interactive),
+                    output_schema: None,
+                })
+                .await
+                .context("project/start via potter app-server")?;
+
+            let project_dir = start_response
+                .progress_file_rel
+                .parent()
+                .context("derive project dir from progress file path")?
+                .to_path_buf();
+            let label = project_label(&project_dir);
+            let prompt_footer = codex_tui::PromptFooterContext::new(
+                start_response
+                    .worktree
+                    .clone()
+                    .unwrap_or_else(|| workdir.clone()),
+                start_response.git_branch.clone(),
+            );
+
+            let project_id = start_response.project_id.clone();
+            router.add_project(project_id.clone(), label.clone(), buffered_events);
+            active.insert(
+                project_id,
+                ActiveProject {
+                    started_at,
+                    start_response,
+                    prompt_footer,
+                    project_dir,
+                    label,
+                },
+            );
+        }
+
+        let project_id = router
+            .ensure_focus()
+            .context("internal error: no active project to focus")?;
+        let project = active
+            .get(&project_id)
+            .context("internal error: focused project is not active")?;
+
+        if rendered_project_id.as_deref() != Some(project_id.as_str()) {
+            ui.set_project_started_at(project.started_at);
+            if rendered_project_id.is_some() || router.active_count() > 1 {
+                ui.insert_notice(format!(
+                    "Showing project {} ({})",
+                    project.label,
+                    project.project_dir.display()
+                ));
+            }
+            rendered_project_id = Some(project_id.clone());
+        }
+
+        let render_options = crate::workflow::project_render_loop::PotterProjectRenderOptions {
+            turn_prompt: options.turn_prompt.clone(),
+            prompt_footer: project.prompt_footer.clone(),
+            pad_before_first_cell: false,
+            initial_status_header_prefix: None,
+        };
+        let exit = {
+            let mut event_source = FocusedProjectEventSource {
+                app_server: &mut *app_server,
+                router: &mut *router,
+                project_id: project_id.clone(),
+            };
+            crate::workflow::project_render_loop::run_potter_project_render_loop(
+                ui,
+                &mut event_source,
+                &project_id,
+                render_options,
+                Vec::new(),
+            )
+            .await?
+        };
+
+        match exit {
+            PotterProjectRenderExit::Completed { outcome } => {
+                let project = active
+                    .remove(&project_id)
+                    .context("internal error: completed project is not active")?;
+                router.remove_project(&project_id);
+                if let Some(notice) = crate::workflow::project_runner::early_stop_notice(&outcome) {
+                    ui.insert_notice(format!("Project {} {notice}", project.label));
+                }
+                if matches!(outcome, PotterProjectOutcome::Succeeded)
+                    && let (Some(worktree_dir), Some(branch)) = (
+                        &project.start_response.worktree,
+                        &project.start_response.git_branch,
+                    )
+                {
+                    crate::workflow::worktree::offer_worktree_integration(
+                        ui,
+                        &workdir,
+                        worktree_dir,
+                        branch,
+                    )
+                    .await?;
+                }
+            }
+            PotterProjectRenderExit::Detached => {
+                router.focus_next();
+            }
+            PotterProjectRenderExit::Interrupted { user_prompt_file } => {
+                let action = ui
+                    .prompt_interrupted_project_action(user_prompt_file)
+                    .await?
+                    .unwrap_or(InterruptedProjectAction::StopIterate);
+
+                match action {
+                    InterruptedProjectAction::StopIterate => {
+                        let (response, buffered_events) = app_server
+                            .project_resolve_interrupt(
+                                crate::app_server::potter::ProjectResolveInterruptParams {
+                                    project_id: project_id.clone(),
+                                    action: crate::app_server::potter::ResolveInterruptAction::Stop,
+                                    turn_prompt_override: None,
+                                },
+                            )
+                            .await
+                            .context("project/resolve_interrupt(stop) via potter app-server")?;
+                        router.route_all(buffered_events);
+
+                        let summary = response.summary.context(
+                            "internal error: missing summary for resolve_interrupt(stop)",
+                        )?;
+                        ui.insert_interrupted_project_summary_block(summary);
+                        active.remove(&project_id);
+                        router.remove_project(&project_id);
+                    }
+                    InterruptedProjectAction::ContinueIterate => {
+                        let (response, buffered_events) = app_server
+                            .project_resolve_interrupt(
+                                crate::app_server::potter::ProjectResolveInterruptParams {
+                                    project_id: project_id.clone(),
+                                    action:
+                                        crate::app_server::potter::ResolveInterruptAction::Continue,
+                                    turn_prompt_override: Some(
+                                        PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE.to_string(),
+                                    ),
+                                },
+                            )
+                            .await
+                            .context("project/resolve_interrupt(continue) via potter app-server")?;
+                        anyhow::ensure!(
+                            response.summary.is_none(),
+                            "internal error: resolve_interrupt(continue) returned summary"
+                        );
+                        router.route_all(buffered_events);
+                    }
+                }
+            }
+            PotterProjectRenderExit::AwaitingPlanApproval {
+                user_prompt_file,
+                todo,
+            } => {
+                let approved = crate::workflow::project_runner::review_plan(
+                    ui,
+                    &workdir,
+                    user_prompt_file,
+                    todo,
+                )
+                .await?;
+                let action = if approved {
+                    crate::app_server::potter::ResolveInterruptAction::Continue
+                } else {
+                    crate::app_server::potter::ResolveInterruptAction::Stop
+                };
+                let (response, buffered_events) = app_server
+                    .project_resolve_interrupt(
+                        crate::app_server::potter::ProjectResolveInterruptParams {
+                            project_id: project_id.clone(),
+                            action,
+                            turn_prompt_override: None,
+                        },
+                    )
+                    .await
+                    .context("project/resolve_interrupt via potter app-server")?;
+                router.route_all(buffered_events);
+
+                if !approved {
+                    let summary = response
+                        .summary
+                        .context("internal error: missing summary for resolve_interrupt(stop)")?;
+                    ui.insert_interrupted_project_summary_block(summary);
+                    active.remove(&project_id);
+                    router.remove_project(&project_id);
+                }
+            }
+            PotterProjectRenderExit::UserRequested => {
+                interrupt_all_projects(app_server, router).await;
+                let project_dir = active
+                    .remove(&project_id)
+                    .map(|project| project.project_dir)
+                    .context("internal error: focused project is not active")?;
+                return Ok(ProjectQueueExit::UserRequestedExit { project_dir });
+            }
+            PotterProjectRenderExit::FatalExitRequested => {
+                interrupt_all_projects(app_server, router).await;
+                return Ok(ProjectQueueExit::FatalExitRequested);
+            }
+        }
+    }
+}
+
+/// Best-effort: stop every server-side project before exiting.
+async fn interrupt_all_projects<S>(app_server: &mut S, router: &ParallelEventRouter)
+where
+    S: ParallelProjectAppServer,
+{
+    for project_id in router.project_ids() {
+        let _ = app_server.project_interrupt(project_id).await;
+    }
+}
+
+/// Short strip label for a project, e.g. `#3` for `.codexpotter/projects/2026/02/01/3`.
+fn project_label(project_dir: &std::path::Path) -> String {
+    match project_dir.file_name() {
+        Some(name) => format!("#{}", name.to_string_lossy()),
+        None => project_dir.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codex_protocol::protocol::PotterRoundKind;
+    use codex_protocol::protocol::PotterRoundOutcome;
+    use pretty_assertions::assert_eq;
+
+    fn tagged(project_id: &str, msg: EventMsg) -> PotterProjectEvent {
+        PotterProjectEvent {
+            project_id: Some(project_id.to_string()),
+            seq: None,
+            event: Event {
+                id: String::new(),
+                msg,
+            },
+        }
+    }
+
+    fn round_started(project_id: &str, current: u32) -> PotterProjectEvent {
+        tagged(
+            project_id,
+            EventMsg::PotterRoundStarted {
+                current,
+                total: 3,
+                kind: PotterRoundKind::Implement,
+            },
+        )
+    }
+
+    fn router_with_two_projects() -> ParallelEventRouter {
+        let mut router = ParallelEventRouter::default();
+        router.add_project("project_1".to_string(), "#1".to_string(), Vec::new());
+        router.add_project("project_2".to_string(), "#2".to_string(), Vec::new());
+        router.ensure_focus();
+        router
+    }
+
+    #[test]
+    fn route_queues_events_per_project() {
+        let mut router = router_with_two_projects();
+        router.route(round_started("project_2", 1));
+        router.route(round_started("project_1", 1));
+        router.route(PotterProjectEvent {
+            project_id: None,
+            seq: None,
+            event: Event {
+                id: String::new(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::Completed,
+                },
+            },
+        });
+        router.route(round_started("project_gone", 1));
+
+        assert!(matches!(
+            router.pop_event("project_1").map(|event| event.msg),
+            Some(EventMsg::PotterRoundStarted { current: 1, .. })
+        ));
+        assert!(matches!(
+            router.pop_event("project_1").map(|event| event.msg),
+            Some(EventMsg::PotterRoundFinished { .. })
+        ));
+        assert!(router.pop_event("project_1").is_none());
+        assert!(matches!(
+            router.pop_event("project_2").map(|event| event.msg),
+            Some(EventMsg::PotterRoundStarted { current: 1, .. })
+        ));
+        assert!(router.pop_event("project_2").is_none());
+        assert!(router.pop_event("project_gone").is_none());
+    }
+
+    #[test]
+    fn strip_tracks_project_state_and_focus() {
+        let mut router = router_with_two_projects();
+        let strip = router.strip();
+        router.route(round_started("project_1", 2));
+        router.route(tagged(
+            "project_2",
+            EventMsg::PotterProjectCompleted {
+                outcome: PotterProjectOutcome::Succeeded,
+            },
+        ));
+        assert_eq!(router.focus_next(), Some("project_2".to_string()));
+
+        let mut expected = vec![
+            ProjectStripEntry {
+                label: "#1".to_string(),
+                state: ProjectStripState::Round {
+                    current: 2,
+                    total: 3,
+                },
+                focused: false,
+            },
+            ProjectStripEntry {
+                label: "#2".to_string(),
+                state: ProjectStripState::Completed(PotterProjectOutcome::Succeeded),
+                focused: true,
+            },
+        ];
+        assert_eq!(strip.entries(), expected);
+
+        router.remove_project("project_2");
+        assert_eq!(router.focused.as_deref(), Some("project_1"));
+        expected.truncate(1);
+        expected[0].focused = true;
+        assert_eq!(strip.entries(), expected);
+        assert!(!strip.is_multi_project());
+    }
+
+    #[test]
+    fn focus_request_detaches_only_when_another_project_is_active() {
+        struct NoServer;
+
+        impl ParallelProjectAppServer for NoServer {
+            fn project_start<'a>(
+                &'a mut self,
+                _params: crate::app_server::potter::ProjectStartParams,
+            ) -> UiFuture<
+                'a,
+                (
+                    crate::app_server::potter::ProjectStartResponse,
+                    Vec<PotterProjectEvent>,
+                ),
+            > {
+                Box::pin(async { anyhow::bail!("unexpected project/start") })
+            }
+
+            fn project_interrupt<'a>(
+                &'a mut self,
+                _project_id: String,
+            ) -> UiFuture<'a, Vec<PotterProjectEvent>> {
+                Box::pin(async { Ok(Vec::new()) })
+            }
+
+            fn project_resolve_interrupt<'a>(
+                &'a mut self,
+                _params: crate::app_server::potter::ProjectResolveInterruptParams,
+            ) -> UiFuture<
+                'a,
+                (
+                    crate::app_server::potter::ProjectResolveInterruptResponse,
+                    Vec<PotterProjectEvent>,
+                ),
+            > {
+                Box::pin(async { anyhow::bail!("unexpected project/resolve_interrupt") })
+            }
+
+            fn take_queued_prompt<'a>(
+                &'a mut self,
+            ) -> UiFuture<'a, (Option<String>, Vec<PotterProjectEvent>)> {
+                Box::pin(async { Ok((None, Vec::new())) })
+            }
+
+            fn read_next_project_event<'a>(
+                &'a mut self,
+            ) -> UiFuture<'a, Option<PotterProjectEvent>> {
+                Box::pin(async { Ok(None) })
+            }
+
+            fn answer_approval<'a>(
+                &'a mut self,
+                _approval_id: String,
+                _response: crate::app_server::potter::ApprovalRequestResponse,
+            ) -> UiFuture<'a, ()> {
+                Box::pin(async { anyhow::bail!("unexpected approval answer") })
+            }
+
+            fn answer_user_input<'a>(
+                &'a mut self,
+                _request_id: String,
+                _response: crate::app_server::potter::UserInputRequestResponse,
+            ) -> UiFuture<'a, ()> {
+                Box::pin(async { anyhow::bail!("unexpected user input answer") })
+            }
+        }
+
+        use crate::workflow::project_render_loop::PotterEventSource;
+
+        let mut router = router_with_two_projects();
+        let strip = router.strip();
+        let mut app_server = NoServer;
+
+        strip.request_focus_next();
+        let mut source = FocusedProjectEventSource {
+            app_server: &mut app_server,
+            router: &mut router,
+            project_id: "project_1".to_string(),
+        };
+        assert!(source.take_detach_request());
+        assert!(!source.take_detach_request());
+
+        router.remove_project("project_2");
+        strip.request_focus_next();
+        let mut source = FocusedProjectEventSource {
+            app_server: &mut app_server,
+            router: &mut router,
+            project_id: "project_1".to_string(),
+        };
+        assert!(!source.take_detach_request());
+    }
+
+    #[test]
+    fn project_label_uses_project_number() {
+        assert_eq!(
+            project_label(std::path::Path::new(".codexpotter/projects/2026/02/01/3")),
+            "#3"
+        );
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module owns the on-disk bootstrap for a new CodexPotter project:
 //! - Create `.codexpotter/projects/YYYY/MM/DD/N/MAIN.md` from prompt templates.
-//! - Record git metadata into YAML front matter (`git_commit`, `git_branch`).
+//! - Record git metadata into YAML front matter (`git_commit`, `git_branch`, and `worktree` for
+//!   `--worktree` projects).
 //! - Provide helpers to read/update selected front matter keys (for example
 //!   `finite_incantatem`).
 //!
@@ -12,12 +13,13 @@
 
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Command;
 
 use anyhow::Context;
 use chrono::DateTime;
 use chrono::Local;
 
+use crate::workflow::git::git_stdout_nonempty;
+
 const PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/prompts/project_main.md"
@@ -28,19 +30,46 @@ const DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
 ));
 const PROMPT_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/prompts/prompt.md"));
+const REVIEW_DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/review_developer_prompt.md"
+));
+const REVIEW_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/review_prompt.md"
+));
+const REPORT_DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/report_developer_prompt.md"
+));
+const REPORT_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/report_prompt.md"
+));
 
 #[derive(Debug, Clone)]
 pub struct ProjectInit {
     pub progress_file_rel: PathBuf,
     pub git_commit_start: String,
+    /// Dedicated git worktree the project runs in (`--worktree` mode).
+    pub worktree: Option<crate::workflow::worktree::ProjectWorktree>,
 }
 
+/// Create a new project under `workdir`.
+///
+/// With `worktree`, a dedicated git worktree and branch are created from the current `HEAD` (see
+/// [`crate::workflow::worktree`]); this requires `workdir` to be a git repository with at least
+/// one commit.
 pub fn init_project(
     workdir: &Path,
     user_prompt: &str,
     now: DateTime<Local>,
+    worktree: bool,
 ) -> anyhow::Result<ProjectInit> {
     let (git_commit, git_branch) = resolve_git_metadata(workdir);
+    if worktree && git_commit.is_empty() {
+        anyhow::bail!("`--worktree` requires a git repository with at least one commit");
+    }
 
     let codexpotter_dir = workdir.join(".codexpotter");
     let projects_root = codexpotter_dir.join("projects");
@@ -56,19 +85,50 @@ pub fn init_project(
     let (project_dir, progress_file_rel) =
         create_next_project_dir(&projects_root, &year, &month, &day)?;
 
+    let worktree = if worktree {
+        match create_project_worktree(workdir, &progress_file_rel) {
+            Ok(worktree) => Some(worktree),
+            Err(err) => {
+                let _ = std::fs::remove_dir_all(&project_dir);
+                return Err(err);
+            }
+        }
+    } else {
+        None
+    };
+
     let main_md = project_dir.join("MAIN.md");
-    let main_md_contents = render_project_main(user_prompt, &git_commit, &git_branch);
+    let main_md_contents = match &worktree {
+        Some(worktree) => {
+            let contents = render_project_main(user_prompt, &git_commit, &worktree.branch);
+            append_front_matter_string(&contents, "worktree", &worktree.dir_rel.to_string_lossy())?
+        }
+        None => render_project_main(user_prompt, &git_commit, &git_branch),
+    };
     std::fs::write(&main_md, main_md_contents)
         .with_context(|| format!("write {}", main_md.display()))?;
 
     Ok(ProjectInit {
         progress_file_rel,
         git_commit_start: git_commit,
+        worktree,
     })
 }
 
+fn create_project_worktree(
+    workdir: &Path,
+    progress_file_rel: &Path,
+) -> anyhow::Result<crate::workflow::worktree::ProjectWorktree> {
+    let project_dir_rel = progress_file_rel
+        .parent()
+        .context("derive project dir from progress file path")?;
+    let worktree = crate::workflow::worktree::project_worktree_for_project_dir(project_dir_rel)?;
+    crate::workflow::worktree::create_worktree(workdir, &worktree)?;
+    Ok(worktree)
+}
+
 pub fn resolve_git_commit(workdir: &Path) -> String {
-    git_stdout_trimmed(workdir, &["rev-parse", "HEAD"]).unwrap_or_default()
+    git_stdout_nonempty(workdir, &["rev-parse", "HEAD"]).unwrap_or_default()
 }
 
 /// Resolve the current git branch name for `workdir`.
@@ -76,7 +136,7 @@ pub fn resolve_git_commit(workdir: &Path) -> String {
 /// Returns `None` when `workdir` is not a git repository, `HEAD` is detached, or when git is not
 /// available.
 pub fn resolve_git_branch(workdir: &Path) -> Option<String> {
-    git_stdout_trimmed(workdir, &["symbolic-ref", "-q", "--short", "HEAD"])
+    git_stdout_nonempty(workdir, &["symbolic-ref", "-q", "--short", "HEAD"])
 }
 
 pub fn render_project_main(user_prompt: &str, git_commit: &str, git_branch: &str) -> String {
@@ -98,6 +158,28 @@ pub fn fixed_prompt() -> &'static str {
     PROMPT_TEMPLATE
 }
 
+/// Render the developer prompt for review rounds (see [`crate::workflow::round_schedule`]).
+pub fn render_review_developer_prompt(progress_file_rel: &Path) -> String {
+    let progress_file_rel = progress_file_rel.to_string_lossy();
+    REVIEW_DEVELOPER_PROMPT_TEMPLATE.replace("{{PROGRESS_FILE}}", &progress_file_rel)
+}
+
+/// Turn prompt sent at the start of review rounds.
+pub fn review_prompt() -> &'static str {
+    REVIEW_PROMPT_TEMPLATE
+}
+
+/// Render the developer prompt for the final report turn (see [`crate::workflow::report`]).
+pub fn render_report_developer_prompt(progress_file_rel: &Path) -> String {
+    let progress_file_rel = progress_file_rel.to_string_lossy();
+    REPORT_DEVELOPER_PROMPT_TEMPLATE.replace("{{PROGRESS_FILE}}", &progress_file_rel)
+}
+
+/// Turn prompt of the final report turn.
+pub fn report_prompt() -> &'static str {
+    REPORT_PROMPT_TEMPLATE
+}
+
 pub fn progress_file_has_finite_incantatem_true(
     workdir: &Path,
     progress_file_rel: &Path,
@@ -146,6 +228,103 @@ pub fn progress_file_git_branch(progress_file: &Path) -> anyhow::Result<Option<S
     read_progress_file_front_matter_string(progress_file, "git_branch")
 }
 
+/// Return the `worktree` value (relative to the workdir) recorded in the progress file front
+/// matter.
+pub fn progress_file_worktree(progress_file: &Path) -> anyhow::Result<Option<PathBuf>> {
+    Ok(read_progress_file_front_matter_string(progress_file, "worktree")?.map(PathBuf::from))
+}
+
+/// Return the `status` value (`initial`, `open`, `skip`) recorded in the progress file front
+/// matter.
+pub fn progress_file_status(progress_file: &Path) -> anyhow::Result<Option<String>> {
+    read_progress_file_front_matter_string(progress_file, "status")
+}
+
+/// Return the entries listed under the progress file's `## Todo` section.
+///
+/// List markers (`- `, `* `, `- [ ] `, `- [x] `) are stripped; blank lines are skipped and indented
+/// lines (continuations, nested lists) are joined to the previous entry.
+pub fn progress_file_todo_items(progress_file: &Path) -> anyhow::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+    Ok(markdown_section_items(&contents, "Todo"))
+}
+
+/// Front matter fields and task lists of a progress file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgressFileOverview {
+    pub status: Option<String>,
+    pub short_title: Option<String>,
+    pub finite_incantatem: bool,
+    pub git_commit: Option<String>,
+    pub in_progress: Vec<String>,
+    pub todo: Vec<String>,
+    pub done: Vec<String>,
+}
+
+/// Read the front matter fields and the `In Progress`, `Todo` and `Done` sections of a progress
+/// file.
+pub fn read_progress_file_overview(progress_file: &Path) -> anyhow::Result<ProgressFileOverview> {
+    let contents = std::fs::read_to_string(progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+    let string = |key: &str| {
+        front_matter_string(&contents, key)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    Ok(ProgressFileOverview {
+        status: string("status"),
+        short_title: string("short_title"),
+        finite_incantatem: front_matter_bool(&contents, "finite_incantatem")?.unwrap_or(false),
+        git_commit: string("git_commit"),
+        in_progress: markdown_section_items(&contents, "In Progress"),
+        todo: markdown_section_items(&contents, "Todo"),
+        done: markdown_section_items(&contents, "Done"),
+    })
+}
+
+fn markdown_section_items(contents: &str, heading: &str) -> Vec<String> {
+    let mut in_section = false;
+    let mut items = Vec::new();
+    for line in contents.lines() {
+        if let Some(title) = line.strip_prefix("## ") {
+            in_section = title.trim() == heading;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indented = line.starts_with(char::is_whitespace);
+        let item = line
+            .trim_start()
+            .strip_prefix("- ")
+            .or_else(|| line.trim_start().strip_prefix("* "))
+            .map(|item| {
+                ["[ ] ", "[x] ", "[X] "]
+                    .iter()
+                    .find_map(|checkbox| item.strip_prefix(checkbox))
+                    .unwrap_or(item)
+                    .trim_start()
+            });
+        match item {
+            Some(item) if !indented || items.is_empty() => items.push(item.to_string()),
+            // Indented lines (including nested list items) belong to the previous item.
+            _ => match items.last_mut() {
+                Some(last) => {
+                    last.push(' ');
+                    last.push_str(line.trim());
+                }
+                None => items.push(line.trim().to_string()),
+            },
+        }
+    }
+    items
+}
+
 fn read_progress_file_front_matter_string(
     progress_file: &Path,
     key: &str,
@@ -363,31 +542,26 @@ fn set_front_matter_bool(contents: &str, key: &str, value: bool) -> anyhow::Resu
     Ok(out)
 }
 
-fn resolve_git_metadata(workdir: &Path) -> (String, String) {
-    let git_commit = git_stdout_trimmed(workdir, &["rev-parse", "HEAD"]).unwrap_or_default();
-    let git_branch =
-        git_stdout_trimmed(workdir, &["symbolic-ref", "-q", "--short", "HEAD"]).unwrap_or_default();
-
-    (git_commit, git_branch)
+/// Insert `key: "value"` as the last front matter entry.
+fn append_front_matter_string(contents: &str, key: &str, value: &str) -> anyhow::Result<String> {
+    let body = contents
+        .strip_prefix("---\n")
+        .context("progress file missing YAML front matter delimiter `---` at top")?;
+    let footer_idx = body
+        .find("\n---\n")
+        .context("progress file YAML front matter missing closing `---`")?;
+    let (front_matter, rest) = body.split_at(footer_idx + 1);
+
+    let value = yaml_escape_double_quoted(value);
+    Ok(format!("---\n{front_matter}{key}: \"{value}\"\n{rest}"))
 }
 
-fn git_stdout_trimmed(workdir: &Path, args: &[&str]) -> Option<String> {
-    let output = Command::new("git")
-        .arg("-C")
-        .arg(workdir)
-        .args(args)
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if stdout.is_empty() {
-        return None;
-    }
+fn resolve_git_metadata(workdir: &Path) -> (String, String) {
+    let git_commit = git_stdout_nonempty(workdir, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let git_branch = git_stdout_nonempty(workdir, &["symbolic-ref", "-q", "--short", "HEAD"])
+        .unwrap_or_default();
 
-    Some(stdout)
+    (git_commit, git_branch)
 }
 
 fn yaml_escape_double_quoted(value: &str) -> String {
@@ -409,7 +583,7 @@ mod tests {
             .single()
             .expect("timestamp");
 
-        let first = init_project(temp.path(), "do something", now).expect("init project");
+        let first = init_project(temp.path(), "do something", now, false).expect("init project");
         assert_eq!(
             first.progress_file_rel,
             PathBuf::from(".codexpotter/projects/2026/01/27/1/MAIN.md")
@@ -427,7 +601,8 @@ mod tests {
         assert!(main.contains("git_commit: \"\""));
         assert!(main.contains("git_branch: \"\""));
 
-        let second = init_project(temp.path(), "do something else", now).expect("init project");
+        let second =
+            init_project(temp.path(), "do something else", now, false).expect("init project");
         assert_eq!(
             second.progress_file_rel,
             PathBuf::from(".codexpotter/projects/2026/01/27/2/MAIN.md")
@@ -503,6 +678,103 @@ git_branch: main
         assert_eq!(git_branch, Some("main".to_string()));
     }
 
+    #[test]
+    fn progress_file_overview_reads_front_matter_and_task_lists() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let progress = temp.path().join("MAIN.md");
+        std::fs::write(
+            &progress,
+            r#"---
+status: open
+finite_incantatem: false
+short_title: Fix resume picker search
+git_commit: "abc123"
+git_branch: "main"
+---
+
+# Overall Goal
+
+Fix it.
+
+## In Progress
+
+- Investigate parser
+
+## Todo
+
+- Write docs
+
+## Done
+
+- [x] Reproduce the bug
+  - Added a failing test
+- Read the picker code
+"#,
+        )
+        .expect("write progress file");
+
+        assert_eq!(
+            read_progress_file_overview(&progress).expect("read overview"),
+            ProgressFileOverview {
+                status: Some("open".to_string()),
+                short_title: Some("Fix resume picker search".to_string()),
+                finite_incantatem: false,
+                git_commit: Some("abc123".to_string()),
+                in_progress: vec!["Investigate parser".to_string()],
+                todo: vec!["Write docs".to_string()],
+                done: vec![
+                    "Reproduce the bug - Added a failing test".to_string(),
+                    "Read the picker code".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn progress_file_todo_items_reads_todo_section() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let progress = temp.path().join("MAIN.md");
+        std::fs::write(
+            &progress,
+            r#"---
+status: open
+---
+
+# Overall Goal
+
+Ship it.
+
+## In Progress
+
+- Investigate parser
+
+## Todo
+
+- Add `--review-plan` flag
+* [ ] Render the plan
+  in the TUI
+
+- Write docs
+
+## Done
+"#,
+        )
+        .expect("write progress file");
+
+        let status = progress_file_status(&progress).expect("read status");
+        assert_eq!(status, Some("open".to_string()));
+
+        let todo = progress_file_todo_items(&progress).expect("read todo");
+        assert_eq!(
+            todo,
+            vec![
+                "Add `--review-plan` flag".to_string(),
+                "Render the plan in the TUI".to_string(),
+                "Write docs".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn init_project_writes_git_commit_and_branch_when_in_repo() {
         if Command::new("git").arg("--version").output().is_err() {
@@ -569,8 +841,8 @@ git_branch: main
                 .success()
         );
 
-        let git_commit = git_stdout_trimmed(workdir, &["rev-parse", "HEAD"]).expect("rev-parse");
-        let git_branch = git_stdout_trimmed(workdir, &["symbolic-ref", "-q", "--short", "HEAD"])
+        let git_commit = git_stdout_nonempty(workdir, &["rev-parse", "HEAD"]).expect("rev-parse");
+        let git_branch = git_stdout_nonempty(workdir, &["symbolic-ref", "-q", "--short", "HEAD"])
             .expect("branch");
         assert_eq!(git_branch, "test-branch");
 
@@ -578,7 +850,7 @@ git_branch: main
             .with_ymd_and_hms(2026, 1, 27, 12, 0, 0)
             .single()
             .expect("timestamp");
-        let init = init_project(workdir, "do something", now).expect("init project");
+        let init = init_project(workdir, "do something", now, false).expect("init project");
 
         let main = std::fs::read_to_string(workdir.join(&init.progress_file_rel)).expect("read");
         assert!(main.contains(&format!("git_commit: \"{git_commit}\"")));
@@ -594,13 +866,67 @@ git_branch: main
                 .success()
         );
 
-        let detached = init_project(workdir, "do something else", now).expect("init detached");
+        let detached =
+            init_project(workdir, "do something else", now, false).expect("init detached");
         let main =
             std::fs::read_to_string(workdir.join(&detached.progress_file_rel)).expect("read");
         assert!(main.contains(&format!("git_commit: \"{git_commit}\"")));
         assert!(main.contains("git_branch: \"\""));
     }
 
+    #[test]
+    fn init_project_with_worktree_creates_branch_and_records_it() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path();
+        let now = Local
+            .with_ymd_and_hms(2026, 1, 27, 12, 0, 0)
+            .single()
+            .expect("timestamp");
+
+        let err = init_project(workdir, "do something", now, true).expect_err("not a repo");
+        assert!(format!("{err:#}").contains("requires a git repository"));
+        assert!(!workdir.join(".codexpotter/projects/2026/01/27/1").exists());
+
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.name", "test"],
+            &["config", "user.email", "test@example.com"],
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        ] {
+            assert!(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(workdir)
+                    .args(args)
+                    .status()
+                    .expect("git")
+                    .success()
+            );
+        }
+
+        let init = init_project(workdir, "do something", now, true).expect("init project");
+        assert_eq!(
+            init.worktree,
+            Some(crate::workflow::worktree::ProjectWorktree {
+                dir_rel: PathBuf::from(".codexpotter/worktrees/2026/01/27/1"),
+                branch: "codexpotter/2026-01-27/1".to_string(),
+            })
+        );
+        assert!(workdir.join(".codexpotter/worktrees/2026/01/27/1").is_dir());
+
+        let progress_file = workdir.join(&init.progress_file_rel);
+        let main = std::fs::read_to_string(&progress_file).expect("read");
+        assert!(main.contains("git_branch: \"codexpotter/2026-01-27/1\"\nworktree: \".codexpotter/worktrees/2026/01/27/1\"\n---\n"));
+        assert_eq!(
+            progress_file_worktree(&progress_file).expect("read worktree"),
+            Some(PathBuf::from(".codexpotter/worktrees/2026/01/27/1"))
+        );
+    }
+
     #[test]
     fn progress_file_has_finite_incantatem_true_reads_front_matter() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -0,0 +1,43 @@
+//! Ownership of running projects.
+//!
+//! The potter app-server owning a project (including the one inside a `start --detach` daemon)
+//! holds an exclusive [`crate::file_lock`] on `<project_dir>/owner.lock` from the moment the
+//! project starts or resumes until it completes, including while it is paused. Only one process
+//! can own a project at a time, and commands that rewrite the workspace under a project, like
+//! `codex-potter rollback`, claim it for their own duration. The lock disappears with its holder,
+//! so crashed owners never block anyone.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::file_lock::FileLock;
+
+/// Resolve `owner.lock` within a project directory.
+pub fn project_owner_lock_path(project_dir: &Path) -> PathBuf {
+    project_dir.join("owner.lock")
+}
+
+/// Take ownership of the project in `project_dir` for as long as the returned lock lives.
+///
+/// Returns `None` when another process owns the project.
+pub fn claim_project(project_dir: &Path) -> anyhow::Result<Option<FileLock>> {
+    crate::file_lock::try_lock_exclusive(&project_owner_lock_path(project_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_has_one_owner_at_a_time() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let owner = claim_project(dir.path())
+            .expect("claim")
+            .expect("unowned project");
+        assert!(claim_project(dir.path()).expect("claim").is_none());
+
+        drop(owner);
+        assert!(claim_project(dir.path()).expect("claim").is_some());
+    }
+}
@@ -10,6 +10,13 @@
 //!   reading from the live server stream.
 //! - The project is considered complete only after observing `PotterProjectCompleted`; missing
 //!   that marker is treated as a fatal protocol error.
+//! - Event sources may ask to detach at a round boundary (`--parallel` focus switching); the
+//!   loop then returns [`PotterProjectRenderExit::Detached`] without consuming further events.
+//! - With `--review-plan` the server pauses after the planning round; the loop returns
+//!   [`PotterProjectRenderExit::AwaitingPlanApproval`] at that round boundary.
+//! - Approval answers from the round UI (`Op::PotterApprovalResponse`) are sent back to the
+//!   server through [`PotterProjectController::answer_approval`], and user input answers
+//!   (`Op::PotterUserInputResponse`) through [`PotterProjectController::answer_user_input`].
 
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -29,6 +36,12 @@ use crate::workflow::round_runner::UiFuture;
 pub trait PotterEventSource {
     /// Read the next event from the server stream.
     fn read_next_event<'a>(&'a mut self) -> UiFuture<'a, Option<Event>>;
+
+    /// Returns `true` when the caller wants to stop rendering this project at the next round
+    /// boundary (e.g. to focus another project in `--parallel` mode).
+    fn take_detach_request(&mut self) -> bool {
+        false
+    }
 }
 
 /// Control plane for a running Potter project hosted by `codex-potter app-server`.
@@ -38,6 +51,24 @@ pub trait PotterProjectController {
     /// Returns any events that were emitted while awaiting the JSON-RPC response. Callers must
     /// render these before reading from the live event stream to preserve event ordering.
     fn interrupt_project<'a>(&'a mut self, project_id: String) -> UiFuture<'a, Vec<Event>>;
+
+    /// Answer an `EventMsg::PotterApprovalRequest` shown by the round UI.
+    fn answer_approval<'a>(
+        &'a mut self,
+        approval_id: String,
+        _response: crate::app_server::potter::ApprovalRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move { anyhow::bail!("cannot answer approval request `{approval_id}`") })
+    }
+
+    /// Answer an `EventMsg::PotterUserInputRequest` shown by the round UI.
+    fn answer_user_input<'a>(
+        &'a mut self,
+        request_id: String,
+        _response: crate::app_server::potter::UserInputRequestResponse,
+    ) -> UiFuture<'a, ()> {
+        Box::pin(async move { anyhow::bail!("cannot answer user input request `{request_id}`") })
+    }
 }
 
 /// Options for rendering a running Potter project (multi-round) from an event stream.
@@ -61,6 +92,15 @@ pub enum PotterProjectRenderExit {
     Completed { outcome: PotterProjectOutcome },
     /// The project was interrupted and is waiting for user action.
     Interrupted { user_prompt_file: PathBuf },
+    /// The planning round finished and the project is waiting for the plan to be approved
+    /// (`--review-plan`).
+    AwaitingPlanApproval {
+        user_prompt_file: PathBuf,
+        todo: Vec<String>,
+    },
+    /// Rendering stopped at a round boundary because the event source requested a detach. The
+    /// project keeps running; unread events stay with the event source.
+    Detached,
     /// The user requested exit while a round UI was running.
     UserRequested,
     /// The UI requested a fatal exit.
@@ -88,6 +128,7 @@ where
 
     let mut pending_events = VecDeque::from(buffered_events);
     let mut project_outcome: Option<PotterProjectOutcome> = None;
+    let mut plan_approval: Option<PotterProjectRenderExit> = None;
     let mut rendered_rounds: u32 = 0;
     let mut pending_initial_status_header_prefix = initial_status_header_prefix;
 
@@ -96,6 +137,10 @@ where
             return Ok(PotterProjectRenderExit::Completed { outcome });
         }
 
+        if rendered_rounds != 0 && pending_events.is_empty() && event_source.take_detach_request() {
+            return Ok(PotterProjectRenderExit::Detached);
+        }
+
         let status_header_prefix = if let Some(prefix) = pending_initial_status_header_prefix.take()
         {
             Some(prefix)
@@ -105,11 +150,15 @@ where
                 event_source,
                 &mut pending_events,
                 &mut project_outcome,
+                &mut plan_approval,
             )
             .await?
         };
 
         let Some(status_header_prefix) = status_header_prefix else {
+            if let Some(exit) = plan_approval.take() {
+                return Ok(exit);
+            }
             let outcome = project_outcome
                 .clone()
                 .unwrap_or(PotterProjectOutcome::Fatal {
@@ -179,7 +228,29 @@ where
 
             tokio::select! {
                 Some(op) = op_rx.recv() => {
-                    if matches!(op, Op::Interrupt) && !interrupt_requested {
+                    if let Op::PotterApprovalResponse { approval_id, choice, feedback } = op {
+                        let response = crate::app_server::potter::ApprovalRequestResponse {
+                            decision: choice,
+                            feedback,
+                        };
+                        if let Err(err) = event_source.answer_approval(approval_id, response).await {
+                            let message = format!(
+                                "failed to answer approval request via potter app-server (project_id={project_id}): {err:#}"
+                            );
+                            project_outcome = Some(PotterProjectOutcome::Fatal { message: message.clone() });
+                            let _ = fatal_exit_tx.send(message);
+                        }
+                    } else if let Op::PotterUserInputResponse { request_id, answers } = op {
+                        let response =
+                            crate::app_server::potter::UserInputRequestResponse { answers };
+                        if let Err(err) = event_source.answer_user_input(request_id, response).await {
+                            let message = format!(
+                                "failed to answer user input request via potter app-server (project_id={project_id}): {err:#}"
+                            );
+                            project_outcome = Some(PotterProjectOutcome::Fatal { message: message.clone() });
+                            let _ = fatal_exit_tx.send(message);
+                        }
+                    } else if matches!(op, Op::Interrupt) && !interrupt_requested {
                         match event_source.interrupt_project(project_id.to_string()).await {
                             Ok(buffered_events) => {
                                 let mut buffered_events = VecDeque::from(buffered_events);
@@ -287,23 +358,33 @@ where
     }
 }
 
+/// Wait for the next `PotterRoundStarted` boundary and return its status header prefix.
+///
+/// Returns `None` once the project completed (`project_outcome` is set) or paused for plan
+/// review (`plan_approval` is set).
 async fn wait_for_next_round_prefix<S>(
     project_id: &str,
     event_source: &mut S,
     pending_events: &mut VecDeque<Event>,
     project_outcome: &mut Option<PotterProjectOutcome>,
+    plan_approval: &mut Option<PotterProjectRenderExit>,
 ) -> anyhow::Result<Option<String>>
 where
     S: PotterEventSource,
 {
     loop {
-        if project_outcome.is_some() {
+        if project_outcome.is_some() || plan_approval.is_some() {
             return Ok(None);
         }
 
         for event in pending_events.iter() {
-            if let EventMsg::PotterRoundStarted { current, total } = &event.msg {
-                return Ok(Some(format!("Round {current}/{total}")));
+            if let EventMsg::PotterRoundStarted {
+                current,
+                total,
+                kind,
+            } = &event.msg
+            {
+                return Ok(Some(kind.round_prefix(*current, *total)));
             }
         }
 
@@ -321,6 +402,17 @@ where
             continue;
         }
 
+        if let Some(idx) = pending_events.iter().position(|event| {
+            matches!(
+                event.msg,
+                EventMsg::PotterProjectAwaitingPlanApproval { .. }
+            )
+        }) && let Some(event) = pending_events.remove(idx)
+        {
+            *plan_approval = Some(plan_approval_exit(project_id, event.msg)?);
+            continue;
+        }
+
         let Some(event) = event_source.read_next_event().await? else {
             let message = format!(
                 "potter app-server event stream closed unexpectedly (project_id={project_id})"
@@ -333,15 +425,42 @@ where
             *project_outcome = Some(outcome.clone());
             continue;
         }
+        if matches!(
+            event.msg,
+            EventMsg::PotterProjectAwaitingPlanApproval { .. }
+        ) {
+            *plan_approval = Some(plan_approval_exit(project_id, event.msg)?);
+            continue;
+        }
 
         pending_events.push_back(event);
     }
 }
 
+fn plan_approval_exit(project_id: &str, msg: EventMsg) -> anyhow::Result<PotterProjectRenderExit> {
+    let EventMsg::PotterProjectAwaitingPlanApproval {
+        project_id: paused_project_id,
+        user_prompt_file,
+        todo,
+    } = msg
+    else {
+        anyhow::bail!("internal error: expected PotterProjectAwaitingPlanApproval marker");
+    };
+    anyhow::ensure!(
+        paused_project_id == project_id,
+        "unexpected PotterProjectAwaitingPlanApproval marker project_id: expected={project_id} actual={paused_project_id}"
+    );
+    Ok(PotterProjectRenderExit::AwaitingPlanApproval {
+        user_prompt_file,
+        todo,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use codex_protocol::protocol::PotterRoundKind;
     use codex_protocol::protocol::PotterRoundOutcome;
     use codex_protocol::protocol::TokenUsage;
     use pretty_assertions::assert_eq;
@@ -386,7 +505,7 @@ mod tests {
                                 PotterRoundOutcome::TaskFailed { message } => {
                                     codex_tui::ExitReason::TaskFailed(message.clone())
                                 }
-                                PotterRoundOutcome::Fatal { message } => {
+                                PotterRoundOutcome::Fatal { message, .. } => {
                                     codex_tui::ExitReason::Fatal(message.clone())
                                 }
                             },
@@ -405,14 +524,46 @@ mod tests {
         }
     }
 
+    /// Round UI that renders until `PotterRoundFinished` without interrupting.
+    #[derive(Default)]
+    struct CompletingUi {
+        rounds_rendered: u32,
+    }
+
+    impl PotterRoundUi for CompletingUi {
+        fn set_project_started_at(&mut self, _started_at: std::time::Instant) {}
+
+        fn render_round<'a>(
+            &'a mut self,
+            params: codex_tui::RenderRoundParams,
+        ) -> UiFuture<'a, codex_tui::AppExitInfo> {
+            self.rounds_rendered += 1;
+            Box::pin(async move {
+                let mut codex_event_rx = params.codex_event_rx;
+                while let Some(event) = codex_event_rx.recv().await {
+                    if matches!(event.msg, EventMsg::PotterRoundFinished { .. }) {
+                        break;
+                    }
+                }
+                Ok(codex_tui::AppExitInfo {
+                    token_usage: TokenUsage::default(),
+                    thread_id: None,
+                    exit_reason: codex_tui::ExitReason::Completed,
+                })
+            })
+        }
+    }
+
     #[derive(Default)]
     struct MockEventSource {
         interrupt_calls: Vec<String>,
+        events: VecDeque<Event>,
     }
 
     impl PotterEventSource for MockEventSource {
         fn read_next_event<'a>(&'a mut self) -> UiFuture<'a, Option<Event>> {
-            Box::pin(async { Ok(None) })
+            let event = self.events.pop_front();
+            Box::pin(async move { Ok(event) })
         }
     }
 
@@ -461,6 +612,7 @@ mod tests {
                 msg: EventMsg::PotterRoundStarted {
                     current: 1,
                     total: 2,
+                    kind: PotterRoundKind::Implement,
                 },
             }],
         )
@@ -476,4 +628,61 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn plan_approval_marker_exits_at_round_boundary() {
+        let mut ui = CompletingUi::default();
+        let mut source = MockEventSource {
+            events: VecDeque::from([
+                Event {
+                    id: "round-finished".to_string(),
+                    msg: EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::Completed,
+                    },
+                },
+                Event {
+                    id: "awaiting-plan-approval".to_string(),
+                    msg: EventMsg::PotterProjectAwaitingPlanApproval {
+                        project_id: String::from("project_1"),
+                        user_prompt_file: PathBuf::from(
+                            ".codexpotter/projects/2026/03/06/4/MAIN.md",
+                        ),
+                        todo: vec![String::from("Add flag"), String::from("Write docs")],
+                    },
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let exit = run_potter_project_render_loop(
+            &mut ui,
+            &mut source,
+            "project_1",
+            PotterProjectRenderOptions {
+                turn_prompt: String::from("Continue"),
+                prompt_footer: codex_tui::PromptFooterContext::new(PathBuf::from("/tmp"), None),
+                pad_before_first_cell: false,
+                initial_status_header_prefix: None,
+            },
+            vec![Event {
+                id: "round-start".to_string(),
+                msg: EventMsg::PotterRoundStarted {
+                    current: 1,
+                    total: 10,
+                    kind: PotterRoundKind::Implement,
+                },
+            }],
+        )
+        .await
+        .expect("render loop");
+
+        assert_eq!(ui.rounds_rendered, 1);
+        assert_eq!(
+            exit,
+            PotterProjectRenderExit::AwaitingPlanApproval {
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/03/06/4/MAIN.md"),
+                todo: vec![String::from("Add flag"), String::from("Write docs")],
+            }
+        );
+    }
 }
@@ -1,26 +1,34 @@
 //! Interactive project queue runner.
 //!
 //! This module runs one or more CodexPotter projects in a loop:
-//! - Collect the next user prompt either from the UI composer or from queued prompts emitted by
-//!   the UI during round execution (see [`crate::workflow::prompt_queue`]).
+//! - Collect the next user prompt from queued prompts emitted by the UI during round execution,
+//!   then from the server's persistent queue, and only then from the UI composer (see
+//!   [`crate::workflow::prompt_queue`]).
 //! - Start a new server-side project via `project/start`.
 //! - Render the project by delegating to [`crate::workflow::project_render_loop`].
 //!
 //! Exiting the UI triggers a best-effort `project/interrupt` so the server does not keep a
 //! dangling running project.
+//!
+//! With `--parallel N` (N > 1) the queue is handed to
+//! [`crate::workflow::parallel_project_runner`] instead.
 
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Instant;
 
 use anyhow::Context;
 use codex_protocol::protocol::Event;
+use codex_protocol::protocol::PotterProjectOutcome;
 use codex_tui::InterruptedProjectAction;
+use codex_tui::PlanApprovalAction;
 
 use crate::workflow::round_runner::UiFuture;
 
-// Sentinel prompt for `Interrupt -> Continue`: forces a new turn while retrying the same round.
-const PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE: &str = "Progress file has been changed by user";
+/// Sentinel prompt for `Interrupt -> Continue`: forces a new turn while retrying the same round.
+pub(crate) const PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE: &str =
+    "Progress file has been changed by user";
 
 /// Runtime configuration for running one or more CodexPotter projects.
 #[derive(Debug, Clone)]
@@ -29,6 +37,8 @@ pub struct ProjectQueueOptions {
     pub rounds: NonZeroUsize,
     /// Per-round prompt passed to the TUI renderer.
     pub turn_prompt: String,
+    /// Maximum number of projects running at once (`--parallel`).
+    pub parallel: NonZeroUsize,
 }
 
 /// Outcome of running the project queue.
@@ -52,10 +62,22 @@ pub async fn run_project_queue(
     workdir: PathBuf,
     options: ProjectQueueOptions,
 ) -> anyhow::Result<ProjectQueueExit> {
+    if options.parallel.get() > 1 {
+        return crate::workflow::parallel_project_runner::run_parallel_project_queue(
+            ui,
+            app_server,
+            workdir,
+            options,
+            &SystemProjectClock,
+        )
+        .await;
+    }
     run_project_queue_with_deps(ui, app_server, workdir, options, &SystemProjectClock).await
 }
 
-trait ProjectRunnerUi: crate::workflow::round_runner::PotterRoundUi {
+pub(crate) trait ProjectRunnerUi:
+    crate::workflow::round_runner::PotterRoundUi + crate::workflow::worktree::WorktreeIntegrationUi
+{
     fn clear(&mut self) -> anyhow::Result<()>;
 
     fn prompt_user<'a>(
@@ -68,12 +90,25 @@ trait ProjectRunnerUi: crate::workflow::round_runner::PotterRoundUi {
         progress_file_rel: PathBuf,
     ) -> UiFuture<'a, Option<InterruptedProjectAction>>;
 
+    /// Show the planned Todo list and ask how to proceed (`--review-plan`).
+    fn prompt_plan_approval<'a>(
+        &'a mut self,
+        progress_file_rel: PathBuf,
+        todo: Vec<String>,
+    ) -> UiFuture<'a, Option<PlanApprovalAction>>;
+
+    fn edit_file_in_external_editor<'a>(&'a mut self, path: PathBuf) -> UiFuture<'a, ()>;
+
     fn insert_interrupted_project_summary_block(
         &mut self,
         summary: crate::app_server::potter::InterruptedProjectSummary,
     );
 
     fn pop_queued_user_prompt(&mut self) -> Option<String>;
+
+    fn set_project_strip(&mut self, strip: Option<codex_tui::ProjectStrip>);
+
+    fn insert_notice(&mut self, message: String);
 }
 
 impl ProjectRunnerUi for codex_tui::CodexPotterTui {
@@ -97,6 +132,24 @@ impl ProjectRunnerUi for codex_tui::CodexPotterTui {
         )
     }
 
+    fn prompt_plan_approval<'a>(
+        &'a mut self,
+        progress_file_rel: PathBuf,
+        todo: Vec<String>,
+    ) -> UiFuture<'a, Option<PlanApprovalAction>> {
+        Box::pin(codex_tui::CodexPotterTui::prompt_plan_approval(
+            self,
+            progress_file_rel,
+            todo,
+        ))
+    }
+
+    fn edit_file_in_external_editor<'a>(&'a mut self, path: PathBuf) -> UiFuture<'a, ()> {
+        Box::pin(codex_tui::CodexPotterTui::edit_file_in_external_editor(
+            self, path,
+        ))
+    }
+
     fn insert_interrupted_project_summary_block(
         &mut self,
         summary: crate::app_server::potter::InterruptedProjectSummary,
@@ -114,13 +167,21 @@ impl ProjectRunnerUi for codex_tui::CodexPotterTui {
     fn pop_queued_user_prompt(&mut self) -> Option<String> {
         codex_tui::CodexPotterTui::pop_queued_user_prompt(self)
     }
+
+    fn set_project_strip(&mut self, strip: Option<codex_tui::ProjectStrip>) {
+        codex_tui::CodexPotterTui::set_project_strip(self, strip);
+    }
+
+    fn insert_notice(&mut self, message: String) {
+        codex_tui::CodexPotterTui::insert_notice(self, message, false);
+    }
 }
 
-trait ProjectClock {
+pub(crate) trait ProjectClock {
     fn now_instant(&self) -> Instant;
 }
 
-struct SystemProjectClock;
+pub(crate) struct SystemProjectClock;
 
 impl ProjectClock for SystemProjectClock {
     fn now_instant(&self) -> Instant {
@@ -128,7 +189,7 @@ impl ProjectClock for SystemProjectClock {
     }
 }
 
-trait ProjectAppServer:
+pub(crate) trait ProjectAppServer:
     crate::workflow::project_render_loop::PotterEventSource
     + crate::workflow::project_render_loop::PotterProjectController
 {
@@ -139,6 +200,9 @@ trait ProjectAppServer:
 
     fn project_interrupt<'a>(&'a mut self, project_id: String) -> UiFuture<'a, ()>;
 
+    /// Take the next prompt from the server's persistent queue (atomically, via `queue/take`).
+    fn take_queued_prompt<'a>(&'a mut self) -> UiFuture<'a, Option<String>>;
+
     fn project_resolve_interrupt<'a>(
         &'a mut self,
         params: crate::app_server::potter::ProjectResolveInterruptParams,
@@ -175,6 +239,13 @@ impl ProjectAppServer for crate::app_server::potter::PotterAppServerClient {
         })
     }
 
+    fn take_queued_prompt<'a>(&'a mut self) -> UiFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let mut buffered_events = Vec::new();
+            self.take_queued_prompt(&mut buffered_events).await
+        })
+    }
+
     fn project_resolve_interrupt<'a>(
         &'a mut self,
         params: crate::app_server::potter::ProjectResolveInterruptParams,
@@ -216,7 +287,16 @@ where
     };
 
     'project: loop {
-        let next_prompt = pending_user_prompts.pop_next_prompt(|| ui.pop_queued_user_prompt());
+        let mut next_prompt = pending_user_prompts.pop_next_prompt(|| ui.pop_queued_user_prompt());
+        if next_prompt.is_none() {
+            next_prompt = match app_server.take_queued_prompt().await {
+                Ok(prompt) => prompt,
+                Err(err) => {
+                    ui.insert_notice(format!("Skipped the prompt queue: {err:#}"));
+                    None
+                }
+            };
+        }
 
         let next_prompt =
             crate::workflow::prompt_queue::next_prompt_or_prompt_user(next_prompt, || {
@@ -241,7 +321,6 @@ where
         ui.set_project_started_at(project_started_at);
 
         let rounds_total_u32 = crate::rounds::round_budget_to_u32(options.rounds)?;
-        let prompt_footer = build_prompt_footer();
 
         let (start_response, buffered_events) = app_server
             .project_start(crate::app_server::potter::ProjectStartParams {
@@ -249,99 +328,282 @@ where
                 cwd: Some(workdir.clone()),
                 rounds: Some(rounds_total_u32),
                 event_mode: Some(crate::app_server::potter::PotterEventMode::Interactive),
+                output_schema: None,
             })
             .await
             .context("project/start via potter app-server")?;
 
+        // In worktree mode the agent works (and commits) in the project worktree.
+        let prompt_footer = match &start_response.worktree {
+            Some(worktree_dir) => codex_tui::PromptFooterContext::new(
+                worktree_dir.clone(),
+                start_response.git_branch.clone(),
+            ),
+            None => build_prompt_footer(),
+        };
+
         let project_dir = start_response
             .progress_file_rel
             .parent()
             .context("derive project dir from progress file path")?
             .to_path_buf();
 
-        let project_id = start_response.project_id.clone();
-        let mut buffered_events = buffered_events;
-        loop {
-            let exit = crate::workflow::project_render_loop::run_potter_project_render_loop(
-                ui,
-                app_server,
-                &project_id,
-                crate::workflow::project_render_loop::PotterProjectRenderOptions {
-                    turn_prompt: options.turn_prompt.clone(),
-                    prompt_footer: prompt_footer.clone(),
-                    pad_before_first_cell: false,
-                    initial_status_header_prefix: None,
-                },
-                buffered_events,
-            )
-            .await?;
+        let project = DrivenProject {
+            project_id: start_response.project_id.clone(),
+            workdir: workdir.clone(),
+            worktree: start_response.worktree.clone(),
+            git_branch: start_response.git_branch.clone(),
+            turn_prompt: options.turn_prompt.clone(),
+            prompt_footer,
+        };
+        match drive_project(ui, app_server, &project, buffered_events).await? {
+            DrivenProjectExit::Finished => {}
+            DrivenProjectExit::UserRequested => {
+                // Best-effort: stop the server-side project before exiting.
+                let _ = app_server.project_interrupt(project.project_id).await;
+                return Ok(ProjectQueueExit::UserRequestedExit { project_dir });
+            }
+            DrivenProjectExit::FatalExitRequested => {
+                let _ = app_server.project_interrupt(project.project_id).await;
+                return Ok(ProjectQueueExit::FatalExitRequested);
+            }
+        }
+    }
 
-            match exit {
-                crate::workflow::project_render_loop::PotterProjectRenderExit::Completed { .. } => {
-                    break;
+    Ok(ProjectQueueExit::Completed)
+}
+
+/// A started project, as [`drive_project`] needs it.
+pub(crate) struct DrivenProject {
+    pub project_id: String,
+    pub workdir: PathBuf,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    pub worktree: Option<PathBuf>,
+    pub git_branch: Option<String>,
+    pub turn_prompt: String,
+    pub prompt_footer: codex_tui::PromptFooterContext,
+}
+
+/// How [`drive_project`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DrivenProjectExit {
+    /// The project completed (or was stopped from an interrupt or plan review prompt).
+    Finished,
+    /// The user requested exit while a round was running; the project is still running.
+    UserRequested,
+    /// The UI requested a fatal exit; the project is still running.
+    FatalExitRequested,
+}
+
+/// Render a started project until it completes, answering interrupt and plan review pauses with
+/// the user.
+pub(crate) async fn drive_project<U, S>(
+    ui: &mut U,
+    app_server: &mut S,
+    project: &DrivenProject,
+    buffered_events: Vec<Event>,
+) -> anyhow::Result<DrivenProjectExit>
+where
+    U: ProjectRunnerUi,
+    S: ProjectAppServer,
+{
+    let DrivenProject {
+        project_id,
+        workdir,
+        worktree,
+        git_branch,
+        turn_prompt,
+        prompt_footer,
+    } = project;
+    let mut buffered_events = buffered_events;
+    loop {
+        let exit = crate::workflow::project_render_loop::run_potter_project_render_loop(
+            ui,
+            app_server,
+            project_id,
+            crate::workflow::project_render_loop::PotterProjectRenderOptions {
+                turn_prompt: turn_prompt.clone(),
+                prompt_footer: prompt_footer.clone(),
+                pad_before_first_cell: false,
+                initial_status_header_prefix: None,
+            },
+            buffered_events,
+        )
+        .await?;
+
+        match exit {
+            crate::workflow::project_render_loop::PotterProjectRenderExit::Completed {
+                outcome,
+            } => {
+                if let Some(notice) = early_stop_notice(&outcome) {
+                    ui.insert_notice(format!("Project {notice}"));
+                }
+                if matches!(outcome, PotterProjectOutcome::Succeeded)
+                    && let (Some(worktree_dir), Some(branch)) = (worktree, git_branch)
+                {
+                    crate::workflow::worktree::offer_worktree_integration(
+                        ui,
+                        workdir,
+                        worktree_dir,
+                        branch,
+                    )
+                    .await?;
                 }
-                crate::workflow::project_render_loop::PotterProjectRenderExit::Interrupted {
-                    user_prompt_file,
-                } => {
-                    let action = ui
-                        .prompt_interrupted_project_action(user_prompt_file)
-                        .await?
-                        .unwrap_or(InterruptedProjectAction::StopIterate);
-
-                    match action {
-                        InterruptedProjectAction::StopIterate => {
-                            let (response, _buffered_events) = app_server
-                                .project_resolve_interrupt(
-                                    crate::app_server::potter::ProjectResolveInterruptParams {
-                                        project_id: project_id.clone(),
-                                        action: crate::app_server::potter::ResolveInterruptAction::Stop,
-                                        turn_prompt_override: None,
-                                    },
-                                )
-                                .await
-                                .context("project/resolve_interrupt(stop) via potter app-server")?;
-
-                            let summary = response
-                                .summary
-                                .context("internal error: missing summary for resolve_interrupt(stop)")?;
-                            ui.insert_interrupted_project_summary_block(summary);
-                            break;
-                        }
-                        InterruptedProjectAction::ContinueIterate => {
-                            let (response, buffered) = app_server
-                                .project_resolve_interrupt(
-                                    crate::app_server::potter::ProjectResolveInterruptParams {
-                                        project_id: project_id.clone(),
-                                        action: crate::app_server::potter::ResolveInterruptAction::Continue,
-                                        turn_prompt_override: Some(
-                                            PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE.to_string(),
-                                        ),
-                                    },
-                                )
-                                .await
-                                .context("project/resolve_interrupt(continue) via potter app-server")?;
-                            anyhow::ensure!(
-                                response.summary.is_none(),
-                                "internal error: resolve_interrupt(continue) returned summary"
-                            );
-                            buffered_events = buffered;
-                        }
+                break;
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::Interrupted {
+                user_prompt_file,
+            } => {
+                let action = ui
+                    .prompt_interrupted_project_action(user_prompt_file)
+                    .await?
+                    .unwrap_or(InterruptedProjectAction::StopIterate);
+
+                match action {
+                    InterruptedProjectAction::StopIterate => {
+                        let (response, _buffered_events) = app_server
+                            .project_resolve_interrupt(
+                                crate::app_server::potter::ProjectResolveInterruptParams {
+                                    project_id: project_id.clone(),
+                                    action: crate::app_server::potter::ResolveInterruptAction::Stop,
+                                    turn_prompt_override: None,
+                                },
+                            )
+                            .await
+                            .context("project/resolve_interrupt(stop) via potter app-server")?;
+
+                        let summary = response
+                            .summary
+                            .context("internal error: missing summary for resolve_interrupt(stop)")?;
+                        ui.insert_interrupted_project_summary_block(summary);
+                        break;
+                    }
+                    InterruptedProjectAction::ContinueIterate => {
+                        let (response, buffered) = app_server
+                            .project_resolve_interrupt(
+                                crate::app_server::potter::ProjectResolveInterruptParams {
+                                    project_id: project_id.clone(),
+                                    action: crate::app_server::potter::ResolveInterruptAction::Continue,
+                                    turn_prompt_override: Some(
+                                        PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE.to_string(),
+                                    ),
+                                },
+                            )
+                            .await
+                            .context("project/resolve_interrupt(continue) via potter app-server")?;
+                        anyhow::ensure!(
+                            response.summary.is_none(),
+                            "internal error: resolve_interrupt(continue) returned summary"
+                        );
+                        buffered_events = buffered;
                     }
                 }
-                crate::workflow::project_render_loop::PotterProjectRenderExit::UserRequested => {
-                    // Best-effort: stop the server-side project before exiting.
-                    let _ = app_server.project_interrupt(project_id.clone()).await;
-                    return Ok(ProjectQueueExit::UserRequestedExit { project_dir });
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::AwaitingPlanApproval {
+                user_prompt_file,
+                todo,
+            } => {
+                if review_plan(ui, workdir, user_prompt_file, todo).await? {
+                    let (response, buffered) = app_server
+                        .project_resolve_interrupt(
+                            crate::app_server::potter::ProjectResolveInterruptParams {
+                                project_id: project_id.clone(),
+                                action: crate::app_server::potter::ResolveInterruptAction::Continue,
+                                turn_prompt_override: None,
+                            },
+                        )
+                        .await
+                        .context("project/resolve_interrupt(continue) via potter app-server")?;
+                    anyhow::ensure!(
+                        response.summary.is_none(),
+                        "internal error: resolve_interrupt(continue) returned summary"
+                    );
+                    buffered_events = buffered;
+                } else {
+                    let (response, _buffered_events) = app_server
+                        .project_resolve_interrupt(
+                            crate::app_server::potter::ProjectResolveInterruptParams {
+                                project_id: project_id.clone(),
+                                action: crate::app_server::potter::ResolveInterruptAction::Stop,
+                                turn_prompt_override: None,
+                            },
+                        )
+                        .await
+                        .context("project/resolve_interrupt(stop) via potter app-server")?;
+
+                    let summary = response
+                        .summary
+                        .context("internal error: missing summary for resolve_interrupt(stop)")?;
+                    ui.insert_interrupted_project_summary_block(summary);
+                    break;
                 }
-                crate::workflow::project_render_loop::PotterProjectRenderExit::FatalExitRequested => {
-                    let _ = app_server.project_interrupt(project_id.clone()).await;
-                    return Ok(ProjectQueueExit::FatalExitRequested);
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::UserRequested => {
+                return Ok(DrivenProjectExit::UserRequested);
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::FatalExitRequested => {
+                return Ok(DrivenProjectExit::FatalExitRequested);
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::Detached => {
+                anyhow::bail!("internal error: project render loop detached");
+            }
+        }
+    }
+
+    Ok(DrivenProjectExit::Finished)
+}
+
+/// Let the user review the plan written by the planning round (`--review-plan`).
+///
+/// Editing opens the progress file in the external editor and shows the re-read Todo list again.
+/// Returns `true` when the plan is approved and `false` when the project should be aborted
+/// (including a cancelled prompt).
+pub(crate) async fn review_plan<U>(
+    ui: &mut U,
+    workdir: &Path,
+    user_prompt_file: PathBuf,
+    mut todo: Vec<String>,
+) -> anyhow::Result<bool>
+where
+    U: ProjectRunnerUi,
+{
+    let progress_file = workdir.join(&user_prompt_file);
+    loop {
+        let action = ui
+            .prompt_plan_approval(user_prompt_file.clone(), todo)
+            .await?
+            .unwrap_or(PlanApprovalAction::Abort);
+        match action {
+            PlanApprovalAction::Approve => return Ok(true),
+            PlanApprovalAction::Abort => return Ok(false),
+            PlanApprovalAction::Edit => {
+                if let Err(err) = ui.edit_file_in_external_editor(progress_file.clone()).await {
+                    ui.insert_notice(format!("{err:#}"));
                 }
+                todo = crate::workflow::project::progress_file_todo_items(&progress_file)?;
             }
         }
     }
+}
 
-    Ok(ProjectQueueExit::Completed)
+/// Why a project stopped before succeeding or using up its rounds (`--stall-rounds`,
+/// `--max-tokens`, `--max-duration`), e.g. `stalled: no git changes in the last 3 rounds`.
+pub(crate) fn early_stop_notice(outcome: &PotterProjectOutcome) -> Option<String> {
+    match outcome {
+        PotterProjectOutcome::Stalled { rounds } => Some(format!(
+            "stalled: {}",
+            crate::workflow::stall::describe_stall(*rounds)
+        )),
+        PotterProjectOutcome::TokenBudgetExhausted { .. } => Some(format!(
+            "token budget exhausted: {}",
+            crate::workflow::budget::describe_budget_exhaustion(outcome)?
+        )),
+        PotterProjectOutcome::TimeBudgetExhausted { .. } => Some(format!(
+            "time budget exhausted: {}",
+            crate::workflow::budget::describe_budget_exhaustion(outcome)?
+        )),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -349,7 +611,7 @@ mod tests {
     use super::*;
 
     use codex_protocol::protocol::EventMsg;
-    use codex_protocol::protocol::PotterProjectOutcome;
+    use codex_protocol::protocol::PotterRoundKind;
     use codex_protocol::protocol::PotterRoundOutcome;
     use codex_protocol::protocol::TokenUsage;
     use pretty_assertions::assert_eq;
@@ -360,6 +622,11 @@ mod tests {
         queued_prompts: VecDeque<String>,
         prompt_user_responses: VecDeque<Option<String>>,
         prompt_interrupted_project_action_responses: VecDeque<Option<InterruptedProjectAction>>,
+        prompt_plan_approval_responses: VecDeque<Option<PlanApprovalAction>>,
+        /// Todo lists shown by each plan approval prompt.
+        reviewed_plans: Vec<Vec<String>>,
+        /// Contents the mock external editor saves to the edited file.
+        edited_file_contents: Option<String>,
         prompt_user_calls: usize,
         prompt_interrupted_project_action_calls: usize,
         clear_calls: usize,
@@ -372,6 +639,9 @@ mod tests {
                 queued_prompts: VecDeque::from(queued_prompts),
                 prompt_user_responses: VecDeque::from(prompt_user_responses),
                 prompt_interrupted_project_action_responses: VecDeque::new(),
+                prompt_plan_approval_responses: VecDeque::new(),
+                reviewed_plans: Vec::new(),
+                edited_file_contents: None,
                 prompt_user_calls: 0,
                 prompt_interrupted_project_action_calls: 0,
                 clear_calls: 0,
@@ -410,7 +680,7 @@ mod tests {
                                 PotterRoundOutcome::TaskFailed { message } => {
                                     codex_tui::ExitReason::TaskFailed(message.clone())
                                 }
-                                PotterRoundOutcome::Fatal { message } => {
+                                PotterRoundOutcome::Fatal { message, .. } => {
                                     codex_tui::ExitReason::Fatal(message.clone())
                                 }
                             },
@@ -429,6 +699,17 @@ mod tests {
         }
     }
 
+    impl crate::workflow::worktree::WorktreeIntegrationUi for MockUi {
+        fn prompt_worktree_integration<'a>(
+            &'a mut self,
+            _actions: Vec<String>,
+        ) -> UiFuture<'a, Option<usize>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn insert_worktree_notice(&mut self, _message: String, _is_error: bool) {}
+    }
+
     impl ProjectRunnerUi for MockUi {
         fn clear(&mut self) -> anyhow::Result<()> {
             self.clear_calls += 1;
@@ -459,6 +740,29 @@ mod tests {
             Box::pin(async move { Ok(response) })
         }
 
+        fn prompt_plan_approval<'a>(
+            &'a mut self,
+            _progress_file_rel: PathBuf,
+            todo: Vec<String>,
+        ) -> UiFuture<'a, Option<PlanApprovalAction>> {
+            self.reviewed_plans.push(todo);
+            let response = self
+                .prompt_plan_approval_responses
+                .pop_front()
+                .unwrap_or(Some(PlanApprovalAction::Abort));
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn edit_file_in_external_editor<'a>(&'a mut self, path: PathBuf) -> UiFuture<'a, ()> {
+            let contents = self.edited_file_contents.clone();
+            Box::pin(async move {
+                if let Some(contents) = contents {
+                    std::fs::write(&path, contents)?;
+                }
+                Ok(())
+            })
+        }
+
         fn insert_interrupted_project_summary_block(
             &mut self,
             _summary: crate::app_server::potter::InterruptedProjectSummary,
@@ -468,6 +772,10 @@ mod tests {
         fn pop_queued_user_prompt(&mut self) -> Option<String> {
             self.queued_prompts.pop_front()
         }
+
+        fn set_project_strip(&mut self, _strip: Option<codex_tui::ProjectStrip>) {}
+
+        fn insert_notice(&mut self, _message: String) {}
     }
 
     struct TestClock;
@@ -480,6 +788,8 @@ mod tests {
 
     #[derive(Debug, Default)]
     struct InterruptContinueAppServer {
+        /// Pause for `--review-plan` after the first round instead of interrupting it.
+        pause_for_plan_approval: bool,
         started_prompts: std::sync::Mutex<Vec<String>>,
         resolve_interrupt_calls:
             std::sync::Mutex<Vec<crate::app_server::potter::ProjectResolveInterruptParams>>,
@@ -529,29 +839,46 @@ mod tests {
                     progress_file: PathBuf::from("/tmp/project_1/MAIN.md"),
                     git_commit_start: String::new(),
                     git_branch: None,
+                    worktree: None,
                     rounds_total: 2,
                 };
 
+                let (round_outcome, pause) = if self.pause_for_plan_approval {
+                    (
+                        PotterRoundOutcome::Completed,
+                        EventMsg::PotterProjectAwaitingPlanApproval {
+                            project_id: String::from("project_1"),
+                            user_prompt_file: progress_file_rel,
+                            todo: vec![String::from("Draft plan")],
+                        },
+                    )
+                } else {
+                    (
+                        PotterRoundOutcome::Interrupted,
+                        EventMsg::PotterProjectInterrupted {
+                            project_id: String::from("project_1"),
+                            user_prompt_file: progress_file_rel,
+                        },
+                    )
+                };
                 let buffered_events = vec![
                     Event {
                         id: String::new(),
                         msg: EventMsg::PotterRoundStarted {
                             current: 1,
                             total: 2,
+                            kind: PotterRoundKind::Implement,
                         },
                     },
                     Event {
                         id: String::new(),
                         msg: EventMsg::PotterRoundFinished {
-                            outcome: PotterRoundOutcome::Interrupted,
+                            outcome: round_outcome,
                         },
                     },
                     Event {
                         id: String::new(),
-                        msg: EventMsg::PotterProjectInterrupted {
-                            project_id: String::from("project_1"),
-                            user_prompt_file: progress_file_rel,
-                        },
+                        msg: pause,
                     },
                 ];
 
@@ -563,6 +890,10 @@ mod tests {
             Box::pin(async { Ok(()) })
         }
 
+        fn take_queued_prompt<'a>(&'a mut self) -> UiFuture<'a, Option<String>> {
+            Box::pin(async { Ok(None) })
+        }
+
         fn project_resolve_interrupt<'a>(
             &'a mut self,
             params: crate::app_server::potter::ProjectResolveInterruptParams,
@@ -587,6 +918,7 @@ mod tests {
                             msg: EventMsg::PotterRoundStarted {
                                 current: 1,
                                 total: 2,
+                                kind: PotterRoundKind::Implement,
                             },
                         },
                         Event {
@@ -662,6 +994,7 @@ mod tests {
                     progress_file,
                     git_commit_start: String::new(),
                     git_branch: None,
+                    worktree: None,
                     rounds_total: 1,
                 };
 
@@ -671,6 +1004,7 @@ mod tests {
                         msg: EventMsg::PotterRoundStarted {
                             current: 1,
                             total: 1,
+                            kind: PotterRoundKind::Implement,
                         },
                     },
                     Event {
@@ -695,6 +1029,10 @@ mod tests {
             Box::pin(async { Ok(()) })
         }
 
+        fn take_queued_prompt<'a>(&'a mut self) -> UiFuture<'a, Option<String>> {
+            Box::pin(async { Ok(None) })
+        }
+
         fn project_resolve_interrupt<'a>(
             &'a mut self,
             _params: crate::app_server::potter::ProjectResolveInterruptParams,
@@ -728,6 +1066,7 @@ mod tests {
             ProjectQueueOptions {
                 rounds: NonZeroUsize::new(1).expect("rounds"),
                 turn_prompt: String::from("Continue"),
+                parallel: NonZeroUsize::MIN,
             },
             &clock,
         )
@@ -758,6 +1097,7 @@ mod tests {
             ProjectQueueOptions {
                 rounds: NonZeroUsize::new(1).expect("rounds"),
                 turn_prompt: String::from("Continue"),
+                parallel: NonZeroUsize::MIN,
             },
             &clock,
         )
@@ -784,6 +1124,7 @@ mod tests {
             ProjectQueueOptions {
                 rounds: NonZeroUsize::new(1).expect("rounds"),
                 turn_prompt: String::from("Continue"),
+                parallel: NonZeroUsize::MIN,
             },
             &clock,
         )
@@ -813,6 +1154,7 @@ mod tests {
             ProjectQueueOptions {
                 rounds: NonZeroUsize::new(2).expect("rounds"),
                 turn_prompt: String::from("Continue"),
+                parallel: NonZeroUsize::MIN,
             },
             &clock,
         )
@@ -833,4 +1175,67 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn review_plan_edit_then_approve_continues_without_prompt_override() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let progress_file = temp
+            .path()
+            .join(".codexpotter/projects/2026/02/01/1/MAIN.md");
+        std::fs::create_dir_all(progress_file.parent().expect("parent")).expect("create dir");
+        std::fs::write(
+            &progress_file,
+            "---\nstatus: open\n---\n\n## Todo\n\n- Draft plan\n",
+        )
+        .expect("write progress file");
+
+        let mut ui = MockUi::new(Vec::new(), vec![Some(String::from("hello")), None]);
+        ui.prompt_plan_approval_responses.extend([
+            Some(PlanApprovalAction::Edit),
+            Some(PlanApprovalAction::Approve),
+        ]);
+        ui.edited_file_contents = Some(String::from(
+            "---\nstatus: open\n---\n\n## Todo\n\n- Smaller first step\n- Second step\n",
+        ));
+
+        let mut app_server = InterruptContinueAppServer {
+            pause_for_plan_approval: true,
+            ..Default::default()
+        };
+        let clock = TestClock;
+
+        let exit = run_project_queue_with_deps(
+            &mut ui,
+            &mut app_server,
+            temp.path().to_path_buf(),
+            ProjectQueueOptions {
+                rounds: NonZeroUsize::new(2).expect("rounds"),
+                turn_prompt: String::from("Continue"),
+                parallel: NonZeroUsize::MIN,
+            },
+            &clock,
+        )
+        .await
+        .expect("run project queue");
+
+        assert_eq!(exit, ProjectQueueExit::Completed);
+        assert_eq!(
+            ui.reviewed_plans,
+            vec![
+                vec![String::from("Draft plan")],
+                vec![
+                    String::from("Smaller first step"),
+                    String::from("Second step"),
+                ],
+            ]
+        );
+        assert_eq!(
+            app_server.resolve_interrupt_calls(),
+            vec![crate::app_server::potter::ProjectResolveInterruptParams {
+                project_id: String::from("project_1"),
+                action: crate::app_server::potter::ResolveInterruptAction::Continue,
+                turn_prompt_override: None,
+            }]
+        );
+    }
 }
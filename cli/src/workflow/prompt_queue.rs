@@ -3,11 +3,23 @@
 //! CodexPotter can obtain follow-up prompts from multiple sources:
 //! - The interactive user composer (typed by the user).
 //! - "Queued user prompts" emitted during round execution and stored by the UI.
+//! - The persistent prompt queue in `.codexpotter/queue.jsonl`, owned by the potter app-server
+//!   (`queue/add`, `queue/list`, `queue/remove`, `queue/reorder`, `queue/take`).
 //!
 //! This module provides a tiny helper to prefer queued prompts when available, falling back to
-//! prompting the user only when the queue is empty.
+//! prompting the user only when the queue is empty, and reads/writes the persistent queue. Every
+//! change to the persistent queue holds `.codexpotter/queue.jsonl.lock` across its read and write,
+//! so concurrent app-servers and clients of the same workdir never lose entries.
 
 use std::future::Future;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NextPrompt {
@@ -59,6 +71,135 @@ where
     }
 }
 
+/// One line of `.codexpotter/queue.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedPrompt {
+    /// Stable identifier used by `queue/remove` and `queue/reorder`.
+    pub id: String,
+    pub prompt: String,
+    pub queued_at_unix_secs: u64,
+}
+
+/// Path of the persistent prompt queue of `workdir`.
+pub fn prompt_queue_path(workdir: &Path) -> PathBuf {
+    workdir.join(".codexpotter").join("queue.jsonl")
+}
+
+/// Read the persistent prompt queue, oldest first. A missing file is an empty queue.
+pub fn read_prompt_queue(workdir: &Path) -> anyhow::Result<Vec<QueuedPrompt>> {
+    let path = prompt_queue_path(workdir);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+    };
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("parse {} line {}", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+fn prompt_queue_lock_path(workdir: &Path) -> PathBuf {
+    workdir.join(".codexpotter").join("queue.jsonl.lock")
+}
+
+/// Apply `update` to the persistent queue under the queue lock, writing the result back when it
+/// succeeds.
+fn update_prompt_queue<T>(
+    workdir: &Path,
+    update: impl FnOnce(&mut Vec<QueuedPrompt>) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let _lock = crate::file_lock::lock_exclusive(&prompt_queue_lock_path(workdir))?;
+    let mut queue = read_prompt_queue(workdir)?;
+    let value = update(&mut queue)?;
+    write_prompt_queue(workdir, &queue)?;
+    Ok(value)
+}
+
+fn write_prompt_queue(workdir: &Path, queue: &[QueuedPrompt]) -> anyhow::Result<()> {
+    let mut contents = String::new();
+    for entry in queue {
+        contents.push_str(&serde_json::to_string(entry).context("encode queued prompt")?);
+        contents.push('\n');
+    }
+    crate::atomic_write::write_atomic_text(&prompt_queue_path(workdir), &contents)
+}
+
+/// Append `prompt` to the persistent queue.
+pub fn push_queued_prompt(workdir: &Path, prompt: String) -> anyhow::Result<QueuedPrompt> {
+    anyhow::ensure!(!prompt.trim().is_empty(), "prompt must not be empty");
+    update_prompt_queue(workdir, |queue| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        // Millisecond timestamps, bumped past any id still in the queue.
+        let mut id = now.as_millis();
+        while queue.iter().any(|entry| entry.id == id.to_string()) {
+            id += 1;
+        }
+        let entry = QueuedPrompt {
+            id: id.to_string(),
+            prompt,
+            queued_at_unix_secs: now.as_secs(),
+        };
+        queue.push(entry.clone());
+        Ok(entry)
+    })
+}
+
+/// Remove the entry `id` from the persistent queue.
+pub fn remove_queued_prompt(workdir: &Path, id: &str) -> anyhow::Result<QueuedPrompt> {
+    update_prompt_queue(workdir, |queue| {
+        let idx = queue
+            .iter()
+            .position(|entry| entry.id == id)
+            .with_context(|| format!("no queued prompt with id {id}"))?;
+        Ok(queue.remove(idx))
+    })
+}
+
+/// Remove and return the next entry of the persistent queue, if any.
+///
+/// The queue lock makes this atomic: concurrent consumers never take the same entry.
+pub fn take_queued_prompt(workdir: &Path) -> anyhow::Result<Option<QueuedPrompt>> {
+    update_prompt_queue(workdir, |queue| {
+        Ok((!queue.is_empty()).then(|| queue.remove(0)))
+    })
+}
+
+/// Put an entry taken with [`take_queued_prompt`] back at the front of the persistent queue.
+pub fn restore_queued_prompt(workdir: &Path, entry: QueuedPrompt) -> anyhow::Result<()> {
+    update_prompt_queue(workdir, |queue| {
+        queue.insert(0, entry);
+        Ok(())
+    })
+}
+
+/// Move the entries listed in `ids` to the front of the persistent queue, in that order.
+///
+/// Entries not listed keep their relative order after them, so a client with a stale view does
+/// not drop prompts queued in the meantime.
+pub fn reorder_prompt_queue(workdir: &Path, ids: &[String]) -> anyhow::Result<Vec<QueuedPrompt>> {
+    update_prompt_queue(workdir, |queue| {
+        let mut reordered = Vec::with_capacity(queue.len());
+        for id in ids {
+            let idx = queue
+                .iter()
+                .position(|entry| &entry.id == id)
+                .with_context(|| format!("no queued prompt with id {id}"))?;
+            reordered.push(queue.remove(idx));
+        }
+        reordered.append(queue);
+        *queue = reordered;
+        Ok(queue.clone())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +263,94 @@ mod tests {
             .expect("resolve prompt");
         assert_eq!(prompt, None);
     }
+
+    #[test]
+    fn persistent_queue_adds_reorders_and_removes_prompts() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path();
+        assert_eq!(read_prompt_queue(workdir).expect("read"), Vec::new());
+
+        let first = push_queued_prompt(workdir, "first".to_string()).expect("push");
+        let second = push_queued_prompt(workdir, "second".to_string()).expect("push");
+        let third = push_queued_prompt(workdir, "third".to_string()).expect("push");
+        assert_ne!(first.id, second.id);
+
+        let prompts = |queue: Vec<QueuedPrompt>| {
+            queue
+                .into_iter()
+                .map(|entry| entry.prompt)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            prompts(reorder_prompt_queue(workdir, &[third.id.clone()]).expect("reorder")),
+            vec!["third", "first", "second"]
+        );
+        assert_eq!(
+            remove_queued_prompt(workdir, &first.id).expect("remove"),
+            first
+        );
+        assert_eq!(
+            prompts(read_prompt_queue(workdir).expect("read")),
+            vec!["third", "second"]
+        );
+        assert!(remove_queued_prompt(workdir, &first.id).is_err());
+        assert!(push_queued_prompt(workdir, "  ".to_string()).is_err());
+
+        let next = take_queued_prompt(workdir).expect("take").expect("entry");
+        assert_eq!(next, third);
+        restore_queued_prompt(workdir, next).expect("restore");
+        assert_eq!(take_queued_prompt(workdir).expect("take"), Some(third));
+        assert_eq!(take_queued_prompt(workdir).expect("take"), Some(second));
+        assert_eq!(take_queued_prompt(workdir).expect("take"), None);
+    }
+
+    #[test]
+    fn concurrent_queue_updates_keep_every_prompt() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path().to_path_buf();
+
+        let writers = (0..4)
+            .map(|writer| {
+                let workdir = workdir.clone();
+                std::thread::spawn(move || {
+                    for idx in 0..10 {
+                        push_queued_prompt(&workdir, format!("{writer}-{idx}")).expect("push");
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().expect("join writer");
+        }
+
+        let queue = read_prompt_queue(&workdir).expect("read");
+        assert_eq!(queue.len(), 40);
+        let ids = queue
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(ids.len(), 40);
+
+        // Concurrent consumers take every entry exactly once.
+        let consumers = (0..4)
+            .map(|_| {
+                let workdir = workdir.clone();
+                std::thread::spawn(move || {
+                    let mut taken = Vec::new();
+                    while let Some(entry) = take_queued_prompt(&workdir).expect("take") {
+                        taken.push(entry.id);
+                    }
+                    taken
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut taken = Vec::new();
+        for consumer in consumers {
+            taken.extend(consumer.join().expect("join consumer"));
+        }
+        taken.sort();
+        let mut expected = queue.into_iter().map(|entry| entry.id).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(taken, expected);
+    }
 }
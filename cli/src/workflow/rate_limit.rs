@@ -0,0 +1,197 @@
+//! Rate-limit aware pausing between rounds.
+//!
+//! Upstream `TokenCount` events carry a [`RateLimitSnapshot`] with the used percentage and reset
+//! time of the account's primary/secondary usage windows. [`RateLimitGate`] keeps the latest
+//! snapshot of a project and, before the next round sends its prompt, reports how long to wait
+//! when a window is at or above `--rate-limit-threshold` (default
+//! [`DEFAULT_RATE_LIMIT_THRESHOLD`]%). Starting a round on an exhausted window would only end in a
+//! usage-limit error a few requests later.
+//!
+//! Windows without a reset time, or whose reset time already passed, never cause a pause: the
+//! snapshot is stale until the next `TokenCount` arrives.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RateLimitSnapshot;
+
+/// Used percentage at which a window pauses the next round when `--rate-limit-threshold` is unset.
+pub const DEFAULT_RATE_LIMIT_THRESHOLD: u8 = 95;
+
+/// Extra wait after a window reset, so the next round does not race the reset.
+const RESET_GRACE: Duration = Duration::from_secs(5);
+
+/// Tracks upstream rate-limit usage across the rounds of a project.
+#[derive(Debug, Clone)]
+pub struct RateLimitGate {
+    threshold_percent: u8,
+    latest: Option<RateLimitSnapshot>,
+}
+
+/// Why and for how long the next round waits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitPause {
+    /// Used percentage of the window the round waits for.
+    pub used_percent: f64,
+    /// Length of that window, when known.
+    pub window_minutes: Option<i64>,
+    /// Time until that window resets (including a short grace period).
+    pub resets_in: Duration,
+}
+
+impl RateLimitPause {
+    /// Event announcing the pause to clients.
+    pub fn event(&self) -> EventMsg {
+        EventMsg::PotterRateLimitWait {
+            used_percent: self.used_percent,
+            window_minutes: self.window_minutes,
+            resets_in: self.resets_in,
+        }
+    }
+}
+
+impl RateLimitGate {
+    pub fn new(threshold_percent: Option<u8>) -> Self {
+        Self {
+            threshold_percent: threshold_percent.unwrap_or(DEFAULT_RATE_LIMIT_THRESHOLD),
+            latest: None,
+        }
+    }
+
+    /// Record the rate limits reported by a `TokenCount` event.
+    pub fn observe(&mut self, snapshot: &RateLimitSnapshot) {
+        self.latest = Some(snapshot.clone());
+    }
+
+    /// Pause needed before starting a round at `now`, if any window is over the threshold.
+    ///
+    /// When several windows are over the threshold, the round waits for the one that resets last.
+    pub fn pause_at(&self, now: SystemTime) -> Option<RateLimitPause> {
+        let snapshot = self.latest.as_ref()?;
+        let now = i64::try_from(now.duration_since(UNIX_EPOCH).ok()?.as_secs()).ok()?;
+        let threshold = f64::from(self.threshold_percent);
+
+        [snapshot.primary.as_ref(), snapshot.secondary.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|window| window.used_percent >= threshold)
+            .filter_map(|window| {
+                let resets_at = window.resets_at.filter(|resets_at| *resets_at > now)?;
+                Some((window, resets_at))
+            })
+            .max_by_key(|(_, resets_at)| *resets_at)
+            .map(|(window, resets_at)| RateLimitPause {
+                used_percent: window.used_percent,
+                window_minutes: window.window_minutes,
+                resets_in: Duration::from_secs(u64::try_from(resets_at - now).unwrap_or(0))
+                    + RESET_GRACE,
+            })
+    }
+}
+
+/// Parse `--rate-limit-threshold` (a percentage between 1 and 100).
+pub fn parse_threshold(value: &str) -> Result<u8, String> {
+    let percent = value
+        .trim()
+        .trim_end_matches('%')
+        .parse::<u8>()
+        .map_err(|_| format!("invalid percentage `{value}`"))?;
+    if !(1..=100).contains(&percent) {
+        return Err(format!(
+            "percentage must be between 1 and 100, got {percent}"
+        ));
+    }
+    Ok(percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codex_protocol::protocol::RateLimitWindow;
+    use pretty_assertions::assert_eq;
+
+    fn window(used_percent: f64, window_minutes: i64, resets_at: Option<i64>) -> RateLimitWindow {
+        RateLimitWindow {
+            used_percent,
+            window_minutes: Some(window_minutes),
+            resets_at,
+        }
+    }
+
+    fn gate_with(primary: RateLimitWindow, secondary: RateLimitWindow) -> RateLimitGate {
+        let mut gate = RateLimitGate::new(Some(90));
+        gate.observe(&RateLimitSnapshot {
+            primary: Some(primary),
+            secondary: Some(secondary),
+            credits: None,
+            plan_type: None,
+        });
+        gate
+    }
+
+    #[test]
+    fn pause_waits_for_the_latest_reset_among_windows_over_threshold() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        assert_eq!(RateLimitGate::new(None).pause_at(now), None);
+        assert_eq!(
+            gate_with(
+                window(89.0, 300, Some(1_600)),
+                window(40.0, 10_080, Some(90_000))
+            )
+            .pause_at(now),
+            None
+        );
+        assert_eq!(
+            gate_with(
+                window(97.5, 300, Some(1_600)),
+                window(40.0, 10_080, Some(90_000))
+            )
+            .pause_at(now),
+            Some(RateLimitPause {
+                used_percent: 97.5,
+                window_minutes: Some(300),
+                resets_in: Duration::from_secs(605),
+            })
+        );
+        assert_eq!(
+            gate_with(
+                window(100.0, 300, Some(1_600)),
+                window(92.0, 10_080, Some(4_000))
+            )
+            .pause_at(now),
+            Some(RateLimitPause {
+                used_percent: 92.0,
+                window_minutes: Some(10_080),
+                resets_in: Duration::from_secs(3_005),
+            })
+        );
+    }
+
+    #[test]
+    fn pause_ignores_windows_without_a_future_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        assert_eq!(
+            gate_with(window(100.0, 300, None), window(99.0, 10_080, Some(1_000))).pause_at(now),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_threshold_accepts_percentages() {
+        assert_eq!(parse_threshold("90"), Ok(90));
+        assert_eq!(parse_threshold("85%"), Ok(85));
+        assert_eq!(
+            parse_threshold("0"),
+            Err("percentage must be between 1 and 100, got 0".to_string())
+        );
+        assert_eq!(
+            parse_threshold("abc"),
+            Err("invalid percentage `abc`".to_string())
+        );
+    }
+}
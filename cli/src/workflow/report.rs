@@ -0,0 +1,465 @@
+//! Final report turn for structured project output (`exec --output-schema`).
+//!
+//! After a project succeeds, one more turn runs on a fresh thread in a read-only sandbox with the
+//! requested JSON Schema as its `outputSchema`. Its final message is parsed as JSON and checked
+//! against the schema before it is handed to the client.
+//!
+//! Upstream already constrains the final message to the schema; the check here guards against
+//! models or providers that ignore it. It covers the subset of JSON Schema structured outputs use
+//! (see [`validate_json_schema`]); schemas using other keywords are rejected up front by
+//! [`check_supported_schema`].
+
+use anyhow::Context;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::user_input::UserInput;
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::unbounded_channel;
+
+/// Run the report turn with `prompt` and return its final message, validated against
+/// `output_schema`.
+pub async fn run_report_turn(
+    config: crate::app_server::AppServerBackendConfig,
+    prompt: String,
+    output_schema: &Value,
+) -> anyhow::Result<Value> {
+    let (op_tx, op_rx) = unbounded_channel::<Op>();
+    let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+    let (fatal_exit_tx, mut fatal_exit_rx) = unbounded_channel::<String>();
+    let backend = tokio::spawn(crate::app_server::run_app_server_backend(
+        config,
+        op_rx,
+        event_tx,
+        fatal_exit_tx,
+    ));
+
+    let _ = op_tx.send(Op::UserInput {
+        items: vec![UserInput::Text {
+            text: prompt,
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: Some(output_schema.clone()),
+    });
+    let message = wait_for_final_message(&mut event_rx, &mut fatal_exit_rx).await;
+    if message.is_err() {
+        backend.abort();
+    }
+    drop(op_tx);
+    let _ = backend.await;
+
+    let message = message?.context("the report turn ended without a final message")?;
+    parse_report(&message, output_schema)
+}
+
+/// Wait for the end of the report turn, returning the last agent message of its turns.
+async fn wait_for_final_message(
+    event_rx: &mut UnboundedReceiver<Event>,
+    fatal_exit_rx: &mut UnboundedReceiver<String>,
+) -> anyhow::Result<Option<String>> {
+    let mut last_agent_message = None;
+    loop {
+        tokio::select! {
+            Some(message) = fatal_exit_rx.recv() => anyhow::bail!(message),
+            event = event_rx.recv() => {
+                let Some(event) = event else {
+                    anyhow::bail!("event stream closed unexpectedly");
+                };
+                match event.msg {
+                    EventMsg::TurnComplete(ev) if ev.last_agent_message.is_some() => {
+                        last_agent_message = ev.last_agent_message;
+                    }
+                    EventMsg::PotterRoundFinished { outcome } => {
+                        return match outcome {
+                            PotterRoundOutcome::Completed => Ok(last_agent_message),
+                            PotterRoundOutcome::Interrupted => anyhow::bail!("interrupted"),
+                            PotterRoundOutcome::UserRequested => anyhow::bail!("user requested"),
+                            PotterRoundOutcome::TaskFailed { message }
+                            | PotterRoundOutcome::Fatal { message, .. } => anyhow::bail!(message),
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Parse the report turn's final message and check it against `output_schema`.
+fn parse_report(message: &str, output_schema: &Value) -> anyhow::Result<Value> {
+    let output: Value =
+        serde_json::from_str(message.trim()).context("the final message is not valid JSON")?;
+    validate_json_schema(&output, output_schema).map_err(|violation| {
+        anyhow::anyhow!("the final message does not match the output schema: {violation}")
+    })?;
+    Ok(output)
+}
+
+/// Keywords [`validate_json_schema`] checks.
+const SUPPORTED_KEYWORDS: [&str; 8] = [
+    "type",
+    "enum",
+    "const",
+    "anyOf",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+];
+
+/// Annotation keywords, which do not constrain values (`format` is an annotation by default in
+/// JSON Schema 2019-09 and later).
+const ANNOTATION_KEYWORDS: [&str; 10] = [
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "format",
+];
+
+/// Check `value` against `schema`, returning the first violation.
+///
+/// Supports `type`, `enum`, `const`, `anyOf`, `properties`, `required`, `additionalProperties` and
+/// `items`, ignoring annotations; [`check_supported_schema`] rejects schemas using anything else.
+pub fn validate_json_schema(value: &Value, schema: &Value) -> Result<(), String> {
+    check(value, schema, "")
+}
+
+/// Reject schemas using keywords [`validate_json_schema`] cannot check (`$ref`, `oneOf`,
+/// `pattern`, `minimum`, ...), naming the first one found.
+pub fn check_supported_schema(schema: &Value) -> Result<(), String> {
+    check_schema_keywords(schema, "")
+}
+
+fn check_schema_keywords(schema: &Value, pointer: &str) -> Result<(), String> {
+    let schema = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(schema) => schema,
+        _ => return Err(violation(pointer, "the schema is not an object")),
+    };
+    for (keyword, value) in schema {
+        let keyword_pointer = format!("{pointer}/{}", escape_pointer(keyword));
+        match keyword.as_str() {
+            "properties" => {
+                let Value::Object(properties) = value else {
+                    return Err(violation(&keyword_pointer, "expected an object of schemas"));
+                };
+                for (name, property) in properties {
+                    check_schema_keywords(
+                        property,
+                        &format!("{keyword_pointer}/{}", escape_pointer(name)),
+                    )?;
+                }
+            }
+            "anyOf" => {
+                let Value::Array(schemas) = value else {
+                    return Err(violation(&keyword_pointer, "expected an array of schemas"));
+                };
+                for (index, schema) in schemas.iter().enumerate() {
+                    check_schema_keywords(schema, &format!("{keyword_pointer}/{index}"))?;
+                }
+            }
+            "items" | "additionalProperties" => check_schema_keywords(value, &keyword_pointer)?,
+            keyword
+                if SUPPORTED_KEYWORDS.contains(&keyword)
+                    || ANNOTATION_KEYWORDS.contains(&keyword) => {}
+            keyword => {
+                return Err(violation(
+                    pointer,
+                    &format!(
+                        "unsupported keyword `{keyword}` (supported: {})",
+                        SUPPORTED_KEYWORDS.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+fn check(value: &Value, schema: &Value, pointer: &str) -> Result<(), String> {
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(violation(pointer, "no value is allowed here")),
+        Value::Object(schema) => schema,
+        _ => return Err(violation(pointer, "the schema is not an object")),
+    };
+
+    let types = match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+        return Err(violation(
+            pointer,
+            &format!("expected {}, got {}", types.join(" or "), type_name(value)),
+        ));
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        return Err(violation(
+            pointer,
+            &format!(
+                "expected one of {}, got {value}",
+                Value::Array(allowed.clone())
+            ),
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(violation(
+            pointer,
+            &format!("expected {expected}, got {value}"),
+        ));
+    }
+    if let Some(Value::Array(any_of)) = schema.get("anyOf")
+        && !any_of
+            .iter()
+            .any(|schema| check(value, schema, pointer).is_ok())
+    {
+        return Err(violation(pointer, "matches none of the `anyOf` schemas"));
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        return Err(violation(
+                            pointer,
+                            &format!("missing required property `{name}`"),
+                        ));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (name, property) in object {
+                let property_pointer = format!("{pointer}/{}", escape_pointer(name));
+                match (
+                    properties.and_then(|properties| properties.get(name)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(property_schema), _) => {
+                        check(property, property_schema, &property_pointer)?
+                    }
+                    (None, Some(Value::Bool(false))) => {
+                        return Err(violation(pointer, &format!("unexpected property `{name}`")));
+                    }
+                    (None, Some(additional)) => check(property, additional, &property_pointer)?,
+                    (None, None) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(item, item_schema, &format!("{pointer}/{index}"))?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        _ => type_name(value) == name,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn violation(pointer: &str, message: &str) -> String {
+    let pointer = if pointer.is_empty() { "/" } else { pointer };
+    format!("at {pointer}: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn report_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string" },
+                "status": { "enum": ["done", "partial"] },
+                "files": { "type": "array", "items": { "type": "string" } },
+                "tests": { "type": ["integer", "null"] }
+            },
+            "required": ["summary", "status"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn matching_values_pass() {
+        for value in [
+            json!({ "summary": "Added the flag", "status": "done" }),
+            json!({
+                "summary": "Added the flag",
+                "status": "partial",
+                "files": ["cli/src/main.rs"],
+                "tests": 3
+            }),
+            json!({ "summary": "", "status": "done", "tests": null }),
+        ] {
+            assert_eq!(validate_json_schema(&value, &report_schema()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn violations_name_the_offending_value() {
+        let cases = [
+            (json!(["done"]), "at /: expected object, got array"),
+            (
+                json!({ "status": "done" }),
+                "at /: missing required property `summary`",
+            ),
+            (
+                json!({ "summary": "x", "status": "started" }),
+                r#"at /status: expected one of ["done","partial"], got "started""#,
+            ),
+            (
+                json!({ "summary": "x", "status": "done", "files": ["a", 1] }),
+                "at /files/1: expected string, got number",
+            ),
+            (
+                json!({ "summary": "x", "status": "done", "tests": 1.5 }),
+                "at /tests: expected integer or null, got number",
+            ),
+            (
+                json!({ "summary": "x", "status": "done", "extra": true }),
+                "at /: unexpected property `extra`",
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                validate_json_schema(&value, &report_schema()),
+                Err(expected.to_string()),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn any_of_and_const_are_checked() {
+        let schema = json!({
+            "anyOf": [
+                { "type": "object", "properties": { "kind": { "const": "ok" } } },
+                { "type": "string" }
+            ]
+        });
+        assert_eq!(
+            validate_json_schema(&json!({ "kind": "ok" }), &schema),
+            Ok(())
+        );
+        assert_eq!(validate_json_schema(&json!("fine"), &schema), Ok(()));
+        assert_eq!(
+            validate_json_schema(&json!({ "kind": "error" }), &schema),
+            Err("at /: matches none of the `anyOf` schemas".to_string())
+        );
+    }
+
+    #[test]
+    fn schemas_with_unchecked_keywords_are_rejected() {
+        assert_eq!(check_supported_schema(&report_schema()), Ok(()));
+        assert_eq!(
+            check_supported_schema(&json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "string",
+                "format": "date-time",
+                "description": "When the work finished"
+            })),
+            Ok(())
+        );
+
+        let cases = [
+            (
+                json!({ "oneOf": [{ "type": "string" }] }),
+                "at /: unsupported keyword `oneOf`",
+            ),
+            (
+                json!({ "type": "object", "properties": { "a/b": { "$ref": "#/$defs/x" } } }),
+                "at /properties/a~1b: unsupported keyword `$ref`",
+            ),
+            (
+                json!({ "type": "array", "items": { "type": "integer", "minimum": 0 } }),
+                "at /items: unsupported keyword `minimum`",
+            ),
+            (
+                json!({ "anyOf": [{ "type": "string", "pattern": "^x" }] }),
+                "at /anyOf/0: unsupported keyword `pattern`",
+            ),
+        ];
+        for (schema, expected) in cases {
+            assert_eq!(
+                check_supported_schema(&schema),
+                Err(format!(
+                    "{expected} (supported: type, enum, const, anyOf, properties, required, \
+                     additionalProperties, items)"
+                )),
+                "{schema}"
+            );
+        }
+    }
+
+    #[test]
+    fn report_must_be_json_matching_the_schema() {
+        assert_eq!(
+            parse_report(
+                "\n{\"summary\": \"Added the flag\", \"status\": \"done\"}\n",
+                &report_schema()
+            )
+            .expect("valid report"),
+            json!({ "summary": "Added the flag", "status": "done" })
+        );
+
+        let err = parse_report("All done!", &report_schema()).expect_err("not json");
+        assert!(
+            format!("{err:#}").starts_with("the final message is not valid JSON: "),
+            "unexpected error: {err:#}"
+        );
+
+        let err = parse_report("{\"summary\": 1, \"status\": \"done\"}", &report_schema())
+            .expect_err("wrong type");
+        assert_eq!(
+            format!("{err:#}"),
+            "the final message does not match the output schema: at /summary: expected string, got number"
+        );
+    }
+}
@@ -27,6 +27,9 @@ use anyhow::Context;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterProjectOutcome;
+#[cfg(test)]
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
 #[cfg(test)]
 use codex_protocol::protocol::SessionConfiguredEvent;
@@ -34,13 +37,14 @@ use codex_tui::ExitReason;
 use codex_tui::InterruptedProjectAction;
 use tokio::sync::mpsc::unbounded_channel;
 
+use crate::workflow::project_runner::PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE;
+
 const PROJECT_MAIN_FILE: &str = "MAIN.md";
 const CODEXPOTTER_DIR: &str = ".codexpotter";
 
-// Sentinel prompt for `Interrupt -> Continue`: forces a new turn while retrying the same round.
-const PROGRESS_FILE_CHANGED_TURN_PROMPT_OVERRIDE: &str = "Progress file has been changed by user";
-
-trait ResumeUi: crate::workflow::round_runner::PotterRoundUi {
+trait ResumeUi:
+    crate::workflow::round_runner::PotterRoundUi + crate::workflow::worktree::WorktreeIntegrationUi
+{
     fn clear(&mut self) -> anyhow::Result<()>;
 
     fn prompt_action_picker<'a>(
@@ -57,6 +61,8 @@ trait ResumeUi: crate::workflow::round_runner::PotterRoundUi {
         &mut self,
         summary: crate::app_server::potter::InterruptedProjectSummary,
     );
+
+    fn insert_notice(&mut self, message: String);
 }
 
 impl ResumeUi for codex_tui::CodexPotterTui {
@@ -95,6 +101,10 @@ impl ResumeUi for codex_tui::CodexPotterTui {
             summary.git_commit_end,
         );
     }
+
+    fn insert_notice(&mut self, message: String) {
+        codex_tui::CodexPotterTui::insert_notice(self, message, false);
+    }
 }
 
 trait ResumeClock {
@@ -317,8 +327,13 @@ where
     C: ResumeClock,
 {
     let project_id = resume.project_id.clone();
-    let prompt_footer =
-        codex_tui::PromptFooterContext::new(resume.working_dir.clone(), resume.git_branch.clone());
+    let prompt_footer = codex_tui::PromptFooterContext::new(
+        resume
+            .worktree
+            .clone()
+            .unwrap_or(resume.working_dir.clone()),
+        resume.git_branch.clone(),
+    );
 
     let (op_tx, mut op_rx) = unbounded_channel::<Op>();
     tokio::spawn(async move { while op_rx.recv().await.is_some() {} });
@@ -410,10 +425,9 @@ where
         None => crate::rounds::round_budget_to_u32(iterate_rounds)?,
     };
     let initial_status_header_prefix = resume.unfinished_round.as_ref().map(|unfinished| {
-        format!(
-            "Round {}/{}",
-            unfinished.round_current, unfinished.round_total
-        )
+        unfinished
+            .round_kind
+            .round_prefix(unfinished.round_current, unfinished.round_total)
     });
 
     let (start_rounds_response, buffered_events) = app_server
@@ -422,6 +436,7 @@ where
             rounds: Some(rounds),
             resume_policy: None,
             event_mode: Some(crate::app_server::potter::PotterEventMode::Interactive),
+            output_schema: None,
         })
         .await
         .context("project/start_rounds via potter app-server")?;
@@ -457,7 +472,24 @@ where
         initial_status_header_prefix = None;
 
         match exit {
-            crate::workflow::project_render_loop::PotterProjectRenderExit::Completed { .. } => {
+            crate::workflow::project_render_loop::PotterProjectRenderExit::Completed {
+                outcome,
+            } => {
+                if let Some(notice) = crate::workflow::project_runner::early_stop_notice(&outcome) {
+                    ui.insert_notice(format!("Project {notice}"));
+                }
+                if matches!(outcome, PotterProjectOutcome::Succeeded)
+                    && let (Some(worktree_dir), Some(branch)) =
+                        (&resume.worktree, &resume.git_branch)
+                {
+                    crate::workflow::worktree::offer_worktree_integration(
+                        ui,
+                        &resume.working_dir,
+                        worktree_dir,
+                        branch,
+                    )
+                    .await?;
+                }
                 return Ok(ResumeExit::Completed);
             }
             crate::workflow::project_render_loop::PotterProjectRenderExit::Interrupted {
@@ -518,6 +550,15 @@ where
                 let _ = app_server.project_interrupt(project_id.clone()).await;
                 return Ok(ResumeExit::FatalExitRequested);
             }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::Detached => {
+                anyhow::bail!("internal error: resumed project render loop detached");
+            }
+            crate::workflow::project_render_loop::PotterProjectRenderExit::AwaitingPlanApproval {
+                ..
+            } => {
+                // `--review-plan` only pauses fresh projects after their planning round.
+                anyhow::bail!("internal error: resumed project paused for plan approval");
+            }
         }
     }
 }
@@ -679,6 +720,7 @@ struct ResumeReplayPlans {
 struct UnfinishedRoundPlan {
     round_current: u32,
     round_total: u32,
+    kind: PotterRoundKind,
     thread_id: codex_protocol::ThreadId,
     rollout_path: PathBuf,
     project_started: Option<(Option<String>, PathBuf)>,
@@ -737,6 +779,7 @@ fn build_round_replay_plans(
         events.push(EventMsg::PotterRoundStarted {
             current: round.round_current,
             total: round.round_total,
+            kind: round.kind,
         });
 
         let rollout_path = resolve_rollout_path_for_replay(project, &round.rollout_path);
@@ -750,6 +793,23 @@ fn build_round_replay_plans(
             .with_context(|| format!("replay rollout {}", rollout_path.display()))?;
         events.append(&mut rollout_events);
 
+        events.extend(round.user_inputs.into_iter().map(|user_input| {
+            EventMsg::PotterUserInputAnswered {
+                server_name: user_input.server_name,
+                answers: user_input.answers,
+                declined: user_input.declined,
+            }
+        }));
+
+        if let Some(verification) = round.verification {
+            events.push(EventMsg::PotterRoundVerification {
+                command: verification.command,
+                passed: verification.passed,
+                exit_code: verification.exit_code,
+                output: verification.output,
+            });
+        }
+
         if let Some(project_succeeded) = round.project_succeeded {
             events.push(EventMsg::PotterProjectSucceeded {
                 rounds: project_succeeded.rounds,
@@ -773,6 +833,7 @@ fn build_round_replay_plans(
     let unfinished_round = index.unfinished_round.map(|round| UnfinishedRoundPlan {
         round_current: round.round_current,
         round_total: round.round_total,
+        kind: round.kind,
         thread_id: round.thread_id,
         rollout_path: resolve_rollout_path_for_replay(project, &round.rollout_path),
         project_started: project_started
@@ -953,6 +1014,7 @@ fn build_unfinished_round_pre_action_events(
     events.push(EventMsg::PotterRoundStarted {
         current: unfinished.round_current,
         total: unfinished.round_total,
+        kind: unfinished.kind,
     });
     events.push(EventMsg::PotterRoundFinished {
         outcome: PotterRoundOutcome::Completed,
@@ -1130,7 +1192,6 @@ pub fn filter_pending_interactive_prompts_for_replay(events: Vec<EventMsg>) -> V
 #[cfg(test)]
 mod tests {
     use super::*;
-    use codex_protocol::protocol::PotterProjectOutcome;
     use codex_protocol::protocol::TokenUsage;
     use pretty_assertions::assert_eq;
 
@@ -1188,7 +1249,7 @@ mod tests {
                                 PotterRoundOutcome::TaskFailed { message } => {
                                     codex_tui::ExitReason::TaskFailed(message.clone())
                                 }
-                                PotterRoundOutcome::Fatal { message } => {
+                                PotterRoundOutcome::Fatal { message, .. } => {
                                     codex_tui::ExitReason::Fatal(message.clone())
                                 }
                             },
@@ -1207,6 +1268,17 @@ mod tests {
         }
     }
 
+    impl crate::workflow::worktree::WorktreeIntegrationUi for MockResumeUi {
+        fn prompt_worktree_integration<'a>(
+            &'a mut self,
+            _actions: Vec<String>,
+        ) -> crate::workflow::round_runner::UiFuture<'a, Option<usize>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn insert_worktree_notice(&mut self, _message: String, _is_error: bool) {}
+    }
+
     impl ResumeUi for MockResumeUi {
         fn clear(&mut self) -> anyhow::Result<()> {
             self.ops.push(MockUiOp::Clear);
@@ -1240,6 +1312,8 @@ mod tests {
             self.ops
                 .push(MockUiOp::InsertInterruptedProjectSummary(summary));
         }
+
+        fn insert_notice(&mut self, _message: String) {}
     }
 
     #[derive(Debug)]
@@ -1432,12 +1506,14 @@ mod tests {
                 .path()
                 .join(".codexpotter/projects/2026/02/01/1/MAIN.md"),
             git_branch: None,
+            worktree: None,
             replay: crate::app_server::potter::ProjectResumeReplay {
                 completed_rounds: Vec::new(),
             },
             unfinished_round: Some(crate::app_server::potter::ProjectResumeUnfinishedRound {
                 round_current: 1,
                 round_total: 1,
+                round_kind: PotterRoundKind::Implement,
                 pre_action_events: vec![
                     EventMsg::PotterProjectStarted {
                         user_message: Some(String::from("hello")),
@@ -1450,6 +1526,7 @@ mod tests {
                     EventMsg::PotterRoundStarted {
                         current: 1,
                         total: 1,
+                        kind: PotterRoundKind::Implement,
                     },
                     EventMsg::PotterRoundFinished {
                         outcome: PotterRoundOutcome::Completed,
@@ -1521,6 +1598,7 @@ mod tests {
             progress_file_rel: progress_file_rel.clone(),
             progress_file: temp.path().join(&progress_file_rel),
             git_branch: None,
+            worktree: None,
             replay: crate::app_server::potter::ProjectResumeReplay {
                 completed_rounds: Vec::new(),
             },
@@ -1534,6 +1612,7 @@ mod tests {
                     msg: EventMsg::PotterRoundStarted {
                         current: 1,
                         total: 1,
+                        kind: PotterRoundKind::Implement,
                     },
                 },
                 Event {
@@ -1558,6 +1637,7 @@ mod tests {
                         msg: EventMsg::PotterRoundStarted {
                             current: 1,
                             total: 1,
+                            kind: PotterRoundKind::Implement,
                         },
                     },
                     Event {
@@ -1636,6 +1716,7 @@ mod tests {
             progress_file_rel: progress_file_rel.clone(),
             progress_file: temp.path().join(&progress_file_rel),
             git_branch: None,
+            worktree: None,
             replay: crate::app_server::potter::ProjectResumeReplay {
                 completed_rounds: Vec::new(),
             },
@@ -1657,6 +1738,7 @@ mod tests {
                     msg: EventMsg::PotterRoundStarted {
                         current: 1,
                         total: 1,
+                        kind: PotterRoundKind::Implement,
                     },
                 },
                 Event {
@@ -1875,6 +1957,8 @@ mod tests {
             &ExitReason::Fatal("boom".to_string()),
             &PotterRoundOutcome::Fatal {
                 message: "boom".to_string(),
+                stderr_tail: Vec::new(),
+                stderr_log: None,
             },
         );
         assert_eq!(decision, ReplayRoundExitDecision::Continue);
@@ -1921,6 +2005,8 @@ mod tests {
             crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             crate::workflow::rollout::PotterRolloutLine::RoundConfigured {
                 thread_id,
@@ -1976,6 +2062,8 @@ mod tests {
             crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             crate::workflow::rollout::PotterRolloutLine::RoundConfigured {
                 thread_id,
@@ -1985,10 +2073,13 @@ mod tests {
             },
             crate::workflow::rollout::PotterRolloutLine::RoundFinished {
                 outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
             },
             crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 2,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             crate::workflow::rollout::PotterRolloutLine::RoundConfigured {
                 thread_id: next_thread_id,
@@ -2041,6 +2132,8 @@ mod tests {
             crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
         ];
 
@@ -2066,6 +2159,7 @@ mod tests {
         let mut unfinished = UnfinishedRoundPlan {
             round_current: 1,
             round_total: 10,
+            kind: PotterRoundKind::Implement,
             thread_id,
             rollout_path: resolved.workdir.join("rollout.jsonl"),
             project_started: Some((
@@ -2094,7 +2188,7 @@ mod tests {
             user_prompt_file,
             &PathBuf::from(".codexpotter/projects/2026/02/01/1/MAIN.md")
         );
-        let EventMsg::PotterRoundStarted { current, total } = &events[1] else {
+        let EventMsg::PotterRoundStarted { current, total, .. } = &events[1] else {
             panic!("expected PotterRoundStarted, got: {:?}", events[1]);
         };
         assert_eq!(*current, 1);
@@ -2119,6 +2213,7 @@ mod tests {
         let mut unfinished = UnfinishedRoundPlan {
             round_current: 2,
             round_total: 10,
+            kind: PotterRoundKind::Review,
             thread_id,
             rollout_path: resolved.workdir.join("rollout.jsonl"),
             project_started: None,
@@ -2129,11 +2224,17 @@ mod tests {
         assert_eq!(unfinished.project_started, None);
         assert_eq!(events.len(), 2);
 
-        let EventMsg::PotterRoundStarted { current, total } = &events[0] else {
+        let EventMsg::PotterRoundStarted {
+            current,
+            total,
+            kind,
+        } = &events[0]
+        else {
             panic!("expected PotterRoundStarted, got: {:?}", events[0]);
         };
         assert_eq!(*current, 2);
         assert_eq!(*total, 10);
+        assert_eq!(*kind, PotterRoundKind::Review);
 
         let EventMsg::PotterRoundFinished { outcome } = &events[1] else {
             panic!("expected PotterRoundFinished, got: {:?}", events[1]);
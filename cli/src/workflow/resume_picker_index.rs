@@ -90,6 +90,9 @@ fn row_for_progress_file(workdir: &Path, progress_file: &Path) -> Option<ResumeP
         crate::workflow::project::progress_file_short_title(&resolved.progress_file).ok()?;
     let git_branch =
         crate::workflow::project::progress_file_git_branch(&resolved.progress_file).ok()?;
+    let worktree = crate::workflow::project::progress_file_worktree(&resolved.progress_file)
+        .ok()?
+        .map(|worktree| resolved.workdir.join(worktree));
 
     let user_request = match short_title {
         Some(title) => title,
@@ -106,6 +109,7 @@ fn row_for_progress_file(workdir: &Path, progress_file: &Path) -> Option<ResumeP
         created_at,
         updated_at,
         git_branch,
+        worktree,
     })
 }
 
@@ -259,6 +263,8 @@ git_branch: "{git_branch}"
             &crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
         )
         .expect("append round_started");
@@ -409,6 +415,8 @@ git_branch: "{git_branch}"
             &crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
         )
         .expect("append round_started");
@@ -444,6 +452,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(10),
             git_branch: None,
+            worktree: None,
         };
         let b = ResumePickerRow {
             project_path: PathBuf::from("/b"),
@@ -451,6 +460,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(20),
             git_branch: None,
+            worktree: None,
         };
         let c = ResumePickerRow {
             project_path: PathBuf::from("/c"),
@@ -458,6 +468,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(20),
             git_branch: None,
+            worktree: None,
         };
 
         let mut rows = vec![a.clone(), b.clone(), c.clone()];
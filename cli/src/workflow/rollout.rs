@@ -1,8 +1,9 @@
 //! Potter rollout log (project boundary JSONL).
 //!
 //! CodexPotter persists an append-only `potter-rollout.jsonl` alongside each project. This log
-//! records project and round boundaries (started/configured/finished) and a subset of metadata
-//! needed for resume and auditing.
+//! records project and round boundaries (started/configured/finished, including each round's
+//! schedule role), per-round git checkpoints, verification gate results, answered user input
+//! requests, stall stops and a subset of metadata needed for resume and auditing.
 //!
 //! The writer is intentionally strict: failures are surfaced to the caller so the control plane
 //! can abort rather than silently diverging from the persisted replay source of truth.
@@ -13,10 +14,14 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::PotterUserInputAnswer;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::workflow::checkpoint::PotterGitCheckpoint;
+
 /// Name of the JSONL file that records CodexPotter project/round boundaries.
 pub const POTTER_ROLLOUT_FILENAME: &str = "potter-rollout.jsonl";
 
@@ -31,6 +36,12 @@ pub enum PotterRolloutLine {
     RoundStarted {
         current: u32,
         total: u32,
+        /// Role of the round in the round schedule (omitted for implement rounds).
+        #[serde(default, skip_serializing_if = "PotterRoundKind::is_implement")]
+        kind: PotterRoundKind,
+        /// Git state when the round started (absent outside git repositories).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        checkpoint: Option<PotterGitCheckpoint>,
     },
     RoundConfigured {
         thread_id: ThreadId,
@@ -40,6 +51,12 @@ pub enum PotterRolloutLine {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         rollout_base_dir: Option<PathBuf>,
     },
+    RoundVerification {
+        command: String,
+        passed: bool,
+        exit_code: Option<i32>,
+        output: String,
+    },
     ProjectSucceeded {
         rounds: u32,
         duration_secs: u64,
@@ -49,6 +66,19 @@ pub enum PotterRolloutLine {
     },
     RoundFinished {
         outcome: PotterRoundOutcome,
+        /// Git state when the round finished (absent outside git repositories).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        checkpoint: Option<PotterGitCheckpoint>,
+    },
+    /// The project was stopped after `rounds` consecutive no-op rounds (`--stall-rounds`).
+    ProjectStalled { rounds: u32 },
+    /// A user input request (`request_user_input` or an MCP elicitation) of the current round was
+    /// answered or declined. Secret answers are recorded masked.
+    UserInputAnswered {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server_name: Option<String>,
+        answers: Vec<PotterUserInputAnswer>,
+        declined: bool,
     },
 }
 
@@ -152,6 +182,8 @@ mod tests {
             &PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
         )
         .expect("append round_started");
@@ -167,6 +199,8 @@ mod tests {
                 PotterRolloutLine::RoundStarted {
                     current: 1,
                     total: 10,
+                    kind: PotterRoundKind::Implement,
+                    checkpoint: None,
                 },
             ]
         );
@@ -3,7 +3,8 @@
 //! Resume needs a structured view of the append-only rollout log. This module parses the log
 //! into:
 //! - the initial `ProjectStarted` info
-//! - a list of completed rounds with their thread ids, rollout paths and outcomes
+//! - a list of completed rounds with their thread ids, rollout paths, answered user input
+//!   requests, verification results and outcomes
 //! - an optional unfinished round at EOF (round started/configured but no finished marker)
 //!
 //! Parsing is strict and validates key invariants so corrupted logs fail fast.
@@ -11,7 +12,9 @@
 use std::path::PathBuf;
 
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::PotterUserInputAnswer;
 
 use crate::workflow::rollout::PotterRolloutLine;
 
@@ -32,8 +35,12 @@ pub struct ProjectStartedIndex {
 pub struct CompletedRoundIndex {
     pub round_current: u32,
     pub round_total: u32,
+    pub kind: PotterRoundKind,
     pub thread_id: ThreadId,
     pub rollout_path: PathBuf,
+    /// User input requests answered (or declined) during the round, in order.
+    pub user_inputs: Vec<UserInputAnsweredIndex>,
+    pub verification: Option<RoundVerificationIndex>,
     pub project_succeeded: Option<ProjectSucceededIndex>,
     pub outcome: PotterRoundOutcome,
 }
@@ -42,10 +49,26 @@ pub struct CompletedRoundIndex {
 pub struct UnfinishedRoundIndex {
     pub round_current: u32,
     pub round_total: u32,
+    pub kind: PotterRoundKind,
     pub thread_id: ThreadId,
     pub rollout_path: PathBuf,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundVerificationIndex {
+    pub command: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserInputAnsweredIndex {
+    pub server_name: Option<String>,
+    pub answers: Vec<PotterUserInputAnswer>,
+    pub declined: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProjectSucceededIndex {
     pub rounds: u32,
@@ -62,7 +85,10 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
     struct RoundBuilder {
         round_current: u32,
         round_total: u32,
+        kind: PotterRoundKind,
         configured: Option<(ThreadId, PathBuf)>,
+        user_inputs: Vec<UserInputAnsweredIndex>,
+        verification: Option<RoundVerificationIndex>,
         project_succeeded: Option<ProjectSucceededIndex>,
     }
 
@@ -85,6 +111,8 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
             PotterRolloutLine::RoundStarted {
                 current: round_current,
                 total: round_total,
+                kind,
+                ..
             } => {
                 if project_started.is_none() {
                     anyhow::bail!("potter-rollout: missing project_started before first round");
@@ -95,7 +123,10 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                 current = Some(RoundBuilder {
                     round_current: *round_current,
                     round_total: *round_total,
+                    kind: *kind,
                     configured: None,
+                    user_inputs: Vec::new(),
+                    verification: None,
                     project_succeeded: None,
                 });
             }
@@ -112,6 +143,41 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                 }
                 builder.configured = Some((*thread_id, rollout_path.clone()));
             }
+            PotterRolloutLine::UserInputAnswered {
+                server_name,
+                answers,
+                declined,
+            } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: user_input_answered outside a round");
+                };
+                builder.user_inputs.push(UserInputAnsweredIndex {
+                    server_name: server_name.clone(),
+                    answers: answers.clone(),
+                    declined: *declined,
+                });
+            }
+            PotterRolloutLine::RoundVerification {
+                command,
+                passed,
+                exit_code,
+                output,
+            } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: round_verification outside a round");
+                };
+                if builder.verification.is_some() || builder.project_succeeded.is_some() {
+                    anyhow::bail!(
+                        "potter-rollout: round_verification must appear once before project_succeeded"
+                    );
+                }
+                builder.verification = Some(RoundVerificationIndex {
+                    command: command.clone(),
+                    passed: *passed,
+                    exit_code: *exit_code,
+                    output: output.clone(),
+                });
+            }
             PotterRolloutLine::ProjectSucceeded {
                 rounds,
                 duration_secs,
@@ -125,6 +191,15 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                 if builder.project_succeeded.is_some() {
                     anyhow::bail!("potter-rollout: duplicate project_succeeded in a single round");
                 }
+                if builder
+                    .verification
+                    .as_ref()
+                    .is_some_and(|verification| !verification.passed)
+                {
+                    anyhow::bail!(
+                        "potter-rollout: project_succeeded after failed round_verification"
+                    );
+                }
                 builder.project_succeeded = Some(ProjectSucceededIndex {
                     rounds: *rounds,
                     duration_secs: *duration_secs,
@@ -133,7 +208,7 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                     git_commit_end: git_commit_end.clone(),
                 });
             }
-            PotterRolloutLine::RoundFinished { outcome } => {
+            PotterRolloutLine::RoundFinished { outcome, .. } => {
                 let Some(builder) = current.take() else {
                     anyhow::bail!("potter-rollout: round_finished without round_started");
                 };
@@ -150,12 +225,22 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                 completed_rounds.push(CompletedRoundIndex {
                     round_current: builder.round_current,
                     round_total: builder.round_total,
+                    kind: builder.kind,
                     thread_id,
                     rollout_path,
+                    user_inputs: builder.user_inputs,
+                    verification: builder.verification,
                     project_succeeded: builder.project_succeeded,
                     outcome: outcome.clone(),
                 });
             }
+            PotterRolloutLine::ProjectStalled { .. } => {
+                // Informational only: resume treats a stalled project like one whose round
+                // budget ran out.
+                if current.is_some() || completed_rounds.is_empty() {
+                    anyhow::bail!("potter-rollout: project_stalled must follow round_finished");
+                }
+            }
         }
     }
 
@@ -170,6 +255,7 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
             Some(UnfinishedRoundIndex {
                 round_current: builder.round_current,
                 round_total: builder.round_total,
+                kind: builder.kind,
                 thread_id,
                 rollout_path,
             })
@@ -213,6 +299,8 @@ mod tests {
             PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             PotterRolloutLine::RoundConfigured {
                 thread_id: thread_id(),
@@ -222,6 +310,7 @@ mod tests {
             },
             PotterRolloutLine::RoundFinished {
                 outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
             },
         ];
 
@@ -236,8 +325,11 @@ mod tests {
                 completed_rounds: vec![CompletedRoundIndex {
                     round_current: 1,
                     round_total: 10,
+                    kind: PotterRoundKind::Implement,
                     thread_id: thread_id(),
                     rollout_path: PathBuf::from("rollout.jsonl"),
+                    user_inputs: Vec::new(),
+                    verification: None,
                     project_succeeded: None,
                     outcome: PotterRoundOutcome::Completed,
                 }],
@@ -246,6 +338,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_resume_index_accepts_project_stalled_only_after_round_finished() {
+        let round = |current| {
+            vec![
+                PotterRolloutLine::RoundStarted {
+                    current,
+                    total: 10,
+                    kind: PotterRoundKind::Implement,
+                    checkpoint: None,
+                },
+                PotterRolloutLine::RoundConfigured {
+                    thread_id: thread_id(),
+                    rollout_path: PathBuf::from("rollout.jsonl"),
+                    rollout_path_raw: None,
+                    rollout_base_dir: None,
+                },
+                PotterRolloutLine::RoundFinished {
+                    outcome: PotterRoundOutcome::Completed,
+                    checkpoint: None,
+                },
+            ]
+        };
+        let mut lines = vec![PotterRolloutLine::ProjectStarted {
+            user_message: None,
+            user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+        }];
+        lines.extend(round(1));
+        lines.push(PotterRolloutLine::ProjectStalled { rounds: 1 });
+        lines.extend(round(2));
+
+        let index = build_resume_index(&lines).expect("build resume index");
+        assert_eq!(index.completed_rounds.len(), 2);
+        assert_eq!(index.unfinished_round, None);
+
+        let mut in_round = lines.clone();
+        in_round.insert(
+            in_round.len() - 1,
+            PotterRolloutLine::ProjectStalled { rounds: 1 },
+        );
+        let err = build_resume_index(&in_round).expect_err("project_stalled inside a round");
+        assert!(
+            err.to_string().contains("project_stalled"),
+            "unexpected error: {err:#}"
+        );
+    }
+
+    #[test]
+    fn build_resume_index_collects_user_inputs_of_each_round() {
+        let answered = |declined| PotterRolloutLine::UserInputAnswered {
+            server_name: None,
+            answers: if declined {
+                Vec::new()
+            } else {
+                vec![PotterUserInputAnswer {
+                    header: "Database".to_string(),
+                    question: "Which database?".to_string(),
+                    answers: vec!["SQLite".to_string()],
+                }]
+            },
+            declined,
+        };
+        let mut lines = vec![
+            PotterRolloutLine::ProjectStarted {
+                user_message: None,
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+            },
+            PotterRolloutLine::RoundStarted {
+                current: 1,
+                total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
+            },
+            PotterRolloutLine::RoundConfigured {
+                thread_id: thread_id(),
+                rollout_path: PathBuf::from("rollout.jsonl"),
+                rollout_path_raw: None,
+                rollout_base_dir: None,
+            },
+            answered(false),
+            answered(true),
+            PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
+            },
+        ];
+
+        let index = build_resume_index(&lines).expect("build resume index");
+        assert_eq!(
+            index.completed_rounds[0]
+                .user_inputs
+                .iter()
+                .map(|user_input| (user_input.answers.len(), user_input.declined))
+                .collect::<Vec<_>>(),
+            vec![(1, false), (0, true)]
+        );
+
+        lines.push(answered(false));
+        let err = build_resume_index(&lines).expect_err("user_input_answered outside a round");
+        assert!(
+            err.to_string().contains("user_input_answered"),
+            "unexpected error: {err:#}"
+        );
+    }
+
     #[test]
     fn build_resume_index_attaches_project_succeeded_to_completed_round() {
         let user_prompt_file = PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md");
@@ -257,6 +453,8 @@ mod tests {
             PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             PotterRolloutLine::RoundConfigured {
                 thread_id: thread_id(),
@@ -273,6 +471,7 @@ mod tests {
             },
             PotterRolloutLine::RoundFinished {
                 outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
             },
         ];
 
@@ -287,8 +486,11 @@ mod tests {
                 completed_rounds: vec![CompletedRoundIndex {
                     round_current: 1,
                     round_total: 10,
+                    kind: PotterRoundKind::Implement,
                     thread_id: thread_id(),
                     rollout_path: PathBuf::from("rollout.jsonl"),
+                    user_inputs: Vec::new(),
+                    verification: None,
                     project_succeeded: Some(ProjectSucceededIndex {
                         rounds: 3,
                         duration_secs: 42,
@@ -314,6 +516,8 @@ mod tests {
             PotterRolloutLine::RoundStarted {
                 current: 2,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             PotterRolloutLine::RoundConfigured {
                 thread_id: thread_id(),
@@ -335,6 +539,7 @@ mod tests {
                 unfinished_round: Some(UnfinishedRoundIndex {
                     round_current: 2,
                     round_total: 10,
+                    kind: PotterRoundKind::Implement,
                     thread_id: thread_id(),
                     rollout_path: PathBuf::from("rollout.jsonl"),
                 }),
@@ -352,6 +557,8 @@ mod tests {
             PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             PotterRolloutLine::RoundConfigured {
                 thread_id: thread_id(),
@@ -370,6 +577,7 @@ mod tests {
                 outcome: PotterRoundOutcome::TaskFailed {
                     message: "nope".to_string(),
                 },
+                checkpoint: None,
             },
         ];
 
@@ -384,6 +592,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_resume_index_records_round_verification_and_rejects_success_after_failure() {
+        let mut lines = vec![
+            PotterRolloutLine::ProjectStarted {
+                user_message: None,
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+            },
+            PotterRolloutLine::RoundStarted {
+                current: 1,
+                total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
+            },
+            PotterRolloutLine::RoundConfigured {
+                thread_id: thread_id(),
+                rollout_path: PathBuf::from("rollout.jsonl"),
+                rollout_path_raw: None,
+                rollout_base_dir: None,
+            },
+            PotterRolloutLine::RoundVerification {
+                command: "cargo test".to_string(),
+                passed: false,
+                exit_code: Some(101),
+                output: "FAILED".to_string(),
+            },
+            PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
+            },
+        ];
+
+        let index = build_resume_index(&lines).expect("build resume index");
+        assert_eq!(
+            index.completed_rounds[0].verification,
+            Some(RoundVerificationIndex {
+                command: "cargo test".to_string(),
+                passed: false,
+                exit_code: Some(101),
+                output: "FAILED".to_string(),
+            })
+        );
+
+        lines.insert(
+            4,
+            PotterRolloutLine::ProjectSucceeded {
+                rounds: 1,
+                duration_secs: 1,
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+                git_commit_start: "start".to_string(),
+                git_commit_end: "end".to_string(),
+            },
+        );
+        let err = build_resume_index(&lines).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("project_succeeded after failed round_verification"),
+            "unexpected error: {err:#}"
+        );
+    }
+
     #[test]
     fn build_resume_index_errors_when_round_finished_missing_round_configured() {
         let lines = vec![
@@ -394,9 +662,12 @@ mod tests {
             PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
+                checkpoint: None,
             },
             PotterRolloutLine::RoundFinished {
                 outcome: PotterRoundOutcome::Completed,
+                checkpoint: None,
             },
         ];
 
@@ -2,20 +2,28 @@
 //!
 //! While a round is running, CodexPotter forwards backend `EventMsg` items to the UI. This bridge
 //! observes the same events to:
-//! - Record `RoundConfigured` / `RoundFinished` (and optional `ProjectSucceeded`) entries into
-//!   `potter-rollout.jsonl`.
+//! - Record `RoundConfigured` / `RoundFinished` (and optional `RoundVerification` /
+//!   `ProjectSucceeded` / `UserInputAnswered`) entries into `potter-rollout.jsonl`, including the
+//!   git checkpoint taken when the round finishes.
+//! - Run the verification gate (when configured) once the round completes, and inject a
+//!   `PotterRoundVerification` event into the UI stream.
+//! - Append the findings of a completed review round (its final agent message) to the progress
+//!   file. Review rounds skip the verification gate since they do not change the code.
 //! - Inject a `PotterProjectSucceeded` event into the UI stream when `finite_incantatem: true` is
-//!   set in the progress file and the current round finishes successfully.
+//!   set in the progress file, the verification gate (if any) passed, and the current round
+//!   finishes successfully.
 //!
 //! The bridge is designed to be strict: persistence failures are treated as fatal so resume never
 //! reads a partially diverged log.
 
 use std::path::PathBuf;
+use std::time::Duration;
 use std::time::Instant;
 
 use anyhow::Context;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::PotterRoundKind;
 use codex_protocol::protocol::PotterRoundOutcome;
 use codex_protocol::protocol::SessionConfiguredEvent;
 
@@ -24,24 +32,37 @@ pub struct PotterRoundEventBridgeConfig {
     pub record_round_configured: bool,
 
     pub workdir: PathBuf,
+    /// Directory the agent changes (the project worktree in `--worktree` mode).
+    pub thread_workdir: PathBuf,
     pub progress_file_rel: PathBuf,
     pub user_prompt_file: PathBuf,
     pub git_commit_start: String,
     pub potter_rollout_path: PathBuf,
     pub project_started_at: Instant,
+    pub round_current: u32,
+    pub round_kind: PotterRoundKind,
     pub project_succeeded_rounds: u32,
+    pub verification_command: Option<String>,
+    pub verification_timeout: Duration,
 }
 
 #[derive(Debug, Clone)]
 pub struct PotterRoundEventBridge {
     workdir: PathBuf,
+    thread_workdir: PathBuf,
     progress_file_rel: PathBuf,
     user_prompt_file: PathBuf,
     git_commit_start: String,
     potter_rollout_path: PathBuf,
     project_started_at: Instant,
+    round_current: u32,
+    round_kind: PotterRoundKind,
     project_succeeded_rounds: u32,
+    verification_command: Option<String>,
+    verification_timeout: Duration,
     has_recorded_round_configured: bool,
+    /// Final agent message of the latest completed turn (review findings in review rounds).
+    last_agent_message: Option<String>,
 }
 
 impl PotterRoundEventBridge {
@@ -49,16 +70,23 @@ impl PotterRoundEventBridge {
         Self {
             has_recorded_round_configured: !config.record_round_configured,
             workdir: config.workdir,
+            thread_workdir: config.thread_workdir,
             progress_file_rel: config.progress_file_rel,
             user_prompt_file: config.user_prompt_file,
             git_commit_start: config.git_commit_start,
             potter_rollout_path: config.potter_rollout_path,
             project_started_at: config.project_started_at,
+            round_current: config.round_current,
+            round_kind: config.round_kind,
             project_succeeded_rounds: config.project_succeeded_rounds,
+            verification_command: config.verification_command,
+            verification_timeout: config.verification_timeout,
+            last_agent_message: None,
         }
     }
 
-    pub fn observe_backend_event(&mut self, event: &Event) -> anyhow::Result<Option<Event>> {
+    /// Observe one backend event and return the synthetic events to forward before it.
+    pub async fn observe_backend_event(&mut self, event: &Event) -> anyhow::Result<Vec<Event>> {
         if !self.has_recorded_round_configured
             && let EventMsg::SessionConfigured(cfg) = &event.msg
         {
@@ -67,19 +95,65 @@ impl PotterRoundEventBridge {
                 .context("record potter-rollout round_configured")?;
         }
 
-        let mut injected: Option<Event> = None;
-        if matches!(
+        if let EventMsg::TurnComplete(ev) = &event.msg
+            && let Some(message) = &ev.last_agent_message
+            && !message.trim().is_empty()
+        {
+            self.last_agent_message = Some(message.clone());
+        }
+
+        if let EventMsg::PotterUserInputAnswered {
+            server_name,
+            answers,
+            declined,
+        } = &event.msg
+        {
+            crate::workflow::rollout::append_line(
+                &self.potter_rollout_path,
+                &crate::workflow::rollout::PotterRolloutLine::UserInputAnswered {
+                    server_name: server_name.clone(),
+                    answers: answers.clone(),
+                    declined: *declined,
+                },
+            )
+            .context("append potter-rollout user_input_answered")?;
+        }
+
+        let mut injected = Vec::new();
+        let round_completed = matches!(
             &event.msg,
             EventMsg::PotterRoundFinished {
                 outcome: PotterRoundOutcome::Completed
             }
-        ) && crate::workflow::project::progress_file_has_finite_incantatem_true(
-            &self.workdir,
-            &self.progress_file_rel,
-        )
-        .context("check progress file finite_incantatem")?
+        );
+
+        match self.round_kind {
+            PotterRoundKind::Implement => {
+                if round_completed && let Some(command) = self.verification_command.clone() {
+                    injected.push(self.run_verification(&command).await?);
+                }
+            }
+            PotterRoundKind::Review => {
+                if round_completed && let Some(findings) = &self.last_agent_message {
+                    crate::workflow::round_schedule::append_review_findings(
+                        &self.workdir,
+                        &self.progress_file_rel,
+                        self.round_current,
+                        findings,
+                    )
+                    .context("append review findings to progress file")?;
+                }
+            }
+        }
+
+        if round_completed
+            && crate::workflow::project::progress_file_has_finite_incantatem_true(
+                &self.workdir,
+                &self.progress_file_rel,
+            )
+            .context("check progress file finite_incantatem")?
         {
-            let git_commit_end = crate::workflow::project::resolve_git_commit(&self.workdir);
+            let git_commit_end = crate::workflow::project::resolve_git_commit(&self.thread_workdir);
             crate::workflow::rollout::append_line(
                 &self.potter_rollout_path,
                 &crate::workflow::rollout::PotterRolloutLine::ProjectSucceeded {
@@ -92,7 +166,7 @@ impl PotterRoundEventBridge {
             )
             .context("append potter-rollout project_succeeded")?;
 
-            injected = Some(Event {
+            injected.push(Event {
                 id: "".to_string(),
                 msg: EventMsg::PotterProjectSucceeded {
                     rounds: self.project_succeeded_rounds,
@@ -105,10 +179,21 @@ impl PotterRoundEventBridge {
         }
 
         if let EventMsg::PotterRoundFinished { outcome } = &event.msg {
+            let checkpoint = crate::workflow::checkpoint::record_checkpoint(
+                &self.thread_workdir,
+                &crate::workflow::checkpoint::checkpoint_ref_name(
+                    &self.progress_file_rel,
+                    // The round being finished is the last one started.
+                    crate::workflow::checkpoint::project_round_index(&self.potter_rollout_path)
+                        .max(1),
+                    crate::workflow::checkpoint::CheckpointPhase::Finished,
+                ),
+            );
             crate::workflow::rollout::append_line(
                 &self.potter_rollout_path,
                 &crate::workflow::rollout::PotterRolloutLine::RoundFinished {
                     outcome: outcome.clone(),
+                    checkpoint,
                 },
             )
             .context("append potter-rollout round_finished")?;
@@ -117,6 +202,62 @@ impl PotterRoundEventBridge {
         Ok(injected)
     }
 
+    /// Run the verification gate and persist its result.
+    ///
+    /// On failure, `finite_incantatem` is reset so the project cannot succeed this round, and the
+    /// command output replaces the previous failure report in the progress file for the next
+    /// round. A passing gate removes that report.
+    async fn run_verification(&self, command: &str) -> anyhow::Result<Event> {
+        let result = crate::workflow::verification::run_verification_command(
+            &self.thread_workdir,
+            command,
+            self.verification_timeout,
+        )
+        .await;
+
+        if result.passed {
+            crate::workflow::verification::clear_verification_failure(
+                &self.workdir,
+                &self.progress_file_rel,
+            )
+            .context("remove verification failure from progress file")?;
+        } else {
+            crate::workflow::verification::record_verification_failure(
+                &self.workdir,
+                &self.progress_file_rel,
+                &result,
+            )
+            .context("record verification failure in progress file")?;
+            crate::workflow::project::set_progress_file_finite_incantatem(
+                &self.workdir,
+                &self.progress_file_rel,
+                false,
+            )
+            .context("reset progress file finite_incantatem")?;
+        }
+
+        crate::workflow::rollout::append_line(
+            &self.potter_rollout_path,
+            &crate::workflow::rollout::PotterRolloutLine::RoundVerification {
+                command: result.command.clone(),
+                passed: result.passed,
+                exit_code: result.exit_code,
+                output: result.output.clone(),
+            },
+        )
+        .context("append potter-rollout round_verification")?;
+
+        Ok(Event {
+            id: "".to_string(),
+            msg: EventMsg::PotterRoundVerification {
+                command: result.command,
+                passed: result.passed,
+                exit_code: result.exit_code,
+                output: result.output,
+            },
+        })
+    }
+
     fn record_round_configured(&self, cfg: &SessionConfiguredEvent) -> anyhow::Result<()> {
         let (rollout_path, rollout_path_raw, rollout_base_dir) =
             crate::workflow::rollout::resolve_rollout_path_for_recording(
@@ -181,8 +322,8 @@ finite_incantatem: {finite}
         }
     }
 
-    #[test]
-    fn observe_backend_event_records_round_configured_once() {
+    #[tokio::test]
+    async fn observe_backend_event_records_round_configured_once() {
         let dir = tempfile::tempdir().expect("tempdir");
         let workdir = dir.path();
         let potter_rollout_path = workdir.join("potter-rollout.jsonl");
@@ -193,17 +334,22 @@ finite_incantatem: {finite}
         let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
             record_round_configured: true,
             workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
             progress_file_rel: PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md"),
             user_prompt_file: PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md"),
             git_commit_start: "start".to_string(),
             potter_rollout_path: potter_rollout_path.clone(),
             project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
             project_succeeded_rounds: 1,
+            verification_command: None,
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
         });
 
         let ev = session_configured_event(workdir, PathBuf::from("upstream.jsonl"));
-        bridge.observe_backend_event(&ev).expect("observe #1");
-        bridge.observe_backend_event(&ev).expect("observe #2");
+        bridge.observe_backend_event(&ev).await.expect("observe #1");
+        bridge.observe_backend_event(&ev).await.expect("observe #2");
 
         let lines = crate::workflow::rollout::read_lines(&potter_rollout_path).expect("read");
         assert_eq!(lines.len(), 1);
@@ -213,8 +359,8 @@ finite_incantatem: {finite}
         ));
     }
 
-    #[test]
-    fn observe_backend_event_injects_project_succeeded_before_round_finished_when_finite() {
+    #[tokio::test]
+    async fn observe_backend_event_injects_project_succeeded_before_round_finished_when_finite() {
         let dir = tempfile::tempdir().expect("tempdir");
         let workdir = dir.path();
         let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md");
@@ -224,12 +370,17 @@ finite_incantatem: {finite}
         let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
             record_round_configured: false,
             workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
             progress_file_rel: progress_file_rel.clone(),
             user_prompt_file: progress_file_rel.clone(),
             git_commit_start: "start".to_string(),
             potter_rollout_path: potter_rollout_path.clone(),
             project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
             project_succeeded_rounds: 3,
+            verification_command: None,
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
         });
 
         let finished = Event {
@@ -241,10 +392,12 @@ finite_incantatem: {finite}
 
         let injected = bridge
             .observe_backend_event(&finished)
+            .await
             .expect("observe finished");
+        assert_eq!(injected.len(), 1);
         assert!(matches!(
-            injected.as_ref().map(|event| &event.msg),
-            Some(EventMsg::PotterProjectSucceeded { rounds: 3, .. })
+            &injected[0].msg,
+            EventMsg::PotterProjectSucceeded { rounds: 3, .. }
         ));
 
         let lines = crate::workflow::rollout::read_lines(&potter_rollout_path).expect("read");
@@ -256,13 +409,14 @@ finite_incantatem: {finite}
         assert!(matches!(
             &lines[1],
             crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::Completed
+                outcome: PotterRoundOutcome::Completed,
+                ..
             }
         ));
     }
 
-    #[test]
-    fn observe_backend_event_does_not_inject_project_succeeded_when_not_finite() {
+    #[tokio::test]
+    async fn observe_backend_event_does_not_inject_project_succeeded_when_not_finite() {
         let dir = tempfile::tempdir().expect("tempdir");
         let workdir = dir.path();
         let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md");
@@ -272,12 +426,17 @@ finite_incantatem: {finite}
         let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
             record_round_configured: false,
             workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
             progress_file_rel: progress_file_rel.clone(),
             user_prompt_file: progress_file_rel.clone(),
             git_commit_start: "start".to_string(),
             potter_rollout_path: potter_rollout_path.clone(),
             project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
             project_succeeded_rounds: 3,
+            verification_command: None,
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
         });
 
         let finished = Event {
@@ -289,21 +448,23 @@ finite_incantatem: {finite}
 
         let injected = bridge
             .observe_backend_event(&finished)
+            .await
             .expect("observe finished");
-        assert!(injected.is_none());
+        assert!(injected.is_empty());
 
         let lines = crate::workflow::rollout::read_lines(&potter_rollout_path).expect("read");
         assert_eq!(lines.len(), 1);
         assert!(matches!(
             &lines[0],
             crate::workflow::rollout::PotterRolloutLine::RoundFinished {
-                outcome: PotterRoundOutcome::Completed
+                outcome: PotterRoundOutcome::Completed,
+                ..
             }
         ));
     }
 
-    #[test]
-    fn observe_backend_event_errors_when_progress_file_missing() {
+    #[tokio::test]
+    async fn observe_backend_event_errors_when_progress_file_missing() {
         let dir = tempfile::tempdir().expect("tempdir");
         let workdir = dir.path();
 
@@ -313,12 +474,17 @@ finite_incantatem: {finite}
         let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
             record_round_configured: false,
             workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
             progress_file_rel: progress_file_rel.clone(),
             user_prompt_file: progress_file_rel,
             git_commit_start: "start".to_string(),
             potter_rollout_path: potter_rollout_path.clone(),
             project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
             project_succeeded_rounds: 1,
+            verification_command: None,
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
         });
 
         let finished = Event {
@@ -330,6 +496,7 @@ finite_incantatem: {finite}
 
         let err = bridge
             .observe_backend_event(&finished)
+            .await
             .expect_err("expected error");
         assert!(
             err.to_string().contains("finite_incantatem"),
@@ -340,4 +507,186 @@ finite_incantatem: {finite}
             "should not write rollout on error"
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn observe_backend_event_blocks_project_succeeded_when_verification_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        write_progress_file(workdir, &progress_file_rel, true);
+
+        let potter_rollout_path = workdir.join("potter-rollout.jsonl");
+        let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
+            record_round_configured: false,
+            workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
+            progress_file_rel: progress_file_rel.clone(),
+            user_prompt_file: progress_file_rel.clone(),
+            git_commit_start: "start".to_string(),
+            potter_rollout_path: potter_rollout_path.clone(),
+            project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
+            project_succeeded_rounds: 2,
+            verification_command: Some("echo broken; exit 1".to_string()),
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
+        });
+
+        let finished = Event {
+            id: "event_2".to_string(),
+            msg: EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+            },
+        };
+
+        let injected = bridge
+            .observe_backend_event(&finished)
+            .await
+            .expect("observe finished");
+        assert_eq!(injected.len(), 1);
+        let EventMsg::PotterRoundVerification {
+            passed,
+            exit_code,
+            output,
+            ..
+        } = &injected[0].msg
+        else {
+            panic!(
+                "expected PotterRoundVerification, got: {:?}",
+                injected[0].msg
+            );
+        };
+        assert_eq!(
+            (*passed, *exit_code, output.as_str()),
+            (false, Some(1), "broken")
+        );
+
+        assert!(
+            !crate::workflow::project::progress_file_has_finite_incantatem_true(
+                workdir,
+                &progress_file_rel
+            )
+            .expect("read finite_incantatem")
+        );
+        let progress = std::fs::read_to_string(workdir.join(&progress_file_rel)).expect("read");
+        assert!(progress.contains("## Verification Failed"), "{progress}");
+        assert!(progress.contains("```text\nbroken\n```"), "{progress}");
+
+        let lines = crate::workflow::rollout::read_lines(&potter_rollout_path).expect("read");
+        assert_eq!(
+            lines,
+            vec![
+                crate::workflow::rollout::PotterRolloutLine::RoundVerification {
+                    command: "echo broken; exit 1".to_string(),
+                    passed: false,
+                    exit_code: Some(1),
+                    output: "broken".to_string(),
+                },
+                crate::workflow::rollout::PotterRolloutLine::RoundFinished {
+                    outcome: PotterRoundOutcome::Completed,
+                    checkpoint: None,
+                },
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn observe_backend_event_injects_verification_before_project_succeeded_when_passed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        write_progress_file(workdir, &progress_file_rel, true);
+
+        let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
+            record_round_configured: false,
+            workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
+            progress_file_rel: progress_file_rel.clone(),
+            user_prompt_file: progress_file_rel,
+            git_commit_start: "start".to_string(),
+            potter_rollout_path: workdir.join("potter-rollout.jsonl"),
+            project_started_at: Instant::now(),
+            round_current: 1,
+            round_kind: PotterRoundKind::Implement,
+            project_succeeded_rounds: 2,
+            verification_command: Some("true".to_string()),
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
+        });
+
+        let injected = bridge
+            .observe_backend_event(&Event {
+                id: "event_2".to_string(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::Completed,
+                },
+            })
+            .await
+            .expect("observe finished");
+        assert_eq!(injected.len(), 2);
+        assert!(matches!(
+            &injected[0].msg,
+            EventMsg::PotterRoundVerification { passed: true, .. }
+        ));
+        assert!(matches!(
+            &injected[1].msg,
+            EventMsg::PotterProjectSucceeded { rounds: 2, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn observe_backend_event_appends_review_findings_without_verification() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let workdir = dir.path();
+        let progress_file_rel = PathBuf::from(".codexpotter/projects/2026/03/04/1/MAIN.md");
+        write_progress_file(workdir, &progress_file_rel, false);
+
+        let mut bridge = PotterRoundEventBridge::new(PotterRoundEventBridgeConfig {
+            record_round_configured: false,
+            workdir: workdir.to_path_buf(),
+            thread_workdir: workdir.to_path_buf(),
+            progress_file_rel: progress_file_rel.clone(),
+            user_prompt_file: progress_file_rel.clone(),
+            git_commit_start: "start".to_string(),
+            potter_rollout_path: workdir.join("potter-rollout.jsonl"),
+            project_started_at: Instant::now(),
+            round_current: 3,
+            round_kind: PotterRoundKind::Review,
+            project_succeeded_rounds: 3,
+            verification_command: Some("exit 1".to_string()),
+            verification_timeout: crate::workflow::verification::DEFAULT_VERIFICATION_TIMEOUT,
+        });
+
+        bridge
+            .observe_backend_event(&Event {
+                id: "event_1".to_string(),
+                msg: EventMsg::TurnComplete(codex_protocol::protocol::TurnCompleteEvent {
+                    turn_id: "turn_1".to_string(),
+                    last_agent_message: Some("- add a test for empty input".to_string()),
+                }),
+            })
+            .await
+            .expect("observe turn complete");
+        let injected = bridge
+            .observe_backend_event(&Event {
+                id: "event_2".to_string(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::Completed,
+                },
+            })
+            .await
+            .expect("observe finished");
+        assert_eq!(injected.len(), 0);
+
+        let progress = std::fs::read_to_string(workdir.join(&progress_file_rel)).expect("read");
+        assert!(
+            progress.contains("## Review Findings (round 3)"),
+            "{progress}"
+        );
+        assert!(
+            progress.ends_with("- add a test for empty input\n"),
+            "{progress}"
+        );
+    }
 }
@@ -2,14 +2,26 @@
 //!
 //! A "round" is one upstream `codex app-server` session driven by the UI. This module wires:
 //! - A backend task that runs the upstream app-server and emits `EventMsg` notifications.
-//! - A forwarder task that persists boundary markers to `potter-rollout.jsonl` via
-//!   [`super::round_event_bridge::PotterRoundEventBridge`] and forwards events to the UI.
+//! - A forwarder task that persists boundary markers to `potter-rollout.jsonl` (and runs the
+//!   optional verification gate) via [`super::round_event_bridge::PotterRoundEventBridge`], and
+//!   forwards events to the UI.
 //! - A UI driver ([`PotterRoundUi`]) that renders the round and sends `Op` requests.
 //!
+//! Review rounds (see [`super::round_schedule`]) swap in the review prompts, apply the
+//! `[rounds.review]` model overrides and run the thread in a read-only sandbox.
+//!
+//! Every round tees the upstream app-server's stderr to `rounds/N/app-server.stderr.log` in the
+//! project directory, where `N` counts the project's rounds across resumes.
+//!
+//! The backend starts the app-server and the round's thread with the UI's first `Op`, so a UI
+//! holding the prompt back (a rate-limit pause) keeps no idle process around, and an interrupt
+//! before the prompt ends the round without starting one.
+//!
 //! On non-completed UI exits (user/fatal/task failure) we abort the backend to avoid orphaned
 //! processes.
 
 use std::future::Future;
+use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::time::Instant;
@@ -18,9 +30,24 @@ use anyhow::Context;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterRoundKind;
+use codex_protocol::protocol::PotterRoundOutcome;
 use codex_tui::ExitReason;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
 
+/// Name of the per-round file the upstream app-server's stderr is tee'd to.
+pub const APP_SERVER_STDERR_LOG_FILENAME: &str = "app-server.stderr.log";
+
+/// `rounds/<round>/app-server.stderr.log` within a project directory.
+pub fn app_server_stderr_log_path(project_dir: &Path, round: u32) -> PathBuf {
+    project_dir
+        .join("rounds")
+        .join(round.to_string())
+        .join(APP_SERVER_STDERR_LOG_FILENAME)
+}
+
 /// Boxed future returned by [`PotterRoundUi`] implementations.
 pub type UiFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + 'a>>;
 
@@ -54,18 +81,105 @@ impl PotterRoundUi for codex_tui::CodexPotterTui {
 pub struct PotterRoundContext {
     pub codex_bin: String,
     pub developer_prompt: String,
+    /// Developer prompt for review rounds.
+    pub review_developer_prompt: String,
+    /// Decides which rounds are review rounds.
+    pub round_schedule: super::round_schedule::RoundSchedule,
     pub backend_launch: crate::app_server::AppServerLaunchConfig,
     pub backend_event_mode: crate::app_server::AppServerEventMode,
+    /// Decides upstream approval requests of every round.
+    pub approval_policy: crate::app_server::approval_policy::ApprovalPolicy,
+    /// Where `ask` approval requests go; `None` denies them.
+    pub approval_asker: Option<crate::app_server::approval_policy::ApprovalAsker>,
+    /// Where user input requests go; `None` declines them.
+    pub user_input_asker: Option<crate::app_server::user_input::UserInputAsker>,
+    /// Retry schedule for recoverable turn errors.
+    pub stream_recovery: crate::app_server::stream_recovery::StreamRecoveryPolicy,
+    /// Upstream app-server processes shared by the project's rounds (`--reuse-app-server`);
+    /// `None` spawns a fresh process per round.
+    pub app_server_pool: Option<crate::app_server::AppServerPool>,
     pub upstream_cli_args: crate::app_server::UpstreamCodexCliArgs,
     pub codex_compat_home: Option<PathBuf>,
+    /// Working directory of the agent thread; differs from `workdir` in `--worktree` mode.
     pub thread_cwd: Option<PathBuf>,
     pub turn_prompt: String,
+    /// Turn prompt for review rounds.
+    pub review_turn_prompt: String,
     pub workdir: PathBuf,
     pub progress_file_rel: PathBuf,
     pub user_prompt_file: PathBuf,
     pub git_commit_start: String,
     pub potter_rollout_path: PathBuf,
     pub project_started_at: Instant,
+    /// Shell command run after each completed round; success requires it to pass.
+    pub verification_command: Option<String>,
+    /// How long the verification command may run before it is killed and the gate fails.
+    pub verification_timeout: std::time::Duration,
+}
+
+impl PotterRoundContext {
+    /// Directory the agent changes: git checkpoints and verification run here.
+    fn thread_workdir(&self) -> &Path {
+        self.thread_cwd.as_deref().unwrap_or(&self.workdir)
+    }
+
+    /// Backend settings for the project's `round`th round, of `kind`.
+    fn backend_config(
+        &self,
+        round: u32,
+        kind: PotterRoundKind,
+        resume_thread_id: Option<codex_protocol::ThreadId>,
+    ) -> crate::app_server::AppServerBackendConfig {
+        let mut config = crate::app_server::AppServerBackendConfig {
+            codex_bin: self.codex_bin.clone(),
+            developer_instructions: Some(self.developer_prompt.clone()),
+            launch: self.backend_launch,
+            upstream_cli_args: self.upstream_cli_args.clone(),
+            reasoning_effort: None,
+            codex_home: self.codex_compat_home.clone(),
+            thread_cwd: self.thread_cwd.clone(),
+            resume_thread_id,
+            event_mode: self.backend_event_mode,
+            approval_policy: self.approval_policy.clone(),
+            approval_asker: self.approval_asker.clone(),
+            user_input_asker: self.user_input_asker.clone(),
+            stream_recovery: self.stream_recovery,
+            process_pool: self.app_server_pool.clone(),
+            stderr_log: self
+                .potter_rollout_path
+                .parent()
+                .map(|project_dir| app_server_stderr_log_path(project_dir, round)),
+        };
+        if kind == PotterRoundKind::Review {
+            let review = &self.round_schedule.review;
+            config.developer_instructions = Some(self.review_developer_prompt.clone());
+            config.launch.thread_sandbox =
+                Some(crate::app_server::upstream_protocol::SandboxMode::ReadOnly);
+            if let Some(model) = &review.model {
+                config.upstream_cli_args.model = Some(model.clone());
+            }
+            config.reasoning_effort = review.reasoning_effort;
+        }
+        config
+    }
+
+    /// Backend settings for the final report turn (see [`super::report`]): a fresh thread in a
+    /// read-only sandbox, logging stderr to `report/app-server.stderr.log`.
+    pub fn report_backend_config(
+        &self,
+        developer_prompt: String,
+    ) -> crate::app_server::AppServerBackendConfig {
+        let mut config = self.backend_config(0, PotterRoundKind::Implement, None);
+        config.developer_instructions = Some(developer_prompt);
+        config.launch.thread_sandbox =
+            Some(crate::app_server::upstream_protocol::SandboxMode::ReadOnly);
+        config.stderr_log = self.potter_rollout_path.parent().map(|project_dir| {
+            project_dir
+                .join("report")
+                .join(APP_SERVER_STDERR_LOG_FILENAME)
+        });
+        config
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -91,6 +205,8 @@ pub struct PotterContinueRoundOptions {
     pub pad_before_first_cell: bool,
     pub round_current: u32,
     pub round_total: u32,
+    /// Kind recorded when the unfinished round started.
+    pub round_kind: PotterRoundKind,
     pub project_succeeded_rounds: u32,
     /// Existing Codex thread to resume for this unfinished round.
     pub resume_thread_id: codex_protocol::ThreadId,
@@ -102,6 +218,7 @@ pub struct PotterContinueRoundOptions {
 pub struct PotterRoundResult {
     pub exit_reason: ExitReason,
     pub stop_due_to_finite_incantatem: bool,
+    pub round_kind: PotterRoundKind,
 }
 
 pub async fn run_potter_round(
@@ -117,6 +234,16 @@ pub async fn run_potter_round(
         project_succeeded_rounds,
     } = options;
 
+    // `project_succeeded_rounds` counts the project's rounds across resumes, which keeps the
+    // schedule cycling where the previous run stopped.
+    let round_kind = context
+        .round_schedule
+        .kind_for_round(project_succeeded_rounds);
+    let prompt = match round_kind {
+        PotterRoundKind::Implement => context.turn_prompt.clone(),
+        PotterRoundKind::Review => context.review_turn_prompt.clone(),
+    };
+
     run_potter_round_inner(
         ui,
         context,
@@ -125,8 +252,9 @@ pub async fn run_potter_round(
             project_started,
             round_current,
             round_total,
+            round_kind,
             project_succeeded_rounds,
-            prompt: context.turn_prompt.clone(),
+            prompt,
             resume_thread_id: None,
             emit_round_started_event: true,
             record_round_started: true,
@@ -150,6 +278,7 @@ pub async fn continue_potter_round(
         pad_before_first_cell,
         round_current,
         round_total,
+        round_kind,
         project_succeeded_rounds,
         resume_thread_id,
         replay_event_msgs,
@@ -163,6 +292,7 @@ pub async fn continue_potter_round(
             project_started: None,
             round_current,
             round_total,
+            round_kind,
             project_succeeded_rounds,
             prompt: String::from("Continue"),
             resume_thread_id: Some(resume_thread_id),
@@ -180,6 +310,7 @@ struct PotterRoundInnerOptions {
     project_started: Option<PotterProjectStartedInfo>,
     round_current: u32,
     round_total: u32,
+    round_kind: PotterRoundKind,
     project_succeeded_rounds: u32,
     prompt: String,
     resume_thread_id: Option<codex_protocol::ThreadId>,
@@ -199,6 +330,7 @@ async fn run_potter_round_inner(
         project_started,
         round_current,
         round_total,
+        round_kind,
         project_succeeded_rounds,
         prompt,
         resume_thread_id,
@@ -239,14 +371,26 @@ async fn run_potter_round_inner(
             msg: EventMsg::PotterRoundStarted {
                 current: round_current,
                 total: round_total,
+                kind: round_kind,
             },
         });
         if record_round_started {
+            let checkpoint = crate::workflow::checkpoint::record_checkpoint(
+                context.thread_workdir(),
+                &crate::workflow::checkpoint::checkpoint_ref_name(
+                    &context.progress_file_rel,
+                    crate::workflow::checkpoint::project_round_index(&context.potter_rollout_path)
+                        .saturating_add(1),
+                    crate::workflow::checkpoint::CheckpointPhase::Started,
+                ),
+            );
             crate::workflow::rollout::append_line(
                 &context.potter_rollout_path,
                 &crate::workflow::rollout::PotterRolloutLine::RoundStarted {
                     current: round_current,
                     total: round_total,
+                    kind: round_kind,
+                    checkpoint,
                 },
             )
             .context("append potter-rollout round_started")?;
@@ -270,18 +414,23 @@ async fn run_potter_round_inner(
             super::round_event_bridge::PotterRoundEventBridgeConfig {
                 record_round_configured,
                 workdir: context.workdir.clone(),
+                thread_workdir: context.thread_workdir().to_path_buf(),
                 progress_file_rel: context.progress_file_rel.clone(),
                 user_prompt_file: context.user_prompt_file.clone(),
                 git_commit_start: context.git_commit_start.clone(),
                 potter_rollout_path: potter_rollout_path.clone(),
                 project_started_at: context.project_started_at,
+                round_current,
+                round_kind,
                 project_succeeded_rounds,
+                verification_command: context.verification_command.clone(),
+                verification_timeout: context.verification_timeout,
             },
         );
 
         tokio::spawn(async move {
             while let Some(event) = backend_event_rx.recv().await {
-                let injected = match bridge.observe_backend_event(&event) {
+                let injected = match bridge.observe_backend_event(&event).await {
                     Ok(injected) => injected,
                     Err(err) => {
                         let _ = fatal_exit_tx.send(format!(
@@ -292,8 +441,9 @@ async fn run_potter_round_inner(
                     }
                 };
 
-                if let Some(injected) = injected
-                    && ui_event_tx.send(injected).is_err()
+                if injected
+                    .into_iter()
+                    .any(|injected| ui_event_tx.send(injected).is_err())
                 {
                     break;
                 }
@@ -305,27 +455,18 @@ async fn run_potter_round_inner(
         })
     };
 
-    let backend = tokio::spawn(crate::app_server::run_app_server_backend(
-        crate::app_server::AppServerBackendConfig {
-            codex_bin: context.codex_bin.clone(),
-            developer_instructions: Some(context.developer_prompt.clone()),
-            launch: context.backend_launch,
-            upstream_cli_args: context.upstream_cli_args.clone(),
-            codex_home: context.codex_compat_home.clone(),
-            thread_cwd: context.thread_cwd.clone(),
-            resume_thread_id,
-            event_mode: context.backend_event_mode,
-        },
+    let backend = tokio::spawn(run_backend_on_first_op(
+        context.backend_config(project_succeeded_rounds, round_kind, resume_thread_id),
         op_rx,
         backend_event_tx,
         fatal_exit_tx,
     ));
 
     ui.set_project_started_at(context.project_started_at);
-    let status_header_prefix = Some(format!("Round {round_current}/{round_total}"));
+    let status_header_prefix = Some(round_kind.round_prefix(round_current, round_total));
     let prompt_footer = codex_tui::PromptFooterContext::new(
-        context.workdir.clone(),
-        crate::workflow::project::resolve_git_branch(&context.workdir),
+        context.thread_workdir().to_path_buf(),
+        crate::workflow::project::resolve_git_branch(context.thread_workdir()),
     );
     let exit_info = ui
         .render_round(codex_tui::RenderRoundParams {
@@ -353,6 +494,7 @@ async fn run_potter_round_inner(
             return Ok(PotterRoundResult {
                 exit_reason,
                 stop_due_to_finite_incantatem: false,
+                round_kind,
             });
         }
     }
@@ -372,5 +514,52 @@ async fn run_potter_round_inner(
     Ok(PotterRoundResult {
         exit_reason,
         stop_due_to_finite_incantatem,
+        round_kind,
     })
 }
+
+/// Run the round's backend once the UI sends its first op.
+///
+/// An `Op::Interrupt` arriving before any prompt finishes the round as interrupted without
+/// starting the app-server.
+async fn run_backend_on_first_op(
+    config: crate::app_server::AppServerBackendConfig,
+    mut op_rx: UnboundedReceiver<Op>,
+    event_tx: UnboundedSender<Event>,
+    fatal_exit_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+    let first_op = match op_rx.recv().await {
+        None => return Ok(()),
+        Some(Op::Interrupt) => {
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::Interrupted,
+                },
+            });
+            return Ok(());
+        }
+        Some(op) => op,
+    };
+
+    let (backend_op_tx, backend_op_rx) = unbounded_channel::<Op>();
+    let _ = backend_op_tx.send(first_op);
+    let backend =
+        crate::app_server::run_app_server_backend(config, backend_op_rx, event_tx, fatal_exit_tx);
+    tokio::pin!(backend);
+    // Dropped once the UI closes its end, so the backend sees the op channel close too.
+    let mut backend_op_tx = Some(backend_op_tx);
+    loop {
+        tokio::select! {
+            result = &mut backend => return result,
+            op = op_rx.recv(), if backend_op_tx.is_some() => match op {
+                Some(op) => {
+                    if let Some(backend_op_tx) = &backend_op_tx {
+                        let _ = backend_op_tx.send(op);
+                    }
+                }
+                None => backend_op_tx = None,
+            },
+        }
+    }
+}
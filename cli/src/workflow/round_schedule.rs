@@ -0,0 +1,186 @@
+//! Round schedule: alternating implement and review rounds.
+//!
+//! By default every round is an implement round driven by the regular developer prompt. A
+//! schedule declared in `~/.codexpotter/config.toml` (`[rounds] schedule`, see
+//! [`crate::config`]) repeats over the project's rounds: `["implement", "implement", "review"]`
+//! makes every third round a review round. Positions are counted across the whole project, so
+//! `resume` continues the cycle where the previous run stopped.
+//!
+//! Review rounds:
+//! - use the review developer and turn prompts (`cli/prompts/review_*.md`),
+//! - may run on a different model / reasoning effort (`[rounds.review]`),
+//! - run in a read-only sandbox. The reviewer cannot edit the progress file, so its final message
+//!   is appended to it ([`append_review_findings`]) for the next implement round.
+//!
+//! The kind of every round is recorded in `PotterRolloutLine::RoundStarted`, so resume replays
+//! the correct labels.
+
+use std::path::Path;
+
+use anyhow::Context;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::PotterRoundKind;
+
+/// Reply a review round gives when it has nothing to report.
+const NO_FINDINGS_REPLY: &str = "No findings.";
+
+/// Which kind each round of a project runs as.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoundSchedule {
+    kinds: Vec<PotterRoundKind>,
+    /// Backend overrides applied to review rounds.
+    pub review: ReviewRoundSettings,
+}
+
+/// Backend overrides for review rounds (`[rounds.review]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewRoundSettings {
+    /// Model used instead of `--model`.
+    pub model: Option<String>,
+    /// Reasoning effort used instead of the Codex config default.
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl RoundSchedule {
+    /// Build a schedule from its configured entries.
+    ///
+    /// An empty schedule runs implement rounds only; a non-empty one must contain at least one
+    /// implement round, otherwise nothing would ever act on the review findings.
+    pub fn new(kinds: Vec<PotterRoundKind>, review: ReviewRoundSettings) -> anyhow::Result<Self> {
+        if !kinds.is_empty() && !kinds.iter().any(PotterRoundKind::is_implement) {
+            anyhow::bail!("round schedule must contain at least one `implement` round");
+        }
+        Ok(Self { kinds, review })
+    }
+
+    /// Kind of the `project_round`-th round of the project (1-based, counted across resumes).
+    pub fn kind_for_round(&self, project_round: u32) -> PotterRoundKind {
+        if self.kinds.is_empty() {
+            return PotterRoundKind::Implement;
+        }
+        let position = usize::try_from(project_round.saturating_sub(1)).unwrap_or(usize::MAX);
+        self.kinds[position % self.kinds.len()]
+    }
+}
+
+/// Parse one schedule entry (`implement` or `review`).
+pub fn parse_round_kind(value: &str) -> anyhow::Result<PotterRoundKind> {
+    match value {
+        "implement" => Ok(PotterRoundKind::Implement),
+        "review" => Ok(PotterRoundKind::Review),
+        other => anyhow::bail!("unknown round kind `{other}` (expected `implement` or `review`)"),
+    }
+}
+
+/// Parse a reasoning effort as accepted by Codex (`low`, `medium`, `high`, ...).
+pub fn parse_reasoning_effort(value: &str) -> anyhow::Result<ReasoningEffort> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .with_context(|| format!("unknown reasoning effort `{value}`"))
+}
+
+/// Append the findings of review round `round_current` to the end of the progress file.
+///
+/// Returns `false` without touching the file when the reviewer reported nothing.
+pub fn append_review_findings(
+    workdir: &Path,
+    progress_file_rel: &Path,
+    round_current: u32,
+    findings: &str,
+) -> anyhow::Result<bool> {
+    let findings = findings.trim();
+    if findings.is_empty() || findings == NO_FINDINGS_REPLY {
+        return Ok(false);
+    }
+
+    let progress_file = workdir.join(progress_file_rel);
+    let mut contents = std::fs::read_to_string(&progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&format!(
+        "\n## Review Findings (round {round_current})\n\n\
+         A read-only review round reported the findings below. Turn them into `Todo` tasks (or \
+         drop the ones that do not hold up) before marking the project as finished, then remove \
+         this section.\n\n\
+         {findings}\n",
+    ));
+
+    std::fs::write(&progress_file, contents)
+        .with_context(|| format!("write {}", progress_file.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kind_for_round_repeats_schedule_across_rounds() {
+        let schedule = RoundSchedule::new(
+            vec![
+                PotterRoundKind::Implement,
+                PotterRoundKind::Implement,
+                PotterRoundKind::Review,
+            ],
+            ReviewRoundSettings::default(),
+        )
+        .expect("schedule");
+
+        assert_eq!(
+            (1..=7)
+                .map(|round| schedule.kind_for_round(round))
+                .collect::<Vec<_>>(),
+            vec![
+                PotterRoundKind::Implement,
+                PotterRoundKind::Implement,
+                PotterRoundKind::Review,
+                PotterRoundKind::Implement,
+                PotterRoundKind::Implement,
+                PotterRoundKind::Review,
+                PotterRoundKind::Implement,
+            ]
+        );
+        assert_eq!(
+            RoundSchedule::default().kind_for_round(3),
+            PotterRoundKind::Implement
+        );
+    }
+
+    #[test]
+    fn new_rejects_review_only_schedule() {
+        let err = RoundSchedule::new(
+            vec![PotterRoundKind::Review],
+            ReviewRoundSettings::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "round schedule must contain at least one `implement` round"
+        );
+    }
+
+    #[test]
+    fn append_review_findings_skips_empty_review() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let rel = Path::new("MAIN.md");
+        std::fs::write(temp.path().join(rel), "# Overall Goal\n").expect("write progress file");
+
+        assert!(!append_review_findings(temp.path(), rel, 3, "No findings.\n").expect("append"));
+        assert!(
+            append_review_findings(temp.path(), rel, 3, "- handle empty input\n").expect("append")
+        );
+
+        let contents = std::fs::read_to_string(temp.path().join(rel)).expect("read");
+        assert_eq!(
+            contents,
+            "# Overall Goal\n\n## Review Findings (round 3)\n\n\
+             A read-only review round reported the findings below. Turn them into `Todo` tasks \
+             (or drop the ones that do not hold up) before marking the project as finished, then \
+             remove this section.\n\n- handle empty input\n"
+        );
+    }
+}
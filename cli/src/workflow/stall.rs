@@ -0,0 +1,122 @@
+//! No-progress detection between rounds (`--stall-rounds N`).
+//!
+//! After every completed round the control plane fingerprints the agent's git checkout (`HEAD`
+//! plus the working tree, excluding `.codexpotter/`; see
+//! [`crate::workflow::checkpoint::working_tree_fingerprint`]). A round whose fingerprint matches
+//! the previous one made no progress: it produced an empty diff or only touched the progress
+//! file. After N such rounds in a row the project ends with `PotterProjectOutcome::Stalled`.
+//!
+//! Detection is best-effort: when no fingerprint can be taken (outside git repositories or when
+//! git fails) the round is treated as having made progress.
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+/// Counts consecutive no-op rounds for one project run.
+#[derive(Debug)]
+pub struct StallDetector {
+    workdir: PathBuf,
+    limit: NonZeroUsize,
+    last_fingerprint: Option<String>,
+    noop_rounds: usize,
+}
+
+impl StallDetector {
+    /// Start tracking `workdir`, taking the baseline fingerprint now. Returns `None` when stall
+    /// detection is disabled.
+    pub fn start(workdir: &Path, limit: Option<NonZeroUsize>) -> Option<Self> {
+        let limit = limit?;
+        Some(Self {
+            workdir: workdir.to_path_buf(),
+            limit,
+            last_fingerprint: crate::workflow::checkpoint::working_tree_fingerprint(workdir),
+            noop_rounds: 0,
+        })
+    }
+
+    /// Record a completed round. Returns the number of consecutive no-op rounds once it reaches
+    /// the limit.
+    pub fn observe_round(&mut self) -> Option<u32> {
+        let fingerprint = crate::workflow::checkpoint::working_tree_fingerprint(&self.workdir);
+        self.observe_fingerprint(fingerprint)
+    }
+
+    fn observe_fingerprint(&mut self, fingerprint: Option<String>) -> Option<u32> {
+        let unchanged = fingerprint.is_some() && fingerprint == self.last_fingerprint;
+        self.last_fingerprint = fingerprint;
+        if !unchanged {
+            self.noop_rounds = 0;
+            return None;
+        }
+
+        self.noop_rounds = self.noop_rounds.saturating_add(1);
+        if self.noop_rounds < self.limit.get() {
+            return None;
+        }
+        Some(u32::try_from(self.noop_rounds).unwrap_or(u32::MAX))
+    }
+}
+
+/// Human-readable reason for a `Stalled { rounds }` outcome.
+pub fn describe_stall(rounds: u32) -> String {
+    if rounds == 1 {
+        "no git changes in the last round".to_string()
+    } else {
+        format!("no git changes in the last {rounds} rounds")
+    }
+}
+
+/// Record in `potter-rollout.jsonl` that the project stopped after `rounds` no-op rounds.
+pub fn record_project_stalled(potter_rollout_path: &Path, rounds: u32) -> anyhow::Result<()> {
+    crate::workflow::rollout::append_line(
+        potter_rollout_path,
+        &crate::workflow::rollout::PotterRolloutLine::ProjectStalled { rounds },
+    )
+    .context("append potter-rollout project_stalled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn detector(limit: usize, baseline: Option<&str>) -> StallDetector {
+        StallDetector {
+            workdir: PathBuf::from("/unused"),
+            limit: NonZeroUsize::new(limit).expect("limit"),
+            last_fingerprint: baseline.map(str::to_string),
+            noop_rounds: 0,
+        }
+    }
+
+    #[test]
+    fn start_is_disabled_without_limit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(StallDetector::start(dir.path(), None).is_none());
+    }
+
+    #[test]
+    fn reports_stall_after_consecutive_noop_rounds() {
+        let mut detector = detector(2, Some("a"));
+
+        assert_eq!(detector.observe_fingerprint(Some("a".to_string())), None);
+        // A change resets the streak.
+        assert_eq!(detector.observe_fingerprint(Some("b".to_string())), None);
+        assert_eq!(detector.observe_fingerprint(Some("b".to_string())), None);
+        assert_eq!(detector.observe_fingerprint(Some("b".to_string())), Some(2));
+    }
+
+    #[test]
+    fn missing_fingerprint_counts_as_progress() {
+        let mut detector = detector(1, None);
+
+        assert_eq!(detector.observe_fingerprint(None), None);
+        assert_eq!(detector.observe_fingerprint(None), None);
+        assert_eq!(detector.observe_fingerprint(Some("a".to_string())), None);
+        assert_eq!(detector.observe_fingerprint(Some("a".to_string())), Some(1));
+    }
+}
@@ -0,0 +1,388 @@
+//! Post-round verification gate.
+//!
+//! When a verification command is configured (`--verify-command`), it is executed in the project
+//! working directory after every completed round. The agent marking `finite_incantatem: true` is
+//! only accepted as project success when the command exits successfully within
+//! `--verify-timeout`; otherwise the flag is reset and the command output replaces the previous
+//! failure report at the end of the progress file, so the next round (which starts from a clean
+//! context) can see what is broken. A passing gate removes the report.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+
+/// How long the verification command may run when `--verify-timeout` is unset.
+pub const DEFAULT_VERIFICATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Maximum number of trailing output lines kept from the verification command.
+const OUTPUT_TAIL_MAX_LINES: usize = 200;
+
+/// Heading of the failure report section in the progress file.
+const FAILURE_REPORT_HEADING: &str = "## Verification Failed";
+
+/// Result of running the verification command once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundVerificationResult {
+    pub command: String,
+    pub passed: bool,
+    /// Process exit code, or `None` when the command was killed (by a signal or the timeout) or
+    /// failed to spawn.
+    pub exit_code: Option<i32>,
+    /// Tail of combined stdout/stderr output.
+    pub output: String,
+}
+
+/// Run `command` through the platform shell in `workdir`, killing it after `timeout`.
+///
+/// Spawn failures and timeouts are reported as a failed gate (with the reason as output) rather
+/// than an error, so a misconfigured or hanging command blocks success instead of aborting the
+/// project.
+pub async fn run_verification_command(
+    workdir: &Path,
+    command: &str,
+    timeout: Duration,
+) -> RoundVerificationResult {
+    let mut cmd = shell_command(command);
+    cmd.current_dir(workdir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Run the shell in its own process group, so a timeout also kills what it started.
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let failed = |output: String| RoundVerificationResult {
+        command: command.to_string(),
+        passed: false,
+        exit_code: None,
+        output,
+    };
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => return failed(format!("failed to run verification command: {err}")),
+    };
+    let pid = child.id();
+
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => {
+            let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !stderr.is_empty() {
+                if !combined.is_empty() && !combined.ends_with('\n') {
+                    combined.push('\n');
+                }
+                combined.push_str(&stderr);
+            }
+            RoundVerificationResult {
+                command: command.to_string(),
+                passed: output.status.success(),
+                exit_code: output.status.code(),
+                output: tail_lines(&combined, OUTPUT_TAIL_MAX_LINES),
+            }
+        }
+        Ok(Err(err)) => failed(format!("failed to run verification command: {err}")),
+        // Dropping the output future killed the shell; its process group goes with it.
+        Err(_) => {
+            kill_process_group(pid);
+            failed(format!(
+                "verification command timed out after {} and was killed",
+                crate::workflow::budget::format_duration(timeout)
+            ))
+        }
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pid: Option<u32>) {
+    let Some(pgid) = pid.and_then(|pid| libc::pid_t::try_from(pid).ok()) else {
+        return;
+    };
+    // SAFETY: `killpg` only sends a signal; `pgid` is the group created for the shell above.
+    unsafe {
+        libc::killpg(pgid, libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: Option<u32>) {}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+fn tail_lines(text: &str, max_lines: usize) -> String {
+    let lines = text.trim_end().lines().collect::<Vec<_>>();
+    let start = lines.len().saturating_sub(max_lines);
+    let mut out = String::new();
+    if start > 0 {
+        out.push_str(&format!("... ({start} earlier lines omitted)\n"));
+    }
+    out.push_str(&lines[start..].join("\n"));
+    out
+}
+
+/// Replace the verification failure report in the progress file with `result`.
+///
+/// The previous report (if any) is removed and the new one is appended to the end of the file, so
+/// the file only ever holds the latest failure.
+pub fn record_verification_failure(
+    workdir: &Path,
+    progress_file_rel: &Path,
+    result: &RoundVerificationResult,
+) -> anyhow::Result<()> {
+    let progress_file = workdir.join(progress_file_rel);
+    let contents = std::fs::read_to_string(&progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+    let mut contents = without_failure_report(&contents).unwrap_or(contents);
+
+    let exit_code = result
+        .exit_code
+        .map(|code| code.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    let fence = if result.output.contains("```") {
+        "~~~~"
+    } else {
+        "```"
+    };
+
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&format!(
+        "\n{FAILURE_REPORT_HEADING}\n\n\
+         The verification command failed after the previous round, so `finite_incantatem` was \
+         reset to false. Fix the failures below before marking the project as finished.\n\n\
+         - Command: `{}`\n\
+         - Exit code: {exit_code}\n\n\
+         {fence}text\n{}\n{fence}\n",
+        result.command, result.output,
+    ));
+
+    std::fs::write(&progress_file, contents)
+        .with_context(|| format!("write {}", progress_file.display()))
+}
+
+/// Remove the verification failure report from the progress file, if it has one.
+pub fn clear_verification_failure(workdir: &Path, progress_file_rel: &Path) -> anyhow::Result<()> {
+    let progress_file = workdir.join(progress_file_rel);
+    let contents = std::fs::read_to_string(&progress_file)
+        .with_context(|| format!("read {}", progress_file.display()))?;
+    let Some(contents) = without_failure_report(&contents) else {
+        return Ok(());
+    };
+    std::fs::write(&progress_file, contents)
+        .with_context(|| format!("write {}", progress_file.display()))
+}
+
+/// `contents` without the failure report section, or `None` when there is none.
+///
+/// The section runs from its heading to the next `## ` heading outside a code fence, or to the end
+/// of the file.
+fn without_failure_report(contents: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut found = false;
+    let mut in_report = false;
+    let mut fence: Option<&str> = None;
+    for line in contents.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if in_report {
+            if let Some(marker) = fence {
+                if trimmed == marker {
+                    fence = None;
+                }
+                continue;
+            }
+            if let Some(marker) = ["```", "~~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker))
+            {
+                fence = Some(marker);
+                continue;
+            }
+            if !trimmed.starts_with("## ") {
+                continue;
+            }
+            in_report = false;
+        }
+        if trimmed == FAILURE_REPORT_HEADING {
+            found = true;
+            in_report = true;
+            continue;
+        }
+        out.push_str(line);
+    }
+    if !found {
+        return None;
+    }
+    if in_report {
+        // The report ran to the end of the file: drop the blank line that separated it.
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_verification_command_captures_exit_code_and_output() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let passed =
+            run_verification_command(dir.path(), "echo ok", DEFAULT_VERIFICATION_TIMEOUT).await;
+        assert_eq!(
+            passed,
+            RoundVerificationResult {
+                command: "echo ok".to_string(),
+                passed: true,
+                exit_code: Some(0),
+                output: "ok".to_string(),
+            }
+        );
+
+        let failed = run_verification_command(
+            dir.path(),
+            "echo out; echo err >&2; exit 3",
+            DEFAULT_VERIFICATION_TIMEOUT,
+        )
+        .await;
+        assert_eq!(
+            failed,
+            RoundVerificationResult {
+                command: "echo out; echo err >&2; exit 3".to_string(),
+                passed: false,
+                exit_code: Some(3),
+                output: "out\nerr".to_string(),
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_verification_command_kills_commands_that_time_out() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let started = std::time::Instant::now();
+        let timed_out =
+            run_verification_command(dir.path(), "echo started; sleep 30", Duration::from_secs(1))
+                .await;
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(
+            timed_out,
+            RoundVerificationResult {
+                command: "echo started; sleep 30".to_string(),
+                passed: false,
+                exit_code: None,
+                output: "verification command timed out after 1s and was killed".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn tail_lines_keeps_last_lines_and_notes_omitted_count() {
+        assert_eq!(
+            tail_lines("a\nb\nc\n", 2),
+            "... (1 earlier lines omitted)\nb\nc"
+        );
+        assert_eq!(tail_lines("a\nb", 5), "a\nb");
+    }
+
+    #[test]
+    fn record_verification_failure_appends_report_section() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rel = Path::new("MAIN.md");
+        std::fs::write(
+            dir.path().join(rel),
+            "---\nfinite_incantatem: false\n---\n\n## Done",
+        )
+        .expect("write progress file");
+
+        record_verification_failure(
+            dir.path(),
+            rel,
+            &RoundVerificationResult {
+                command: "cargo test".to_string(),
+                passed: false,
+                exit_code: Some(101),
+                output: "test foo ... FAILED".to_string(),
+            },
+        )
+        .expect("record");
+
+        let contents = std::fs::read_to_string(dir.path().join(rel)).expect("read");
+        assert_eq!(
+            contents,
+            "---\nfinite_incantatem: false\n---\n\n## Done\n\n## Verification Failed\n\n\
+             The verification command failed after the previous round, so `finite_incantatem` \
+             was reset to false. Fix the failures below before marking the project as \
+             finished.\n\n\
+             - Command: `cargo test`\n\
+             - Exit code: 101\n\n\
+             ```text\ntest foo ... FAILED\n```\n"
+        );
+    }
+
+    #[test]
+    fn record_verification_failure_replaces_previous_report() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let rel = Path::new("MAIN.md");
+        std::fs::write(
+            dir.path().join(rel),
+            "---\nfinite_incantatem: false\n---\n\n## Done\n",
+        )
+        .expect("write progress file");
+        let failure = |output: &str| RoundVerificationResult {
+            command: "cargo test".to_string(),
+            passed: false,
+            exit_code: Some(101),
+            output: output.to_string(),
+        };
+
+        record_verification_failure(dir.path(), rel, &failure("## first\nfailed"))
+            .expect("record first");
+        // Notes the agent added after the report stay in place.
+        let mut contents = std::fs::read_to_string(dir.path().join(rel)).expect("read");
+        contents.push_str("\n## Notes\n\n- flaky test\n");
+        std::fs::write(dir.path().join(rel), contents).expect("write notes");
+        record_verification_failure(dir.path(), rel, &failure("second")).expect("record second");
+
+        let contents = std::fs::read_to_string(dir.path().join(rel)).expect("read");
+        assert_eq!(
+            contents,
+            "---\nfinite_incantatem: false\n---\n\n## Done\n\n## Notes\n\n- flaky test\n\n\
+             ## Verification Failed\n\n\
+             The verification command failed after the previous round, so `finite_incantatem` \
+             was reset to false. Fix the failures below before marking the project as \
+             finished.\n\n\
+             - Command: `cargo test`\n\
+             - Exit code: 101\n\n\
+             ```text\nsecond\n```\n"
+        );
+
+        clear_verification_failure(dir.path(), rel).expect("clear");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(rel)).expect("read"),
+            "---\nfinite_incantatem: false\n---\n\n## Done\n\n## Notes\n\n- flaky test\n"
+        );
+    }
+}
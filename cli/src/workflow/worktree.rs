@@ -0,0 +1,329 @@
+//! Isolated git worktrees for `--worktree` projects.
+//!
+//! By default a project mutates the launch working directory directly. In worktree mode the
+//! project gets a dedicated `git worktree` (and branch) instead, so the user can keep working in
+//! the main checkout while rounds run:
+//!
+//! - Project `.codexpotter/projects/YYYY/MM/DD/N` runs in `.codexpotter/worktrees/YYYY/MM/DD/N` on
+//!   branch `codexpotter/YYYY-MM-DD/N`.
+//! - The progress file stays in the launch workdir (so `resume` can discover it) and records the
+//!   worktree path in its front matter (`worktree`).
+//! - Once the project succeeds, the branch can be merged or rebased back into the branch checked
+//!   out in the launch workdir, or left as-is.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::workflow::git::CODEXPOTTER_DIR_EXCLUDE;
+use crate::workflow::git::git_stdout;
+use crate::workflow::round_runner::UiFuture;
+
+const WORKTREE_BRANCH_PREFIX: &str = "codexpotter";
+
+/// A project worktree, relative to the launch workdir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectWorktree {
+    /// Worktree directory relative to the launch workdir.
+    pub dir_rel: PathBuf,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+}
+
+/// How to bring a finished worktree branch back into the launch workdir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorktreeIntegration {
+    /// `git merge` the branch into the current branch.
+    Merge,
+    /// Rebase the branch onto the current branch, then fast-forward.
+    Rebase,
+}
+
+/// Derive the worktree location and branch name for a project directory.
+///
+/// `project_dir_rel` must have the `.codexpotter/projects/YYYY/MM/DD/N` layout created by
+/// [`crate::workflow::project::init_project`].
+pub fn project_worktree_for_project_dir(project_dir_rel: &Path) -> anyhow::Result<ProjectWorktree> {
+    let rel = project_dir_rel
+        .strip_prefix(Path::new(".codexpotter").join("projects"))
+        .with_context(|| {
+            format!(
+                "project dir {} is not under .codexpotter/projects",
+                project_dir_rel.display()
+            )
+        })?;
+    let components = rel
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    let [year, month, day, idx] = components.as_slice() else {
+        anyhow::bail!(
+            "unexpected project dir layout: {}",
+            project_dir_rel.display()
+        );
+    };
+
+    Ok(ProjectWorktree {
+        dir_rel: PathBuf::from(".codexpotter").join("worktrees").join(rel),
+        branch: format!("{WORKTREE_BRANCH_PREFIX}/{year}-{month}-{day}/{idx}"),
+    })
+}
+
+/// Create `worktree` from the current `HEAD` of `workdir`.
+pub fn create_worktree(workdir: &Path, worktree: &ProjectWorktree) -> anyhow::Result<()> {
+    let dir = workdir.join(&worktree.dir_rel);
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    let dir = dir.to_string_lossy();
+    git_stdout(
+        workdir,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            &worktree.branch,
+            &dir,
+            "HEAD",
+        ],
+    )
+    .with_context(|| format!("create git worktree {dir}"))?;
+    Ok(())
+}
+
+/// Resolve the worktree recorded in a progress file, if any.
+///
+/// Errors when the progress file references a worktree that no longer exists, so a resumed
+/// project never silently falls back to mutating the launch workdir.
+pub fn resolve_project_worktree_dir(
+    workdir: &Path,
+    progress_file: &Path,
+) -> anyhow::Result<Option<PathBuf>> {
+    let Some(dir_rel) = crate::workflow::project::progress_file_worktree(progress_file)
+        .context("read worktree from progress file")?
+    else {
+        return Ok(None);
+    };
+
+    let dir = workdir.join(dir_rel);
+    if !dir.is_dir() {
+        anyhow::bail!(
+            "project worktree {} no longer exists (see `git worktree list`)",
+            dir.display()
+        );
+    }
+    Ok(Some(dir))
+}
+
+/// Bring `branch` (checked out in `worktree_dir`) back into the branch checked out in `workdir`.
+///
+/// Conflicts abort the operation and leave both checkouts as they were, so the user can
+/// integrate manually.
+pub fn integrate_worktree_branch(
+    workdir: &Path,
+    worktree_dir: &Path,
+    branch: &str,
+    integration: WorktreeIntegration,
+) -> anyhow::Result<()> {
+    let dirty = git_stdout(
+        worktree_dir,
+        &["status", "--porcelain", "--", ":/", CODEXPOTTER_DIR_EXCLUDE],
+    )?;
+    if !dirty.is_empty() {
+        anyhow::bail!(
+            "worktree {} has uncommitted changes; commit them first",
+            worktree_dir.display()
+        );
+    }
+
+    match integration {
+        WorktreeIntegration::Merge => {
+            if let Err(err) = git_stdout(workdir, &["merge", "--no-edit", branch]) {
+                let _ = git_stdout(workdir, &["merge", "--abort"]);
+                return Err(err).with_context(|| format!("merge {branch}"));
+            }
+        }
+        WorktreeIntegration::Rebase => {
+            let onto = git_stdout(workdir, &["rev-parse", "HEAD"])?;
+            if let Err(err) = git_stdout(worktree_dir, &["rebase", "-q", &onto]) {
+                let _ = git_stdout(worktree_dir, &["rebase", "--abort"]);
+                return Err(err).with_context(|| format!("rebase {branch} onto {onto}"));
+            }
+            git_stdout(workdir, &["merge", "-q", "--ff-only", branch])
+                .with_context(|| format!("fast-forward to {branch}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// UI hooks used to offer integrating a succeeded worktree project.
+pub trait WorktreeIntegrationUi {
+    /// Let the user pick one of `actions`; `None` when cancelled.
+    fn prompt_worktree_integration<'a>(
+        &'a mut self,
+        actions: Vec<String>,
+    ) -> UiFuture<'a, Option<usize>>;
+
+    /// Report the outcome of the selected action.
+    fn insert_worktree_notice(&mut self, message: String, is_error: bool);
+}
+
+impl WorktreeIntegrationUi for codex_tui::CodexPotterTui {
+    fn prompt_worktree_integration<'a>(
+        &'a mut self,
+        actions: Vec<String>,
+    ) -> UiFuture<'a, Option<usize>> {
+        Box::pin(codex_tui::CodexPotterTui::prompt_action_picker(
+            self, actions,
+        ))
+    }
+
+    fn insert_worktree_notice(&mut self, message: String, is_error: bool) {
+        codex_tui::CodexPotterTui::insert_notice(self, message, is_error);
+    }
+}
+
+/// Offer to merge/rebase a succeeded worktree project back, or leave its branch.
+///
+/// Integration failures are reported to the UI rather than returned: the branch and worktree are
+/// left intact so the user can integrate manually.
+pub async fn offer_worktree_integration<U>(
+    ui: &mut U,
+    workdir: &Path,
+    worktree_dir: &Path,
+    branch: &str,
+) -> anyhow::Result<()>
+where
+    U: WorktreeIntegrationUi + ?Sized,
+{
+    let target =
+        crate::workflow::project::resolve_git_branch(workdir).unwrap_or_else(|| "HEAD".to_string());
+    let actions = vec![
+        format!("Merge {branch} into {target}"),
+        format!("Rebase {branch} onto {target}"),
+        format!("Leave branch {branch}"),
+    ];
+
+    let integration = match ui.prompt_worktree_integration(actions).await? {
+        Some(0) => WorktreeIntegration::Merge,
+        Some(1) => WorktreeIntegration::Rebase,
+        _ => {
+            ui.insert_worktree_notice(
+                format!("Left branch {branch} in {}", worktree_dir.display()),
+                false,
+            );
+            return Ok(());
+        }
+    };
+
+    match integrate_worktree_branch(workdir, worktree_dir, branch, integration) {
+        Ok(()) => {
+            let message = match integration {
+                WorktreeIntegration::Merge => format!("Merged {branch} into {target}"),
+                WorktreeIntegration::Rebase => {
+                    format!("Rebased {branch} onto {target} and fast-forwarded {target}")
+                }
+            };
+            ui.insert_worktree_notice(message, false);
+        }
+        Err(err) => {
+            ui.insert_worktree_notice(format!("{err:#}; branch {branch} was left as-is"), true);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn git(workdir: &Path, args: &[&str]) -> String {
+        git_stdout(workdir, args).unwrap_or_else(|err| panic!("{err:#}"))
+    }
+
+    fn init_repo(workdir: &Path) {
+        git(workdir, &["init", "-q", "-b", "main"]);
+        git(workdir, &["config", "user.name", "test"]);
+        git(workdir, &["config", "user.email", "test@example.com"]);
+        std::fs::write(workdir.join("a.txt"), "one\n").expect("write a.txt");
+        git(workdir, &["add", "."]);
+        git(workdir, &["commit", "-q", "-m", "init"]);
+    }
+
+    #[test]
+    fn project_worktree_for_project_dir_mirrors_project_layout() {
+        let worktree =
+            project_worktree_for_project_dir(Path::new(".codexpotter/projects/2026/03/04/2"))
+                .expect("worktree");
+        assert_eq!(
+            worktree,
+            ProjectWorktree {
+                dir_rel: PathBuf::from(".codexpotter/worktrees/2026/03/04/2"),
+                branch: "codexpotter/2026-03-04/2".to_string(),
+            }
+        );
+
+        assert!(project_worktree_for_project_dir(Path::new("projects/1")).is_err());
+    }
+
+    #[test]
+    fn merge_and_rebase_bring_worktree_commits_back() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path();
+        init_repo(workdir);
+
+        for (project_dir, integration) in [
+            (
+                ".codexpotter/projects/2026/03/04/1",
+                WorktreeIntegration::Merge,
+            ),
+            (
+                ".codexpotter/projects/2026/03/04/2",
+                WorktreeIntegration::Rebase,
+            ),
+        ] {
+            let worktree =
+                project_worktree_for_project_dir(Path::new(project_dir)).expect("worktree");
+            create_worktree(workdir, &worktree).expect("create worktree");
+            let worktree_dir = workdir.join(&worktree.dir_rel);
+
+            let file = format!("{}.txt", worktree.branch.replace('/', "-"));
+            std::fs::write(worktree_dir.join(&file), "agent\n").expect("write file");
+            git(&worktree_dir, &["add", "."]);
+            git(&worktree_dir, &["commit", "-q", "-m", "agent work"]);
+
+            integrate_worktree_branch(workdir, &worktree_dir, &worktree.branch, integration)
+                .expect("integrate");
+            assert!(workdir.join(&file).exists());
+        }
+
+        assert_eq!(git(workdir, &["branch", "--show-current"]), "main");
+    }
+
+    #[test]
+    fn integrate_refuses_dirty_worktree() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path();
+        init_repo(workdir);
+
+        let worktree =
+            project_worktree_for_project_dir(Path::new(".codexpotter/projects/2026/03/04/1"))
+                .expect("worktree");
+        create_worktree(workdir, &worktree).expect("create worktree");
+        let worktree_dir = workdir.join(&worktree.dir_rel);
+        std::fs::write(worktree_dir.join("a.txt"), "changed\n").expect("write a.txt");
+
+        let err = integrate_worktree_branch(
+            workdir,
+            &worktree_dir,
+            &worktree.branch,
+            WorktreeIntegration::Merge,
+        )
+        .expect_err("dirty worktree");
+        assert!(format!("{err:#}").contains("uncommitted changes"));
+    }
+}
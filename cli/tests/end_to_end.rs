@@ -0,0 +1,725 @@
+//! End-to-end tests running `codex-potter` against the scripted fake app-server
+//! (`codex-potter-fake-backend`, selected with `--codex-bin`).
+//!
+//! Each test gets its own workdir and `HOME`, so user config, global gitignore and upstream
+//! rollouts stay inside a tempdir. The fake binary is built on first use (into the target directory
+//! of `codex-potter`), so running only this package's tests works too.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::sync::mpsc;
+use std::time::Duration;
+use std::time::Instant;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Build the fake app-server next to `codex-potter` (once per test binary) and return its path.
+fn fake_backend_bin() -> PathBuf {
+    static BIN: OnceLock<PathBuf> = OnceLock::new();
+    BIN.get_or_init(|| {
+        let exe = Path::new(env!("CARGO_BIN_EXE_codex-potter"));
+        let profile_dir = exe.parent().expect("codex-potter binary dir");
+        let target_dir = profile_dir.parent().expect("target dir");
+        let profile = match profile_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("profile dir name")
+        {
+            "debug" => "dev",
+            other => other,
+        };
+        let output = Command::new(env!("CARGO"))
+            .args([
+                "build",
+                "--quiet",
+                "-p",
+                "codex-potter-fake-backend",
+                "--profile",
+            ])
+            .arg(profile)
+            .arg("--target-dir")
+            .arg(target_dir)
+            .arg("--manifest-path")
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("../Cargo.toml"))
+            .output()
+            .expect("run cargo build");
+        assert!(
+            output.status.success(),
+            "building codex-potter-fake-backend failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let bin = exe.with_file_name(format!(
+            "codex-potter-fake-backend{}",
+            std::env::consts::EXE_SUFFIX
+        ));
+        assert!(
+            bin.exists(),
+            "{} not found after building it",
+            bin.display()
+        );
+        bin
+    })
+    .clone()
+}
+
+struct TestEnv {
+    _dir: TempDir,
+    home: PathBuf,
+    workdir: PathBuf,
+    scenario: PathBuf,
+}
+
+impl TestEnv {
+    fn new(scenario: Value) -> Self {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let home = dir.path().join("home");
+        let workdir = dir.path().join("work");
+        std::fs::create_dir_all(&home).expect("create home");
+        std::fs::create_dir_all(&workdir).expect("create workdir");
+        let scenario_path = dir.path().join("scenario.json");
+        std::fs::write(&scenario_path, scenario.to_string()).expect("write scenario");
+        Self {
+            _dir: dir,
+            home,
+            workdir,
+            scenario: scenario_path,
+        }
+    }
+
+    fn write_user_config(&self, contents: &str) {
+        let config_dir = self.home.join(".codexpotter");
+        std::fs::create_dir_all(&config_dir).expect("create config dir");
+        std::fs::write(config_dir.join("config.toml"), contents).expect("write config");
+    }
+
+    fn codex_potter(&self, args: &[&str]) -> Command {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_codex-potter"));
+        cmd.current_dir(&self.workdir)
+            .env("HOME", &self.home)
+            .env("XDG_CONFIG_HOME", self.home.join(".config"))
+            .env("GIT_CONFIG_GLOBAL", self.home.join(".gitconfig"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("CODEX_HOME", self.home.join(".codex"))
+            .env("CODEX_POTTER_FAKE_SCENARIO", &self.scenario)
+            .env_remove("CODEX_BIN")
+            .arg("--codex-bin")
+            .arg(fake_backend_bin())
+            .args(args);
+        cmd
+    }
+
+    /// Messages codex-potter sent to the fake app-server, across all rounds.
+    fn client_messages(&self) -> Vec<Value> {
+        let log = std::fs::read_to_string(self.scenario.with_extension("requests.jsonl"))
+            .expect("read request log");
+        log.lines()
+            .map(|line| serde_json::from_str(line).expect("request log line"))
+            .collect()
+    }
+}
+
+fn run_with_timeout(mut cmd: Command) -> Output {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn codex-potter");
+    // Drain both pipes while waiting, so a chatty child never blocks on a full pipe.
+    let stdout = drain(child.stdout.take().expect("stdout pipe"));
+    let stderr = drain(child.stderr.take().expect("stderr pipe"));
+
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("poll codex-potter") {
+            break Some(status);
+        }
+        if Instant::now() > deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let stdout = stdout.join().expect("join stdout reader");
+    let stderr = stderr.join().expect("join stderr reader");
+    let Some(status) = status else {
+        panic!(
+            "codex-potter timed out\nstdout:\n{}\nstderr:\n{}",
+            String::from_utf8_lossy(&stdout),
+            String::from_utf8_lossy(&stderr)
+        );
+    };
+    Output {
+        status,
+        stdout,
+        stderr,
+    }
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// Run `codex-potter ... exec --json ...` and parse its JSONL stdout.
+fn run_exec_json(cmd: Command) -> (bool, Vec<Value>) {
+    let output = run_with_timeout(cmd);
+    let stdout = String::from_utf8(output.stdout).expect("utf-8 stdout");
+    let events = stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line).unwrap_or_else(|err| {
+                panic!(
+                    "stdout line is not JSON ({err}): {line}\nstderr:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                )
+            })
+        })
+        .collect();
+    (output.status.success(), events)
+}
+
+fn event_type(event: &Value) -> &str {
+    event["type"].as_str().unwrap_or_default()
+}
+
+/// `(item type, text or command)` of every `item.completed` event in `events`.
+fn completed_items(events: &[Value]) -> Vec<(String, String)> {
+    events
+        .iter()
+        .filter(|event| event_type(event) == "item.completed")
+        .map(|event| {
+            let item = &event["item"];
+            let summary = match item["type"].as_str() {
+                Some("agent_message") => item["text"].clone(),
+                Some("command_execution") => item["command"].clone(),
+                Some("file_change") => item["changes"][0]["kind"].clone(),
+                _ => Value::Null,
+            };
+            (
+                item["type"].as_str().unwrap_or_default().to_string(),
+                summary.as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn last_event<'a>(events: &'a [Value], ty: &str) -> &'a Value {
+    events
+        .iter()
+        .rev()
+        .find(|event| event_type(event) == ty)
+        .unwrap_or_else(|| panic!("no {ty} event in {events:#?}"))
+}
+
+#[test]
+fn exec_json_runs_a_scripted_project_to_completion() {
+    let env = TestEnv::new(json!({
+        "turns": [{ "steps": [
+            { "type": "agent_message", "text": "Formatting first" },
+            { "type": "request_approval", "command": "cargo fmt", "output": "" },
+            { "type": "exec", "command": "ls", "output": "README.md\n" },
+            { "type": "write_file", "path": "hello.txt", "content": "hello\n" },
+            { "type": "finish_project" },
+            { "type": "agent_message", "text": "Wrote hello.txt" }
+        ] }]
+    }));
+
+    let (success, events) =
+        run_exec_json(env.codex_potter(&["--rounds", "3", "exec", "--json", "Write hello.txt"]));
+
+    assert!(success, "exec failed: {events:#?}");
+    assert_eq!(
+        std::fs::read_to_string(env.workdir.join("hello.txt")).expect("read hello.txt"),
+        "hello\n"
+    );
+    assert_eq!(
+        completed_items(&events),
+        vec![
+            ("agent_message".to_string(), "Formatting first".to_string()),
+            (
+                "command_execution".to_string(),
+                "bash -lc 'cargo fmt'".to_string()
+            ),
+            ("command_execution".to_string(), "bash -lc ls".to_string()),
+            ("file_change".to_string(), "add".to_string()),
+            ("agent_message".to_string(), "Wrote hello.txt".to_string()),
+        ]
+    );
+    let completed = last_event(&events, "potter.project.completed");
+    assert_eq!(completed["outcome"], json!("succeeded"));
+    assert_eq!(completed["rounds_run"], json!(1));
+
+    let approval_decisions = env
+        .client_messages()
+        .iter()
+        .filter_map(|message| message["result"]["decision"].as_str().map(str::to_string))
+        .collect::<Vec<_>>();
+    assert_eq!(approval_decisions, vec!["accept".to_string()]);
+}
+
+#[test]
+fn exec_output_schema_runs_a_report_turn_after_the_project() {
+    let env = TestEnv::new(json!({
+        "turns": [
+            { "steps": [
+                { "type": "finish_project" },
+                { "type": "agent_message", "text": "Done" }
+            ] },
+            { "steps": [
+                { "type": "agent_message", "text": "{\"summary\": \"Nothing to do\"}" }
+            ] }
+        ]
+    }));
+    let schema = json!({
+        "type": "object",
+        "properties": { "summary": { "type": "string" } },
+        "required": ["summary"],
+        "additionalProperties": false
+    });
+    std::fs::write(env.workdir.join("schema.json"), schema.to_string()).expect("write schema");
+
+    let (success, events) = run_exec_json(env.codex_potter(&[
+        "exec",
+        "--json",
+        "--output-schema",
+        "schema.json",
+        "--output-last-message",
+        "report.json",
+        "Summarize",
+    ]));
+
+    assert!(success, "exec failed: {events:#?}");
+    let completed = last_event(&events, "potter.project.completed");
+    assert_eq!(completed["outcome"], json!("succeeded"));
+    assert_eq!(completed["output"], json!({ "summary": "Nothing to do" }));
+    assert_eq!(
+        std::fs::read_to_string(env.workdir.join("report.json")).expect("read report"),
+        "{\n  \"summary\": \"Nothing to do\"\n}\n"
+    );
+
+    let output_schemas = env
+        .client_messages()
+        .iter()
+        .filter(|message| message["method"] == json!("turn/start"))
+        .map(|message| message["params"]["outputSchema"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(output_schemas, vec![Value::Null, schema]);
+}
+
+#[test]
+fn exec_resume_replays_recorded_rounds_and_continues() {
+    let env = TestEnv::new(json!({
+        "turns": [{ "steps": [
+            { "type": "write_file", "path": "notes.txt", "content": "round 1\n" },
+            { "type": "agent_message", "text": "Round one done" }
+        ] }]
+    }));
+
+    let (success, events) =
+        run_exec_json(env.codex_potter(&["--rounds", "1", "exec", "--json", "Take notes"]));
+    assert!(
+        !success,
+        "the round budget runs out before the project finishes"
+    );
+    assert_eq!(
+        last_event(&events, "potter.project.completed")["outcome"],
+        json!("budget_exhausted")
+    );
+    let project_dir = last_event(&events, "potter.project.started")["project_dir"]
+        .as_str()
+        .expect("project_dir")
+        .to_string();
+
+    // The scenario is exhausted, so the new round runs the default turn and finishes the project.
+    let (success, events) = run_exec_json(env.codex_potter(&[
+        "--rounds",
+        "1",
+        "exec",
+        "--json",
+        "resume",
+        &project_dir,
+    ]));
+    assert!(success, "exec resume failed: {events:#?}");
+
+    let replay_start = events
+        .iter()
+        .position(|event| event_type(event) == "potter.replay.started")
+        .expect("potter.replay.started");
+    let replay_end = events
+        .iter()
+        .position(|event| event_type(event) == "potter.replay.completed")
+        .expect("potter.replay.completed");
+    assert_eq!(events[replay_start]["rounds"], json!(1));
+    assert_eq!(
+        completed_items(&events[replay_start..replay_end]),
+        vec![
+            ("file_change".to_string(), "add".to_string()),
+            ("agent_message".to_string(), "Round one done".to_string()),
+        ]
+    );
+    assert_eq!(
+        completed_items(&events[replay_end..]),
+        vec![("agent_message".to_string(), "Done.".to_string())]
+    );
+    assert_eq!(
+        last_event(&events, "potter.project.completed")["outcome"],
+        json!("succeeded")
+    );
+}
+
+#[test]
+fn exec_recovers_from_a_stream_disconnect() {
+    let env = TestEnv::new(json!({
+        "turns": [
+            { "steps": [{ "type": "stream_disconnect" }] },
+            { "steps": [
+                { "type": "agent_message", "text": "Back online" },
+                { "type": "finish_project" }
+            ] }
+        ]
+    }));
+    env.write_user_config("[stream_recovery]\nbase_backoff = \"1s\"\njitter = 0\n");
+
+    let (success, events) =
+        run_exec_json(env.codex_potter(&["--rounds", "1", "exec", "--json", "Keep going"]));
+
+    assert!(success, "exec failed: {events:#?}");
+    let types = events.iter().map(event_type).collect::<Vec<_>>();
+    assert!(
+        types.contains(&"potter.stream_recovery.update")
+            && types.contains(&"potter.stream_recovery.recovered"),
+        "missing stream recovery events: {types:#?}"
+    );
+
+    let turn_inputs = env
+        .client_messages()
+        .iter()
+        .filter(|message| message["method"] == json!("turn/start"))
+        .map(|message| message["params"]["input"][0]["text"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        turn_inputs.len(),
+        2,
+        "turn/start requests: {turn_inputs:#?}"
+    );
+    assert_eq!(turn_inputs[1], json!("Continue"));
+}
+
+/// A `codex-potter app-server` child driven over JSON-RPC.
+struct PotterAppServer {
+    child: Child,
+    stdin: ChildStdin,
+    messages: mpsc::Receiver<Value>,
+    next_id: i64,
+}
+
+impl PotterAppServer {
+    fn spawn(env: &TestEnv) -> Self {
+        let mut cmd = env.codex_potter(&["--rounds", "2", "app-server"]);
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("spawn codex-potter app-server");
+        let stdin = child.stdin.take().expect("app-server stdin");
+        let stdout = child.stdout.take().expect("app-server stdout");
+        let (tx, messages) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                    continue;
+                };
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            child,
+            stdin,
+            messages,
+            next_id: 1,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        writeln!(self.stdin, "{message}").expect("write to app-server");
+        self.stdin.flush().expect("flush app-server stdin");
+    }
+
+    /// Send a request and return its response (`result` or `error`), skipping notifications.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "id": id, "method": method, "params": params }));
+        self.wait_for(|message| message["id"] == json!(id) && message.get("method").is_none())
+    }
+
+    fn wait_for(&mut self, mut matches: impl FnMut(&Value) -> bool) -> Value {
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.messages.recv_timeout(remaining) {
+                Ok(message) if matches(&message) => return message,
+                Ok(_) => {}
+                Err(err) => panic!("no matching app-server message: {err}"),
+            }
+        }
+    }
+
+    /// Wait for a `codex/event/potter` notification whose event `msg` matches.
+    fn wait_for_event(&mut self, mut matches: impl FnMut(&Value) -> bool) -> Value {
+        self.wait_for(|message| {
+            message["method"] == json!("codex/event/potter") && matches(&message["params"]["msg"])
+        })
+    }
+}
+
+impl Drop for PotterAppServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn app_server_interrupts_a_running_round() {
+    let env = TestEnv::new(json!({
+        "turns": [{ "steps": [
+            { "type": "agent_message", "text": "Working on it" },
+            { "type": "wait_for_interrupt" }
+        ] }]
+    }));
+    let mut server = PotterAppServer::spawn(&env);
+
+    let initialize = server.request(
+        "initialize",
+        json!({ "clientInfo": { "name": "end-to-end", "title": null, "version": "0.0.0" } }),
+    );
+    assert!(initialize.get("result").is_some(), "{initialize:#?}");
+    server.send(json!({ "method": "initialized" }));
+
+    let started = server.request(
+        "project/start",
+        json!({ "userMessage": "Do something slow", "cwd": env.workdir }),
+    );
+    let project_id = started["result"]["projectId"]
+        .as_str()
+        .unwrap_or_else(|| panic!("project/start failed: {started:#?}"))
+        .to_string();
+
+    server.wait_for_event(|msg| {
+        msg["type"] == json!("agent_message") && msg["message"] == json!("Working on it")
+    });
+    let interrupt = server.request("project/interrupt", json!({ "projectId": project_id }));
+    assert_eq!(interrupt["result"], json!({}));
+
+    let finished = server.wait_for_event(|msg| msg["type"] == json!("potter_round_finished"));
+    assert_eq!(
+        finished["params"]["msg"]["outcome"]["type"],
+        json!("interrupted")
+    );
+    assert_eq!(finished["params"]["projectId"], json!(project_id));
+
+    // The project is parked as interrupted shortly after its round finishes.
+    let deadline = Instant::now() + TIMEOUT;
+    let resolved = loop {
+        let response = server.request(
+            "project/resolve_interrupt",
+            json!({ "projectId": project_id, "action": "stop" }),
+        );
+        if response.get("result").is_some() || Instant::now() > deadline {
+            break response;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    assert!(resolved.get("result").is_some(), "{resolved:#?}");
+
+    let interrupts = env
+        .client_messages()
+        .iter()
+        .filter(|message| message["method"] == json!("turn/interrupt"))
+        .count();
+    assert_eq!(interrupts, 1);
+}
+
+#[test]
+fn app_server_attach_replays_the_active_round() {
+    let env = TestEnv::new(json!({
+        "turns": [{ "steps": [
+            { "type": "agent_message", "text": "Working on it" },
+            { "type": "wait_for_interrupt" }
+        ] }]
+    }));
+    let mut server = PotterAppServer::spawn(&env);
+
+    let initialize = server.request(
+        "initialize",
+        json!({ "clientInfo": { "name": "end-to-end", "title": null, "version": "0.0.0" } }),
+    );
+    assert!(initialize.get("result").is_some(), "{initialize:#?}");
+    server.send(json!({ "method": "initialized" }));
+
+    let started = server.request(
+        "project/start",
+        json!({ "userMessage": "Do something slow", "cwd": env.workdir }),
+    );
+    let project_id = started["result"]["projectId"]
+        .as_str()
+        .unwrap_or_else(|| panic!("project/start failed: {started:#?}"))
+        .to_string();
+    let live = server.wait_for_event(|msg| {
+        msg["type"] == json!("agent_message") && msg["message"] == json!("Working on it")
+    });
+
+    let attached = server.request("project/attach", json!({}));
+    let result = &attached["result"];
+    assert_eq!(result["projectId"], json!(project_id), "{attached:#?}");
+    let events = result["events"].as_array().expect("replayed events");
+    let types = events
+        .iter()
+        .map(|event| event["event"]["msg"]["type"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    for expected in [
+        "potter_project_started",
+        "potter_round_started",
+        "agent_message",
+    ] {
+        assert!(types.contains(&expected), "missing {expected}: {types:#?}");
+    }
+    let seqs = events
+        .iter()
+        .map(|event| event["seq"].as_u64().expect("seq"))
+        .collect::<Vec<_>>();
+    assert!(
+        seqs.windows(2).all(|pair| pair[0] < pair[1]),
+        "replay out of order: {seqs:?}"
+    );
+    // The live notification carries the seq it was replayed with.
+    assert!(seqs.contains(&live["params"]["seq"].as_u64().expect("live seq")));
+
+    let other = server.request("project/attach", json!({ "projectId": "/nowhere/MAIN.md" }));
+    assert!(other.get("error").is_some(), "{other:#?}");
+
+    let detached = server.request("project/detach", json!({ "projectId": project_id }));
+    assert_eq!(detached["result"], json!({}));
+}
+
+#[test]
+fn app_server_prompt_queue_survives_restarts() {
+    let env = TestEnv::new(json!({ "turns": [] }));
+    let prompts = |response: &Value| {
+        response["result"]["entries"]
+            .as_array()
+            .unwrap_or_else(|| panic!("queue response: {response:#?}"))
+            .iter()
+            .map(|entry| entry["prompt"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let first_id = {
+        let mut server = PotterAppServer::spawn(&env);
+        let mut ids = Vec::new();
+        for prompt in ["first", "second", "third"] {
+            let added = server.request("queue/add", json!({ "prompt": prompt }));
+            ids.push(
+                added["result"]["entry"]["id"]
+                    .as_str()
+                    .unwrap_or_else(|| panic!("queue/add failed: {added:#?}"))
+                    .to_string(),
+            );
+        }
+        let reordered = server.request("queue/reorder", json!({ "ids": [ids[2]] }));
+        assert_eq!(prompts(&reordered), vec!["third", "first", "second"]);
+        let removed = server.request("queue/remove", json!({ "id": ids[1] }));
+        assert_eq!(removed["result"]["entry"]["prompt"], json!("second"));
+        ids[0].clone()
+    };
+
+    let mut server = PotterAppServer::spawn(&env);
+    let listed = server.request("queue/list", json!({}));
+    assert_eq!(prompts(&listed), vec!["third", "first"]);
+    assert_eq!(listed["result"]["entries"][1]["id"], json!(first_id));
+    assert!(env.workdir.join(".codexpotter/queue.jsonl").is_file());
+
+    let taken = server.request("queue/take", json!({}));
+    assert_eq!(taken["result"]["entry"]["prompt"], json!("third"));
+    let taken = server.request("queue/take", json!({}));
+    assert_eq!(taken["result"]["entry"]["id"], json!(first_id));
+    let taken = server.request("queue/take", json!({}));
+    assert_eq!(taken["result"], json!({ "entry": null }));
+}
+
+#[cfg(unix)]
+#[test]
+fn detached_project_is_listed_and_stopped() {
+    let env = TestEnv::new(json!({
+        "turns": [{ "steps": [
+            { "type": "agent_message", "text": "Working on it" },
+            { "type": "wait_for_interrupt" }
+        ] }]
+    }));
+    let stdout = |output: &Output| String::from_utf8_lossy(&output.stdout).to_string();
+
+    let started = run_with_timeout(env.codex_potter(&[
+        "--rounds",
+        "2",
+        "start",
+        "--detach",
+        "Do something slow",
+    ]));
+    assert!(started.status.success(), "{started:#?}");
+    assert!(
+        stdout(&started).contains("in the background"),
+        "{}",
+        stdout(&started)
+    );
+
+    // The record is rewritten once the first round starts.
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        let status = stdout(&run_with_timeout(env.codex_potter(&["status"])));
+        if status.contains("running") && status.contains("round 1/2") {
+            break;
+        }
+        assert!(Instant::now() < deadline, "daemon never ran: {status}");
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let stopped = run_with_timeout(env.codex_potter(&["stop"]));
+    assert!(stopped.status.success(), "{stopped:#?}");
+    assert!(stdout(&stopped).contains("stopped"), "{}", stdout(&stopped));
+
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        let status = stdout(&run_with_timeout(env.codex_potter(&["status"])));
+        if status.contains("stopped") {
+            break;
+        }
+        assert!(Instant::now() < deadline, "daemon never finished: {status}");
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,48 @@
+//! Scripted stand-in for `codex app-server`, for codex-potter end-to-end tests.
+//!
+//! Point codex-potter at it with `--codex-bin path/to/codex-potter-fake-backend`:
+//!
+//! - `--version` reports [`FAKE_CODEX_VERSION`], inside the range codex-potter supports.
+//! - `app-server` (other arguments such as `--sandbox` are ignored) serves the app-server
+//!   JSON-RPC protocol on stdin/stdout, replaying the scenario named by
+//!   `CODEX_POTTER_FAKE_SCENARIO` (see [`scenario`]).
+//!
+//! Rollout files are written under `$CODEX_HOME/sessions` (default `~/.codex/sessions`).
+
+mod scenario;
+mod server;
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::scenario::ScenarioFile;
+use crate::server::FakeAppServer;
+
+/// Upstream `codex` version the fake app-server claims to be.
+pub const FAKE_CODEX_VERSION: &str = "0.104.0";
+
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "--version") {
+        println!("codex-cli {FAKE_CODEX_VERSION}");
+        return Ok(());
+    }
+    if !args.iter().any(|arg| arg == "app-server") {
+        anyhow::bail!("usage: codex-potter-fake-backend [--version | [OPTIONS] app-server]");
+    }
+
+    let scenario = ScenarioFile::from_env()?;
+    let codex_home = codex_home()?;
+    let stdin = std::io::stdin().lock();
+    let stdout = std::io::stdout().lock();
+    FakeAppServer::new(stdin, stdout, scenario, codex_home).run()
+}
+
+fn codex_home() -> anyhow::Result<PathBuf> {
+    if let Some(codex_home) = std::env::var_os("CODEX_HOME").filter(|value| !value.is_empty()) {
+        return Ok(PathBuf::from(codex_home));
+    }
+    let home = std::env::var_os("HOME").context("neither CODEX_HOME nor HOME is set")?;
+    Ok(PathBuf::from(home).join(".codex"))
+}
@@ -0,0 +1,246 @@
+//! Scripted turns replayed by the fake app-server.
+//!
+//! A scenario is a JSON file selected with [`SCENARIO_ENV_VAR`]:
+//!
+//! ```json
+//! {
+//!   "turns": [
+//!     { "steps": [
+//!       { "type": "agent_message", "text": "Writing the file" },
+//!       { "type": "write_file", "path": "hello.txt", "content": "hello\n" },
+//!       { "type": "finish_project" }
+//!     ] }
+//!   ]
+//! }
+//! ```
+//!
+//! Every `turn/start` consumes the next turn, whichever process serves it: codex-potter spawns a
+//! new app-server per round unless `--reuse-app-server` starts every round as a new thread on the
+//! same process, so the position is kept in a `<scenario>.cursor` file next to the scenario rather
+//! than in memory. Nothing is keyed on process or thread start, so turns line up with rounds
+//! either way. Once the turns are exhausted (or without a scenario) every turn runs
+//! [`default_turn`]. Every client message is appended to `<scenario>.requests.jsonl` so tests can
+//! assert what codex-potter sent.
+
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Environment variable holding the path of the scenario file.
+pub const SCENARIO_ENV_VAR: &str = "CODEX_POTTER_FAKE_SCENARIO";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub turns: Vec<ScenarioTurn>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ScenarioTurn {
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// One scripted action of a turn. Steps run in order; `stream_disconnect` and
+/// `wait_for_interrupt` end the turn, so steps after them are skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Step {
+    /// Emit an `agent_message`; the last one becomes the turn's `last_agent_message`.
+    AgentMessage {
+        text: String,
+    },
+    /// Emit `exec_command_begin` / `exec_command_end` for `bash -lc <command>` with a scripted
+    /// result. The command is not run.
+    Exec {
+        command: String,
+        #[serde(default)]
+        output: String,
+        #[serde(default)]
+        exit_code: i32,
+    },
+    /// Write `content` to `path` (relative to the thread cwd) and emit `patch_apply_begin` /
+    /// `patch_apply_end`.
+    WriteFile {
+        path: PathBuf,
+        content: String,
+    },
+    /// Ask the client to approve `command` (`item/commandExecution/requestApproval`) and emit the
+    /// exec events only when it is accepted.
+    RequestApproval {
+        command: String,
+        #[serde(default)]
+        output: String,
+    },
+    /// Set `finite_incantatem: true` in the progress file named by the developer instructions.
+    FinishProject,
+    /// Emit a retryable `ResponseStreamDisconnected` error and end the turn without a message.
+    StreamDisconnect,
+    /// Block until the client sends `turn/interrupt`, then end the turn as interrupted.
+    WaitForInterrupt,
+    Sleep {
+        ms: u64,
+    },
+}
+
+/// Turn run when the scenario has no turn left: report success and finish the project.
+pub fn default_turn() -> ScenarioTurn {
+    ScenarioTurn {
+        steps: vec![
+            Step::AgentMessage {
+                text: "Done.".to_string(),
+            },
+            Step::FinishProject,
+        ],
+    }
+}
+
+/// A scenario loaded from disk, plus its cursor and request log files.
+#[derive(Debug, Clone)]
+pub struct ScenarioFile {
+    path: PathBuf,
+    scenario: Scenario,
+}
+
+impl ScenarioFile {
+    /// The scenario named by [`SCENARIO_ENV_VAR`], if set.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        match std::env::var_os(SCENARIO_ENV_VAR) {
+            Some(path) if !path.is_empty() => Self::load(Path::new(&path)).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("read scenario {}", path.display()))?;
+        let scenario = serde_json::from_str(&contents)
+            .with_context(|| format!("parse scenario {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            scenario,
+        })
+    }
+
+    fn cursor_path(&self) -> PathBuf {
+        self.path.with_extension("cursor")
+    }
+
+    fn requests_log_path(&self) -> PathBuf {
+        self.path.with_extension("requests.jsonl")
+    }
+
+    /// Take the next scripted turn, advancing the shared cursor.
+    pub fn next_turn(&self) -> anyhow::Result<ScenarioTurn> {
+        let cursor_path = self.cursor_path();
+        let cursor = match std::fs::read_to_string(&cursor_path) {
+            Ok(contents) => contents
+                .trim()
+                .parse::<usize>()
+                .with_context(|| format!("parse cursor {}", cursor_path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(err) => {
+                return Err(err).with_context(|| format!("read cursor {}", cursor_path.display()));
+            }
+        };
+        std::fs::write(&cursor_path, format!("{}\n", cursor + 1))
+            .with_context(|| format!("write cursor {}", cursor_path.display()))?;
+
+        Ok(self
+            .scenario
+            .turns
+            .get(cursor)
+            .cloned()
+            .unwrap_or_else(default_turn))
+    }
+
+    /// Append a raw client message to the request log.
+    pub fn log_client_message(&self, line: &str) -> anyhow::Result<()> {
+        let log_path = self.requests_log_path();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .with_context(|| format!("open request log {}", log_path.display()))?;
+        writeln!(file, "{}", line.trim_end())
+            .with_context(|| format!("write request log {}", log_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn turns_are_consumed_across_loads_then_fall_back_to_the_default() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("scenario.json");
+        std::fs::write(
+            &path,
+            r#"{ "turns": [
+                { "steps": [
+                    { "type": "exec", "command": "cargo test", "output": "ok" },
+                    { "type": "stream_disconnect" }
+                ] },
+                { "steps": [{ "type": "sleep", "ms": 5 }] }
+            ] }"#,
+        )
+        .expect("write scenario");
+
+        let first = ScenarioFile::load(&path).expect("load scenario");
+        assert_eq!(
+            first.next_turn().expect("first turn"),
+            ScenarioTurn {
+                steps: vec![
+                    Step::Exec {
+                        command: "cargo test".to_string(),
+                        output: "ok".to_string(),
+                        exit_code: 0,
+                    },
+                    Step::StreamDisconnect,
+                ],
+            }
+        );
+
+        // Without `--reuse-app-server`, a later round runs in a new process that loads the
+        // scenario again.
+        let second = ScenarioFile::load(&path).expect("reload scenario");
+        assert_eq!(
+            second.next_turn().expect("second turn"),
+            ScenarioTurn {
+                steps: vec![Step::Sleep { ms: 5 }],
+            }
+        );
+        assert_eq!(second.next_turn().expect("third turn"), default_turn());
+
+        second
+            .log_client_message("{\"method\":\"initialized\"}\n")
+            .expect("log message");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("scenario.requests.jsonl"))
+                .expect("read request log"),
+            "{\"method\":\"initialized\"}\n"
+        );
+    }
+
+    #[test]
+    fn unknown_step_types_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("scenario.json");
+        std::fs::write(
+            &path,
+            r#"{ "turns": [{ "steps": [{ "type": "dance" }] }] }"#,
+        )
+        .expect("write scenario");
+
+        let err = ScenarioFile::load(&path).expect_err("unknown step");
+        assert!(
+            format!("{err:#}").contains("unknown variant `dance`"),
+            "unexpected error: {err:#}"
+        );
+    }
+}
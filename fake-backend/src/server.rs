@@ -0,0 +1,811 @@
+//! JSON-RPC loop speaking the subset of the `codex app-server` protocol codex-potter uses.
+//!
+//! Messages are one JSON object per line without the `"jsonrpc"` field, like upstream. Supported
+//! requests: `initialize`, `thread/start`, `thread/resume`, `thread/rollback`, `turn/start` and
+//! `turn/interrupt`. A turn runs synchronously right after its `turn/start` response: events go out
+//! as `codex/event/<type>` notifications, and the loop only reads client messages when a step waits
+//! for one (an approval answer or `turn/interrupt`).
+//!
+//! Like upstream, every thread writes a rollout file (`session_meta`, `turn_context` and
+//! `event_msg` lines) under `$CODEX_HOME/sessions` so `codex-potter resume` can replay it.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use codex_protocol::ThreadId;
+use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::protocol::AgentMessageEvent;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecCommandBeginEvent;
+use codex_protocol::protocol::ExecCommandEndEvent;
+use codex_protocol::protocol::ExecCommandSource;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::PatchApplyBeginEvent;
+use codex_protocol::protocol::PatchApplyEndEvent;
+use codex_protocol::protocol::ThreadRolledBackEvent;
+use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::protocol::TurnAbortedEvent;
+use codex_protocol::protocol::TurnCompleteEvent;
+use codex_protocol::protocol::TurnStartedEvent;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::FAKE_CODEX_VERSION;
+use crate::scenario::ScenarioFile;
+use crate::scenario::ScenarioTurn;
+use crate::scenario::Step;
+use crate::scenario::default_turn;
+
+const MODEL: &str = "fake-model";
+const MODEL_PROVIDER: &str = "fake";
+
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+const METHOD_NOT_FOUND_ERROR_CODE: i64 = -32601;
+
+/// Developer instructions line naming the progress file (see `cli/prompts/developer_prompt.md`).
+const PROGRESS_FILE_PREFIX: &str = "- Progress file: `";
+
+struct FakeThread {
+    id: String,
+    cwd: PathBuf,
+    rollout_path: PathBuf,
+    /// Progress file named by the developer instructions, resolved against `cwd`.
+    progress_file: Option<PathBuf>,
+}
+
+/// How a turn's steps ended.
+enum TurnEnd {
+    Completed,
+    Disconnected,
+    Interrupted,
+    /// The client closed stdin while a step waited for it.
+    ClientGone,
+}
+
+pub struct FakeAppServer<R, W> {
+    input: R,
+    output: W,
+    scenario: Option<ScenarioFile>,
+    codex_home: PathBuf,
+    thread: Option<FakeThread>,
+    next_turn: u64,
+    next_call: u64,
+    next_server_request_id: i64,
+}
+
+impl<R: BufRead, W: Write> FakeAppServer<R, W> {
+    pub fn new(input: R, output: W, scenario: Option<ScenarioFile>, codex_home: PathBuf) -> Self {
+        Self {
+            input,
+            output,
+            scenario,
+            codex_home,
+            thread: None,
+            next_turn: 1,
+            next_call: 1,
+            next_server_request_id: 0,
+        }
+    }
+
+    /// Serve client messages until stdin is closed.
+    pub fn run(&mut self) -> anyhow::Result<()> {
+        while let Some(message) = self.read_message()? {
+            let (Some(id), Some(method)) = (message.get("id"), message.get("method")) else {
+                // Notifications (`initialized`) and stray responses need no answer.
+                continue;
+            };
+            let id = id.clone();
+            let method = method.as_str().unwrap_or_default().to_string();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            self.handle_request(id, &method, params)?;
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, id: Value, method: &str, params: Value) -> anyhow::Result<()> {
+        match method {
+            "initialize" => self.respond(
+                id,
+                json!({ "userAgent": format!("codex_potter_fake_backend/{FAKE_CODEX_VERSION}") }),
+            ),
+            "thread/start" => {
+                let thread_id = ThreadId::new().to_string();
+                let response = self.open_thread(thread_id, &params, true)?;
+                self.respond(id, response)
+            }
+            "thread/resume" => {
+                let thread_id = params
+                    .get("threadId")
+                    .and_then(Value::as_str)
+                    .context("thread/resume without threadId")?
+                    .to_string();
+                let response = self.open_thread(thread_id, &params, false)?;
+                self.respond(id, response)
+            }
+            "thread/rollback" => {
+                let Some(thread) = &self.thread else {
+                    return self.respond_error(id, INVALID_REQUEST_ERROR_CODE, "no thread");
+                };
+                let response =
+                    json!({ "thread": { "id": thread.id, "path": thread.rollout_path } });
+                let num_turns = params
+                    .get("numTurns")
+                    .and_then(Value::as_u64)
+                    .and_then(|num_turns| u32::try_from(num_turns).ok())
+                    .unwrap_or(1);
+                self.respond(id, response)?;
+                self.emit(
+                    "",
+                    EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns }),
+                )
+            }
+            "turn/start" => {
+                if self.thread.is_none() {
+                    return self.respond_error(id, INVALID_REQUEST_ERROR_CODE, "no thread");
+                }
+                let turn_id = format!("turn-{}", self.next_turn);
+                self.next_turn += 1;
+                self.respond(id, json!({ "turn": { "id": turn_id } }))?;
+                let turn = match &self.scenario {
+                    Some(scenario) => scenario.next_turn()?,
+                    None => default_turn(),
+                };
+                self.run_turn(&turn_id, turn)
+            }
+            // Interrupts only make sense while a turn waits for one.
+            "turn/interrupt" => {
+                self.respond_error(id, INVALID_REQUEST_ERROR_CODE, "no turn in progress")
+            }
+            other => self.respond_error(
+                id,
+                METHOD_NOT_FOUND_ERROR_CODE,
+                &format!("unsupported method {other:?}"),
+            ),
+        }
+    }
+
+    fn open_thread(
+        &mut self,
+        thread_id: String,
+        params: &Value,
+        is_new: bool,
+    ) -> anyhow::Result<Value> {
+        let cwd = match params.get("cwd").and_then(Value::as_str) {
+            Some(cwd) => PathBuf::from(cwd),
+            None => std::env::current_dir().context("resolve current directory")?,
+        };
+        let progress_file = params
+            .get("developerInstructions")
+            .and_then(Value::as_str)
+            .and_then(progress_file_from_instructions)
+            .map(|path| cwd.join(path));
+
+        let sessions_dir = self.codex_home.join("sessions");
+        std::fs::create_dir_all(&sessions_dir)
+            .with_context(|| format!("create {}", sessions_dir.display()))?;
+        let rollout_path = sessions_dir.join(format!("rollout-{thread_id}.jsonl"));
+        if is_new {
+            append_rollout_line(
+                &rollout_path,
+                &json!({
+                    "type": "session_meta",
+                    "payload": { "id": thread_id, "cwd": cwd, "model_provider": MODEL_PROVIDER },
+                }),
+            )?;
+        } else if !rollout_path.exists() {
+            anyhow::bail!("no rollout for thread {thread_id}");
+        }
+        append_rollout_line(
+            &rollout_path,
+            &json!({ "type": "turn_context", "payload": { "cwd": cwd, "model": MODEL } }),
+        )?;
+
+        let response = json!({
+            "thread": { "id": thread_id, "path": rollout_path },
+            "model": MODEL,
+            "modelProvider": MODEL_PROVIDER,
+            "cwd": cwd,
+            "approvalPolicy": "never",
+            "sandbox": { "type": "dangerFullAccess" },
+            "reasoningEffort": null,
+        });
+        self.thread = Some(FakeThread {
+            id: thread_id,
+            cwd,
+            rollout_path,
+            progress_file,
+        });
+        Ok(response)
+    }
+
+    fn run_turn(&mut self, turn_id: &str, turn: ScenarioTurn) -> anyhow::Result<()> {
+        self.emit(
+            turn_id,
+            EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: turn_id.to_string(),
+                model_context_window: None,
+            }),
+        )?;
+
+        let mut last_agent_message = None;
+        let mut end = TurnEnd::Completed;
+        for step in turn.steps {
+            match step {
+                Step::AgentMessage { text } => {
+                    self.emit(
+                        turn_id,
+                        EventMsg::AgentMessage(AgentMessageEvent {
+                            message: text.clone(),
+                            phase: None,
+                        }),
+                    )?;
+                    last_agent_message = Some(text);
+                }
+                Step::Exec {
+                    command,
+                    output,
+                    exit_code,
+                } => self.emit_exec(turn_id, &command, &output, exit_code)?,
+                Step::WriteFile { path, content } => self.write_file(turn_id, &path, &content)?,
+                Step::RequestApproval { command, output } => {
+                    match self.request_command_approval(turn_id, &command)? {
+                        Some(true) => self.emit_exec(turn_id, &command, &output, 0)?,
+                        Some(false) => {}
+                        None => {
+                            end = TurnEnd::ClientGone;
+                            break;
+                        }
+                    }
+                }
+                Step::FinishProject => self.finish_project()?,
+                Step::StreamDisconnect => {
+                    self.emit(
+                        turn_id,
+                        EventMsg::Error(ErrorEvent {
+                            message: "stream disconnected before completion: error sending \
+                                      request for url (https://api.openai.com/v1/responses)"
+                                .to_string(),
+                            codex_error_info: Some(CodexErrorInfo::ResponseStreamDisconnected {
+                                http_status_code: None,
+                            }),
+                        }),
+                    )?;
+                    end = TurnEnd::Disconnected;
+                    break;
+                }
+                Step::WaitForInterrupt => {
+                    end = if self.wait_for_interrupt()? {
+                        TurnEnd::Interrupted
+                    } else {
+                        TurnEnd::ClientGone
+                    };
+                    break;
+                }
+                Step::Sleep { ms } => std::thread::sleep(Duration::from_millis(ms)),
+            }
+        }
+
+        match end {
+            TurnEnd::Completed => self.emit(
+                turn_id,
+                EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: turn_id.to_string(),
+                    last_agent_message,
+                }),
+            ),
+            // Upstream ends a failed turn with an empty completion.
+            TurnEnd::Disconnected => self.emit(
+                turn_id,
+                EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: turn_id.to_string(),
+                    last_agent_message: None,
+                }),
+            ),
+            TurnEnd::Interrupted => self.emit(
+                turn_id,
+                EventMsg::TurnAborted(TurnAbortedEvent {
+                    turn_id: Some(turn_id.to_string()),
+                    reason: TurnAbortReason::Interrupted,
+                }),
+            ),
+            TurnEnd::ClientGone => Ok(()),
+        }
+    }
+
+    fn emit_exec(
+        &mut self,
+        turn_id: &str,
+        command: &str,
+        output: &str,
+        exit_code: i32,
+    ) -> anyhow::Result<()> {
+        let call_id = self.next_call_id();
+        let argv = vec!["bash".to_string(), "-lc".to_string(), command.to_string()];
+        let cwd = self.thread_cwd()?;
+        let parsed_cmd = vec![ParsedCommand::Unknown {
+            cmd: command.to_string(),
+        }];
+        self.emit(
+            turn_id,
+            EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                call_id: call_id.clone(),
+                process_id: None,
+                turn_id: turn_id.to_string(),
+                command: argv.clone(),
+                cwd: cwd.clone(),
+                parsed_cmd: parsed_cmd.clone(),
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+            }),
+        )?;
+        self.emit(
+            turn_id,
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                call_id,
+                process_id: None,
+                turn_id: turn_id.to_string(),
+                command: argv,
+                cwd,
+                parsed_cmd,
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+                stdout: output.to_string(),
+                stderr: String::new(),
+                aggregated_output: output.to_string(),
+                exit_code,
+                duration: Duration::ZERO,
+                formatted_output: output.to_string(),
+            }),
+        )
+    }
+
+    fn write_file(&mut self, turn_id: &str, path: &Path, content: &str) -> anyhow::Result<()> {
+        let path = self.thread_cwd()?.join(path);
+        let (change, marker) = match std::fs::read_to_string(&path) {
+            Ok(previous) => (
+                FileChange::Update {
+                    unified_diff: whole_file_diff(&previous, content),
+                    move_path: None,
+                },
+                'M',
+            ),
+            Err(_) => (
+                FileChange::Add {
+                    content: content.to_string(),
+                },
+                'A',
+            ),
+        };
+        let changes = HashMap::from([(path.clone(), change)]);
+        let call_id = self.next_call_id();
+        self.emit(
+            turn_id,
+            EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+                call_id: call_id.clone(),
+                turn_id: turn_id.to_string(),
+                auto_approved: true,
+                changes: changes.clone(),
+            }),
+        )?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create {}", parent.display()))?;
+        }
+        std::fs::write(&path, content).with_context(|| format!("write {}", path.display()))?;
+
+        self.emit(
+            turn_id,
+            EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+                call_id,
+                turn_id: turn_id.to_string(),
+                stdout: format!(
+                    "Success. Updated the following files:\n{marker} {}\n",
+                    path.display()
+                ),
+                stderr: String::new(),
+                success: true,
+                changes,
+            }),
+        )
+    }
+
+    /// Ask the client to approve `command`; `None` when the client went away before answering.
+    fn request_command_approval(
+        &mut self,
+        turn_id: &str,
+        command: &str,
+    ) -> anyhow::Result<Option<bool>> {
+        let request_id = self.next_server_request_id;
+        self.next_server_request_id += 1;
+        let item_id = self.next_call_id();
+        let (thread_id, cwd) = match &self.thread {
+            Some(thread) => (thread.id.clone(), thread.cwd.clone()),
+            None => anyhow::bail!("approval requested without a thread"),
+        };
+        self.send(&json!({
+            "id": request_id,
+            "method": "item/commandExecution/requestApproval",
+            "params": {
+                "threadId": thread_id,
+                "turnId": turn_id,
+                "itemId": item_id,
+                "command": command,
+                "cwd": cwd,
+                "reason": null,
+            },
+        }))?;
+
+        while let Some(message) = self.read_message()? {
+            if message.get("method").is_some() {
+                self.reject_while_busy(&message)?;
+                continue;
+            }
+            if message.get("id") != Some(&json!(request_id)) {
+                continue;
+            }
+            let decision = message
+                .get("result")
+                .and_then(|result| result.get("decision"))
+                .and_then(Value::as_str);
+            return Ok(Some(matches!(
+                decision,
+                Some("accept" | "acceptForSession")
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Wait for `turn/interrupt`; `false` when the client went away first.
+    fn wait_for_interrupt(&mut self) -> anyhow::Result<bool> {
+        while let Some(message) = self.read_message()? {
+            if message.get("method").and_then(Value::as_str) == Some("turn/interrupt")
+                && let Some(id) = message.get("id")
+            {
+                self.respond(id.clone(), json!({}))?;
+                return Ok(true);
+            }
+            self.reject_while_busy(&message)?;
+        }
+        Ok(false)
+    }
+
+    /// Answer a request that arrives while a turn step waits for something else.
+    fn reject_while_busy(&mut self, message: &Value) -> anyhow::Result<()> {
+        let (Some(id), Some(method)) = (message.get("id"), message.get("method")) else {
+            return Ok(());
+        };
+        let message = format!("{method} is not supported while a turn is running");
+        self.respond_error(id.clone(), INVALID_REQUEST_ERROR_CODE, &message)
+    }
+
+    fn finish_project(&mut self) -> anyhow::Result<()> {
+        let progress_file = self
+            .thread
+            .as_ref()
+            .and_then(|thread| thread.progress_file.clone())
+            .context("finish_project: developer instructions name no progress file")?;
+        let contents = std::fs::read_to_string(&progress_file)
+            .with_context(|| format!("read {}", progress_file.display()))?;
+        let updated = contents.replacen("finite_incantatem: false", "finite_incantatem: true", 1);
+        std::fs::write(&progress_file, updated)
+            .with_context(|| format!("write {}", progress_file.display()))
+    }
+
+    fn thread_cwd(&self) -> anyhow::Result<PathBuf> {
+        self.thread
+            .as_ref()
+            .map(|thread| thread.cwd.clone())
+            .context("no thread")
+    }
+
+    fn next_call_id(&mut self) -> String {
+        let call_id = format!("call-{}", self.next_call);
+        self.next_call += 1;
+        call_id
+    }
+
+    /// Send `msg` as a `codex/event/<type>` notification and record it in the rollout.
+    fn emit(&mut self, event_id: &str, msg: EventMsg) -> anyhow::Result<()> {
+        let event = Event {
+            id: event_id.to_string(),
+            msg,
+        };
+        let mut params = serde_json::to_value(&event).context("serialize event")?;
+        let event_type = params
+            .get("msg")
+            .and_then(|msg| msg.get("type"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        if let Some(thread) = &self.thread {
+            append_rollout_line(
+                &thread.rollout_path,
+                &json!({ "type": "event_msg", "payload": params["msg"] }),
+            )?;
+            params["conversationId"] = json!(thread.id);
+        }
+        self.send(&json!({ "method": format!("codex/event/{event_type}"), "params": params }))
+    }
+
+    fn respond(&mut self, id: Value, result: Value) -> anyhow::Result<()> {
+        self.send(&json!({ "id": id, "result": result }))
+    }
+
+    fn respond_error(&mut self, id: Value, code: i64, message: &str) -> anyhow::Result<()> {
+        self.send(&json!({ "id": id, "error": { "code": code, "message": message } }))
+    }
+
+    fn send(&mut self, message: &Value) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.output, message).context("write message")?;
+        self.output.write_all(b"\n").context("write message")?;
+        self.output.flush().context("flush stdout")
+    }
+
+    /// Next client message; `None` once stdin is closed.
+    fn read_message(&mut self) -> anyhow::Result<Option<Value>> {
+        loop {
+            let mut line = String::new();
+            if self.input.read_line(&mut line).context("read stdin")? == 0 {
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(scenario) = &self.scenario {
+                scenario.log_client_message(&line)?;
+            }
+            let message = serde_json::from_str(&line)
+                .with_context(|| format!("parse client message: {}", line.trim_end()))?;
+            return Ok(Some(message));
+        }
+    }
+}
+
+fn progress_file_from_instructions(instructions: &str) -> Option<PathBuf> {
+    instructions.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(PROGRESS_FILE_PREFIX)?;
+        let (path, _) = rest.split_once('`')?;
+        Some(PathBuf::from(path))
+    })
+}
+
+fn whole_file_diff(previous: &str, content: &str) -> String {
+    let mut diff = format!(
+        "@@ -1,{} +1,{} @@\n",
+        previous.lines().count(),
+        content.lines().count()
+    );
+    for line in previous.lines() {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in content.lines() {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    diff
+}
+
+fn append_rollout_line(rollout_path: &Path, line: &Value) -> anyhow::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(rollout_path)
+        .with_context(|| format!("open rollout {}", rollout_path.display()))?;
+    writeln!(file, "{line}").with_context(|| format!("write rollout {}", rollout_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn run_session(input: &str, scenario: Option<ScenarioFile>, codex_home: &Path) -> Vec<Value> {
+        let mut output = Vec::new();
+        FakeAppServer::new(
+            input.as_bytes(),
+            &mut output,
+            scenario,
+            codex_home.to_path_buf(),
+        )
+        .run()
+        .expect("run fake app-server");
+        String::from_utf8(output)
+            .expect("utf-8 output")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect()
+    }
+
+    #[test]
+    fn default_turn_finishes_the_project_and_records_a_rollout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let progress_file = dir
+            .path()
+            .join(".codexpotter/projects/2026/10/16/1/MAIN.md");
+        std::fs::create_dir_all(progress_file.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&progress_file, "---\nfinite_incantatem: false\n---\n").expect("write");
+
+        let thread_start = json!({
+            "id": 2,
+            "method": "thread/start",
+            "params": {
+                "cwd": dir.path(),
+                "developerInstructions":
+                    "- Progress file: `.codexpotter/projects/2026/10/16/1/MAIN.md`\n",
+            },
+        });
+        let input = [
+            json!({ "id": 1, "method": "initialize", "params": {} }),
+            json!({ "method": "initialized" }),
+            thread_start,
+            json!({ "id": 3, "method": "turn/start", "params": { "input": [] } }),
+        ]
+        .iter()
+        .map(|message| format!("{message}\n"))
+        .collect::<String>();
+
+        let output = run_session(&input, None, &dir.path().join("codex-home"));
+        let methods = output
+            .iter()
+            .map(
+                |message| match message.get("method").and_then(Value::as_str) {
+                    Some(method) => method.to_string(),
+                    None => format!("response {}", message["id"]),
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            vec![
+                "response 1",
+                "response 2",
+                "response 3",
+                "codex/event/task_started",
+                "codex/event/agent_message",
+                "codex/event/task_complete",
+            ]
+        );
+        assert_eq!(
+            output[0]["result"]["userAgent"],
+            json!(format!("codex_potter_fake_backend/{FAKE_CODEX_VERSION}"))
+        );
+        assert_eq!(
+            output[5]["params"]["msg"]["last_agent_message"],
+            json!("Done.")
+        );
+        assert_eq!(
+            std::fs::read_to_string(&progress_file).expect("read progress file"),
+            "---\nfinite_incantatem: true\n---\n"
+        );
+
+        let rollout_path = PathBuf::from(
+            output[1]["result"]["thread"]["path"]
+                .as_str()
+                .expect("rollout path"),
+        );
+        let rollout_types = std::fs::read_to_string(rollout_path)
+            .expect("read rollout")
+            .lines()
+            .map(|line| {
+                let line: Value = serde_json::from_str(line).expect("rollout line");
+                match line["type"].as_str() {
+                    Some("event_msg") => format!("event_msg {}", line["payload"]["type"]),
+                    other => other.unwrap_or_default().to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rollout_types,
+            vec![
+                "session_meta",
+                "turn_context",
+                "event_msg \"task_started\"",
+                "event_msg \"agent_message\"",
+                "event_msg \"task_complete\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn write_file_steps_write_relative_to_the_thread_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scenario_path = dir.path().join("scenario.json");
+        std::fs::write(
+            &scenario_path,
+            r#"{ "turns": [{ "steps": [
+                { "type": "write_file", "path": "src/hello.txt", "content": "hello\n" },
+                { "type": "write_file", "path": "src/hello.txt", "content": "bye\n" }
+            ] }] }"#,
+        )
+        .expect("write scenario");
+        let workdir = dir.path().join("work");
+        std::fs::create_dir_all(&workdir).expect("mkdir");
+
+        let input = [
+            json!({ "id": 1, "method": "thread/start", "params": { "cwd": workdir } }),
+            json!({ "id": 2, "method": "turn/start", "params": { "input": [] } }),
+        ]
+        .iter()
+        .map(|message| format!("{message}\n"))
+        .collect::<String>();
+        let output = run_session(
+            &input,
+            Some(ScenarioFile::load(&scenario_path).expect("load scenario")),
+            &dir.path().join("codex-home"),
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(workdir.join("src/hello.txt")).expect("read written file"),
+            "bye\n"
+        );
+        let change_types = output
+            .iter()
+            .filter(|message| message["method"] == json!("codex/event/patch_apply_begin"))
+            .map(|message| {
+                let changes = message["params"]["msg"]["changes"]
+                    .as_object()
+                    .expect("changes");
+                changes
+                    .values()
+                    .map(|change| change["type"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            change_types,
+            vec![vec![json!("add")], vec![json!("update")]]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("scenario.requests.jsonl"))
+                .expect("read request log")
+                .lines()
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn rounds_on_a_reused_process_follow_the_scenario_cursor() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scenario_path = dir.path().join("scenario.json");
+        std::fs::write(
+            &scenario_path,
+            r#"{ "turns": [
+                { "steps": [{ "type": "agent_message", "text": "first" }] },
+                { "steps": [{ "type": "agent_message", "text": "second" }] }
+            ] }"#,
+        )
+        .expect("write scenario");
+
+        // `--reuse-app-server` starts every round as a new thread on the same process.
+        let input = [
+            json!({ "id": 1, "method": "thread/start", "params": { "cwd": dir.path() } }),
+            json!({ "id": 2, "method": "turn/start", "params": { "input": [] } }),
+            json!({ "id": 3, "method": "thread/start", "params": { "cwd": dir.path() } }),
+            json!({ "id": 4, "method": "turn/start", "params": { "input": [] } }),
+        ]
+        .iter()
+        .map(|message| format!("{message}\n"))
+        .collect::<String>();
+        let output = run_session(
+            &input,
+            Some(ScenarioFile::load(&scenario_path).expect("load scenario")),
+            &dir.path().join("codex-home"),
+        );
+
+        let messages = output
+            .iter()
+            .filter(|message| message["method"] == json!("codex/event/agent_message"))
+            .map(|message| message["params"]["msg"]["message"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec![json!("first"), json!("second")]);
+    }
+}
@@ -0,0 +1,26 @@
+//! Command-line behaviour of the fake app-server binary.
+//!
+//! Besides checking the arguments codex-potter relies on, this target makes `cargo test
+//! --workspace` build the binary for the end-to-end tests in `cli/tests`.
+
+use std::process::Command;
+
+use pretty_assertions::assert_eq;
+
+#[test]
+fn version_is_reported_like_codex_and_other_commands_are_refused() {
+    let bin = env!("CARGO_BIN_EXE_codex-potter-fake-backend");
+
+    let version = Command::new(bin)
+        .arg("--version")
+        .output()
+        .expect("run --version");
+    assert!(version.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&version.stdout),
+        "codex-cli 0.104.0\n"
+    );
+
+    let exec = Command::new(bin).arg("exec").output().expect("run exec");
+    assert!(!exec.status.success());
+}
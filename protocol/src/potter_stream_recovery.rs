@@ -48,6 +48,17 @@ pub fn is_retryable_stream_error(event: &ErrorEvent) -> bool {
     }
 }
 
+/// Returns `true` when `event` reports an exhausted usage limit.
+///
+/// Retrying only helps once the limit resets, so these errors are not
+/// [retryable](is_retryable_stream_error); callers may instead wait for the reset.
+pub fn is_usage_limit_error(event: &ErrorEvent) -> bool {
+    matches!(
+        event.codex_error_info,
+        Some(CodexErrorInfo::UsageLimitExceeded)
+    )
+}
+
 /// Returns `true` when `msg` counts as "activity" for CodexPotter stream recovery.
 ///
 /// Activity is defined by the workflow spec as receiving any valid:
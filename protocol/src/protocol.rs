@@ -17,7 +17,9 @@ use crate::num_format::format_with_separators;
 use crate::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
+use crate::request_user_input::RequestUserInputAnswer;
 use crate::request_user_input::RequestUserInputEvent;
+use crate::request_user_input::RequestUserInputQuestion;
 use crate::user_input::UserInput;
 use serde::Deserialize;
 use serde::Serialize;
@@ -43,6 +45,23 @@ pub enum Op {
 
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
+
+    /// Answer an [`EventMsg::PotterApprovalRequest`] (`codex-potter` only).
+    PotterApprovalResponse {
+        approval_id: String,
+        choice: PotterApprovalChoice,
+        /// Optional note for the model when the request is denied.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        feedback: Option<String>,
+    },
+
+    /// Answer an [`EventMsg::PotterUserInputRequest`] (`codex-potter` only).
+    PotterUserInputResponse {
+        request_id: String,
+        /// Answers keyed by question id; `None` declines the request.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        answers: Option<HashMap<String, RequestUserInputAnswer>>,
+    },
 }
 
 /// Event Queue Entry - events from agent
@@ -135,6 +154,49 @@ pub enum EventMsg {
     PotterRoundStarted {
         current: u32,
         total: u32,
+        /// Role of this round in the configured round schedule.
+        #[serde(default, skip_serializing_if = "PotterRoundKind::is_implement")]
+        kind: PotterRoundKind,
+    },
+
+    /// `codex-potter` project budget status (outside of the app-server protocol).
+    ///
+    /// Emitted after `PotterRoundStarted` and whenever token usage changes while `--max-tokens`
+    /// or `--max-duration` is configured, so the UI can show the remaining budget next to the
+    /// round prefix.
+    PotterProjectBudget {
+        /// Tokens left before `--max-tokens` is reached.
+        tokens_remaining: Option<u64>,
+        /// Wall time left before `--max-duration` is reached.
+        time_remaining: Option<Duration>,
+    },
+
+    /// `codex-potter` pauses before sending the round prompt because an upstream rate-limit
+    /// window is at or above `--rate-limit-threshold` (outside of the app-server protocol).
+    ///
+    /// The round continues on its own once the window resets.
+    PotterRateLimitWait {
+        /// Used percentage (0-100) of the window the round waits for.
+        used_percent: f64,
+        /// Rolling window duration, in minutes.
+        window_minutes: Option<i64>,
+        /// Time until the window resets.
+        resets_in: Duration,
+    },
+
+    /// The round runs on an upstream `codex app-server` process kept alive from a previous round
+    /// (`--reuse-app-server`), emitted right after `SessionConfigured`.
+    PotterAppServerReused {
+        /// Spawn + `initialize` time the round did not have to spend.
+        startup_saved: Duration,
+    },
+
+    /// Last lines the upstream `codex app-server` wrote to stderr, emitted right before the error
+    /// that ends the round (outside of the app-server protocol).
+    PotterAppServerStderr {
+        tail: Vec<String>,
+        /// Round log the complete stderr is tee'd to (`rounds/N/app-server.stderr.log`).
+        log_path: Option<PathBuf>,
     },
 
     /// `codex-potter` round finished (outside of the app-server protocol).
@@ -160,6 +222,10 @@ pub enum EventMsg {
         max_attempts: u32,
         /// The retryable error message that triggered this update.
         error_message: String,
+        /// How long CodexPotter waits before sending `continue`.
+        backoff: Duration,
+        /// Why CodexPotter waits that long.
+        strategy: PotterStreamRecoveryStrategy,
     },
 
     /// `codex-potter` stream recovery finished successfully (activity observed).
@@ -178,6 +244,80 @@ pub enum EventMsg {
         max_attempts: u32,
     },
 
+    /// `codex-potter` post-round verification gate result (outside of the app-server protocol).
+    ///
+    /// Emitted after a round completes when a verification command is configured, before the
+    /// round's `PotterRoundFinished` marker. A project can only succeed when the gate passes.
+    PotterRoundVerification {
+        /// Shell command that was executed.
+        command: String,
+        /// Whether the command exited successfully.
+        passed: bool,
+        /// Exit code, when the process exited normally.
+        exit_code: Option<i32>,
+        /// Tail of the combined stdout/stderr output.
+        output: String,
+    },
+
+    /// `codex-potter` approval policy asks the user to decide an upstream request (outside of the
+    /// app-server protocol).
+    ///
+    /// Synthesized by the Potter app-server client from an `approval/request` server request. The
+    /// round stays blocked until it is answered with [`Op::PotterApprovalResponse`].
+    PotterApprovalRequest {
+        /// Identifier to echo back in the answer.
+        approval_id: String,
+        /// What the agent asked to do.
+        kind: PotterApprovalKind,
+        /// Command line or affected paths, for display.
+        summary: String,
+        /// Why the policy asks (matched rule, or the policy default).
+        reason: String,
+    },
+
+    /// `codex-potter` approval policy decision (outside of the app-server protocol).
+    ///
+    /// Emitted whenever the bridge answers an upstream approval request, whether a rule or the user
+    /// decided it.
+    PotterApprovalDecision {
+        /// What the agent asked to do.
+        kind: PotterApprovalKind,
+        /// Command line or affected paths, for display.
+        summary: String,
+        /// Whether the request was approved.
+        approved: bool,
+        /// Rule reason, a description of the rule / default that decided, or the user's answer.
+        reason: String,
+    },
+
+    /// The agent (`request_user_input`) or an MCP server (elicitation) asks the user questions
+    /// (outside of the app-server protocol).
+    ///
+    /// Synthesized by the Potter app-server client from a `userInput/request` server request. The
+    /// round stays blocked until it is answered with [`Op::PotterUserInputResponse`].
+    PotterUserInputRequest {
+        /// Identifier to echo back in the answer.
+        request_id: String,
+        /// MCP server asking, for elicitations; `None` when the agent asks.
+        server_name: Option<String>,
+        /// Elicitation message shown above the questions.
+        message: Option<String>,
+        questions: Vec<RequestUserInputQuestion>,
+    },
+
+    /// `codex-potter` answered a user input request (outside of the app-server protocol).
+    ///
+    /// Emitted by the backend bridge once the answer is sent upstream, and recorded in
+    /// `potter-rollout.jsonl` so resume replays it.
+    PotterUserInputAnswered {
+        /// MCP server that asked, for elicitations; `None` when the agent asked.
+        server_name: Option<String>,
+        /// Answer of each question, in question order (empty when declined).
+        answers: Vec<PotterUserInputAnswer>,
+        /// Whether the request was declined instead of answered.
+        declined: bool,
+    },
+
     /// `codex-potter` project finished successfully (outside of the app-server protocol).
     PotterProjectSucceeded {
         /// Total number of rounds rendered for this CodexPotter project.
@@ -192,6 +332,15 @@ pub enum EventMsg {
         git_commit_end: String,
     },
 
+    /// Structured final report of a `codex-potter` project (outside of the app-server protocol).
+    ///
+    /// Emitted right before a successful [`EventMsg::PotterProjectCompleted`] when the client asked
+    /// for structured output (`exec --output-schema`).
+    PotterProjectReport {
+        /// Final message of the report turn, validated against the requested JSON Schema.
+        output: Value,
+    },
+
     /// `codex-potter` project completed (outside of the app-server protocol).
     ///
     /// This marker is emitted exactly once at the end of a project run so clients can exit a
@@ -213,6 +362,21 @@ pub enum EventMsg {
         user_prompt_file: PathBuf,
     },
 
+    /// `codex-potter` project finished its planning round and is waiting for the plan to be
+    /// approved (outside of the app-server protocol).
+    ///
+    /// Only emitted with `--review-plan`, once the progress file moves from `status: initial` to
+    /// `status: open`. The project stays paused like [`EventMsg::PotterProjectInterrupted`] until
+    /// the caller resolves it: continuing approves the plan, stopping aborts the project.
+    PotterProjectAwaitingPlanApproval {
+        /// Unique identifier for the active project within the server process.
+        project_id: String,
+        /// User prompt file for this CodexPotter project (e.g. `.codexpotter/projects/.../MAIN.md`).
+        user_prompt_file: PathBuf,
+        /// Entries of the progress file's `## Todo` section.
+        todo: Vec<String>,
+    },
+
     WebSearchEnd(WebSearchEndEvent),
 
     /// Notification that the server is about to execute a command.
@@ -276,6 +440,76 @@ pub enum EventMsg {
     Unknown,
 }
 
+/// How `codex-potter` schedules a stream recovery retry.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PotterStreamRecoveryStrategy {
+    /// Exponential backoff from `base` up to `max`, randomized by up to `jitter_percent`.
+    Backoff {
+        base: Duration,
+        max: Duration,
+        jitter_percent: u32,
+    },
+    /// Wait until the usage limit reported by the last rate-limit snapshot resets.
+    UsageLimitReset,
+}
+
+/// Role of a `codex-potter` round within the configured round schedule.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterRoundKind {
+    /// Works through the progress file with the regular developer prompt.
+    #[default]
+    Implement,
+    /// Reviews the changes so far in a read-only sandbox and reports findings.
+    Review,
+}
+
+impl PotterRoundKind {
+    pub fn is_implement(&self) -> bool {
+        matches!(self, Self::Implement)
+    }
+
+    /// Status header prefix for a round, e.g. `Round 2/10` or `Round 3/10 (review)`.
+    pub fn round_prefix(self, current: u32, total: u32) -> String {
+        match self {
+            Self::Implement => format!("Round {current}/{total}"),
+            Self::Review => format!("Round {current}/{total} (review)"),
+        }
+    }
+}
+
+/// Kind of upstream request decided by the `codex-potter` approval policy.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterApprovalKind {
+    /// Running a shell command.
+    Command,
+    /// Writing files (patches).
+    FileChange,
+}
+
+/// User answer to an [`EventMsg::PotterApprovalRequest`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterApprovalChoice {
+    /// Approve this request only.
+    Approve,
+    /// Approve this request and identical ones for the rest of the round.
+    ApproveForSession,
+    /// Decline the request; the model is told and continues.
+    Deny,
+}
+
+/// One answered question of an [`EventMsg::PotterUserInputAnswered`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PotterUserInputAnswer {
+    pub header: String,
+    pub question: String,
+    /// Selected option labels and/or free text; masked for secret questions.
+    pub answers: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum PotterRoundOutcome {
@@ -288,6 +522,12 @@ pub enum PotterRoundOutcome {
     },
     Fatal {
         message: String,
+        /// Last lines of the upstream app-server's stderr before the round died.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        stderr_tail: Vec<String>,
+        /// Round log with the complete upstream app-server stderr.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stderr_log: Option<PathBuf>,
     },
 }
 
@@ -295,9 +535,25 @@ pub enum PotterRoundOutcome {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum PotterProjectOutcome {
     Succeeded,
-    /// The project was stopped by the user after an interrupt (Esc).
+    /// The project was stopped by the user after an interrupt (Esc) or when rejecting the plan
+    /// (`--review-plan`).
     Interrupted,
     BudgetExhausted,
+    /// The project was stopped after `rounds` consecutive rounds left the git tree unchanged
+    /// (`--stall-rounds`).
+    Stalled {
+        rounds: u32,
+    },
+    /// The project was stopped after using `tokens_used` tokens (`--max-tokens`).
+    TokenBudgetExhausted {
+        tokens_used: u64,
+        max_tokens: u64,
+    },
+    /// The project was stopped after running for `elapsed_secs` seconds (`--max-duration`).
+    TimeBudgetExhausted {
+        elapsed_secs: u64,
+        max_duration_secs: u64,
+    },
     TaskFailed {
         message: String,
     },
@@ -2,6 +2,7 @@
 
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterApprovalChoice;
 
 use crate::history_cell::HistoryCell;
 use crate::verbosity::Verbosity;
@@ -55,4 +56,31 @@ pub enum AppEvent {
     VerbositySelected {
         verbosity: Verbosity,
     },
+
+    /// Answer an approval request shown in the approval popup.
+    PotterApprovalAnswered {
+        approval_id: String,
+        choice: PotterApprovalChoice,
+    },
+
+    /// Deny an approval request with feedback typed in the composer.
+    PotterApprovalFeedbackRequested {
+        approval_id: String,
+    },
+
+    /// Answer the current question of a user input request with a popup option.
+    PotterUserInputOptionChosen {
+        request_id: String,
+        answer: String,
+    },
+
+    /// Answer the current question of a user input request with text typed in the composer.
+    PotterUserInputTextRequested {
+        request_id: String,
+    },
+
+    /// Decline a user input request.
+    PotterUserInputDeclined {
+        request_id: String,
+    },
 }
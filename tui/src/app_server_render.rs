@@ -13,8 +13,10 @@ use codex_protocol::models::MessagePhase;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterApprovalChoice;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TurnStartedEvent;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
 use codex_protocol::user_input::UserInput;
 use ratatui::prelude::Widget;
 use ratatui::text::Line;
@@ -41,6 +43,7 @@ use crate::external_editor_integration;
 use crate::file_search::FileSearchManager;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
+use crate::history_cell_potter::PotterRateLimitWaitCell;
 use crate::history_cell_potter::PotterStreamRecoveryRetryCell;
 use crate::history_cell_potter::PotterStreamRecoveryUnrecoverableCell;
 use crate::render::line_utils::dim_lines;
@@ -350,6 +353,8 @@ pub struct RoundUiState<'a> {
 pub struct ProjectRenderContext {
     pub project_started_at: Instant,
     pub prompt_footer: PromptFooterContext,
+    /// Status strip shown while several projects run concurrently (`--parallel`).
+    pub project_strip: Option<crate::ProjectStrip>,
 }
 
 fn text_user_input_op(text: String) -> Op {
@@ -380,6 +385,7 @@ pub async fn run_round_with_tui_options_and_queue(
     let ProjectRenderContext {
         project_started_at,
         prompt_footer,
+        project_strip,
     } = context;
     let RoundBackendChannels {
         codex_op_tx,
@@ -411,6 +417,7 @@ pub async fn run_round_with_tui_options_and_queue(
 
     let mut bottom_pane = new_default_bottom_pane(tui, app_event_tx.clone(), true);
     bottom_pane.set_prompt_footer_context(prompt_footer);
+    bottom_pane.set_project_strip(project_strip);
     bottom_pane.set_project_started_at(Some(project_started_at));
     bottom_pane.set_status_header_prefix(options.status_header_prefix.clone());
     if let Some(draft) = state.composer_draft.take() {
@@ -671,11 +678,61 @@ impl AppServerEventProcessor {
                     crate::history_cell_potter::new_potter_project_hint(user_prompt_file),
                 ));
             }
-            EventMsg::PotterRoundStarted { current, total } => {
+            EventMsg::PotterRoundStarted {
+                current,
+                total,
+                kind,
+            } => {
                 self.flush_pending_live_activity_cells();
                 self.needs_final_message_separator = true;
                 self.emit_history_cell(Box::new(
-                    crate::history_cell_potter::new_potter_round_started(current, total),
+                    crate::history_cell_potter::new_potter_round_started(current, total, kind),
+                ));
+            }
+            EventMsg::PotterRoundVerification {
+                command,
+                passed,
+                exit_code,
+                output,
+            } => {
+                self.flush_pending_live_activity_cells();
+                self.emit_history_cell(Box::new(
+                    crate::history_cell_potter::PotterRoundVerificationCell {
+                        command,
+                        passed,
+                        exit_code,
+                        output,
+                    },
+                ));
+            }
+            EventMsg::PotterApprovalDecision {
+                kind,
+                summary,
+                approved,
+                reason,
+            } => {
+                self.flush_pending_live_activity_cells();
+                self.emit_history_cell(Box::new(
+                    crate::history_cell_potter::PotterApprovalDecisionCell {
+                        kind,
+                        summary,
+                        approved,
+                        reason,
+                    },
+                ));
+            }
+            EventMsg::PotterUserInputAnswered {
+                server_name,
+                answers,
+                declined,
+            } => {
+                self.flush_pending_live_activity_cells();
+                self.emit_history_cell(Box::new(
+                    crate::history_cell_potter::PotterUserInputAnsweredCell {
+                        server_name,
+                        answers,
+                        declined,
+                    },
                 ));
             }
             EventMsg::PotterProjectSucceeded {
@@ -694,8 +751,22 @@ impl AppServerEventProcessor {
                     git_commit_end,
                 });
             }
-            EventMsg::PotterRoundFinished { .. } => {
+            EventMsg::PotterRoundFinished { outcome } => {
                 self.flush_pending_live_activity_cells();
+                if let codex_protocol::protocol::PotterRoundOutcome::Fatal {
+                    stderr_tail,
+                    stderr_log,
+                    ..
+                } = outcome
+                    && (!stderr_tail.is_empty() || stderr_log.is_some())
+                {
+                    self.emit_history_cell(Box::new(
+                        crate::history_cell_potter::PotterAppServerStderrCell {
+                            tail: stderr_tail,
+                            log_path: stderr_log,
+                        },
+                    ));
+                }
                 if let Some(done) = self.pending_potter_project_succeeded.take() {
                     self.emit_history_cell(Box::new(
                         crate::history_cell_potter::new_potter_project_succeeded(
@@ -1161,6 +1232,16 @@ struct RenderAppState {
     unified_exec_wait: Option<UnifiedExecWaitStatus>,
     stream_error_status_header: Option<String>,
     potter_stream_recovery_retry_cell: Option<PotterStreamRecoveryRetryCell>,
+    /// Countdown shown while the round waits for a rate-limit window to reset.
+    potter_rate_limit_wait_cell: Option<PotterRateLimitWaitCell>,
+    /// Approval requests waiting for the user; the front one is shown in the approval popup.
+    approval_requests: VecDeque<crate::approval_request_picker::PendingApprovalRequest>,
+    /// Approval request whose "Deny with feedback" text the composer is collecting.
+    approval_feedback_for: Option<String>,
+    /// User input requests waiting for answers; the front one is shown in the question popup.
+    user_input_requests: VecDeque<crate::user_input_request_picker::PendingUserInputRequest>,
+    /// User input request whose current question the composer is answering.
+    user_input_text_for: Option<String>,
     commit_anim_running: Arc<AtomicBool>,
     has_emitted_history_lines: bool,
     exit_after_next_draw: bool,
@@ -1198,6 +1279,11 @@ impl RenderAppState {
             unified_exec_wait: None,
             stream_error_status_header: None,
             potter_stream_recovery_retry_cell: None,
+            potter_rate_limit_wait_cell: None,
+            approval_requests: VecDeque::new(),
+            approval_feedback_for: None,
+            user_input_requests: VecDeque::new(),
+            user_input_text_for: None,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             has_emitted_history_lines: false,
             exit_after_next_draw: false,
@@ -1289,6 +1375,11 @@ impl RenderAppState {
             transient_lines.extend(cell.display_lines(width));
         }
 
+        if let Some(cell) = self.potter_rate_limit_wait_cell.as_ref() {
+            transient_lines.push(Line::from(""));
+            transient_lines.extend(cell.display_lines(width));
+        }
+
         // When the bottom pane shrinks (e.g., after a turn completes and the status indicator is
         // removed), the prompt background can end up directly adjacent to the last transcript
         // line. Keep a blank line between the transcript and the bottom pane for readability.
@@ -1557,6 +1648,49 @@ impl RenderAppState {
             return;
         }
 
+        // Divergence (codex-potter): with `--parallel`, Ctrl+O asks the runner to focus the next
+        // project once the current round finishes.
+        if key_event.modifiers == crossterm::event::KeyModifiers::CONTROL
+            && matches!(key_event.code, crossterm::event::KeyCode::Char('o'))
+            && let Some(strip) = self
+                .bottom_pane
+                .project_strip()
+                .filter(|strip| strip.is_multi_project())
+        {
+            if !is_press {
+                return;
+            }
+            strip.request_focus_next();
+            frame_requester.schedule_frame();
+            return;
+        }
+
+        if matches!(key_event.code, crossterm::event::KeyCode::Esc)
+            && key_event.modifiers == crossterm::event::KeyModifiers::NONE
+            && !self.bottom_pane.composer().popup_active()
+            && let Some(approval_id) = self.approval_feedback_for.clone()
+        {
+            if !is_press {
+                return;
+            }
+            self.answer_approval_request(approval_id, PotterApprovalChoice::Deny, None);
+            frame_requester.schedule_frame();
+            return;
+        }
+
+        if matches!(key_event.code, crossterm::event::KeyCode::Esc)
+            && key_event.modifiers == crossterm::event::KeyModifiers::NONE
+            && !self.bottom_pane.composer().popup_active()
+            && let Some(request_id) = self.user_input_text_for.clone()
+        {
+            if !is_press {
+                return;
+            }
+            self.answer_user_input_request(request_id, None);
+            frame_requester.schedule_frame();
+            return;
+        }
+
         if matches!(key_event.code, crossterm::event::KeyCode::Esc)
             && key_event.modifiers == crossterm::event::KeyModifiers::NONE
             && self.codex_op_tx.is_some()
@@ -1583,6 +1717,24 @@ impl RenderAppState {
         }
 
         match result {
+            InputResult::Submitted(text) | InputResult::Queued(text)
+                if self.approval_feedback_for.is_some() =>
+            {
+                if let Some(approval_id) = self.approval_feedback_for.clone() {
+                    let feedback = Some(text.trim().to_string()).filter(|text| !text.is_empty());
+                    self.answer_approval_request(approval_id, PotterApprovalChoice::Deny, feedback);
+                }
+                frame_requester.schedule_frame();
+            }
+            InputResult::Submitted(text) | InputResult::Queued(text)
+                if self.user_input_text_for.is_some() =>
+            {
+                if let Some(request_id) = self.user_input_text_for.take() {
+                    self.bottom_pane.set_prompt_footer_override(None);
+                    self.record_user_input_answer(request_id, text.trim().to_string());
+                }
+                frame_requester.schedule_frame();
+            }
             InputResult::Submitted(text) | InputResult::Queued(text) => {
                 let history_text = self
                     .bottom_pane
@@ -1658,6 +1810,84 @@ impl RenderAppState {
         }
     }
 
+    /// Show the approval popup for the front request of the queue, if any.
+    fn show_next_approval_request(&mut self) {
+        if let Some(request) = self.approval_requests.front() {
+            let params = crate::approval_request_picker::build_approval_request_params(request);
+            self.bottom_pane.composer_mut().show_selection_view(params);
+        }
+    }
+
+    /// Send the user's answer for `approval_id` and move on to the next queued request.
+    fn answer_approval_request(
+        &mut self,
+        approval_id: String,
+        choice: PotterApprovalChoice,
+        feedback: Option<String>,
+    ) {
+        if self.approval_feedback_for.take().is_some() {
+            self.bottom_pane.set_prompt_footer_override(None);
+        }
+        self.approval_requests
+            .retain(|request| request.approval_id != approval_id);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PotterApprovalResponse {
+                approval_id,
+                choice,
+                feedback,
+            }));
+        self.show_next_approval_request();
+    }
+
+    /// Show the question popup for the front user input request, if any.
+    fn show_next_user_input_question(&mut self) {
+        if let Some(request) = self.user_input_requests.front() {
+            let params =
+                crate::user_input_request_picker::build_user_input_question_params(request);
+            self.bottom_pane.composer_mut().show_selection_view(params);
+        }
+    }
+
+    /// Record the answer to the current question of `request_id`; once every question is
+    /// answered, send the answers.
+    fn record_user_input_answer(&mut self, request_id: String, answer: String) {
+        let Some(request) = self
+            .user_input_requests
+            .iter_mut()
+            .find(|request| request.request_id == request_id)
+        else {
+            return;
+        };
+        if !request.is_complete() {
+            request.record_answer(answer);
+        }
+        if request.is_complete() {
+            let answers = request.clone().into_answers();
+            self.answer_user_input_request(request_id, Some(answers));
+        } else {
+            self.show_next_user_input_question();
+        }
+    }
+
+    /// Send the answers for `request_id` (`None` declines) and move on to the next queued request.
+    fn answer_user_input_request(
+        &mut self,
+        request_id: String,
+        answers: Option<HashMap<String, RequestUserInputAnswer>>,
+    ) {
+        if self.user_input_text_for.take().is_some() {
+            self.bottom_pane.set_prompt_footer_override(None);
+        }
+        self.user_input_requests
+            .retain(|request| request.request_id != request_id);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PotterUserInputResponse {
+                request_id,
+                answers,
+            }));
+        self.show_next_user_input_question();
+    }
+
     fn refresh_queued_user_messages(&mut self) {
         let messages: Vec<String> = self.queued_user_messages.iter().cloned().collect();
         self.bottom_pane.set_queued_user_messages(messages);
@@ -1769,6 +1999,33 @@ impl RenderAppState {
                 }
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::PotterApprovalAnswered {
+                approval_id,
+                choice,
+            } => {
+                self.answer_approval_request(approval_id, choice, None);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::PotterApprovalFeedbackRequested { approval_id } => {
+                self.approval_feedback_for = Some(approval_id);
+                self.bottom_pane
+                    .set_prompt_footer_override(Some(PromptFooterOverride::ApprovalFeedbackHint));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::PotterUserInputOptionChosen { request_id, answer } => {
+                self.record_user_input_answer(request_id, answer);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::PotterUserInputTextRequested { request_id } => {
+                self.user_input_text_for = Some(request_id);
+                self.bottom_pane
+                    .set_prompt_footer_override(Some(PromptFooterOverride::UserInputAnswerHint));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::PotterUserInputDeclined { request_id } => {
+                self.answer_user_input_request(request_id, None);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StartCommitAnimation => {
                 anyhow::ensure!(
                     self.codex_op_tx.is_some(),
@@ -1862,11 +2119,15 @@ impl RenderAppState {
                 attempt,
                 max_attempts,
                 error_message,
+                backoff,
+                strategy,
             } => {
                 self.potter_stream_recovery_retry_cell = Some(PotterStreamRecoveryRetryCell {
                     attempt: *attempt,
                     max_attempts: *max_attempts,
                     error_message: error_message.clone(),
+                    backoff: *backoff,
+                    strategy: *strategy,
                 });
 
                 self.processor.current_elapsed_secs = self
@@ -1882,6 +2143,62 @@ impl RenderAppState {
                 frame_requester.schedule_frame();
                 return Ok(());
             }
+            EventMsg::PotterRateLimitWait {
+                used_percent,
+                window_minutes,
+                resets_in,
+            } => {
+                self.potter_rate_limit_wait_cell = Some(PotterRateLimitWaitCell {
+                    used_percent: *used_percent,
+                    window_minutes: *window_minutes,
+                    resumes_at: Instant::now() + *resets_in,
+                });
+                self.bottom_pane
+                    .update_status_header(String::from("Waiting for rate limits to reset"));
+                frame_requester.schedule_frame();
+                return Ok(());
+            }
+            EventMsg::PotterApprovalRequest {
+                approval_id,
+                kind,
+                summary,
+                reason,
+            } => {
+                self.approval_requests.push_back(
+                    crate::approval_request_picker::PendingApprovalRequest {
+                        approval_id: approval_id.clone(),
+                        kind: *kind,
+                        summary: summary.clone(),
+                        reason: reason.clone(),
+                    },
+                );
+                if self.approval_requests.len() == 1 {
+                    self.show_next_approval_request();
+                }
+                frame_requester.schedule_frame();
+                return Ok(());
+            }
+            EventMsg::PotterUserInputRequest {
+                request_id,
+                server_name,
+                message,
+                questions,
+            } => {
+                self.user_input_requests.push_back(
+                    crate::user_input_request_picker::PendingUserInputRequest {
+                        request_id: request_id.clone(),
+                        server_name: server_name.clone(),
+                        message: message.clone(),
+                        questions: questions.clone(),
+                        answers: Vec::new(),
+                    },
+                );
+                if self.user_input_requests.len() == 1 {
+                    self.show_next_user_input_question();
+                }
+                frame_requester.schedule_frame();
+                return Ok(());
+            }
             EventMsg::PotterStreamRecoveryGaveUp {
                 error_message,
                 max_attempts,
@@ -1935,17 +2252,42 @@ impl RenderAppState {
             EventMsg::TurnComplete(_)
             | EventMsg::TurnAborted(_)
             | EventMsg::PotterRoundFinished { .. } => {
+                self.potter_rate_limit_wait_cell = None;
                 self.clear_unified_exec_state();
             }
             _ => {}
         }
 
         match &event.msg {
-            EventMsg::PotterRoundStarted { current, total } => {
+            EventMsg::PotterRoundStarted {
+                current,
+                total,
+                kind,
+            } => {
+                self.bottom_pane
+                    .set_status_header_prefix(Some(kind.round_prefix(*current, *total)));
+                self.bottom_pane.set_status_header_startup_saved(None);
+            }
+            EventMsg::PotterAppServerReused { startup_saved } => {
                 self.bottom_pane
-                    .set_status_header_prefix(Some(format!("Round {current}/{total}")));
+                    .set_status_header_startup_saved(Some(*startup_saved));
+                return Ok(());
+            }
+            EventMsg::PotterProjectBudget {
+                tokens_remaining,
+                time_remaining,
+            } => {
+                let now = Instant::now();
+                self.bottom_pane.set_status_header_budget(Some(
+                    crate::status_indicator_widget::StatusHeaderBudget {
+                        tokens_remaining: *tokens_remaining,
+                        deadline: time_remaining.and_then(|remaining| now.checked_add(remaining)),
+                    },
+                ));
+                return Ok(());
             }
             EventMsg::TurnStarted(_) => {
+                self.potter_rate_limit_wait_cell = None;
                 self.reasoning_status.reset();
                 self.unified_exec_wait = None;
                 self.bottom_pane
@@ -2020,7 +2362,7 @@ impl RenderAppState {
                     codex_protocol::protocol::PotterRoundOutcome::TaskFailed { message } => {
                         ExitReason::TaskFailed(message.clone())
                     }
-                    codex_protocol::protocol::PotterRoundOutcome::Fatal { message } => {
+                    codex_protocol::protocol::PotterRoundOutcome::Fatal { message, .. } => {
                         ExitReason::Fatal(message.clone())
                     }
                 };
@@ -2200,6 +2542,8 @@ mod tests {
     use codex_protocol::protocol::PatchApplyBeginEvent;
     use codex_protocol::protocol::PatchApplyEndEvent;
     use codex_protocol::protocol::PlanDeltaEvent;
+    use codex_protocol::protocol::PotterRoundKind;
+    use codex_protocol::protocol::PotterStreamRecoveryStrategy;
     use codex_protocol::protocol::SessionConfiguredEvent;
     use codex_protocol::protocol::StreamErrorEvent;
     use codex_protocol::protocol::TerminalInteractionEvent;
@@ -2424,6 +2768,12 @@ mod tests {
                     error_message:
                         "stream disconnected before completion: error sending request for url (...)"
                             .to_string(),
+                    backoff: std::time::Duration::ZERO,
+                    strategy: PotterStreamRecoveryStrategy::Backoff {
+                        base: std::time::Duration::from_secs(1),
+                        max: std::time::Duration::from_secs(300),
+                        jitter_percent: 0,
+                    },
                 },
             },
         )
@@ -2543,6 +2893,12 @@ mod tests {
                     error_message:
                         "stream disconnected before completion: error sending request for url (...)"
                             .to_string(),
+                    backoff: std::time::Duration::ZERO,
+                    strategy: PotterStreamRecoveryStrategy::Backoff {
+                        base: std::time::Duration::from_secs(1),
+                        max: std::time::Duration::from_secs(300),
+                        jitter_percent: 0,
+                    },
                 },
             },
         )
@@ -2564,6 +2920,12 @@ mod tests {
                     error_message:
                         "stream disconnected before completion: error sending request for url (...)"
                             .to_string(),
+                    backoff: std::time::Duration::ZERO,
+                    strategy: PotterStreamRecoveryStrategy::Backoff {
+                        base: std::time::Duration::from_secs(1),
+                        max: std::time::Duration::from_secs(300),
+                        jitter_percent: 0,
+                    },
                 },
             },
         )
@@ -3394,6 +3756,120 @@ mod tests {
         pretty_assertions::assert_eq!(events, vec![vec!["• Edited file.txt (+1 -1)".to_string()]]);
     }
 
+    #[test]
+    fn round_renderer_approval_requests_are_answered_in_order() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let width: u16 = 80;
+        let (tx_raw, mut rx_app) = unbounded_channel::<AppEvent>();
+        let app_event_tx = AppEventSender::new(tx_raw);
+        let processor = AppServerEventProcessor::new(app_event_tx.clone(), Verbosity::Simple);
+
+        let (op_tx, _op_rx) = unbounded_channel::<Op>();
+        let bottom_pane = BottomPane::new(BottomPaneParams {
+            frame_requester: crate::tui::FrameRequester::test_dummy(),
+            enhanced_keys_supported: false,
+            app_event_tx: app_event_tx.clone(),
+            animations_enabled: false,
+            placeholder_text: "Assign new task to CodexPotter".to_string(),
+            disable_paste_burst: false,
+        });
+        let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
+        let mut app = RenderAppState::new(
+            processor,
+            app_event_tx,
+            Some(op_tx),
+            bottom_pane,
+            crate::prompt_history_store::PromptHistoryStore::new(),
+            file_search,
+            VecDeque::new(),
+        );
+        app.bottom_pane.set_task_running(true);
+
+        for (approval_id, summary) in [("approval-1", "git push"), ("approval-2", "rm -rf target")]
+        {
+            app.handle_codex_event(
+                crate::tui::FrameRequester::test_dummy(),
+                Event {
+                    id: String::new(),
+                    msg: EventMsg::PotterApprovalRequest {
+                        approval_id: approval_id.to_string(),
+                        kind: codex_protocol::protocol::PotterApprovalKind::Command,
+                        summary: summary.to_string(),
+                        reason: "not covered by any approval rule".to_string(),
+                    },
+                },
+            )
+            .expect("handle approval request");
+        }
+        assert!(app.bottom_pane.composer().selection_popup_visible());
+
+        // Answers popup actions the way the app event loop does, and returns the sent ops.
+        let mut drain_answers = |app: &mut RenderAppState| {
+            let mut ops = Vec::new();
+            while let Ok(event) = rx_app.try_recv() {
+                match event {
+                    AppEvent::PotterApprovalAnswered {
+                        approval_id,
+                        choice,
+                    } => app.answer_approval_request(approval_id, choice, None),
+                    AppEvent::PotterApprovalFeedbackRequested { approval_id } => {
+                        app.approval_feedback_for = Some(approval_id);
+                    }
+                    AppEvent::CodexOp(op) => ops.push(op),
+                    _ => {}
+                }
+            }
+            ops
+        };
+
+        // Esc dismisses the popup, which denies the first request and shows the second one.
+        app.handle_key_event(
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            crate::tui::FrameRequester::test_dummy(),
+            width,
+        );
+        let mut ops = drain_answers(&mut app);
+        assert!(app.bottom_pane.composer().selection_popup_visible());
+
+        // "Deny with feedback" collects the next submission instead of queueing it.
+        app.handle_key_event(
+            KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE),
+            crate::tui::FrameRequester::test_dummy(),
+            width,
+        );
+        ops.extend(drain_answers(&mut app));
+        app.bottom_pane
+            .composer_mut()
+            .set_text_content("clean only the debug build".to_string());
+        app.handle_key_event(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            crate::tui::FrameRequester::test_dummy(),
+            width,
+        );
+        ops.extend(drain_answers(&mut app));
+
+        pretty_assertions::assert_eq!(
+            ops,
+            vec![
+                Op::PotterApprovalResponse {
+                    approval_id: "approval-1".to_string(),
+                    choice: PotterApprovalChoice::Deny,
+                    feedback: None,
+                },
+                Op::PotterApprovalResponse {
+                    approval_id: "approval-2".to_string(),
+                    choice: PotterApprovalChoice::Deny,
+                    feedback: Some("clean only the debug build".to_string()),
+                },
+            ]
+        );
+        assert!(app.approval_requests.is_empty());
+        assert!(app.queued_user_messages.is_empty());
+    }
+
     #[test]
     fn round_renderer_ctrl_c_flushes_buffered_minimal_agent_stream_before_exit() {
         use crossterm::event::KeyCode;
@@ -3801,7 +4277,8 @@ mod tests {
         let viewport_height = pane_height.saturating_add(transient_height);
 
         let history_lines =
-            crate::history_cell_potter::new_potter_round_started(1, 10).display_lines(width);
+            crate::history_cell_potter::new_potter_round_started(1, 10, PotterRoundKind::Implement)
+                .display_lines(width);
         let history_height = u16::try_from(history_lines.len()).unwrap_or(u16::MAX);
         let height = history_height.saturating_add(viewport_height).max(1);
 
@@ -3889,6 +4366,12 @@ mod tests {
                 attempt: 3,
                 max_attempts: 10,
                 error_message: "stream disconnected before completion: error sending request for url (https://free.xxsxx.fun/v1/responses)".to_string(),
+                backoff: std::time::Duration::from_secs(2),
+                strategy: PotterStreamRecoveryStrategy::Backoff {
+                    base: std::time::Duration::from_secs(1),
+                    max: std::time::Duration::from_secs(300),
+                    jitter_percent: 0,
+                },
             });
 
         let transient_lines = app.build_transient_lines(width);
@@ -3898,7 +4381,8 @@ mod tests {
         let viewport_height = pane_height.saturating_add(transient_height);
 
         let history_lines =
-            crate::history_cell_potter::new_potter_round_started(1, 10).display_lines(width);
+            crate::history_cell_potter::new_potter_round_started(1, 10, PotterRoundKind::Implement)
+                .display_lines(width);
         let history_height = u16::try_from(history_lines.len()).unwrap_or(u16::MAX);
         let height = history_height.saturating_add(viewport_height).max(1);
 
@@ -5334,6 +5818,7 @@ mod tests {
             msg: EventMsg::PotterRoundStarted {
                 current: 1,
                 total: 10,
+                kind: PotterRoundKind::Implement,
             },
         });
 
@@ -6637,6 +7122,7 @@ mod tests {
             msg: EventMsg::PotterRoundStarted {
                 current: 1,
                 total: 15,
+                kind: PotterRoundKind::Implement,
             },
         });
 
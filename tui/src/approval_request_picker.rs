@@ -0,0 +1,168 @@
+//! Builds the popup that asks the user to decide an upstream approval request.
+//!
+//! Shown for `EventMsg::PotterApprovalRequest`, which the approval policy emits for requests it
+//! leaves to the user (`ask`). Every choice, including dismissing the popup, answers with an
+//! `Op::PotterApprovalResponse`; "Deny with feedback" first collects the feedback in the composer.
+
+use codex_protocol::protocol::PotterApprovalChoice;
+use codex_protocol::protocol::PotterApprovalKind;
+use crossterm::event::KeyCode;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::key_hint;
+
+/// Prompt footer shown while the composer collects "Deny with feedback" text.
+pub(crate) const APPROVAL_FEEDBACK_HINT: &str =
+    "Type feedback for the agent and press Enter to deny the request (Esc: deny without feedback).";
+
+/// An approval request waiting for the user's answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingApprovalRequest {
+    pub approval_id: String,
+    pub kind: PotterApprovalKind,
+    pub summary: String,
+    pub reason: String,
+}
+
+fn answer_action(
+    approval_id: &str,
+    choice: PotterApprovalChoice,
+) -> Box<dyn Fn(&AppEventSender) + Send + Sync> {
+    let approval_id = approval_id.to_string();
+    Box::new(move |tx: &AppEventSender| {
+        tx.send(AppEvent::PotterApprovalAnswered {
+            approval_id: approval_id.clone(),
+            choice,
+        });
+    })
+}
+
+fn approval_request_items(approval_id: &str) -> Vec<SelectionItem> {
+    let feedback_approval_id = approval_id.to_string();
+    let ask_feedback = Box::new(move |tx: &AppEventSender| {
+        tx.send(AppEvent::PotterApprovalFeedbackRequested {
+            approval_id: feedback_approval_id.clone(),
+        });
+    }) as _;
+
+    vec![
+        SelectionItem {
+            name: "Approve".to_string(),
+            description: Some("Allow this request once".to_string()),
+            dismiss_on_select: true,
+            actions: vec![answer_action(approval_id, PotterApprovalChoice::Approve)],
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Approve for this round".to_string(),
+            description: Some("Also allow identical requests until the round ends".to_string()),
+            dismiss_on_select: true,
+            actions: vec![answer_action(
+                approval_id,
+                PotterApprovalChoice::ApproveForSession,
+            )],
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Deny".to_string(),
+            description: Some("Tell the agent the request was declined".to_string()),
+            dismiss_on_select: true,
+            actions: vec![answer_action(approval_id, PotterApprovalChoice::Deny)],
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Deny with feedback".to_string(),
+            description: Some("Type a note for the agent in the composer".to_string()),
+            dismiss_on_select: true,
+            actions: vec![ask_feedback],
+            ..Default::default()
+        },
+    ]
+}
+
+/// Popup parameters for `request`; dismissing the popup denies the request.
+pub(crate) fn build_approval_request_params(
+    request: &PendingApprovalRequest,
+) -> SelectionViewParams {
+    let title = match request.kind {
+        PotterApprovalKind::Command => "Allow the agent to run this command?",
+        PotterApprovalKind::FileChange => "Allow the agent to change these files?",
+    };
+
+    SelectionViewParams {
+        title: Some(title.to_string()),
+        subtitle: Some(request.summary.clone()),
+        footer_note: Some(Line::from(request.reason.clone()).dim()),
+        footer_hint: Some(Line::from(vec![
+            "Press ".into(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " to confirm or ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " to deny".into(),
+        ])),
+        items: approval_request_items(&request.approval_id),
+        on_cancel: Some(answer_action(
+            &request.approval_id,
+            PotterApprovalChoice::Deny,
+        )),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    #[test]
+    fn approval_request_choices_answer_with_their_decision() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let params = build_approval_request_params(&PendingApprovalRequest {
+            approval_id: "approval-1".to_string(),
+            kind: PotterApprovalKind::Command,
+            summary: "git push".to_string(),
+            reason: "not covered by any approval rule".to_string(),
+        });
+
+        for item in &params.items {
+            for action in &item.actions {
+                action(&tx);
+            }
+        }
+        if let Some(on_cancel) = &params.on_cancel {
+            on_cancel(&tx);
+        }
+
+        let mut answers = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            answers.push(match event {
+                AppEvent::PotterApprovalAnswered {
+                    approval_id,
+                    choice,
+                } => format!("{approval_id}: {choice:?}"),
+                AppEvent::PotterApprovalFeedbackRequested { approval_id } => {
+                    format!("{approval_id}: feedback")
+                }
+                other => panic!("unexpected app event: {other:?}"),
+            });
+        }
+        assert_eq!(
+            answers,
+            vec![
+                "approval-1: Approve".to_string(),
+                "approval-1: ApproveForSession".to_string(),
+                "approval-1: Deny".to_string(),
+                "approval-1: feedback".to_string(),
+                "approval-1: Deny".to_string(),
+            ]
+        );
+    }
+}
@@ -39,6 +39,7 @@ pub use queued_user_messages::QueuedUserMessages;
 
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::time::Instant;
 
 use ratatui::buffer::Buffer;
@@ -49,7 +50,9 @@ use ratatui::widgets::WidgetRef;
 
 use crate::app_event_sender::AppEventSender;
 use crate::external_editor_integration;
+use crate::project_strip::ProjectStrip;
 use crate::render::renderable::Renderable;
+use crate::status_indicator_widget::StatusHeaderBudget;
 use crate::status_indicator_widget::StatusIndicatorWidget;
 use crate::tui::FrameRequester;
 
@@ -60,6 +63,10 @@ pub const QUIT_SHORTCUT_TIMEOUT: std::time::Duration = std::time::Duration::from
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PromptFooterOverride {
     ExternalEditorHint,
+    /// The next submission is feedback for a denied approval request.
+    ApprovalFeedbackHint,
+    /// The next submission answers the current question of a user input request.
+    UserInputAnswerHint,
 }
 
 /// The context shown in the 1-line prompt footer under the composer.
@@ -104,12 +111,16 @@ pub struct BottomPane {
     status: Option<StatusIndicatorWidget>,
     status_header: String,
     status_header_prefix: Option<String>,
+    status_header_budget: Option<StatusHeaderBudget>,
+    status_header_startup_saved: Option<Duration>,
     project_started_at: Option<Instant>,
     status_details: Option<String>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 
     queued_user_messages: QueuedUserMessages,
+    /// Status strip for concurrently running projects (`--parallel`).
+    project_strip: Option<ProjectStrip>,
     composer: ChatComposer,
     prompt_footer_override: Option<PromptFooterOverride>,
     prompt_footer: PromptFooterContext,
@@ -141,11 +152,14 @@ impl BottomPane {
             status: None,
             status_header: String::from("Working"),
             status_header_prefix: None,
+            status_header_budget: None,
+            status_header_startup_saved: None,
             project_started_at: None,
             status_details: None,
             context_window_percent: None,
             context_window_used_tokens: None,
             queued_user_messages: QueuedUserMessages::new(),
+            project_strip: None,
             composer,
             prompt_footer_override: None,
             // Avoid deriving this from the process cwd so tests stay deterministic. Callers are
@@ -239,6 +253,39 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Set the remaining project budget shown after the round prefix
+    /// (e.g. `Round 3/10 (4m 13s) · 812K tokens, 26m 10s left · ...`).
+    pub fn set_status_header_budget(&mut self, budget: Option<StatusHeaderBudget>) {
+        if self.status_header_budget == budget {
+            return;
+        }
+
+        self.status_header_budget = budget;
+
+        if let Some(status) = self.status.as_mut() {
+            status.set_header_budget(self.status_header_budget);
+            status.set_header_startup_saved(self.status_header_startup_saved);
+        }
+
+        self.request_redraw();
+    }
+
+    /// Set the app-server startup time the current round saved by reusing a process
+    /// (e.g. `Round 3/10 (4m 13s, 3.2s startup saved) · ...`).
+    pub fn set_status_header_startup_saved(&mut self, startup_saved: Option<Duration>) {
+        if self.status_header_startup_saved == startup_saved {
+            return;
+        }
+
+        self.status_header_startup_saved = startup_saved;
+
+        if let Some(status) = self.status.as_mut() {
+            status.set_header_startup_saved(self.status_header_startup_saved);
+        }
+
+        self.request_redraw();
+    }
+
     /// Set the start time for the current CodexPotter project.
     ///
     /// When configured, the live status indicator renders a dim elapsed timer after the round
@@ -257,6 +304,23 @@ impl BottomPane {
         self.queued_user_messages.messages = queued;
     }
 
+    pub fn set_project_strip(&mut self, strip: Option<ProjectStrip>) {
+        self.project_strip = strip;
+        self.request_redraw();
+    }
+
+    pub fn project_strip(&self) -> Option<&ProjectStrip> {
+        self.project_strip.as_ref()
+    }
+
+    fn project_strip_height(&self) -> u16 {
+        u16::from(
+            self.project_strip
+                .as_ref()
+                .is_some_and(ProjectStrip::is_multi_project),
+        )
+    }
+
     pub fn set_prompt_footer_override(&mut self, override_mode: Option<PromptFooterOverride>) {
         self.prompt_footer_override = override_mode;
     }
@@ -276,6 +340,7 @@ impl BottomPane {
             StatusIndicatorWidget::new(self.frame_requester.clone(), self.animations_enabled);
         status.update_header_prefix(self.status_header_prefix.clone());
         status.set_header_prefix_elapsed_start(self.project_started_at);
+        status.set_header_budget(self.status_header_budget);
         status.update_header(self.status_header.clone());
         status.update_details(self.status_details.clone());
         status.set_context_window_visible(true);
@@ -354,7 +419,26 @@ impl Renderable for BottomPane {
             return;
         }
 
-        let top_area = Rect::new(area.x, area.y, area.width, height_above_composer);
+        let mut top_area = Rect::new(area.x, area.y, area.width, height_above_composer);
+
+        if let Some(line) = self
+            .project_strip
+            .as_ref()
+            .and_then(ProjectStrip::render_line)
+        {
+            let mut strip_rect = Rect::new(top_area.x, top_area.y, top_area.width, 1);
+            let indent = crate::ui_consts::LIVE_PREFIX_COLS;
+            if strip_rect.width > indent {
+                strip_rect.x += indent;
+                strip_rect.width = strip_rect.width.saturating_sub(indent);
+            }
+            WidgetRef::render_ref(&line, strip_rect, buf);
+            top_area.y = top_area.y.saturating_add(1);
+            top_area.height = top_area.height.saturating_sub(1);
+            if top_area.height == 0 {
+                return;
+            }
+        }
 
         let status_height = self
             .status
@@ -394,10 +478,12 @@ impl Renderable for BottomPane {
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        self.status
-            .as_ref()
-            .map(|status| status.desired_height(width).saturating_add(2))
-            .unwrap_or(0)
+        self.project_strip_height()
+            + self
+                .status
+                .as_ref()
+                .map(|status| status.desired_height(width).saturating_add(2))
+                .unwrap_or(0)
             + self.queued_user_messages.desired_height(width)
             + self.composer.desired_height(width)
             + if self.composer.selection_popup_visible() {
@@ -447,6 +533,14 @@ fn render_prompt_footer(
             " ".into(),
             Span::from(external_editor_integration::EXTERNAL_EDITOR_HINT).bold(),
         ]),
+        Some(PromptFooterOverride::ApprovalFeedbackHint) => ratatui::text::Line::from(vec![
+            " ".into(),
+            Span::from(crate::approval_request_picker::APPROVAL_FEEDBACK_HINT).bold(),
+        ]),
+        Some(PromptFooterOverride::UserInputAnswerHint) => ratatui::text::Line::from(vec![
+            " ".into(),
+            Span::from(crate::user_input_request_picker::USER_INPUT_ANSWER_HINT).bold(),
+        ]),
         None => {
             let dir_display =
                 crate::text_formatting::format_directory_for_display(working_dir, Some(50));
@@ -1,3 +1,6 @@
+use std::path::Path;
+
+use anyhow::Context;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
@@ -38,6 +41,32 @@ pub async fn run_external_editor(
     }
 }
 
+/// Open `path` in the external editor and write the edited contents back.
+///
+/// Fails with [`MISSING_EDITOR_ERROR`] when neither `$VISUAL` nor `$EDITOR` is set.
+pub async fn edit_file_in_external_editor(tui: &mut Tui, path: &Path) -> anyhow::Result<()> {
+    let editor_cmd = match external_editor::resolve_editor_command() {
+        Ok(cmd) => cmd,
+        Err(external_editor::EditorError::MissingEditor) => {
+            anyhow::bail!(MISSING_EDITOR_ERROR)
+        }
+        Err(err) => return Err(anyhow::Error::new(err)),
+    };
+
+    let seed = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let edited = tui
+        .with_restored(RestoreMode::KeepRaw, || async {
+            external_editor::run_editor(&seed, &editor_cmd).await
+        })
+        .await
+        .map_err(|err| anyhow::Error::msg(err.to_string()))?;
+
+    if edited != seed {
+        std::fs::write(path, edited).with_context(|| format!("write {}", path.display()))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
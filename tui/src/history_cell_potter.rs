@@ -4,14 +4,20 @@
 //!
 //! Upstream Codex does not render these cells. They are used to surface CodexPotter-specific
 //! runner behavior, such as multi-round iteration markers, project hints, stream recovery retries,
-//! and the final "project succeeded" summary.
+//! rate-limit pauses, verification gate results, approval policy decisions, answered user input
+//! requests, the app-server stderr of failed rounds, the `--review-plan` Todo list, and the final "project succeeded" summary.
 //!
 //! See `tui/AGENTS.md` ("Additional CodexPotter items" and "auto retry on stream/network errors").
 
 use std::path::PathBuf;
 use std::time::Duration;
+use std::time::Instant;
 use std::{ffi::OsStr, path::Path};
 
+use codex_protocol::protocol::PotterApprovalKind;
+use codex_protocol::protocol::PotterRoundKind;
+use codex_protocol::protocol::PotterStreamRecoveryStrategy;
+use codex_protocol::protocol::PotterUserInputAnswer;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -28,13 +34,21 @@ use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 
 /// Render a marker that indicates an iteration round boundary.
-pub fn new_potter_round_started(current: u32, total: u32) -> PrefixedWrappedHistoryCell {
+pub fn new_potter_round_started(
+    current: u32,
+    total: u32,
+    kind: PotterRoundKind,
+) -> PrefixedWrappedHistoryCell {
     let style = Style::default()
         .fg(secondary_color())
         .add_modifier(Modifier::BOLD);
+    let label = match kind {
+        PotterRoundKind::Implement => "iteration round",
+        PotterRoundKind::Review => "review round",
+    };
     let text: Text<'static> = Line::from(vec![
         Span::styled("CodexPotter: ", style),
-        format!("iteration round {current}/{total}").into(),
+        format!("{label} {current}/{total}").into(),
     ])
     .into();
     PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
@@ -207,6 +221,43 @@ pub struct PotterStreamRecoveryRetryCell {
     pub attempt: u32,
     pub max_attempts: u32,
     pub error_message: String,
+    pub backoff: Duration,
+    pub strategy: PotterStreamRecoveryStrategy,
+}
+
+impl PotterStreamRecoveryRetryCell {
+    /// E.g. `in 4s (exponential backoff, 1s to 5m 00s, ±20% jitter)`.
+    fn schedule_label(&self) -> String {
+        let fmt_elapsed_compact = crate::status_indicator_widget::fmt_elapsed_compact;
+        let mut label = String::new();
+        if !self.backoff.is_zero() {
+            label.push_str(&format!(
+                " in {}",
+                fmt_elapsed_compact(self.backoff.as_secs())
+            ));
+        }
+        match self.strategy {
+            PotterStreamRecoveryStrategy::Backoff {
+                base,
+                max,
+                jitter_percent,
+            } => {
+                label.push_str(&format!(
+                    " (exponential backoff, {} to {}",
+                    fmt_elapsed_compact(base.as_secs()),
+                    fmt_elapsed_compact(max.as_secs())
+                ));
+                if jitter_percent > 0 {
+                    label.push_str(&format!(", ±{jitter_percent}% jitter"));
+                }
+                label.push(')');
+            }
+            PotterStreamRecoveryStrategy::UsageLimitReset => {
+                label.push_str(" (waiting for the usage limit to reset)");
+            }
+        }
+        label
+    }
 }
 
 impl HistoryCell for PotterStreamRecoveryRetryCell {
@@ -224,6 +275,7 @@ impl HistoryCell for PotterStreamRecoveryRetryCell {
                 Span::styled("CodexPotter", potter_style),
                 ": ".into(),
                 format!("retry {}/{}", self.attempt, self.max_attempts).into(),
+                self.schedule_label().dim(),
             ])],
             RtOptions::new(width.max(1) as usize)
                 .initial_indent(Line::from("• ".dim()))
@@ -246,12 +298,116 @@ impl HistoryCell for PotterStreamRecoveryRetryCell {
     }
 }
 
+#[derive(Debug, Clone)]
+/// Cell counting down while CodexPotter holds a round back until a rate-limit window resets.
+pub struct PotterRateLimitWaitCell {
+    pub used_percent: f64,
+    pub window_minutes: Option<i64>,
+    pub resumes_at: Instant,
+}
+
+impl PotterRateLimitWaitCell {
+    /// E.g. `5h window`, `7d window`.
+    fn window_label(&self) -> Option<String> {
+        let minutes = self.window_minutes.filter(|minutes| *minutes > 0)?;
+        let length = if minutes % (24 * 60) == 0 {
+            format!("{}d", minutes / (24 * 60))
+        } else if minutes % 60 == 0 {
+            format!("{}h", minutes / 60)
+        } else {
+            format!("{minutes}m")
+        };
+        Some(format!("{length} window"))
+    }
+
+    fn display_lines_at(&self, width: u16, now: Instant) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let mut usage = format!("rate limit {:.0}% used", self.used_percent);
+        if let Some(window) = self.window_label() {
+            usage.push_str(&format!(" ({window})"));
+        }
+        let remaining = self.resumes_at.saturating_duration_since(now);
+
+        word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                usage.into(),
+                format!(
+                    ", resuming in {}",
+                    crate::status_indicator_widget::fmt_elapsed_compact(remaining.as_secs())
+                )
+                .dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  ")),
+        )
+    }
+}
+
+impl HistoryCell for PotterRateLimitWaitCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.display_lines_at(width, Instant::now())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use ratatui::style::Color;
 
+    #[test]
+    fn potter_round_started_labels_review_rounds() {
+        let rendered = new_potter_round_started(3, 10, PotterRoundKind::Review)
+            .display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rendered,
+            vec!["• CodexPotter: review round 3/10".to_string()]
+        );
+    }
+
+    #[test]
+    fn potter_rate_limit_wait_counts_down_to_the_reset() {
+        let now = Instant::now();
+        let cell = PotterRateLimitWaitCell {
+            used_percent: 97.4,
+            window_minutes: Some(300),
+            resumes_at: now + Duration::from_secs(723),
+        };
+
+        let rendered = cell
+            .display_lines_at(80, now)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec!["• CodexPotter: rate limit 97% used (5h window), resuming in 12m 03s".to_string()]
+        );
+    }
+
     #[test]
     fn potter_project_summary_interrupted_is_red_and_not_bold() {
         let cell = new_potter_project_interrupted(
@@ -276,6 +432,158 @@ mod tests {
             "Interrupted marker should not be bold: {interrupted:?}"
         );
     }
+
+    #[test]
+    fn potter_round_verification_failed_shows_exit_code_and_output_tail() {
+        let cell = PotterRoundVerificationCell {
+            command: "cargo test".to_string(),
+            passed: false,
+            exit_code: Some(101),
+            output: (1..=12)
+                .map(|idx| format!("line {idx}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let rendered = cell
+            .display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered[0],
+            "• CodexPotter: verification failed (exit 101) cargo test"
+        );
+        assert_eq!(rendered[1], "  └ line 3");
+        assert_eq!(rendered.last().map(String::as_str), Some("    line 12"));
+    }
+
+    #[test]
+    fn potter_approval_decision_shows_declined_command_and_reason() {
+        let cell = PotterApprovalDecisionCell {
+            kind: PotterApprovalKind::Command,
+            summary: "git push origin main".to_string(),
+            approved: false,
+            reason: "Pushing is left to the user.".to_string(),
+        };
+
+        let rendered = cell
+            .display_lines(120)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "• CodexPotter: declined command git push origin main (Pushing is left to the \
+                 user.)"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn potter_user_input_answered_lists_answers_per_question() {
+        let cell = PotterUserInputAnsweredCell {
+            server_name: Some("deploy".to_string()),
+            answers: vec![PotterUserInputAnswer {
+                header: "Targets".to_string(),
+                question: "Which targets?".to_string(),
+                answers: vec!["linux".to_string(), "macos".to_string()],
+            }],
+            declined: false,
+        };
+
+        let rendered = cell
+            .display_lines(100)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "• CodexPotter: answered user input request from deploy".to_string(),
+                "  └ Targets: linux, macos".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn potter_app_server_stderr_shows_tail_and_log_path() {
+        let cell = PotterAppServerStderrCell {
+            tail: vec![
+                "ERROR codex_core: failed to load config".to_string(),
+                "thread 'main' panicked".to_string(),
+            ],
+            log_path: Some(PathBuf::from(
+                ".codexpotter/projects/2026/03/07/9/rounds/2/app-server.stderr.log",
+            )),
+        };
+
+        let rendered = cell
+            .display_lines(100)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "• CodexPotter: app-server stderr before the round failed".to_string(),
+                "  └ ERROR codex_core: failed to load config".to_string(),
+                "    thread 'main' panicked".to_string(),
+                "    Full log: .codexpotter/projects/2026/03/07/9/rounds/2/app-server.stderr.log"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn potter_plan_review_lists_numbered_todo_items() {
+        let cell = PotterPlanReviewCell {
+            user_prompt_file: PathBuf::from(".codexpotter/projects/2026/03/07/9/MAIN.md"),
+            todo: vec!["Add the flag".to_string(), "Write docs".to_string()],
+        };
+
+        let rendered = cell
+            .display_lines(100)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            vec![
+                "• CodexPotter: plan ready for review .codexpotter/projects/2026/03/07/9/MAIN.md"
+                    .to_string(),
+                "  └ 1. Add the flag".to_string(),
+                "    2. Write docs".to_string(),
+            ]
+        );
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -317,3 +625,277 @@ impl HistoryCell for PotterStreamRecoveryUnrecoverableCell {
         out
     }
 }
+
+/// Maximum number of trailing verification output lines shown in the transcript.
+const VERIFICATION_OUTPUT_MAX_LINES: usize = 10;
+
+#[derive(Debug, Clone)]
+/// History cell shown after the post-round verification gate ran.
+pub struct PotterRoundVerificationCell {
+    pub command: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+impl HistoryCell for PotterRoundVerificationCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+
+        let status: Span<'static> = if self.passed {
+            "verification passed".green()
+        } else {
+            match self.exit_code {
+                Some(code) => format!("verification failed (exit {code})").red(),
+                None => "verification failed".red(),
+            }
+        };
+
+        let mut out = word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                status,
+                " ".into(),
+                self.command.clone().dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  "))
+                .break_words(true),
+        );
+
+        if self.passed {
+            return out;
+        }
+
+        let output_lines = self.output.lines().collect::<Vec<_>>();
+        let tail = &output_lines[output_lines
+            .len()
+            .saturating_sub(VERIFICATION_OUTPUT_MAX_LINES)..];
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            tail.iter().map(|line| vec![line.to_string().dim()]),
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+/// History cell listing the planned Todo items while `--review-plan` waits for approval.
+pub struct PotterPlanReviewCell {
+    pub user_prompt_file: PathBuf,
+    pub todo: Vec<String>,
+}
+
+impl HistoryCell for PotterPlanReviewCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+
+        let mut out = word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                "plan ready for review ".into(),
+                self.user_prompt_file.to_string_lossy().to_string().dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  "))
+                .break_words(true),
+        );
+
+        let items = if self.todo.is_empty() {
+            vec![vec!["(no Todo items)".dim().italic()]]
+        } else {
+            self.todo
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| vec![format!("{}. ", idx + 1).dim(), item.clone().into()])
+                .collect()
+        };
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            items,
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+/// History cell shown when the approval policy answered an upstream approval request.
+pub struct PotterApprovalDecisionCell {
+    pub kind: PotterApprovalKind,
+    pub summary: String,
+    pub approved: bool,
+    pub reason: String,
+}
+
+impl HistoryCell for PotterApprovalDecisionCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let subject = match self.kind {
+            PotterApprovalKind::Command => "command",
+            PotterApprovalKind::FileChange => "file change",
+        };
+        let status: Span<'static> = if self.approved {
+            format!("approved {subject}").dim()
+        } else {
+            format!("declined {subject}").red()
+        };
+
+        word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                status,
+                " ".into(),
+                self.summary.clone().into(),
+                " ".into(),
+                format!("({})", self.reason).dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  "))
+                .break_words(true),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+/// History cell shown once a user input request (agent question or MCP elicitation) is answered.
+pub struct PotterUserInputAnsweredCell {
+    pub server_name: Option<String>,
+    pub answers: Vec<PotterUserInputAnswer>,
+    pub declined: bool,
+}
+
+impl HistoryCell for PotterUserInputAnsweredCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let mut header = vec![Span::styled("CodexPotter", potter_style), ": ".into()];
+        header.push(if self.declined {
+            "declined user input request".yellow()
+        } else {
+            "answered user input request".into()
+        });
+        if let Some(server_name) = &self.server_name {
+            header.push(format!(" from {server_name}").dim());
+        }
+
+        let mut out = word_wrap_lines(
+            [Line::from(header)],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  "))
+                .break_words(true),
+        );
+
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            self.answers.iter().map(|answer| {
+                vec![
+                    format!("{}: ", answer.header).dim(),
+                    answer.answers.join(", ").into(),
+                ]
+            }),
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+/// History cell with the last lines of the upstream app-server's stderr when a round fails, and
+/// the round log that has the complete output.
+pub struct PotterAppServerStderrCell {
+    pub tail: Vec<String>,
+    pub log_path: Option<PathBuf>,
+}
+
+impl HistoryCell for PotterAppServerStderrCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let mut out = word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                "app-server stderr before the round failed".into(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  "))
+                .break_words(true),
+        );
+
+        let mut lines: Vec<Vec<Span<'static>>> = self
+            .tail
+            .iter()
+            .map(|line| vec![line.clone().dim()])
+            .collect();
+        if let Some(log_path) = &self.log_path {
+            lines.push(vec![
+                "Full log: ".into(),
+                log_path.to_string_lossy().to_string().cyan(),
+            ]);
+        }
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            lines,
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
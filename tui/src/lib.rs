@@ -9,6 +9,7 @@ mod ansi_escape;
 mod app_event;
 mod app_event_sender;
 mod app_server_render;
+mod approval_request_picker;
 mod bottom_pane;
 mod codex_config;
 mod color;
@@ -31,8 +32,10 @@ mod markdown_stream;
 mod mention_codec;
 mod multi_agents;
 mod path_utils;
+mod plan_approval_prompt;
 mod potter_config;
 mod potter_tui;
+mod project_strip;
 mod prompt_history_store;
 mod render;
 mod resume_picker_prompt;
@@ -56,6 +59,7 @@ mod ui_consts;
 mod update_action;
 mod update_prompt;
 mod updates;
+mod user_input_request_picker;
 mod verbosity;
 mod verbosity_picker;
 mod verbosity_prompt;
@@ -71,8 +75,12 @@ pub use exit::ExitReason;
 pub use global_gitignore_prompt::GlobalGitignorePromptOutcome;
 pub use global_gitignore_prompt::run_global_gitignore_prompt;
 pub use interrupted_project_prompt::InterruptedProjectAction;
+pub use plan_approval_prompt::PlanApprovalAction;
 pub use potter_tui::CodexPotterTui;
 pub use potter_tui::RenderRoundParams;
+pub use project_strip::ProjectStrip;
+pub use project_strip::ProjectStripEntry;
+pub use project_strip::ProjectStripState;
 pub use resume_picker_prompt::ResumePickerOutcome;
 pub use resume_picker_prompt::ResumePickerRow;
 pub use update_action::UpdateAction;
@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::prelude::Widget;
+use ratatui::text::Line;
+use tokio_stream::StreamExt;
+
+use crate::bottom_pane::ListSelectionView;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::render::renderable::Renderable;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+
+/// How to proceed after the planning round (`--review-plan`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanApprovalAction {
+    /// Start the implementation rounds with the current plan.
+    Approve,
+    /// Edit the progress file in `$VISUAL` / `$EDITOR`, then review again.
+    Edit,
+    /// Stop the project before any implementation round runs.
+    Abort,
+}
+
+fn plan_approval_items() -> Vec<SelectionItem> {
+    [
+        "Approve plan and start implementing",
+        "Edit plan in $EDITOR",
+        "Abort project",
+    ]
+    .into_iter()
+    .map(|name| SelectionItem {
+        name: name.to_string(),
+        dismiss_on_select: true,
+        ..Default::default()
+    })
+    .collect()
+}
+
+fn plan_approval_view_params(progress_file_rel: PathBuf) -> SelectionViewParams {
+    SelectionViewParams {
+        title: Some("Review the plan before implementation starts".to_string()),
+        subtitle: Some(progress_file_rel.to_string_lossy().to_string()),
+        footer_note: Some(Line::from(
+            "Edits to the Todo list are picked up by the next round.",
+        )),
+        footer_hint: Some(Line::from("Press enter to confirm, Ctrl+C to abort.")),
+        items: plan_approval_items(),
+        ..Default::default()
+    }
+}
+
+fn handle_prompt_key_event(view: &mut ListSelectionView, key_event: KeyEvent) {
+    if key_event.kind == KeyEventKind::Release {
+        return;
+    }
+
+    // The previous round may still have the user reaching for Esc; require an explicit selection
+    // (Enter/number) or Ctrl+C so the plan is never dismissed by accident.
+    if key_event.modifiers == KeyModifiers::NONE && matches!(key_event.code, KeyCode::Esc) {
+        return;
+    }
+
+    if key_event.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key_event.code, KeyCode::Char('c'))
+    {
+        if key_event.kind == KeyEventKind::Press {
+            view.cancel();
+        }
+        return;
+    }
+
+    view.handle_key_event(key_event);
+}
+
+pub async fn prompt_plan_approval_action(
+    tui: &mut Tui,
+    progress_file_rel: PathBuf,
+) -> anyhow::Result<Option<PlanApprovalAction>> {
+    let (app_event_tx, _app_event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let app_event_tx = crate::app_event_sender::AppEventSender::new(app_event_tx);
+    let mut view =
+        ListSelectionView::new(plan_approval_view_params(progress_file_rel), app_event_tx);
+
+    let width = tui.terminal.last_known_screen_size.width.max(1);
+    tui.draw(view.desired_height(width).saturating_add(1), |frame| {
+        let area = frame.area();
+        ratatui::widgets::Clear.render(area, frame.buffer_mut());
+        let view_area = ratatui::layout::Rect::new(
+            area.x,
+            area.y.saturating_add(1),
+            area.width,
+            area.height.saturating_sub(1),
+        );
+        view.render(view_area, frame.buffer_mut());
+    })?;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+
+    while !view.is_complete() {
+        let Some(event) = events.next().await else {
+            break;
+        };
+        match event {
+            TuiEvent::Key(key_event) => {
+                handle_prompt_key_event(&mut view, key_event);
+                tui.frame_requester().schedule_frame();
+            }
+            TuiEvent::Paste(_) => {}
+            TuiEvent::Draw => {
+                let width = tui.terminal.last_known_screen_size.width.max(1);
+                tui.draw(view.desired_height(width).saturating_add(1), |frame| {
+                    let area = frame.area();
+                    ratatui::widgets::Clear.render(area, frame.buffer_mut());
+                    let view_area = ratatui::layout::Rect::new(
+                        area.x,
+                        area.y.saturating_add(1),
+                        area.width,
+                        area.height.saturating_sub(1),
+                    );
+                    view.render(view_area, frame.buffer_mut());
+                })?;
+            }
+        }
+    }
+
+    // Clear the inline viewport so subsequent screens start clean.
+    tui.terminal.clear()?;
+
+    let Some(idx) = view.take_last_selected_index() else {
+        return Ok(None);
+    };
+
+    match idx {
+        0 => Ok(Some(PlanApprovalAction::Approve)),
+        1 => Ok(Some(PlanApprovalAction::Edit)),
+        2 => Ok(Some(PlanApprovalAction::Abort)),
+        _ => anyhow::bail!("internal error: unexpected plan approval selection {idx}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn new_view() -> ListSelectionView {
+        ListSelectionView::new(
+            plan_approval_view_params(PathBuf::from(".codexpotter/projects/2026/03/06/4/MAIN.md")),
+            crate::app_event_sender::AppEventSender::new(tokio::sync::mpsc::unbounded_channel().0),
+        )
+    }
+
+    #[test]
+    fn plan_approval_prompt_esc_does_not_cancel() {
+        let mut view = new_view();
+
+        handle_prompt_key_event(&mut view, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(
+            !view.is_complete(),
+            "expected Esc not to cancel the plan approval prompt"
+        );
+
+        handle_prompt_key_event(
+            &mut view,
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        );
+        assert!(
+            view.is_complete(),
+            "expected Ctrl+C to cancel the plan approval prompt"
+        );
+        assert_eq!(view.take_last_selected_index(), None);
+    }
+
+    #[test]
+    fn plan_approval_prompt_enter_selects_approve_by_default() {
+        let mut view = new_view();
+
+        handle_prompt_key_event(&mut view, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(view.is_complete());
+        assert_eq!(view.take_last_selected_index(), Some(0));
+    }
+}
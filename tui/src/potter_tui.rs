@@ -42,6 +42,7 @@ pub struct CodexPotterTui {
     startup_codex_model_config: Option<crate::codex_config::ResolvedCodexModelConfig>,
     verbosity: Verbosity,
     needs_startup_verbosity_prompt: bool,
+    project_strip: Option<crate::ProjectStrip>,
 }
 
 impl CodexPotterTui {
@@ -77,6 +78,7 @@ impl CodexPotterTui {
             startup_codex_model_config: None,
             verbosity,
             needs_startup_verbosity_prompt,
+            project_strip: None,
         })
     }
 
@@ -243,6 +245,13 @@ impl CodexPotterTui {
         self.project_started_at = Some(started_at);
     }
 
+    /// Show a per-project status strip while rendering rounds (`--parallel`).
+    ///
+    /// Pass `None` to hide the strip again.
+    pub fn set_project_strip(&mut self, strip: Option<crate::ProjectStrip>) {
+        self.project_strip = strip;
+    }
+
     /// Prompt the user to select an action from a list.
     ///
     /// Returns:
@@ -278,6 +287,48 @@ impl CodexPotterTui {
         result
     }
 
+    /// Show the planned Todo list and ask whether to approve, edit, or abort it
+    /// (`--review-plan`).
+    ///
+    /// Returns `None` when the prompt is cancelled (Ctrl+C).
+    pub async fn prompt_plan_approval(
+        &mut self,
+        progress_file_rel: PathBuf,
+        todo: Vec<String>,
+    ) -> anyhow::Result<Option<crate::PlanApprovalAction>> {
+        let width = self.tui.terminal.last_known_screen_size.width.max(1);
+        let mut lines = crate::history_cell_potter::PotterPlanReviewCell {
+            user_prompt_file: progress_file_rel.clone(),
+            todo,
+        }
+        .display_lines(width);
+        if self.has_rendered_round {
+            lines.insert(0, ratatui::text::Line::from(""));
+        }
+        self.tui.insert_history_lines(lines);
+
+        let result = crate::plan_approval_prompt::prompt_plan_approval_action(
+            &mut self.tui,
+            progress_file_rel,
+        )
+        .await;
+
+        self.reset_event_stream_after_prompt();
+
+        result
+    }
+
+    /// Open `path` in `$VISUAL` / `$EDITOR` and save the edited contents back to it.
+    pub async fn edit_file_in_external_editor(&mut self, path: PathBuf) -> anyhow::Result<()> {
+        let result =
+            crate::external_editor_integration::edit_file_in_external_editor(&mut self.tui, &path)
+                .await;
+
+        self.reset_event_stream_after_prompt();
+
+        result
+    }
+
     /// Insert a summary block for an interrupted CodexPotter project into the transcript.
     pub fn insert_interrupted_project_summary(
         &mut self,
@@ -308,6 +359,22 @@ impl CodexPotterTui {
         self.tui.insert_history_lines(lines);
     }
 
+    /// Insert a one-line notice into the transcript, rendered as an error when `is_error` is set.
+    pub fn insert_notice(&mut self, message: String, is_error: bool) {
+        let width = self.tui.terminal.last_known_screen_size.width.max(1);
+        let mut lines = if is_error {
+            crate::history_cell::new_error_event(message).display_lines(width)
+        } else {
+            crate::history_cell::new_info_event(message, None).display_lines(width)
+        };
+
+        if self.has_rendered_round {
+            lines.insert(0, ratatui::text::Line::from(""));
+        }
+
+        self.tui.insert_history_lines(lines);
+    }
+
     /// Prompt the user to select a resumable CodexPotter project to resume.
     ///
     /// `Esc` returns [`crate::ResumePickerOutcome::StartFresh`] (do not exit the app).
@@ -377,6 +444,7 @@ impl CodexPotterTui {
         let context = crate::app_server_render::ProjectRenderContext {
             project_started_at,
             prompt_footer,
+            project_strip: self.project_strip.clone(),
         };
         let state = crate::app_server_render::RoundUiState {
             queued_user_messages: &mut queued,
@@ -0,0 +1,211 @@
+//! Per-project status strip for `codex-potter --parallel`.
+//!
+//! When several projects run concurrently, the transcript only shows the *focused* project. The
+//! strip is a one-line summary of every active project rendered in the bottom pane, and it carries
+//! the user's request to move focus to the next project (`Ctrl+O`) back to the project runner.
+//!
+//! [`ProjectStrip`] is a cheap, cloneable handle: the runner updates entries as events arrive for
+//! unfocused projects, and the round renderer reads them on every draw.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_protocol::protocol::PotterProjectOutcome;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+/// Lifecycle state of one project shown in the strip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectStripState {
+    /// `project/start` succeeded but no round has started yet.
+    Starting,
+    /// A round is running.
+    Round { current: u32, total: u32 },
+    /// The project is interrupted and waiting for the user.
+    Interrupted,
+    /// The planning round finished and the plan is waiting for approval (`--review-plan`).
+    AwaitingPlanApproval,
+    /// The project completed with the given outcome.
+    Completed(PotterProjectOutcome),
+}
+
+/// One project in the strip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectStripEntry {
+    /// Short label identifying the project (e.g. `#3`).
+    pub label: String,
+    pub state: ProjectStripState,
+    /// Whether the transcript currently shows this project.
+    pub focused: bool,
+}
+
+#[derive(Debug, Default)]
+struct ProjectStripInner {
+    entries: Vec<ProjectStripEntry>,
+    focus_next_requested: bool,
+}
+
+/// Shared handle to the project status strip.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectStrip {
+    inner: Arc<Mutex<ProjectStripInner>>,
+}
+
+impl ProjectStrip {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace all entries.
+    pub fn set_entries(&self, entries: Vec<ProjectStripEntry>) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.entries = entries;
+        }
+    }
+
+    /// Snapshot of the current entries.
+    pub fn entries(&self) -> Vec<ProjectStripEntry> {
+        self.inner
+            .lock()
+            .map(|inner| inner.entries.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` when the strip has more than one project to switch between.
+    pub fn is_multi_project(&self) -> bool {
+        self.inner
+            .lock()
+            .map(|inner| inner.entries.len() > 1)
+            .unwrap_or(false)
+    }
+
+    /// Ask the runner to move focus to the next project at the next round boundary.
+    pub fn request_focus_next(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.focus_next_requested = true;
+        }
+    }
+
+    /// Consume a pending focus request, if any.
+    pub fn take_focus_next_request(&self) -> bool {
+        self.inner
+            .lock()
+            .map(|mut inner| std::mem::take(&mut inner.focus_next_requested))
+            .unwrap_or(false)
+    }
+
+    /// Render the strip as a single line, or `None` when there is nothing to switch between.
+    pub(crate) fn render_line(&self) -> Option<Line<'static>> {
+        let inner = self.inner.lock().ok()?;
+        if inner.entries.len() < 2 {
+            return None;
+        }
+
+        let mut spans: Vec<Span<'static>> = vec!["Projects ".dim()];
+        for (idx, entry) in inner.entries.iter().enumerate() {
+            if idx > 0 {
+                spans.push(" · ".dim());
+            }
+            let label = if entry.focused {
+                Span::from(format!("▸{}", entry.label)).bold()
+            } else {
+                Span::from(entry.label.clone())
+            };
+            spans.push(label);
+            spans.push(" ".into());
+            spans.push(state_span(&entry.state));
+        }
+
+        if inner.focus_next_requested {
+            spans.push("  switching after this round".dim());
+        } else {
+            spans.push("  ctrl+o".into());
+            spans.push(" next".dim());
+        }
+
+        Some(Line::from(spans))
+    }
+}
+
+fn state_span(state: &ProjectStripState) -> Span<'static> {
+    match state {
+        ProjectStripState::Starting => "starting".dim(),
+        ProjectStripState::Round { current, total } => format!("{current}/{total}").cyan(),
+        ProjectStripState::Interrupted => "interrupted".magenta(),
+        ProjectStripState::AwaitingPlanApproval => "plan review".magenta(),
+        ProjectStripState::Completed(PotterProjectOutcome::Succeeded) => "✓".green(),
+        ProjectStripState::Completed(PotterProjectOutcome::BudgetExhausted) => {
+            "budget exhausted".dim()
+        }
+        ProjectStripState::Completed(PotterProjectOutcome::Interrupted) => "stopped".dim(),
+        ProjectStripState::Completed(PotterProjectOutcome::Stalled { .. }) => "stalled".dim(),
+        ProjectStripState::Completed(PotterProjectOutcome::TokenBudgetExhausted { .. }) => {
+            "token budget exhausted".dim()
+        }
+        ProjectStripState::Completed(PotterProjectOutcome::TimeBudgetExhausted { .. }) => {
+            "time budget exhausted".dim()
+        }
+        ProjectStripState::Completed(
+            PotterProjectOutcome::TaskFailed { .. } | PotterProjectOutcome::Fatal { .. },
+        ) => "✗".red(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn render_line_hides_single_project() {
+        let strip = ProjectStrip::new();
+        strip.set_entries(vec![ProjectStripEntry {
+            label: "#1".to_string(),
+            state: ProjectStripState::Starting,
+            focused: true,
+        }]);
+        assert!(strip.render_line().is_none());
+        assert!(!strip.is_multi_project());
+    }
+
+    #[test]
+    fn render_line_marks_focused_project_and_states() {
+        let strip = ProjectStrip::new();
+        strip.set_entries(vec![
+            ProjectStripEntry {
+                label: "#1".to_string(),
+                state: ProjectStripState::Round {
+                    current: 2,
+                    total: 10,
+                },
+                focused: true,
+            },
+            ProjectStripEntry {
+                label: "#2".to_string(),
+                state: ProjectStripState::Completed(PotterProjectOutcome::Succeeded),
+                focused: false,
+            },
+        ]);
+
+        let line = strip.render_line().expect("strip line");
+        assert_eq!(line_text(&line), "Projects ▸#1 2/10 · #2 ✓  ctrl+o next");
+
+        strip.request_focus_next();
+        let line = strip.render_line().expect("strip line");
+        assert_eq!(
+            line_text(&line),
+            "Projects ▸#1 2/10 · #2 ✓  switching after this round"
+        );
+        assert!(strip.take_focus_next_request());
+        assert!(!strip.take_focus_next_request());
+    }
+}
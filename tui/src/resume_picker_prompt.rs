@@ -46,6 +46,8 @@ pub struct ResumePickerRow {
     pub updated_at: SystemTime,
     /// Git branch recorded in the project progress file front matter.
     pub git_branch: Option<String>,
+    /// Git worktree the project runs in (`--worktree` projects only).
+    pub worktree: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -168,10 +170,14 @@ impl ResumePickerScreen {
             .iter()
             .map(|row| {
                 format!(
-                    "{}\n{}\n{}",
+                    "{}\n{}\n{}\n{}",
                     row.user_request,
                     row.git_branch.as_deref().unwrap_or_default(),
                     row.project_path.to_string_lossy(),
+                    row.worktree
+                        .as_deref()
+                        .map(|worktree| worktree.to_string_lossy())
+                        .unwrap_or_default(),
                 )
                 .to_lowercase()
             })
@@ -475,7 +481,10 @@ fn calculate_column_metrics(
         let created = human_time_ago(row.created_at, now);
         let updated = human_time_ago(row.updated_at, now);
         let branch_raw = row.git_branch.clone().unwrap_or_default();
-        let branch = right_elide(&branch_raw, 24);
+        let mut branch = right_elide(&branch_raw, 24);
+        if row.worktree.is_some() {
+            branch.push_str(" (worktree)");
+        }
         max_created_width = max_created_width.max(UnicodeWidthStr::width(created.as_str()));
         max_updated_width = max_updated_width.max(UnicodeWidthStr::width(updated.as_str()));
         max_branch_width = max_branch_width.max(UnicodeWidthStr::width(branch.as_str()));
@@ -672,6 +681,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -679,6 +689,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -686,6 +697,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                worktree: None,
             },
         ];
 
@@ -719,6 +731,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -726,6 +739,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -733,6 +747,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                worktree: None,
             },
         ];
 
@@ -765,6 +780,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -772,6 +788,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                worktree: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -779,6 +796,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                worktree: None,
             },
         ];
 
@@ -799,4 +817,37 @@ mod tests {
         screen.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
         assert_eq!(screen.selected, 1);
     }
+
+    #[test]
+    fn resume_picker_marks_and_searches_worktree_projects() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let rows = vec![
+            ResumePickerRow {
+                project_path: PathBuf::from("/tmp/a"),
+                user_request: "Fix resume picker timestamps".to_string(),
+                created_at: now,
+                updated_at: now,
+                git_branch: Some("main".to_string()),
+                worktree: None,
+            },
+            ResumePickerRow {
+                project_path: PathBuf::from("/tmp/b"),
+                user_request: "Investigate lazy pagination cap".to_string(),
+                created_at: now,
+                updated_at: now,
+                git_branch: Some("codexpotter/2026-03-04/2".to_string()),
+                worktree: Some(PathBuf::from("/repo/.codexpotter/worktrees/2026/03/04/2")),
+            },
+        ];
+
+        let metrics = calculate_column_metrics(&rows, &[0, 1], now);
+        assert_eq!(metrics.labels[0].2, "main");
+        assert_eq!(metrics.labels[1].2, "codexpotter/2026-03-04/2 (worktree)");
+
+        let mut screen = ResumePickerScreen::new(FrameRequester::test_dummy(), rows, now);
+        for ch in "worktrees".chars() {
+            screen.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!(screen.filtered_indices, vec![1]);
+    }
 }
@@ -7,6 +7,10 @@
 //!
 //! - The header may include an optional round prefix with a total timer (e.g.
 //!   `Round 2/10 (4m 13s) · Working`).
+//! - With `--max-tokens` / `--max-duration`, the remaining project budget follows the round prefix
+//!   (e.g. `Round 2/10 (4m 13s) · 812K tokens, 26m 10s left · Working`).
+//! - With `--reuse-app-server`, rounds that reuse the previous app-server process show the startup
+//!   time they saved next to the total timer (e.g. `Round 2/10 (4m 13s, 3.2s startup saved)`).
 //! - Remaining context is rendered in the status line while a task is running.
 //! - We do not show an "esc to interrupt" hint (even though <kbd>Esc</kbd> interrupts running tasks).
 
@@ -37,12 +41,22 @@ use crate::wrapping::word_wrap_lines;
 const DETAILS_MAX_LINES: usize = 3;
 const DETAILS_PREFIX: &str = "  └ ";
 
+/// Remaining project budget shown after the header prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusHeaderBudget {
+    pub tokens_remaining: Option<u64>,
+    /// When the wall-clock budget runs out.
+    pub deadline: Option<Instant>,
+}
+
 /// Renders a live status line (with optional details) while a task is running.
 pub struct StatusIndicatorWidget {
     /// Animated header text (defaults to "Working").
     header: String,
     header_prefix: Option<String>,
     header_prefix_elapsed_offset: Option<Duration>,
+    header_budget: Option<StatusHeaderBudget>,
+    header_startup_saved: Option<Duration>,
     details: Option<String>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
@@ -81,6 +95,8 @@ impl StatusIndicatorWidget {
             header: String::from("Working"),
             header_prefix: None,
             header_prefix_elapsed_offset: None,
+            header_budget: None,
+            header_startup_saved: None,
             details: None,
             context_window_percent: None,
             context_window_used_tokens: None,
@@ -111,6 +127,16 @@ impl StatusIndicatorWidget {
             started_at.map(|started_at| self.last_resume_at.saturating_duration_since(started_at));
     }
 
+    /// Configure the remaining project budget shown after the header prefix (when present).
+    pub fn set_header_budget(&mut self, budget: Option<StatusHeaderBudget>) {
+        self.header_budget = budget;
+    }
+
+    /// Configure the app-server startup time the current round saved by reusing a process.
+    pub fn set_header_startup_saved(&mut self, startup_saved: Option<Duration>) {
+        self.header_startup_saved = startup_saved;
+    }
+
     /// Update the details text shown below the header.
     pub fn update_details(&mut self, details: Option<String>) {
         self.details = details
@@ -177,6 +203,40 @@ impl StatusIndicatorWidget {
         self.elapsed_duration_at(Instant::now()).as_secs()
     }
 
+    /// Parenthesized label after the header prefix, e.g. `4m 13s, 3.2s startup saved`.
+    fn header_prefix_timing_label(&self, elapsed_duration: Duration) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(prefix_elapsed_offset) = self.header_prefix_elapsed_offset {
+            let prefix_elapsed = prefix_elapsed_offset.saturating_add(elapsed_duration);
+            parts.push(fmt_elapsed_compact(prefix_elapsed.as_secs()));
+        }
+        if let Some(startup_saved) = self.header_startup_saved {
+            parts.push(format!("{:.1}s startup saved", startup_saved.as_secs_f64()));
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        Some(parts.join(", "))
+    }
+
+    /// Remaining budget label, e.g. `812K tokens, 26m 10s left`.
+    fn header_budget_label_at(&self, now: Instant) -> Option<String> {
+        let budget = self.header_budget?;
+        let mut parts = Vec::new();
+        if let Some(tokens_remaining) = budget.tokens_remaining {
+            let tokens = i64::try_from(tokens_remaining).unwrap_or(i64::MAX);
+            parts.push(format!("{} tokens", format_tokens_compact(tokens)));
+        }
+        if let Some(deadline) = budget.deadline {
+            let remaining = deadline.saturating_duration_since(now);
+            parts.push(fmt_elapsed_compact(remaining.as_secs()));
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        Some(format!("{} left", parts.join(", ")))
+    }
+
     /// Wrap the details text into a fixed width and return the lines, truncating if necessary.
     fn wrapped_details_lines(&self, width: u16) -> Vec<Line<'static>> {
         let Some(details) = self.details.as_deref() else {
@@ -235,10 +295,11 @@ impl Renderable for StatusIndicatorWidget {
                 prefix.to_string(),
                 Style::default().fg(secondary_color()).bold(),
             ));
-            if let Some(prefix_elapsed_offset) = self.header_prefix_elapsed_offset {
-                let prefix_elapsed = prefix_elapsed_offset.saturating_add(elapsed_duration);
-                let pretty_prefix_elapsed = fmt_elapsed_compact(prefix_elapsed.as_secs());
-                spans.push(format!(" ({pretty_prefix_elapsed})").dim());
+            if let Some(timing_label) = self.header_prefix_timing_label(elapsed_duration) {
+                spans.push(format!(" ({timing_label})").dim());
+            }
+            if let Some(budget_label) = self.header_budget_label_at(now) {
+                spans.push(format!(" · {budget_label}").dim());
             }
             if !self.header.is_empty() {
                 spans.push(" · ".dim());
@@ -377,6 +438,49 @@ mod tests {
         insta::assert_snapshot!(terminal.backend());
     }
 
+    #[test]
+    fn header_budget_label_shows_remaining_tokens_and_time() {
+        let mut w = StatusIndicatorWidget::new(crate::tui::FrameRequester::test_dummy(), false);
+        let now = Instant::now();
+        assert_eq!(w.header_budget_label_at(now), None);
+
+        w.set_header_budget(Some(StatusHeaderBudget {
+            tokens_remaining: Some(812_000),
+            deadline: Some(now + Duration::from_secs(26 * 60 + 10)),
+        }));
+        assert_eq!(
+            w.header_budget_label_at(now),
+            Some("812K tokens, 26m 10s left".to_string())
+        );
+
+        w.set_header_budget(Some(StatusHeaderBudget {
+            tokens_remaining: None,
+            deadline: Some(now),
+        }));
+        assert_eq!(
+            w.header_budget_label_at(now + Duration::from_secs(5)),
+            Some("0s left".to_string())
+        );
+    }
+
+    #[test]
+    fn header_prefix_timing_label_shows_saved_startup() {
+        let mut w = StatusIndicatorWidget::new(crate::tui::FrameRequester::test_dummy(), false);
+        assert_eq!(w.header_prefix_timing_label(Duration::ZERO), None);
+
+        w.set_header_startup_saved(Some(Duration::from_millis(3_240)));
+        assert_eq!(
+            w.header_prefix_timing_label(Duration::ZERO),
+            Some("3.2s startup saved".to_string())
+        );
+
+        w.header_prefix_elapsed_offset = Some(Duration::from_secs(4 * 60));
+        assert_eq!(
+            w.header_prefix_timing_label(Duration::from_secs(13)),
+            Some("4m 13s, 3.2s startup saved".to_string())
+        );
+    }
+
     #[test]
     fn timer_pauses_when_requested() {
         let mut widget = StatusIndicatorWidget::new(crate::tui::FrameRequester::test_dummy(), true);
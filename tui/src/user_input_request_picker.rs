@@ -0,0 +1,281 @@
+//! Builds the popups that collect answers for a user input request.
+//!
+//! Shown for `EventMsg::PotterUserInputRequest`, which the backend emits when the agent's
+//! `request_user_input` tool or an MCP elicitation asks the user something. Questions are asked one
+//! at a time: each popup lists the question's options, plus "Type an answer" (collected in the
+//! composer) for free-form questions. Dismissing any popup declines the whole request.
+
+use std::collections::HashMap;
+
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use crossterm::event::KeyCode;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::key_hint;
+
+/// Prompt footer shown while the composer collects a typed answer.
+pub(crate) const USER_INPUT_ANSWER_HINT: &str =
+    "Type your answer and press Enter (Esc: decline the request).";
+
+/// A user input request waiting for the user's answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingUserInputRequest {
+    pub request_id: String,
+    pub server_name: Option<String>,
+    pub message: Option<String>,
+    pub questions: Vec<RequestUserInputQuestion>,
+    /// Answers of the questions asked so far, in question order.
+    pub answers: Vec<String>,
+}
+
+impl PendingUserInputRequest {
+    /// The next unanswered question; `None` once every question is answered.
+    pub fn current_question(&self) -> Option<&RequestUserInputQuestion> {
+        self.questions.get(self.answers.len())
+    }
+
+    pub fn record_answer(&mut self, answer: String) {
+        self.answers.push(answer);
+    }
+
+    /// Whether the request has no questions left to ask.
+    pub fn is_complete(&self) -> bool {
+        self.current_question().is_none()
+    }
+
+    /// Answers keyed by question id, as sent in `Op::PotterUserInputResponse`.
+    pub fn into_answers(self) -> HashMap<String, RequestUserInputAnswer> {
+        self.questions
+            .into_iter()
+            .zip(self.answers)
+            .map(|(question, answer)| {
+                (
+                    question.id,
+                    RequestUserInputAnswer {
+                        answers: vec![answer],
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+fn choose_action(request_id: &str, answer: &str) -> Box<dyn Fn(&AppEventSender) + Send + Sync> {
+    let request_id = request_id.to_string();
+    let answer = answer.to_string();
+    Box::new(move |tx: &AppEventSender| {
+        tx.send(AppEvent::PotterUserInputOptionChosen {
+            request_id: request_id.clone(),
+            answer: answer.clone(),
+        });
+    })
+}
+
+fn decline_action(request_id: &str) -> Box<dyn Fn(&AppEventSender) + Send + Sync> {
+    let request_id = request_id.to_string();
+    Box::new(move |tx: &AppEventSender| {
+        tx.send(AppEvent::PotterUserInputDeclined {
+            request_id: request_id.clone(),
+        });
+    })
+}
+
+fn question_items(request_id: &str, question: &RequestUserInputQuestion) -> Vec<SelectionItem> {
+    let options = question.options.as_deref().unwrap_or_default();
+    let mut items = options
+        .iter()
+        .map(|option| SelectionItem {
+            name: option.label.clone(),
+            description: Some(option.description.clone()).filter(|text| !text.is_empty()),
+            dismiss_on_select: true,
+            actions: vec![choose_action(request_id, &option.label)],
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+
+    if question.is_other || options.is_empty() {
+        let text_request_id = request_id.to_string();
+        items.push(SelectionItem {
+            name: "Type an answer".to_string(),
+            description: Some("Write the answer in the composer".to_string()),
+            dismiss_on_select: true,
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::PotterUserInputTextRequested {
+                    request_id: text_request_id.clone(),
+                });
+            })],
+            ..Default::default()
+        });
+    }
+    items
+}
+
+/// Popup parameters for the current question of `request`; dismissing the popup declines it.
+///
+/// A request without questions (an elicitation that only asks for confirmation) shows a single
+/// "Accept" choice.
+pub(crate) fn build_user_input_question_params(
+    request: &PendingUserInputRequest,
+) -> SelectionViewParams {
+    let asker = match request.server_name.as_deref() {
+        Some(server_name) => format!("MCP server `{server_name}`"),
+        None => "The agent".to_string(),
+    };
+    let (title, subtitle, items) = match request.current_question() {
+        Some(question) => (
+            format!("{asker} asks: {}", question.header),
+            Some(question.question.clone()),
+            question_items(&request.request_id, question),
+        ),
+        None => (
+            format!("{asker} asks for confirmation"),
+            None,
+            vec![SelectionItem {
+                name: "Accept".to_string(),
+                dismiss_on_select: true,
+                actions: vec![choose_action(&request.request_id, "")],
+                ..Default::default()
+            }],
+        ),
+    };
+
+    SelectionViewParams {
+        title: Some(title),
+        subtitle: subtitle.or_else(|| request.message.clone()),
+        footer_note: request
+            .current_question()
+            .and(request.message.clone())
+            .map(|message| Line::from(message).dim()),
+        footer_hint: Some(Line::from(vec![
+            "Press ".into(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " to confirm or ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " to decline".into(),
+        ])),
+        items,
+        on_cancel: Some(decline_action(&request.request_id)),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn question(id: &str, options: &[&str], is_other: bool) -> RequestUserInputQuestion {
+        RequestUserInputQuestion {
+            id: id.to_string(),
+            header: id.to_string(),
+            question: format!("Which {id}?"),
+            is_other,
+            is_secret: false,
+            options: Some(
+                options
+                    .iter()
+                    .map(|label| RequestUserInputQuestionOption {
+                        label: label.to_string(),
+                        description: String::new(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn user_input_questions_are_asked_in_order_and_collected_by_id() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut request = PendingUserInputRequest {
+            request_id: "user-input-1".to_string(),
+            server_name: None,
+            message: None,
+            questions: vec![
+                question("database", &["SQLite", "Postgres"], true),
+                question("name", &[], false),
+            ],
+            answers: Vec::new(),
+        };
+
+        let params = build_user_input_question_params(&request);
+        assert_eq!(
+            params
+                .items
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SQLite", "Postgres", "Type an answer"]
+        );
+        for item in &params.items {
+            for action in &item.actions {
+                action(&tx);
+            }
+        }
+        if let Some(on_cancel) = &params.on_cancel {
+            on_cancel(&tx);
+        }
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(match event {
+                AppEvent::PotterUserInputOptionChosen { request_id, answer } => {
+                    format!("{request_id}: {answer}")
+                }
+                AppEvent::PotterUserInputTextRequested { request_id } => {
+                    format!("{request_id}: text")
+                }
+                AppEvent::PotterUserInputDeclined { request_id } => {
+                    format!("{request_id}: declined")
+                }
+                other => panic!("unexpected app event: {other:?}"),
+            });
+        }
+        assert_eq!(
+            events,
+            vec![
+                "user-input-1: SQLite".to_string(),
+                "user-input-1: Postgres".to_string(),
+                "user-input-1: text".to_string(),
+                "user-input-1: declined".to_string(),
+            ]
+        );
+
+        request.record_answer("SQLite".to_string());
+        assert_eq!(
+            build_user_input_question_params(&request)
+                .items
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Type an answer"]
+        );
+        request.record_answer("inventory".to_string());
+        assert!(request.is_complete());
+        assert_eq!(
+            request.into_answers(),
+            HashMap::from([
+                (
+                    "database".to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec!["SQLite".to_string()],
+                    },
+                ),
+                (
+                    "name".to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec!["inventory".to_string()],
+                    },
+                ),
+            ])
+        );
+    }
+}